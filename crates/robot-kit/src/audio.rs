@@ -0,0 +1,264 @@
+//! Shared audio playback
+//!
+//! One place for speaker device selection so `SpeakTool` and `EmoteTool`
+//! go through the same ALSA path instead of fighting over the device:
+//! `aplay` on the configured device with a `paplay` (PulseAudio) fallback.
+//! [`AudioPlayer`] adds async playback with stop/overlap semantics on top;
+//! tests plug in a null [`AudioSink`] to assert scheduling without hardware.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Play a WAV file on the configured speaker device and wait for it.
+///
+/// `volume` is 0.0–1.0; `aplay` has no per-stream volume, so attenuated
+/// playback prefers `paplay --volume` and falls back to full volume.
+pub async fn play_file(device: &str, path: &Path, volume: f64) -> Result<()> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Non-UTF8 sound path: {}", path.display()))?;
+
+    if volume < 1.0 {
+        // PulseAudio volume scale: 0–65536
+        let pa_volume = (volume.clamp(0.0, 1.0) * 65536.0) as u32;
+        let attenuated = tokio::process::Command::new("paplay")
+            .args(["--volume", &pa_volume.to_string(), path_str])
+            .output()
+            .await;
+        if matches!(&attenuated, Ok(out) if out.status.success()) {
+            return Ok(());
+        }
+        tracing::debug!("paplay with volume unavailable, playing at full volume");
+    }
+
+    let output = tokio::process::Command::new("aplay")
+        .args(["-D", device, path_str])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let fallback = tokio::process::Command::new("paplay")
+            .arg(path_str)
+            .output()
+            .await?;
+        if !fallback.status.success() {
+            anyhow::bail!(
+                "Audio playback failed. Tried aplay and paplay.\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Playback backend for [`AudioPlayer`]. The default sink shells out via
+/// [`play_file`]; tests use a null sink.
+#[async_trait]
+pub trait AudioSink: Send + Sync {
+    /// Play one file to completion (or until the task is aborted)
+    async fn play(&self, path: &Path, volume: f64) -> Result<()>;
+}
+
+/// Default sink: the shared aplay/paplay pipeline
+struct DeviceSink {
+    device: String,
+}
+
+#[async_trait]
+impl AudioSink for DeviceSink {
+    async fn play(&self, path: &Path, volume: f64) -> Result<()> {
+        play_file(&self.device, path, volume).await
+    }
+}
+
+/// What to do when a sound starts while another is playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Let sounds play concurrently
+    Mix,
+    /// Stop the current sound first
+    Replace,
+}
+
+impl OverlapPolicy {
+    /// Parse the config string; anything unrecognized falls back to Replace
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "mix" => Self::Mix,
+            _ => Self::Replace,
+        }
+    }
+}
+
+/// Async sound player with stop and overlap control
+pub struct AudioPlayer {
+    sink: Arc<dyn AudioSink>,
+    overlap: OverlapPolicy,
+    active: Mutex<Vec<tokio::task::JoinHandle<()>>>,
+}
+
+impl AudioPlayer {
+    pub fn new(device: &str, overlap: OverlapPolicy) -> Self {
+        Self::with_sink(
+            Arc::new(DeviceSink {
+                device: device.to_string(),
+            }),
+            overlap,
+        )
+    }
+
+    /// Construct with a custom sink (tests use a null sink)
+    pub fn with_sink(sink: Arc<dyn AudioSink>, overlap: OverlapPolicy) -> Self {
+        Self {
+            sink,
+            overlap,
+            active: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Start playing a file. With `wait` the call returns when playback
+    /// finishes; otherwise playback continues in the background.
+    pub async fn play(&self, path: PathBuf, volume: f64, wait: bool) -> Result<()> {
+        if self.overlap == OverlapPolicy::Replace {
+            self.stop().await;
+        }
+
+        let sink = self.sink.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = sink.play(&path, volume).await {
+                tracing::warn!("Sound playback failed: {e}");
+            }
+        });
+
+        if wait {
+            // An abort via stop() while waiting is not an error
+            let _ = handle.await;
+        } else {
+            let mut active = self.active.lock().await;
+            active.retain(|h| !h.is_finished());
+            active.push(handle);
+        }
+        Ok(())
+    }
+
+    /// Stop all active playback; returns how many sounds were stopped.
+    pub async fn stop(&self) -> usize {
+        let mut active = self.active.lock().await;
+        active.retain(|h| !h.is_finished());
+        let stopped = active.len();
+        for handle in active.drain(..) {
+            handle.abort();
+        }
+        stopped
+    }
+
+    /// Number of sounds currently playing
+    pub async fn active_count(&self) -> usize {
+        let mut active = self.active.lock().await;
+        active.retain(|h| !h.is_finished());
+        active.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Null sink: records starts and "plays" until aborted
+    struct NullSink {
+        started: std::sync::Mutex<Vec<(PathBuf, f64)>>,
+    }
+
+    impl NullSink {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                started: std::sync::Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl AudioSink for NullSink {
+        async fn play(&self, path: &Path, volume: f64) -> Result<()> {
+            self.started
+                .lock()
+                .unwrap()
+                .push((path.to_path_buf(), volume));
+            tokio::time::sleep(Duration::from_secs(300)).await;
+            Ok(())
+        }
+    }
+
+    /// Instant sink: playback completes immediately
+    struct InstantSink;
+
+    #[async_trait]
+    impl AudioSink for InstantSink {
+        async fn play(&self, _path: &Path, _volume: f64) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn settle() {
+        // Let spawned playback tasks reach the sink
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    #[tokio::test]
+    async fn replace_policy_stops_previous_playback() {
+        let sink = NullSink::new();
+        let player = AudioPlayer::with_sink(sink.clone(), OverlapPolicy::Replace);
+
+        player.play(PathBuf::from("a.wav"), 1.0, false).await.unwrap();
+        settle().await;
+        player.play(PathBuf::from("b.wav"), 0.5, false).await.unwrap();
+        settle().await;
+
+        assert_eq!(player.active_count().await, 1);
+        let started = sink.started.lock().unwrap().clone();
+        assert_eq!(started.len(), 2);
+        assert_eq!(started[1], (PathBuf::from("b.wav"), 0.5));
+    }
+
+    #[tokio::test]
+    async fn mix_policy_overlaps_playback() {
+        let player = AudioPlayer::with_sink(NullSink::new(), OverlapPolicy::Mix);
+
+        player.play(PathBuf::from("a.wav"), 1.0, false).await.unwrap();
+        player.play(PathBuf::from("b.wav"), 1.0, false).await.unwrap();
+        settle().await;
+
+        assert_eq!(player.active_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn stop_clears_active_playback() {
+        let player = AudioPlayer::with_sink(NullSink::new(), OverlapPolicy::Mix);
+
+        player.play(PathBuf::from("a.wav"), 1.0, false).await.unwrap();
+        player.play(PathBuf::from("b.wav"), 1.0, false).await.unwrap();
+        settle().await;
+
+        assert_eq!(player.stop().await, 2);
+        assert_eq!(player.active_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn wait_returns_after_playback_completes() {
+        let player = AudioPlayer::with_sink(Arc::new(InstantSink), OverlapPolicy::Replace);
+        player.play(PathBuf::from("a.wav"), 1.0, true).await.unwrap();
+        assert_eq!(player.active_count().await, 0);
+    }
+
+    #[test]
+    fn overlap_policy_parses_with_replace_default() {
+        assert_eq!(OverlapPolicy::parse("mix"), OverlapPolicy::Mix);
+        assert_eq!(OverlapPolicy::parse("replace"), OverlapPolicy::Replace);
+        assert_eq!(OverlapPolicy::parse("bogus"), OverlapPolicy::Replace);
+    }
+}
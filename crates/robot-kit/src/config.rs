@@ -20,6 +20,36 @@ pub struct RobotConfig {
 
     /// Safety limits
     pub safety: SafetyConfig,
+
+    /// Emote / soundboard settings
+    #[serde(default)]
+    pub emote: EmoteConfig,
+}
+
+/// Soundboard configuration for `EmoteTool`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmoteConfig {
+    /// Named sound effects: name → WAV file path
+    #[serde(default)]
+    pub sounds: std::collections::HashMap<String, PathBuf>,
+
+    /// What happens when a sound starts while another is playing:
+    /// "replace" stops the current sound first, "mix" plays them together
+    #[serde(default = "default_overlap")]
+    pub overlap: String,
+}
+
+fn default_overlap() -> String {
+    "replace".to_string()
+}
+
+impl Default for EmoteConfig {
+    fn default() -> Self {
+        Self {
+            sounds: std::collections::HashMap::new(),
+            overlap: default_overlap(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +68,102 @@ pub struct DriveConfig {
 
     /// Max rotation in rad/s
     pub max_rotation: f64,
+
+    /// Max travel in a single command (meters)
+    #[serde(default = "default_max_distance_per_command")]
+    pub max_distance_per_command: f64,
+
+    /// Max rotation in a single command (degrees)
+    #[serde(default = "default_max_rotation_per_command")]
+    pub max_rotation_per_command: f64,
+
+    /// What to do with commands exceeding the per-command limits:
+    /// "clamp" (default, with a note in the result) or "reject"
+    #[serde(default = "default_limit_policy")]
+    pub limit_policy: String,
+
+    /// Drive kinematics: "omni" (mecanum/omni wheels, can strafe)
+    /// or "differential" (two-wheel, no lateral movement)
+    #[serde(default = "default_kinematics")]
+    pub kinematics: String,
+
+    /// Heading drift tolerance while translating (radians)
+    /// Corrections are injected when odometry reports more drift than this
+    #[serde(default = "default_heading_hold_tolerance")]
+    pub heading_hold_tolerance: f64,
+
+    /// Per-motor trim and straight-line correction
+    #[serde(default)]
+    pub calibration: DriveCalibration,
+}
+
+fn default_kinematics() -> String {
+    "omni".to_string()
+}
+
+fn default_max_distance_per_command() -> f64 {
+    5.0
+}
+
+fn default_max_rotation_per_command() -> f64 {
+    360.0
+}
+
+fn default_limit_policy() -> String {
+    "clamp".to_string()
+}
+
+fn default_heading_hold_tolerance() -> f64 {
+    0.05
+}
+
+/// Per-motor trim multipliers for unbalanced motors.
+///
+/// Differential bases use `left`/`right`; omni bases use the four wheel
+/// entries. Values are clamped to 0.5–1.5 when applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveCalibration {
+    /// Left motor trim (differential)
+    #[serde(default = "default_trim")]
+    pub left: f64,
+    /// Right motor trim (differential)
+    #[serde(default = "default_trim")]
+    pub right: f64,
+    /// Front-left wheel trim (omni)
+    #[serde(default = "default_trim")]
+    pub front_left: f64,
+    /// Front-right wheel trim (omni)
+    #[serde(default = "default_trim")]
+    pub front_right: f64,
+    /// Rear-left wheel trim (omni)
+    #[serde(default = "default_trim")]
+    pub rear_left: f64,
+    /// Rear-right wheel trim (omni)
+    #[serde(default = "default_trim")]
+    pub rear_right: f64,
+    /// Straight-line correction gain: angular bias applied per m/s of
+    /// forward speed, proportional to the left/right trim imbalance.
+    /// 0.0 disables the correction.
+    #[serde(default)]
+    pub straight_line_gain: f64,
+}
+
+fn default_trim() -> f64 {
+    1.0
+}
+
+impl Default for DriveCalibration {
+    fn default() -> Self {
+        Self {
+            left: 1.0,
+            right: 1.0,
+            front_left: 1.0,
+            front_right: 1.0,
+            rear_left: 1.0,
+            rear_right: 1.0,
+            straight_line_gain: 0.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +180,61 @@ pub struct CameraConfig {
 
     /// Ollama URL for vision
     pub ollama_url: String,
+
+    /// MJPEG stream server for remote monitoring (camera-stream feature)
+    #[serde(default)]
+    pub stream: CameraStreamConfig,
+}
+
+/// MJPEG camera stream server settings.
+///
+/// Disabled by default; when enabled the stream requires `token` as a
+/// `?token=` query parameter or `X-Stream-Token` header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraStreamConfig {
+    /// Serve the stream at all
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Bind address for the stream server
+    #[serde(default = "default_stream_bind")]
+    pub bind_addr: String,
+
+    /// TCP port (0 = ephemeral)
+    #[serde(default = "default_stream_port")]
+    pub port: u16,
+
+    /// Access token; empty disables auth (local-only setups)
+    #[serde(default)]
+    pub token: String,
+
+    /// Frame rate cap in frames per second
+    #[serde(default = "default_stream_fps")]
+    pub max_fps: f64,
+}
+
+fn default_stream_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_stream_port() -> u16 {
+    8554
+}
+
+fn default_stream_fps() -> f64 {
+    5.0
+}
+
+impl Default for CameraStreamConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: default_stream_bind(),
+            port: default_stream_port(),
+            token: String::new(),
+            max_fps: default_stream_fps(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,11 +251,39 @@ pub struct AudioConfig {
     /// Path to whisper.cpp binary
     pub whisper_path: PathBuf,
 
+    /// Default transcription language: ISO 639 code (e.g. "de") or "auto"
+    #[serde(default = "default_whisper_language")]
+    pub whisper_language: String,
+
+    /// Translate transcripts to English by default
+    #[serde(default)]
+    pub whisper_translate: bool,
+
     /// Path to piper binary
     pub piper_path: PathBuf,
 
     /// Piper voice model
     pub piper_voice: String,
+
+    /// Directory for Piper voice models (auto-downloads land here)
+    #[serde(default = "default_model_dir")]
+    pub model_dir: PathBuf,
+
+    /// Voice model index URL for auto-download: a JSON map of voice name to
+    /// { onnx_url, onnx_sha256, config_url, config_sha256 }.
+    /// Empty string disables auto-download (local models keep working).
+    #[serde(default)]
+    pub model_index_url: String,
+}
+
+fn default_whisper_language() -> String {
+    "auto".to_string()
+}
+
+fn default_model_dir() -> PathBuf {
+    directories::UserDirs::new()
+        .map(|d| d.home_dir().join(".zeroclaw/models/piper"))
+        .unwrap_or_else(|| PathBuf::from("/usr/local/share/piper"))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,9 +291,21 @@ pub struct SensorConfig {
     /// LIDAR device (e.g., "/dev/ttyUSB0")
     pub lidar_port: String,
 
-    /// LIDAR type ("rplidar", "ydlidar", "mock")
+    /// LIDAR type ("rplidar", "ydlidar", "ros2", "mock")
     pub lidar_type: String,
 
+    /// ROS2 LaserScan topic (lidar_type = "ros2")
+    #[serde(default = "default_scan_topic")]
+    pub ros2_scan_topic: String,
+
+    /// ROS2 Odometry topic
+    #[serde(default = "default_odom_topic")]
+    pub ros2_odom_topic: String,
+
+    /// ROS2 QoS reliability: "best_effort" (most LIDAR drivers) or "reliable"
+    #[serde(default = "default_ros2_qos")]
+    pub ros2_qos: String,
+
     /// GPIO pins for motion sensors (BCM numbering)
     pub motion_pins: Vec<u8>,
 
@@ -92,6 +313,18 @@ pub struct SensorConfig {
     pub ultrasonic_pins: Option<(u8, u8)>,
 }
 
+fn default_scan_topic() -> String {
+    "/scan".to_string()
+}
+
+fn default_odom_topic() -> String {
+    "/odom".to_string()
+}
+
+fn default_ros2_qos() -> String {
+    "best_effort".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyConfig {
     /// Minimum obstacle distance before auto-stop (meters)
@@ -147,12 +380,40 @@ pub struct SafetyConfig {
     /// Default: 5 seconds
     pub sensor_timeout_secs: u64,
 
+    /// Max age of a LIDAR reading before it is ignored (seconds)
+    /// Default: 2.0
+    #[serde(default = "default_lidar_max_age_secs")]
+    pub lidar_max_age_secs: f64,
+
+    /// Max age of an ultrasonic reading before it is ignored (seconds)
+    /// Default: 1.0
+    #[serde(default = "default_ultrasonic_max_age_secs")]
+    pub ultrasonic_max_age_secs: f64,
+
+    /// Two fresh proximity sources differing by more than this (meters)
+    /// count as disagreeing; the closer reading wins and the mismatch is logged
+    /// Default: 0.5m
+    #[serde(default = "default_proximity_disagreement_delta")]
+    pub proximity_disagreement_delta: f64,
+
     /// Speed limit when sensors are in mock/unavailable mode (0.0 - 1.0)
     /// Extra caution when flying blind
     /// Default: 0.2 (20% speed)
     pub blind_mode_speed_limit: f64,
 }
 
+fn default_lidar_max_age_secs() -> f64 {
+    2.0
+}
+
+fn default_ultrasonic_max_age_secs() -> f64 {
+    1.0
+}
+
+fn default_proximity_disagreement_delta() -> f64 {
+    0.5
+}
+
 impl Default for RobotConfig {
     fn default() -> Self {
         Self {
@@ -162,6 +423,12 @@ impl Default for RobotConfig {
                 serial_port: "/dev/ttyACM0".to_string(),
                 max_speed: 0.5,
                 max_rotation: 1.0,
+                max_distance_per_command: default_max_distance_per_command(),
+                max_rotation_per_command: default_max_rotation_per_command(),
+                limit_policy: default_limit_policy(),
+                kinematics: default_kinematics(),
+                heading_hold_tolerance: default_heading_hold_tolerance(),
+                calibration: DriveCalibration::default(),
             },
             camera: CameraConfig {
                 device: "/dev/video0".to_string(),
@@ -169,18 +436,26 @@ impl Default for RobotConfig {
                 height: 480,
                 vision_model: "moondream".to_string(),
                 ollama_url: "http://localhost:11434".to_string(),
+                stream: CameraStreamConfig::default(),
             },
             audio: AudioConfig {
                 mic_device: "default".to_string(),
                 speaker_device: "default".to_string(),
                 whisper_model: "base".to_string(),
                 whisper_path: PathBuf::from("/usr/local/bin/whisper-cpp"),
+                whisper_language: default_whisper_language(),
+                whisper_translate: false,
                 piper_path: PathBuf::from("/usr/local/bin/piper"),
                 piper_voice: "en_US-lessac-medium".to_string(),
+                model_dir: default_model_dir(),
+                model_index_url: String::new(),
             },
             sensors: SensorConfig {
                 lidar_port: "/dev/ttyUSB0".to_string(),
                 lidar_type: "mock".to_string(),
+                ros2_scan_topic: default_scan_topic(),
+                ros2_odom_topic: default_odom_topic(),
+                ros2_qos: default_ros2_qos(),
                 motion_pins: vec![17, 27],
                 ultrasonic_pins: Some((23, 24)),
             },
@@ -195,8 +470,12 @@ impl Default for RobotConfig {
                 confirm_movement: false,      // Don't require verbal confirm
                 predict_collisions: true,     // Use LIDAR prediction
                 sensor_timeout_secs: 5,       // Block if sensors stale 5s
+                lidar_max_age_secs: default_lidar_max_age_secs(),
+                ultrasonic_max_age_secs: default_ultrasonic_max_age_secs(),
+                proximity_disagreement_delta: default_proximity_disagreement_delta(),
                 blind_mode_speed_limit: 0.2,  // 20% speed without sensors
             },
+            emote: EmoteConfig::default(),
         }
     }
 }
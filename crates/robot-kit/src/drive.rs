@@ -6,7 +6,7 @@
 //! - Serial: Arduino/motor controller via serial commands
 //! - Mock: Logs commands for testing
 
-use crate::config::RobotConfig;
+use crate::config::{DriveCalibration, RobotConfig};
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -26,12 +26,55 @@ trait DriveBackend: Send + Sync {
         duration_ms: u64,
     ) -> Result<()>;
     async fn stop(&self) -> Result<()>;
-    #[allow(dead_code)]
-    async fn get_odometry(&self) -> Result<(f64, f64, f64)>; // x, y, theta - reserved for future odometry integration
+    async fn get_odometry(&self) -> Result<(f64, f64, f64)>; // x, y, theta
+}
+
+/// A single velocity command sent to a drive backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriveCommand {
+    pub linear_x: f64,
+    pub linear_y: f64,
+    pub angular_z: f64,
+    pub duration_ms: u64,
+}
+
+impl DriveCommand {
+    /// Pure rotation with no translation (a heading-hold correction)
+    pub fn is_rotation_only(&self) -> bool {
+        self.linear_x == 0.0 && self.linear_y == 0.0 && self.angular_z != 0.0
+    }
+}
+
+/// Observable state of the mock drive backend.
+///
+/// Tests read the recorded command stream and can simulate heading drift
+/// per translation command to exercise the heading-hold path.
+#[derive(Default)]
+pub struct MockDriveState {
+    commands: std::sync::Mutex<Vec<DriveCommand>>,
+    heading: std::sync::Mutex<f64>,
+    heading_drift_per_command: std::sync::Mutex<f64>,
+}
+
+impl MockDriveState {
+    /// Commands recorded so far (in order)
+    pub fn commands(&self) -> Vec<DriveCommand> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    /// Simulate heading drift: each translation command rotates the robot
+    /// by this much (radians). Rotation-only commands reset the heading,
+    /// modeling a successful correction.
+    pub fn set_heading_drift_per_command(&self, drift: f64) {
+        *self.heading_drift_per_command.lock().unwrap() = drift;
+    }
 }
 
 /// Mock backend for testing
-struct MockDrive;
+#[derive(Default)]
+struct MockDrive {
+    state: Arc<MockDriveState>,
+}
 
 #[async_trait]
 impl DriveBackend for MockDrive {
@@ -49,6 +92,19 @@ impl DriveBackend for MockDrive {
             angular_z,
             duration_ms
         );
+        let command = DriveCommand {
+            linear_x,
+            linear_y,
+            angular_z,
+            duration_ms,
+        };
+        self.state.commands.lock().unwrap().push(command);
+        if command.is_rotation_only() {
+            *self.state.heading.lock().unwrap() = 0.0;
+        } else if linear_x != 0.0 || linear_y != 0.0 {
+            let drift = *self.state.heading_drift_per_command.lock().unwrap();
+            *self.state.heading.lock().unwrap() += drift;
+        }
         tokio::time::sleep(Duration::from_millis(duration_ms.min(100))).await;
         Ok(())
     }
@@ -59,7 +115,7 @@ impl DriveBackend for MockDrive {
     }
 
     async fn get_odometry(&self) -> Result<(f64, f64, f64)> {
-        Ok((0.0, 0.0, 0.0))
+        Ok((0.0, 0.0, *self.state.heading.lock().unwrap()))
     }
 }
 
@@ -177,15 +233,30 @@ impl DriveBackend for SerialDrive {
     }
 }
 
+/// Segment length used while translating with heading hold
+const TRANSLATE_SEGMENT_MS: u64 = 500;
+/// Duration of an injected heading correction
+const HEADING_CORRECTION_MS: u64 = 100;
+/// Sane range for per-motor trim multipliers
+const TRIM_MIN: f64 = 0.5;
+const TRIM_MAX: f64 = 1.5;
+
+/// Callback invoked with suggested trims when calibration is persisted
+pub type CalibrationCallback = Arc<dyn Fn(&DriveCalibration) + Send + Sync>;
+
 /// Main Drive Tool
 pub struct DriveTool {
     config: RobotConfig,
     backend: Arc<dyn DriveBackend>,
+    mock_state: Option<Arc<MockDriveState>>,
     last_command: Arc<Mutex<Option<std::time::Instant>>>,
+    calibration_callback: Option<CalibrationCallback>,
+    description: String,
 }
 
 impl DriveTool {
     pub fn new(config: RobotConfig) -> Self {
+        let mut mock_state = None;
         let backend: Arc<dyn DriveBackend> = match config.drive.backend.as_str() {
             "ros2" => Arc::new(Ros2Drive {
                 topic: config.drive.ros2_topic.clone(),
@@ -194,14 +265,200 @@ impl DriveTool {
                 port: config.drive.serial_port.clone(),
             }),
             // "gpio" => Arc::new(GpioDrive::new(&config)), // Would use rppal
-            _ => Arc::new(MockDrive),
+            _ => {
+                let mock = MockDrive::default();
+                mock_state = Some(mock.state.clone());
+                Arc::new(mock)
+            }
         };
 
+        // Advertise the configured limits so the model plans within them
+        let description = format!(
+            "Move the robot. Supports omni-directional movement (forward, backward, strafe left/right, rotate). \
+             Use 'stop' action to halt immediately. Distance is in meters, rotation in degrees. \
+             Per-command limits: {:.1}m travel, {:.0}deg rotation, {:.1}m/s top speed; \
+             commands beyond these are {}.",
+            config.drive.max_distance_per_command,
+            config.drive.max_rotation_per_command,
+            config.drive.max_speed,
+            if config.drive.limit_policy == "reject" {
+                "rejected"
+            } else {
+                "clamped"
+            }
+        );
+
         Self {
             config,
             backend,
+            mock_state,
             last_command: Arc::new(Mutex::new(None)),
+            calibration_callback: None,
+            description,
+        }
+    }
+
+    /// Set a callback invoked with suggested trims when the `"calibrate"`
+    /// action is run with `persist: true` (e.g. to write them to robot.toml).
+    #[must_use]
+    pub fn with_calibration_callback(mut self, callback: CalibrationCallback) -> Self {
+        self.calibration_callback = Some(callback);
+        self
+    }
+
+    /// Observable mock backend state (None for real backends)
+    pub fn mock_state(&self) -> Option<Arc<MockDriveState>> {
+        self.mock_state.clone()
+    }
+
+    /// Enforce a per-command limit before anything reaches a backend.
+    ///
+    /// Values at or below the limit pass through untouched. Beyond it, the
+    /// `drive.limit_policy` decides: "reject" returns a failed result naming
+    /// the limit, anything else clamps and returns a note for the output.
+    /// This applies even when the safety feature is compiled out.
+    fn enforce_limit(
+        &self,
+        value: f64,
+        limit: f64,
+        what: &str,
+        unit: &str,
+    ) -> std::result::Result<(f64, Option<String>), ToolResult> {
+        if value <= limit {
+            return Ok((value, None));
+        }
+        if self.config.drive.limit_policy == "reject" {
+            Err(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Rejected: {what} {value:.1}{unit} exceeds the per-command limit of {limit:.1}{unit}"
+                )),
+            })
+        } else {
+            tracing::warn!("Clamping {what} from {value:.1}{unit} to {limit:.1}{unit}");
+            Ok((
+                limit,
+                Some(format!(
+                    " ({what} clamped from {value:.1}{unit} to the {limit:.1}{unit} limit)"
+                )),
+            ))
+        }
+    }
+
+    /// Apply per-motor trims and straight-line correction to a twist command.
+    ///
+    /// Trims are clamped to 0.5–1.5. The straight-line gain turns left/right
+    /// trim imbalance into an angular bias proportional to forward speed.
+    fn apply_trims(&self, linear_x: f64, linear_y: f64, angular_z: f64) -> (f64, f64, f64) {
+        let cal = &self.config.drive.calibration;
+        let (scale, imbalance) = if self.config.drive.kinematics == "differential" {
+            let left = cal.left.clamp(TRIM_MIN, TRIM_MAX);
+            let right = cal.right.clamp(TRIM_MIN, TRIM_MAX);
+            ((left + right) / 2.0, right - left)
+        } else {
+            let fl = cal.front_left.clamp(TRIM_MIN, TRIM_MAX);
+            let fr = cal.front_right.clamp(TRIM_MIN, TRIM_MAX);
+            let rl = cal.rear_left.clamp(TRIM_MIN, TRIM_MAX);
+            let rr = cal.rear_right.clamp(TRIM_MIN, TRIM_MAX);
+            ((fl + fr + rl + rr) / 4.0, (fr + rr) / 2.0 - (fl + rl) / 2.0)
+        };
+
+        let bias = cal.straight_line_gain * imbalance * linear_x;
+        if (scale - 1.0).abs() > f64::EPSILON || bias.abs() > f64::EPSILON {
+            tracing::debug!(
+                "Applying motor trims: scale={:.3}, angular bias={:.3}",
+                scale,
+                bias
+            );
+        }
+
+        (linear_x * scale, linear_y * scale, angular_z + bias)
+    }
+
+    /// Send a twist command to the backend with trims applied.
+    async fn send_command(
+        &self,
+        linear_x: f64,
+        linear_y: f64,
+        angular_z: f64,
+        duration_ms: u64,
+    ) -> Result<()> {
+        let (vx, vy, az) = self.apply_trims(linear_x, linear_y, angular_z);
+        self.backend.move_robot(vx, vy, az, duration_ms).await
+    }
+
+    /// Suggest trim values from a measured straight-line drift.
+    ///
+    /// `drift` is lateral displacement in meters over `distance` meters of
+    /// forward travel; positive means the robot drifted left (right side
+    /// stronger), so the right-side trims are reduced.
+    fn suggest_trims(&self, drift: f64, distance: f64) -> DriveCalibration {
+        let mut cal = self.config.drive.calibration.clone();
+        let ratio = (drift / distance.max(0.1)).clamp(-0.5, 0.5);
+        let (left_factor, right_factor) = if ratio > 0.0 {
+            (1.0, 1.0 - ratio)
+        } else {
+            (1.0 + ratio, 1.0)
+        };
+
+        cal.left = (cal.left * left_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal.right = (cal.right * right_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal.front_left = (cal.front_left * left_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal.rear_left = (cal.rear_left * left_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal.front_right = (cal.front_right * right_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal.rear_right = (cal.rear_right * right_factor).clamp(TRIM_MIN, TRIM_MAX);
+        cal
+    }
+
+    /// Translate along `angle_rad` (0 = forward, positive = left) for
+    /// `distance` meters, holding the starting heading.
+    ///
+    /// Movement is issued in segments; between segments the odometry heading
+    /// is compared against the start and a small opposing rotation is
+    /// injected when drift exceeds `drive.heading_hold_tolerance`.
+    async fn translate_with_heading_hold(
+        &self,
+        angle_rad: f64,
+        distance: f64,
+        speed: f64,
+    ) -> Result<(u64, u32)> {
+        let max_speed = self.config.drive.max_speed * speed;
+        let linear_x = angle_rad.cos() * max_speed;
+        let linear_y = angle_rad.sin() * max_speed;
+        let total_ms = ((distance / max_speed * 1000.0) as u64)
+            .min(self.config.safety.max_drive_duration * 1000);
+
+        let (_, _, start_heading) = self.backend.get_odometry().await.unwrap_or((0.0, 0.0, 0.0));
+        let tolerance = self.config.drive.heading_hold_tolerance;
+        let mut corrections = 0u32;
+        let mut remaining = total_ms;
+
+        while remaining > 0 {
+            let segment = remaining.min(TRANSLATE_SEGMENT_MS);
+            self.send_command(linear_x, linear_y, 0.0, segment).await?;
+            remaining -= segment;
+
+            if remaining == 0 {
+                break;
+            }
+            if let Ok((_, _, heading)) = self.backend.get_odometry().await {
+                let drift = heading - start_heading;
+                if drift.abs() > tolerance {
+                    let correction = -drift.signum() * self.config.drive.max_rotation * 0.25;
+                    tracing::debug!(
+                        "Heading hold: drift {:.3}rad, injecting correction {:.3}rad/s",
+                        drift,
+                        correction
+                    );
+                    self.send_command(0.0, 0.0, correction, HEADING_CORRECTION_MS)
+                        .await?;
+                    corrections += 1;
+                }
+            }
         }
+
+        Ok((total_ms, corrections))
     }
 }
 
@@ -212,8 +469,7 @@ impl Tool for DriveTool {
     }
 
     fn description(&self) -> &str {
-        "Move the robot. Supports omni-directional movement (forward, backward, strafe left/right, rotate). \
-         Use 'stop' action to halt immediately. Distance is in meters, rotation in degrees."
+        &self.description
     }
 
     fn parameters_schema(&self) -> Value {
@@ -222,13 +478,29 @@ impl Tool for DriveTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["forward", "backward", "left", "right", "rotate_left", "rotate_right", "stop", "custom"],
-                    "description": "Movement action. 'left'/'right' are strafe (omni wheels). 'rotate_*' spins in place."
+                    "enum": ["forward", "backward", "left", "right", "strafe_left", "strafe_right", "translate", "rotate_left", "rotate_right", "stop", "custom", "calibrate"],
+                    "description": "Movement action. 'strafe_*'/'translate' are lateral/diagonal moves with heading hold (omni wheels only). 'rotate_*' spins in place."
                 },
                 "distance": {
                     "type": "number",
                     "description": "Distance in meters (for linear moves) or degrees (for rotation). Default 0.5m or 90deg."
                 },
+                "direction": {
+                    "type": "number",
+                    "description": "Translate only: direction angle in degrees (0 = forward, 90 = left, -90 = right)"
+                },
+                "measured_drift": {
+                    "type": "number",
+                    "description": "Calibrate only: measured lateral drift in meters after the calibration run (positive = drifted left)"
+                },
+                "calibration_distance": {
+                    "type": "number",
+                    "description": "Calibrate only: straight-line distance for the calibration run in meters. Default 2.0."
+                },
+                "persist": {
+                    "type": "boolean",
+                    "description": "Calibrate only: persist the suggested trims via the configured callback"
+                },
                 "speed": {
                     "type": "number",
                     "description": "Speed multiplier 0.0-1.0. Default 0.5 (half speed for safety)."
@@ -251,6 +523,10 @@ impl Tool for DriveTool {
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
         let action = args["action"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
@@ -275,6 +551,9 @@ impl Tool for DriveTool {
         let speed = args["speed"].as_f64().unwrap_or(0.5).clamp(0.0, 1.0);
         let max_speed = self.config.drive.max_speed * speed;
         let max_rotation = self.config.drive.max_rotation * speed;
+        let max_distance = self.config.drive.max_distance_per_command;
+        let max_degrees = self.config.drive.max_rotation_per_command;
+        let mut limit_note: Option<String> = None;
 
         let (linear_x, linear_y, angular_z, duration_ms) = match action {
             "stop" => {
@@ -287,6 +566,11 @@ impl Tool for DriveTool {
             }
             "forward" => {
                 let dist = args["distance"].as_f64().unwrap_or(0.5);
+                let (dist, note) = match self.enforce_limit(dist, max_distance, "distance", "m") {
+                    Ok(limited) => limited,
+                    Err(result) => return Ok(result),
+                };
+                limit_note = note;
                 let duration = (dist / max_speed * 1000.0) as u64;
                 (
                     max_speed,
@@ -297,6 +581,11 @@ impl Tool for DriveTool {
             }
             "backward" => {
                 let dist = args["distance"].as_f64().unwrap_or(0.5);
+                let (dist, note) = match self.enforce_limit(dist, max_distance, "distance", "m") {
+                    Ok(limited) => limited,
+                    Err(result) => return Ok(result),
+                };
+                limit_note = note;
                 let duration = (dist / max_speed * 1000.0) as u64;
                 (
                     -max_speed,
@@ -307,6 +596,11 @@ impl Tool for DriveTool {
             }
             "left" => {
                 let dist = args["distance"].as_f64().unwrap_or(0.5);
+                let (dist, note) = match self.enforce_limit(dist, max_distance, "distance", "m") {
+                    Ok(limited) => limited,
+                    Err(result) => return Ok(result),
+                };
+                limit_note = note;
                 let duration = (dist / max_speed * 1000.0) as u64;
                 (
                     0.0,
@@ -317,6 +611,11 @@ impl Tool for DriveTool {
             }
             "right" => {
                 let dist = args["distance"].as_f64().unwrap_or(0.5);
+                let (dist, note) = match self.enforce_limit(dist, max_distance, "distance", "m") {
+                    Ok(limited) => limited,
+                    Err(result) => return Ok(result),
+                };
+                limit_note = note;
                 let duration = (dist / max_speed * 1000.0) as u64;
                 (
                     0.0,
@@ -325,8 +624,94 @@ impl Tool for DriveTool {
                     duration.min(self.config.safety.max_drive_duration * 1000),
                 )
             }
+            "calibrate" => {
+                let distance = args["calibration_distance"].as_f64().unwrap_or(2.0);
+                let Some(drift) = args["measured_drift"].as_f64() else {
+                    // Step 1: drive straight so the drift can be measured
+                    let duration = ((distance / max_speed * 1000.0) as u64)
+                        .min(self.config.safety.max_drive_duration * 1000);
+                    self.send_command(max_speed, 0.0, 0.0, duration).await?;
+                    return Ok(ToolResult {
+                        success: true,
+                        output: format!(
+                            "Calibration run: drove {distance:.1}m forward. Measure the lateral \
+                             drift in meters (positive = drifted left) and call calibrate again \
+                             with measured_drift."
+                        ),
+                        error: None,
+                    });
+                };
+
+                // Step 2: compute suggested trims from the measured drift
+                let suggested = self.suggest_trims(drift, distance);
+                let persisted = if args["persist"].as_bool().unwrap_or(false) {
+                    if let Some(callback) = &self.calibration_callback {
+                        callback(&suggested);
+                        true
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                };
+                tracing::info!(
+                    "Calibration suggestion from {drift:.3}m drift over {distance:.1}m: {suggested:?}"
+                );
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Suggested trims for {drift:.3}m drift over {distance:.1}m: {}{}",
+                        serde_json::to_string(&suggested).unwrap_or_default(),
+                        if persisted {
+                            " (persisted)"
+                        } else {
+                            " (set these under [drive.calibration] in robot.toml)"
+                        }
+                    ),
+                    error: None,
+                });
+            }
+            "strafe_left" | "strafe_right" | "translate" => {
+                if self.config.drive.kinematics == "differential" {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Action '{action}' requires omni kinematics; drive.kinematics is 'differential'"
+                        )),
+                    });
+                }
+                let dist = args["distance"].as_f64().unwrap_or(0.5);
+                let (dist, note) = match self.enforce_limit(dist, max_distance, "distance", "m") {
+                    Ok(limited) => limited,
+                    Err(result) => return Ok(result),
+                };
+                let angle_deg = match action {
+                    "strafe_left" => 90.0,
+                    "strafe_right" => -90.0,
+                    _ => args["direction"].as_f64().unwrap_or(0.0),
+                };
+                let (duration_ms, corrections) = self
+                    .translate_with_heading_hold(angle_deg.to_radians(), dist, speed)
+                    .await?;
+                return Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Translated: action={}, direction={:.0}deg, distance={:.2}m, duration={}ms, heading corrections={}{}",
+                        action, angle_deg, dist, duration_ms, corrections,
+                        note.unwrap_or_default()
+                    ),
+                    error: None,
+                });
+            }
             "rotate_left" => {
                 let degrees = args["distance"].as_f64().unwrap_or(90.0);
+                let (degrees, note) =
+                    match self.enforce_limit(degrees, max_degrees, "rotation", "deg") {
+                        Ok(limited) => limited,
+                        Err(result) => return Ok(result),
+                    };
+                limit_note = note;
                 let radians = degrees.to_radians();
                 let duration = (radians / max_rotation * 1000.0) as u64;
                 (
@@ -338,6 +723,12 @@ impl Tool for DriveTool {
             }
             "rotate_right" => {
                 let degrees = args["distance"].as_f64().unwrap_or(90.0);
+                let (degrees, note) =
+                    match self.enforce_limit(degrees, max_degrees, "rotation", "deg") {
+                        Ok(limited) => limited,
+                        Err(result) => return Ok(result),
+                    };
+                limit_note = note;
                 let radians = degrees.to_radians();
                 let duration = (radians / max_rotation * 1000.0) as u64;
                 (
@@ -368,15 +759,19 @@ impl Tool for DriveTool {
             }
         };
 
-        self.backend
-            .move_robot(linear_x, linear_y, angular_z, duration_ms)
+        self.send_command(linear_x, linear_y, angular_z, duration_ms)
             .await?;
 
         Ok(ToolResult {
             success: true,
             output: format!(
-                "Moved: action={}, linear=({:.2}, {:.2}), angular={:.2}, duration={}ms",
-                action, linear_x, linear_y, angular_z, duration_ms
+                "Moved: action={}, linear=({:.2}, {:.2}), angular={:.2}, duration={}ms{}",
+                action,
+                linear_x,
+                linear_y,
+                angular_z,
+                duration_ms,
+                limit_note.unwrap_or_default()
             ),
             error: None,
         })
@@ -419,10 +814,283 @@ mod tests {
         assert!(result.output.contains("stopped"));
     }
 
+    #[tokio::test]
+    async fn strafe_left_emits_lateral_command() {
+        let tool = DriveTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "strafe_left", "distance": 0.1}))
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let commands = tool.mock_state().unwrap().commands();
+        assert!(!commands.is_empty());
+        assert!(commands[0].linear_y > 0.0);
+        assert_eq!(commands[0].angular_z, 0.0);
+    }
+
+    #[tokio::test]
+    async fn translate_diagonal_splits_velocity() {
+        let tool = DriveTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "translate", "direction": 45.0, "distance": 0.1}))
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let commands = tool.mock_state().unwrap().commands();
+        assert!(commands[0].linear_x > 0.0);
+        assert!(commands[0].linear_y > 0.0);
+    }
+
+    #[tokio::test]
+    async fn translate_rejected_for_differential_kinematics() {
+        let mut config = RobotConfig::default();
+        config.drive.kinematics = "differential".to_string();
+        let tool = DriveTool::new(config);
+        let result = tool
+            .execute(json!({"action": "strafe_right", "distance": 0.5}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("differential"));
+    }
+
+    #[tokio::test]
+    async fn heading_hold_injects_corrections_on_drift() {
+        let tool = DriveTool::new(RobotConfig::default());
+        let state = tool.mock_state().unwrap();
+        // Each translation segment drifts well past the 0.05 rad tolerance.
+        state.set_heading_drift_per_command(0.2);
+
+        // Long enough for several segments (default 0.5 m/s × 0.5 speed).
+        let result = tool
+            .execute(json!({"action": "strafe_left", "distance": 0.5}))
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let commands = state.commands();
+        let corrections: Vec<_> = commands.iter().filter(|c| c.is_rotation_only()).collect();
+        assert!(
+            !corrections.is_empty(),
+            "expected heading corrections in command stream: {commands:?}"
+        );
+        // Corrections oppose the positive drift.
+        assert!(corrections[0].angular_z < 0.0);
+    }
+
+    #[tokio::test]
+    async fn trims_scale_differential_commands() {
+        let mut config = RobotConfig::default();
+        config.drive.kinematics = "differential".to_string();
+        config.drive.calibration.right = 0.9;
+        config.drive.calibration.straight_line_gain = 1.0;
+        let tool = DriveTool::new(config);
+
+        tool.execute(json!({"action": "forward", "distance": 0.1, "speed": 1.0}))
+            .await
+            .unwrap();
+
+        let commands = tool.mock_state().unwrap().commands();
+        // Scale is (1.0 + 0.9) / 2 = 0.95 of max_speed (0.5 m/s).
+        assert!((commands[0].linear_x - 0.5 * 0.95).abs() < 1e-9);
+        // Right motor weaker trim => negative (rightward) angular bias.
+        assert!(commands[0].angular_z < 0.0);
+    }
+
+    #[tokio::test]
+    async fn trims_scale_omni_commands() {
+        let mut config = RobotConfig::default();
+        config.drive.calibration.front_left = 0.8;
+        config.drive.calibration.rear_left = 0.8;
+        let tool = DriveTool::new(config);
+
+        tool.execute(json!({"action": "strafe_left", "distance": 0.1, "speed": 1.0}))
+            .await
+            .unwrap();
+
+        let commands = tool.mock_state().unwrap().commands();
+        // Scale is (0.8 + 1.0 + 0.8 + 1.0) / 4 = 0.9 of max_speed.
+        assert!((commands[0].linear_y - 0.5 * 0.9).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn trims_clamped_to_sane_range() {
+        let mut config = RobotConfig::default();
+        config.drive.kinematics = "differential".to_string();
+        config.drive.calibration.left = 10.0;
+        config.drive.calibration.right = 10.0;
+        let tool = DriveTool::new(config);
+
+        tool.execute(json!({"action": "forward", "distance": 0.1, "speed": 1.0}))
+            .await
+            .unwrap();
+
+        let commands = tool.mock_state().unwrap().commands();
+        // Trims clamp to 1.5, so scale never exceeds 1.5 × max_speed.
+        assert!((commands[0].linear_x - 0.5 * 1.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn calibrate_suggests_reduced_right_trim_on_left_drift() {
+        let tool = DriveTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "calibrate", "measured_drift": 0.2, "calibration_distance": 2.0}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Suggested trims"));
+        // 0.2m drift over 2m => right trims scaled by 0.9.
+        assert!(result.output.contains("0.9"));
+    }
+
+    #[tokio::test]
+    async fn calibrate_persists_via_callback() {
+        let captured: Arc<std::sync::Mutex<Option<DriveCalibration>>> =
+            Arc::new(std::sync::Mutex::new(None));
+        let captured_clone = captured.clone();
+        let tool = DriveTool::new(RobotConfig::default()).with_calibration_callback(Arc::new(
+            move |cal: &DriveCalibration| {
+                *captured_clone.lock().unwrap() = Some(cal.clone());
+            },
+        ));
+
+        let result = tool
+            .execute(json!({"action": "calibrate", "measured_drift": -0.2, "calibration_distance": 2.0, "persist": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("persisted"));
+
+        let cal = captured.lock().unwrap().clone().unwrap();
+        // Negative (rightward) drift => left trims reduced.
+        assert!((cal.front_left - 0.9).abs() < 1e-9);
+        assert!((cal.front_right - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn description_advertises_per_command_limits() {
+        let mut config = RobotConfig::default();
+        config.drive.max_distance_per_command = 3.0;
+        config.drive.limit_policy = "reject".to_string();
+        let tool = DriveTool::new(config);
+        assert!(tool.description().contains("3.0m travel"));
+        assert!(tool.description().contains("rejected"));
+    }
+
+    #[tokio::test]
+    async fn distance_over_limit_clamped_with_note() {
+        let mut config = RobotConfig::default();
+        config.drive.max_distance_per_command = 0.2;
+        let tool = DriveTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "forward", "distance": 50.0}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("clamped from 50.0m"));
+
+        // The backend only ever saw the clamped travel (0.2m at 0.25 m/s).
+        let commands = tool.mock_state().unwrap().commands();
+        assert_eq!(commands[0].duration_ms, 800);
+    }
+
+    #[tokio::test]
+    async fn distance_over_limit_rejected_before_backend() {
+        let mut config = RobotConfig::default();
+        config.drive.max_distance_per_command = 0.2;
+        config.drive.limit_policy = "reject".to_string();
+        let tool = DriveTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "forward", "distance": 50.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("0.2m"));
+        assert!(tool.mock_state().unwrap().commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn distance_exactly_at_limit_passes_untouched() {
+        let mut config = RobotConfig::default();
+        config.drive.max_distance_per_command = 0.5;
+        config.drive.limit_policy = "reject".to_string();
+        let tool = DriveTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "forward", "distance": 0.5}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(!result.output.contains("clamped"));
+    }
+
+    #[tokio::test]
+    async fn rotation_over_limit_clamped_with_note() {
+        let tool = DriveTool::new(RobotConfig::default());
+
+        // Default limit is 360deg per command.
+        let result = tool
+            .execute(json!({"action": "rotate_left", "distance": 720.0}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("clamped from 720.0deg"));
+    }
+
+    #[tokio::test]
+    async fn rotation_over_limit_rejected() {
+        let mut config = RobotConfig::default();
+        config.drive.limit_policy = "reject".to_string();
+        let tool = DriveTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "rotate_right", "distance": 720.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("360.0deg"));
+        assert!(tool.mock_state().unwrap().commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn translate_distance_over_limit_rejected() {
+        let mut config = RobotConfig::default();
+        config.drive.max_distance_per_command = 0.2;
+        config.drive.limit_policy = "reject".to_string();
+        let tool = DriveTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "strafe_left", "distance": 10.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(tool.mock_state().unwrap().commands().is_empty());
+    }
+
     #[tokio::test]
     async fn drive_unknown_action() {
         let tool = DriveTool::new(RobotConfig::default());
         let result = tool.execute(json!({"action": "fly"})).await.unwrap();
         assert!(!result.success);
     }
+
+    #[tokio::test]
+    async fn malformed_args_rejected_before_backend() {
+        let tool = DriveTool::new(RobotConfig::default());
+        for args in [
+            json!({}),
+            json!({"action": "forward", "distance": "far"}),
+            json!({"action": "forward", "speed": "fast"}),
+            json!("forward"),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+        assert!(tool.mock_state().unwrap().commands().is_empty());
+    }
 }
@@ -3,6 +3,7 @@
 //! Control LED matrix/strips for robot "expressions" and play sounds.
 //! Makes the robot more engaging for kids!
 
+use crate::audio::{AudioPlayer, OverlapPolicy};
 use crate::config::RobotConfig;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
@@ -115,9 +116,9 @@ impl Expression {
 }
 
 pub struct EmoteTool {
-    #[allow(dead_code)]
     config: RobotConfig,
     sounds_dir: PathBuf,
+    player: AudioPlayer,
 }
 
 impl EmoteTool {
@@ -126,7 +127,39 @@ impl EmoteTool {
             .map(|d| d.home_dir().join(".zeroclaw/sounds"))
             .unwrap_or_else(|| PathBuf::from("/usr/local/share/zeroclaw/sounds"));
 
-        Self { config, sounds_dir }
+        let player = AudioPlayer::new(
+            &config.audio.speaker_device,
+            OverlapPolicy::parse(&config.emote.overlap),
+        );
+
+        Self {
+            config,
+            sounds_dir,
+            player,
+        }
+    }
+
+    /// Resolve a soundboard entry, erroring with the configured names
+    fn soundboard_path(&self, name: &str) -> Result<PathBuf> {
+        if let Some(path) = self.config.emote.sounds.get(name) {
+            return Ok(path.clone());
+        }
+        let mut names: Vec<&str> = self
+            .config
+            .emote
+            .sounds
+            .keys()
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        anyhow::bail!(
+            "Unknown sound '{name}'. Configured sounds: {}",
+            if names.is_empty() {
+                "(none)".to_string()
+            } else {
+                names.join(", ")
+            }
+        )
     }
 
     /// Set LED matrix expression
@@ -160,21 +193,20 @@ impl EmoteTool {
         }
     }
 
-    /// Play emotion sound effect
+    /// Play emotion sound effect: the configured soundboard entry when one
+    /// exists, otherwise the legacy `<sounds_dir>/<emotion>.wav` convention
     async fn play_emotion_sound(&self, emotion: &str) -> Result<()> {
-        let sound_file = self.sounds_dir.join(format!("{}.wav", emotion));
+        let sound_file = match self.config.emote.sounds.get(emotion) {
+            Some(path) => path.clone(),
+            None => self.sounds_dir.join(format!("{}.wav", emotion)),
+        };
 
         if !sound_file.exists() {
             tracing::debug!("No sound file for emotion: {}", emotion);
             return Ok(());
         }
 
-        tokio::process::Command::new("aplay")
-            .arg(sound_file)
-            .output()
-            .await?;
-
-        Ok(())
+        self.player.play(sound_file, 1.0, true).await
     }
 
     /// Animate expression (e.g., blinking)
@@ -221,13 +253,31 @@ impl Tool for EmoteTool {
     fn description(&self) -> &str {
         "Express emotions through LED display and sounds. Use this to show the robot's \
          emotional state - happy when playing, sad when saying goodbye, excited for games, etc. \
-         This makes interactions with kids more engaging!"
+         Also a soundboard: action 'play_sound' plays a configured sound (optional volume, \
+         wait), 'stop_sound' stops playback."
     }
 
     fn parameters_schema(&self) -> Value {
         json!({
             "type": "object",
             "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["play_sound", "stop_sound"],
+                    "description": "Soundboard action instead of an expression"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "For 'play_sound': which configured sound to play"
+                },
+                "volume": {
+                    "type": "number",
+                    "description": "For 'play_sound': playback volume 0.0-1.0 (default 1.0)"
+                },
+                "wait": {
+                    "type": "boolean",
+                    "description": "For 'play_sound': wait for playback to finish (default false)"
+                },
                 "expression": {
                     "type": "string",
                     "enum": ["happy", "sad", "surprised", "thinking", "sleepy", "excited", "love", "angry", "confused", "wink"],
@@ -246,12 +296,65 @@ impl Tool for EmoteTool {
                     "type": "integer",
                     "description": "How long to hold expression in seconds (default 3)"
                 }
-            },
-            "required": ["expression"]
+            }
         })
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
+        // Soundboard actions take precedence over expressions
+        if let Some(action) = args["action"].as_str() {
+            return match action {
+                "play_sound" => {
+                    let Some(name) = args["name"].as_str() else {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some("'play_sound' requires 'name'".to_string()),
+                        });
+                    };
+                    let path = match self.soundboard_path(name) {
+                        Ok(path) => path,
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(e.to_string()),
+                            });
+                        }
+                    };
+                    let volume = args["volume"].as_f64().unwrap_or(1.0).clamp(0.0, 1.0);
+                    let wait = args["wait"].as_bool().unwrap_or(false);
+                    self.player.play(path, volume, wait).await?;
+                    Ok(ToolResult {
+                        success: true,
+                        output: if wait {
+                            format!("Played sound '{name}'")
+                        } else {
+                            format!("Playing sound '{name}'")
+                        },
+                        error: None,
+                    })
+                }
+                "stop_sound" => {
+                    let stopped = self.player.stop().await;
+                    Ok(ToolResult {
+                        success: true,
+                        output: format!("Stopped {stopped} active sound(s)"),
+                        error: None,
+                    })
+                }
+                _ => Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Unknown action: {action}")),
+                }),
+            };
+        }
+
         let expression_str = args["expression"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'expression' parameter"))?;
@@ -311,6 +414,55 @@ mod tests {
         assert_eq!(expr.pattern().len(), 64); // 8x8
     }
 
+    #[tokio::test]
+    async fn play_sound_unknown_name_lists_configured_sounds() {
+        let mut config = RobotConfig::default();
+        config
+            .emote
+            .sounds
+            .insert("beep".to_string(), PathBuf::from("/tmp/beep.wav"));
+        config
+            .emote
+            .sounds
+            .insert("chime".to_string(), PathBuf::from("/tmp/chime.wav"));
+        let tool = EmoteTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "play_sound", "name": "klaxon"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("klaxon"));
+        assert!(error.contains("beep, chime"));
+    }
+
+    #[tokio::test]
+    async fn stop_sound_with_nothing_playing_succeeds() {
+        let tool = EmoteTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "stop_sound"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Stopped 0"));
+    }
+
+    #[tokio::test]
+    async fn malformed_args_rejected() {
+        let tool = EmoteTool::new(RobotConfig::default());
+        for args in [
+            json!({"expression": "nonexistent"}),
+            json!({"action": "dance_party"}),
+            json!({"action": "play_sound", "name": "beep", "volume": "loud"}),
+            json!({"expression": "happy", "duration": 1.5}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
+
     #[tokio::test]
     async fn emote_happy() {
         let tool = EmoteTool::new(RobotConfig::default());
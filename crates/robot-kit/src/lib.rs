@@ -87,6 +87,7 @@
 #![allow(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod audio;
 pub mod config;
 pub mod traits;
 
@@ -115,7 +116,7 @@ pub use sense::SenseTool;
 pub use speak::SpeakTool;
 
 #[cfg(feature = "safety")]
-pub use safety::{preflight_check, SafeDrive, SafetyEvent, SafetyMonitor, SensorReading};
+pub use safety::{preflight_check, EventSink, SafeDrive, SafetyEvent, SafetyMonitor, SensorReading};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -124,14 +125,30 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 ///
 /// Returns a Vec of boxed tools ready for use with an agent.
 pub fn create_tools(config: &RobotConfig) -> Vec<Box<dyn Tool>> {
-    vec![
+    let tools: Vec<Box<dyn Tool>> = vec![
         Box::new(DriveTool::new(config.clone())),
         Box::new(LookTool::new(config.clone())),
         Box::new(ListenTool::new(config.clone())),
         Box::new(SpeakTool::new(config.clone())),
         Box::new(SenseTool::new(config.clone())),
         Box::new(EmoteTool::new(config.clone())),
-    ]
+    ];
+    self_check_schemas(&tools);
+    tools
+}
+
+/// Startup self-check: every tool must declare a well-formed parameters
+/// schema, otherwise `validate_args` would silently skip checks.
+fn self_check_schemas(tools: &[Box<dyn Tool>]) {
+    for tool in tools {
+        if let Err(e) = traits::check_parameters_schema(&tool.parameters_schema()) {
+            tracing::error!(
+                "Tool '{}' declares an invalid parameters schema: {e}",
+                tool.name()
+            );
+            debug_assert!(false, "tool '{}' schema: {e}", tool.name());
+        }
+    }
 }
 
 /// Create all robot tools with safety wrapper on drive
@@ -143,12 +160,14 @@ pub fn create_safe_tools(
     let drive = std::sync::Arc::new(DriveTool::new(config.clone()));
     let safe_drive = SafeDrive::new(drive, safety);
 
-    vec![
+    let tools: Vec<Box<dyn Tool>> = vec![
         Box::new(safe_drive),
         Box::new(LookTool::new(config.clone())),
         Box::new(ListenTool::new(config.clone())),
         Box::new(SpeakTool::new(config.clone())),
         Box::new(SenseTool::new(config.clone())),
         Box::new(EmoteTool::new(config.clone())),
-    ]
+    ];
+    self_check_schemas(&tools);
+    tools
 }
@@ -13,6 +13,7 @@ use std::path::{Path, PathBuf};
 pub struct ListenTool {
     config: RobotConfig,
     recordings_dir: PathBuf,
+    description: String,
 }
 
 impl ListenTool {
@@ -23,12 +24,75 @@ impl ListenTool {
 
         let _ = std::fs::create_dir_all(&recordings_dir);
 
+        let description = format!(
+            "Listen for speech and transcribe it to text. Records from the microphone \
+             for the specified duration, then converts speech to text using Whisper. \
+             Default language: '{}'{}.",
+            config.audio.whisper_language,
+            if config.audio.whisper_translate {
+                ", translating to English"
+            } else {
+                ""
+            }
+        );
+
         Self {
             config,
             recordings_dir,
+            description,
+        }
+    }
+
+    /// Reject malformed language codes before spawning whisper:
+    /// "auto" or a 2-3 letter lowercase ISO 639 code
+    fn validate_language(code: &str) -> Result<()> {
+        if code == "auto"
+            || ((2..=3).contains(&code.len())
+                && code.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            Ok(())
+        } else {
+            anyhow::bail!(
+                "Invalid language code '{code}': expected an ISO 639 code like 'de' or 'auto'"
+            )
         }
     }
 
+    /// Build the whisper.cpp argument list
+    fn whisper_args(
+        model_path: &Path,
+        audio_path: &Path,
+        language: &str,
+        translate: bool,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "-m".to_string(),
+            model_path.to_string_lossy().into_owned(),
+            "-f".to_string(),
+            audio_path.to_string_lossy().into_owned(),
+            "--no-timestamps".to_string(),
+            "-otxt".to_string(),
+            "-l".to_string(),
+            language.to_string(),
+        ];
+        if translate {
+            args.push("--translate".to_string());
+        }
+        args
+    }
+
+    /// Pull the detected language out of whisper.cpp's stderr when running
+    /// with `-l auto` (line: "auto-detected language: de (p = 0.98)")
+    fn detected_language(stderr: &str) -> Option<String> {
+        stderr.lines().find_map(|line| {
+            line.split("auto-detected language:")
+                .nth(1)?
+                .split_whitespace()
+                .next()
+                .map(str::to_string)
+        })
+    }
+
     /// Record audio using arecord (ALSA)
     async fn record_audio(&self, duration_secs: u64) -> Result<PathBuf> {
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -66,8 +130,14 @@ impl ListenTool {
         Ok(filename)
     }
 
-    /// Transcribe audio using whisper.cpp
-    async fn transcribe(&self, audio_path: &Path) -> Result<String> {
+    /// Transcribe audio using whisper.cpp. Returns the transcript plus the
+    /// auto-detected language, when whisper reports one.
+    async fn transcribe(
+        &self,
+        audio_path: &Path,
+        language: &str,
+        translate: bool,
+    ) -> Result<(String, Option<String>)> {
         let whisper_path = &self.config.audio.whisper_path;
         let model = &self.config.audio.whisper_model;
 
@@ -83,14 +153,12 @@ impl ListenTool {
 
         // Run whisper.cpp
         let output = tokio::process::Command::new(whisper_path)
-            .args([
-                "-m",
-                model_path.to_str().unwrap(),
-                "-f",
-                audio_path.to_str().unwrap(),
-                "--no-timestamps",
-                "-otxt", // Output as text
-            ])
+            .args(Self::whisper_args(
+                &model_path,
+                audio_path,
+                language,
+                translate,
+            ))
             .output()
             .await?;
 
@@ -101,6 +169,12 @@ impl ListenTool {
             );
         }
 
+        let detected = if language == "auto" {
+            Self::detected_language(&String::from_utf8_lossy(&output.stderr))
+        } else {
+            None
+        };
+
         // whisper.cpp outputs to <input>.txt
         let txt_path = audio_path.with_extension("wav.txt");
         let transcript = tokio::fs::read_to_string(&txt_path)
@@ -110,7 +184,7 @@ impl ListenTool {
         // Clean up temp files
         let _ = tokio::fs::remove_file(&txt_path).await;
 
-        Ok(transcript.trim().to_string())
+        Ok((transcript.trim().to_string(), detected))
     }
 }
 
@@ -121,8 +195,7 @@ impl Tool for ListenTool {
     }
 
     fn description(&self) -> &str {
-        "Listen for speech and transcribe it to text. Records from the microphone \
-         for the specified duration, then converts speech to text using Whisper."
+        &self.description
     }
 
     fn parameters_schema(&self) -> Value {
@@ -138,13 +211,41 @@ impl Tool for ListenTool {
                 "prompt": {
                     "type": "string",
                     "description": "Optional context hint for transcription (e.g., 'The speaker is a child')"
+                },
+                "language": {
+                    "type": "string",
+                    "description": "ISO 639 language code ('de', 'en', ...) or 'auto' to detect. Defaults to the configured language."
+                },
+                "translate_to_english": {
+                    "type": "boolean",
+                    "description": "Translate the transcript to English (defaults to the configured setting)"
                 }
             }
         })
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
         let duration = args["duration"].as_u64().unwrap_or(5).clamp(1, 30);
+        let language = args["language"]
+            .as_str()
+            .unwrap_or(&self.config.audio.whisper_language)
+            .to_string();
+        let translate = args["translate_to_english"]
+            .as_bool()
+            .unwrap_or(self.config.audio.whisper_translate);
+
+        // Reject bad language codes before recording or spawning whisper
+        if let Err(e) = Self::validate_language(&language) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            });
+        }
 
         // Record audio
         tracing::info!("Recording audio for {} seconds...", duration);
@@ -161,11 +262,15 @@ impl Tool for ListenTool {
 
         // Transcribe
         tracing::info!("Transcribing audio...");
-        match self.transcribe(&audio_path).await {
-            Ok(transcript) => {
+        match self.transcribe(&audio_path, &language, translate).await {
+            Ok((transcript, detected)) => {
                 // Clean up audio file
                 let _ = tokio::fs::remove_file(&audio_path).await;
 
+                let language_note = detected
+                    .map(|code| format!(" (detected language: {code})"))
+                    .unwrap_or_default();
+
                 if transcript.is_empty() {
                     Ok(ToolResult {
                         success: true,
@@ -175,7 +280,7 @@ impl Tool for ListenTool {
                 } else {
                     Ok(ToolResult {
                         success: true,
-                        output: format!("I heard: \"{}\"", transcript),
+                        output: format!("I heard: \"{}\"{}", transcript, language_note),
                         error: None,
                     })
                 }
@@ -204,5 +309,80 @@ mod tests {
         let tool = ListenTool::new(RobotConfig::default());
         let schema = tool.parameters_schema();
         assert!(schema["properties"]["duration"].is_object());
+        assert!(schema["properties"]["language"].is_object());
+        assert!(schema["properties"]["translate_to_english"].is_object());
+    }
+
+    #[test]
+    fn description_names_configured_language() {
+        let mut config = RobotConfig::default();
+        config.audio.whisper_language = "de".to_string();
+        config.audio.whisper_translate = true;
+        let tool = ListenTool::new(config);
+        assert!(tool.description().contains("'de'"));
+        assert!(tool.description().contains("translating to English"));
+    }
+
+    #[test]
+    fn whisper_args_include_language_and_translate() {
+        let args = ListenTool::whisper_args(
+            Path::new("/models/ggml-base.bin"),
+            Path::new("/tmp/rec.wav"),
+            "de",
+            true,
+        );
+        let lang_pos = args.iter().position(|a| a == "-l").unwrap();
+        assert_eq!(args[lang_pos + 1], "de");
+        assert!(args.contains(&"--translate".to_string()));
+        assert!(args.contains(&"--no-timestamps".to_string()));
+    }
+
+    #[test]
+    fn whisper_args_omit_translate_by_default() {
+        let args = ListenTool::whisper_args(
+            Path::new("/models/ggml-base.bin"),
+            Path::new("/tmp/rec.wav"),
+            "auto",
+            false,
+        );
+        assert!(!args.contains(&"--translate".to_string()));
+        let lang_pos = args.iter().position(|a| a == "-l").unwrap();
+        assert_eq!(args[lang_pos + 1], "auto");
+    }
+
+    #[test]
+    fn language_codes_validated_before_spawn() {
+        assert!(ListenTool::validate_language("auto").is_ok());
+        assert!(ListenTool::validate_language("de").is_ok());
+        assert!(ListenTool::validate_language("yue").is_ok());
+        assert!(ListenTool::validate_language("german").is_err());
+        assert!(ListenTool::validate_language("DE").is_err());
+        assert!(ListenTool::validate_language("").is_err());
+    }
+
+    #[tokio::test]
+    async fn malformed_args_rejected_before_recording() {
+        let tool = ListenTool::new(RobotConfig::default());
+        for args in [
+            json!({"duration": "five"}),
+            json!({"duration": 2.5}),
+            json!({"translate_to_english": "yes"}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
+
+    #[test]
+    fn detected_language_parsed_from_stderr() {
+        let stderr = "whisper_init_from_file...\n\
+                      auto-detected language: de (p = 0.976143)\n\
+                      main: processing...\n";
+        assert_eq!(
+            ListenTool::detected_language(stderr),
+            Some("de".to_string())
+        );
+        assert_eq!(ListenTool::detected_language("no detection line"), None);
     }
 }
@@ -3,16 +3,24 @@
 //! Captures an image from the camera and optionally describes it
 //! using a local vision model (LLaVA, Moondream) via Ollama.
 
+#[cfg(feature = "camera-stream")]
+pub mod stream;
+
 use crate::config::RobotConfig;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::path::PathBuf;
+use std::sync::Arc;
 
+/// Clones share the capture lock, so a cloned tool (e.g. the MJPEG stream's
+/// frame source) never fights the original for the camera device.
+#[derive(Clone)]
 pub struct LookTool {
     config: RobotConfig,
     capture_dir: PathBuf,
+    capture_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl LookTool {
@@ -27,11 +35,14 @@ impl LookTool {
         Self {
             config,
             capture_dir,
+            capture_lock: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
 
     /// Capture image using ffmpeg (works with most cameras)
     async fn capture_image(&self) -> Result<PathBuf> {
+        // One capture at a time: the device can't serve two readers
+        let _guard = self.capture_lock.lock().await;
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         let filename = self.capture_dir.join(format!("capture_{}.jpg", timestamp));
 
@@ -122,6 +133,29 @@ impl LookTool {
 
         Ok(description)
     }
+
+    /// Start the MJPEG stream server from `camera.stream`, using this tool's
+    /// capture pipeline as the frame source. Returns `None` when disabled.
+    #[cfg(feature = "camera-stream")]
+    pub async fn start_stream(
+        &self,
+    ) -> Result<Option<(std::net::SocketAddr, tokio::task::JoinHandle<()>)>> {
+        let stream_config = self.config.camera.stream.clone();
+        if !stream_config.enabled {
+            return Ok(None);
+        }
+        let server = stream::StreamServer::new(stream_config, Arc::new(self.clone()));
+        Ok(Some(server.serve().await?))
+    }
+}
+
+#[cfg(feature = "camera-stream")]
+#[async_trait]
+impl stream::FrameSource for LookTool {
+    async fn next_frame(&self) -> Result<Vec<u8>> {
+        let path = self.capture_image().await?;
+        Ok(tokio::fs::read(&path).await?)
+    }
 }
 
 #[async_trait]
@@ -154,6 +188,10 @@ impl Tool for LookTool {
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
         let action = args["action"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
@@ -247,4 +285,19 @@ mod tests {
         let schema = tool.parameters_schema();
         assert!(schema["properties"]["action"].is_object());
     }
+
+    #[tokio::test]
+    async fn malformed_args_rejected_before_capture() {
+        let tool = LookTool::new(RobotConfig::default());
+        for args in [
+            serde_json::json!({}),
+            serde_json::json!({"action": "zoom"}),
+            serde_json::json!({"action": 1}),
+            serde_json::json!({"action": "find", "prompt": 42}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
 }
@@ -0,0 +1,258 @@
+//! MJPEG camera stream server (`camera-stream` feature)
+//!
+//! Serves the latest camera frames as an HTTP `multipart/x-mixed-replace`
+//! stream for remote monitoring. Frames come from a [`FrameSource`] so the
+//! server shares the tool's capture pipeline instead of opening the camera
+//! device a second time. Access requires the configured token as a
+//! `?token=` query parameter or `X-Stream-Token` header; the frame rate is
+//! capped by `max_fps`.
+
+use crate::config::CameraStreamConfig;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Multipart boundary between JPEG frames
+pub const BOUNDARY: &str = "zeroclawframe";
+
+/// Supplies JPEG-encoded frames to the stream server.
+///
+/// `LookTool` implements this using its capture pipeline; tests can plug in
+/// a synthetic source.
+#[async_trait]
+pub trait FrameSource: Send + Sync {
+    /// Produce the next JPEG-encoded frame
+    async fn next_frame(&self) -> Result<Vec<u8>>;
+}
+
+/// HTTP server streaming MJPEG from a [`FrameSource`]
+pub struct StreamServer {
+    config: CameraStreamConfig,
+    source: Arc<dyn FrameSource>,
+}
+
+impl StreamServer {
+    pub fn new(config: CameraStreamConfig, source: Arc<dyn FrameSource>) -> Self {
+        Self { config, source }
+    }
+
+    /// Bind and start serving. Returns the bound address and the accept-loop
+    /// task; abort the task to stop the server.
+    pub async fn serve(self) -> Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
+        let listener =
+            TcpListener::bind((self.config.bind_addr.as_str(), self.config.port)).await?;
+        let addr = listener.local_addr()?;
+        let frame_interval = Duration::from_secs_f64(1.0 / self.config.max_fps.max(0.1));
+        let token = self.config.token.clone();
+        let source = self.source;
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let Ok((stream, peer)) = listener.accept().await else {
+                    break;
+                };
+                tracing::debug!(%peer, "Camera stream client connected");
+                let token = token.clone();
+                let source = source.clone();
+                tokio::spawn(async move {
+                    stream_client(stream, &token, source, frame_interval).await;
+                });
+            }
+        });
+
+        tracing::info!(%addr, "Camera MJPEG stream serving");
+        Ok((addr, handle))
+    }
+}
+
+/// Serve one client: auth check, multipart header, then frames until the
+/// client disconnects.
+async fn stream_client(
+    mut stream: TcpStream,
+    token: &str,
+    source: Arc<dyn FrameSource>,
+    frame_interval: Duration,
+) {
+    let mut buf = [0u8; 2048];
+    let n = stream.read(&mut buf).await.unwrap_or(0);
+    let request = String::from_utf8_lossy(&buf[..n]);
+
+    if !authorized(&request, token) {
+        let _ = stream
+            .write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await;
+        return;
+    }
+
+    let header = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={BOUNDARY}\r\n\
+         Cache-Control: no-cache\r\n\
+         Connection: close\r\n\r\n"
+    );
+    if stream.write_all(header.as_bytes()).await.is_err() {
+        return;
+    }
+
+    loop {
+        match source.next_frame().await {
+            Ok(frame) => {
+                if stream.write_all(&frame_part(&frame)).await.is_err() {
+                    break; // client gone
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Camera stream frame capture failed: {e}");
+                break;
+            }
+        }
+        tokio::time::sleep(frame_interval).await;
+    }
+}
+
+/// Token check: query parameter `token=` on the request line, or an
+/// `X-Stream-Token` header. An empty configured token disables auth.
+fn authorized(request: &str, token: &str) -> bool {
+    if token.is_empty() {
+        return true;
+    }
+
+    let mut lines = request.lines();
+    if let Some(request_line) = lines.next() {
+        if let Some(query) = request_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|path| path.split_once('?'))
+            .map(|(_, q)| q)
+        {
+            if query
+                .split('&')
+                .any(|pair| pair.strip_prefix("token=") == Some(token))
+            {
+                return true;
+            }
+        }
+    }
+
+    lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("x-stream-token") && value.trim() == token
+        })
+    })
+}
+
+/// One multipart chunk: boundary, JPEG part headers, frame bytes
+fn frame_part(frame: &[u8]) -> Vec<u8> {
+    let mut part = format!(
+        "--{BOUNDARY}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+        frame.len()
+    )
+    .into_bytes();
+    part.extend_from_slice(frame);
+    part.extend_from_slice(b"\r\n");
+    part
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synthetic frame source: fixed JPEG-ish payloads
+    struct FakeFrames;
+
+    #[async_trait]
+    impl FrameSource for FakeFrames {
+        async fn next_frame(&self) -> Result<Vec<u8>> {
+            Ok(b"\xff\xd8FAKEJPEG\xff\xd9".to_vec())
+        }
+    }
+
+    fn test_config(token: &str) -> CameraStreamConfig {
+        CameraStreamConfig {
+            enabled: true,
+            bind_addr: "127.0.0.1".to_string(),
+            port: 0,
+            token: token.to_string(),
+            max_fps: 50.0,
+        }
+    }
+
+    async fn fetch(addr: SocketAddr, request: &str, max_bytes: usize) -> Vec<u8> {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut received = Vec::new();
+        let mut buf = [0u8; 512];
+        while received.len() < max_bytes {
+            match tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf)).await {
+                Ok(Ok(n)) if n > 0 => received.extend_from_slice(&buf[..n]),
+                _ => break,
+            }
+        }
+        received
+    }
+
+    #[test]
+    fn frame_part_uses_multipart_framing() {
+        let part = frame_part(b"JPEGDATA");
+        let text = String::from_utf8_lossy(&part);
+        assert!(text.starts_with("--zeroclawframe\r\n"));
+        assert!(text.contains("Content-Type: image/jpeg\r\n"));
+        assert!(text.contains("Content-Length: 8\r\n"));
+        assert!(text.ends_with("JPEGDATA\r\n"));
+    }
+
+    #[tokio::test]
+    async fn streams_frames_with_query_token() {
+        let server = StreamServer::new(test_config("secret"), Arc::new(FakeFrames));
+        let (addr, handle) = server.serve().await.unwrap();
+
+        let body = fetch(addr, "GET /stream?token=secret HTTP/1.1\r\n\r\n", 400).await;
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.starts_with("HTTP/1.1 200 OK"));
+        assert!(text.contains("multipart/x-mixed-replace; boundary=zeroclawframe"));
+        // At least two frames within the window
+        assert!(text.matches("--zeroclawframe").count() >= 2);
+        assert!(text.contains("FAKEJPEG"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn accepts_header_token() {
+        let server = StreamServer::new(test_config("secret"), Arc::new(FakeFrames));
+        let (addr, handle) = server.serve().await.unwrap();
+
+        let body = fetch(
+            addr,
+            "GET /stream HTTP/1.1\r\nX-Stream-Token: secret\r\n\r\n",
+            200,
+        )
+        .await;
+        assert!(String::from_utf8_lossy(&body).starts_with("HTTP/1.1 200 OK"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_token() {
+        let server = StreamServer::new(test_config("secret"), Arc::new(FakeFrames));
+        let (addr, handle) = server.serve().await.unwrap();
+
+        for request in [
+            "GET /stream HTTP/1.1\r\n\r\n",
+            "GET /stream?token=wrong HTTP/1.1\r\n\r\n",
+        ] {
+            let body = fetch(addr, request, 200).await;
+            assert!(
+                String::from_utf8_lossy(&body).starts_with("HTTP/1.1 401"),
+                "request not rejected: {request}"
+            );
+        }
+
+        handle.abort();
+    }
+}
@@ -20,13 +20,14 @@ use crate::config::{RobotConfig, SafetyConfig};
 use crate::traits::ToolResult;
 use anyhow::Result;
 use portable_atomic::{AtomicU64, Ordering};
+use serde::Serialize;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, RwLock};
 
 /// Safety events broadcast to all listeners
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum SafetyEvent {
     /// Obstacle detected, movement blocked
     ObstacleDetected { distance: f64, angle: u16 },
@@ -36,14 +37,29 @@ pub enum SafetyEvent {
     WatchdogTimeout,
     /// Movement approved
     MovementApproved,
-    /// Movement denied with reason
-    MovementDenied { reason: String },
+    /// Movement denied with reason (and the blocked command, when known)
+    MovementDenied {
+        reason: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        command: Option<serde_json::Value>,
+    },
     /// Bump sensor triggered
     BumpDetected { sensor: String },
+    /// All proximity sources for a movement are stale; moving would be blind
+    SensorStale { sources: Vec<String> },
     /// System recovered, ready to move again
     Recovered,
 }
 
+/// Hook for forwarding safety events into an external system (logging,
+/// tracing, telemetry).
+///
+/// `emit` is called inline from the safety path, so implementations MUST be
+/// non-blocking — push into a bounded queue and count drops rather than wait.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: &SafetyEvent);
+}
+
 /// Real-time safety state
 pub struct SafetyState {
     /// Is it safe to move?
@@ -73,11 +89,56 @@ impl Default for SafetyState {
     }
 }
 
+/// Proximity sources the monitor fuses for obstacle decisions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProximitySource {
+    Lidar,
+    Ultrasonic,
+}
+
+impl ProximitySource {
+    const ALL: [Self; 2] = [Self::Lidar, Self::Ultrasonic];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Lidar => "lidar",
+            Self::Ultrasonic => "ultrasonic",
+        }
+    }
+
+    fn max_age(self, config: &SafetyConfig) -> Duration {
+        Duration::from_secs_f64(match self {
+            Self::Lidar => config.lidar_max_age_secs,
+            Self::Ultrasonic => config.ultrasonic_max_age_secs,
+        })
+    }
+}
+
+/// Latest proximity reading from one source, with its arrival time
+#[derive(Debug, Clone, Copy)]
+struct ProximityReading {
+    distance: f64,
+    angle: u16,
+    at: Instant,
+}
+
+/// Fused view of the proximity sources
+enum FusedProximity {
+    /// No source has ever reported (e.g. mock mode)
+    NoData,
+    /// Closest fresh reading wins
+    Fresh { distance: f64, angle: u16 },
+    /// Every source that ever reported has aged out
+    AllStale { sources: Vec<String> },
+}
+
 /// Safety monitor - runs as background task
 pub struct SafetyMonitor {
     config: SafetyConfig,
     state: Arc<SafetyState>,
+    proximity: RwLock<[Option<ProximityReading>; 2]>,
     event_tx: broadcast::Sender<SafetyEvent>,
+    event_sink: std::sync::RwLock<Option<Arc<dyn EventSink>>>,
     shutdown: AtomicBool,
 }
 
@@ -87,7 +148,9 @@ impl SafetyMonitor {
         let monitor = Self {
             config,
             state: Arc::new(SafetyState::default()),
+            proximity: RwLock::new([None, None]),
             event_tx,
+            event_sink: std::sync::RwLock::new(None),
             shutdown: AtomicBool::new(false),
         };
         (monitor, event_rx)
@@ -101,6 +164,35 @@ impl SafetyMonitor {
         self.event_tx.subscribe()
     }
 
+    /// Attach an external event sink. Every safety event is forwarded to it
+    /// in addition to the broadcast channel.
+    pub fn set_event_sink(&self, sink: Arc<dyn EventSink>) {
+        let mut guard = self.event_sink.write().unwrap_or_else(|e| e.into_inner());
+        *guard = Some(sink);
+    }
+
+    /// Publish an event to the broadcast channel and the optional sink.
+    fn publish(&self, event: SafetyEvent) {
+        if let Some(sink) = self
+            .event_sink
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            sink.emit(&event);
+        }
+        let _ = self.event_tx.send(event);
+    }
+
+    /// Report a command the safety layer refused, with its full payload.
+    /// Called by `SafeDrive` so external sinks see what was blocked.
+    pub fn report_blocked_command(&self, reason: &str, command: serde_json::Value) {
+        self.publish(SafetyEvent::MovementDenied {
+            reason: reason.to_string(),
+            command: Some(command),
+        });
+    }
+
     /// Check if movement is currently allowed
     pub async fn can_move(&self) -> bool {
         if self.state.estop_active.load(Ordering::SeqCst) {
@@ -114,6 +206,61 @@ impl SafetyMonitor {
         *self.state.speed_limit.read().await
     }
 
+    /// Fuse the per-source proximity readings, ignoring anything older than
+    /// its source's configured max age. When two fresh sources disagree by
+    /// more than `proximity_disagreement_delta`, the closer (more
+    /// conservative) reading wins and the mismatch is logged.
+    async fn fused_proximity(&self) -> FusedProximity {
+        let slots = self.proximity.read().await;
+        let mut fresh: Vec<(ProximitySource, ProximityReading)> = Vec::new();
+        let mut stale: Vec<String> = Vec::new();
+        for source in ProximitySource::ALL {
+            let Some(reading) = slots[source as usize] else {
+                continue;
+            };
+            if reading.at.elapsed() <= source.max_age(&self.config) {
+                fresh.push((source, reading));
+            } else {
+                stale.push(source.name().to_string());
+            }
+        }
+        drop(slots);
+
+        if fresh.is_empty() {
+            return if stale.is_empty() {
+                FusedProximity::NoData
+            } else {
+                FusedProximity::AllStale { sources: stale }
+            };
+        }
+
+        if fresh.len() == 2 {
+            let delta = (fresh[0].1.distance - fresh[1].1.distance).abs();
+            if delta > self.config.proximity_disagreement_delta {
+                tracing::warn!(
+                    "Proximity sources disagree: {}={:.2}m vs {}={:.2}m (delta {:.2}m) - \
+                     using the closer reading",
+                    fresh[0].0.name(),
+                    fresh[0].1.distance,
+                    fresh[1].0.name(),
+                    fresh[1].1.distance,
+                    delta
+                );
+            }
+        }
+
+        let mut closest = fresh[0].1;
+        for (_, reading) in &fresh[1..] {
+            if reading.distance < closest.distance {
+                closest = *reading;
+            }
+        }
+        FusedProximity::Fresh {
+            distance: closest.distance,
+            angle: closest.angle,
+        }
+    }
+
     /// Request permission to move - returns allowed speed multiplier or error
     pub async fn request_movement(&self, direction: &str, distance: f64) -> Result<f64, String> {
         // Check E-stop
@@ -129,15 +276,31 @@ impl SafetyMonitor {
                 .unwrap_or_else(|| "Movement blocked".to_string()));
         }
 
-        // Check obstacle distance in movement direction
-        let min_dist = *self.state.min_obstacle_distance.read().await;
+        // Check obstacle distance in movement direction, using only fresh
+        // proximity readings. All sources stale = we are blind: fail closed.
+        let min_dist = match self.fused_proximity().await {
+            FusedProximity::Fresh { distance, .. } => {
+                *self.state.min_obstacle_distance.write().await = distance;
+                distance
+            }
+            FusedProximity::NoData => *self.state.min_obstacle_distance.read().await,
+            FusedProximity::AllStale { sources } => {
+                let msg = format!(
+                    "Proximity data stale from all sources: {}",
+                    sources.join(", ")
+                );
+                self.publish(SafetyEvent::SensorStale { sources });
+                return Err(msg);
+            }
+        };
         if min_dist < self.config.min_obstacle_distance {
             let msg = format!(
                 "Obstacle too close: {:.2}m (min: {:.2}m)",
                 min_dist, self.config.min_obstacle_distance
             );
-            let _ = self.event_tx.send(SafetyEvent::MovementDenied {
+            self.publish(SafetyEvent::MovementDenied {
                 reason: msg.clone(),
+                command: None,
             });
             return Err(msg);
         }
@@ -170,7 +333,7 @@ impl SafetyMonitor {
         // Calculate speed limit based on proximity
         let speed_mult = self.calculate_speed_limit(min_dist).await;
 
-        let _ = self.event_tx.send(SafetyEvent::MovementApproved);
+        self.publish(SafetyEvent::MovementApproved);
         Ok(speed_mult)
     }
 
@@ -199,7 +362,7 @@ impl SafetyMonitor {
         self.state.can_move.store(false, Ordering::SeqCst);
         *self.state.block_reason.write().await = Some(reason.to_string());
 
-        let _ = self.event_tx.send(SafetyEvent::EmergencyStop {
+        self.publish(SafetyEvent::EmergencyStop {
             reason: reason.to_string(),
         });
     }
@@ -211,11 +374,40 @@ impl SafetyMonitor {
         self.state.can_move.store(true, Ordering::SeqCst);
         *self.state.block_reason.write().await = None;
 
-        let _ = self.event_tx.send(SafetyEvent::Recovered);
+        self.publish(SafetyEvent::Recovered);
     }
 
-    /// Update obstacle distance (call from sensor loop)
+    /// Update obstacle distance from LIDAR (call from sensor loop)
     pub async fn update_obstacle_distance(&self, distance: f64, angle: u16) {
+        self.record_proximity(ProximitySource::Lidar, distance, angle)
+            .await;
+    }
+
+    /// Update obstacle distance from the ultrasonic ranger (forward-facing)
+    pub async fn update_ultrasonic_distance(&self, distance: f64) {
+        self.record_proximity(ProximitySource::Ultrasonic, distance, 0)
+            .await;
+    }
+
+    /// Record a reading for one source, then re-evaluate the movement block
+    /// against the fused view of all fresh sources.
+    async fn record_proximity(&self, source: ProximitySource, distance: f64, angle: u16) {
+        {
+            let mut slots = self.proximity.write().await;
+            slots[source as usize] = Some(ProximityReading {
+                distance,
+                angle,
+                at: Instant::now(),
+            });
+        }
+
+        // The reading just recorded is always fresh, so fusion cannot
+        // report NoData or AllStale here.
+        let (distance, angle) = match self.fused_proximity().await {
+            FusedProximity::Fresh { distance, angle } => (distance, angle),
+            FusedProximity::NoData | FusedProximity::AllStale { .. } => (distance, angle),
+        };
+
         // Update minimum distance tracking
         {
             let mut min_dist = self.state.min_obstacle_distance.write().await;
@@ -232,9 +424,7 @@ impl SafetyMonitor {
             *self.state.block_reason.write().await =
                 Some(format!("Obstacle at {:.2}m ({}°)", distance, angle));
 
-            let _ = self
-                .event_tx
-                .send(SafetyEvent::ObstacleDetected { distance, angle });
+            self.publish(SafetyEvent::ObstacleDetected { distance, angle });
         } else if !self.state.estop_active.load(Ordering::SeqCst) {
             // Clear block if obstacle moved away and no E-stop
             self.state.can_move.store(true, Ordering::SeqCst);
@@ -250,7 +440,7 @@ impl SafetyMonitor {
         self.state.can_move.store(false, Ordering::SeqCst);
         *self.state.block_reason.write().await = Some(format!("Bump: {}", sensor));
 
-        let _ = self.event_tx.send(SafetyEvent::BumpDetected {
+        self.publish(SafetyEvent::BumpDetected {
             sensor: sensor.to_string(),
         });
 
@@ -258,11 +448,19 @@ impl SafetyMonitor {
         tokio::spawn({
             let state = self.state.clone();
             let event_tx = self.event_tx.clone();
+            let sink = self
+                .event_sink
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
             async move {
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 if !state.estop_active.load(Ordering::SeqCst) {
                     state.can_move.store(true, Ordering::SeqCst);
                     *state.block_reason.write().await = None;
+                    if let Some(sink) = &sink {
+                        sink.emit(&SafetyEvent::Recovered);
+                    }
                     let _ = event_tx.send(SafetyEvent::Recovered);
                 }
             }
@@ -288,6 +486,9 @@ impl SafetyMonitor {
                         SensorReading::Lidar { distance, angle } => {
                             self.update_obstacle_distance(distance, angle).await;
                         }
+                        SensorReading::Ultrasonic { distance } => {
+                            self.update_ultrasonic_distance(distance).await;
+                        }
                         SensorReading::Bump { sensor } => {
                             self.bump_detected(&sensor).await;
                         }
@@ -320,7 +521,7 @@ impl SafetyMonitor {
                         let elapsed = Duration::from_millis(now_ms - last_cmd_ms);
                         if elapsed > watchdog_timeout {
                             tracing::info!("Watchdog timeout - no commands for {:?}", elapsed);
-                            let _ = self.event_tx.send(SafetyEvent::WatchdogTimeout);
+                            self.publish(SafetyEvent::WatchdogTimeout);
                             // Don't block movement, just notify
                         }
                     }
@@ -334,6 +535,7 @@ impl SafetyMonitor {
 #[derive(Debug, Clone)]
 pub enum SensorReading {
     Lidar { distance: f64, angle: u16 },
+    Ultrasonic { distance: f64 },
     Bump { sensor: String },
     Estop { pressed: bool },
 }
@@ -371,6 +573,10 @@ impl crate::traits::Tool for SafeDrive {
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
         // ToolResult imported at top of file
 
+        if let Err(e) = crate::traits::Tool::validate_args(self, &args) {
+            return Ok(ToolResult::error(e));
+        }
+
         let action = args["action"].as_str().unwrap_or("unknown");
         let distance = args["distance"].as_f64().unwrap_or(0.5);
 
@@ -396,11 +602,14 @@ impl crate::traits::Tool for SafeDrive {
 
                 self.inner_drive.execute(modified_args).await
             }
-            Err(reason) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Safety blocked movement: {}", reason)),
-            }),
+            Err(reason) => {
+                self.safety.report_blocked_command(&reason, args);
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Safety blocked movement: {}", reason)),
+                })
+            }
         }
     }
 }
@@ -501,6 +710,25 @@ mod tests {
         assert!((speed - 0.0).abs() < 0.01);
     }
 
+    #[tokio::test]
+    async fn event_sink_receives_published_events() {
+        struct CountingSink(std::sync::atomic::AtomicUsize);
+        impl EventSink for CountingSink {
+            fn emit(&self, _event: &SafetyEvent) {
+                self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let (monitor, _rx) = SafetyMonitor::new(SafetyConfig::default());
+        let sink = Arc::new(CountingSink(std::sync::atomic::AtomicUsize::new(0)));
+        monitor.set_event_sink(sink.clone());
+
+        monitor.emergency_stop("test").await;
+        monitor.report_blocked_command("estop", serde_json::json!({"action": "forward"}));
+
+        assert_eq!(sink.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn request_movement_blocked() {
         let config = SafetyConfig {
@@ -517,6 +745,95 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn all_sources_stale_fails_closed() {
+        let config = SafetyConfig {
+            lidar_max_age_secs: 0.05,
+            ultrasonic_max_age_secs: 0.05,
+            ..Default::default()
+        };
+        let (monitor, mut rx) = SafetyMonitor::new(config);
+
+        // Both sources report clear, then die.
+        monitor.update_obstacle_distance(2.0, 0).await;
+        monitor.update_ultrasonic_distance(2.0).await;
+        tokio::time::sleep(Duration::from_millis(120)).await;
+
+        let result = monitor.request_movement("forward", 0.5).await;
+        let err = result.unwrap_err();
+        assert!(err.contains("stale"), "{err}");
+        assert!(err.contains("lidar") && err.contains("ultrasonic"), "{err}");
+
+        // A SensorStale event names the dead sources.
+        let mut saw_stale = false;
+        while let Ok(event) = rx.try_recv() {
+            if let SafetyEvent::SensorStale { sources } = event {
+                assert!(sources.contains(&"lidar".to_string()));
+                assert!(sources.contains(&"ultrasonic".to_string()));
+                saw_stale = true;
+            }
+        }
+        assert!(saw_stale);
+    }
+
+    #[tokio::test]
+    async fn stale_source_ignored_when_another_is_fresh() {
+        let config = SafetyConfig {
+            lidar_max_age_secs: 0.05,
+            ultrasonic_max_age_secs: 10.0,
+            ..Default::default()
+        };
+        let (monitor, _rx) = SafetyMonitor::new(config);
+
+        // LIDAR sees a wall right in front, then dies.
+        monitor.update_obstacle_distance(0.1, 0).await;
+        assert!(!monitor.can_move().await);
+        tokio::time::sleep(Duration::from_millis(120)).await;
+
+        // A fresh ultrasonic reading says the path is clear; the dead
+        // LIDAR's scary reading must no longer count.
+        monitor.update_ultrasonic_distance(2.0).await;
+        assert!(monitor.can_move().await);
+        assert!(monitor.request_movement("forward", 0.5).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn disagreeing_sources_resolve_to_closer_reading() {
+        let config = SafetyConfig {
+            min_obstacle_distance: 0.3,
+            proximity_disagreement_delta: 0.5,
+            ..Default::default()
+        };
+        let (monitor, _rx) = SafetyMonitor::new(config);
+
+        // LIDAR says clear, ultrasonic says something at 0.6m.
+        monitor.update_obstacle_distance(2.0, 0).await;
+        monitor.update_ultrasonic_distance(0.6).await;
+
+        // The conservative reading wins: still allowed, but slowed.
+        let speed = monitor.request_movement("forward", 0.1).await.unwrap();
+        assert!(speed > 0.0 && speed < 1.0, "speed={speed}");
+        let fused = *monitor.state.min_obstacle_distance.read().await;
+        assert!((fused - 0.6).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn safe_drive_rejects_malformed_args_before_safety_check() {
+        let (monitor, _rx) = SafetyMonitor::new(SafetyConfig::default());
+        let drive = Arc::new(crate::drive::DriveTool::new(RobotConfig::default()));
+        let safe = SafeDrive::new(drive, Arc::new(monitor));
+
+        for args in [
+            serde_json::json!({}),
+            serde_json::json!({"action": "fly"}),
+            serde_json::json!({"action": "forward", "distance": "far"}),
+        ] {
+            let result = crate::traits::Tool::execute(&safe, args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
+
     impl Default for SafetyConfig {
         fn default() -> Self {
             Self {
@@ -530,6 +847,9 @@ mod tests {
                 confirm_movement: false,
                 predict_collisions: true,
                 sensor_timeout_secs: 5,
+                lidar_max_age_secs: 2.0,
+                ultrasonic_max_age_secs: 1.0,
+                proximity_disagreement_delta: 0.5,
                 blind_mode_speed_limit: 0.2,
             }
         }
@@ -3,13 +3,21 @@
 //! Provides environmental awareness through various sensors.
 //! Supports multiple backends: direct GPIO, ROS2 topics, or mock.
 
+#[cfg(feature = "ros2")]
+pub mod ros2;
+
 use crate::config::RobotConfig;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+
+/// Queue depth for sensor subscriptions
+const SUBSCRIPTION_QUEUE_DEPTH: usize = 16;
 
 /// LIDAR scan result
 #[derive(Debug, Clone)]
@@ -29,9 +37,62 @@ pub struct MotionResult {
     pub sensors_triggered: Vec<u8>,
 }
 
+/// Sensor kinds available for subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    Lidar,
+    Motion,
+    Distance,
+}
+
+impl SensorKind {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "lidar" | "scan" => Some(Self::Lidar),
+            "motion" => Some(Self::Motion),
+            "distance" | "ultrasonic" => Some(Self::Distance),
+            _ => None,
+        }
+    }
+}
+
+/// A single reading emitted by a sensor subscription
+#[derive(Debug, Clone)]
+pub enum SensorReading {
+    Lidar {
+        nearest: (f64, u16),
+        forward_clear: bool,
+    },
+    Motion {
+        detected: bool,
+        sensors_triggered: Vec<u8>,
+    },
+    Distance {
+        meters: f64,
+    },
+}
+
+impl SensorReading {
+    /// Scalar value for min/max/avg summaries: nearest obstacle distance,
+    /// ultrasonic meters, or motion as 0.0/1.0.
+    pub fn value(&self) -> f64 {
+        match self {
+            Self::Lidar { nearest, .. } => nearest.0,
+            Self::Motion { detected, .. } => f64::from(u8::from(*detected)),
+            Self::Distance { meters } => *meters,
+        }
+    }
+}
+
+/// Clones share the scan cache, subscription counter, and (with the ros2
+/// feature) the topic subscription backend.
+#[derive(Clone)]
 pub struct SenseTool {
     config: RobotConfig,
     last_scan: Arc<Mutex<Option<LidarScan>>>,
+    active_subscriptions: Arc<AtomicUsize>,
+    #[cfg(feature = "ros2")]
+    ros2_backend: Arc<std::sync::OnceLock<Arc<ros2::Ros2Backend>>>,
 }
 
 impl SenseTool {
@@ -39,18 +100,162 @@ impl SenseTool {
         Self {
             config,
             last_scan: Arc::new(Mutex::new(None)),
+            active_subscriptions: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "ros2")]
+            ros2_backend: Arc::new(std::sync::OnceLock::new()),
         }
     }
 
+    /// Shared ROS2 subscription backend, started on first use
+    #[cfg(feature = "ros2")]
+    fn ros2(&self) -> &Arc<ros2::Ros2Backend> {
+        self.ros2_backend
+            .get_or_init(|| ros2::Ros2Backend::start(&self.config))
+    }
+
+    /// Number of currently running subscription tasks (for leak checks)
+    pub fn active_subscriptions(&self) -> usize {
+        self.active_subscriptions.load(Ordering::SeqCst)
+    }
+
+    /// Subscribe to periodic readings of one sensor kind.
+    ///
+    /// A background task polls the sensor at `interval` and sends readings
+    /// into the returned channel until the receiver is dropped, at which
+    /// point the task exits.
+    pub fn subscribe(&self, kind: SensorKind, interval: Duration) -> mpsc::Receiver<SensorReading> {
+        let (tx, rx) = mpsc::channel(SUBSCRIPTION_QUEUE_DEPTH);
+        let reader = self.clone();
+        let active = self.active_subscriptions.clone();
+
+        active.fetch_add(1, Ordering::SeqCst);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    () = tx.closed() => break,
+                    _ = ticker.tick() => {
+                        let reading = match kind {
+                            SensorKind::Lidar => reader.scan_lidar().await.map(|scan| {
+                                SensorReading::Lidar {
+                                    nearest: scan.nearest,
+                                    forward_clear: scan.forward_clear,
+                                }
+                            }),
+                            SensorKind::Motion => reader.check_motion().await.map(|m| {
+                                SensorReading::Motion {
+                                    detected: m.detected,
+                                    sensors_triggered: m.sensors_triggered,
+                                }
+                            }),
+                            SensorKind::Distance => reader
+                                .check_distance()
+                                .await
+                                .map(|meters| SensorReading::Distance { meters }),
+                        };
+                        match reading {
+                            Ok(reading) => {
+                                if tx.send(reading).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Err(e) => tracing::warn!("Sensor subscription read failed: {e}"),
+                        }
+                    }
+                }
+            }
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        rx
+    }
+
+    /// Bridge a subscription into the SafetyMonitor's sensor channel so the
+    /// monitor shares one hardware poll loop with other subscribers.
+    #[cfg(feature = "safety")]
+    pub fn attach_safety_monitor(
+        &self,
+        kind: SensorKind,
+        interval: Duration,
+        tx: mpsc::Sender<crate::safety::SensorReading>,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut rx = self.subscribe(kind, interval);
+        tokio::spawn(async move {
+            while let Some(reading) = rx.recv().await {
+                let converted = match reading {
+                    SensorReading::Lidar {
+                        nearest: (distance, angle),
+                        ..
+                    } => Some(crate::safety::SensorReading::Lidar { distance, angle }),
+                    SensorReading::Distance { meters } => {
+                        Some(crate::safety::SensorReading::Ultrasonic { distance: meters })
+                    }
+                    // Motion has no safety-monitor equivalent
+                    SensorReading::Motion { .. } => None,
+                };
+                if let Some(converted) = converted {
+                    if tx.send(converted).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
     /// Read LIDAR scan
     async fn scan_lidar(&self) -> Result<LidarScan> {
         match self.config.sensors.lidar_type.as_str() {
             "rplidar" => self.scan_rplidar().await,
-            "ros2" => self.scan_ros2().await,
+            "ros2" => {
+                #[cfg(feature = "ros2")]
+                {
+                    self.scan_ros2_latest().await
+                }
+                #[cfg(not(feature = "ros2"))]
+                {
+                    self.scan_ros2().await
+                }
+            }
             _ => self.scan_mock().await,
         }
     }
 
+    /// Latest scan from the ROS2 topic subscription
+    #[cfg(feature = "ros2")]
+    async fn scan_ros2_latest(&self) -> Result<LidarScan> {
+        match self.ros2().latest_scan() {
+            Some((scan, _age)) => Ok(scan),
+            None => {
+                tracing::warn!(
+                    topic = %self.config.sensors.ros2_scan_topic,
+                    "No LaserScan received yet, using mock data"
+                );
+                self.scan_mock().await
+            }
+        }
+    }
+
+    /// Staleness note for scan-based results (empty for non-ROS2 backends,
+    /// which read the hardware directly per call)
+    fn scan_staleness_note(&self) -> String {
+        #[cfg(feature = "ros2")]
+        if self.config.sensors.lidar_type == "ros2" {
+            return match self.ros2().latest_scan() {
+                Some((_, age)) => {
+                    let age = age.as_secs_f64();
+                    if age > self.config.safety.sensor_timeout_secs as f64 {
+                        format!(" [scan STALE: {age:.1}s old]")
+                    } else {
+                        format!(" [scan age {age:.1}s]")
+                    }
+                }
+                None => " [no scan received yet]".to_string(),
+            };
+        }
+        String::new()
+    }
+
     /// Mock LIDAR for testing
     async fn scan_mock(&self) -> Result<LidarScan> {
         // Simulate a room with walls
@@ -139,7 +344,9 @@ impl SenseTool {
         }
     }
 
-    /// Read from ROS2 /scan topic
+    /// Read from ROS2 /scan topic (one-shot fallback when the ros2 feature
+    /// and its streaming subscription are compiled out)
+    #[cfg(not(feature = "ros2"))]
     async fn scan_ros2(&self) -> Result<LidarScan> {
         let output = tokio::process::Command::new("ros2")
             .args(["topic", "echo", "--once", "/scan"])
@@ -241,9 +448,11 @@ impl Tool for SenseTool {
     }
 
     fn description(&self) -> &str {
-        "Check robot sensors. Actions: 'scan' for LIDAR (360° obstacle map), \
-         'motion' for PIR motion detection, 'distance' for ultrasonic range, \
-         'all' for combined sensor report."
+        "Check robot sensors. Actions: 'scan'/'lidar' for LIDAR (360° obstacle map), \
+         'lidar_sectors' for per-quadrant nearest obstacles, 'odometry' for pose \
+         (ROS2 backend), 'motion' for PIR motion detection, 'distance' for \
+         ultrasonic range, 'all' for combined sensor report, 'monitor' to watch \
+         one sensor over a time window and report min/max/avg."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -252,13 +461,26 @@ impl Tool for SenseTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["scan", "motion", "distance", "all", "clear_ahead"],
+                    "enum": ["scan", "lidar", "lidar_sectors", "odometry", "motion", "distance", "all", "clear_ahead", "monitor"],
                     "description": "Which sensor(s) to read"
                 },
                 "direction": {
                     "type": "string",
                     "enum": ["forward", "left", "right", "back", "all"],
                     "description": "For 'scan': which direction to report (default 'forward')"
+                },
+                "sensor": {
+                    "type": "string",
+                    "enum": ["lidar", "motion", "distance"],
+                    "description": "For 'monitor': which sensor to watch (default 'lidar')"
+                },
+                "duration_secs": {
+                    "type": "number",
+                    "description": "For 'monitor': observation window in seconds (default 5, max 30)"
+                },
+                "interval_ms": {
+                    "type": "integer",
+                    "description": "For 'monitor': sampling interval in milliseconds (default 200)"
                 }
             },
             "required": ["action"]
@@ -266,12 +488,16 @@ impl Tool for SenseTool {
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
         let action = args["action"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
 
         match action {
-            "scan" => {
+            "scan" | "lidar" => {
                 let scan = self.scan_lidar().await?;
                 let direction = args["direction"].as_str().unwrap_or("forward");
 
@@ -332,11 +558,82 @@ impl Tool for SenseTool {
 
                 Ok(ToolResult {
                     success: true,
-                    output: report,
+                    output: format!("{report}{}", self.scan_staleness_note()),
                     error: None,
                 })
             }
 
+            "lidar_sectors" => {
+                let scan = self.scan_lidar().await?;
+                let sector_min = |degrees: &mut dyn Iterator<Item = usize>| {
+                    degrees
+                        .map(|d| scan.ranges[d])
+                        .fold(f64::INFINITY, f64::min)
+                };
+                let front = sector_min(&mut (0..45).chain(315..360));
+                let left = sector_min(&mut (45..135));
+                let back = sector_min(&mut (135..225));
+                let right = sector_min(&mut (225..315));
+
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "LIDAR sectors (nearest obstacle): front {front:.2}m, \
+                         left {left:.2}m, back {back:.2}m, right {right:.2}m{}",
+                        self.scan_staleness_note()
+                    ),
+                    error: None,
+                })
+            }
+
+            "odometry" => {
+                #[cfg(feature = "ros2")]
+                {
+                    let timeout = self.config.safety.sensor_timeout_secs as f64;
+                    Ok(match self.ros2().latest_odom() {
+                        Some((odom, age)) => {
+                            let age = age.as_secs_f64();
+                            let staleness = if age > timeout {
+                                format!(" [STALE: {age:.1}s old]")
+                            } else {
+                                format!(" [age {age:.1}s]")
+                            };
+                            ToolResult {
+                                success: true,
+                                output: format!(
+                                    "Odometry: x {:.2}m, y {:.2}m, yaw {:.1}°, \
+                                     speed {:.2}m/s, turning {:.2}rad/s{staleness}",
+                                    odom.x,
+                                    odom.y,
+                                    odom.yaw.to_degrees(),
+                                    odom.linear,
+                                    odom.angular
+                                ),
+                                error: None,
+                            }
+                        }
+                        None => ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(format!(
+                                "No odometry received yet on {}",
+                                self.config.sensors.ros2_odom_topic
+                            )),
+                        },
+                    })
+                }
+                #[cfg(not(feature = "ros2"))]
+                {
+                    Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(
+                            "Odometry requires the ros2 feature and backend".to_string(),
+                        ),
+                    })
+                }
+            }
+
             "motion" => {
                 let motion = self.check_motion().await?;
                 let output = if motion.detected {
@@ -415,6 +712,52 @@ impl Tool for SenseTool {
                 })
             }
 
+            "monitor" => {
+                let sensor = args["sensor"].as_str().unwrap_or("lidar");
+                let Some(kind) = SensorKind::parse(sensor) else {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Unknown sensor: {sensor}")),
+                    });
+                };
+                let duration = args["duration_secs"].as_f64().unwrap_or(5.0).clamp(0.1, 30.0);
+                let interval_ms = args["interval_ms"].as_u64().unwrap_or(200).max(10);
+
+                let mut rx = self.subscribe(kind, Duration::from_millis(interval_ms));
+                let deadline =
+                    tokio::time::Instant::now() + Duration::from_secs_f64(duration);
+                let mut samples: Vec<f64> = Vec::new();
+                while let Ok(Some(reading)) =
+                    tokio::time::timeout_at(deadline, rx.recv()).await
+                {
+                    samples.push(reading.value());
+                }
+                drop(rx);
+
+                if samples.is_empty() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("No readings from {sensor} in {duration:.1}s")),
+                    });
+                }
+
+                let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let avg = samples.iter().sum::<f64>() / samples.len() as f64;
+
+                Ok(ToolResult {
+                    success: true,
+                    output: format!(
+                        "Monitored {sensor} for {duration:.1}s ({} samples): \
+                         min {min:.2}, max {max:.2}, avg {avg:.2}",
+                        samples.len()
+                    ),
+                    error: None,
+                })
+            }
+
             _ => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -454,4 +797,105 @@ mod tests {
             .unwrap();
         assert!(result.success);
     }
+
+    #[tokio::test]
+    async fn lidar_sectors_reports_per_quadrant_minimums() {
+        let tool = SenseTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "lidar_sectors"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        // Mock room: wall ahead at 2m, object on the left at 1m
+        assert!(result.output.contains("front 2.00m"));
+        assert!(result.output.contains("left 1.00m"));
+        assert!(result.output.contains("back 3.00m"));
+    }
+
+    #[tokio::test]
+    async fn subscribe_streams_lidar_readings() {
+        let tool = SenseTool::new(RobotConfig::default());
+        let mut rx = tool.subscribe(SensorKind::Lidar, Duration::from_millis(10));
+        let reading = rx.recv().await.unwrap();
+        assert!(matches!(reading, SensorReading::Lidar { .. }));
+        // Mock room has an object at 1m
+        assert!((reading.value() - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn monitor_action_summarizes_window() {
+        let tool = SenseTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({
+                "action": "monitor",
+                "sensor": "lidar",
+                "duration_secs": 0.2,
+                "interval_ms": 20
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "error: {:?}", result.error);
+        assert!(result.output.contains("min 1.00"));
+        assert!(result.output.contains("avg 1.00"));
+    }
+
+    #[tokio::test]
+    async fn monitor_rejects_unknown_sensor() {
+        let tool = SenseTool::new(RobotConfig::default());
+        let result = tool
+            .execute(json!({"action": "monitor", "sensor": "sonar"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn malformed_args_rejected() {
+        let tool = SenseTool::new(RobotConfig::default());
+        for args in [
+            json!({}),
+            json!({"action": "scan", "direction": "up"}),
+            json!({"action": "monitor", "interval_ms": "fast"}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
+
+    #[tokio::test]
+    async fn no_task_leak_after_subscribe_drop_cycles() {
+        let tool = SenseTool::new(RobotConfig::default());
+        for _ in 0..100 {
+            let mut rx = tool.subscribe(SensorKind::Lidar, Duration::from_millis(5));
+            let _ = rx.recv().await;
+            drop(rx);
+        }
+        // Tasks notice the dropped receiver via tx.closed() and exit.
+        for _ in 0..100 {
+            if tool.active_subscriptions() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(tool.active_subscriptions(), 0);
+    }
+
+    #[cfg(feature = "safety")]
+    #[tokio::test]
+    async fn attach_safety_monitor_forwards_lidar_readings() {
+        let tool = SenseTool::new(RobotConfig::default());
+        let (tx, mut rx) = mpsc::channel(8);
+        let handle = tool.attach_safety_monitor(SensorKind::Lidar, Duration::from_millis(10), tx);
+
+        let reading = rx.recv().await.unwrap();
+        match reading {
+            crate::safety::SensorReading::Lidar { distance, .. } => {
+                assert!((distance - 1.0).abs() < 1e-9);
+            }
+            other => panic!("unexpected reading: {other:?}"),
+        }
+        drop(rx);
+        handle.abort();
+    }
 }
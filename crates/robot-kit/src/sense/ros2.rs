@@ -0,0 +1,387 @@
+//! ROS2 sensing backend (`ros2` feature)
+//!
+//! Subscribes to configurable `LaserScan` and `Odometry` topics by running
+//! `ros2 topic echo` child processes and caching the latest parsed message
+//! per topic, with a receipt timestamp so callers can report staleness.
+//! QoS reliability is configurable because many LIDAR drivers publish
+//! best-effort. Parsing and conversion are plain functions so they can be
+//! tested against recorded message fixtures without a ROS2 install.
+
+use super::LidarScan;
+use crate::config::RobotConfig;
+use anyhow::{Context, Result};
+use std::process::Stdio;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Delay before re-spawning `ros2 topic echo` after it exits
+const RESPAWN_DELAY: Duration = Duration::from_secs(2);
+
+/// Parsed `sensor_msgs/msg/LaserScan` fields we care about
+#[derive(Debug, Clone)]
+pub struct LaserScanMsg {
+    pub angle_min: f64,
+    pub angle_increment: f64,
+    pub range_min: f64,
+    pub range_max: f64,
+    pub ranges: Vec<f64>,
+}
+
+/// Parsed `nav_msgs/msg/Odometry` fields, with yaw extracted from the
+/// orientation quaternion (planar robots: roll/pitch assumed zero)
+#[derive(Debug, Clone)]
+pub struct OdometryMsg {
+    pub x: f64,
+    pub y: f64,
+    pub yaw: f64,
+    pub linear: f64,
+    pub angular: f64,
+}
+
+/// Caches the latest message per subscribed topic
+pub struct Ros2Backend {
+    scan_slot: Arc<RwLock<Option<(LidarScan, Instant)>>>,
+    odom_slot: Arc<RwLock<Option<(OdometryMsg, Instant)>>>,
+}
+
+impl Ros2Backend {
+    /// Spawn the topic subscription tasks and return the shared cache.
+    pub fn start(config: &RobotConfig) -> Arc<Self> {
+        let backend = Arc::new(Self {
+            scan_slot: Arc::new(RwLock::new(None)),
+            odom_slot: Arc::new(RwLock::new(None)),
+        });
+
+        let qos = config.sensors.ros2_qos.clone();
+        let min_obstacle_distance = config.safety.min_obstacle_distance;
+
+        let scan_slot = backend.scan_slot.clone();
+        tokio::spawn(echo_loop(
+            config.sensors.ros2_scan_topic.clone(),
+            qos.clone(),
+            Arc::new(move |message: &str| match parse_laser_scan(message) {
+                Ok(msg) => {
+                    let scan = scan_to_lidar(&msg, min_obstacle_distance);
+                    *scan_slot.write().unwrap() = Some((scan, Instant::now()));
+                }
+                Err(e) => tracing::debug!("LaserScan parse failed: {e}"),
+            }),
+        ));
+
+        let odom_slot = backend.odom_slot.clone();
+        tokio::spawn(echo_loop(
+            config.sensors.ros2_odom_topic.clone(),
+            qos,
+            Arc::new(move |message: &str| match parse_odometry(message) {
+                Ok(msg) => {
+                    *odom_slot.write().unwrap() = Some((msg, Instant::now()));
+                }
+                Err(e) => tracing::debug!("Odometry parse failed: {e}"),
+            }),
+        ));
+
+        backend
+    }
+
+    /// Latest converted scan plus its age
+    pub fn latest_scan(&self) -> Option<(LidarScan, Duration)> {
+        self.scan_slot
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|(scan, at)| (scan.clone(), at.elapsed()))
+    }
+
+    /// Latest odometry plus its age
+    pub fn latest_odom(&self) -> Option<(OdometryMsg, Duration)> {
+        self.odom_slot
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|(odom, at)| (odom.clone(), at.elapsed()))
+    }
+}
+
+/// Run `ros2 topic echo` forever, handing each `---`-delimited message to
+/// `handler`, re-spawning the process if it exits (driver restart, etc.).
+async fn echo_loop(topic: String, qos: String, handler: Arc<dyn Fn(&str) + Send + Sync>) {
+    loop {
+        let child = tokio::process::Command::new("ros2")
+            .args(["topic", "echo", "--qos-reliability", &qos, &topic])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match child {
+            Ok(mut child) => {
+                if let Some(stdout) = child.stdout.take() {
+                    let mut lines = BufReader::new(stdout).lines();
+                    let mut message = String::new();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if line.trim_start().starts_with("---") {
+                            if !message.is_empty() {
+                                handler(&message);
+                                message.clear();
+                            }
+                        } else {
+                            message.push_str(&line);
+                            message.push('\n');
+                        }
+                    }
+                }
+                let _ = child.wait().await;
+                tracing::warn!(topic, "ros2 topic echo exited, re-spawning");
+            }
+            Err(e) => tracing::warn!(topic, "ros2 topic echo unavailable: {e}"),
+        }
+        tokio::time::sleep(RESPAWN_DELAY).await;
+    }
+}
+
+/// Parse the YAML `ros2 topic echo` prints for a `LaserScan` message.
+/// Handles both block (`- 1.0` per line) and flow (`[1.0, 2.0]`) range lists.
+pub fn parse_laser_scan(message: &str) -> Result<LaserScanMsg> {
+    let mut angle_min = None;
+    let mut angle_increment = None;
+    let mut range_min = 0.0;
+    let mut range_max = f64::INFINITY;
+    let mut ranges = Vec::new();
+    let mut in_ranges = false;
+
+    for line in message.lines() {
+        let trimmed = line.trim();
+        if in_ranges {
+            if let Some(value) = trimmed.strip_prefix("- ") {
+                ranges.push(parse_yaml_f64(value));
+                continue;
+            }
+            in_ranges = false;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "angle_min" => angle_min = Some(parse_yaml_f64(value)),
+            "angle_increment" => angle_increment = Some(parse_yaml_f64(value)),
+            "range_min" => range_min = parse_yaml_f64(value),
+            "range_max" => range_max = parse_yaml_f64(value),
+            "ranges" => {
+                if let Some(flow) = value.strip_prefix('[') {
+                    ranges.extend(
+                        flow.trim_end_matches(']')
+                            .split(',')
+                            .map(|v| parse_yaml_f64(v.trim())),
+                    );
+                } else {
+                    in_ranges = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    anyhow::ensure!(!ranges.is_empty(), "LaserScan message has no ranges");
+    Ok(LaserScanMsg {
+        angle_min: angle_min.context("LaserScan message missing angle_min")?,
+        angle_increment: angle_increment.context("LaserScan message missing angle_increment")?,
+        range_min,
+        range_max,
+        ranges,
+    })
+}
+
+/// Parse the YAML `ros2 topic echo` prints for an `Odometry` message.
+pub fn parse_odometry(message: &str) -> Result<OdometryMsg> {
+    let mut x = None;
+    let mut y = None;
+    let mut qz = None;
+    let mut qw = None;
+    let mut linear = None;
+    let mut angular = None;
+    let mut section = "";
+
+    for line in message.lines() {
+        let trimmed = line.trim();
+        match trimmed {
+            "position:" => section = "position",
+            "orientation:" => section = "orientation",
+            "linear:" => section = "linear",
+            "angular:" => section = "angular",
+            _ => {
+                let Some((key, value)) = trimmed.split_once(':') else {
+                    continue;
+                };
+                let value = parse_yaml_f64(value.trim());
+                match (section, key) {
+                    ("position", "x") => x = Some(value),
+                    ("position", "y") => y = Some(value),
+                    ("orientation", "z") => qz = Some(value),
+                    ("orientation", "w") => qw = Some(value),
+                    ("linear", "x") => linear = Some(value),
+                    ("angular", "z") => angular = Some(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let qz = qz.context("Odometry message missing orientation.z")?;
+    let qw = qw.context("Odometry message missing orientation.w")?;
+    Ok(OdometryMsg {
+        x: x.context("Odometry message missing position.x")?,
+        y: y.context("Odometry message missing position.y")?,
+        yaw: 2.0 * qz.atan2(qw),
+        linear: linear.unwrap_or(0.0),
+        angular: angular.unwrap_or(0.0),
+    })
+}
+
+/// Convert a `LaserScan` into the crate's one-reading-per-degree scan,
+/// keeping the minimum range per degree bin and dropping invalid readings.
+pub fn scan_to_lidar(msg: &LaserScanMsg, min_obstacle_distance: f64) -> LidarScan {
+    let mut ranges = vec![999.0; 360];
+    for (i, &r) in msg.ranges.iter().enumerate() {
+        if !r.is_finite() || r < msg.range_min || r > msg.range_max {
+            continue;
+        }
+        let angle = msg.angle_min + msg.angle_increment * i as f64;
+        let deg = angle.to_degrees().round().rem_euclid(360.0) as usize % 360;
+        if r < ranges[deg] {
+            ranges[deg] = r;
+        }
+    }
+
+    let nearest = ranges
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .map(|(i, &d)| (d, i as u16))
+        .unwrap_or((999.0, 0));
+
+    let forward_clear = ranges[0..30]
+        .iter()
+        .chain(ranges[330..360].iter())
+        .all(|&d| d > min_obstacle_distance);
+
+    LidarScan {
+        ranges,
+        nearest,
+        forward_clear,
+    }
+}
+
+/// ROS2 YAML floats include `.inf` / `-.inf` / `.nan`
+fn parse_yaml_f64(raw: &str) -> f64 {
+    match raw {
+        ".inf" | "inf" => f64::INFINITY,
+        "-.inf" | "-inf" => f64::NEG_INFINITY,
+        ".nan" | "nan" => f64::NAN,
+        _ => raw.parse().unwrap_or(f64::NAN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recorded (abridged) `ros2 topic echo /scan` output
+    const SCAN_FIXTURE: &str = "\
+header:
+  stamp:
+    sec: 1700000000
+    nanosec: 0
+  frame_id: laser
+angle_min: 0.0
+angle_max: 6.2831853
+angle_increment: 1.5707963
+time_increment: 0.0
+scan_time: 0.1
+range_min: 0.15
+range_max: 12.0
+ranges:
+- 2.0
+- 1.0
+- .inf
+- 0.05
+intensities: []
+";
+
+    /// Recorded (abridged) `ros2 topic echo /odom` output: 90° yaw
+    const ODOM_FIXTURE: &str = "\
+header:
+  frame_id: odom
+child_frame_id: base_link
+pose:
+  pose:
+    position:
+      x: 1.5
+      y: -0.25
+      z: 0.0
+    orientation:
+      x: 0.0
+      y: 0.0
+      z: 0.7071068
+      w: 0.7071068
+twist:
+  twist:
+    linear:
+      x: 0.3
+      y: 0.0
+      z: 0.0
+    angular:
+      x: 0.0
+      y: 0.0
+      z: 0.1
+";
+
+    #[test]
+    fn parses_laser_scan_block_list() {
+        let msg = parse_laser_scan(SCAN_FIXTURE).unwrap();
+        assert!((msg.angle_min - 0.0).abs() < 1e-9);
+        assert!((msg.angle_increment - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((msg.range_min - 0.15).abs() < 1e-9);
+        assert_eq!(msg.ranges.len(), 4);
+        assert!(msg.ranges[2].is_infinite());
+    }
+
+    #[test]
+    fn parses_laser_scan_flow_list() {
+        let msg =
+            parse_laser_scan("angle_min: 0.0\nangle_increment: 0.1\nranges: [1.0, .inf, 2.5]\n")
+                .unwrap();
+        assert_eq!(msg.ranges.len(), 3);
+        assert!(msg.ranges[1].is_infinite());
+        assert!((msg.ranges[2] - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scan_conversion_bins_by_degree_and_drops_invalid() {
+        let msg = parse_laser_scan(SCAN_FIXTURE).unwrap();
+        let scan = scan_to_lidar(&msg, 0.3);
+        // 90° increments: 0°=2.0, 90°=1.0; .inf and below-range_min dropped
+        assert!((scan.ranges[0] - 2.0).abs() < 1e-9);
+        assert!((scan.ranges[90] - 1.0).abs() < 1e-9);
+        assert!((scan.ranges[180] - 999.0).abs() < 1e-9);
+        assert!((scan.ranges[270] - 999.0).abs() < 1e-9);
+        assert_eq!(scan.nearest, (1.0, 90));
+        assert!(scan.forward_clear);
+    }
+
+    #[test]
+    fn parses_odometry_pose_yaw_and_twist() {
+        let odom = parse_odometry(ODOM_FIXTURE).unwrap();
+        assert!((odom.x - 1.5).abs() < 1e-9);
+        assert!((odom.y + 0.25).abs() < 1e-9);
+        // z = w = sin(45°): quaternion for a 90° yaw
+        assert!((odom.yaw - std::f64::consts::FRAC_PI_2).abs() < 1e-5);
+        assert!((odom.linear - 0.3).abs() < 1e-9);
+        assert!((odom.angular - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn laser_scan_without_ranges_is_rejected() {
+        assert!(parse_laser_scan("angle_min: 0.0\nangle_increment: 0.1\n").is_err());
+    }
+}
@@ -3,16 +3,20 @@
 //! Converts text to speech using Piper TTS (fast, offline, runs on Pi).
 //! Plays audio through the speaker.
 
+pub mod model_manager;
+
 use crate::config::RobotConfig;
 use crate::traits::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
+use model_manager::ModelManager;
 use serde_json::{json, Value};
 use std::path::PathBuf;
 
 pub struct SpeakTool {
     config: RobotConfig,
     audio_dir: PathBuf,
+    models: ModelManager,
 }
 
 impl SpeakTool {
@@ -23,7 +27,16 @@ impl SpeakTool {
 
         let _ = std::fs::create_dir_all(&audio_dir);
 
-        Self { config, audio_dir }
+        let models = ModelManager::new(
+            config.audio.model_dir.clone(),
+            config.audio.model_index_url.clone(),
+        );
+
+        Self {
+            config,
+            audio_dir,
+            models,
+        }
     }
 
     /// Generate speech using Piper and play it
@@ -32,13 +45,8 @@ impl SpeakTool {
         let voice = &self.config.audio.piper_voice;
         let speaker_device = &self.config.audio.speaker_device;
 
-        // Model path
-        let model_path = directories::UserDirs::new()
-            .map(|d| {
-                d.home_dir()
-                    .join(format!(".zeroclaw/models/piper/{}.onnx", voice))
-            })
-            .unwrap_or_else(|| PathBuf::from(format!("/usr/local/share/piper/{}.onnx", voice)));
+        // Resolve the voice model, auto-downloading on first use
+        let model_path = self.models.ensure_voice(voice).await?;
 
         // Adjust text based on emotion (simple SSML-like modifications)
         let processed_text = match emotion {
@@ -73,28 +81,8 @@ impl SpeakTool {
             anyhow::bail!("Piper TTS failed");
         }
 
-        // Play audio using aplay
-        let play_result = tokio::process::Command::new("aplay")
-            .args(["-D", speaker_device, output_path.to_str().unwrap()])
-            .output()
-            .await?;
-
-        if !play_result.status.success() {
-            // Fallback: try paplay (PulseAudio)
-            let fallback = tokio::process::Command::new("paplay")
-                .arg(output_path.to_str().unwrap())
-                .output()
-                .await?;
-
-            if !fallback.status.success() {
-                anyhow::bail!(
-                    "Audio playback failed. Tried aplay and paplay.\n{}",
-                    String::from_utf8_lossy(&play_result.stderr)
-                );
-            }
-        }
-
-        Ok(())
+        // Play through the shared audio pipeline (aplay + paplay fallback)
+        crate::audio::play_file(speaker_device, &output_path, 1.0).await
     }
 
     /// Play a sound effect
@@ -109,17 +97,7 @@ impl SpeakTool {
             anyhow::bail!("Sound file not found: {}", sound_file.display());
         }
 
-        let speaker_device = &self.config.audio.speaker_device;
-        let output = tokio::process::Command::new("aplay")
-            .args(["-D", speaker_device, sound_file.to_str().unwrap()])
-            .output()
-            .await?;
-
-        if !output.status.success() {
-            anyhow::bail!("Sound playback failed");
-        }
-
-        Ok(())
+        crate::audio::play_file(&self.config.audio.speaker_device, &sound_file, 1.0).await
     }
 }
 
@@ -131,7 +109,8 @@ impl Tool for SpeakTool {
 
     fn description(&self) -> &str {
         "Speak text out loud using text-to-speech. The robot will say the given text \
-         through its speaker. Can also play sound effects like 'beep', 'chime', 'laugh'."
+         through its speaker. Can also play sound effects like 'beep', 'chime', 'laugh', \
+         or pre-fetch a Piper voice with action 'download_voice'."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -150,12 +129,49 @@ impl Tool for SpeakTool {
                 "sound": {
                     "type": "string",
                     "description": "Play a sound effect instead of speaking (e.g., 'beep', 'chime', 'laugh', 'alert')"
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["download_voice"],
+                    "description": "Optional: 'download_voice' pre-fetches a voice model instead of speaking"
+                },
+                "voice": {
+                    "type": "string",
+                    "description": "For 'download_voice': voice name (defaults to the configured voice)"
                 }
             }
         })
     }
 
     async fn execute(&self, args: Value) -> Result<ToolResult> {
+        if let Err(e) = self.validate_args(&args) {
+            return Ok(ToolResult::error(e));
+        }
+
+        // Explicit voice pre-fetch
+        if args["action"].as_str() == Some("download_voice") {
+            let voice = args["voice"]
+                .as_str()
+                .unwrap_or(&self.config.audio.piper_voice);
+            let cached = self.models.is_cached(voice);
+            return match self.models.ensure_voice(voice).await {
+                Ok(path) => Ok(ToolResult {
+                    success: true,
+                    output: if cached {
+                        format!("Voice '{}' already cached at {}", voice, path.display())
+                    } else {
+                        format!("Voice '{}' downloaded to {}", voice, path.display())
+                    },
+                    error: None,
+                }),
+                Err(e) => Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Voice download failed: {e:#}")),
+                }),
+            };
+        }
+
         // Check if playing a sound effect
         if let Some(sound) = args["sound"].as_str() {
             return match self.play_sound(sound).await {
@@ -227,5 +243,40 @@ mod tests {
         let schema = tool.parameters_schema();
         assert!(schema["properties"]["text"].is_object());
         assert!(schema["properties"]["emotion"].is_object());
+        assert!(schema["properties"]["voice"].is_object());
+    }
+
+    #[tokio::test]
+    async fn malformed_args_rejected_before_synthesis() {
+        let tool = SpeakTool::new(RobotConfig::default());
+        for args in [
+            json!({"text": 42}),
+            json!({"action": "install_voice"}),
+            json!({"text": "hi", "emotion": "furious"}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().starts_with("Invalid arguments:"));
+        }
+    }
+
+    #[tokio::test]
+    async fn download_voice_offline_names_expected_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = RobotConfig::default();
+        config.audio.model_dir = dir.path().to_path_buf();
+        let tool = SpeakTool::new(config);
+
+        let result = tool
+            .execute(json!({"action": "download_voice"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        // Error points at exactly which file was expected where
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("en_US-lessac-medium.onnx"));
     }
 }
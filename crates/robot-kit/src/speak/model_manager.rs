@@ -0,0 +1,297 @@
+//! Piper voice model management
+//!
+//! Resolves a voice by name from a JSON model index, downloads the `.onnx`
+//! model and its config on first use, verifies SHA-256 checksums, and caches
+//! the files in the configured model directory. Downloads are atomic: bytes
+//! go to a `.part` temp file which is renamed into place only after the
+//! checksum matches, so a crash mid-download never leaves a corrupt model.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One voice in the model index
+#[derive(Debug, Clone, Deserialize)]
+pub struct VoiceEntry {
+    /// URL of the `.onnx` model file
+    pub onnx_url: String,
+    /// Expected SHA-256 of the model file (hex)
+    pub onnx_sha256: String,
+    /// URL of the voice config JSON
+    pub config_url: String,
+    /// Expected SHA-256 of the config file (hex)
+    pub config_sha256: String,
+}
+
+/// Downloads and caches Piper voice models.
+///
+/// Offline operation keeps working whenever the voice already exists in the
+/// model directory; the network is only touched on a cache miss.
+pub struct ModelManager {
+    model_dir: PathBuf,
+    index_url: String,
+}
+
+impl ModelManager {
+    pub fn new(model_dir: PathBuf, index_url: String) -> Self {
+        Self {
+            model_dir,
+            index_url,
+        }
+    }
+
+    /// Path where the voice's `.onnx` model lives (or will land)
+    pub fn model_path(&self, voice: &str) -> PathBuf {
+        self.model_dir.join(format!("{voice}.onnx"))
+    }
+
+    /// Path of the voice config (Piper convention: `<voice>.onnx.json`)
+    pub fn config_path(&self, voice: &str) -> PathBuf {
+        self.model_dir.join(format!("{voice}.onnx.json"))
+    }
+
+    /// Both files already present locally?
+    pub fn is_cached(&self, voice: &str) -> bool {
+        self.model_path(voice).exists() && self.config_path(voice).exists()
+    }
+
+    /// Ensure a voice is available locally, downloading it on first use.
+    /// Returns the path to the `.onnx` model.
+    pub async fn ensure_voice(&self, voice: &str) -> Result<PathBuf> {
+        let model_path = self.model_path(voice);
+        if self.is_cached(voice) {
+            return Ok(model_path);
+        }
+
+        let missing = format!(
+            "voice '{voice}' not found locally (expected {} and {})",
+            model_path.display(),
+            self.config_path(voice).display()
+        );
+
+        if self.index_url.is_empty() {
+            anyhow::bail!("{missing}; no model_index_url configured for auto-download");
+        }
+
+        let entry = self
+            .fetch_index()
+            .await
+            .with_context(|| missing.clone())?
+            .remove(voice)
+            .with_context(|| format!("{missing}; index has no entry for it"))?;
+
+        self.download_verified(&entry.onnx_url, &entry.onnx_sha256, &model_path)
+            .await
+            .with_context(|| missing.clone())?;
+        self.download_verified(
+            &entry.config_url,
+            &entry.config_sha256,
+            &self.config_path(voice),
+        )
+        .await
+        .with_context(|| missing)?;
+
+        tracing::info!(voice, path = %model_path.display(), "Piper voice downloaded");
+        Ok(model_path)
+    }
+
+    /// Fetch and parse the voice index
+    async fn fetch_index(&self) -> Result<HashMap<String, VoiceEntry>> {
+        let response = reqwest::get(&self.index_url)
+            .await
+            .with_context(|| format!("failed to fetch voice index from {}", self.index_url))?
+            .error_for_status()
+            .with_context(|| format!("voice index request to {} failed", self.index_url))?;
+        response
+            .json()
+            .await
+            .context("voice index is not valid JSON")
+    }
+
+    /// Download a file, verify its SHA-256, then atomically move it in place.
+    async fn download_verified(&self, url: &str, expected_sha256: &str, dest: &Path) -> Result<()> {
+        tokio::fs::create_dir_all(&self.model_dir).await?;
+
+        let bytes = reqwest::get(url)
+            .await
+            .with_context(|| format!("download from {url} failed"))?
+            .error_for_status()
+            .with_context(|| format!("download from {url} failed"))?
+            .bytes()
+            .await
+            .with_context(|| format!("download from {url} failed"))?;
+
+        let actual = hex::encode(Sha256::digest(&bytes));
+        if !actual.eq_ignore_ascii_case(expected_sha256) {
+            anyhow::bail!(
+                "checksum mismatch for {url}: expected {expected_sha256}, got {actual}"
+            );
+        }
+
+        // Temp file + rename keeps partially written models out of dest
+        let tmp = dest.with_extension("part");
+        tokio::fs::write(&tmp, &bytes).await?;
+        tokio::fs::rename(&tmp, dest).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    type Routes = Arc<tokio::sync::Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Minimal HTTP fixture server: serves a path → body map that tests can
+    /// fill in after the server address is known.
+    async fn serve() -> (std::net::SocketAddr, Routes) {
+        let routes: Routes = Arc::new(tokio::sync::Mutex::new(HashMap::new()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_routes = routes.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let routes = server_routes.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let path = request
+                        .split_whitespace()
+                        .nth(1)
+                        .unwrap_or("/")
+                        .to_string();
+                    let response = match routes.lock().await.get(&path) {
+                        Some(body) => {
+                            let mut r = format!(
+                                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                                body.len()
+                            )
+                            .into_bytes();
+                            r.extend_from_slice(body);
+                            r
+                        }
+                        None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec(),
+                    };
+                    let _ = stream.write_all(&response).await;
+                });
+            }
+        });
+        (addr, routes)
+    }
+
+    fn sha256_hex(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    fn index_json(addr: std::net::SocketAddr, model: &[u8], config: &[u8]) -> Vec<u8> {
+        serde_json::json!({
+            "test-voice": {
+                "onnx_url": format!("http://{addr}/model.onnx"),
+                "onnx_sha256": sha256_hex(model),
+                "config_url": format!("http://{addr}/model.onnx.json"),
+                "config_sha256": sha256_hex(config),
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[tokio::test]
+    async fn downloads_verifies_and_caches_voice() {
+        let model = b"fake onnx bytes".to_vec();
+        let config = b"{\"sample_rate\": 22050}".to_vec();
+        let (addr, routes) = serve().await;
+        {
+            let mut routes = routes.lock().await;
+            routes.insert("/model.onnx".to_string(), model.clone());
+            routes.insert("/model.onnx.json".to_string(), config.clone());
+            routes.insert("/index.json".to_string(), index_json(addr, &model, &config));
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ModelManager::new(
+            dir.path().to_path_buf(),
+            format!("http://{addr}/index.json"),
+        );
+
+        let path = manager.ensure_voice("test-voice").await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), model);
+        assert_eq!(
+            std::fs::read(manager.config_path("test-voice")).unwrap(),
+            config
+        );
+        // No .part temp files left behind
+        assert!(!path.with_extension("part").exists());
+
+        // Second call is served from cache: point at a dead index URL
+        let offline = ModelManager::new(
+            dir.path().to_path_buf(),
+            "http://127.0.0.1:1/index.json".to_string(),
+        );
+        assert!(offline.ensure_voice("test-voice").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn checksum_mismatch_rejects_download() {
+        let model = b"fake onnx bytes".to_vec();
+        let config = b"{}".to_vec();
+        let (addr, routes) = serve().await;
+        let bad_index = serde_json::json!({
+            "test-voice": {
+                "onnx_url": format!("http://{addr}/model.onnx"),
+                "onnx_sha256": "0".repeat(64),
+                "config_url": format!("http://{addr}/model.onnx.json"),
+                "config_sha256": sha256_hex(&config),
+            }
+        })
+        .to_string()
+        .into_bytes();
+        {
+            let mut routes = routes.lock().await;
+            routes.insert("/model.onnx".to_string(), model.clone());
+            routes.insert("/model.onnx.json".to_string(), config.clone());
+            routes.insert("/index.json".to_string(), bad_index);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ModelManager::new(
+            dir.path().to_path_buf(),
+            format!("http://{addr}/index.json"),
+        );
+
+        let err = manager.ensure_voice("test-voice").await.unwrap_err();
+        assert!(format!("{err:#}").contains("checksum mismatch"));
+        assert!(!manager.model_path("test-voice").exists());
+    }
+
+    #[tokio::test]
+    async fn offline_miss_error_names_expected_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ModelManager::new(
+            dir.path().to_path_buf(),
+            "http://127.0.0.1:1/index.json".to_string(),
+        );
+
+        let err = manager.ensure_voice("missing-voice").await.unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("missing-voice.onnx"));
+        assert!(message.contains(dir.path().to_str().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn no_index_url_fails_without_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ModelManager::new(dir.path().to_path_buf(), String::new());
+        let err = manager.ensure_voice("missing-voice").await.unwrap_err();
+        assert!(format!("{err:#}").contains("no model_index_url"));
+    }
+}
@@ -157,9 +157,10 @@ mod unit_tests {
         let config = RobotConfig::default();
         let tool = DriveTool::new(config);
 
-        let result = tool.execute(json!({})).await;
+        let result = tool.execute(json!({})).await.unwrap();
 
-        assert!(result.is_err());
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("'action' is required"));
     }
 
     #[tokio::test]
@@ -271,9 +272,13 @@ mod unit_tests {
         let config = RobotConfig::default();
         let tool = EmoteTool::new(config);
 
-        let result = tool.execute(json!({"expression": "nonexistent"})).await;
+        let result = tool
+            .execute(json!({"expression": "nonexistent"}))
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("must be one of"));
     }
 
     // =========================================================================
@@ -332,6 +337,9 @@ mod safety_tests {
             confirm_movement: false,
             predict_collisions: true,
             sensor_timeout_secs: 5,
+            lidar_max_age_secs: 2.0,
+            ultrasonic_max_age_secs: 1.0,
+            proximity_disagreement_delta: 0.5,
             blind_mode_speed_limit: 0.2,
         }
     }
@@ -512,6 +520,17 @@ mod integration_tests {
         assert!(names.contains(&"emote"));
     }
 
+    #[test]
+    fn every_tool_schema_passes_self_check() {
+        for tool in create_tools(&RobotConfig::default()) {
+            assert!(
+                crate::traits::check_parameters_schema(&tool.parameters_schema()).is_ok(),
+                "tool '{}' declares an invalid schema",
+                tool.name()
+            );
+        }
+    }
+
     #[cfg(feature = "safety")]
     #[tokio::test]
     async fn safe_drive_blocks_on_obstacle() {
@@ -47,6 +47,166 @@ impl ToolResult {
     }
 }
 
+/// JSON types the robot tool schemas are allowed to declare
+const KNOWN_TYPES: [&str; 6] = ["string", "number", "integer", "boolean", "array", "object"];
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Collect every violation of `args` against a tool parameters schema.
+///
+/// Checks the subset of JSON Schema the robot tools use: top-level required
+/// fields, property types, and enum values. Unknown extra arguments are
+/// allowed and `null` is treated as absent, matching how the tools default
+/// optional parameters.
+fn schema_violations(schema: &Value, args: &Value) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if !args.is_object() {
+        violations.push(format!(
+            "arguments must be a JSON object, got {}",
+            type_name(args)
+        ));
+        return violations;
+    }
+
+    if let Some(required) = schema["required"].as_array() {
+        for field in required.iter().filter_map(Value::as_str) {
+            if args.get(field).is_none_or(Value::is_null) {
+                violations.push(format!("'{field}' is required"));
+            }
+        }
+    }
+
+    if let Some(properties) = schema["properties"].as_object() {
+        for (name, prop) in properties {
+            let Some(value) = args.get(name) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            if let Some(expected) = prop["type"].as_str() {
+                if !type_matches(expected, value) {
+                    violations.push(format!(
+                        "'{name}': expected {expected}, got {}",
+                        type_name(value)
+                    ));
+                    continue;
+                }
+            }
+            if let Some(allowed) = prop["enum"].as_array() {
+                if !allowed.contains(value) {
+                    violations.push(format!(
+                        "'{name}' must be one of [{}]",
+                        allowed
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+/// Startup self-check that a tool's parameters schema is well-formed.
+///
+/// Verifies the subset of JSON Schema [`Tool::validate_args`] understands:
+/// an object schema whose properties declare known types, enum values match
+/// the declared type, and required names exist under `properties`.
+pub fn check_parameters_schema(schema: &Value) -> std::result::Result<(), String> {
+    let mut problems = Vec::new();
+
+    if schema["type"].as_str() != Some("object") {
+        problems.push("top-level 'type' must be \"object\"".to_string());
+    }
+
+    let properties = schema["properties"].as_object();
+    match properties {
+        None => problems.push("'properties' must be an object".to_string()),
+        Some(props) => {
+            for (name, prop) in props {
+                match prop["type"].as_str() {
+                    None => problems.push(format!("property '{name}' is missing a 'type'")),
+                    Some(t) if !KNOWN_TYPES.contains(&t) => {
+                        problems.push(format!("property '{name}' has unknown type '{t}'"));
+                    }
+                    _ => {}
+                }
+                if let Some(allowed) = prop.get("enum") {
+                    match allowed.as_array() {
+                        None => problems.push(format!("property '{name}' has a non-array enum")),
+                        Some(values) if values.is_empty() => {
+                            problems.push(format!("property '{name}' has an empty enum"));
+                        }
+                        Some(values) => {
+                            if let Some(t) = prop["type"].as_str() {
+                                for value in values {
+                                    if !type_matches(t, value) {
+                                        problems.push(format!(
+                                            "property '{name}' enum value {value} is not a {t}"
+                                        ));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(required) = schema.get("required") {
+        match required.as_array() {
+            None => problems.push("'required' must be an array".to_string()),
+            Some(names) => {
+                for name in names {
+                    match name.as_str() {
+                        None => problems.push(format!("'required' entry {name} is not a string")),
+                        Some(name) => {
+                            if properties.is_none_or(|p| !p.contains_key(name)) {
+                                problems.push(format!(
+                                    "required field '{name}' is not declared under properties"
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
 /// Description of a tool for LLM function calling
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSpec {
@@ -107,6 +267,21 @@ pub trait Tool: Send + Sync {
     /// This is used by the LLM to understand how to call the tool.
     fn parameters_schema(&self) -> Value;
 
+    /// Validate arguments against the parameters schema.
+    ///
+    /// Call this at the top of `execute` to reject malformed calls with a
+    /// consistent, actionable message listing every violation (e.g.
+    /// "'action' must be one of [forward, backward, ...]") instead of each
+    /// tool hand-rolling its own parsing errors.
+    fn validate_args(&self, args: &Value) -> std::result::Result<(), String> {
+        let violations = schema_violations(&self.parameters_schema(), args);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid arguments: {}", violations.join("; ")))
+        }
+    }
+
     /// Execute the tool with the given arguments
     ///
     /// Arguments are passed as JSON matching the parameters_schema.
@@ -121,3 +296,104 @@ pub trait Tool: Send + Sync {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["go", "stop"],
+                    "description": "what to do"
+                },
+                "distance": { "type": "number", "description": "meters" },
+                "count": { "type": "integer", "description": "how many" },
+                "wait": { "type": "boolean", "description": "block" }
+            },
+            "required": ["action"]
+        })
+    }
+
+    #[test]
+    fn valid_args_have_no_violations() {
+        let args = json!({"action": "go", "distance": 1.5, "count": 3, "wait": true});
+        assert!(schema_violations(&schema(), &args).is_empty());
+    }
+
+    #[test]
+    fn missing_required_field_reported() {
+        let violations = schema_violations(&schema(), &json!({"distance": 1.0}));
+        assert_eq!(violations, vec!["'action' is required"]);
+    }
+
+    #[test]
+    fn every_violation_listed() {
+        let args = json!({"action": "fly", "distance": "far", "count": 1.5});
+        let violations = schema_violations(&schema(), &args);
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(violations.iter().any(|v| v.contains("one of [go, stop]")));
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("expected number, got string")));
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("expected integer, got number")));
+    }
+
+    #[test]
+    fn non_object_args_rejected() {
+        let violations = schema_violations(&schema(), &json!(["go"]));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("JSON object"));
+    }
+
+    #[test]
+    fn null_optional_treated_as_absent() {
+        let args = json!({"action": "go", "distance": null});
+        assert!(schema_violations(&schema(), &args).is_empty());
+    }
+
+    #[test]
+    fn extra_arguments_allowed() {
+        let args = json!({"action": "go", "reason": "because"});
+        assert!(schema_violations(&schema(), &args).is_empty());
+    }
+
+    #[test]
+    fn self_check_accepts_tool_schemas() {
+        assert!(check_parameters_schema(&schema()).is_ok());
+    }
+
+    #[test]
+    fn self_check_rejects_malformed_schemas() {
+        // Not an object schema
+        assert!(check_parameters_schema(&json!({"type": "array"})).is_err());
+        // Property with an unknown type
+        let e = check_parameters_schema(&json!({
+            "type": "object",
+            "properties": { "x": { "type": "float" } }
+        }))
+        .unwrap_err();
+        assert!(e.contains("unknown type 'float'"));
+        // Required field never declared
+        let e = check_parameters_schema(&json!({
+            "type": "object",
+            "properties": {},
+            "required": ["ghost"]
+        }))
+        .unwrap_err();
+        assert!(e.contains("ghost"));
+        // Enum values must match the declared type
+        let e = check_parameters_schema(&json!({
+            "type": "object",
+            "properties": { "x": { "type": "string", "enum": [1, 2] } }
+        }))
+        .unwrap_err();
+        assert!(e.contains("is not a string"));
+    }
+}
@@ -1,13 +1,26 @@
 /*
  * ZeroClaw Arduino Uno Firmware
  *
- * Listens for JSON commands on Serial (115200 baud), executes gpio_read/gpio_write,
- * responds with JSON. Compatible with ZeroClaw SerialPeripheral protocol.
+ * Listens for JSON commands on Serial (115200 baud), executes gpio_read,
+ * gpio_write, servo_write and tone/no_tone, responds with JSON. Compatible
+ * with ZeroClaw SerialPeripheral protocol.
  *
  * Protocol (newline-delimited JSON):
  *   Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
+ *             {"id":"2","cmd":"servo_write","args":{"pin":9,"angle":90}}
+ *             {"id":"3","cmd":"tone","args":{"pin":8,"frequency_hz":440,"duration_ms":500}}
  *   Response: {"id":"1","ok":true,"result":"done"}
  *
+ * Each pin is owned by whatever used it first (plain GPIO or servo);
+ * conflicting commands are rejected so a servo signal cannot be clobbered
+ * by a stray digitalWrite.
+ *
+ * Protocol v2 (optional): a host that sends protocol_hello with "crc":true
+ * switches both sides to CRC32-framed lines — every frame carries a trailing
+ * "crc" field covering the rest of the payload, and corrupted frames get a
+ * CRC-mismatch error so the host retransmits. Hosts that never send the
+ * hello keep the plain v1 framing above.
+ *
  * Arduino Uno: Pin 13 has built-in LED. Digital pins 0-13 supported.
  *
  * 1. Open in Arduino IDE
@@ -16,11 +29,82 @@
  * 4. Upload
  */
 
+#include <Servo.h>
+
 #define BAUDRATE 115200
 #define MAX_LINE 256
 
+// Firmware build version; bump when the sketch changes behavior.
+#define FW_VERSION "0.1.0"
+// Highest wire protocol this sketch speaks. The host duplicates this
+// constant (PROTOCOL_V2) and cross-checks the two in a test.
+#define PROTOCOL_VERSION 2
+
+// Pin ownership: first use wins, conflicting commands are rejected.
+#define USE_NONE 0
+#define USE_GPIO 1
+#define USE_SERVO 2
+
 char lineBuf[MAX_LINE];
 int lineLen = 0;
+char respBuf[256];
+bool crcMode = false;
+byte pinUse[14];     // zero-initialized = USE_NONE
+Servo servos[14];    // lazily attached by servo_write
+
+// CRC32 (IEEE, bit-reflected), fed incrementally. unsigned long is 32-bit on AVR.
+unsigned long crcUpdate(unsigned long crc, const char* bytes, int len) {
+  for (int i = 0; i < len; i++) {
+    crc ^= (unsigned long)(unsigned char)bytes[i];
+    for (int bit = 0; bit < 8; bit++) {
+      if (crc & 1UL) {
+        crc = (crc >> 1) ^ 0xEDB88320UL;
+      } else {
+        crc >>= 1;
+      }
+    }
+  }
+  return crc;
+}
+
+unsigned long crc32(const char* bytes, int len) {
+  return ~crcUpdate(0xFFFFFFFFUL, bytes, len);
+}
+
+// Verify and strip a trailing ,"crc":N field in place: the CRC32 of the
+// payload before it, re-closed with '}', must match the claimed value.
+// Lines without the field pass (legacy v1 hosts).
+bool checkCrc(char* line) {
+  char* field = NULL;
+  char* p = line;
+  while ((p = strstr(p, ",\"crc\":")) != NULL) {
+    field = p;  // last occurrence, so arg contents can't shadow the field
+    p++;
+  }
+  if (!field) return true;
+  unsigned long claimed = strtoul(field + 7, NULL, 10);
+  unsigned long crc = crcUpdate(0xFFFFFFFFUL, line, field - line);
+  crc = ~crcUpdate(crc, "}", 1);
+  if (crc != claimed) return false;
+  // Re-close the payload for the command parsers.
+  field[0] = '}';
+  field[1] = '\0';
+  return true;
+}
+
+// Send one response line, CRC32-framed when the host negotiated v2.
+void sendLine(const char* payload) {
+  int len = strlen(payload);
+  if (crcMode && len > 0) {
+    unsigned long crc = crc32(payload, len);
+    Serial.write((const uint8_t*)payload, len - 1);
+    Serial.print(",\"crc\":");
+    Serial.print(crc);
+    Serial.println("}");
+  } else {
+    Serial.println(payload);
+  }
+}
 
 // Parse integer from JSON: "pin":13 or "value":1
 int parseArg(const char* key, const char* json) {
@@ -55,43 +139,68 @@ bool hasCmd(const char* json, const char* cmd) {
   return strstr(json, search) != NULL;
 }
 
-void handleLine(const char* line) {
+void handleLine(char* line) {
   char idBuf[16];
   copyId(idBuf, sizeof(idBuf), line);
 
+  if (!checkCrc(line)) {
+    // The host retransmits with the same id on mismatch.
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"CRC mismatch\"}",
+             idBuf);
+    sendLine(respBuf);
+    return;
+  }
+
+  if (hasCmd(line, "protocol_hello")) {
+    crcMode = strstr(line, "\"crc\":true") != NULL;
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"{\\\"version\\\":%d,\\\"crc\\\":%s}\"}",
+             idBuf, PROTOCOL_VERSION, crcMode ? "true" : "false");
+    sendLine(respBuf);
+    return;
+  }
+
   if (hasCmd(line, "ping")) {
-    Serial.print("{\"id\":\"");
-    Serial.print(idBuf);
-    Serial.println("\",\"ok\":true,\"result\":\"pong\"}");
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"pong\"}", idBuf);
+    sendLine(respBuf);
     return;
   }
 
   // Phase C: Dynamic discovery — report GPIO pins and LED pin
   if (hasCmd(line, "capabilities")) {
-    Serial.print("{\"id\":\"");
-    Serial.print(idBuf);
-    Serial.print("\",\"ok\":true,\"result\":\"{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13}\"}");
-    Serial.println();
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13,"
+             "\\\"servo\\\":true,\\\"tone\\\":true,"
+             "\\\"fw_version\\\":\\\"" FW_VERSION "\\\",\\\"protocol_version\\\":%d}\"}",
+             idBuf, PROTOCOL_VERSION);
+    sendLine(respBuf);
     return;
   }
 
   if (hasCmd(line, "gpio_read")) {
     int pin = parseArg("pin", line);
     if (pin < 0 || pin > 13) {
-      Serial.print("{\"id\":\"");
-      Serial.print(idBuf);
-      Serial.print("\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin ");
-      Serial.print(pin);
-      Serial.println("\"}");
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin %d\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    if (pinUse[pin] == USE_SERVO) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Pin %d is driving a servo\"}",
+               idBuf, pin);
+      sendLine(respBuf);
       return;
     }
+    pinUse[pin] = USE_GPIO;
     pinMode(pin, INPUT);
     int val = digitalRead(pin);
-    Serial.print("{\"id\":\"");
-    Serial.print(idBuf);
-    Serial.print("\",\"ok\":true,\"result\":\"");
-    Serial.print(val);
-    Serial.println("\"}");
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"%d\"}", idBuf, val);
+    sendLine(respBuf);
     return;
   }
 
@@ -99,25 +208,125 @@ void handleLine(const char* line) {
     int pin = parseArg("pin", line);
     int value = parseArg("value", line);
     if (pin < 0 || pin > 13) {
-      Serial.print("{\"id\":\"");
-      Serial.print(idBuf);
-      Serial.print("\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin ");
-      Serial.print(pin);
-      Serial.println("\"}");
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin %d\"}",
+               idBuf, pin);
+      sendLine(respBuf);
       return;
     }
+    if (pinUse[pin] == USE_SERVO) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Pin %d is driving a servo\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    pinUse[pin] = USE_GPIO;
     pinMode(pin, OUTPUT);
     digitalWrite(pin, value ? HIGH : LOW);
-    Serial.print("{\"id\":\"");
-    Serial.print(idBuf);
-    Serial.println("\",\"ok\":true,\"result\":\"done\"}");
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"done\"}", idBuf);
+    sendLine(respBuf);
+    return;
+  }
+
+  if (hasCmd(line, "servo_write")) {
+    int pin = parseArg("pin", line);
+    int angle = parseArg("angle", line);
+    if (pin < 2 || pin > 13) {  // 0/1 carry the serial link
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin %d\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    if (pinUse[pin] == USE_GPIO) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Pin %d already in use as gpio\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    if (angle < 0 || angle > 180) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid angle %d (0-180)\"}",
+               idBuf, angle);
+      sendLine(respBuf);
+      return;
+    }
+    if (!servos[pin].attached()) {  // lazy attach on first use
+      servos[pin].attach(pin);
+      pinUse[pin] = USE_SERVO;
+    }
+    servos[pin].write(angle);
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"done\"}", idBuf);
+    sendLine(respBuf);
+    return;
+  }
+
+  if (hasCmd(line, "no_tone")) {
+    int pin = parseArg("pin", line);
+    if (pin < 2 || pin > 13) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin %d\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    noTone(pin);
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"done\"}", idBuf);
+    sendLine(respBuf);
+    return;
+  }
+
+  if (hasCmd(line, "tone")) {
+    int pin = parseArg("pin", line);
+    long freq = 0;
+    {
+      const char* p = strstr(line, "\"frequency_hz\":");
+      if (p) freq = atol(p + 15);
+    }
+    int duration = parseArg("duration_ms", line);
+    if (pin < 2 || pin > 13) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin %d\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    if (pinUse[pin] == USE_SERVO) {
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Pin %d is driving a servo\"}",
+               idBuf, pin);
+      sendLine(respBuf);
+      return;
+    }
+    if (freq < 31 || freq > 65535L) {  // tone() lower bound on 16 MHz AVR
+      snprintf(respBuf, sizeof(respBuf),
+               "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid frequency_hz %ld (31-65535)\"}",
+               idBuf, freq);
+      sendLine(respBuf);
+      return;
+    }
+    pinUse[pin] = USE_GPIO;
+    if (duration > 0) {
+      tone(pin, (unsigned int)freq, (unsigned long)duration);
+    } else {
+      tone(pin, (unsigned int)freq);
+    }
+    snprintf(respBuf, sizeof(respBuf),
+             "{\"id\":\"%s\",\"ok\":true,\"result\":\"done\"}", idBuf);
+    sendLine(respBuf);
     return;
   }
 
   // Unknown command
-  Serial.print("{\"id\":\"");
-  Serial.print(idBuf);
-  Serial.println("\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}");
+  snprintf(respBuf, sizeof(respBuf),
+           "{\"id\":\"%s\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}",
+           idBuf);
+  sendLine(respBuf);
 }
 
 void setup() {
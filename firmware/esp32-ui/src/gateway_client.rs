@@ -0,0 +1,287 @@
+//! Gateway client — WebSocket event stream plus HTTP control requests.
+//!
+//! Runs on its own thread and talks to the UI exclusively through
+//! channels ([`GatewayCommand`] in, [`GatewayEvent`] out), so the network
+//! layer can be exercised without a display and the UI without a network.
+//! Reconnects with a fixed backoff whenever the socket drops; the paired
+//! gateway token is read from NVS (namespace `zeroclaw`, key
+//! `paired_token`), where the provisioning flow stores it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use embedded_svc::http::client::Client as HttpClient;
+use esp_idf_svc::http::client::{Configuration as HttpConfiguration, EspHttpConnection};
+use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs};
+use esp_idf_svc::ws::client::{
+    EspWebSocketClient, EspWebSocketClientConfig, WebSocketEvent, WebSocketEventType,
+};
+use log::{info, warn};
+
+/// How long to wait before re-dialing a dropped gateway connection.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(3);
+
+/// Truncation length for the message previews shown on the panel.
+const PREVIEW_CHARS: usize = 96;
+
+/// What the network layer tells the UI.
+pub enum GatewayEvent {
+    Connected,
+    Disconnected,
+    /// Provider/model reported by GET /api/status
+    Status { provider: String, model: String },
+    /// Last message that went into the agent (user side)
+    Inbound(String),
+    /// Last response that came out of the agent
+    Outbound(String),
+    /// E-stop state as confirmed by the gateway
+    EstopEngaged(bool),
+    Error(String),
+}
+
+/// What the UI asks the network layer to do.
+pub enum GatewayCommand {
+    EngageEstop,
+    ResumeEstop,
+}
+
+pub struct GatewayConfig {
+    /// e.g. "http://192.168.1.50:3000" — ws:// is derived from this
+    pub base_url: String,
+    /// Paired gateway token (Bearer / ?token=)
+    pub token: String,
+}
+
+/// Read the paired gateway token the provisioning flow stored in NVS.
+pub fn load_paired_token(partition: EspDefaultNvsPartition) -> Option<String> {
+    let nvs = EspNvs::new(partition, "zeroclaw", false).ok()?;
+    let mut buf = [0u8; 128];
+    match nvs.get_str("paired_token", &mut buf) {
+        Ok(Some(token)) if !token.is_empty() => Some(token.to_string()),
+        _ => None,
+    }
+}
+
+/// Spawn the client thread; returns the UI-facing channel endpoints.
+pub fn spawn(config: GatewayConfig) -> (Sender<GatewayCommand>, Receiver<GatewayEvent>) {
+    let (cmd_tx, cmd_rx) = channel();
+    let (event_tx, event_rx) = channel();
+    std::thread::Builder::new()
+        .name("gateway".into())
+        .stack_size(16 * 1024)
+        .spawn(move || run(&config, &cmd_rx, &event_tx))
+        .expect("failed to spawn gateway client thread");
+    (cmd_tx, event_rx)
+}
+
+fn run(config: &GatewayConfig, cmd_rx: &Receiver<GatewayCommand>, event_tx: &Sender<GatewayEvent>) {
+    loop {
+        if let Err(e) = connect_and_serve(config, cmd_rx, event_tx) {
+            warn!("gateway connection failed: {e:#}");
+            let _ = event_tx.send(GatewayEvent::Error(format!("{e:#}")));
+        }
+        let _ = event_tx.send(GatewayEvent::Disconnected);
+        std::thread::sleep(RECONNECT_BACKOFF);
+    }
+}
+
+/// One connection lifetime: status snapshot, WebSocket stream, command
+/// pump. Returns when the socket closes so the outer loop can re-dial.
+fn connect_and_serve(
+    config: &GatewayConfig,
+    cmd_rx: &Receiver<GatewayCommand>,
+    event_tx: &Sender<GatewayEvent>,
+) -> Result<()> {
+    // Snapshot provider/model and e-stop state over plain HTTP first —
+    // cheap, and it validates the token before we hold a socket open.
+    if let Ok(status) = http_get_json(config, "/api/status") {
+        let _ = event_tx.send(GatewayEvent::Status {
+            provider: status["provider"].as_str().unwrap_or("?").to_string(),
+            model: status["model"].as_str().unwrap_or("?").to_string(),
+        });
+    }
+    if let Ok(estop) = http_get_json(config, "/api/estop") {
+        let engaged = estop["state"]["kill_all"].as_bool().unwrap_or(false);
+        let _ = event_tx.send(GatewayEvent::EstopEngaged(engaged));
+    }
+
+    let ws_url = format!(
+        "{}/ws/chat?token={}&name=esp32-panel",
+        config.base_url.replacen("http", "ws", 1),
+        config.token
+    );
+    let closed = Arc::new(AtomicBool::new(false));
+    let cb_closed = closed.clone();
+    let cb_events = event_tx.clone();
+    let _ws = EspWebSocketClient::new(
+        &ws_url,
+        &EspWebSocketClientConfig::default(),
+        Duration::from_secs(10),
+        move |event: &Result<WebSocketEvent, esp_idf_svc::sys::EspError>| match event {
+            Ok(ev) => match &ev.event_type {
+                WebSocketEventType::Connected => {
+                    let _ = cb_events.send(GatewayEvent::Connected);
+                }
+                WebSocketEventType::Text(text) => {
+                    if let Some(parsed) = parse_ws_event(text) {
+                        let _ = cb_events.send(parsed);
+                    }
+                }
+                WebSocketEventType::Closed | WebSocketEventType::Disconnected => {
+                    cb_closed.store(true, Ordering::Relaxed);
+                }
+                _ => {}
+            },
+            Err(_) => cb_closed.store(true, Ordering::Relaxed),
+        },
+    )
+    .context("WebSocket connect failed")?;
+
+    info!("gateway WebSocket open: {ws_url}");
+
+    // Pump UI commands while the socket lives; wake up periodically to
+    // notice a dropped socket even when the panel is idle.
+    while !closed.load(Ordering::Relaxed) {
+        match cmd_rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(GatewayCommand::EngageEstop) => {
+                let outcome = http_post_json(
+                    config,
+                    "/api/estop",
+                    r#"{"action":"engage","level":"kill-all"}"#,
+                );
+                match outcome {
+                    Ok(_) => {
+                        let _ = event_tx.send(GatewayEvent::EstopEngaged(true));
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(GatewayEvent::Error(format!("estop failed: {e:#}")));
+                    }
+                }
+            }
+            Ok(GatewayCommand::ResumeEstop) => {
+                let outcome = http_post_json(config, "/api/estop", r#"{"action":"resume"}"#);
+                match outcome {
+                    Ok(_) => {
+                        let _ = event_tx.send(GatewayEvent::EstopEngaged(false));
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(GatewayEvent::Error(format!("resume failed: {e:#}")));
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("UI side hung up"),
+        }
+    }
+    Ok(())
+}
+
+/// Map one WebSocket frame from the chat stream onto a panel event.
+/// Pure so it can be unit-tested off-device.
+fn parse_ws_event(text: &str) -> Option<GatewayEvent> {
+    let parsed: serde_json::Value = serde_json::from_str(text).ok()?;
+    match parsed["type"].as_str()? {
+        "session_start" | "connected" => Some(GatewayEvent::Connected),
+        "message" | "agent_start" => Some(GatewayEvent::Inbound(preview(
+            parsed["content"]
+                .as_str()
+                .or_else(|| parsed["preview"].as_str())
+                .unwrap_or(""),
+        ))),
+        "done" => Some(GatewayEvent::Outbound(preview(
+            parsed["full_response"].as_str().unwrap_or(""),
+        ))),
+        "error" => Some(GatewayEvent::Error(preview(
+            parsed["message"].as_str().unwrap_or("gateway error"),
+        ))),
+        _ => None,
+    }
+}
+
+/// Clip a message to one panel line worth of characters.
+fn preview(s: &str) -> String {
+    let trimmed = s.trim().replace('\n', " ");
+    if trimmed.chars().count() <= PREVIEW_CHARS {
+        trimmed
+    } else {
+        let clipped: String = trimmed.chars().take(PREVIEW_CHARS).collect();
+        format!("{clipped}…")
+    }
+}
+
+fn http_get_json(config: &GatewayConfig, path: &str) -> Result<serde_json::Value> {
+    let mut client = HttpClient::wrap(EspHttpConnection::new(&HttpConfiguration::default())?);
+    let url = format!("{}{path}", config.base_url);
+    let auth = format!("Bearer {}", config.token);
+    let headers = [("Authorization", auth.as_str())];
+    let request = client.request(embedded_svc::http::Method::Get, &url, &headers)?;
+    let mut response = request.submit()?;
+    let mut body = Vec::new();
+    let mut buf = [0u8; 512];
+    loop {
+        let n = response.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&buf[..n]);
+    }
+    anyhow::ensure!(
+        response.status() < 400,
+        "GET {path} returned {}",
+        response.status()
+    );
+    serde_json::from_slice(&body).with_context(|| format!("invalid JSON from {path}"))
+}
+
+fn http_post_json(config: &GatewayConfig, path: &str, body: &str) -> Result<u16> {
+    let mut client = HttpClient::wrap(EspHttpConnection::new(&HttpConfiguration::default())?);
+    let url = format!("{}{path}", config.base_url);
+    let auth = format!("Bearer {}", config.token);
+    let headers = [
+        ("Authorization", auth.as_str()),
+        ("Content-Type", "application/json"),
+    ];
+    let mut request = client.post(&url, &headers)?;
+    request.write_all(body.as_bytes())?;
+    let response = request.submit()?;
+    let status = response.status();
+    anyhow::ensure!(status < 400, "POST {path} returned {status}");
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn done_frame_becomes_outbound_preview() {
+        let ev = parse_ws_event(r#"{"type":"done","full_response":"All set."}"#);
+        assert!(matches!(ev, Some(GatewayEvent::Outbound(s)) if s == "All set."));
+    }
+
+    #[test]
+    fn long_messages_are_clipped_for_the_panel() {
+        let long = "x".repeat(300);
+        let frame = format!(r#"{{"type":"done","full_response":"{long}"}}"#);
+        let Some(GatewayEvent::Outbound(s)) = parse_ws_event(&frame) else {
+            panic!("expected outbound event");
+        };
+        assert!(s.chars().count() <= PREVIEW_CHARS + 1);
+        assert!(s.ends_with('…'));
+    }
+
+    #[test]
+    fn chunk_frames_are_ignored() {
+        assert!(parse_ws_event(r#"{"type":"chunk","content":"partial"}"#).is_none());
+        assert!(parse_ws_event("not json").is_none());
+    }
+
+    #[test]
+    fn error_frames_surface_to_the_panel() {
+        let ev = parse_ws_event(r#"{"type":"error","message":"provider down"}"#);
+        assert!(matches!(ev, Some(GatewayEvent::Error(s)) if s == "provider down"));
+    }
+}
@@ -1,21 +1,163 @@
-//! ZeroClaw ESP32 UI firmware scaffold.
+//! ZeroClaw ESP32 UI firmware — live agent status panel.
 //!
-//! This binary initializes ESP-IDF, boots a minimal Slint UI, and keeps
-//! architecture boundaries explicit so hardware integrations can be added
-//! incrementally.
+//! Boots a Slint screen showing gateway connection state, the active
+//! provider/model, the latest inbound/outbound message previews and a
+//! touch E-stop button (tap to engage, long-press to resume). All
+//! network traffic lives in [`gateway_client`] on its own thread; the UI
+//! and the client only talk through channels, so either side can be
+//! tested in isolation.
+//!
+//! Configuration: the gateway base URL is compiled in via the
+//! `ZEROCLAW_GATEWAY_URL` env var (falls back to a LAN default); the
+//! paired token comes from NVS (`zeroclaw/paired_token`), written during
+//! provisioning.
+
+mod gateway_client;
+
+use std::time::Duration;
 
 use anyhow::Context;
-use log::info;
+use esp_idf_svc::eventloop::EspSystemEventLoop;
+use esp_idf_svc::hal::modem::Modem;
+use esp_idf_svc::hal::peripherals::Peripherals;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use esp_idf_svc::wifi::{AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi};
+use log::{info, warn};
+
+use gateway_client::{GatewayCommand, GatewayConfig, GatewayEvent};
 
 slint::include_modules!();
 
+/// WiFi credentials baked in at build time, matching the peripheral firmware.
+const WIFI_SSID: Option<&str> = option_env!("ZEROCLAW_WIFI_SSID");
+const WIFI_PASS: Option<&str> = option_env!("ZEROCLAW_WIFI_PASS");
+
+/// Compile-time override: ZEROCLAW_GATEWAY_URL=http://host:port
+const DEFAULT_GATEWAY_URL: &str = match option_env!("ZEROCLAW_GATEWAY_URL") {
+    Some(url) => url,
+    None => "http://192.168.1.1:3000",
+};
+
+fn start_wifi(
+    modem: Modem,
+    nvs: EspDefaultNvsPartition,
+    ssid: &str,
+    pass: &str,
+) -> anyhow::Result<BlockingWifi<EspWifi<'static>>> {
+    let sys_loop = EspSystemEventLoop::take()?;
+    let mut wifi = BlockingWifi::wrap(EspWifi::new(modem, sys_loop.clone(), Some(nvs))?, sys_loop)?;
+    wifi.set_configuration(&Configuration::Client(ClientConfiguration {
+        ssid: ssid
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("WiFi SSID too long"))?,
+        password: pass
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("WiFi password too long"))?,
+        auth_method: if pass.is_empty() {
+            AuthMethod::None
+        } else {
+            AuthMethod::WPA2Personal
+        },
+        ..Default::default()
+    }))?;
+    wifi.start()?;
+    wifi.connect()?;
+    wifi.wait_netif_up()?;
+    info!(
+        "WiFi connected: {:?}",
+        wifi.wifi().sta_netif().get_ip_info()?
+    );
+    Ok(wifi)
+}
+
 fn main() -> anyhow::Result<()> {
     esp_idf_svc::sys::link_patches();
     esp_idf_svc::log::EspLogger::initialize_default();
 
-    info!("Starting ZeroClaw ESP32 UI scaffold");
+    info!("Starting ZeroClaw ESP32 UI");
+
+    let peripherals = Peripherals::take()?;
+    let nvs = EspDefaultNvsPartition::take().context("failed to take NVS partition")?;
+
+    let _wifi = match WIFI_SSID {
+        Some(ssid) => Some(start_wifi(
+            peripherals.modem,
+            nvs.clone(),
+            ssid,
+            WIFI_PASS.unwrap_or(""),
+        )?),
+        None => {
+            warn!("ZEROCLAW_WIFI_SSID not set at build time; panel will stay offline");
+            None
+        }
+    };
+
+    let token = gateway_client::load_paired_token(nvs).unwrap_or_else(|| {
+        warn!("no paired token in NVS (zeroclaw/paired_token); gateway requests will be rejected until provisioned");
+        String::new()
+    });
 
     let window = MainWindow::new().context("failed to create MainWindow")?;
+
+    let (cmd_tx, event_rx) = gateway_client::spawn(GatewayConfig {
+        base_url: DEFAULT_GATEWAY_URL.trim_end_matches('/').to_string(),
+        token,
+    });
+
+    // UI -> network: the E-stop callbacks just enqueue commands; the
+    // gateway client thread does the HTTP work and reports back.
+    {
+        let tx = cmd_tx.clone();
+        window.on_engage_estop(move || {
+            let _ = tx.send(GatewayCommand::EngageEstop);
+        });
+        let tx = cmd_tx.clone();
+        window.on_resume_estop(move || {
+            let _ = tx.send(GatewayCommand::ResumeEstop);
+        });
+    }
+
+    // Network -> UI: drain the event channel from a UI-thread timer so
+    // property updates never cross threads.
+    let weak = window.as_weak();
+    let poll_timer = slint::Timer::default();
+    poll_timer.start(
+        slint::TimerMode::Repeated,
+        Duration::from_millis(200),
+        move || {
+            let Some(window) = weak.upgrade() else {
+                return;
+            };
+            while let Ok(event) = event_rx.try_recv() {
+                match event {
+                    GatewayEvent::Connected => {
+                        window.set_connected(true);
+                        window.set_connection_status("connected".into());
+                    }
+                    GatewayEvent::Disconnected => {
+                        window.set_connected(false);
+                        window.set_connection_status("reconnecting…".into());
+                    }
+                    GatewayEvent::Status { provider, model } => {
+                        window.set_provider_model(format!("{provider} / {model}").into());
+                    }
+                    GatewayEvent::Inbound(text) => {
+                        window.set_last_inbound(text.into());
+                    }
+                    GatewayEvent::Outbound(text) => {
+                        window.set_last_outbound(text.into());
+                    }
+                    GatewayEvent::EstopEngaged(engaged) => {
+                        window.set_estop_engaged(engaged);
+                    }
+                    GatewayEvent::Error(message) => {
+                        window.set_connection_status(message.into());
+                    }
+                }
+            }
+        },
+    );
+
     window.run().context("MainWindow event loop failed")?;
 
     Ok(())
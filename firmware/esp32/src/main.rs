@@ -1,16 +1,76 @@
 //! ZeroClaw ESP32 firmware — JSON-over-serial peripheral.
 //!
-//! Listens for newline-delimited JSON commands on UART0, executes gpio_read/gpio_write,
-//! responds with JSON. Compatible with host ZeroClaw SerialPeripheral protocol.
+//! Listens for newline-delimited JSON commands on UART0, executes
+//! gpio_mode/gpio_read/gpio_write, responds with JSON. Compatible with host
+//! ZeroClaw SerialPeripheral protocol.
+//!
+//! Pins start out unconfigured; send gpio_mode to pick a direction:
+//!   {"id":"1","cmd":"gpio_mode","args":{"pin":4,"mode":"input_pullup"}}
+//! gpio_read only samples input-configured pins and gpio_write only drives
+//! output-configured pins — anything else is a descriptive error. Pins 2
+//! (LED) and 13 are configured as outputs at boot for compatibility with
+//! older hosts.
+//!
+//! WiFi/TCP transport (optional): bake credentials at build time —
+//!   ZEROCLAW_WIFI_SSID=mynet ZEROCLAW_WIFI_PASS=secret cargo build --release
+//! and the firmware joins the network and listens on TCP port 3333,
+//! serving the same protocol as UART0. Both transports are active at the
+//! same time; each response goes back on the transport its request came
+//! from, and a dropped TCP client never stalls the loop. On the host:
+//!   zeroclaw peripheral add esp32 tcp://<device-ip>:3333
+//!
+//! Deep sleep (battery nodes): a `sleep` command with `duration_ms` and an
+//! optional RTC-capable `wake_gpio` is acknowledged first, then the chip
+//! enters deep sleep once the ack has drained. Waking reboots the firmware,
+//! which emits one unsolicited line so the host knows it is back:
+//!   {"event":"wake","reason":"timer"}
+//! Reasons: "timer" (duration elapsed), "gpio" (wake_gpio went high),
+//! "other" (any other esp-idf wakeup cause). Cold boots emit nothing.
+//!
+//! Protocol v2 (optional): a host that sends `protocol_hello` with
+//! `"crc":true` switches its transport to CRC32-framed lines — every frame
+//! carries a trailing `"crc"` field covering the rest of the payload, and
+//! corrupted frames get a CRC-mismatch error so the host retransmits. The
+//! mode is per transport (each TCP client and the UART negotiate
+//! independently); hosts that never send the hello keep plain v1 framing.
 //!
 //! Protocol: same as STM32 — see docs/hardware-peripherals-design.md
 
-use esp_idf_svc::hal::gpio::PinDriver;
+use esp_idf_svc::eventloop::EspSystemEventLoop;
+use esp_idf_svc::hal::gpio::{AnyIOPin, IOPin, Input, Output, PinDriver, Pull};
+use esp_idf_svc::hal::modem::Modem;
 use esp_idf_svc::hal::peripherals::Peripherals;
 use esp_idf_svc::hal::uart::{UartConfig, UartDriver};
 use esp_idf_svc::hal::units::Hertz;
-use log::info;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
+use esp_idf_svc::wifi::{AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi};
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+/// WiFi credentials baked in at build time; leave unset for UART-only.
+const WIFI_SSID: Option<&str> = option_env!("ZEROCLAW_WIFI_SSID");
+const WIFI_PASS: Option<&str> = option_env!("ZEROCLAW_WIFI_PASS");
+
+/// TCP port for the WiFi transport.
+const TCP_PORT: u16 = 3333;
+
+/// Most sub-commands accepted in one batch.
+const MAX_BATCH: usize = 16;
+
+/// GPIOs that can wake the chip from deep sleep (RTC domain, classic ESP32).
+const RTC_WAKE_PINS: [i32; 18] = [
+    0, 2, 4, 12, 13, 14, 15, 25, 26, 27, 32, 33, 34, 35, 36, 37, 38, 39,
+];
+
+/// Firmware build version, injected by Cargo at build time.
+const FW_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Highest wire protocol this firmware speaks. The host duplicates this
+/// constant (`PROTOCOL_V2`) and cross-checks the two in a test.
+const PROTOCOL_VERSION: u8 = 2;
 
 /// Incoming command from host.
 #[derive(Debug, Deserialize)]
@@ -30,6 +90,370 @@ struct Response {
     error: Option<String>,
 }
 
+/// A validated `sleep` command, parked until the acknowledgement has been
+/// written on the transport it arrived on — deep sleep never returns, so
+/// `main` executes it only at the end of a loop iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SleepRequest {
+    duration_ms: u64,
+    wake_gpio: Option<i32>,
+}
+
+static PENDING_SLEEP: std::sync::Mutex<Option<SleepRequest>> = std::sync::Mutex::new(None);
+
+/// Map the esp-idf wakeup cause onto the wire-protocol wake reasons
+/// (see module docs). `None` for a cold boot: the host never saw us
+/// leave, so there is nothing to announce.
+fn wake_reason() -> Option<&'static str> {
+    use esp_idf_svc::sys;
+    match unsafe { sys::esp_sleep_get_wakeup_cause() } {
+        sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_TIMER => Some("timer"),
+        sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_EXT0 => Some("gpio"),
+        sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_UNDEFINED => None,
+        _ => Some("other"),
+    }
+}
+
+/// Arm the wake sources and enter deep sleep. The ack must already be on
+/// the wire: this resets the chip, and the next thing the host hears is
+/// the wake event after reboot.
+fn enter_deep_sleep(uart: &UartDriver, req: &SleepRequest) {
+    info!(
+        "entering deep sleep for {} ms (wake_gpio: {:?})",
+        req.duration_ms, req.wake_gpio
+    );
+    let _ = uart.wait_tx_done(100);
+    unsafe {
+        esp_idf_svc::sys::esp_sleep_enable_timer_wakeup(req.duration_ms * 1000);
+        if let Some(pin) = req.wake_gpio {
+            // ext0: wake when the pin goes high
+            esp_idf_svc::sys::esp_sleep_enable_ext0_wakeup(pin, 1);
+        }
+        esp_idf_svc::sys::esp_deep_sleep_start();
+    }
+}
+
+/// Configured direction of a pin, as named in the serial protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PinMode {
+    Input,
+    InputPullup,
+    Output,
+}
+
+impl PinMode {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "input" => Some(Self::Input),
+            "input_pullup" => Some(Self::InputPullup),
+            "output" => Some(Self::Output),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::InputPullup => "input_pullup",
+            Self::Output => "output",
+        }
+    }
+}
+
+/// CRC32 (IEEE, bit-reflected) over a frame payload.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Append a `,"crc":N` field (CRC32 of the payload without it) before the
+/// final `}` of a serialized response.
+fn add_crc(payload: &str) -> String {
+    let crc = crc32(payload.as_bytes());
+    format!("{},\"crc\":{}}}", &payload[..payload.len() - 1], crc)
+}
+
+/// Split off a trailing `,"crc":N` field, verify it against the re-closed
+/// payload, and return the payload. Lines without the field pass through
+/// (legacy v1 hosts).
+fn check_crc(line: &str) -> anyhow::Result<String> {
+    let Some(idx) = line.rfind(",\"crc\":") else {
+        return Ok(line.to_string());
+    };
+    let digits = line
+        .strip_suffix('}')
+        .map(|rest| &rest[idx + 7..])
+        .ok_or_else(|| anyhow::anyhow!("Malformed crc frame"))?;
+    let claimed: u32 = digits
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Malformed crc field"))?;
+    let payload = format!("{}}}", &line[..idx]);
+    if crc32(payload.as_bytes()) != claimed {
+        anyhow::bail!("CRC mismatch");
+    }
+    Ok(payload)
+}
+
+/// Best-effort id extraction for frames that fail the CRC check, so the
+/// host can correlate the error with its pending request.
+fn extract_id(line: &str) -> String {
+    line.find("\"id\":\"")
+        .map(|i| &line[i + 6..])
+        .and_then(|rest| rest.split('"').next())
+        .unwrap_or("0")
+        .to_string()
+}
+
+/// Pure pin -> mode map; the driver-holding registry mirrors it.
+/// Kept separate so the logic is testable off-target.
+#[derive(Debug, Default)]
+struct ModeTable {
+    modes: BTreeMap<i32, PinMode>,
+}
+
+impl ModeTable {
+    fn set(&mut self, pin: i32, mode: PinMode) {
+        self.modes.insert(pin, mode);
+    }
+
+    fn get(&self, pin: i32) -> Option<PinMode> {
+        self.modes.get(&pin).copied()
+    }
+
+    /// Mode map for the capabilities response: {"2":"output",...}
+    fn to_json(&self) -> serde_json::Value {
+        let map: serde_json::Map<String, serde_json::Value> = self
+            .modes
+            .iter()
+            .map(|(pin, mode)| (pin.to_string(), mode.as_str().into()))
+            .collect();
+        serde_json::Value::Object(map)
+    }
+}
+
+/// One registered pin: either an idle AnyIOPin or a configured driver.
+enum PinSlot {
+    Unconfigured(AnyIOPin),
+    Input(PinDriver<'static, AnyIOPin, Input>),
+    Output(PinDriver<'static, AnyIOPin, Output>),
+    /// Transient state while set_mode converts the driver.
+    Moving,
+}
+
+/// Runtime pin registry: owns the drivers, tracks modes.
+struct GpioRegistry {
+    slots: BTreeMap<i32, PinSlot>,
+    table: ModeTable,
+}
+
+impl GpioRegistry {
+    fn new() -> Self {
+        Self {
+            slots: BTreeMap::new(),
+            table: ModeTable::default(),
+        }
+    }
+
+    fn register(&mut self, pin: i32, io: AnyIOPin) {
+        self.slots.insert(pin, PinSlot::Unconfigured(io));
+    }
+
+    fn pins(&self) -> Vec<i32> {
+        self.slots.keys().copied().collect()
+    }
+
+    fn set_mode(&mut self, pin: i32, mode: PinMode) -> anyhow::Result<()> {
+        let slot = self
+            .slots
+            .get_mut(&pin)
+            .ok_or_else(|| anyhow::anyhow!("Pin {} not available on this board", pin))?;
+
+        let pull = if mode == PinMode::InputPullup {
+            Pull::Up
+        } else {
+            Pull::Floating
+        };
+
+        *slot = match (std::mem::replace(slot, PinSlot::Moving), mode) {
+            (PinSlot::Unconfigured(io), PinMode::Output) => {
+                PinSlot::Output(PinDriver::output(io)?)
+            }
+            (PinSlot::Unconfigured(io), _) => {
+                let mut driver = PinDriver::input(io)?;
+                driver.set_pull(pull)?;
+                PinSlot::Input(driver)
+            }
+            (PinSlot::Input(driver), PinMode::Output) => PinSlot::Output(driver.into_output()?),
+            (PinSlot::Input(mut driver), _) => {
+                driver.set_pull(pull)?;
+                PinSlot::Input(driver)
+            }
+            (PinSlot::Output(driver), PinMode::Output) => PinSlot::Output(driver),
+            (PinSlot::Output(driver), _) => {
+                let mut driver = driver.into_input()?;
+                driver.set_pull(pull)?;
+                PinSlot::Input(driver)
+            }
+            (PinSlot::Moving, _) => anyhow::bail!("Pin {} is busy", pin),
+        };
+        self.table.set(pin, mode);
+        Ok(())
+    }
+
+    fn read(&mut self, pin: i32) -> anyhow::Result<u8> {
+        match self.slots.get(&pin) {
+            Some(PinSlot::Input(driver)) => Ok(driver.is_high() as u8),
+            Some(PinSlot::Output(_)) => anyhow::bail!(
+                "Pin {} is configured as output; send gpio_mode with \"input\" first",
+                pin
+            ),
+            Some(_) => anyhow::bail!(
+                "Pin {} is not configured; send gpio_mode with \"input\" first",
+                pin
+            ),
+            None => anyhow::bail!("Pin {} not available on this board", pin),
+        }
+    }
+
+    fn write(&mut self, pin: i32, value: u64) -> anyhow::Result<()> {
+        match self.slots.get_mut(&pin) {
+            Some(PinSlot::Output(driver)) => {
+                let level = esp_idf_svc::hal::gpio::Level::from(value != 0);
+                driver.set_level(level)?;
+                Ok(())
+            }
+            Some(PinSlot::Input(_)) => anyhow::bail!(
+                "Pin {} is configured as input; send gpio_mode with \"output\" first",
+                pin
+            ),
+            Some(_) => anyhow::bail!(
+                "Pin {} is not configured; send gpio_mode with \"output\" first",
+                pin
+            ),
+            None => anyhow::bail!("Pin {} not available on this board", pin),
+        }
+    }
+}
+
+/// Join the configured network; blocks until the interface is up.
+fn start_wifi(modem: Modem, ssid: &str, pass: &str) -> anyhow::Result<BlockingWifi<EspWifi<'static>>> {
+    let sys_loop = EspSystemEventLoop::take()?;
+    let nvs = EspDefaultNvsPartition::take()?;
+    let mut wifi = BlockingWifi::wrap(EspWifi::new(modem, sys_loop.clone(), Some(nvs))?, sys_loop)?;
+    wifi.set_configuration(&Configuration::Client(ClientConfiguration {
+        ssid: ssid
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("WiFi SSID too long"))?,
+        password: pass
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("WiFi password too long"))?,
+        auth_method: if pass.is_empty() {
+            AuthMethod::None
+        } else {
+            AuthMethod::WPA2Personal
+        },
+        ..Default::default()
+    }))?;
+    wifi.start()?;
+    wifi.connect()?;
+    wifi.wait_netif_up()?;
+    info!(
+        "WiFi connected: {:?}",
+        wifi.wifi().sta_netif().get_ip_info()?
+    );
+    Ok(wifi)
+}
+
+/// One connected TCP client with its own line buffer, so a slow or
+/// half-closed client cannot corrupt another client's framing.
+struct TcpClient {
+    stream: TcpStream,
+    line: Vec<u8>,
+    /// CRC32 framing, negotiated per connection via protocol_hello.
+    crc: bool,
+}
+
+/// Accept any pending TCP connections (non-blocking).
+fn accept_tcp(listener: &Option<TcpListener>, clients: &mut Vec<TcpClient>) {
+    let Some(listener) = listener else { return };
+    loop {
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                if stream.set_nonblocking(true).is_err() {
+                    continue;
+                }
+                info!("TCP client connected: {}", addr);
+                clients.push(TcpClient {
+                    stream,
+                    line: Vec::new(),
+                    crc: false,
+                });
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+            Err(_) => return,
+        }
+    }
+}
+
+/// Read pending bytes from every TCP client, answering complete lines on
+/// the connection they arrived on. Dropped clients are pruned; the loop
+/// (and the UART transport) keeps running regardless.
+fn service_tcp(clients: &mut Vec<TcpClient>, registry: &mut GpioRegistry) {
+    let mut i = 0;
+    while i < clients.len() {
+        if service_client(&mut clients[i], registry) {
+            i += 1;
+        } else {
+            clients.remove(i);
+        }
+    }
+}
+
+/// Returns false when the client is gone and should be dropped.
+fn service_client(client: &mut TcpClient, registry: &mut GpioRegistry) -> bool {
+    let mut buf = [0u8; 256];
+    loop {
+        match client.stream.read(&mut buf) {
+            Ok(0) => return false,
+            Ok(n) => {
+                for &b in &buf[..n] {
+                    if b == b'\n' {
+                        if !client.line.is_empty() {
+                            if let Ok(line_str) = std::str::from_utf8(&client.line) {
+                                if let Some(out) = handle_line(line_str, registry, &mut client.crc)
+                                {
+                                    if client
+                                        .stream
+                                        .write_all(format!("{}\n", out).as_bytes())
+                                        .is_err()
+                                    {
+                                        return false;
+                                    }
+                                }
+                            }
+                            client.line.clear();
+                        }
+                    } else {
+                        client.line.push(b);
+                        if client.line.len() > 400 {
+                            client.line.clear();
+                        }
+                    }
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return true,
+            Err(_) => return false,
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     esp_idf_svc::sys::link_patches();
     esp_idf_svc::log::EspLogger::initialize_default();
@@ -37,9 +461,26 @@ fn main() -> anyhow::Result<()> {
     let peripherals = Peripherals::take()?;
     let pins = peripherals.pins;
 
-    // Create GPIO output drivers first (they take ownership of pins)
-    let mut gpio2 = PinDriver::output(pins.gpio2)?;
-    let mut gpio13 = PinDriver::output(pins.gpio13)?;
+    let mut registry = GpioRegistry::new();
+    registry.register(0, pins.gpio0.downgrade());
+    registry.register(1, pins.gpio1.downgrade());
+    registry.register(2, pins.gpio2.downgrade());
+    registry.register(3, pins.gpio3.downgrade());
+    registry.register(4, pins.gpio4.downgrade());
+    registry.register(5, pins.gpio5.downgrade());
+    registry.register(12, pins.gpio12.downgrade());
+    registry.register(13, pins.gpio13.downgrade());
+    registry.register(14, pins.gpio14.downgrade());
+    registry.register(15, pins.gpio15.downgrade());
+    registry.register(16, pins.gpio16.downgrade());
+    registry.register(17, pins.gpio17.downgrade());
+    registry.register(18, pins.gpio18.downgrade());
+    registry.register(19, pins.gpio19.downgrade());
+
+    // Outputs at boot for compatibility with hosts that never send gpio_mode
+    // (gpio2 is the on-board LED on most dev boards).
+    registry.set_mode(2, PinMode::Output)?;
+    registry.set_mode(13, PinMode::Output)?;
 
     // UART0: TX=21, RX=20 (ESP32) — ESP32-C3 may use different pins; adjust for your board
     let config = UartConfig::new().baudrate(Hertz(115_200));
@@ -52,12 +493,53 @@ fn main() -> anyhow::Result<()> {
         &config,
     )?;
 
+    // Optional WiFi/TCP transport alongside UART0
+    let _wifi = match WIFI_SSID {
+        Some(ssid) if !ssid.is_empty() => {
+            match start_wifi(peripherals.modem, ssid, WIFI_PASS.unwrap_or("")) {
+                Ok(wifi) => Some(wifi),
+                Err(e) => {
+                    warn!("WiFi startup failed ({}); continuing UART-only", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let listener = if _wifi.is_some() {
+        match TcpListener::bind(("0.0.0.0", TCP_PORT)) {
+            Ok(listener) => {
+                listener.set_nonblocking(true)?;
+                info!("TCP transport listening on port {}", TCP_PORT);
+                Some(listener)
+            }
+            Err(e) => {
+                warn!("TCP bind failed ({}); continuing UART-only", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let mut clients: Vec<TcpClient> = Vec::new();
+
     info!("ZeroClaw ESP32 firmware ready on UART0 (115200)");
 
+    // Back from deep sleep: one unsolicited line so the host knows. Sent
+    // unframed — the reboot cleared any negotiated CRC mode anyway.
+    if let Some(reason) = wake_reason() {
+        info!("woke from deep sleep: {}", reason);
+        let _ = uart.write(format!("{{\"event\":\"wake\",\"reason\":\"{}\"}}\n", reason).as_bytes());
+    }
+
     let mut buf = [0u8; 512];
     let mut line = Vec::new();
+    let mut uart_crc = false;
 
     loop {
+        accept_tcp(&listener, &mut clients);
+        service_tcp(&mut clients, &mut registry);
+
         match uart.read(&mut buf, 100) {
             Ok(0) => continue,
             Ok(n) => {
@@ -65,9 +547,9 @@ fn main() -> anyhow::Result<()> {
                     if b == b'\n' {
                         if !line.is_empty() {
                             if let Ok(line_str) = std::str::from_utf8(&line) {
-                                if let Ok(resp) = handle_request(line_str, &mut gpio2, &mut gpio13)
+                                if let Some(out) =
+                                    handle_line(line_str, &mut registry, &mut uart_crc)
                                 {
-                                    let out = serde_json::to_string(&resp).unwrap_or_default();
                                     let _ = uart.write(format!("{}\n", out).as_bytes());
                                 }
                             }
@@ -83,39 +565,157 @@ fn main() -> anyhow::Result<()> {
             }
             Err(_) => {}
         }
+
+        // A sleep command was acknowledged earlier in this iteration; the
+        // ack is on the wire (or draining), so it is safe to go down now.
+        let pending = PENDING_SLEEP.lock().unwrap().take();
+        if let Some(req) = pending {
+            enter_deep_sleep(&uart, &req);
+        }
+    }
+}
+
+/// Full line handling shared by UART and TCP: verify the optional CRC32
+/// frame, dispatch, and serialize the response framed to match the
+/// transport's negotiated mode.
+fn handle_line(line: &str, registry: &mut GpioRegistry, crc_mode: &mut bool) -> Option<String> {
+    let resp = match check_crc(line.trim()) {
+        Ok(payload) => handle_request(&payload, registry, crc_mode).ok()?,
+        Err(_) => Response {
+            // The host retransmits with the same id on mismatch.
+            id: extract_id(line),
+            ok: false,
+            result: String::new(),
+            error: Some("CRC mismatch".into()),
+        },
+    };
+    let mut out = serde_json::to_string(&resp).ok()?;
+    if *crc_mode {
+        out = add_crc(&out);
     }
+    Some(out)
 }
 
-fn handle_request<G2, G13>(
+fn handle_request(
     line: &str,
-    gpio2: &mut PinDriver<'_, G2>,
-    gpio13: &mut PinDriver<'_, G13>,
-) -> anyhow::Result<Response>
-where
-    G2: esp_idf_svc::hal::gpio::OutputMode,
-    G13: esp_idf_svc::hal::gpio::OutputMode,
-{
+    registry: &mut GpioRegistry,
+    crc_mode: &mut bool,
+) -> anyhow::Result<Response> {
     let req: Request = serde_json::from_str(line.trim())?;
     let id = req.id.clone();
 
     let result = match req.cmd.as_str() {
+        "protocol_hello" => {
+            // Switch this transport to CRC32 framing if the host asked for
+            // it; the confirmation below is the first framed response.
+            *crc_mode = req.args.get("crc").and_then(|v| v.as_bool()).unwrap_or(false);
+            Ok(serde_json::json!({ "version": PROTOCOL_VERSION, "crc": *crc_mode }).to_string())
+        }
+        "batch" => {
+            // One line, many operations: each sub-command keeps its own id
+            // and reports its own success or failure, so an LED pattern is
+            // one round-trip instead of one per pin write.
+            let commands = req
+                .args
+                .get("commands")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| anyhow::anyhow!("batch requires a 'commands' array"))?;
+            if commands.is_empty() {
+                Err(anyhow::anyhow!("batch is empty"))
+            } else if commands.len() > MAX_BATCH {
+                Err(anyhow::anyhow!(
+                    "Batch too large: {} commands (max {})",
+                    commands.len(),
+                    MAX_BATCH
+                ))
+            } else {
+                let mut responses = Vec::with_capacity(commands.len());
+                for entry in commands {
+                    let sub_id = entry
+                        .get("id")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("0")
+                        .to_string();
+                    if entry.get("cmd").and_then(|v| v.as_str()) == Some("batch") {
+                        responses.push(Response {
+                            id: sub_id,
+                            ok: false,
+                            result: String::new(),
+                            error: Some("batch cannot be nested".into()),
+                        });
+                        continue;
+                    }
+                    match handle_request(&entry.to_string(), registry, crc_mode) {
+                        Ok(resp) => responses.push(resp),
+                        Err(e) => responses.push(Response {
+                            id: sub_id,
+                            ok: false,
+                            result: String::new(),
+                            error: Some(e.to_string()),
+                        }),
+                    }
+                }
+                Ok(serde_json::to_string(&responses)?)
+            }
+        }
         "capabilities" => {
-            // Phase C: report GPIO pins and LED pin (matches Arduino protocol)
+            // Phase C: report GPIO pins, current mode map, LED pin and
+            // firmware identity (so the host can flag version skew)
             let caps = serde_json::json!({
-                "gpio": [0, 1, 2, 3, 4, 5, 12, 13, 14, 15, 16, 17, 18, 19],
-                "led_pin": 2
+                "gpio": registry.pins(),
+                "modes": registry.table.to_json(),
+                "led_pin": 2,
+                "sleep": true,
+                "fw_version": FW_VERSION,
+                "protocol_version": PROTOCOL_VERSION
             });
             Ok(caps.to_string())
         }
+        "sleep" => {
+            // Validated and parked here; executed by main after the ack
+            // is flushed, because deep sleep resets the chip.
+            let duration_ms = req.args.get("duration_ms").and_then(|v| v.as_u64());
+            let wake_gpio = req.args.get("wake_gpio").and_then(|v| v.as_i64());
+            match (duration_ms, wake_gpio) {
+                (None, _) | (Some(0), _) => {
+                    Err(anyhow::anyhow!("'duration_ms' is required and must be > 0"))
+                }
+                (Some(_), Some(pin)) if !RTC_WAKE_PINS.contains(&(pin as i32)) => {
+                    Err(anyhow::anyhow!(
+                        "wake_gpio {pin} is not RTC-capable (use 0,2,4,12-15,25-27,32-39)"
+                    ))
+                }
+                (Some(duration_ms), wake_gpio) => {
+                    *PENDING_SLEEP.lock().unwrap() = Some(SleepRequest {
+                        duration_ms,
+                        wake_gpio: wake_gpio.map(|p| p as i32),
+                    });
+                    Ok(format!("sleeping for {duration_ms} ms"))
+                }
+            }
+        }
+        "gpio_mode" => {
+            let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
+            let mode = req
+                .args
+                .get("mode")
+                .and_then(|v| v.as_str())
+                .and_then(PinMode::parse)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("'mode' must be \"input\", \"input_pullup\" or \"output\"")
+                })?;
+            registry.set_mode(pin_num, mode)?;
+            Ok("done".into())
+        }
         "gpio_read" => {
             let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
-            let value = gpio_read(pin_num)?;
+            let value = registry.read(pin_num)?;
             Ok(value.to_string())
         }
         "gpio_write" => {
             let pin_num = req.args.get("pin").and_then(|v| v.as_u64()).unwrap_or(0) as i32;
             let value = req.args.get("value").and_then(|v| v.as_u64()).unwrap_or(0);
-            gpio_write(gpio2, gpio13, pin_num, value)?;
+            registry.write(pin_num, value)?;
             Ok("done".into())
         }
         _ => Err(anyhow::anyhow!("Unknown command: {}", req.cmd)),
@@ -137,27 +737,214 @@ where
     }
 }
 
-fn gpio_read(_pin: i32) -> anyhow::Result<u8> {
-    // TODO: implement input pin read — requires storing InputPin drivers per pin
-    Ok(0)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_parses_gpio_mode_json() {
+        let req: Request =
+            serde_json::from_str(r#"{"id":"7","cmd":"gpio_mode","args":{"pin":4,"mode":"input"}}"#)
+                .unwrap();
+        assert_eq!(req.id, "7");
+        assert_eq!(req.cmd, "gpio_mode");
+        assert_eq!(req.args["pin"], 4);
+        assert_eq!(req.args["mode"], "input");
+    }
+
+    #[test]
+    fn malformed_request_is_an_error_not_a_panic() {
+        assert!(serde_json::from_str::<Request>("{\"id\":\"1\"").is_err());
+        assert!(serde_json::from_str::<Request>("not json").is_err());
+    }
+
+    #[test]
+    fn pin_mode_parses_protocol_names_only() {
+        assert_eq!(PinMode::parse("input"), Some(PinMode::Input));
+        assert_eq!(PinMode::parse("input_pullup"), Some(PinMode::InputPullup));
+        assert_eq!(PinMode::parse("output"), Some(PinMode::Output));
+        assert_eq!(PinMode::parse("INPUT"), None);
+        assert_eq!(PinMode::parse("pullup"), None);
+    }
 
-fn gpio_write<G2, G13>(
-    gpio2: &mut PinDriver<'_, G2>,
-    gpio13: &mut PinDriver<'_, G13>,
-    pin: i32,
-    value: u64,
-) -> anyhow::Result<()>
-where
-    G2: esp_idf_svc::hal::gpio::OutputMode,
-    G13: esp_idf_svc::hal::gpio::OutputMode,
-{
-    let level = esp_idf_svc::hal::gpio::Level::from(value != 0);
+    #[test]
+    fn mode_table_tracks_latest_mode_per_pin() {
+        let mut table = ModeTable::default();
+        assert_eq!(table.get(4), None);
+        table.set(4, PinMode::Input);
+        table.set(4, PinMode::Output);
+        table.set(2, PinMode::InputPullup);
+        assert_eq!(table.get(4), Some(PinMode::Output));
+        assert_eq!(table.get(2), Some(PinMode::InputPullup));
+    }
+
+    #[test]
+    fn mode_table_serializes_for_capabilities() {
+        let mut table = ModeTable::default();
+        table.set(2, PinMode::Output);
+        table.set(13, PinMode::InputPullup);
+        assert_eq!(
+            table.to_json(),
+            serde_json::json!({"2": "output", "13": "input_pullup"})
+        );
+    }
+
+    #[test]
+    fn sleep_command_is_validated_and_parked_for_main() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = false;
+
+        let resp = handle_request(
+            r#"{"id":"8","cmd":"sleep","args":{"duration_ms":60000,"wake_gpio":14}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(resp.ok);
+        assert!(resp.result.contains("60000"));
+        assert_eq!(
+            PENDING_SLEEP.lock().unwrap().take(),
+            Some(SleepRequest {
+                duration_ms: 60000,
+                wake_gpio: Some(14)
+            })
+        );
+
+        // Missing duration and non-RTC wake pins never arm a sleep.
+        let resp = handle_request(
+            r#"{"id":"9","cmd":"sleep","args":{}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(!resp.ok);
+        let resp = handle_request(
+            r#"{"id":"10","cmd":"sleep","args":{"duration_ms":1000,"wake_gpio":5}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(!resp.ok);
+        assert!(resp.error.as_deref().unwrap().contains("RTC-capable"));
+        assert!(PENDING_SLEEP.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn capabilities_reports_firmware_identity() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = false;
+        let resp = handle_request(
+            r#"{"id":"3","cmd":"capabilities","args":{}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(resp.ok);
+        let caps: serde_json::Value = serde_json::from_str(&resp.result).unwrap();
+        assert_eq!(caps["fw_version"], FW_VERSION);
+        assert_eq!(caps["protocol_version"], PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn batch_reports_per_sub_command_results() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = false;
+        // No pins registered off-target, so gpio_write fails while
+        // capabilities succeeds — a partial failure, reported per entry.
+        let resp = handle_request(
+            r#"{"id":"5","cmd":"batch","args":{"commands":[
+                {"id":"5.0","cmd":"capabilities","args":{}},
+                {"id":"5.1","cmd":"gpio_write","args":{"pin":4,"value":1}}
+            ]}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(resp.ok);
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&resp.result).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["id"], "5.0");
+        assert_eq!(entries[0]["ok"], true);
+        assert_eq!(entries[1]["id"], "5.1");
+        assert_eq!(entries[1]["ok"], false);
+        assert!(entries[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("not available"));
+    }
+
+    #[test]
+    fn oversized_and_nested_batches_are_rejected() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = false;
+
+        let entries: Vec<String> = (0..MAX_BATCH + 1)
+            .map(|i| format!(r#"{{"id":"{i}","cmd":"capabilities","args":{{}}}}"#))
+            .collect();
+        let line = format!(
+            r#"{{"id":"9","cmd":"batch","args":{{"commands":[{}]}}}}"#,
+            entries.join(",")
+        );
+        let resp = handle_request(&line, &mut registry, &mut crc_mode).unwrap();
+        assert!(!resp.ok);
+        assert!(resp.error.as_deref().unwrap().contains("Batch too large"));
+
+        let resp = handle_request(
+            r#"{"id":"9","cmd":"batch","args":{"commands":[{"id":"9.0","cmd":"batch","args":{"commands":[]}}]}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(resp.ok);
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&resp.result).unwrap();
+        assert_eq!(entries[0]["ok"], false);
+        assert!(entries[0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("cannot be nested"));
+    }
+
+    #[test]
+    fn crc32_matches_known_vector() {
+        // IEEE CRC32 check value for "123456789".
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn crc_frame_roundtrips_and_rejects_corruption() {
+        let payload = r#"{"id":"3","ok":true,"result":"done"}"#;
+        let framed = add_crc(payload);
+        assert_eq!(check_crc(&framed).unwrap(), payload);
+        assert!(check_crc(&framed.replace("done", "dOne")).is_err());
+        // Plain v1 lines pass through untouched.
+        assert_eq!(check_crc(payload).unwrap(), payload);
+    }
+
+    #[test]
+    fn protocol_hello_switches_transport_to_crc_framing() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = false;
+        let out = handle_line(
+            r#"{"id":"1","cmd":"protocol_hello","args":{"version":2,"crc":true}}"#,
+            &mut registry,
+            &mut crc_mode,
+        )
+        .unwrap();
+        assert!(crc_mode);
+        // The confirmation itself is already framed.
+        let payload = check_crc(&out).unwrap();
+        assert!(payload.contains(r#"\"crc\":true"#), "got: {payload}");
+    }
 
-    match pin {
-        2 => gpio2.set_level(level)?,
-        13 => gpio13.set_level(level)?,
-        _ => anyhow::bail!("Pin {} not configured (add to gpio_write)", pin),
+    #[test]
+    fn corrupted_frame_answers_with_crc_mismatch() {
+        let mut registry = GpioRegistry::new();
+        let mut crc_mode = true;
+        let framed = add_crc(r#"{"id":"9","cmd":"capabilities","args":{}}"#)
+            .replace("capabilities", "cApabilities");
+        let out = handle_line(&framed, &mut registry, &mut crc_mode).unwrap();
+        let payload = check_crc(&out).unwrap();
+        assert!(payload.contains("CRC mismatch"), "got: {payload}");
+        assert!(payload.contains(r#""id":"9""#), "got: {payload}");
     }
-    Ok(())
 }
@@ -3,7 +3,59 @@
 //! Listens for newline-delimited JSON on USART2 (PA2=TX, PA3=RX).
 //! USART2 is connected to ST-Link VCP — host sees /dev/ttyACM0 (Linux) or /dev/cu.usbmodem* (macOS).
 //!
+//! Reception runs over a DMA ring buffer with idle-line detection: the
+//! executor task sleeps until a line (or buffer chunk) is in, and bytes
+//! arriving while a response is still being transmitted are buffered by
+//! the DMA instead of dropped — back-to-back batch commands at 115200
+//! survive without flow control. Responses go out via TX DMA.
+//!
 //! Protocol: same as Arduino/ESP32 — see docs/hardware-peripherals-design.md
+//!
+//! PWM pin map (Arduino-style number -> STM32 pin -> timer channel):
+//!
+//! | Arduino | STM32 | Timer    |
+//! |---------|-------|----------|
+//! | D3      | PB3   | TIM2_CH2 |
+//! | D5      | PB4   | TIM3_CH1 |
+//! | D6      | PB10  | TIM2_CH3 |
+//! | D9      | PC7   | TIM3_CH2 |
+//!
+//! Frequency is a per-timer setting, so D3/D6 (TIM2) share one frequency
+//! and D5/D9 (TIM3) share another. TIM4 is the embassy time driver and
+//! is not available for PWM.
+//!
+//! ADC pin map (Arduino-style analog pin -> STM32 pin -> ADC1 channel):
+//!
+//! | Arduino | STM32 | Channel   |
+//! |---------|-------|-----------|
+//! | A0      | PA0   | ADC1_IN0  |
+//! | A1      | PA1   | ADC1_IN1  |
+//! | A2      | PA4   | ADC1_IN4  |
+//! | A3      | PB0   | ADC1_IN8  |
+//! | A4      | PC1   | ADC1_IN11 |
+//! | A5      | PC0   | ADC1_IN10 |
+//!
+//! Digital pins D2/D4/D7/D8/D10/D11/D12/D13 are direction-switchable via
+//! gpio_mode ("input", "input_pullup" or "output"; all start as outputs).
+//! D0/D1 carry the ST-Link VCP UART and D3/D5/D6/D9 are claimed by PWM, so
+//! their modes are reserved.
+//!
+//! Protocol v2 (optional): a host that sends `protocol_hello` with
+//! `"crc":true` switches both sides to CRC32-framed lines — every frame
+//! carries a trailing `"crc"` field covering the rest of the payload, and
+//! frames that fail the check are rejected so the host retransmits.
+//! Hosts that never send the hello keep the plain v1 framing above.
+//!
+//! A `batch` command carries up to [`MAX_BATCH`] sub-commands (each with
+//! its own id) in one line and answers with an array of responses in
+//! order, saving a serial round-trip per operation for things like LED
+//! patterns. Failures are reported per sub-command.
+//!
+//! Generic I2C bridging on I2C1 (SCL=D15/PB8, SDA=D14/PB9, 100 kHz):
+//! `i2c_write` takes an address and a hex byte string, `i2c_read` takes
+//! an address, register and length and answers hex-encoded bytes, so new
+//! sensors (BME280, OLEDs, ...) need no firmware changes. NACKs and bus
+//! timeouts come back in the JSON error field.
 
 #![no_std]
 #![no_main]
@@ -12,14 +64,98 @@ use core::fmt::Write;
 use core::str;
 use defmt::info;
 use embassy_executor::Spawner;
-use embassy_stm32::gpio::{Level, Output, Speed};
+use embassy_stm32::adc::Adc;
+use embassy_stm32::gpio::{Flex, Level, OutputType, Pull, Speed};
+use embassy_stm32::i2c::I2c;
+use embassy_stm32::mode::Blocking;
+use embassy_stm32::peripherals::{ADC1, PA0, PA1, PA4, PB0, PC0, PC1, TIM2, TIM3};
+use embassy_stm32::time::Hertz;
+use embassy_stm32::timer::simple_pwm::{PwmPin, SimplePwm};
 use embassy_stm32::usart::{Config, Uart};
+use embassy_stm32::{bind_interrupts, peripherals, usart, Peri};
 use heapless::String;
 use {defmt_rtt as _, panic_probe as _};
 
+bind_interrupts!(struct Irqs {
+    USART2 => usart::InterruptHandler<peripherals::USART2>;
+});
+
 /// Arduino-style pin 13 = PA5 (User LED LD2 on Nucleo-F401RE)
 const LED_PIN: u8 = 13;
 
+/// Firmware build version, injected by Cargo at build time.
+const FW_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Highest wire protocol this firmware speaks. The host duplicates this
+/// constant (`PROTOCOL_V2`) and cross-checks the two in a test.
+const PROTOCOL_VERSION: u8 = 2;
+
+/// Configured direction of a digital pin, as named in the serial protocol.
+/// Reserved pins (UART, PWM) cannot be re-directed.
+#[derive(Clone, Copy, PartialEq)]
+enum PinMode {
+    Reserved,
+    Output,
+    Input,
+    InputPullup,
+}
+
+impl PinMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Reserved => "reserved",
+            Self::Output => "output",
+            Self::Input => "input",
+            Self::InputPullup => "input_pullup",
+        }
+    }
+}
+
+/// Arduino-style pins with a usable timer channel (see module docs)
+const PWM_PINS: [u8; 4] = [3, 5, 6, 9];
+
+/// Timer clock with the default RCC config (HSI 16 MHz, no PLL,
+/// APB1 prescaler 1)
+const TIM_CLK_HZ: u32 = 16_000_000;
+
+/// PWM frequency range we accept (servo 50 Hz up to fast motor drive)
+const PWM_FREQ_MIN: u32 = 1;
+const PWM_FREQ_MAX: u32 = 100_000;
+
+/// Arduino-style analog pins A0-A5 wired to ADC1 (see module docs)
+const ADC_PINS: [u8; 6] = [0, 1, 2, 3, 4, 5];
+
+/// ADC reference voltage in millivolts (VDDA = 3.3 V on the Nucleo)
+const VREF_MV: u32 = 3300;
+
+/// Full-scale reading at 12-bit resolution
+const ADC_MAX: u32 = 4095;
+
+/// Most sub-commands accepted in one batch
+const MAX_BATCH: usize = 16;
+
+/// Largest I2C transfer per command (read length or write payload)
+const I2C_MAX_BYTES: usize = 32;
+
+/// Map an embassy I2C error to a protocol error string.
+fn i2c_err_str(e: embassy_stm32::i2c::Error) -> &'static str {
+    use embassy_stm32::i2c::Error;
+    match e {
+        Error::Nack => "NACK: no device acknowledged (check address and wiring)",
+        Error::Timeout => "I2C bus timeout",
+        Error::Bus => "I2C bus error",
+        Error::Arbitration => "I2C arbitration lost",
+        _ => "I2C transfer failed",
+    }
+}
+
+/// Frequency the timer actually hits for a request: the hardware divides
+/// the timer clock by an integer, so e.g. 60 Hz lands on 59 or 60 Hz
+/// depending on the divisor rounding.
+fn achieved_hz(requested: u32) -> u32 {
+    TIM_CLK_HZ / (TIM_CLK_HZ / requested)
+}
+
 /// Parse integer from JSON: "pin":13 or "value":1
 fn parse_arg(line: &[u8], key: &[u8]) -> Option<i32> {
     // key like b"pin" -> search for b"\"pin\":"
@@ -38,30 +174,89 @@ fn parse_arg(line: &[u8], key: &[u8]) -> Option<i32> {
     len += 2;
     let suffix = &suffix[..len];
 
+    let start = find(line, suffix)?;
+    let rest = &line[start + len..];
+    let mut num: i32 = 0;
+    let mut neg = false;
+    let mut j = 0;
+    if j < rest.len() && rest[j] == b'-' {
+        neg = true;
+        j += 1;
+    }
+    while j < rest.len() && rest[j].is_ascii_digit() {
+        num = num * 10 + (rest[j] - b'0') as i32;
+        j += 1;
+    }
+    Some(if neg { -num } else { num })
+}
+
+/// CRC32 (IEEE, bit-reflected), fed incrementally — bitwise rather than
+/// table-driven, since flash is tighter than time here.
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    crc
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !crc32_update(0xFFFF_FFFF, bytes)
+}
+
+/// Find the start of a trailing `,"crc":` field, scanning from the end so
+/// argument contents cannot shadow the real field.
+fn find_crc_field(line: &[u8]) -> Option<usize> {
+    let pat = b",\"crc\":";
+    if line.len() < pat.len() {
+        return None;
+    }
+    for i in (0..=line.len() - pat.len()).rev() {
+        if line[i..].starts_with(pat) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Verify a trailing crc field at `idx`: the CRC32 of the payload before
+/// it, re-closed with '}', must match the claimed digits.
+fn verify_crc(line: &[u8], idx: usize) -> bool {
+    let digits = &line[idx + 7..];
+    let mut claimed: u32 = 0;
+    let mut j = 0;
+    while j < digits.len() && digits[j].is_ascii_digit() {
+        claimed = claimed.wrapping_mul(10).wrapping_add((digits[j] - b'0') as u32);
+        j += 1;
+    }
+    if j == 0 || j + 1 != digits.len() || digits[j] != b'}' {
+        return false;
+    }
+    let crc = !crc32_update(crc32_update(0xFFFF_FFFF, &line[..idx]), b"}");
+    crc == claimed
+}
+
+/// First occurrence of `pat` in `line`.
+fn find(line: &[u8], pat: &[u8]) -> Option<usize> {
     let line_len = line.len();
-    if line_len < len {
+    if line_len < pat.len() {
         return None;
     }
-    for i in 0..=line_len - len {
-        if line[i..].starts_with(suffix) {
-            let rest = &line[i + len..];
-            let mut num: i32 = 0;
-            let mut neg = false;
-            let mut j = 0;
-            if j < rest.len() && rest[j] == b'-' {
-                neg = true;
-                j += 1;
-            }
-            while j < rest.len() && rest[j].is_ascii_digit() {
-                num = num * 10 + (rest[j] - b'0') as i32;
-                j += 1;
-            }
-            return Some(if neg { -num } else { num });
+    for i in 0..=line_len - pat.len() {
+        if line[i..].starts_with(pat) {
+            return Some(i);
         }
     }
     None
 }
 
+fn contains(line: &[u8], pat: &[u8]) -> bool {
+    find(line, pat).is_some()
+}
+
 fn has_cmd(line: &[u8], cmd: &[u8]) -> bool {
     let mut pat: [u8; 64] = [0; 64];
     pat[0..7].copy_from_slice(b"\"cmd\":\"");
@@ -70,26 +265,66 @@ fn has_cmd(line: &[u8], cmd: &[u8]) -> bool {
     pat[7 + clen] = b'"';
     let pat = &pat[..8 + clen];
 
-    let line_len = line.len();
-    if line_len < pat.len() {
-        return false;
+    contains(line, pat)
+}
+
+/// Parse the "mode" string argument of gpio_mode. Longest name first:
+/// "input" is a prefix of "input_pullup".
+fn parse_mode(line: &[u8]) -> Option<PinMode> {
+    if contains(line, b"\"mode\":\"input_pullup\"") {
+        Some(PinMode::InputPullup)
+    } else if contains(line, b"\"mode\":\"input\"") {
+        Some(PinMode::Input)
+    } else if contains(line, b"\"mode\":\"output\"") {
+        Some(PinMode::Output)
+    } else {
+        None
     }
-    for i in 0..=line_len - pat.len() {
-        if line[i..].starts_with(pat) {
-            return true;
+}
+
+fn hex_val(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode the hex string argument of i2c_write: "bytes":"F425" -> [0xF4, 0x25].
+/// None on a missing key, odd digit count, non-hex characters, empty or
+/// oversized payload.
+fn parse_hex_bytes(line: &[u8], out: &mut [u8; I2C_MAX_BYTES]) -> Option<usize> {
+    let start = find(line, b"\"bytes\":\"")? + 9;
+    let mut n = 0usize;
+    let mut i = start;
+    while i < line.len() && line[i] != b'"' {
+        if i + 1 >= line.len() || n >= I2C_MAX_BYTES {
+            return None;
         }
+        let hi = hex_val(line[i])?;
+        let lo = hex_val(line[i + 1])?;
+        out[n] = (hi << 4) | lo;
+        n += 1;
+        i += 2;
+    }
+    if n > 0 {
+        Some(n)
+    } else {
+        None
     }
-    false
 }
 
-/// Extract "id" for response
+/// Extract "id" for response. The last occurrence wins: a batch line
+/// carries sub-command ids inside "args", which serde serializes before
+/// the outer "id" (keys are sorted), so the outer id is the final one.
 fn copy_id(line: &[u8], out: &mut [u8]) -> usize {
     let prefix = b"\"id\":\"";
     if line.len() < prefix.len() + 1 {
         out[0] = b'0';
         return 1;
     }
-    for i in 0..=line.len() - prefix.len() {
+    for i in (0..=line.len() - prefix.len()).rev() {
         if line[i..].starts_with(prefix) {
             let start = i + prefix.len();
             let mut j = 0;
@@ -104,6 +339,470 @@ fn copy_id(line: &[u8], out: &mut [u8]) -> usize {
     1
 }
 
+/// All the hardware a command can touch, so the dispatcher can be a
+/// function shared by single commands and batch sub-commands instead of
+/// one giant inline chain in the read loop.
+struct Board {
+    flex: [Option<Flex<'static>>; 14],
+    modes: [PinMode; 14],
+    pwm_tim2: SimplePwm<'static, TIM2>,
+    pwm_tim3: SimplePwm<'static, TIM3>,
+    adc: Adc<'static, ADC1>,
+    adc_a0: Peri<'static, PA0>,
+    adc_a1: Peri<'static, PA1>,
+    adc_a2: Peri<'static, PA4>,
+    adc_a3: Peri<'static, PB0>,
+    adc_a4: Peri<'static, PC1>,
+    adc_a5: Peri<'static, PC0>,
+    i2c: I2c<'static, Blocking>,
+}
+
+/// Execute one command (a full line or one batch entry) and append its
+/// response object to `resp`. The entry's own "id" is echoed back.
+fn dispatch(line: &[u8], board: &mut Board, resp: &mut String<512>) {
+    let mut id_buf = [0u8; 16];
+    let id_len = copy_id(line, &mut id_buf);
+    let id_str = str::from_utf8(&id_buf[..id_len]).unwrap_or("0");
+
+    if has_cmd(line, b"ping") {
+        let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"pong\"}}", id_str);
+    } else if has_cmd(line, b"capabilities") {
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"pwm\\\":[3,5,6,9],\\\"adc\\\":[0,1,2,3,4,5],\\\"modes\\\":{{",
+            id_str
+        );
+        let mut first = true;
+        for (pin, mode) in board.modes.iter().enumerate() {
+            if *mode == PinMode::Reserved {
+                continue;
+            }
+            if !first {
+                let _ = write!(resp, ",");
+            }
+            first = false;
+            let _ = write!(resp, "\\\"{}\\\":\\\"{}\\\"", pin, mode.as_str());
+        }
+        let _ = write!(
+            resp,
+            "}},\\\"led_pin\\\":{},\\\"i2c\\\":true,\\\"i2c_pins\\\":\\\"SCL=D15/PB8,SDA=D14/PB9\\\",\\\"fw_version\\\":\\\"{}\\\",\\\"protocol_version\\\":{}}}\"}}",
+            LED_PIN, FW_VERSION, PROTOCOL_VERSION
+        );
+    } else if has_cmd(line, b"gpio_mode") {
+        let pin = parse_arg(line, b"pin").unwrap_or(-1);
+        if let Some(mode) = parse_mode(line) {
+            let slot = if (0..=13).contains(&pin) {
+                board.flex[pin as usize].as_mut()
+            } else {
+                None
+            };
+            if let Some(f) = slot {
+                match mode {
+                    PinMode::Output => f.set_as_output(Speed::Low),
+                    PinMode::Input => f.set_as_input(Pull::None),
+                    PinMode::InputPullup => f.set_as_input(Pull::Up),
+                    PinMode::Reserved => {}
+                }
+                board.modes[pin as usize] = mode;
+                let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"done\"}}", id_str);
+            } else {
+                let _ = write!(
+                    resp,
+                    "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} is reserved (UART/PWM) or invalid; mode pins: 2,4,7,8,10,11,12,13\"}}",
+                    id_str, pin
+                );
+            }
+        } else {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"mode must be input, input_pullup or output\"}}",
+                id_str
+            );
+        }
+    } else if has_cmd(line, b"gpio_read") {
+        let pin = parse_arg(line, b"pin").unwrap_or(-1);
+        if !(0..=13).contains(&pin) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin {}\"}}",
+                id_str, pin
+            );
+        } else {
+            match board.modes[pin as usize] {
+                PinMode::Input | PinMode::InputPullup => {
+                    let value = board.flex[pin as usize].as_mut().map(|f| f.is_high() as u8).unwrap_or(0);
+                    let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{}\"}}", id_str, value);
+                }
+                PinMode::Output => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} is configured as output; send gpio_mode input first\"}}",
+                        id_str, pin
+                    );
+                }
+                PinMode::Reserved => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} is reserved (UART/PWM)\"}}",
+                        id_str, pin
+                    );
+                }
+            }
+        }
+    } else if has_cmd(line, b"gpio_write") {
+        let pin = parse_arg(line, b"pin").unwrap_or(-1);
+        let value = parse_arg(line, b"value").unwrap_or(0);
+        if !(0..=13).contains(&pin) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin {}\"}}",
+                id_str, pin
+            );
+        } else {
+            match board.modes[pin as usize] {
+                PinMode::Output => {
+                    if let Some(f) = board.flex[pin as usize].as_mut() {
+                        f.set_level(if value != 0 { Level::High } else { Level::Low });
+                    }
+                    let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"done\"}}", id_str);
+                }
+                PinMode::Input | PinMode::InputPullup => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} is configured as input; send gpio_mode output first\"}}",
+                        id_str, pin
+                    );
+                }
+                PinMode::Reserved => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} is reserved (UART/PWM)\"}}",
+                        id_str, pin
+                    );
+                }
+            }
+        }
+    } else if has_cmd(line, b"pwm_write") {
+        let pin = parse_arg(line, b"pin").unwrap_or(-1);
+        let freq = parse_arg(line, b"frequency_hz").unwrap_or(-1);
+        let duty = parse_arg(line, b"duty").unwrap_or(-1);
+        if !(0..=1000).contains(&duty) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"duty must be 0-1000 per mille, got {}\"}}",
+                id_str, duty
+            );
+        } else if freq < PWM_FREQ_MIN as i32 || freq > PWM_FREQ_MAX as i32 {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"frequency_hz must be {}-{}, got {}\"}}",
+                id_str, PWM_FREQ_MIN, PWM_FREQ_MAX, freq
+            );
+        } else if pin < 0 || !PWM_PINS.contains(&(pin as u8)) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin {} has no timer channel; PWM pins: 3,5,6,9\"}}",
+                id_str, pin
+            );
+        } else {
+            let freq_hz = Hertz(freq as u32);
+            let duty = duty as u32;
+            // Frequency is per-timer: D3/D6 share TIM2, D5/D9 share TIM3
+            match pin {
+                3 => {
+                    board.pwm_tim2.set_frequency(freq_hz);
+                    let max = board.pwm_tim2.ch2().max_duty_cycle() as u32;
+                    board.pwm_tim2.ch2().set_duty_cycle((max * duty / 1000) as u16);
+                    board.pwm_tim2.ch2().enable();
+                }
+                6 => {
+                    board.pwm_tim2.set_frequency(freq_hz);
+                    let max = board.pwm_tim2.ch3().max_duty_cycle() as u32;
+                    board.pwm_tim2.ch3().set_duty_cycle((max * duty / 1000) as u16);
+                    board.pwm_tim2.ch3().enable();
+                }
+                5 => {
+                    board.pwm_tim3.set_frequency(freq_hz);
+                    let max = board.pwm_tim3.ch1().max_duty_cycle() as u32;
+                    board.pwm_tim3.ch1().set_duty_cycle((max * duty / 1000) as u16);
+                    board.pwm_tim3.ch1().enable();
+                }
+                _ => {
+                    board.pwm_tim3.set_frequency(freq_hz);
+                    let max = board.pwm_tim3.ch2().max_duty_cycle() as u32;
+                    board.pwm_tim3.ch2().set_duty_cycle((max * duty / 1000) as u16);
+                    board.pwm_tim3.ch2().enable();
+                }
+            }
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":true,\"result\":\"pwm pin {} duty {}/1000 at {} Hz\"}}",
+                id_str,
+                pin,
+                duty,
+                achieved_hz(freq as u32)
+            );
+        }
+    } else if has_cmd(line, b"analog_read") {
+        let pin = parse_arg(line, b"pin").unwrap_or(-1);
+        if pin < 0 || !ADC_PINS.contains(&(pin as u8)) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Pin A{} has no ADC channel; analog pins: A0-A5\"}}",
+                id_str, pin
+            );
+        } else {
+            let raw = match pin {
+                0 => board.adc.blocking_read(&mut board.adc_a0),
+                1 => board.adc.blocking_read(&mut board.adc_a1),
+                2 => board.adc.blocking_read(&mut board.adc_a2),
+                3 => board.adc.blocking_read(&mut board.adc_a3),
+                4 => board.adc.blocking_read(&mut board.adc_a4),
+                _ => board.adc.blocking_read(&mut board.adc_a5),
+            };
+            let mv = raw as u32 * VREF_MV / ADC_MAX;
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"raw\\\":{},\\\"millivolts\\\":{}}}\"}}",
+                id_str, raw, mv
+            );
+        }
+    } else if has_cmd(line, b"i2c_read") {
+        let addr = parse_arg(line, b"address").unwrap_or(-1);
+        let reg = parse_arg(line, b"register").unwrap_or(-1);
+        let len = parse_arg(line, b"length").unwrap_or(1);
+        if !(0..=127).contains(&addr) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"address must be 0-127, got {}\"}}",
+                id_str, addr
+            );
+        } else if !(0..=255).contains(&reg) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"register must be 0-255, got {}\"}}",
+                id_str, reg
+            );
+        } else if len < 1 || len > I2C_MAX_BYTES as i32 {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"length must be 1-{}, got {}\"}}",
+                id_str, I2C_MAX_BYTES, len
+            );
+        } else {
+            let mut buf = [0u8; I2C_MAX_BYTES];
+            match board
+                .i2c
+                .blocking_write_read(addr as u8, &[reg as u8], &mut buf[..len as usize])
+            {
+                Ok(()) => {
+                    let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"", id_str);
+                    for b in &buf[..len as usize] {
+                        let _ = write!(resp, "{:02X}", b);
+                    }
+                    let _ = write!(resp, "\"}}");
+                }
+                Err(e) => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"{}\"}}",
+                        id_str,
+                        i2c_err_str(e)
+                    );
+                }
+            }
+        }
+    } else if has_cmd(line, b"i2c_write") {
+        let addr = parse_arg(line, b"address").unwrap_or(-1);
+        let mut bytes = [0u8; I2C_MAX_BYTES];
+        if !(0..=127).contains(&addr) {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"address must be 0-127, got {}\"}}",
+                id_str, addr
+            );
+        } else if let Some(n) = parse_hex_bytes(line, &mut bytes) {
+            match board.i2c.blocking_write(addr as u8, &bytes[..n]) {
+                Ok(()) => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":true,\"result\":\"wrote {} bytes to 0x{:02X}\"}}",
+                        id_str, n, addr
+                    );
+                }
+                Err(e) => {
+                    let _ = write!(
+                        resp,
+                        "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"{}\"}}",
+                        id_str,
+                        i2c_err_str(e)
+                    );
+                }
+            }
+        } else {
+            let _ = write!(
+                resp,
+                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"bytes must be a hex string of 1-{} bytes\"}}",
+                id_str, I2C_MAX_BYTES
+            );
+        }
+    } else {
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}}",
+            id_str
+        );
+    }
+}
+
+/// Append `src` to `dst`, escaping it for embedding inside a JSON string.
+fn escape_into(dst: &mut String<2048>, src: &str) {
+    for c in src.chars() {
+        match c {
+            '"' => {
+                let _ = dst.push_str("\\\"");
+            }
+            '\\' => {
+                let _ = dst.push_str("\\\\");
+            }
+            _ => {
+                let _ = dst.push(c);
+            }
+        }
+    }
+}
+
+/// Yield the top-level `{...}` object slices of the "commands" array,
+/// starting at `body` (just past the opening '['). Nested braces in
+/// sub-args are tracked with a depth counter.
+fn next_object(body: &[u8], from: usize) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, &b) in body.iter().enumerate().skip(from) {
+        match b {
+            b'{' => {
+                if depth == 0 {
+                    start = Some(i);
+                }
+                depth += 1;
+            }
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    if let Some(s) = start {
+                        return Some((s, i + 1));
+                    }
+                }
+            }
+            b']' if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Run every entry of a batch through the dispatcher and collect the
+/// responses, in order, into one escaped JSON array.
+fn handle_batch(
+    line: &[u8],
+    id_str: &str,
+    board: &mut Board,
+    resp: &mut String<2048>,
+    sub: &mut String<512>,
+) {
+    let Some(arr) = find(line, b"\"commands\":[") else {
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"batch requires a commands array\"}}",
+            id_str
+        );
+        return;
+    };
+    let body = &line[arr + 12..];
+
+    // Bound the batch before executing anything
+    let mut count = 0usize;
+    let mut pos = 0usize;
+    while let Some((_, end)) = next_object(body, pos) {
+        count += 1;
+        pos = end;
+    }
+    if count == 0 {
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"batch is empty\"}}",
+            id_str
+        );
+        return;
+    }
+    if count > MAX_BATCH {
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Batch too large: {} commands (max {})\"}}",
+            id_str, count, MAX_BATCH
+        );
+        return;
+    }
+
+    let _ = write!(resp, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"[", id_str);
+    let mut pos = 0usize;
+    let mut first = true;
+    while let Some((start, end)) = next_object(body, pos) {
+        pos = end;
+        if !first {
+            let _ = resp.push(',');
+        }
+        first = false;
+        sub.clear();
+        dispatch(&body[start..end], board, sub);
+        escape_into(resp, sub.as_str());
+    }
+    let _ = write!(resp, "]\"}}");
+}
+
+/// Handle one complete received line: CRC check, protocol_hello, batch
+/// or a plain command — building the full (unframed) response in `resp`.
+/// Transport-agnostic on purpose: nothing in here touches the UART, so
+/// the same path serves DMA reception here and host-side testing.
+fn handle_line(
+    line: &[u8],
+    board: &mut Board,
+    crc_mode: &mut bool,
+    resp: &mut String<2048>,
+    sub: &mut String<512>,
+) {
+    let mut id_buf = [0u8; 16];
+    let id_len = copy_id(line, &mut id_buf);
+    let id_str = str::from_utf8(&id_buf[..id_len]).unwrap_or("0");
+    let crc_ok = match find_crc_field(line) {
+        Some(idx) => verify_crc(line, idx),
+        None => true,
+    };
+
+    resp.clear();
+    if !crc_ok {
+        // The host retransmits with the same id on mismatch
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"CRC mismatch\"}}",
+            id_str
+        );
+    } else if has_cmd(line, b"protocol_hello") {
+        *crc_mode = contains(line, b"\"crc\":true");
+        let _ = write!(
+            resp,
+            "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"version\\\":{},\\\"crc\\\":{}}}\"}}",
+            id_str, PROTOCOL_VERSION, crc_mode
+        );
+    } else if has_cmd(line, b"batch") {
+        handle_batch(line, id_str, board, resp, sub);
+    } else {
+        sub.clear();
+        dispatch(line, board, sub);
+        let _ = resp.push_str(sub.as_str());
+    }
+}
+
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) {
     let p = embassy_stm32::init(Default::default());
@@ -111,74 +810,132 @@ async fn main(_spawner: Spawner) {
     let mut config = Config::default();
     config.baudrate = 115_200;
 
-    let mut usart = Uart::new_blocking(p.USART2, p.PA3, p.PA2, config).unwrap();
-    let mut led = Output::new(p.PA5, Level::Low, Speed::Low);
+    // USART2 with DMA on both directions (F401: TX = DMA1 stream 6,
+    // RX = DMA1 stream 5). The receiver runs a DMA ring buffer with
+    // idle-line detection, so bytes arriving while a response is still
+    // going out are captured instead of dropped.
+    let uart = Uart::new(p.USART2, p.PA3, p.PA2, Irqs, p.DMA1_CH6, p.DMA1_CH5, config).unwrap();
+    let (mut tx, rx) = uart.split();
+    let rx_ring = cortex_m::singleton!(: [u8; 512] = [0; 512]).unwrap();
+    let mut rx = rx.into_ring_buffered(rx_ring);
+
+    // Direction-switchable digital pins (Arduino-style index -> STM32 pin):
+    // D2=PA10, D4=PB5, D7=PA8, D8=PA9, D10=PB6, D11=PA7, D12=PA6, D13=PA5 (LED)
+    // D0/D1 carry the VCP UART and D3/D5/D6/D9 belong to PWM, so their
+    // slots stay empty.
+    let mut flex: [Option<Flex>; 14] = [
+        None,
+        None,
+        Some(Flex::new(p.PA10)),
+        None,
+        Some(Flex::new(p.PB5)),
+        None,
+        None,
+        Some(Flex::new(p.PA8)),
+        Some(Flex::new(p.PA9)),
+        None,
+        Some(Flex::new(p.PB6)),
+        Some(Flex::new(p.PA7)),
+        Some(Flex::new(p.PA6)),
+        Some(Flex::new(p.PA5)),
+    ];
+    // Everything starts as a low output, matching the old firmware (and
+    // keeping the LED on D13 writable without a gpio_mode first).
+    let mut modes: [PinMode; 14] = [PinMode::Reserved; 14];
+    for (pin, slot) in flex.iter_mut().enumerate() {
+        if let Some(f) = slot {
+            f.set_low();
+            f.set_as_output(Speed::Low);
+            modes[pin] = PinMode::Output;
+        }
+    }
+
+    // PWM timers (channels idle until the first pwm_write):
+    // TIM2: D3 = PB3 (CH2), D6 = PB10 (CH3)
+    let pwm_tim2 = SimplePwm::new(
+        p.TIM2,
+        None,
+        Some(PwmPin::new(p.PB3, OutputType::PushPull)),
+        Some(PwmPin::new(p.PB10, OutputType::PushPull)),
+        None,
+        Hertz(1_000),
+        Default::default(),
+    );
+    // TIM3: D5 = PB4 (CH1), D9 = PC7 (CH2)
+    let pwm_tim3 = SimplePwm::new(
+        p.TIM3,
+        Some(PwmPin::new(p.PB4, OutputType::PushPull)),
+        Some(PwmPin::new(p.PC7, OutputType::PushPull)),
+        None,
+        None,
+        Hertz(1_000),
+        Default::default(),
+    );
+
+    // I2C1 on the Arduino header: SCL = D15/PB8, SDA = D14/PB9, standard
+    // mode 100 kHz (every I2C device supports it; the bridge is not the
+    // bottleneck at 32 bytes per transfer)
+    let i2c = I2c::new_blocking(p.I2C1, p.PB8, p.PB9, Hertz(100_000), Default::default());
+
+    // ADC1 and the analog header pins A0-A5 (sampled on demand)
+    let mut board = Board {
+        flex,
+        modes,
+        pwm_tim2,
+        pwm_tim3,
+        adc: Adc::new(p.ADC1),
+        adc_a0: p.PA0,
+        adc_a1: p.PA1,
+        adc_a2: p.PA4,
+        adc_a3: p.PB0,
+        adc_a4: p.PC1,
+        adc_a5: p.PC0,
+        i2c,
+    };
 
     info!("ZeroClaw Nucleo firmware ready on USART2 (115200)");
 
-    let mut line_buf: heapless::Vec<u8, 256> = heapless::Vec::new();
-    let mut id_buf = [0u8; 16];
-    let mut resp_buf: String<128> = String::new();
+    let mut line_buf: heapless::Vec<u8, 1024> = heapless::Vec::new();
+    let mut resp_buf: String<2048> = String::new();
+    let mut sub_buf: String<512> = String::new();
+    // CRC32 framing, enabled when the host sends protocol_hello with crc:true
+    let mut crc_mode = false;
+    let mut chunk = [0u8; 128];
 
     loop {
-        let mut byte = [0u8; 1];
-        if usart.blocking_read(&mut byte).is_ok() {
-            let b = byte[0];
+        // Returns on line idle, so a whole command line typically
+        // arrives in one wakeup
+        let n = match rx.read(&mut chunk).await {
+            Ok(n) => n,
+            Err(_) => {
+                // Ring overrun or framing error: whatever line was in
+                // flight is torn, drop it and resynchronize on the next
+                // newline (the host retransmits on timeout)
+                line_buf.clear();
+                continue;
+            }
+        };
+        for &b in &chunk[..n] {
             if b == b'\n' || b == b'\r' {
-                if !line_buf.is_empty() {
-                    let id_len = copy_id(&line_buf, &mut id_buf);
-                    let id_str = str::from_utf8(&id_buf[..id_len]).unwrap_or("0");
-
-                    resp_buf.clear();
-                    if has_cmd(&line_buf, b"ping") {
-                        let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"pong\"}}", id_str);
-                    } else if has_cmd(&line_buf, b"capabilities") {
-                        let _ = write!(
-                            resp_buf,
-                            "{{\"id\":\"{}\",\"ok\":true,\"result\":\"{{\\\"gpio\\\":[0,1,2,3,4,5,6,7,8,9,10,11,12,13],\\\"led_pin\\\":13}}\"}}",
-                            id_str
-                        );
-                    } else if has_cmd(&line_buf, b"gpio_read") {
-                        let pin = parse_arg(&line_buf, b"pin").unwrap_or(-1);
-                        if pin == LED_PIN as i32 {
-                            // Output doesn't support read; return 0 (LED state not readable)
-                            let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"0\"}}", id_str);
-                        } else if pin >= 0 && pin <= 13 {
-                            let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"0\"}}", id_str);
-                        } else {
-                            let _ = write!(
-                                resp_buf,
-                                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin {}\"}}",
-                                id_str, pin
-                            );
-                        }
-                    } else if has_cmd(&line_buf, b"gpio_write") {
-                        let pin = parse_arg(&line_buf, b"pin").unwrap_or(-1);
-                        let value = parse_arg(&line_buf, b"value").unwrap_or(0);
-                        if pin == LED_PIN as i32 {
-                            led.set_level(if value != 0 { Level::High } else { Level::Low });
-                            let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"done\"}}", id_str);
-                        } else if pin >= 0 && pin <= 13 {
-                            let _ = write!(resp_buf, "{{\"id\":\"{}\",\"ok\":true,\"result\":\"done\"}}", id_str);
-                        } else {
-                            let _ = write!(
-                                resp_buf,
-                                "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin {}\"}}",
-                                id_str, pin
-                            );
-                        }
-                    } else {
-                        let _ = write!(
-                            resp_buf,
-                            "{{\"id\":\"{}\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}}",
-                            id_str
-                        );
-                    }
+                if line_buf.is_empty() {
+                    continue;
+                }
+                handle_line(&line_buf, &mut board, &mut crc_mode, &mut resp_buf, &mut sub_buf);
 
-                    let _ = usart.blocking_write(resp_buf.as_bytes());
-                    let _ = usart.blocking_write(b"\n");
-                    line_buf.clear();
+                if crc_mode {
+                    // Frame the response: CRC32 of the whole payload,
+                    // spliced in before the closing brace.
+                    let bytes = resp_buf.as_bytes();
+                    let crc = crc32(bytes);
+                    let _ = tx.write(&bytes[..bytes.len() - 1]).await;
+                    let mut tail: String<24> = String::new();
+                    let _ = write!(tail, ",\"crc\":{}}}", crc);
+                    let _ = tx.write(tail.as_bytes()).await;
+                } else {
+                    let _ = tx.write(resp_buf.as_bytes()).await;
                 }
+                let _ = tx.write(b"\n").await;
+                line_buf.clear();
             } else if line_buf.push(b).is_err() {
                 line_buf.clear();
             }
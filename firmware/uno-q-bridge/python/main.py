@@ -6,6 +6,9 @@ import threading
 from arduino.app_utils import App, Bridge
 
 ZEROCLAW_PORT = 9999
+# Bumped whenever the command set changes; the host asks with "version"
+# and falls back to the GPIO-only v1 set when the bridge does not answer.
+BRIDGE_VERSION = 2
 
 def handle_client(conn):
     try:
@@ -14,11 +17,15 @@ def handle_client(conn):
             conn.close()
             return
         parts = data.split()
+        cmd = parts[0].lower()
+        if cmd == "version":
+            conn.sendall(f"{BRIDGE_VERSION}\n".encode())
+            conn.close()
+            return
         if len(parts) < 2:
             conn.sendall(b"error: invalid command\n")
             conn.close()
             return
-        cmd = parts[0].lower()
         if cmd == "gpio_write" and len(parts) >= 3:
             pin = int(parts[1])
             value = int(parts[2])
@@ -28,6 +35,18 @@ def handle_client(conn):
             pin = int(parts[1])
             val = Bridge.call("digitalRead", [pin])
             conn.sendall(f"{val}\n".encode())
+        elif cmd == "analog_read" and len(parts) >= 2:
+            pin = int(parts[1])
+            val = Bridge.call("analogRead", [pin])
+            conn.sendall(f"{val}\n".encode())
+        elif cmd == "pwm_write" and len(parts) >= 3:
+            pin = int(parts[1])
+            duty = int(parts[2])
+            if duty < 0 or duty > 1000:
+                conn.sendall(b"error: duty must be 0-1000 (per-mille)\n")
+            else:
+                Bridge.call("pwmWrite", [pin, duty])
+                conn.sendall(b"ok\n")
         else:
             conn.sendall(b"error: unknown command\n")
     except Exception as e:
@@ -13,10 +13,23 @@ int gpio_read(int pin) {
   return digitalRead(pin);
 }
 
+int analog_read(int pin) {
+  return analogRead(pin);
+}
+
+// duty arrives in per-mille (0-1000) to match the serial protocol;
+// analogWrite wants 0-255.
+void pwm_write(int pin, int duty) {
+  pinMode(pin, OUTPUT);
+  analogWrite(pin, (duty * 255) / 1000);
+}
+
 void setup() {
   Bridge.begin();
   Bridge.provide("digitalWrite", gpio_write);
   Bridge.provide("digitalRead", gpio_read);
+  Bridge.provide("analogRead", analog_read);
+  Bridge.provide("pwmWrite", pwm_write);
 }
 
 void loop() {
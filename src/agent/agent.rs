@@ -378,6 +378,7 @@ impl Agent {
         let memory: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
             &config.memory,
             &config.embedding_routes,
+            &config.embeddings,
             Some(&config.storage.provider.config),
             &config.workspace_dir,
             config.api_key.as_deref(),
@@ -1018,6 +1019,7 @@ impl Agent {
 
             let mut streamed_text = String::new();
             let mut streamed_tool_calls: Vec<crate::providers::traits::ToolCall> = Vec::new();
+            let mut streamed_usage: Option<crate::providers::traits::TokenUsage> = None;
             let mut got_stream = false;
 
             while let Some(item) = stream.next().await {
@@ -1066,6 +1068,9 @@ impl Agent {
                         } => {
                             let _ = event_tx.send(TurnEvent::ToolResult { name, output }).await;
                         }
+                        crate::providers::traits::StreamEvent::Usage(usage) => {
+                            streamed_usage = Some(usage);
+                        }
                         crate::providers::traits::StreamEvent::Final => break,
                     },
                     Err(_) => break,
@@ -1081,7 +1086,7 @@ impl Agent {
                 crate::providers::ChatResponse {
                     text: Some(streamed_text),
                     tool_calls: streamed_tool_calls,
-                    usage: None,
+                    usage: streamed_usage,
                     reasoning_content: None,
                 }
             } else {
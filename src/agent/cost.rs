@@ -44,22 +44,17 @@ pub(crate) fn record_tool_loop_cost_usage(
         .try_with(Clone::clone)
         .ok()
         .flatten()?;
-    // 3-tier model pricing lookup: direct name → provider/model → suffix after last `/`
-    let pricing = ctx
-        .prices
-        .get(model)
-        .or_else(|| ctx.prices.get(&format!("{provider_name}/{model}")))
-        .or_else(|| {
-            model
-                .rsplit_once('/')
-                .and_then(|(_, suffix)| ctx.prices.get(suffix))
-        });
+    let pricing = lookup_model_pricing(&ctx, provider_name, model);
     let cost_usage = CostTokenUsage::new(
         model,
         input_tokens,
         output_tokens,
         pricing.map_or(0.0, |entry| entry.input),
         pricing.map_or(0.0, |entry| entry.output),
+    )
+    .with_cache_tokens(
+        usage.cached_input_tokens.unwrap_or(0),
+        usage.cache_write_input_tokens.unwrap_or(0),
     );
 
     if pricing.is_none() {
@@ -81,6 +76,76 @@ pub(crate) fn record_tool_loop_cost_usage(
     Some((cost_usage.total_tokens, cost_usage.cost_usd))
 }
 
+/// 3-tier model pricing lookup: direct name → provider/model → suffix after last `/`.
+fn lookup_model_pricing<'a>(
+    ctx: &'a ToolLoopCostTrackingContext,
+    provider_name: &str,
+    model: &str,
+) -> Option<&'a ModelPricing> {
+    ctx.prices
+        .get(model)
+        .or_else(|| ctx.prices.get(&format!("{provider_name}/{model}")))
+        .or_else(|| {
+            model
+                .rsplit_once('/')
+                .and_then(|(_, suffix)| ctx.prices.get(suffix))
+        })
+}
+
+/// Record one LLM turn in the per-sender usage ledger behind `/usage`.
+///
+/// Uses provider-reported tokens when present; otherwise falls back to the
+/// supplied chars/4 approximations and marks the turn as estimated. Cost is
+/// priced from the tracking context when scoped, zero otherwise. No-op
+/// outside a channel turn (no `TOOL_LOOP_SENDER_KEY` scoped).
+pub(crate) fn record_sender_usage_for_turn(
+    provider_name: &str,
+    model: &str,
+    usage: Option<&crate::providers::traits::TokenUsage>,
+    estimated_input_tokens: u64,
+    estimated_output_tokens: u64,
+) {
+    let Some(sender_key) = super::loop_::TOOL_LOOP_SENDER_KEY
+        .try_with(Clone::clone)
+        .ok()
+        .flatten()
+    else {
+        return;
+    };
+
+    let reported = usage.and_then(|u| {
+        let input = u.input_tokens.unwrap_or(0);
+        let output = u.output_tokens.unwrap_or(0);
+        (input.saturating_add(output) > 0).then_some((input, output))
+    });
+    let (input_tokens, output_tokens, estimated) = match reported {
+        Some((input, output)) => (input, output, false),
+        None => (estimated_input_tokens, estimated_output_tokens, true),
+    };
+
+    let pricing = TOOL_LOOP_COST_TRACKING_CONTEXT
+        .try_with(Clone::clone)
+        .ok()
+        .flatten()
+        .and_then(|ctx| lookup_model_pricing(&ctx, provider_name, model).cloned());
+    let cost_usd = CostTokenUsage::new(
+        model,
+        input_tokens,
+        output_tokens,
+        pricing.as_ref().map_or(0.0, |entry| entry.input),
+        pricing.as_ref().map_or(0.0, |entry| entry.output),
+    )
+    .cost_usd;
+
+    crate::cost::sender_usage_ledger().record(
+        &sender_key,
+        input_tokens,
+        output_tokens,
+        cost_usd,
+        estimated,
+    );
+}
+
 /// Check budget before an LLM call. Returns `None` when no cost tracking
 /// context is scoped (tests, delegate, CLI without cost config).
 pub(crate) fn check_tool_loop_budget() -> Option<BudgetCheck> {
@@ -27,8 +27,8 @@ use uuid::Uuid;
 
 // Cost tracking moved to `super::cost`.
 pub(crate) use super::cost::{
-    check_tool_loop_budget, record_tool_loop_cost_usage, ToolLoopCostTrackingContext,
-    TOOL_LOOP_COST_TRACKING_CONTEXT,
+    check_tool_loop_budget, record_sender_usage_for_turn, record_tool_loop_cost_usage,
+    ToolLoopCostTrackingContext, TOOL_LOOP_COST_TRACKING_CONTEXT,
 };
 
 /// Minimum characters per chunk when relaying LLM text to a streaming draft.
@@ -388,6 +388,19 @@ fn compact_history_for_budget(
     }
 }
 
+/// Approximate-token thresholds for the in-loop budget safety net, as
+/// `(critical, soft)`. When `models refresh` cached a context window for this
+/// model, compact proactively at 90%/70% of it; otherwise fall back to the
+/// fixed defaults sized for large-context models.
+fn budget_compaction_thresholds(provider_name: &str, model: &str) -> (usize, usize) {
+    match crate::onboard::runtime_context_window(provider_name, model)
+        .and_then(|window| usize::try_from(window).ok())
+    {
+        Some(window) => (window.saturating_mul(9) / 10, window.saturating_mul(7) / 10),
+        None => (800_000, 500_000),
+    }
+}
+
 /// Convert a tool registry to OpenAI function-calling format for native tool support.
 fn tools_to_openai_format(tools_registry: &[Box<dyn Tool>]) -> Vec<serde_json::Value> {
     tools_registry
@@ -491,17 +504,70 @@ fn build_hardware_context(
         context.push_str("[Hardware documentation]\n");
     }
     for chunk in chunks {
-        let board_tag = chunk.board.as_deref().unwrap_or("generic");
         let _ = writeln!(
             context,
             "--- {} ({}) ---\n{}\n",
-            chunk.source, board_tag, chunk.content
+            chunk.source,
+            chunk.citation_detail(),
+            chunk.content
         );
     }
     context.push('\n');
     context
 }
 
+/// Like [`build_hardware_context`], but numbers the documentation blocks,
+/// instructs the model to cite them with `[n]` markers, and returns the
+/// block provenance so the response can be expanded into a sources footer
+/// via [`crate::rag::expand_citation_markers`]. Used when `[rag]`
+/// `citations` is enabled.
+fn build_hardware_context_cited(
+    rag: &crate::rag::HardwareRag,
+    user_msg: &str,
+    boards: &[String],
+    chunk_limit: usize,
+) -> (String, Vec<crate::rag::CitationSource>) {
+    if rag.is_empty() || boards.is_empty() {
+        return (String::new(), Vec::new());
+    }
+
+    let mut context = String::new();
+
+    let pin_ctx = rag.pin_alias_context(user_msg, boards);
+    if !pin_ctx.is_empty() {
+        context.push_str(&pin_ctx);
+    }
+
+    let chunks = rag.retrieve(user_msg, boards, chunk_limit);
+    if chunks.is_empty() && pin_ctx.is_empty() {
+        return (String::new(), Vec::new());
+    }
+
+    let mut sources = Vec::new();
+    if !chunks.is_empty() {
+        context.push_str("[Hardware documentation]\n");
+        context.push_str(
+            "When your answer uses a numbered block below, cite it inline with its [n] marker.\n",
+        );
+    }
+    for (i, chunk) in chunks.iter().enumerate() {
+        let detail = chunk.citation_detail();
+        let _ = writeln!(
+            context,
+            "[{}] {} ({detail})\n{}\n",
+            i + 1,
+            chunk.source,
+            chunk.content
+        );
+        sources.push(crate::rag::CitationSource {
+            path: chunk.source.clone(),
+            detail,
+        });
+    }
+    context.push('\n');
+    (context, sources)
+}
+
 // Tool execution moved to `super::tool_execution`.
 pub(crate) use super::tool_execution::{
     execute_tools_parallel, execute_tools_sequential, should_execute_tools_in_parallel,
@@ -2130,6 +2196,8 @@ struct StreamedChatOutcome {
     response_text: String,
     tool_calls: Vec<ToolCall>,
     forwarded_live_deltas: bool,
+    /// Usage from the stream's final chunk, when the provider reports it.
+    usage: Option<crate::providers::traits::TokenUsage>,
 }
 
 
@@ -2173,6 +2241,9 @@ async fn consume_provider_streaming_response(
         let event = event_result.map_err(|err| anyhow::anyhow!("provider stream error: {err}"))?;
         match event {
             StreamEvent::Final => break,
+            StreamEvent::Usage(usage) => {
+                outcome.usage = Some(usage);
+            }
             StreamEvent::ToolCall(tool_call) => {
                 outcome.tool_calls.push(tool_call);
                 suppress_forwarding = true;
@@ -2684,7 +2755,7 @@ pub(crate) async fn run_tool_call_loop(
                     Ok(crate::providers::ChatResponse {
                         text: Some(streamed.response_text),
                         tool_calls: streamed.tool_calls,
-                        usage: None,
+                        usage: streamed.usage,
                         reasoning_content: None,
                     })
                 }
@@ -2815,6 +2886,17 @@ pub(crate) async fn run_tool_call_loop(
                     .and_then(|usage| record_tool_loop_cost_usage(provider_name, model, usage));
 
                 let response_text = resp.text_or_empty().to_string();
+
+                // Per-sender `/usage` accounting: provider-reported tokens
+                // when present, otherwise a chars/4 approximation marked as
+                // estimated. No-op outside channel turns.
+                record_sender_usage_for_turn(
+                    provider_name,
+                    model,
+                    resp.usage.as_ref(),
+                    estimate_history_tokens(history) as u64,
+                    (response_text.len().div_ceil(4)) as u64,
+                );
                 // First try native structured tool calls (OpenAI-format).
                 // Fall back to text-based parsing (XML tags, markdown blocks,
                 // GLM format) only if the provider returned no native calls —
@@ -3776,16 +3858,19 @@ pub(crate) async fn run_tool_call_loop(
         // Token budget safety net — compact history if context is too large
         let total_chars: usize = history.iter().map(|m| m.content.len()).sum();
         let approx_tokens = total_chars / 3;
-        if approx_tokens > 800_000 {
+        let (critical_tokens, soft_tokens) = budget_compaction_thresholds(provider_name, model);
+        if approx_tokens > critical_tokens {
             tracing::warn!(
                 approx_tokens,
+                critical_tokens,
                 history_len = history.len(),
                 "Token budget critical — aggressive compaction"
             );
             compact_history_for_budget(history, 1000, 10);
-        } else if approx_tokens > 500_000 {
+        } else if approx_tokens > soft_tokens {
             tracing::info!(
                 approx_tokens,
+                soft_tokens,
                 history_len = history.len(),
                 "Token budget high — soft compaction"
             );
@@ -3901,10 +3986,14 @@ pub async fn run(
         &config.workspace_dir,
     ));
 
+    // Cached model metadata (context windows) for compaction thresholds.
+    crate::onboard::load_runtime_model_metadata(&config.workspace_dir).await;
+
     // ── Memory (the brain) ────────────────────────────────────────
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
         &config.memory,
         &config.embedding_routes,
+        &config.embeddings,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -3953,12 +4042,19 @@ pub async fn run(
     );
 
     let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        crate::peripherals::create_peripheral_tools(&config, &security).await?;
     if !peripheral_tools.is_empty() {
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
 
+    #[cfg(feature = "robot-kit")]
+    if let Err(e) =
+        crate::tools::register_robot_tools(&config.peripherals.robot, &mut tools_registry)
+    {
+        tracing::warn!("Failed to register robot kit tools: {e}");
+    }
+
     // ── Capability-based tool access control ─────────────────────
     // When `allowed_tools` is `Some(list)`, restrict the tool registry to only
     // those tools whose name appears in the list. Unknown names are silently
@@ -4080,12 +4176,25 @@ pub async fn run(
     });
 
     // ── Hardware RAG (datasheet retrieval when peripherals + datasheet_dir) ──
+    let rag_chunking = crate::rag::ChunkingSettings {
+        strategy: config.rag.chunking.strategy.clone(),
+        max_tokens: config.rag.chunking.max_tokens,
+        overlap_tokens: config.rag.chunking.overlap_tokens,
+        embed_model: config.embeddings.model.clone(),
+    };
     let hardware_rag: Option<crate::rag::HardwareRag> = config
         .peripherals
         .datasheet_dir
         .as_ref()
         .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
+        .map(|dir| {
+            crate::rag::HardwareRag::load_with_settings(
+                &config.workspace_dir,
+                dir.trim(),
+                &rag_chunking,
+            )
+            .map(|(rag, _)| rag)
+        })
         .and_then(Result::ok)
         .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
     if let Some(ref rag) = hardware_rag {
@@ -4339,10 +4448,16 @@ pub async fn run(
         )
         .await;
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-        let hw_context = hardware_rag
-            .as_ref()
-            .map(|r| build_hardware_context(r, &effective_msg, &board_names, rag_limit))
-            .unwrap_or_default();
+        let (hw_context, rag_sources) = match hardware_rag.as_ref() {
+            Some(r) if config.rag.citations => {
+                build_hardware_context_cited(r, &effective_msg, &board_names, rag_limit)
+            }
+            Some(r) => (
+                build_hardware_context(r, &effective_msg, &board_names, rag_limit),
+                Vec::new(),
+            ),
+            None => (String::new(), Vec::new()),
+        };
         let context = format!("{mem_context}{hw_context}");
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
         let enriched = if context.is_empty() {
@@ -4472,6 +4587,10 @@ pub async fn run(
                 }
             }
         }
+        if !rag_sources.is_empty() {
+            // No gateway file-serving route exists, so paths stay plain text.
+            response = crate::rag::expand_citation_markers(&response, &rag_sources, None);
+        }
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
@@ -4545,6 +4664,8 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    // Drop provider-side conversation state (e.g. Gemini cachedContent)
+                    provider.clear_conversation_state().await;
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -4624,10 +4745,16 @@ pub async fn run(
             )
             .await;
             let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-            let hw_context = hardware_rag
-                .as_ref()
-                .map(|r| build_hardware_context(r, &effective_input, &board_names, rag_limit))
-                .unwrap_or_default();
+            let (hw_context, rag_sources) = match hardware_rag.as_ref() {
+                Some(r) if config.rag.citations => {
+                    build_hardware_context_cited(r, &effective_input, &board_names, rag_limit)
+                }
+                Some(r) => (
+                    build_hardware_context(r, &effective_input, &board_names, rag_limit),
+                    Vec::new(),
+                ),
+                None => (String::new(), Vec::new()),
+            };
             let context = format!("{mem_context}{hw_context}");
             let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
             let enriched = if context.is_empty() {
@@ -4766,10 +4893,16 @@ pub async fn run(
                             tracing::warn!(
                                 "Context overflow in interactive loop, attempting recovery"
                             );
+                            // Prefer the context window the provider reports
+                            // (e.g. llama.cpp `/props`) over the configured cap.
+                            let context_window = provider
+                                .detected_context_window()
+                                .await
+                                .unwrap_or(config.agent.max_context_tokens);
                             let mut compressor =
                                 crate::agent::context_compressor::ContextCompressor::new(
                                     config.agent.context_compression.clone(),
-                                    config.agent.max_context_tokens,
+                                    context_window,
                                 )
                                 .with_memory(mem.clone());
                             let error_msg = format!("{e}");
@@ -4811,6 +4944,11 @@ pub async fn run(
             drop(delta_tx);
             let _ = consumer_handle.await;
 
+            let response = if rag_sources.is_empty() {
+                response
+            } else {
+                crate::rag::expand_citation_markers(&response, &rag_sources, None)
+            };
             final_output = response.clone();
             if content_was_streamed.load(std::sync::atomic::Ordering::Relaxed) {
                 println!();
@@ -4826,9 +4964,13 @@ pub async fn run(
 
             // Context compression before hard trimming to preserve long-context signal.
             {
+                let context_window = provider
+                    .detected_context_window()
+                    .await
+                    .unwrap_or(config.agent.max_context_tokens);
                 let compressor = crate::agent::context_compressor::ContextCompressor::new(
                     config.agent.context_compression.clone(),
-                    config.agent.max_context_tokens,
+                    context_window,
                 )
                 .with_memory(mem.clone());
                 match compressor
@@ -4916,9 +5058,11 @@ pub async fn process_message(
         &config.workspace_dir,
     ));
     let approval_manager = ApprovalManager::for_non_interactive(&config.autonomy);
+    crate::onboard::load_runtime_model_metadata(&config.workspace_dir).await;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
         &config.memory,
         &config.embedding_routes,
+        &config.embeddings,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -4956,9 +5100,16 @@ pub async fn process_message(
         None,
     );
     let peripheral_tools: Vec<Box<dyn Tool>> =
-        crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
+        crate::peripherals::create_peripheral_tools(&config, &security).await?;
     tools_registry.extend(peripheral_tools);
 
+    #[cfg(feature = "robot-kit")]
+    if let Err(e) =
+        crate::tools::register_robot_tools(&config.peripherals.robot, &mut tools_registry)
+    {
+        tracing::warn!("Failed to register robot kit tools: {e}");
+    }
+
     // ── Wire MCP tools (non-fatal) — process_message path ────────
     // NOTE: Same ordering contract as the CLI path above — MCP tools must be
     // injected after filter_primary_agent_tools_or_fail (or equivalent built-in
@@ -5042,12 +5193,25 @@ pub async fn process_message(
         &provider_runtime_options,
     )?;
 
+    let rag_chunking = crate::rag::ChunkingSettings {
+        strategy: config.rag.chunking.strategy.clone(),
+        max_tokens: config.rag.chunking.max_tokens,
+        overlap_tokens: config.rag.chunking.overlap_tokens,
+        embed_model: config.embeddings.model.clone(),
+    };
     let hardware_rag: Option<crate::rag::HardwareRag> = config
         .peripherals
         .datasheet_dir
         .as_ref()
         .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
+        .map(|dir| {
+            crate::rag::HardwareRag::load_with_settings(
+                &config.workspace_dir,
+                dir.trim(),
+                &rag_chunking,
+            )
+            .map(|(rag, _)| rag)
+        })
         .and_then(Result::ok)
         .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
     let board_names: Vec<String> = config
@@ -5198,10 +5362,16 @@ pub async fn process_message(
     )
     .await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-    let hw_context = hardware_rag
-        .as_ref()
-        .map(|r| build_hardware_context(r, effective_msg_ref, &board_names, rag_limit))
-        .unwrap_or_default();
+    let (hw_context, rag_sources) = match hardware_rag.as_ref() {
+        Some(r) if config.rag.citations => {
+            build_hardware_context_cited(r, effective_msg_ref, &board_names, rag_limit)
+        }
+        Some(r) => (
+            build_hardware_context(r, effective_msg_ref, &board_names, rag_limit),
+            Vec::new(),
+        ),
+        None => (String::new(), Vec::new()),
+    };
     let context = format!("{mem_context}{hw_context}");
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
     let enriched = if context.is_empty() {
@@ -5223,7 +5393,7 @@ pub async fn process_message(
         excluded_tools.extend(config.autonomy.non_cli_excluded_tools.iter().cloned());
     }
 
-    agent_turn(
+    let response = agent_turn(
         provider.as_ref(),
         &mut history,
         &tools_registry,
@@ -5242,7 +5412,17 @@ pub async fn process_message(
         activated_handle_pm.as_ref(),
         None,
     )
-    .await
+    .await?;
+
+    if rag_sources.is_empty() {
+        Ok(response)
+    } else {
+        Ok(crate::rag::expand_citation_markers(
+            &response,
+            &rag_sources,
+            None,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -9707,6 +9887,36 @@ Let me check the result."#;
         assert!(history[1].content.contains("...(truncated,"));
     }
 
+    #[tokio::test]
+    async fn budget_compaction_thresholds_use_cached_context_window() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state_dir = tmp.path().join("state");
+        std::fs::create_dir_all(&state_dir).unwrap();
+        let cache = serde_json::json!({
+            "version": 1,
+            "entries": [{
+                "provider": "openrouter",
+                "fetched_at_unix": 0,
+                "models": ["acme/tiny-8k"],
+                "metadata": [{"id": "acme/tiny-8k", "context_window": 8192}]
+            }]
+        });
+        std::fs::write(state_dir.join("models_cache.json"), cache.to_string()).unwrap();
+
+        crate::onboard::load_runtime_model_metadata(tmp.path()).await;
+
+        // Cached window compacts proactively at 90%/70% instead of the
+        // large-context defaults.
+        let (critical, soft) = budget_compaction_thresholds("openrouter", "acme/tiny-8k");
+        assert_eq!(critical, 8192 * 9 / 10);
+        assert_eq!(soft, 8192 * 7 / 10);
+
+        // Models without cached metadata keep the fixed defaults.
+        let (critical, soft) = budget_compaction_thresholds("openrouter", "acme/uncached-model");
+        assert_eq!(critical, 800_000);
+        assert_eq!(soft, 500_000);
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_batches_parallel_calls_to_max_limit() {
         // Provider returns 6 tool calls, max_parallel=2 → should batch into 3 groups
@@ -10312,6 +10522,7 @@ Let me check the result."#;
                     input_tokens: Some(1_000),
                     output_tokens: Some(200),
                     cached_input_tokens: None,
+                    cache_write_input_tokens: None,
                 }),
                 reasoning_content: None,
             }]))),
@@ -10482,6 +10693,7 @@ Let me check the result."#;
                     input_tokens: Some(500),
                     output_tokens: Some(100),
                     cached_input_tokens: None,
+                    cache_write_input_tokens: None,
                 }),
                 reasoning_content: None,
             }]))),
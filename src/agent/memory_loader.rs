@@ -124,6 +124,8 @@ mod tests {
                 namespace: "default".into(),
                 importance: None,
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             }])
         }
 
@@ -235,6 +237,8 @@ mod tests {
                     namespace: "default".into(),
                     importance: None,
                     superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
                 },
                 MemoryEntry {
                     id: "2".into(),
@@ -247,6 +251,8 @@ mod tests {
                     namespace: "default".into(),
                     importance: None,
                     superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
                 },
             ]),
         };
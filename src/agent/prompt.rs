@@ -6,7 +6,7 @@ use crate::security::AutonomyLevel;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
-use chrono::{Datelike, Local, Timelike};
+use chrono::{Datelike, Local};
 use std::fmt::Write;
 use std::path::Path;
 
@@ -268,24 +268,34 @@ impl PromptSection for DateTimeSection {
     }
 
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        let now = Local::now();
-        // Force Gregorian year to avoid confusion with local calendars (e.g. Buddhist calendar).
-        let (year, month, day) = (now.year(), now.month(), now.day());
-        let (hour, minute, second) = (now.hour(), now.minute(), now.second());
-        let tz = now.format("%Z");
-
-        Ok(format!(
-            "## CRITICAL CONTEXT: CURRENT DATE & TIME\n\n\
-             The following is the ABSOLUTE TRUTH regarding the current date and time. \
-             Use this for all relative time calculations (e.g. \"last 7 days\").\n\n\
-             Date: {year:04}-{month:02}-{day:02}\n\
-             Time: {hour:02}:{minute:02}:{second:02} ({tz})\n\
-             ISO 8601: {year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}{}",
-            now.format("%:z")
-        ))
+        Ok(render_datetime_section(&Local::now()))
     }
 }
 
+/// Render the date section from an explicit clock reading.
+///
+/// Deliberately omits hours/minutes/seconds: the system prompt is the cacheable
+/// prefix for providers with prompt caching (Anthropic `cache_control` blocks),
+/// and a ticking timestamp here would bust the cache on every turn. The precise
+/// send time is prefixed to each user message instead. The output is byte-stable
+/// for any two clock readings on the same calendar day.
+fn render_datetime_section(now: &chrono::DateTime<Local>) -> String {
+    // Force Gregorian year to avoid confusion with local calendars (e.g. Buddhist calendar).
+    let (year, month, day) = (now.year(), now.month(), now.day());
+    let tz = now.format("%Z");
+
+    format!(
+        "## CRITICAL CONTEXT: CURRENT DATE & TIME\n\n\
+         The following is the ABSOLUTE TRUTH regarding the current date. \
+         Use this for all relative time calculations (e.g. \"last 7 days\").\n\n\
+         Date: {year:04}-{month:02}-{day:02}\n\
+         Timezone: {tz} ({})\n\
+         The exact time of the current message is prefixed to the user turn, \
+         e.g. `[{year:04}-{month:02}-{day:02} 14:05:00 {tz}]`.",
+        now.format("%:z")
+    )
+}
+
 impl PromptSection for ChannelMediaSection {
     fn name(&self) -> &str {
         "channel_media"
@@ -523,7 +533,44 @@ mod tests {
         let payload = rendered.trim_start_matches("## CRITICAL CONTEXT: CURRENT DATE & TIME\n\n");
         assert!(payload.chars().any(|c| c.is_ascii_digit()));
         assert!(payload.contains("Date:"));
-        assert!(payload.contains("Time:"));
+        assert!(payload.contains("Timezone:"));
+    }
+
+    #[test]
+    fn datetime_section_is_byte_stable_within_a_day() {
+        // The system prompt is the cacheable prefix for prompt-caching
+        // providers; two renders on the same calendar day must be identical
+        // bytes so they don't bust the provider's prompt cache.
+        use chrono::TimeZone;
+        let morning = Local.with_ymd_and_hms(2026, 8, 30, 9, 15, 3).unwrap();
+        let evening = Local.with_ymd_and_hms(2026, 8, 30, 21, 44, 58).unwrap();
+        assert_eq!(
+            render_datetime_section(&morning),
+            render_datetime_section(&evening)
+        );
+    }
+
+    #[test]
+    fn datetime_section_changes_when_the_date_changes() {
+        use chrono::TimeZone;
+        let today = Local.with_ymd_and_hms(2026, 8, 30, 12, 0, 0).unwrap();
+        let tomorrow = Local.with_ymd_and_hms(2026, 8, 31, 12, 0, 0).unwrap();
+        assert_ne!(
+            render_datetime_section(&today),
+            render_datetime_section(&tomorrow)
+        );
+    }
+
+    #[test]
+    fn datetime_section_omits_ticking_time() {
+        use chrono::TimeZone;
+        let now = Local.with_ymd_and_hms(2026, 8, 30, 9, 15, 3).unwrap();
+        let rendered = render_datetime_section(&now);
+        assert!(
+            !rendered.contains("09:15:03"),
+            "clock time must stay out of the cacheable prefix"
+        );
+        assert!(rendered.contains("Date: 2026-08-30"));
     }
 
     #[test]
@@ -8,8 +8,9 @@ use crate::security::AutonomyLevel;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -66,6 +67,8 @@ pub struct ApprovalManager {
     /// When `true`, tools that would require interactive approval are
     /// auto-denied instead. Used for channel-driven (non-CLI) runs.
     non_interactive: bool,
+    /// Usernames allowed to answer non-CLI approval prompts (normalized).
+    approval_actors: HashSet<String>,
     /// Session-scoped allowlist built from "Always" responses.
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
@@ -80,6 +83,11 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             non_interactive: false,
+            approval_actors: config
+                .approval_actors
+                .iter()
+                .map(|a| normalize_actor(a))
+                .collect(),
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
         }
@@ -96,6 +104,11 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             non_interactive: true,
+            approval_actors: config
+                .approval_actors
+                .iter()
+                .map(|a| normalize_actor(a))
+                .collect(),
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
         }
@@ -196,6 +209,225 @@ impl ApprovalManager {
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Whether `actor` may answer non-CLI approval prompts (e.g. press a
+    /// Telegram inline-keyboard button). Checked against the
+    /// `autonomy.approval_actors` allowlist; an empty list defers to the
+    /// channel's own user allowlist.
+    pub fn is_non_cli_approval_actor_allowed(&self, actor: &str) -> bool {
+        actor_allowed(&self.approval_actors, actor)
+    }
+
+    /// Register a pending non-CLI approval request in the global registry.
+    ///
+    /// Returns the `apr-…` request id (carried in button callback data) and
+    /// a receiver that fires with the first decision recorded for it.
+    pub fn create_non_cli_pending(
+        &self,
+        tool_name: &str,
+    ) -> (String, tokio::sync::oneshot::Receiver<ApprovalResponse>) {
+        pending_approvals().create(tool_name, self.approval_actors.clone())
+    }
+}
+
+// ── Non-CLI pending approvals ────────────────────────────────────
+
+/// How long a channel approval prompt stays answerable.
+pub const NON_CLI_APPROVAL_TTL_SECS: u64 = 300;
+
+/// Outcome of resolving a pending non-CLI approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveOutcome {
+    /// First decision for this request; the waiting turn has been notified.
+    Resolved(ApprovalResponse),
+    /// A decision was already recorded — late presses change nothing.
+    AlreadyResolved,
+    /// The request outlived its TTL before anyone answered.
+    Expired,
+    /// The actor is not on the `approval_actors` allowlist.
+    ActorNotAllowed,
+    /// No request with this id (never existed, or long since pruned).
+    Unknown,
+}
+
+struct PendingEntry {
+    tool_name: String,
+    allowed_actors: HashSet<String>,
+    expires_at: Instant,
+    resolved: bool,
+    notify: Option<tokio::sync::oneshot::Sender<ApprovalResponse>>,
+}
+
+/// Registry of approval requests awaiting a decision from a channel.
+///
+/// Global for the same reason channel listeners are long-lived singletons:
+/// the listener that receives a button press holds no reference to the
+/// agent turn that asked the question, so the id in the callback data is
+/// the only join key.
+#[derive(Default)]
+pub struct PendingApprovals {
+    entries: Mutex<HashMap<String, PendingEntry>>,
+}
+
+/// The process-wide pending-approval registry.
+pub fn pending_approvals() -> &'static PendingApprovals {
+    static REGISTRY: std::sync::OnceLock<PendingApprovals> = std::sync::OnceLock::new();
+    REGISTRY.get_or_init(PendingApprovals::default)
+}
+
+impl PendingApprovals {
+    /// Register a request with the default TTL; see [`Self::create_with_ttl`].
+    pub fn create(
+        &self,
+        tool_name: &str,
+        allowed_actors: HashSet<String>,
+    ) -> (String, tokio::sync::oneshot::Receiver<ApprovalResponse>) {
+        self.create_with_ttl(
+            tool_name,
+            allowed_actors,
+            Duration::from_secs(NON_CLI_APPROVAL_TTL_SECS),
+        )
+    }
+
+    /// Register a request, returning its `apr-…` id and the decision receiver.
+    pub fn create_with_ttl(
+        &self,
+        tool_name: &str,
+        allowed_actors: HashSet<String>,
+        ttl: Duration,
+    ) -> (String, tokio::sync::oneshot::Receiver<ApprovalResponse>) {
+        let id = format!("apr-{}", &uuid::Uuid::new_v4().simple().to_string()[..6]);
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        // Prune entries expired long enough ago that a late press no longer
+        // deserves a distinct "expired" answer.
+        entries.retain(|_, e| now < e.expires_at + Duration::from_secs(NON_CLI_APPROVAL_TTL_SECS));
+        entries.insert(
+            id.clone(),
+            PendingEntry {
+                tool_name: tool_name.to_string(),
+                allowed_actors,
+                expires_at: now + ttl,
+                resolved: false,
+                notify: Some(tx),
+            },
+        );
+        (id, rx)
+    }
+
+    /// Tool name behind a pending request, for rendering outcome messages.
+    pub fn tool_name(&self, id: &str) -> Option<String> {
+        self.entries.lock().get(id).map(|e| e.tool_name.clone())
+    }
+
+    /// Snapshot of unresolved, unexpired requests as `(id, tool_name)` pairs,
+    /// sorted by id — for `/approvals`-style listings.
+    pub fn pending(&self) -> Vec<(String, String)> {
+        let now = Instant::now();
+        let entries = self.entries.lock();
+        let mut pending: Vec<(String, String)> = entries
+            .iter()
+            .filter(|(_, e)| !e.resolved && now < e.expires_at)
+            .map(|(id, e)| (id.clone(), e.tool_name.clone()))
+            .collect();
+        pending.sort();
+        pending
+    }
+
+    /// Record `actor`'s decision for a pending request.
+    ///
+    /// Only the first decision wins — repeat presses report
+    /// [`ResolveOutcome::AlreadyResolved`] so a double-tapped button cannot
+    /// approve twice or flip an earlier answer.
+    pub fn resolve(&self, id: &str, actor: &str, decision: ApprovalResponse) -> ResolveOutcome {
+        let mut entries = self.entries.lock();
+        let Some(entry) = entries.get_mut(id) else {
+            return ResolveOutcome::Unknown;
+        };
+        if !actor_allowed(&entry.allowed_actors, actor) {
+            return ResolveOutcome::ActorNotAllowed;
+        }
+        if entry.resolved {
+            return ResolveOutcome::AlreadyResolved;
+        }
+        if Instant::now() >= entry.expires_at {
+            return ResolveOutcome::Expired;
+        }
+        entry.resolved = true;
+        if let Some(tx) = entry.notify.take() {
+            let _ = tx.send(decision);
+        }
+        ResolveOutcome::Resolved(decision)
+    }
+}
+
+/// Maximum number of outbound approval prompts remembered for emoji-reaction
+/// shortcuts. Oldest entries are evicted first.
+pub const APPROVAL_PROMPT_INDEX_CAP: usize = 256;
+
+/// Bounded index mapping outbound approval prompt messages
+/// (keyed by channel + platform message id) to their pending request ids,
+/// so an emoji reaction on a prompt can be resolved like a
+/// `/approve-confirm` or `/approve-deny`.
+#[derive(Default)]
+pub struct ApprovalPromptIndex {
+    entries: Mutex<ApprovalPromptEntries>,
+}
+
+#[derive(Default)]
+struct ApprovalPromptEntries {
+    /// Insertion order for bounded eviction.
+    order: VecDeque<String>,
+    by_key: HashMap<String, String>,
+}
+
+/// Global prompt index shared by channels and the approval flow.
+pub fn approval_prompt_index() -> &'static ApprovalPromptIndex {
+    static INDEX: std::sync::OnceLock<ApprovalPromptIndex> = std::sync::OnceLock::new();
+    INDEX.get_or_init(ApprovalPromptIndex::default)
+}
+
+impl ApprovalPromptIndex {
+    fn key(channel: &str, message_id: &str) -> String {
+        format!("{channel}:{message_id}")
+    }
+
+    /// Remember that `message_id` on `channel` is the prompt for `request_id`.
+    pub fn record(&self, channel: &str, message_id: &str, request_id: &str) {
+        let key = Self::key(channel, message_id);
+        let mut entries = self.entries.lock();
+        if entries
+            .by_key
+            .insert(key.clone(), request_id.to_string())
+            .is_none()
+        {
+            entries.order.push_back(key);
+        }
+        while entries.order.len() > APPROVAL_PROMPT_INDEX_CAP {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.by_key.remove(&oldest);
+            }
+        }
+    }
+
+    /// Pending request id behind a prompt message, if any.
+    pub fn request_id_for(&self, channel: &str, message_id: &str) -> Option<String> {
+        self.entries
+            .lock()
+            .by_key
+            .get(&Self::key(channel, message_id))
+            .cloned()
+    }
+}
+
+/// Normalize an approver identity the way channels do (`@Name` → `name`).
+fn normalize_actor(actor: &str) -> String {
+    actor.trim().trim_start_matches('@').to_ascii_lowercase()
+}
+
+fn actor_allowed(allowlist: &HashSet<String>, actor: &str) -> bool {
+    allowlist.is_empty() || allowlist.contains(&normalize_actor(actor))
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -542,6 +774,99 @@ mod tests {
         assert!(mgr.needs_approval("shell"));
     }
 
+    // ── Non-CLI pending approvals ────────────────────────────
+
+    fn allow_any() -> HashSet<String> {
+        HashSet::new()
+    }
+
+    #[test]
+    fn pending_first_decision_wins_and_repeat_presses_are_ignored() {
+        let registry = PendingApprovals::default();
+        let (id, mut rx) = registry.create("file_write", allow_any());
+        assert!(id.starts_with("apr-"));
+
+        assert_eq!(
+            registry.resolve(&id, "zverozabr", ApprovalResponse::Yes),
+            ResolveOutcome::Resolved(ApprovalResponse::Yes)
+        );
+        assert_eq!(rx.try_recv(), Ok(ApprovalResponse::Yes));
+
+        // A double-tapped button (same or different decision) changes nothing.
+        assert_eq!(
+            registry.resolve(&id, "zverozabr", ApprovalResponse::Yes),
+            ResolveOutcome::AlreadyResolved
+        );
+        assert_eq!(
+            registry.resolve(&id, "zverozabr", ApprovalResponse::No),
+            ResolveOutcome::AlreadyResolved
+        );
+    }
+
+    #[test]
+    fn pending_expired_request_answers_expired_not_resolved() {
+        let registry = PendingApprovals::default();
+        let (id, mut rx) = registry.create_with_ttl("shell", allow_any(), Duration::ZERO);
+
+        assert_eq!(
+            registry.resolve(&id, "zverozabr", ApprovalResponse::Yes),
+            ResolveOutcome::Expired
+        );
+        assert!(rx.try_recv().is_err(), "expired request must not notify");
+    }
+
+    #[test]
+    fn pending_unknown_id_is_unknown() {
+        let registry = PendingApprovals::default();
+        assert_eq!(
+            registry.resolve("apr-ffffff", "zverozabr", ApprovalResponse::Yes),
+            ResolveOutcome::Unknown
+        );
+    }
+
+    #[test]
+    fn pending_actor_allowlist_is_enforced() {
+        let registry = PendingApprovals::default();
+        let allow: HashSet<String> = ["zverozabr".to_string()].into_iter().collect();
+        let (id, _rx) = registry.create("file_write", allow);
+
+        assert_eq!(
+            registry.resolve(&id, "mallory", ApprovalResponse::Yes),
+            ResolveOutcome::ActorNotAllowed
+        );
+        // `@`-prefix and case differences still match the allowlist.
+        assert_eq!(
+            registry.resolve(&id, "@Zverozabr", ApprovalResponse::Yes),
+            ResolveOutcome::Resolved(ApprovalResponse::Yes)
+        );
+    }
+
+    #[test]
+    fn pending_registry_reports_tool_name() {
+        let registry = PendingApprovals::default();
+        let (id, _rx) = registry.create("http_request", allow_any());
+        assert_eq!(registry.tool_name(&id).as_deref(), Some("http_request"));
+        assert_eq!(registry.tool_name("apr-000000"), None);
+    }
+
+    #[test]
+    fn non_cli_actor_allowlist_empty_allows_anyone() {
+        let mgr = ApprovalManager::for_non_interactive(&AutonomyConfig::default());
+        assert!(mgr.is_non_cli_approval_actor_allowed("anyone"));
+    }
+
+    #[test]
+    fn non_cli_actor_allowlist_restricts_and_normalizes() {
+        let config = AutonomyConfig {
+            approval_actors: vec!["@Zverozabr".into()],
+            ..AutonomyConfig::default()
+        };
+        let mgr = ApprovalManager::for_non_interactive(&config);
+        assert!(mgr.is_non_cli_approval_actor_allowed("zverozabr"));
+        assert!(mgr.is_non_cli_approval_actor_allowed("@zverozabr"));
+        assert!(!mgr.is_non_cli_approval_actor_allowed("mallory"));
+    }
+
     // ── ApprovalResponse serde ───────────────────────────────
 
     #[test]
@@ -610,4 +935,54 @@ mod tests {
             "always_ask must override auto_approve"
         );
     }
+
+    // ── Approval prompt index ────────────────────────────────
+
+    #[test]
+    fn prompt_index_records_and_looks_up_by_channel_and_message() {
+        let index = ApprovalPromptIndex::default();
+        index.record("telegram", "42", "apr-one");
+        index.record("slack", "1741234567.000100", "apr-two");
+
+        assert_eq!(
+            index.request_id_for("telegram", "42"),
+            Some("apr-one".to_string())
+        );
+        assert_eq!(
+            index.request_id_for("slack", "1741234567.000100"),
+            Some("apr-two".to_string())
+        );
+        // Same message id on a different channel is a different key.
+        assert_eq!(index.request_id_for("slack", "42"), None);
+        assert_eq!(index.request_id_for("telegram", "43"), None);
+    }
+
+    #[test]
+    fn prompt_index_rerecording_a_message_replaces_without_duplicating() {
+        let index = ApprovalPromptIndex::default();
+        index.record("telegram", "42", "apr-old");
+        index.record("telegram", "42", "apr-new");
+
+        assert_eq!(
+            index.request_id_for("telegram", "42"),
+            Some("apr-new".to_string())
+        );
+        assert_eq!(index.entries.lock().order.len(), 1);
+    }
+
+    #[test]
+    fn prompt_index_evicts_oldest_entries_beyond_cap() {
+        let index = ApprovalPromptIndex::default();
+        for i in 0..=APPROVAL_PROMPT_INDEX_CAP {
+            index.record("telegram", &i.to_string(), &format!("apr-{i}"));
+        }
+
+        // The very first entry was evicted; the newest survives.
+        assert_eq!(index.request_id_for("telegram", "0"), None);
+        assert_eq!(
+            index.request_id_for("telegram", &APPROVAL_PROMPT_INDEX_CAP.to_string()),
+            Some(format!("apr-{APPROVAL_PROMPT_INDEX_CAP}"))
+        );
+        assert_eq!(index.entries.lock().order.len(), APPROVAL_PROMPT_INDEX_CAP);
+    }
 }
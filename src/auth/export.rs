@@ -0,0 +1,335 @@
+// Auth profile export/import for machine migration.
+//
+// `zeroclaw auth export` serializes stored auth profiles to a single JSON
+// file. Profile metadata (ids, providers, kinds, timestamps) is always stored
+// in the clear for auditing; the actual credentials are only included with
+// `--include-secrets`, encrypted with a passphrase-derived key using the same
+// ChaCha20-Poly1305 AEAD the `SecretStore` uses for at-rest secrets. The
+// passphrase is prompted interactively and never appears on argv.
+//
+// Format: `{ version, exported_at, profiles: [metadata...],
+// encrypted_profiles: "authexp1:<hex(salt ‖ nonce ‖ ciphertext ‖ tag)>" }`.
+// The Poly1305 tag makes tampering (or a wrong passphrase) fail cleanly.
+
+use crate::auth::profiles::{AuthProfile, AuthProfileKind, AuthProfilesData};
+use anyhow::{Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Current export file format version.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Prefix marking a passphrase-encrypted profile payload.
+const CIPHERTEXT_PREFIX: &str = "authexp1:";
+
+/// Passphrase KDF iteration count (iterated salted SHA-256).
+const KDF_ITERATIONS: u32 = 100_000;
+
+/// ChaCha20-Poly1305 nonce length in bytes.
+const NONCE_LEN: usize = 12;
+
+/// KDF salt length in bytes.
+const SALT_LEN: usize = 32;
+
+/// Cleartext per-profile metadata, safe to inspect without a passphrase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileMetadata {
+    pub id: String,
+    pub provider: String,
+    pub profile_name: String,
+    pub kind: AuthProfileKind,
+    pub account_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// On-disk export file layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthExportFile {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub profiles: Vec<ProfileMetadata>,
+    /// Passphrase-encrypted profile payload; absent for metadata-only exports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_profiles: Option<String>,
+}
+
+/// Decrypted credential payload of an export with secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedProfiles {
+    pub profiles: BTreeMap<String, AuthProfile>,
+    pub active_profiles: BTreeMap<String, String>,
+}
+
+/// Build an export of `data`. With a passphrase the full profiles (including
+/// tokens) are encrypted into the payload; without one only metadata is kept.
+pub fn build_export(data: &AuthProfilesData, passphrase: Option<&str>) -> Result<AuthExportFile> {
+    let metadata = data
+        .profiles
+        .values()
+        .map(|profile| ProfileMetadata {
+            id: profile.id.clone(),
+            provider: profile.provider.clone(),
+            profile_name: profile.profile_name.clone(),
+            kind: profile.kind,
+            account_id: profile.account_id.clone(),
+            created_at: profile.created_at,
+            updated_at: profile.updated_at,
+        })
+        .collect();
+
+    let encrypted_profiles = match passphrase {
+        Some(passphrase) => {
+            let payload = ImportedProfiles {
+                profiles: data.profiles.clone(),
+                active_profiles: data.active_profiles.clone(),
+            };
+            let plaintext =
+                serde_json::to_vec(&payload).context("Failed to serialize auth profiles")?;
+            Some(seal(&plaintext, passphrase)?)
+        }
+        None => None,
+    };
+
+    Ok(AuthExportFile {
+        version: EXPORT_VERSION,
+        exported_at: Utc::now(),
+        profiles: metadata,
+        encrypted_profiles,
+    })
+}
+
+/// Write an export file with owner-only (0600) permissions.
+pub fn write_export_file(path: &Path, export: &AuthExportFile) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(export).context("Failed to serialize auth export file")?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write export file: {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .context("Failed to set export file permissions")?;
+    }
+
+    Ok(())
+}
+
+/// Read and parse an export file, validating the format version.
+pub fn read_export_file(path: &Path) -> Result<AuthExportFile> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read export file: {}", path.display()))?;
+    let export: AuthExportFile =
+        serde_json::from_str(&json).context("Export file is not a valid zeroclaw auth export")?;
+    anyhow::ensure!(
+        export.version == EXPORT_VERSION,
+        "Unsupported auth export version {} (this build supports {EXPORT_VERSION})",
+        export.version
+    );
+    Ok(export)
+}
+
+/// Decrypt the credential payload of an export created with `--include-secrets`.
+pub fn decrypt_export(export: &AuthExportFile, passphrase: &str) -> Result<ImportedProfiles> {
+    let Some(ciphertext) = export.encrypted_profiles.as_deref() else {
+        anyhow::bail!(
+            "This export contains only profile metadata. \
+             Re-run `zeroclaw auth export --include-secrets` on the source machine to migrate credentials."
+        );
+    };
+    let plaintext = open(ciphertext, passphrase)?;
+    serde_json::from_slice(&plaintext).context("Decrypted export payload is not valid JSON")
+}
+
+/// Derive a 256-bit key from a passphrase via iterated salted SHA-256.
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 32];
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(passphrase.as_bytes());
+    block.copy_from_slice(&hasher.finalize());
+    for _ in 0..KDF_ITERATIONS {
+        let mut hasher = Sha256::new();
+        hasher.update(block);
+        hasher.update(passphrase.as_bytes());
+        block.copy_from_slice(&hasher.finalize());
+    }
+    block
+}
+
+/// Encrypt `plaintext` with a passphrase-derived key.
+/// Output: `authexp1:<hex(salt ‖ nonce ‖ ciphertext ‖ tag)>`.
+fn seal(plaintext: &[u8], passphrase: &str) -> Result<String> {
+    let salt = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let key_bytes = derive_key(passphrase, &salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("Export encryption failed: {e}"))?;
+
+    let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(format!("{CIPHERTEXT_PREFIX}{}", hex_encode(&blob)))
+}
+
+/// Decrypt an `authexp1:` payload. Fails cleanly on a wrong passphrase or
+/// tampered ciphertext thanks to the Poly1305 authentication tag.
+fn open(value: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let hex_str = value
+        .strip_prefix(CIPHERTEXT_PREFIX)
+        .context("Export payload has an unrecognized encryption format")?;
+    let blob = hex_decode(hex_str).context("Export payload is corrupt (invalid hex)")?;
+    anyhow::ensure!(
+        blob.len() > SALT_LEN + NONCE_LEN,
+        "Export payload is truncated"
+    );
+
+    let (salt, rest) = blob.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+    let key_bytes = derive_key(passphrase, salt);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Decryption failed — wrong passphrase or tampered export"))
+}
+
+/// Hex-encode bytes to a lowercase hex string.
+fn hex_encode(data: &[u8]) -> String {
+    let mut s = String::with_capacity(data.len() * 2);
+    for b in data {
+        use std::fmt::Write;
+        let _ = write!(s, "{b:02x}");
+    }
+    s
+}
+
+/// Hex-decode a hex string to bytes.
+#[allow(clippy::manual_is_multiple_of)]
+fn hex_decode(hex: &str) -> Result<Vec<u8>> {
+    if (hex.len() & 1) != 0 {
+        anyhow::bail!("Hex string has odd length");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| anyhow::anyhow!("Invalid hex at position {i}: {e}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_data() -> AuthProfilesData {
+        let mut data = AuthProfilesData::default();
+        let profile = AuthProfile::new_token("openrouter", "default", "sk-secret-token".into());
+        data.active_profiles
+            .insert("openrouter".into(), profile.id.clone());
+        data.profiles.insert(profile.id.clone(), profile);
+        data
+    }
+
+    #[test]
+    fn export_import_roundtrip_preserves_profiles() {
+        let data = sample_data();
+        let export = build_export(&data, Some("correct horse")).unwrap();
+
+        // Serialize through JSON as the CLI does.
+        let json = serde_json::to_string(&export).unwrap();
+        let parsed: AuthExportFile = serde_json::from_str(&json).unwrap();
+
+        let imported = decrypt_export(&parsed, "correct horse").unwrap();
+        assert_eq!(imported.profiles.len(), 1);
+        let profile = &imported.profiles["openrouter:default"];
+        assert_eq!(profile.token.as_deref(), Some("sk-secret-token"));
+        assert_eq!(imported.active_profiles["openrouter"], "openrouter:default");
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_cleanly() {
+        let export = build_export(&sample_data(), Some("correct horse")).unwrap();
+        let err = decrypt_export(&export, "battery staple").unwrap_err();
+        assert!(
+            err.to_string().contains("wrong passphrase or tampered"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let mut export = build_export(&sample_data(), Some("correct horse")).unwrap();
+
+        // Flip one byte inside the ciphertext portion (past salt + nonce).
+        let value = export.encrypted_profiles.take().unwrap();
+        let hex_str = value.strip_prefix(CIPHERTEXT_PREFIX).unwrap();
+        let mut blob = hex_decode(hex_str).unwrap();
+        blob[SALT_LEN + NONCE_LEN] ^= 0xff;
+        export.encrypted_profiles = Some(format!("{CIPHERTEXT_PREFIX}{}", hex_encode(&blob)));
+
+        let err = decrypt_export(&export, "correct horse").unwrap_err();
+        assert!(
+            err.to_string().contains("wrong passphrase or tampered"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn metadata_only_export_contains_no_token_material() {
+        let export = build_export(&sample_data(), None).unwrap();
+        assert!(export.encrypted_profiles.is_none());
+        assert_eq!(export.profiles.len(), 1);
+        assert_eq!(export.profiles[0].id, "openrouter:default");
+
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(
+            !json.contains("sk-secret-token"),
+            "metadata-only export must not leak tokens"
+        );
+
+        let err = decrypt_export(&export, "anything").unwrap_err();
+        assert!(err.to_string().contains("only profile metadata"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn export_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("auth-export.json");
+        let export = build_export(&sample_data(), Some("pass")).unwrap();
+        write_export_file(&path, &export).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "Export file must be 0600");
+
+        let parsed = read_export_file(&path).unwrap();
+        let imported = decrypt_export(&parsed, "pass").unwrap();
+        assert_eq!(imported.profiles.len(), 1);
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("auth-export.json");
+        let mut export = build_export(&sample_data(), None).unwrap();
+        export.version = 99;
+        let json = serde_json::to_string(&export).unwrap();
+        std::fs::write(&path, json).unwrap();
+
+        let err = read_export_file(&path).unwrap_err();
+        assert!(err.to_string().contains("Unsupported auth export version"));
+    }
+}
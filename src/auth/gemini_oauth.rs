@@ -183,6 +183,15 @@ pub async fn exchange_code_for_tokens(
 }
 
 pub async fn refresh_access_token(client: &Client, refresh_token: &str) -> Result<TokenSet> {
+    refresh_access_token_at(client, GOOGLE_OAUTH_TOKEN_URL, refresh_token).await
+}
+
+/// Refresh against a specific token endpoint (mockable in tests).
+pub(crate) async fn refresh_access_token_at(
+    client: &Client,
+    token_url: &str,
+    refresh_token: &str,
+) -> Result<TokenSet> {
     let (client_id, client_secret) = get_oauth_credentials()?;
     let form = [
         ("grant_type", "refresh_token"),
@@ -192,7 +201,7 @@ pub async fn refresh_access_token(client: &Client, refresh_token: &str) -> Resul
     ];
 
     let response = client
-        .post(GOOGLE_OAUTH_TOKEN_URL)
+        .post(token_url)
         .form(&form)
         .send()
         .await
@@ -1,14 +1,14 @@
 pub mod anthropic_token;
+pub mod export;
 pub mod gemini_oauth;
 pub mod oauth_common;
 pub mod openai_oauth;
 pub mod profiles;
 
-use crate::auth::openai_oauth::refresh_access_token;
 use crate::auth::profiles::{
     profile_id, AuthProfile, AuthProfileKind, AuthProfilesData, AuthProfilesStore, TokenSet,
 };
-use crate::config::Config;
+use crate::config::{AuthRotationConfig, Config};
 use anyhow::Result;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -24,30 +24,54 @@ const OPENAI_REFRESH_FAILURE_BACKOFF_SECS: u64 = 10;
 const OAUTH_REFRESH_MAX_ATTEMPTS: usize = 3;
 const OAUTH_REFRESH_RETRY_BASE_DELAY_MS: u64 = 350;
 static REFRESH_BACKOFFS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+static PROFILE_COOLDOWNS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+static PROFILE_LAST_USED: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+static ROTATION_CURSORS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
 
 #[derive(Clone)]
 pub struct AuthService {
     store: AuthProfilesStore,
     client: reqwest::Client,
+    openai_token_url: String,
+    gemini_token_url: String,
+    rotation: AuthRotationConfig,
 }
 
 impl AuthService {
     pub fn from_config(config: &Config) -> Self {
         let state_dir = state_dir_from_config(config);
-        Self::new(&state_dir, config.secrets.encrypt)
+        let mut service = Self::new(&state_dir, config.secrets.encrypt);
+        service.rotation = config.auth.rotation.clone();
+        service
     }
 
     pub fn new(state_dir: &Path, encrypt_secrets: bool) -> Self {
         Self {
             store: AuthProfilesStore::new(state_dir, encrypt_secrets),
             client: reqwest::Client::new(),
+            openai_token_url: openai_oauth::OPENAI_OAUTH_TOKEN_URL.to_string(),
+            gemini_token_url: gemini_oauth::GOOGLE_OAUTH_TOKEN_URL.to_string(),
+            rotation: AuthRotationConfig::default(),
         }
     }
 
+    /// Point token refreshes at mock endpoints (tests only).
+    #[cfg(test)]
+    fn with_token_urls(mut self, openai_token_url: &str, gemini_token_url: &str) -> Self {
+        self.openai_token_url = openai_token_url.to_string();
+        self.gemini_token_url = gemini_token_url.to_string();
+        self
+    }
+
     pub async fn load_profiles(&self) -> Result<AuthProfilesData> {
         self.store.load().await
     }
 
+    /// Insert or replace a full profile, e.g. from `zeroclaw auth import`.
+    pub async fn upsert_profile(&self, profile: AuthProfile, set_active: bool) -> Result<()> {
+        self.store.upsert_profile(profile, set_active).await
+    }
+
     pub async fn store_openai_tokens(
         &self,
         profile_name: &str,
@@ -210,20 +234,25 @@ impl AuthService {
             );
         }
 
-        let mut refreshed =
-            match refresh_openai_access_token_with_retries(&self.client, &refresh_token).await {
-                Ok(tokens) => {
-                    clear_refresh_backoff(&profile_id);
-                    tokens
-                }
-                Err(err) => {
-                    set_refresh_backoff(
-                        &profile_id,
-                        Duration::from_secs(OPENAI_REFRESH_FAILURE_BACKOFF_SECS),
-                    );
-                    return Err(err);
-                }
-            };
+        let mut refreshed = match refresh_openai_access_token_with_retries(
+            &self.client,
+            &self.openai_token_url,
+            &refresh_token,
+        )
+        .await
+        {
+            Ok(tokens) => {
+                clear_refresh_backoff(&profile_id);
+                tokens
+            }
+            Err(err) => {
+                set_refresh_backoff(
+                    &profile_id,
+                    Duration::from_secs(OPENAI_REFRESH_FAILURE_BACKOFF_SECS),
+                );
+                return Err(err);
+            }
+        };
         if refreshed.refresh_token.is_none() {
             refreshed
                 .refresh_token
@@ -299,20 +328,25 @@ impl AuthService {
             );
         }
 
-        let mut refreshed =
-            match refresh_gemini_access_token_with_retries(&self.client, &refresh_token).await {
-                Ok(tokens) => {
-                    clear_refresh_backoff(&profile_id);
-                    tokens
-                }
-                Err(err) => {
-                    set_refresh_backoff(
-                        &profile_id,
-                        Duration::from_secs(OPENAI_REFRESH_FAILURE_BACKOFF_SECS),
-                    );
-                    return Err(err);
-                }
-            };
+        let mut refreshed = match refresh_gemini_access_token_with_retries(
+            &self.client,
+            &self.gemini_token_url,
+            &refresh_token,
+        )
+        .await
+        {
+            Ok(tokens) => {
+                clear_refresh_backoff(&profile_id);
+                tokens
+            }
+            Err(err) => {
+                set_refresh_backoff(
+                    &profile_id,
+                    Duration::from_secs(OPENAI_REFRESH_FAILURE_BACKOFF_SECS),
+                );
+                return Err(err);
+            }
+        };
         if refreshed.refresh_token.is_none() {
             refreshed
                 .refresh_token
@@ -338,6 +372,226 @@ impl AuthService {
         Ok(updated.token_set.map(|t| t.access_token))
     }
 
+    /// Pick the next usable profile for `provider` per the configured
+    /// rotation strategy, skipping profiles that are cooling down after a
+    /// quota error.
+    ///
+    /// Returns `None` when rotation is disabled or when `pinned` carries an
+    /// explicit per-agent profile override — rotation never overrides a
+    /// pinned profile. Callers then fall back to the normal active-profile
+    /// selection.
+    pub async fn next_rotation_profile(
+        &self,
+        provider: &str,
+        pinned: Option<&str>,
+    ) -> Result<Option<String>> {
+        if !self.rotation.enabled || pinned.is_some() {
+            return Ok(None);
+        }
+
+        let provider = normalize_provider(provider)?;
+        let data = self.store.load().await?;
+        let candidates = rotation_candidates(&data, &provider);
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let active = data.active_profiles.get(&provider).cloned();
+        let chosen = select_rotation_profile(
+            &self.rotation.strategy,
+            &provider,
+            &candidates,
+            active.as_deref(),
+        );
+        if let Some(id) = &chosen {
+            mark_profile_used(&provider, id);
+        }
+        Ok(chosen)
+    }
+
+    /// Cool down `used_profile` after a quota error and pick a replacement.
+    ///
+    /// The exhausted profile is skipped for the configured cooldown period.
+    /// Returns the next usable profile for the same provider, or `None` when
+    /// rotation is disabled or no other profile is available — the caller
+    /// then surfaces the quota error to the reliability layer.
+    pub async fn handle_quota_error(
+        &self,
+        provider: &str,
+        used_profile: Option<&str>,
+    ) -> Result<Option<String>> {
+        if !self.rotation.enabled {
+            return Ok(None);
+        }
+
+        let provider = normalize_provider(provider)?;
+        let data = self.store.load().await?;
+
+        let exhausted = used_profile
+            .map(|profile| resolve_requested_profile_id(&provider, profile))
+            .or_else(|| select_profile_id(&data, &provider, None));
+        let Some(exhausted) = exhausted else {
+            return Ok(None);
+        };
+
+        set_profile_cooldown(&exhausted, Duration::from_secs(self.rotation.cooldown_secs));
+        tracing::warn!(
+            "Auth profile {exhausted} cooling down for {}s after quota error",
+            self.rotation.cooldown_secs
+        );
+
+        let candidates = rotation_candidates(&data, &provider);
+        let active = data.active_profiles.get(&provider).cloned();
+        let next = select_rotation_profile(
+            &self.rotation.strategy,
+            &provider,
+            &candidates,
+            active.as_deref(),
+        );
+        if let Some(id) = &next {
+            mark_profile_used(&provider, id);
+        }
+        Ok(next)
+    }
+
+    /// Refresh every OAuth profile whose access token expires within `window`.
+    ///
+    /// Returns one outcome per candidate profile; a failing profile is
+    /// reported in its outcome instead of aborting the rest of the scan.
+    pub async fn refresh_expiring_oauth_profiles(
+        &self,
+        window: Duration,
+    ) -> Result<Vec<TokenRefreshOutcome>> {
+        let data = self.store.load().await?;
+
+        let candidates: Vec<(String, String)> = data
+            .profiles
+            .iter()
+            .filter(|(_, profile)| {
+                profile.kind == AuthProfileKind::OAuth
+                    && matches!(
+                        profile.provider.as_str(),
+                        OPENAI_CODEX_PROVIDER | GEMINI_PROVIDER
+                    )
+                    && profile.token_set.as_ref().is_some_and(|tokens| {
+                        tokens.refresh_token.is_some() && tokens.is_expiring_within(window)
+                    })
+            })
+            .map(|(profile_id, profile)| (profile_id.clone(), profile.provider.clone()))
+            .collect();
+
+        let mut outcomes = Vec::with_capacity(candidates.len());
+        for (profile_id, provider) in candidates {
+            let result = self
+                .refresh_oauth_profile(&provider, &profile_id, window)
+                .await;
+            outcomes.push(TokenRefreshOutcome {
+                profile_id,
+                provider,
+                result,
+            });
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Refresh a single OAuth profile if it is still expiring within `window`.
+    ///
+    /// Returns `Ok(false)` when no refresh was needed (another caller already
+    /// refreshed it while we waited for the per-profile lock).
+    async fn refresh_oauth_profile(
+        &self,
+        provider: &str,
+        profile_id: &str,
+        window: Duration,
+    ) -> Result<bool> {
+        let refresh_lock = refresh_lock_for_profile(profile_id);
+        let _guard = refresh_lock.lock().await;
+
+        // Re-load after waiting for lock to avoid duplicate refreshes.
+        let data = self.store.load().await?;
+        let Some(latest_profile) = data.profiles.get(profile_id) else {
+            return Ok(false);
+        };
+
+        let Some(latest_tokens) = latest_profile.token_set.as_ref() else {
+            return Ok(false);
+        };
+
+        if !latest_tokens.is_expiring_within(window) {
+            return Ok(false);
+        }
+
+        let Some(refresh_token) = latest_tokens.refresh_token.clone() else {
+            return Ok(false);
+        };
+
+        if let Some(remaining) = refresh_backoff_remaining(profile_id) {
+            anyhow::bail!("token refresh is in backoff for {remaining}s due to previous failures");
+        }
+
+        let refresh_result = match provider {
+            OPENAI_CODEX_PROVIDER => {
+                refresh_openai_access_token_with_retries(
+                    &self.client,
+                    &self.openai_token_url,
+                    &refresh_token,
+                )
+                .await
+            }
+            GEMINI_PROVIDER => {
+                refresh_gemini_access_token_with_retries(
+                    &self.client,
+                    &self.gemini_token_url,
+                    &refresh_token,
+                )
+                .await
+            }
+            other => anyhow::bail!("Unsupported OAuth provider for token refresh: {other}"),
+        };
+
+        let mut refreshed = match refresh_result {
+            Ok(tokens) => {
+                clear_refresh_backoff(profile_id);
+                tokens
+            }
+            Err(err) => {
+                set_refresh_backoff(
+                    profile_id,
+                    Duration::from_secs(OPENAI_REFRESH_FAILURE_BACKOFF_SECS),
+                );
+                return Err(err);
+            }
+        };
+        if refreshed.refresh_token.is_none() {
+            refreshed
+                .refresh_token
+                .clone_from(&latest_tokens.refresh_token);
+        }
+
+        let account_id = match provider {
+            OPENAI_CODEX_PROVIDER => {
+                openai_oauth::extract_account_id_from_jwt(&refreshed.access_token)
+            }
+            _ => refreshed
+                .id_token
+                .as_deref()
+                .and_then(gemini_oauth::extract_account_email_from_id_token),
+        }
+        .or_else(|| latest_profile.account_id.clone());
+
+        self.store
+            .update_profile(profile_id, |profile| {
+                profile.kind = AuthProfileKind::OAuth;
+                profile.token_set = Some(refreshed.clone());
+                profile.account_id.clone_from(&account_id);
+                Ok(())
+            })
+            .await?;
+
+        Ok(true)
+    }
+
     /// Get Gemini profile info (for provider initialization).
     pub async fn get_gemini_profile(
         &self,
@@ -347,6 +601,16 @@ impl AuthService {
     }
 }
 
+/// Result of a single background refresh attempt for one auth profile.
+///
+/// `result` is `Ok(true)` when tokens were refreshed and persisted, and
+/// `Ok(false)` when the profile turned out not to need a refresh.
+pub struct TokenRefreshOutcome {
+    pub profile_id: String,
+    pub provider: String,
+    pub result: Result<bool>,
+}
+
 pub fn normalize_provider(provider: &str) -> Result<String> {
     let normalized = provider.trim().to_ascii_lowercase();
     match normalized.as_str() {
@@ -408,12 +672,13 @@ pub fn select_profile_id(
 
 async fn refresh_openai_access_token_with_retries(
     client: &reqwest::Client,
+    token_url: &str,
     refresh_token: &str,
 ) -> Result<TokenSet> {
     let mut last_error: Option<anyhow::Error> = None;
 
     for attempt in 1..=OAUTH_REFRESH_MAX_ATTEMPTS {
-        match refresh_access_token(client, refresh_token).await {
+        match openai_oauth::refresh_access_token_at(client, token_url, refresh_token).await {
             Ok(tokens) => return Ok(tokens),
             Err(err) => {
                 let should_retry = attempt < OAUTH_REFRESH_MAX_ATTEMPTS;
@@ -440,12 +705,13 @@ async fn refresh_openai_access_token_with_retries(
 
 async fn refresh_gemini_access_token_with_retries(
     client: &reqwest::Client,
+    token_url: &str,
     refresh_token: &str,
 ) -> Result<TokenSet> {
     let mut last_error: Option<anyhow::Error> = None;
 
     for attempt in 1..=OAUTH_REFRESH_MAX_ATTEMPTS {
-        match gemini_oauth::refresh_access_token(client, refresh_token).await {
+        match gemini_oauth::refresh_access_token_at(client, token_url, refresh_token).await {
             Ok(tokens) => return Ok(tokens),
             Err(err) => {
                 let should_retry = attempt < OAUTH_REFRESH_MAX_ATTEMPTS;
@@ -508,6 +774,96 @@ fn clear_refresh_backoff(profile_id: &str) {
     }
 }
 
+/// Seconds until a quota-exhausted profile becomes usable again, if it is
+/// currently cooling down. Used by `zeroclaw auth status`.
+pub fn profile_cooldown_remaining(profile_id: &str) -> Option<u64> {
+    let map = PROFILE_COOLDOWNS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut guard = map.lock().ok()?;
+    let now = Instant::now();
+    let deadline = guard.get(profile_id).copied()?;
+    if deadline <= now {
+        guard.remove(profile_id);
+        return None;
+    }
+    Some((deadline - now).as_secs().max(1))
+}
+
+fn set_profile_cooldown(profile_id: &str, duration: Duration) {
+    let map = PROFILE_COOLDOWNS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut guard) = map.lock() {
+        guard.insert(profile_id.to_string(), Instant::now() + duration);
+    }
+}
+
+/// Profiles of `provider` that are eligible for rotation: every stored
+/// profile for that provider minus the ones still cooling down, in stable
+/// (sorted) id order.
+fn rotation_candidates(data: &AuthProfilesData, provider: &str) -> Vec<String> {
+    data.profiles
+        .iter()
+        .filter(|(id, profile)| {
+            profile.provider == provider && profile_cooldown_remaining(id).is_none()
+        })
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+fn select_rotation_profile(
+    strategy: &str,
+    provider: &str,
+    candidates: &[String],
+    active: Option<&str>,
+) -> Option<String> {
+    if candidates.is_empty() {
+        return None;
+    }
+
+    match strategy {
+        "least_recent" => {
+            let last_used = PROFILE_LAST_USED.get_or_init(|| Mutex::new(HashMap::new()));
+            let guard = last_used.lock().ok()?;
+            candidates
+                .iter()
+                .min_by_key(|id| guard.get(id.as_str()).copied())
+                .cloned()
+        }
+        "on_quota_error" => {
+            // Stay on the active (pinned) profile until it hits quota.
+            active
+                .filter(|id| candidates.iter().any(|c| c == id))
+                .map(ToString::to_string)
+                .or_else(|| candidates.first().cloned())
+        }
+        // round_robin (default): advance past the previously used profile.
+        _ => {
+            let cursors = ROTATION_CURSORS.get_or_init(|| Mutex::new(HashMap::new()));
+            let guard = cursors.lock().ok()?;
+            let start = guard
+                .get(provider)
+                .and_then(|last| candidates.iter().position(|c| c == last))
+                .map(|pos| (pos + 1) % candidates.len());
+            match start {
+                Some(pos) => candidates.get(pos).cloned(),
+                None => active
+                    .filter(|id| candidates.iter().any(|c| c == id))
+                    .map(ToString::to_string)
+                    .or_else(|| candidates.first().cloned()),
+            }
+        }
+    }
+}
+
+fn mark_profile_used(provider: &str, profile_id: &str) {
+    let last_used = PROFILE_LAST_USED.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut guard) = last_used.lock() {
+        guard.insert(profile_id.to_string(), Instant::now());
+    }
+    let cursors = ROTATION_CURSORS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut guard) = cursors.lock() {
+        guard.insert(provider.to_string(), profile_id.to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -571,4 +927,231 @@ mod tests {
             Some(id_active)
         );
     }
+
+    fn rotation_service(state_dir: &Path, strategy: &str) -> AuthService {
+        let mut service = AuthService::new(state_dir, false);
+        service.rotation = AuthRotationConfig {
+            enabled: true,
+            strategy: strategy.into(),
+            cooldown_secs: 300,
+        };
+        service
+    }
+
+    async fn store_rotation_profiles(service: &AuthService, provider: &str, names: &[&str]) {
+        for name in names {
+            service
+                .store_provider_token(provider, name, "tok", HashMap::new(), false)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn round_robin_rotation_cycles_profiles_starting_from_active() {
+        let tmp = tempfile::tempdir().unwrap();
+        let service = rotation_service(tmp.path(), "round_robin");
+        store_rotation_profiles(&service, "rotation-rr", &["a", "b"]).await;
+        service
+            .set_active_profile("rotation-rr", "a")
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(
+                service
+                    .next_rotation_profile("rotation-rr", None)
+                    .await
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        assert_eq!(seen, ["rotation-rr:a", "rotation-rr:b", "rotation-rr:a"]);
+    }
+
+    #[tokio::test]
+    async fn least_recent_rotation_prefers_longest_idle_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let service = rotation_service(tmp.path(), "least_recent");
+        store_rotation_profiles(&service, "rotation-lr", &["a", "b"]).await;
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(
+                service
+                    .next_rotation_profile("rotation-lr", None)
+                    .await
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        assert_eq!(seen, ["rotation-lr:a", "rotation-lr:b", "rotation-lr:a"]);
+    }
+
+    #[tokio::test]
+    async fn rotation_respects_pinned_profiles_and_disabled_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let service = rotation_service(tmp.path(), "round_robin");
+        store_rotation_profiles(&service, "rotation-pin", &["a", "b"]).await;
+
+        // Explicit per-agent override wins over rotation.
+        assert!(service
+            .next_rotation_profile("rotation-pin", Some("b"))
+            .await
+            .unwrap()
+            .is_none());
+
+        // Disabled rotation always falls back to normal profile selection.
+        let disabled = AuthService::new(tmp.path(), false);
+        assert!(disabled
+            .next_rotation_profile("rotation-pin", None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn quota_error_cools_down_profile_and_rotates_to_next() {
+        let tmp = tempfile::tempdir().unwrap();
+        let service = rotation_service(tmp.path(), "on_quota_error");
+        store_rotation_profiles(&service, "rotation-cd", &["a", "b"]).await;
+        service
+            .set_active_profile("rotation-cd", "a")
+            .await
+            .unwrap();
+
+        // The pinned active profile is used until it hits quota.
+        assert_eq!(
+            service
+                .next_rotation_profile("rotation-cd", None)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("rotation-cd:a")
+        );
+
+        let next = service
+            .handle_quota_error("rotation-cd", Some("rotation-cd:a"))
+            .await
+            .unwrap();
+        assert_eq!(next.as_deref(), Some("rotation-cd:b"));
+        assert!(profile_cooldown_remaining("rotation-cd:a").is_some());
+
+        // With every profile cooling down there is nothing left to rotate to.
+        let exhausted = service
+            .handle_quota_error("rotation-cd", Some("rotation-cd:b"))
+            .await
+            .unwrap();
+        assert!(exhausted.is_none());
+    }
+
+    #[test]
+    fn profile_cooldowns_expire() {
+        set_profile_cooldown("rotation-exp:x", Duration::from_millis(50));
+        assert!(profile_cooldown_remaining("rotation-exp:x").is_some());
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(profile_cooldown_remaining("rotation-exp:x").is_none());
+    }
+
+    fn near_expiry_token_set(expires_in_secs: i64) -> TokenSet {
+        TokenSet {
+            access_token: "old-access".into(),
+            refresh_token: Some("old-refresh".into()),
+            id_token: None,
+            expires_at: Some(chrono::Utc::now() + chrono::Duration::seconds(expires_in_secs)),
+            token_type: Some("Bearer".into()),
+            scope: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_expiring_profiles_refreshes_near_expiry_openai_profile() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "new-access",
+                "refresh_token": "new-refresh",
+                "expires_in": 3600,
+                "token_type": "Bearer"
+            })))
+            .mount(&server)
+            .await;
+
+        let auth =
+            AuthService::new(tmp.path(), false).with_token_urls(&server.uri(), &server.uri());
+        auth.store_openai_tokens("bg-refresh-ok", near_expiry_token_set(60), None, true)
+            .await
+            .unwrap();
+
+        let outcomes = auth
+            .refresh_expiring_oauth_profiles(Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].provider, OPENAI_CODEX_PROVIDER);
+        assert!(matches!(outcomes[0].result, Ok(true)));
+
+        let data = auth.load_profiles().await.unwrap();
+        let tokens = data.profiles[&outcomes[0].profile_id]
+            .token_set
+            .clone()
+            .unwrap();
+        assert_eq!(tokens.access_token, "new-access");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("new-refresh"));
+    }
+
+    #[tokio::test]
+    async fn refresh_expiring_profiles_skips_fresh_profiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let auth = AuthService::new(tmp.path(), false);
+        auth.store_openai_tokens("bg-refresh-fresh", near_expiry_token_set(3600), None, true)
+            .await
+            .unwrap();
+
+        let outcomes = auth
+            .refresh_expiring_oauth_profiles(Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert!(outcomes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn refresh_expiring_profiles_reports_per_profile_failures() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+
+        let auth =
+            AuthService::new(tmp.path(), false).with_token_urls(&server.uri(), &server.uri());
+        auth.store_openai_tokens("bg-refresh-err", near_expiry_token_set(60), None, true)
+            .await
+            .unwrap();
+
+        let outcomes = auth
+            .refresh_expiring_oauth_profiles(Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert_eq!(outcomes.len(), 1);
+        let err = outcomes[0].result.as_ref().unwrap_err();
+        assert!(err.to_string().contains("500"), "unexpected error: {err}");
+
+        // The stored tokens are left untouched on failure.
+        let data = auth.load_profiles().await.unwrap();
+        let tokens = data.profiles[&outcomes[0].profile_id]
+            .token_set
+            .clone()
+            .unwrap();
+        assert_eq!(tokens.access_token, "old-access");
+    }
 }
@@ -112,6 +112,15 @@ pub async fn exchange_code_for_tokens(
 }
 
 pub async fn refresh_access_token(client: &Client, refresh_token: &str) -> Result<TokenSet> {
+    refresh_access_token_at(client, OPENAI_OAUTH_TOKEN_URL, refresh_token).await
+}
+
+/// Refresh against a specific token endpoint (mockable in tests).
+pub(crate) async fn refresh_access_token_at(
+    client: &Client,
+    token_url: &str,
+    refresh_token: &str,
+) -> Result<TokenSet> {
     let form = [
         ("grant_type", "refresh_token"),
         ("refresh_token", refresh_token),
@@ -119,7 +128,7 @@ pub async fn refresh_access_token(client: &Client, refresh_token: &str) -> Resul
     ];
 
     let response = client
-        .post(OPENAI_OAUTH_TOKEN_URL)
+        .post(token_url)
         .form(&form)
         .send()
         .await
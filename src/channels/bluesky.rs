@@ -253,7 +253,11 @@ impl BlueskyChannel {
             thread_ts: Some(notif.uri.clone()),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
     }
 
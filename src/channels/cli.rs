@@ -50,7 +50,11 @@ impl Channel for CliChannel {
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             };
 
             if tx.send(msg).await.is_err() {
@@ -120,7 +124,11 @@ mod tests {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -142,7 +150,11 @@ mod tests {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -0,0 +1,156 @@
+//! Inbound message deduplication for re-delivered channel updates.
+//!
+//! Telegram and Slack both re-deliver updates when an ack times out, which
+//! would otherwise answer the same message twice (and double-execute tools).
+//! [`MessageDeduper`] remembers recently seen `(channel, message id)` pairs in
+//! a bounded, TTL-expiring map consulted before a message is dispatched. It
+//! lives in the channel runtime context so it survives supervised listener
+//! restarts; it intentionally resets on daemon restart.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bound on remembered message ids so a chatty deployment can't grow
+/// the map without limit; the oldest entry is evicted first.
+const MAX_TRACKED_MESSAGES: usize = 4096;
+
+/// Bounded TTL cache of recently seen `(channel, message id)` pairs.
+pub struct MessageDeduper {
+    ttl: Duration,
+    max_entries: usize,
+    seen: Mutex<HashMap<String, Instant>>,
+}
+
+impl MessageDeduper {
+    /// Create a deduper with the given TTL. A zero TTL disables dedup
+    /// (every message passes through).
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, MAX_TRACKED_MESSAGES)
+    }
+
+    fn with_capacity(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries: max_entries.max(1),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns `true` when dedup is active (non-zero TTL).
+    pub fn enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Record a message and report whether it is new.
+    ///
+    /// Returns `false` for a re-delivery (same channel and id seen within the
+    /// TTL). Messages with an empty id bypass dedup and always return `true`,
+    /// as does everything when dedup is disabled.
+    pub fn record_if_new(&self, channel: &str, message_id: &str) -> bool {
+        if !self.enabled() || message_id.is_empty() {
+            return true;
+        }
+
+        let key = format!("{channel}:{message_id}");
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+
+        seen.retain(|_, seen_at| now.duration_since(*seen_at) < self.ttl);
+
+        if seen.contains_key(&key) {
+            return false;
+        }
+
+        if seen.len() >= self.max_entries {
+            let evict_key = seen
+                .iter()
+                .min_by_key(|(_, seen_at)| *seen_at)
+                .map(|(k, _)| k.clone());
+            if let Some(evict_key) = evict_key {
+                seen.remove(&evict_key);
+            }
+        }
+
+        seen.insert(key, now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn duplicate_within_ttl_is_dropped() {
+        let deduper = MessageDeduper::new(Duration::from_secs(600));
+        assert!(deduper.record_if_new("telegram", "42"));
+        assert!(!deduper.record_if_new("telegram", "42"));
+        // Same id on a different channel is a different message.
+        assert!(deduper.record_if_new("slack", "42"));
+    }
+
+    #[test]
+    fn duplicate_after_ttl_expiry_passes_again() {
+        let deduper = MessageDeduper::new(Duration::from_millis(30));
+        assert!(deduper.record_if_new("telegram", "42"));
+        assert!(!deduper.record_if_new("telegram", "42"));
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(
+            deduper.record_if_new("telegram", "42"),
+            "entry expired after TTL"
+        );
+    }
+
+    #[test]
+    fn empty_ids_and_disabled_dedup_bypass() {
+        let deduper = MessageDeduper::new(Duration::from_secs(600));
+        assert!(deduper.record_if_new("telegram", ""));
+        assert!(
+            deduper.record_if_new("telegram", ""),
+            "empty id never dedups"
+        );
+
+        let disabled = MessageDeduper::new(Duration::ZERO);
+        assert!(!disabled.enabled());
+        assert!(disabled.record_if_new("telegram", "42"));
+        assert!(disabled.record_if_new("telegram", "42"));
+    }
+
+    #[test]
+    fn bounded_capacity_evicts_oldest_first() {
+        let deduper = MessageDeduper::with_capacity(Duration::from_secs(600), 2);
+        assert!(deduper.record_if_new("telegram", "1"));
+        assert!(deduper.record_if_new("telegram", "2"));
+        // Evicts "1" (oldest) to make room.
+        assert!(deduper.record_if_new("telegram", "3"));
+        assert!(deduper.record_if_new("telegram", "1"), "oldest was evicted");
+        assert!(
+            !deduper.record_if_new("telegram", "3"),
+            "newest still cached"
+        );
+    }
+
+    #[tokio::test]
+    async fn survives_listener_task_restart() {
+        // The deduper is shared via the runtime context, not owned by a
+        // listener task — entries recorded by a listener that later dies
+        // must still suppress re-deliveries seen by its replacement.
+        let deduper = Arc::new(MessageDeduper::new(Duration::from_secs(600)));
+
+        let first_listener = {
+            let deduper = Arc::clone(&deduper);
+            tokio::spawn(async move { deduper.record_if_new("telegram", "42") })
+        };
+        assert!(first_listener.await.unwrap());
+
+        // "Restart": a fresh task sees the same re-delivered update.
+        let second_listener = {
+            let deduper = Arc::clone(&deduper);
+            tokio::spawn(async move { deduper.record_if_new("telegram", "42") })
+        };
+        assert!(!second_listener.await.unwrap(), "duplicate across restart");
+    }
+}
@@ -1,3 +1,4 @@
+use super::media_markers::{MediaMarker, MediaMarkerKind};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
@@ -9,6 +10,13 @@ use uuid::Uuid;
 
 const DINGTALK_BOT_CALLBACK_TOPIC: &str = "/v1.0/im/bot/messages/get";
 
+/// Base URL of DingTalk's legacy OpenAPI (token + media upload endpoints).
+const DINGTALK_OAPI_BASE: &str = "https://oapi.dingtalk.com";
+
+/// DingTalk caps a single message body at 4096 characters; longer replies
+/// are split via the shared outbound splitter.
+const DINGTALK_MESSAGE_LIMIT: usize = 4096;
+
 /// DingTalk channel — connects via Stream Mode WebSocket for real-time messages.
 /// Replies are sent through per-message session webhook URLs.
 pub struct DingTalkChannel {
@@ -20,6 +28,11 @@ pub struct DingTalkChannel {
     session_webhooks: Arc<RwLock<HashMap<String, String>>>,
     /// Per-channel proxy URL override.
     proxy_url: Option<String>,
+    /// Cached OpenAPI access token + expiry timestamp, fetched with the same
+    /// client credentials the stream registration uses.
+    token_cache: Arc<RwLock<Option<(String, u64)>>>,
+    /// OpenAPI base URL, overridable for tests.
+    oapi_base: String,
 }
 
 /// Response from DingTalk gateway connection registration.
@@ -29,6 +42,70 @@ struct GatewayResponse {
     ticket: String,
 }
 
+/// Whether outbound content uses markdown constructs worth rendering as a
+/// markdown card. Plain replies go out as `text` so DingTalk doesn't strip
+/// whitespace or mangle literal `#`/`*` characters.
+fn looks_like_markdown(content: &str) -> bool {
+    if content.contains("```") || content.contains("**") || content.contains("](") {
+        return true;
+    }
+    content.lines().any(|line| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with('#')
+            || trimmed.starts_with("- ")
+            || trimmed.starts_with("* ")
+            || trimmed.starts_with("> ")
+            || trimmed.starts_with('|')
+    })
+}
+
+/// Link-card body for a media marker whose target is already a URL — nothing
+/// to upload, DingTalk renders the card natively.
+fn link_card_body(marker: &MediaMarker) -> serde_json::Value {
+    let pic_url = if marker.kind == MediaMarkerKind::Image {
+        marker.target.as_str()
+    } else {
+        ""
+    };
+    serde_json::json!({
+        "msgtype": "link",
+        "link": {
+            "title": marker.kind.marker_name(),
+            "text": marker.target,
+            "messageUrl": marker.target,
+            "picUrl": pic_url,
+        }
+    })
+}
+
+/// Message body referencing an uploaded `media_id`.
+fn media_message_body(kind: MediaMarkerKind, media_id: &str) -> serde_json::Value {
+    match kind {
+        MediaMarkerKind::Image => serde_json::json!({
+            "msgtype": "image",
+            "image": { "media_id": media_id }
+        }),
+        MediaMarkerKind::Voice | MediaMarkerKind::Audio => serde_json::json!({
+            "msgtype": "voice",
+            "voice": { "media_id": media_id }
+        }),
+        MediaMarkerKind::Video | MediaMarkerKind::Document => serde_json::json!({
+            "msgtype": "file",
+            "file": { "media_id": media_id }
+        }),
+    }
+}
+
+/// Media upload `type` parameter for a marker kind.
+fn upload_media_type(kind: MediaMarkerKind) -> &'static str {
+    match kind {
+        MediaMarkerKind::Image => "image",
+        MediaMarkerKind::Voice | MediaMarkerKind::Audio => "voice",
+        MediaMarkerKind::Video => "video",
+        MediaMarkerKind::Document => "file",
+    }
+}
+
 impl DingTalkChannel {
     pub fn new(client_id: String, client_secret: String, allowed_users: Vec<String>) -> Self {
         Self {
@@ -37,6 +114,8 @@ impl DingTalkChannel {
             allowed_users,
             session_webhooks: Arc::new(RwLock::new(HashMap::new())),
             proxy_url: None,
+            token_cache: Arc::new(RwLock::new(None)),
+            oapi_base: DINGTALK_OAPI_BASE.to_string(),
         }
     }
 
@@ -46,6 +125,12 @@ impl DingTalkChannel {
         self
     }
 
+    /// Override the OpenAPI base URL (tests point this at a mock server).
+    pub fn with_oapi_base(mut self, base: String) -> Self {
+        self.oapi_base = base;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_channel_proxy_client("channel.dingtalk", self.proxy_url.as_deref())
     }
@@ -112,37 +197,119 @@ impl DingTalkChannel {
         let gw: GatewayResponse = resp.json().await?;
         Ok(gw)
     }
-}
 
-#[async_trait]
-impl Channel for DingTalkChannel {
-    fn name(&self) -> &str {
-        "dingtalk"
+    /// Get a valid OpenAPI access token, refreshing if expired. Cached so
+    /// repeated media uploads within the token's lifetime reuse one fetch.
+    async fn get_access_token(&self) -> anyhow::Result<String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        {
+            let cache = self.token_cache.read().await;
+            if let Some((ref token, expiry)) = *cache {
+                if now < expiry {
+                    return Ok(token.clone());
+                }
+            }
+        }
+
+        let resp = self
+            .http_client()
+            .get(format!(
+                "{}/gettoken?appkey={}&appsecret={}",
+                self.oapi_base, self.client_id, self.client_secret
+            ))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("DingTalk token request failed ({status}): {err}");
+        }
+
+        let data: serde_json::Value = resp.json().await?;
+        if data.get("errcode").and_then(|c| c.as_i64()).unwrap_or(0) != 0 {
+            anyhow::bail!("DingTalk token request rejected: {data}");
+        }
+        let token = data
+            .get("access_token")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing access_token in DingTalk response"))?
+            .to_string();
+        let expires_in = data
+            .get("expires_in")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(7200);
+
+        // Expire 60 seconds early to avoid edge cases
+        let expiry = now + expires_in.saturating_sub(60);
+        {
+            let mut cache = self.token_cache.write().await;
+            *cache = Some((token.clone(), expiry));
+        }
+        Ok(token)
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let webhooks = self.session_webhooks.read().await;
-        let webhook_url = webhooks.get(&message.recipient).ok_or_else(|| {
-            anyhow::anyhow!(
-                "No session webhook found for chat {}. \
-                 The user must send a message first to establish a session.",
-                message.recipient
-            )
-        })?;
+    /// Upload a local file through DingTalk's media upload API; returns the
+    /// `media_id` to reference in an outbound message.
+    async fn upload_media(&self, marker: &MediaMarker) -> anyhow::Result<String> {
+        let token = self.get_access_token().await?;
+        let bytes = tokio::fs::read(&marker.target)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to read attachment {}: {e}", marker.target))?;
+        let file_name = std::path::Path::new(&marker.target)
+            .file_name()
+            .map_or_else(
+                || "attachment".to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+
+        let form = reqwest::multipart::Form::new().part(
+            "media",
+            reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+        );
 
-        let title = message.subject.as_deref().unwrap_or("ZeroClaw");
-        let body = serde_json::json!({
-            "msgtype": "markdown",
-            "markdown": {
-                "title": title,
-                "text": message.content,
-            }
-        });
+        let resp = self
+            .http_client()
+            .post(format!(
+                "{}/media/upload?access_token={token}&type={}",
+                self.oapi_base,
+                upload_media_type(marker.kind)
+            ))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("DingTalk media upload failed ({status}): {err}");
+        }
 
+        let data: serde_json::Value = resp.json().await?;
+        if data.get("errcode").and_then(|c| c.as_i64()).unwrap_or(0) != 0 {
+            anyhow::bail!("DingTalk media upload rejected: {data}");
+        }
+        data.get("media_id")
+            .and_then(|m| m.as_str())
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow::anyhow!("Missing media_id in DingTalk upload response"))
+    }
+
+    /// POST one message body to a session webhook, checking both the HTTP
+    /// status and DingTalk's in-body `errcode`.
+    async fn post_webhook_json(
+        &self,
+        webhook_url: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<()> {
         let resp = self
             .http_client()
             .post(webhook_url)
-            .json(&body)
+            .json(body)
             .send()
             .await?;
 
@@ -152,6 +319,85 @@ impl Channel for DingTalkChannel {
             anyhow::bail!("DingTalk webhook reply failed ({status}): {err}");
         }
 
+        let data: serde_json::Value = resp.json().await.unwrap_or_default();
+        if data.get("errcode").and_then(|c| c.as_i64()).unwrap_or(0) != 0 {
+            anyhow::bail!("DingTalk webhook reply rejected: {data}");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Channel for DingTalkChannel {
+    fn name(&self) -> &str {
+        "dingtalk"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let webhook_url = {
+            let webhooks = self.session_webhooks.read().await;
+            webhooks.get(&message.recipient).cloned().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No session webhook found for chat {}. \
+                         The user must send a message first to establish a session.",
+                    message.recipient
+                )
+            })?
+        };
+
+        let (mut text, markers) = super::media_markers::parse_media_markers(&message.content);
+
+        // Resolve media markers first: URLs become link cards, local files are
+        // uploaded. An upload failure degrades to a note appended to the text
+        // so the rest of the reply still goes out.
+        let mut media_bodies = Vec::new();
+        for marker in &markers {
+            if marker.target.starts_with("http://") || marker.target.starts_with("https://") {
+                media_bodies.push(link_card_body(marker));
+                continue;
+            }
+            match self.upload_media(marker).await {
+                Ok(media_id) => media_bodies.push(media_message_body(marker.kind, &media_id)),
+                Err(e) => {
+                    use std::fmt::Write;
+                    tracing::warn!("DingTalk: media upload failed for {}: {e}", marker.target);
+                    if !text.is_empty() {
+                        text.push_str("\n\n");
+                    }
+                    let _ = write!(text, "(attachment {} could not be sent)", marker.target);
+                }
+            }
+        }
+
+        if !text.trim().is_empty() {
+            let use_markdown = looks_like_markdown(&text);
+            let format = if use_markdown {
+                super::OutboundFormat::Markdown
+            } else {
+                super::OutboundFormat::Plain
+            };
+            let title = message.subject.as_deref().unwrap_or("ZeroClaw");
+
+            for chunk in super::split_outbound_message(&text, DINGTALK_MESSAGE_LIMIT, format) {
+                let body = if use_markdown {
+                    serde_json::json!({
+                        "msgtype": "markdown",
+                        "markdown": { "title": title, "text": chunk }
+                    })
+                } else {
+                    serde_json::json!({
+                        "msgtype": "text",
+                        "text": { "content": chunk }
+                    })
+                };
+                self.post_webhook_json(&webhook_url, &body).await?;
+            }
+        }
+
+        for body in &media_bodies {
+            self.post_webhook_json(&webhook_url, body).await?;
+        }
+
         Ok(())
     }
 
@@ -291,7 +537,11 @@ impl Channel for DingTalkChannel {
                         thread_ts: None,
                         reply_to_message_id: None,
                         interruption_scope_id: None,
+                        is_edit: false,
+                        is_group: false,
+                        payload: None,
                         attachments: vec![],
+                        metadata: None,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -396,4 +646,159 @@ client_secret = "secret"
         let chat_id = DingTalkChannel::resolve_chat_id(&data, "staff-1");
         assert_eq!(chat_id, "cid-group");
     }
+
+    #[test]
+    fn markdown_heuristic_detects_markdown_constructs() {
+        assert!(looks_like_markdown("# Heading\nbody"));
+        assert!(looks_like_markdown("see ```rust\ncode\n```"));
+        assert!(looks_like_markdown("- item one\n- item two"));
+        assert!(looks_like_markdown("a [link](https://example.com)"));
+        assert!(looks_like_markdown("**bold** claim"));
+        assert!(looks_like_markdown("| col | col |"));
+    }
+
+    #[test]
+    fn markdown_heuristic_leaves_plain_text_alone() {
+        assert!(!looks_like_markdown("just a plain reply"));
+        assert!(!looks_like_markdown("math: 2 * 3 = 6 and #42 inline"));
+        assert!(!looks_like_markdown("multi\nline\nplain text"));
+    }
+
+    #[test]
+    fn link_card_sets_pic_url_only_for_images() {
+        let image = MediaMarker {
+            kind: MediaMarkerKind::Image,
+            target: "https://example.com/chart.png".into(),
+        };
+        let body = link_card_body(&image);
+        assert_eq!(body["msgtype"], "link");
+        assert_eq!(body["link"]["messageUrl"], "https://example.com/chart.png");
+        assert_eq!(body["link"]["picUrl"], "https://example.com/chart.png");
+
+        let doc = MediaMarker {
+            kind: MediaMarkerKind::Document,
+            target: "https://example.com/report.pdf".into(),
+        };
+        let body = link_card_body(&doc);
+        assert_eq!(body["link"]["picUrl"], "");
+    }
+
+    #[test]
+    fn media_message_body_matches_upload_type() {
+        let body = media_message_body(MediaMarkerKind::Image, "m-1");
+        assert_eq!(body["msgtype"], "image");
+        assert_eq!(body["image"]["media_id"], "m-1");
+
+        let body = media_message_body(MediaMarkerKind::Voice, "m-2");
+        assert_eq!(body["msgtype"], "voice");
+
+        let body = media_message_body(MediaMarkerKind::Document, "m-3");
+        assert_eq!(body["msgtype"], "file");
+
+        assert_eq!(upload_media_type(MediaMarkerKind::Image), "image");
+        assert_eq!(upload_media_type(MediaMarkerKind::Audio), "voice");
+        assert_eq!(upload_media_type(MediaMarkerKind::Video), "video");
+        assert_eq!(upload_media_type(MediaMarkerKind::Document), "file");
+    }
+
+    #[tokio::test]
+    async fn upload_failure_degrades_to_text_with_note() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/gettoken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "errcode": 0, "access_token": "tok", "expires_in": 7200
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/media/upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "errcode": 40005, "errmsg": "invalid media type"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/session-webhook"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"errcode": 0, "errmsg": "ok"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), b"png-bytes").unwrap();
+
+        let ch = DingTalkChannel::new("id".into(), "secret".into(), vec!["*".into()])
+            .with_oapi_base(server.uri());
+        ch.session_webhooks
+            .write()
+            .await
+            .insert("chat-1".into(), format!("{}/session-webhook", server.uri()));
+
+        let content = format!("here it is [IMAGE:{}]", tmp.path().display());
+        ch.send(&SendMessage::new(&content, "chat-1"))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let webhook_post = requests
+            .iter()
+            .find(|r| r.url.path() == "/session-webhook")
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&webhook_post.body).unwrap();
+        assert_eq!(body["msgtype"], "text");
+        let text = body["text"]["content"].as_str().unwrap();
+        assert!(text.contains("here it is"), "got: {text}");
+        assert!(text.contains("could not be sent"), "got: {text}");
+    }
+
+    #[tokio::test]
+    async fn url_markers_become_link_cards_without_upload() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/session-webhook"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"errcode": 0, "errmsg": "ok"})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let ch = DingTalkChannel::new("id".into(), "secret".into(), vec!["*".into()])
+            .with_oapi_base(server.uri());
+        ch.session_webhooks
+            .write()
+            .await
+            .insert("chat-1".into(), format!("{}/session-webhook", server.uri()));
+
+        ch.send(&SendMessage::new(
+            "chart attached [IMAGE:https://example.com/chart.png]",
+            "chat-1",
+        ))
+        .await
+        .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let bodies: Vec<serde_json::Value> = requests
+            .iter()
+            .map(|r| serde_json::from_slice(&r.body).unwrap())
+            .collect();
+        assert_eq!(bodies[0]["msgtype"], "text");
+        assert_eq!(bodies[1]["msgtype"], "link");
+        assert_eq!(
+            bodies[1]["link"]["messageUrl"],
+            "https://example.com/chart.png"
+        );
+    }
 }
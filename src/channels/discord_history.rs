@@ -500,7 +500,11 @@ impl Channel for DiscordHistoryChannel {
                             thread_ts: None,
                             reply_to_message_id: None,
                             interruption_scope_id: None,
+                            is_edit: false,
+                            is_group: false,
+                            payload: None,
                             attachments: Vec::new(),
+                            metadata: None,
                         };
                         if tx.send(channel_msg).await.is_err() {
                             break;
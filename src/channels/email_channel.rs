@@ -22,7 +22,7 @@ use rustls::{ClientConfig, RootCertStore};
 use rustls_pki_types::DnsName;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpStream;
@@ -70,6 +70,12 @@ pub struct EmailConfig {
     /// Default subject line for outgoing emails (default: "ZeroClaw Message")
     #[serde(default = "default_subject")]
     pub default_subject: String,
+    /// Maximum size in MB for a single saved inbound attachment (default: 5)
+    #[serde(default = "default_max_attachment_mb")]
+    pub max_attachment_mb: u64,
+    /// Maximum combined size in MB of saved attachments per message (default: 20)
+    #[serde(default = "default_max_message_attachments_mb")]
+    pub max_message_attachments_mb: u64,
 }
 
 impl crate::config::traits::ChannelConfig for EmailConfig {
@@ -99,6 +105,12 @@ fn default_true() -> bool {
 fn default_subject() -> String {
     "ZeroClaw Message".into()
 }
+fn default_max_attachment_mb() -> u64 {
+    5
+}
+fn default_max_message_attachments_mb() -> u64 {
+    20
+}
 
 impl Default for EmailConfig {
     fn default() -> Self {
@@ -115,16 +127,34 @@ impl Default for EmailConfig {
             idle_timeout_secs: default_idle_timeout(),
             allowed_senders: Vec::new(),
             default_subject: default_subject(),
+            max_attachment_mb: default_max_attachment_mb(),
+            max_message_attachments_mb: default_max_message_attachments_mb(),
         }
     }
 }
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// Subdirectory of the workspace where inbound attachments are saved.
+const EMAIL_ATTACHMENT_SAVE_SUBDIR: &str = "email_attachments";
+/// Cap on sanitized attachment filename length.
+const EMAIL_ATTACHMENT_FILENAME_MAX_CHARS: usize = 120;
+
+/// Cap on remembered inbound subjects used for "Re:" threading. The map is
+/// cleared wholesale at capacity — losing a reply subject only means falling
+/// back to the default subject, so simple beats an LRU here.
+const MAX_TRACKED_REPLY_SUBJECTS: usize = 512;
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    /// Inbound Message-ID → subject, so replies can reuse the original
+    /// subject with a "Re: " prefix instead of the default subject.
+    reply_subjects: Arc<Mutex<HashMap<String, String>>>,
+    /// Workspace directory for persisting inbound attachments.
+    /// When unset, attachments are dropped (text-only behavior).
+    workspace_dir: Option<std::path::PathBuf>,
 }
 
 impl EmailChannel {
@@ -132,9 +162,17 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            reply_subjects: Arc::new(Mutex::new(HashMap::new())),
+            workspace_dir: None,
         }
     }
 
+    /// Configure workspace directory used for persisting inbound attachments.
+    pub fn with_workspace_dir(mut self, dir: std::path::PathBuf) -> Self {
+        self.workspace_dir = Some(dir);
+        self
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
         if self.config.allowed_senders.is_empty() {
@@ -180,6 +218,81 @@ impl EmailChannel {
         normalized
     }
 
+    /// Strip quoted reply text from an inbound body so the agent only sees
+    /// the new content: drops `>`-quoted lines and truncates at the first
+    /// reply-attribution marker (Gmail/Apple Mail "On … wrote:", Outlook
+    /// "-----Original Message-----" or underscore separator). Falls back to
+    /// the original body when stripping would leave nothing.
+    fn strip_quoted_reply(body: &str) -> String {
+        let lines: Vec<&str> = body.lines().collect();
+        let mut kept: Vec<&str> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('>') {
+                continue;
+            }
+            if Self::is_reply_attribution_start(trimmed, lines.get(i + 1), lines.get(i + 2)) {
+                break;
+            }
+            kept.push(line);
+        }
+        let stripped = kept.join("\n").trim().to_string();
+        if stripped.is_empty() {
+            body.trim().to_string()
+        } else {
+            stripped
+        }
+    }
+
+    /// Whether `line` begins a quoted-reply block. Gmail wraps long
+    /// attribution lines, so the "wrote:" suffix may land on one of the next
+    /// two lines.
+    fn is_reply_attribution_start(
+        line: &str,
+        next: Option<&&str>,
+        after_next: Option<&&str>,
+    ) -> bool {
+        if line == "-----Original Message-----" {
+            return true; // Outlook
+        }
+        if line.len() >= 10 && line.chars().all(|c| c == '_') {
+            return true; // Outlook web separator
+        }
+        if !line.starts_with("On ") {
+            return false;
+        }
+        if line.trim_end().ends_with("wrote:") {
+            return true; // Gmail / Apple Mail
+        }
+        [next, after_next]
+            .into_iter()
+            .flatten()
+            .any(|l| l.trim_end().ends_with("wrote:"))
+    }
+
+    /// Build a reply subject from the original, adding a single "Re: "
+    /// prefix (never stacking "Re: Re:").
+    fn reply_subject(original: &str) -> String {
+        let trimmed = original.trim();
+        if trimmed
+            .get(..3)
+            .is_some_and(|p| p.eq_ignore_ascii_case("re:"))
+        {
+            trimmed.to_string()
+        } else {
+            format!("Re: {trimmed}")
+        }
+    }
+
+    /// Remember an inbound subject so a later reply can reuse it.
+    async fn remember_subject(&self, msg_id: &str, subject: &str) {
+        let mut subjects = self.reply_subjects.lock().await;
+        if subjects.len() >= MAX_TRACKED_REPLY_SUBJECTS {
+            subjects.clear();
+        }
+        subjects.insert(msg_id.to_string(), subject.to_string());
+    }
+
     /// Extract the sender address from a parsed email
     fn extract_sender(parsed: &mail_parser::Message) -> String {
         parsed
@@ -212,6 +325,213 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Sanitize an attachment filename down to a safe basename component.
+    ///
+    /// Crafted names like `../../etc/passwd` or `..\evil.sh` must never
+    /// escape the attachment directory: only the final path component is
+    /// kept, separators are replaced, and empty/dot-only results rejected.
+    fn sanitize_attachment_filename(file_name: &str) -> Option<String> {
+        let basename = std::path::Path::new(file_name)
+            .file_name()?
+            .to_str()?
+            .trim();
+        if basename.is_empty() || basename == "." || basename == ".." {
+            return None;
+        }
+
+        let sanitized: String = basename
+            .replace(['/', '\\'], "_")
+            .chars()
+            .take(EMAIL_ATTACHMENT_FILENAME_MAX_CHARS)
+            .collect();
+        if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
+            None
+        } else {
+            Some(sanitized)
+        }
+    }
+
+    /// Reduce a sender address to a safe directory name component.
+    fn sanitize_sender_dir(sender: &str) -> String {
+        let cleaned: String = sender
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '@' | '.') {
+                    ch
+                } else {
+                    '_'
+                }
+            })
+            .take(128)
+            .collect();
+        let trimmed = cleaned.trim_matches('.');
+        if trimmed.is_empty() {
+            "unknown".to_string()
+        } else {
+            trimmed.to_string()
+        }
+    }
+
+    /// Check whether a saved attachment should be handed to the multimodal
+    /// pipeline as an image (by MIME type, falling back to extension).
+    fn is_image_attachment(mime_type: Option<&str>, file_name: &str) -> bool {
+        if let Some(mime) = mime_type {
+            if mime.starts_with("image/") {
+                return true;
+            }
+        }
+        let ext = std::path::Path::new(file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+        matches!(
+            ext.as_str(),
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp"
+        )
+    }
+
+    /// Walk a parsed message's MIME attachments (including nested multiparts)
+    /// and decide which to save, applying the per-attachment and per-message
+    /// size limits. Returns accepted attachments plus human-readable notes for
+    /// everything that was skipped.
+    fn plan_attachments(
+        parsed: &mail_parser::Message,
+        max_attachment_mb: u64,
+        max_message_mb: u64,
+    ) -> (Vec<PlannedAttachment>, Vec<String>) {
+        let max_attachment_bytes = max_attachment_mb.saturating_mul(1024 * 1024);
+        let max_message_bytes = max_message_mb.saturating_mul(1024 * 1024);
+
+        let mut planned = Vec::new();
+        let mut notes = Vec::new();
+        let mut total_bytes: u64 = 0;
+
+        for (idx, part) in parsed.attachments().enumerate() {
+            let part: &mail_parser::MessagePart = part;
+            let data = part.contents();
+            if data.is_empty() {
+                continue;
+            }
+
+            let raw_name = MimeHeaders::attachment_name(part)
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("attachment-{}", idx + 1));
+            let file_name = match Self::sanitize_attachment_filename(&raw_name) {
+                Some(name) => name,
+                None => {
+                    notes.push(format!("attachment {} skipped: invalid filename", raw_name));
+                    continue;
+                }
+            };
+
+            let size = data.len() as u64;
+            if max_attachment_bytes > 0 && size > max_attachment_bytes {
+                notes.push(format!(
+                    "attachment {} skipped: {} MB > {} MB limit",
+                    file_name,
+                    size.div_ceil(1024 * 1024),
+                    max_attachment_mb
+                ));
+                continue;
+            }
+            if max_message_bytes > 0 && total_bytes + size > max_message_bytes {
+                notes.push(format!(
+                    "attachment {} skipped: message total would exceed {} MB limit",
+                    file_name, max_message_mb
+                ));
+                continue;
+            }
+            total_bytes += size;
+
+            let mime_type = MimeHeaders::content_type(part).map(|ct| {
+                ct.subtype().map_or_else(
+                    || ct.ctype().to_string(),
+                    |sub| format!("{}/{}", ct.ctype(), sub),
+                )
+            });
+            let is_image = Self::is_image_attachment(mime_type.as_deref(), &file_name);
+
+            planned.push(PlannedAttachment {
+                file_name,
+                data: data.to_vec(),
+                is_image,
+            });
+        }
+
+        (planned, notes)
+    }
+
+    /// Save planned attachments under `{workspace}/email_attachments/{sender}/`
+    /// and return content lines referencing the saved paths — `[IMAGE:/path]`
+    /// markers for images (multimodal pipeline) and `[Document: name] /path`
+    /// for everything else (readable with file tools).
+    async fn save_attachments(
+        &self,
+        sender: &str,
+        attachments: &[PlannedAttachment],
+    ) -> Vec<String> {
+        let Some(ref workspace) = self.workspace_dir else {
+            return Vec::new();
+        };
+        if attachments.is_empty() {
+            return Vec::new();
+        }
+
+        let save_dir = workspace
+            .join(EMAIL_ATTACHMENT_SAVE_SUBDIR)
+            .join(Self::sanitize_sender_dir(sender));
+        if let Err(e) = tokio::fs::create_dir_all(&save_dir).await {
+            warn!(
+                "Email attachment directory creation failed for {}: {}",
+                save_dir.display(),
+                e
+            );
+            return attachments
+                .iter()
+                .map(|a| format!("attachment {} skipped: could not be saved", a.file_name))
+                .collect();
+        }
+
+        let mut lines = Vec::new();
+        for attachment in attachments {
+            let unique_name = format!(
+                "{}_{}",
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0),
+                attachment.file_name
+            );
+            let path = save_dir.join(&unique_name);
+            match tokio::fs::write(&path, &attachment.data).await {
+                Ok(()) => {
+                    if attachment.is_image {
+                        lines.push(format!("[IMAGE:{}]", path.display()));
+                    } else {
+                        lines.push(format!(
+                            "[Document: {}] {}",
+                            attachment.file_name,
+                            path.display()
+                        ));
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Email attachment write failed for {}: {}",
+                        path.display(),
+                        e
+                    );
+                    lines.push(format!(
+                        "attachment {} skipped: could not be saved",
+                        attachment.file_name
+                    ));
+                }
+            }
+        }
+        lines
+    }
+
     /// Connect to IMAP server with TLS and authenticate
     async fn connect_imap(&self) -> Result<ImapSession> {
         let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
@@ -284,7 +604,20 @@ impl EmailChannel {
                         let sender = Self::extract_sender(&parsed);
                         let subject = parsed.subject().unwrap_or("(no subject)").to_string();
                         let body_text = Self::extract_text(&parsed);
-                        let content = format!("Subject: {}\n\n{}", subject, body_text);
+                        let content = format!(
+                            "Subject: {}\n\n{}",
+                            subject,
+                            Self::strip_quoted_reply(&body_text)
+                        );
+                        let (attachments, attachment_notes) = if self.workspace_dir.is_some() {
+                            Self::plan_attachments(
+                                &parsed,
+                                self.config.max_attachment_mb,
+                                self.config.max_message_attachments_mb,
+                            )
+                        } else {
+                            (Vec::new(), Vec::new())
+                        };
                         let msg_id = parsed
                             .message_id()
                             .map(|s| s.to_string())
@@ -319,8 +652,12 @@ impl EmailChannel {
                             _uid: uid,
                             msg_id,
                             sender,
+                            subject,
                             content,
+                            raw_body: body_text,
                             timestamp: ts,
+                            attachments,
+                            attachment_notes,
                         });
                     }
                 }
@@ -470,17 +807,36 @@ impl EmailChannel {
                 continue;
             }
 
+            self.remember_subject(&email.msg_id, &email.subject).await;
+
+            // Persist attachments only after the sender passed the allowlist
+            // and the message passed dedup — never write blocked senders'
+            // files into the workspace.
+            let mut content = email.content;
+            let mut extra_lines = self
+                .save_attachments(&email.sender, &email.attachments)
+                .await;
+            extra_lines.extend(email.attachment_notes);
+            if !extra_lines.is_empty() {
+                content.push_str("\n\n");
+                content.push_str(&extra_lines.join("\n"));
+            }
+
             let msg = ChannelMessage {
-                id: email.msg_id,
+                id: email.msg_id.clone(),
                 reply_target: email.sender.clone(),
                 sender: email.sender,
-                content: email.content,
+                content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
-                reply_to_message_id: None,
+                reply_to_message_id: Some(email.msg_id),
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: Some(serde_json::json!({ "raw_body": email.raw_body })),
             };
 
             if tx.send(msg).await.is_err() {
@@ -514,8 +870,24 @@ struct ParsedEmail {
     _uid: u32,
     msg_id: String,
     sender: String,
+    subject: String,
     content: String,
+    /// Body text before quoted-reply stripping, exposed to hooks via
+    /// `ChannelMessage::metadata`.
+    raw_body: String,
     timestamp: u64,
+    /// MIME attachments accepted for saving (within size limits).
+    attachments: Vec<PlannedAttachment>,
+    /// Notes about skipped attachments, surfaced in the message text.
+    attachment_notes: Vec<String>,
+}
+
+/// A MIME attachment that passed filename sanitization and size limits,
+/// held in memory until the sender allowlist/dedup checks pass.
+struct PlannedAttachment {
+    file_name: String,
+    data: Vec<u8>,
+    is_image: bool,
 }
 
 /// Result from waiting on IDLE
@@ -532,7 +904,24 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Use explicit subject if provided, otherwise fall back to legacy parsing or default
+        // Synthetic "gen-…" ids mark inbound emails without a Message-ID
+        // header — they are not valid reply anchors.
+        let reply_id = message
+            .reply_to_message_id
+            .as_deref()
+            .filter(|id| !id.starts_with("gen-"));
+        let remembered_subject = match reply_id {
+            Some(id) => self
+                .reply_subjects
+                .lock()
+                .await
+                .get(id)
+                .map(|s| Self::reply_subject(s)),
+            None => None,
+        };
+
+        // Use explicit subject if provided, otherwise fall back to legacy
+        // parsing, the remembered reply subject, or the default
         let default_subject = self.config.default_subject.as_str();
         let (subject, body) = if let Some(ref subj) = message.subject {
             (subj.as_str(), message.content.as_str())
@@ -542,15 +931,22 @@ impl Channel for EmailChannel {
             } else {
                 (default_subject, message.content.as_str())
             }
+        } else if let Some(ref subj) = remembered_subject {
+            (subj.as_str(), message.content.as_str())
         } else {
             (default_subject, message.content.as_str())
         };
 
-        let email = Message::builder()
+        let mut builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .subject(subject);
+        if let Some(id) = reply_id {
+            // Thread the reply under the original message
+            let anchor = format!("<{id}>");
+            builder = builder.in_reply_to(anchor.clone()).references(anchor);
+        }
+        let email = builder.singlepart(SinglePart::plain(body.to_string()))?;
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -683,6 +1079,8 @@ mod tests {
             idle_timeout_secs: 1200,
             allowed_senders: vec!["allowed@example.com".to_string()],
             default_subject: "Custom Subject".to_string(),
+            max_attachment_mb: 5,
+            max_message_attachments_mb: 20,
         };
         assert_eq!(config.imap_host, "imap.example.com");
         assert_eq!(config.imap_folder, "Archive");
@@ -705,6 +1103,8 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
             default_subject: "Test Subject".to_string(),
+            max_attachment_mb: 5,
+            max_message_attachments_mb: 20,
         };
         let cloned = config.clone();
         assert_eq!(cloned.imap_host, config.imap_host);
@@ -913,6 +1313,85 @@ mod tests {
         );
     }
 
+    // Quoted-reply stripping tests
+
+    #[test]
+    fn strip_quoted_reply_gmail_fixture() {
+        let body = "Thanks, that works for me.\n\nOn Mon, Jan 5, 2026 at 10:30 AM Alice <alice@example.com> wrote:\n> Earlier message text\n> spanning two lines.";
+        assert_eq!(
+            EmailChannel::strip_quoted_reply(body),
+            "Thanks, that works for me."
+        );
+    }
+
+    #[test]
+    fn strip_quoted_reply_gmail_wrapped_attribution() {
+        // Gmail wraps long attribution lines; "wrote:" lands on the next line.
+        let body = "Sounds good.\n\nOn Mon, Jan 5, 2026 at 10:30 AM Alice Lidell\n<alice@example.com> wrote:\n> Earlier text";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Sounds good.");
+    }
+
+    #[test]
+    fn strip_quoted_reply_outlook_fixture() {
+        let body = "Will do.\n\n-----Original Message-----\nFrom: Bob <bob@example.com>\nSent: Monday, January 5, 2026 10:30 AM\nTo: Agent <agent@example.com>\nSubject: Status\n\nEarlier text";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Will do.");
+    }
+
+    #[test]
+    fn strip_quoted_reply_outlook_web_separator() {
+        let body = "On it.\n\n________________________________\nFrom: Bob <bob@example.com>\nSent: Monday, January 5, 2026 10:30 AM";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "On it.");
+    }
+
+    #[test]
+    fn strip_quoted_reply_apple_mail_fixture() {
+        // Apple Mail quotes the attribution line itself.
+        let body = "Confirmed.\n\n> On Jan 5, 2026, at 10:30, Alice <alice@example.com> wrote:\n> \n> Earlier text";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), "Confirmed.");
+    }
+
+    #[test]
+    fn strip_quoted_reply_keeps_interleaved_new_text() {
+        let body = "> Did you deploy?\nYes, this morning.\n> Any errors?\nNone so far.";
+        assert_eq!(
+            EmailChannel::strip_quoted_reply(body),
+            "Yes, this morning.\nNone so far."
+        );
+    }
+
+    #[test]
+    fn strip_quoted_reply_falls_back_when_everything_is_quoted() {
+        let body = "> Only quoted text\n> and nothing new.";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), body);
+    }
+
+    #[test]
+    fn strip_quoted_reply_leaves_plain_bodies_untouched() {
+        let body = "On balance I think we should ship it.";
+        assert_eq!(EmailChannel::strip_quoted_reply(body), body);
+    }
+
+    #[test]
+    fn reply_subject_adds_single_re_prefix() {
+        assert_eq!(EmailChannel::reply_subject("Status"), "Re: Status");
+        assert_eq!(EmailChannel::reply_subject("Re: Status"), "Re: Status");
+        assert_eq!(EmailChannel::reply_subject("RE: Status"), "RE: Status");
+    }
+
+    #[tokio::test]
+    async fn remember_subject_caps_tracked_entries() {
+        let channel = EmailChannel::new(EmailConfig::default());
+        for i in 0..MAX_TRACKED_REPLY_SUBJECTS {
+            channel
+                .remember_subject(&format!("id-{i}"), "Subject")
+                .await;
+        }
+        channel.remember_subject("one-more", "Subject").await;
+        let subjects = channel.reply_subjects.lock().await;
+        assert_eq!(subjects.len(), 1);
+        assert!(subjects.contains_key("one-more"));
+    }
+
     // Default function tests
 
     #[test]
@@ -952,6 +1431,8 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["allowed@example.com".to_string()],
             default_subject: "Serialization Test".to_string(),
+            max_attachment_mb: 5,
+            max_message_attachments_mb: 20,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -1028,4 +1509,248 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("imap.debug.com"));
     }
+
+    // Attachment extraction tests
+
+    fn multipart_fixture() -> String {
+        [
+            "From: alice@example.com",
+            "Subject: Files",
+            "MIME-Version: 1.0",
+            "Content-Type: multipart/mixed; boundary=\"outer\"",
+            "",
+            "--outer",
+            "Content-Type: text/plain",
+            "",
+            "See attached.",
+            "--outer",
+            "Content-Type: multipart/related; boundary=\"inner\"",
+            "",
+            "--inner",
+            "Content-Type: image/png",
+            "Content-Disposition: attachment; filename=\"photo.png\"",
+            "",
+            "PNGDATA",
+            "--inner",
+            "Content-Type: application/pdf",
+            "Content-Disposition: attachment; filename=\"report.pdf\"",
+            "",
+            "PDFDATA",
+            "--inner--",
+            "--outer",
+            "Content-Type: application/octet-stream",
+            "Content-Disposition: attachment; filename=\"../../etc/passwd\"",
+            "",
+            "SNEAKY",
+            "--outer--",
+            "",
+        ]
+        .join("\r\n")
+    }
+
+    fn single_attachment_fixture(filename: &str, body: &str) -> String {
+        [
+            "From: alice@example.com",
+            "Subject: One file",
+            "MIME-Version: 1.0",
+            "Content-Type: multipart/mixed; boundary=\"b\"",
+            "",
+            "--b",
+            "Content-Type: text/plain",
+            "",
+            "hi",
+            "--b",
+            "Content-Type: application/octet-stream",
+            &format!("Content-Disposition: attachment; filename=\"{filename}\""),
+            "",
+            body,
+            "--b--",
+            "",
+        ]
+        .join("\r\n")
+    }
+
+    #[test]
+    fn plan_attachments_extracts_nested_multipart_attachments() {
+        let raw = multipart_fixture();
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+
+        let (planned, notes) = EmailChannel::plan_attachments(&parsed, 5, 20);
+
+        assert!(notes.is_empty(), "unexpected notes: {notes:?}");
+        let names: Vec<&str> = planned.iter().map(|a| a.file_name.as_str()).collect();
+        assert!(
+            names.contains(&"photo.png"),
+            "missing nested image: {names:?}"
+        );
+        assert!(
+            names.contains(&"report.pdf"),
+            "missing nested pdf: {names:?}"
+        );
+
+        let photo = planned.iter().find(|a| a.file_name == "photo.png").unwrap();
+        assert!(photo.is_image);
+        assert_eq!(photo.data, b"PNGDATA");
+        let pdf = planned
+            .iter()
+            .find(|a| a.file_name == "report.pdf")
+            .unwrap();
+        assert!(!pdf.is_image);
+    }
+
+    #[test]
+    fn plan_attachments_sanitizes_path_traversal_filenames() {
+        let raw = multipart_fixture();
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+
+        let (planned, _) = EmailChannel::plan_attachments(&parsed, 5, 20);
+
+        let sneaky = planned.iter().find(|a| a.data == b"SNEAKY").unwrap();
+        assert_eq!(
+            sneaky.file_name, "passwd",
+            "traversal components must be stripped"
+        );
+        for attachment in &planned {
+            assert!(!attachment.file_name.contains('/'));
+            assert!(!attachment.file_name.contains('\\'));
+        }
+    }
+
+    #[test]
+    fn plan_attachments_sanitizes_backslash_traversal_filenames() {
+        let raw = single_attachment_fixture("..\\..\\evil.sh", "EVIL");
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+
+        let (planned, notes) = EmailChannel::plan_attachments(&parsed, 5, 20);
+
+        assert!(notes.is_empty(), "unexpected notes: {notes:?}");
+        assert_eq!(planned.len(), 1);
+        assert!(!planned[0].file_name.contains('\\'));
+        assert!(!planned[0].file_name.contains('/'));
+    }
+
+    #[test]
+    fn plan_attachments_skips_oversized_attachment_with_note() {
+        let big = "A".repeat(2 * 1024 * 1024);
+        let raw = single_attachment_fixture("big.bin", &big);
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+
+        let (planned, notes) = EmailChannel::plan_attachments(&parsed, 1, 20);
+
+        assert!(planned.is_empty(), "oversized attachment must be skipped");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0], "attachment big.bin skipped: 2 MB > 1 MB limit");
+    }
+
+    #[test]
+    fn plan_attachments_enforces_per_message_quota() {
+        let chunk = "A".repeat(700 * 1024);
+        let raw = [
+            "From: alice@example.com",
+            "Subject: Two files",
+            "MIME-Version: 1.0",
+            "Content-Type: multipart/mixed; boundary=\"b\"",
+            "",
+            "--b",
+            "Content-Type: application/octet-stream",
+            "Content-Disposition: attachment; filename=\"one.bin\"",
+            "",
+            &chunk,
+            "--b",
+            "Content-Type: application/octet-stream",
+            "Content-Disposition: attachment; filename=\"two.bin\"",
+            "",
+            &chunk,
+            "--b--",
+            "",
+        ]
+        .join("\r\n");
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+
+        let (planned, notes) = EmailChannel::plan_attachments(&parsed, 1, 1);
+
+        assert_eq!(planned.len(), 1, "only the first attachment fits the quota");
+        assert_eq!(planned[0].file_name, "one.bin");
+        assert_eq!(notes.len(), 1);
+        assert!(
+            notes[0].contains("two.bin skipped") && notes[0].contains("1 MB limit"),
+            "unexpected note: {}",
+            notes[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn save_attachments_writes_into_per_sender_dir() {
+        let workspace = tempfile::tempdir().unwrap();
+        let channel = EmailChannel::new(EmailConfig::default())
+            .with_workspace_dir(workspace.path().to_path_buf());
+
+        let attachments = vec![
+            PlannedAttachment {
+                file_name: "report.pdf".to_string(),
+                data: b"PDF".to_vec(),
+                is_image: false,
+            },
+            PlannedAttachment {
+                file_name: "photo.png".to_string(),
+                data: b"PNG".to_vec(),
+                is_image: true,
+            },
+        ];
+
+        let lines = channel
+            .save_attachments("alice@example.com", &attachments)
+            .await;
+
+        assert_eq!(lines.len(), 2);
+        assert!(
+            lines[0].starts_with("[Document: report.pdf] "),
+            "unexpected document line: {}",
+            lines[0]
+        );
+        assert!(
+            lines[1].starts_with("[IMAGE:") && lines[1].ends_with(']'),
+            "unexpected image marker: {}",
+            lines[1]
+        );
+
+        let sender_dir = workspace
+            .path()
+            .join(EMAIL_ATTACHMENT_SAVE_SUBDIR)
+            .join("alice@example.com");
+        let saved: Vec<String> = std::fs::read_dir(&sender_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(saved.len(), 2);
+        assert!(saved.iter().any(|n| n.ends_with("_report.pdf")));
+        assert!(saved.iter().any(|n| n.ends_with("_photo.png")));
+    }
+
+    #[tokio::test]
+    async fn save_attachments_without_workspace_is_a_noop() {
+        let channel = EmailChannel::new(EmailConfig::default());
+        let attachments = vec![PlannedAttachment {
+            file_name: "report.pdf".to_string(),
+            data: b"PDF".to_vec(),
+            is_image: false,
+        }];
+
+        let lines = channel
+            .save_attachments("alice@example.com", &attachments)
+            .await;
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn sanitize_sender_dir_replaces_unsafe_characters() {
+        assert_eq!(
+            EmailChannel::sanitize_sender_dir("alice@example.com"),
+            "alice@example.com"
+        );
+        let weird = EmailChannel::sanitize_sender_dir("Bob <bob@ex.com>/../");
+        assert!(!weird.contains('/'));
+        assert!(!weird.contains('<') && !weird.contains('>'));
+        assert_eq!(EmailChannel::sanitize_sender_dir("..."), "unknown");
+    }
 }
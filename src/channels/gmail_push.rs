@@ -495,7 +495,11 @@ impl GmailPushChannel {
                         thread_ts: Some(gmail_msg.thread_id),
                         reply_to_message_id: None,
                         interruption_scope_id: None,
+                        is_edit: false,
+                        is_group: false,
+                        payload: None,
                         attachments: Vec::new(),
+                        metadata: None,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
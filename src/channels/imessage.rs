@@ -296,7 +296,11 @@ end tell"#
                             thread_ts: None,
                             reply_to_message_id: None,
                             interruption_scope_id: None,
+                            is_edit: false,
+                            is_group: false,
+                            payload: None,
                             attachments: vec![],
+                            metadata: None,
                         };
 
                         if tx.send(msg).await.is_err() {
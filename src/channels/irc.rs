@@ -27,10 +27,16 @@ pub struct IrcChannel {
     username: String,
     channels: Vec<String>,
     allowed_users: Vec<String>,
+    /// Per-channel allowlists (channel name → nicks) overriding
+    /// `allowed_users` for messages from that channel.
+    channel_allowed_users: std::collections::HashMap<String, Vec<String>>,
     server_password: Option<String>,
     nickserv_password: Option<String>,
     sasl_password: Option<String>,
     verify_tls: bool,
+    /// Pinned server certificate SHA-256 fingerprint (hex; parsed at
+    /// connect time so a malformed value fails loudly, not silently).
+    tls_fingerprint: Option<String>,
     /// Shared write half of the TLS stream for sending messages.
     writer: Arc<Mutex<Option<WriteHalf>>>,
 }
@@ -148,6 +154,87 @@ fn encode_sasl_plain(nick: &str, password: &str) -> String {
     out
 }
 
+/// State of an in-flight SASL PLAIN negotiation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaslState {
+    /// `CAP REQ :sasl` sent, waiting for the server's ACK/NAK.
+    AwaitingCap,
+    /// `AUTHENTICATE PLAIN` sent, waiting for the `+` challenge.
+    AwaitingChallenge,
+    /// Credentials sent, waiting for 903 (success) or 904-907 (failure).
+    AwaitingResult,
+    /// Negotiation over — succeeded, failed, or abandoned.
+    Finished,
+}
+
+/// SASL PLAIN negotiation state machine (IRCv3).
+///
+/// Driven by inbound server messages: `on_message` advances the state and
+/// returns the raw IRC lines the caller must send. Once `is_finished`,
+/// further messages produce no actions, so reconnects simply build a fresh
+/// negotiator.
+struct SaslNegotiator {
+    state: SaslState,
+    nick: String,
+    password: String,
+}
+
+impl SaslNegotiator {
+    fn new(nick: String, password: String) -> Self {
+        Self {
+            state: SaslState::AwaitingCap,
+            nick,
+            password,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == SaslState::Finished
+    }
+
+    /// Advance the negotiation on a server message, returning lines to send.
+    fn on_message(&mut self, msg: &IrcMessage) -> Vec<String> {
+        if self.is_finished() {
+            return Vec::new();
+        }
+        match (self.state, msg.command.as_str()) {
+            (SaslState::AwaitingCap, "CAP") if msg.params.iter().any(|p| p.contains("sasl")) => {
+                if msg.params.iter().any(|p| p == "ACK") {
+                    self.state = SaslState::AwaitingChallenge;
+                    vec!["AUTHENTICATE PLAIN".to_string()]
+                } else if msg.params.iter().any(|p| p == "NAK") {
+                    tracing::warn!("IRC server does not support SASL, continuing without it");
+                    self.state = SaslState::Finished;
+                    vec!["CAP END".to_string()]
+                } else {
+                    Vec::new()
+                }
+            }
+            (SaslState::AwaitingChallenge, "AUTHENTICATE")
+                if msg.params.first().is_some_and(|p| p == "+") =>
+            {
+                self.state = SaslState::AwaitingResult;
+                vec![format!(
+                    "AUTHENTICATE {}",
+                    encode_sasl_plain(&self.nick, &self.password)
+                )]
+            }
+            // RPL_SASLSUCCESS
+            (_, "903") => {
+                self.state = SaslState::Finished;
+                vec!["CAP END".to_string()]
+            }
+            // ERR_SASLFAIL / ERR_SASLTOOLONG / ERR_SASLABORTED / ERR_SASLALREADY
+            (_, "904" | "905" | "906" | "907") => {
+                tracing::warn!("IRC SASL authentication failed ({})", msg.command);
+                self.state = SaslState::Finished;
+                vec!["CAP END".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
 /// Split a message into lines safe for IRC transmission.
 ///
 /// IRC is a line-based protocol — `\r\n` terminates each command, so any
@@ -233,10 +320,12 @@ pub struct IrcChannelConfig {
     pub username: Option<String>,
     pub channels: Vec<String>,
     pub allowed_users: Vec<String>,
+    pub channel_allowed_users: std::collections::HashMap<String, Vec<String>>,
     pub server_password: Option<String>,
     pub nickserv_password: Option<String>,
     pub sasl_password: Option<String>,
     pub verify_tls: bool,
+    pub tls_fingerprint: Option<String>,
 }
 
 impl IrcChannel {
@@ -249,10 +338,12 @@ impl IrcChannel {
             username,
             channels: cfg.channels,
             allowed_users: cfg.allowed_users,
+            channel_allowed_users: cfg.channel_allowed_users,
             server_password: cfg.server_password,
             nickserv_password: cfg.nickserv_password,
             sasl_password: cfg.sasl_password,
             verify_tls: cfg.verify_tls,
+            tls_fingerprint: cfg.tls_fingerprint,
             writer: Arc::new(Mutex::new(None)),
         }
     }
@@ -266,6 +357,27 @@ impl IrcChannel {
             .any(|u| u.eq_ignore_ascii_case(nick))
     }
 
+    /// Allowlist check for a message from `nick`, honoring the per-channel
+    /// override when the message came from a channel listed in
+    /// `channel_allowed_users`. DMs and unlisted channels fall back to the
+    /// global `allowed_users`.
+    fn is_user_allowed_in(&self, nick: &str, channel: Option<&str>) -> bool {
+        if let Some(chan) = channel {
+            if let Some(users) = self
+                .channel_allowed_users
+                .iter()
+                .find(|(c, _)| c.eq_ignore_ascii_case(chan))
+                .map(|(_, users)| users)
+            {
+                if users.iter().any(|u| u == "*") {
+                    return true;
+                }
+                return users.iter().any(|u| u.eq_ignore_ascii_case(nick));
+            }
+        }
+        self.is_user_allowed(nick)
+    }
+
     /// Create a TLS connection to the IRC server.
     async fn connect(
         &self,
@@ -273,7 +385,13 @@ impl IrcChannel {
         let addr = format!("{}:{}", self.server, self.port);
         let tcp = tokio::net::TcpStream::connect(&addr).await?;
 
-        let tls_config = if self.verify_tls {
+        let tls_config = if let Some(ref fingerprint) = self.tls_fingerprint {
+            let pinned = parse_fingerprint(fingerprint)?;
+            rustls::ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(PinnedFingerprint { pinned }))
+                .with_no_client_auth()
+        } else if self.verify_tls {
             let root_store: rustls::RootCertStore =
                 webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
             rustls::ClientConfig::builder()
@@ -302,6 +420,82 @@ impl IrcChannel {
     }
 }
 
+/// Parse a SHA-256 certificate fingerprint from hex, tolerating the
+/// colon-separated form tools like `openssl x509 -fingerprint` emit.
+fn parse_fingerprint(s: &str) -> anyhow::Result<Vec<u8>> {
+    let hex: String = s
+        .chars()
+        .filter(|c| *c != ':')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if hex.len() != 64 {
+        anyhow::bail!(
+            "TLS fingerprint must be 32 hex bytes (SHA-256), got {:?}",
+            s
+        );
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| anyhow::anyhow!("invalid hex in TLS fingerprint: {:?}", s))
+        })
+        .collect()
+}
+
+/// Certificate verifier that accepts only a certificate whose SHA-256
+/// fingerprint matches the pinned value. Replaces chain validation, so
+/// self-signed certificates work as long as the fingerprint matches.
+#[derive(Debug)]
+struct PinnedFingerprint {
+    pinned: Vec<u8>,
+}
+
+impl rustls::client::danger::ServerCertVerifier for PinnedFingerprint {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        use sha2::Digest;
+        let digest = sha2::Sha256::digest(end_entity.as_ref());
+        if digest.as_slice() == self.pinned.as_slice() {
+            Ok(rustls::client::danger::ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::General(
+                "server certificate does not match pinned fingerprint".into(),
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &rustls::pki_types::CertificateDer<'_>,
+        _dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &rustls::pki_types::CertificateDer<'_>,
+        _dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        rustls::crypto::ring::default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
 /// Certificate verifier that accepts any certificate (for `verify_tls=false`).
 #[derive(Debug)]
 struct NoVerify;
@@ -350,6 +544,13 @@ impl Channel for IrcChannel {
         "irc"
     }
 
+    /// IRC lines are capped at 512 bytes including protocol overhead; 400
+    /// chars keeps typical content comfortably under that after the sender
+    /// prefix reserve.
+    fn outbound_message_limit(&self) -> usize {
+        400
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let mut guard = self.writer.lock().await;
         let writer = guard
@@ -360,10 +561,19 @@ impl Channel for IrcChannel {
         // 512 - sender prefix (~64 bytes for :nick!user@host) - "PRIVMSG " - target - " :" - "\r\n"
         let overhead = SENDER_PREFIX_RESERVE + 10 + message.recipient.len() + 2;
         let max_payload = 512_usize.saturating_sub(overhead);
-        let chunks = split_message(&message.content, max_payload);
 
-        for chunk in chunks {
-            Self::send_raw(writer, &format!("PRIVMSG {} :{chunk}", message.recipient)).await?;
+        // Shared splitter first for paragraph-aware parts with "[i/n]"
+        // numbering; the byte-exact IRC splitter then guards the 512-byte
+        // line limit (the shared limit is in chars, not bytes).
+        let parts = super::split_outbound_message(
+            &message.content,
+            self.outbound_message_limit(),
+            super::OutboundFormat::Plain,
+        );
+        for part in parts {
+            for chunk in split_message(&part, max_payload) {
+                Self::send_raw(writer, &format!("PRIVMSG {} :{chunk}", message.recipient)).await?;
+            }
         }
 
         Ok(())
@@ -408,7 +618,10 @@ impl Channel for IrcChannel {
         let mut buf_reader = BufReader::new(reader);
         let mut line = String::new();
         let mut registered = false;
-        let mut sasl_pending = self.sasl_password.is_some();
+        let mut sasl = self
+            .sasl_password
+            .as_ref()
+            .map(|password| SaslNegotiator::new(current_nick.clone(), password.clone()));
 
         loop {
             line.clear();
@@ -425,78 +638,28 @@ impl Channel for IrcChannel {
                 continue;
             };
 
-            match msg.command.as_str() {
-                "PING" => {
-                    let token = msg.params.first().map_or("", String::as_str);
-                    let mut guard = self.writer.lock().await;
-                    if let Some(ref mut w) = *guard {
-                        Self::send_raw(w, &format!("PONG :{token}")).await?;
-                    }
-                }
-
-                // CAP responses for SASL
-                "CAP" => {
-                    if sasl_pending && msg.params.iter().any(|p| p.contains("sasl")) {
-                        if msg.params.iter().any(|p| p.contains("ACK")) {
-                            // CAP * ACK :sasl — server accepted, start SASL auth
-                            let mut guard = self.writer.lock().await;
-                            if let Some(ref mut w) = *guard {
-                                Self::send_raw(w, "AUTHENTICATE PLAIN").await?;
-                            }
-                        } else if msg.params.iter().any(|p| p.contains("NAK")) {
-                            // CAP * NAK :sasl — server rejected SASL, proceed without it
-                            tracing::warn!(
-                                "IRC server does not support SASL, continuing without it"
-                            );
-                            sasl_pending = false;
-                            let mut guard = self.writer.lock().await;
-                            if let Some(ref mut w) = *guard {
-                                Self::send_raw(w, "CAP END").await?;
-                            }
-                        }
-                    }
-                }
-
-                "AUTHENTICATE" => {
-                    // Server sends "AUTHENTICATE +" to request credentials
-                    if sasl_pending && msg.params.first().is_some_and(|p| p == "+") {
-                        // sasl_password is loaded from runtime config, not hard-coded
-                        if let Some(password) = self.sasl_password.as_deref() {
-                            let encoded = encode_sasl_plain(&current_nick, password);
-                            let mut guard = self.writer.lock().await;
-                            if let Some(ref mut w) = *guard {
-                                Self::send_raw(w, &format!("AUTHENTICATE {encoded}")).await?;
-                            }
-                        } else {
-                            // SASL was requested but no password is configured; abort SASL
-                            tracing::warn!(
-                                "SASL authentication requested but no SASL password is configured; aborting SASL"
-                            );
-                            sasl_pending = false;
-                            let mut guard = self.writer.lock().await;
-                            if let Some(ref mut w) = *guard {
-                                Self::send_raw(w, "CAP END").await?;
+            // Feed the SASL state machine first — it owns CAP/AUTHENTICATE
+            // and the 903-907 numerics until negotiation finishes.
+            if let Some(ref mut negotiator) = sasl {
+                if !negotiator.is_finished() {
+                    let actions = negotiator.on_message(&msg);
+                    if !actions.is_empty() {
+                        let mut guard = self.writer.lock().await;
+                        if let Some(ref mut w) = *guard {
+                            for action in &actions {
+                                Self::send_raw(w, action).await?;
                             }
                         }
                     }
                 }
+            }
 
-                // RPL_SASLSUCCESS (903) — SASL done, end CAP
-                "903" => {
-                    sasl_pending = false;
-                    let mut guard = self.writer.lock().await;
-                    if let Some(ref mut w) = *guard {
-                        Self::send_raw(w, "CAP END").await?;
-                    }
-                }
-
-                // SASL failure (904, 905, 906, 907)
-                "904" | "905" | "906" | "907" => {
-                    tracing::warn!("IRC SASL authentication failed ({})", msg.command);
-                    sasl_pending = false;
+            match msg.command.as_str() {
+                "PING" => {
+                    let token = msg.params.first().map_or("", String::as_str);
                     let mut guard = self.writer.lock().await;
                     if let Some(ref mut w) = *guard {
-                        Self::send_raw(w, "CAP END").await?;
+                        Self::send_raw(w, &format!("PONG :{token}")).await?;
                     }
                 }
 
@@ -532,6 +695,10 @@ impl Channel for IrcChannel {
                         Self::send_raw(w, &format!("NICK {alt}")).await?;
                     }
                     current_nick = alt;
+                    // Keep SASL credentials in sync with the effective nick
+                    if let Some(ref mut negotiator) = sasl {
+                        negotiator.nick.clone_from(&current_nick);
+                    }
                 }
 
                 "PRIVMSG" => {
@@ -550,13 +717,13 @@ impl Channel for IrcChannel {
                         continue;
                     }
 
-                    if !self.is_user_allowed(sender_nick) {
-                        continue;
-                    }
-
                     // Determine reply target: if sent to a channel, reply to channel;
                     // if DM (target == our nick), reply to sender
                     let is_channel = target.starts_with('#') || target.starts_with('&');
+
+                    if !self.is_user_allowed_in(sender_nick, is_channel.then_some(target)) {
+                        continue;
+                    }
                     let reply_target = if is_channel {
                         target.to_string()
                     } else {
@@ -582,7 +749,11 @@ impl Channel for IrcChannel {
                         thread_ts: None,
                         reply_to_message_id: None,
                         interruption_scope_id: None,
+                        is_edit: false,
+                        is_group: false,
+                        payload: None,
                         attachments: vec![],
+                        metadata: None,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -724,6 +895,88 @@ mod tests {
         assert_eq!(encoded, "AG5pY2sA");
     }
 
+    // ── SASL negotiation state machine ──────────────────────
+
+    fn make_negotiator() -> SaslNegotiator {
+        SaslNegotiator::new("jilles".into(), "sesame".into())
+    }
+
+    #[test]
+    fn sasl_cap_ack_starts_authentication() {
+        let mut neg = make_negotiator();
+        let msg = IrcMessage::parse(":server CAP * ACK :sasl").unwrap();
+        assert_eq!(neg.on_message(&msg), vec!["AUTHENTICATE PLAIN"]);
+        assert!(!neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_cap_nak_abandons_negotiation() {
+        let mut neg = make_negotiator();
+        let msg = IrcMessage::parse(":server CAP * NAK :sasl").unwrap();
+        assert_eq!(neg.on_message(&msg), vec!["CAP END"]);
+        assert!(neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_challenge_sends_encoded_credentials() {
+        let mut neg = make_negotiator();
+        neg.on_message(&IrcMessage::parse(":server CAP * ACK :sasl").unwrap());
+        let actions = neg.on_message(&IrcMessage::parse("AUTHENTICATE +").unwrap());
+        assert_eq!(actions, vec!["AUTHENTICATE AGppbGxlcwBzZXNhbWU="]);
+        assert!(!neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_challenge_before_cap_ack_is_ignored() {
+        let mut neg = make_negotiator();
+        let actions = neg.on_message(&IrcMessage::parse("AUTHENTICATE +").unwrap());
+        assert!(actions.is_empty());
+        assert!(!neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_success_ends_cap_negotiation() {
+        let mut neg = make_negotiator();
+        neg.on_message(&IrcMessage::parse(":server CAP * ACK :sasl").unwrap());
+        neg.on_message(&IrcMessage::parse("AUTHENTICATE +").unwrap());
+        let actions =
+            neg.on_message(&IrcMessage::parse(":server 903 jilles :SASL successful").unwrap());
+        assert_eq!(actions, vec!["CAP END"]);
+        assert!(neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_failure_ends_cap_negotiation() {
+        for code in ["904", "905", "906", "907"] {
+            let mut neg = make_negotiator();
+            neg.on_message(&IrcMessage::parse(":server CAP * ACK :sasl").unwrap());
+            neg.on_message(&IrcMessage::parse("AUTHENTICATE +").unwrap());
+            let line = format!(":server {code} jilles :SASL failed");
+            let actions = neg.on_message(&IrcMessage::parse(&line).unwrap());
+            assert_eq!(actions, vec!["CAP END"], "code {code}");
+            assert!(neg.is_finished(), "code {code}");
+        }
+    }
+
+    #[test]
+    fn sasl_ignores_unrelated_messages() {
+        let mut neg = make_negotiator();
+        let ping = IrcMessage::parse("PING :token").unwrap();
+        assert!(neg.on_message(&ping).is_empty());
+        let cap_other = IrcMessage::parse(":server CAP * ACK :multi-prefix").unwrap();
+        assert!(neg.on_message(&cap_other).is_empty());
+        assert!(!neg.is_finished());
+    }
+
+    #[test]
+    fn sasl_finished_stays_inert() {
+        let mut neg = make_negotiator();
+        neg.on_message(&IrcMessage::parse(":server CAP * NAK :sasl").unwrap());
+        assert!(neg.is_finished());
+        let actions = neg.on_message(&IrcMessage::parse(":server CAP * ACK :sasl").unwrap());
+        assert!(actions.is_empty());
+    }
+
     // ── Message splitting ───────────────────────────────────
 
     #[test]
@@ -827,10 +1080,12 @@ mod tests {
             username: None,
             channels: vec![],
             allowed_users: vec!["alice".into(), "bob".into()],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         });
         assert!(ch.is_user_allowed("alice"));
         assert!(ch.is_user_allowed("bob"));
@@ -846,10 +1101,12 @@ mod tests {
             username: None,
             channels: vec![],
             allowed_users: vec!["Alice".into()],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         });
         assert!(ch.is_user_allowed("alice"));
         assert!(ch.is_user_allowed("ALICE"));
@@ -865,14 +1122,99 @@ mod tests {
             username: None,
             channels: vec![],
             allowed_users: vec![],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         });
         assert!(!ch.is_user_allowed("anyone"));
     }
 
+    // ── Per-channel allowlist ───────────────────────────────
+
+    fn make_channel_with_overrides() -> IrcChannel {
+        let mut channel_allowed_users = std::collections::HashMap::new();
+        channel_allowed_users.insert("#ops".to_string(), vec!["carol".to_string()]);
+        channel_allowed_users.insert("#open".to_string(), vec!["*".to_string()]);
+        IrcChannel::new(IrcChannelConfig {
+            server: "irc.test".into(),
+            port: 6697,
+            nickname: "bot".into(),
+            username: None,
+            channels: vec!["#ops".into(), "#open".into()],
+            allowed_users: vec!["alice".into()],
+            channel_allowed_users,
+            server_password: None,
+            nickserv_password: None,
+            sasl_password: None,
+            verify_tls: true,
+            tls_fingerprint: None,
+        })
+    }
+
+    #[test]
+    fn channel_allowlist_overrides_global() {
+        let ch = make_channel_with_overrides();
+        assert!(ch.is_user_allowed_in("carol", Some("#ops")));
+        // alice is globally allowed but #ops has its own list
+        assert!(!ch.is_user_allowed_in("alice", Some("#ops")));
+    }
+
+    #[test]
+    fn channel_allowlist_wildcard_allows_anyone() {
+        let ch = make_channel_with_overrides();
+        assert!(ch.is_user_allowed_in("stranger", Some("#open")));
+    }
+
+    #[test]
+    fn unlisted_channel_uses_global_allowlist() {
+        let ch = make_channel_with_overrides();
+        assert!(ch.is_user_allowed_in("alice", Some("#random")));
+        assert!(!ch.is_user_allowed_in("carol", Some("#random")));
+    }
+
+    #[test]
+    fn dm_uses_global_allowlist() {
+        let ch = make_channel_with_overrides();
+        assert!(ch.is_user_allowed_in("alice", None));
+        assert!(!ch.is_user_allowed_in("carol", None));
+    }
+
+    #[test]
+    fn channel_allowlist_is_case_insensitive() {
+        let ch = make_channel_with_overrides();
+        assert!(ch.is_user_allowed_in("CAROL", Some("#OPS")));
+    }
+
+    // ── TLS fingerprint parsing ─────────────────────────────
+
+    #[test]
+    fn parse_fingerprint_plain_hex() {
+        let hex = "ab".repeat(32);
+        let parsed = parse_fingerprint(&hex).unwrap();
+        assert_eq!(parsed, vec![0xab; 32]);
+    }
+
+    #[test]
+    fn parse_fingerprint_accepts_colons_and_mixed_case() {
+        let colons = (0..32).map(|_| "Ab").collect::<Vec<_>>().join(":");
+        let parsed = parse_fingerprint(&colons).unwrap();
+        assert_eq!(parsed, vec![0xab; 32]);
+    }
+
+    #[test]
+    fn parse_fingerprint_rejects_wrong_length() {
+        assert!(parse_fingerprint("abcd").is_err());
+        assert!(parse_fingerprint(&"ab".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn parse_fingerprint_rejects_non_hex() {
+        assert!(parse_fingerprint(&"zz".repeat(32)).is_err());
+    }
+
     // ── Constructor ─────────────────────────────────────────
 
     #[test]
@@ -884,10 +1226,12 @@ mod tests {
             username: None,
             channels: vec![],
             allowed_users: vec![],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         });
         assert_eq!(ch.username, "mybot");
     }
@@ -901,10 +1245,12 @@ mod tests {
             username: Some("customuser".into()),
             channels: vec![],
             allowed_users: vec![],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         });
         assert_eq!(ch.username, "customuser");
         assert_eq!(ch.nickname, "mybot");
@@ -925,10 +1271,12 @@ mod tests {
             username: Some("zeroclaw".into()),
             channels: vec!["#test".into()],
             allowed_users: vec!["alice".into()],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: Some("serverpass".into()),
             nickserv_password: Some("nspass".into()),
             sasl_password: Some("saslpass".into()),
             verify_tls: false,
+            tls_fingerprint: None,
         });
         assert_eq!(ch.server, "irc.example.com");
         assert_eq!(ch.port, 6697);
@@ -955,10 +1303,12 @@ mod tests {
             username: Some("zeroclaw".into()),
             channels: vec!["#test".into(), "#dev".into()],
             allowed_users: vec!["alice".into()],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: Some("secret".into()),
             sasl_password: None,
             verify_tls: Some(true),
+            tls_fingerprint: None,
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -994,6 +1344,29 @@ nickname = "bot"
         assert!(parsed.nickserv_password.is_none());
         assert!(parsed.sasl_password.is_none());
         assert!(parsed.verify_tls.is_none());
+        assert!(parsed.tls_fingerprint.is_none());
+        assert!(parsed.channel_allowed_users.is_empty());
+    }
+
+    #[test]
+    fn irc_config_channel_allowlist_toml() {
+        use crate::config::schema::IrcConfig;
+
+        let toml_str = r##"
+server = "irc.example.com"
+nickname = "bot"
+channels = ["#ops", "#dev"]
+
+[channel_allowed_users]
+"#ops" = ["carol", "dave"]
+"##;
+        let parsed: IrcConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(parsed.channels, vec!["#ops", "#dev"]);
+        assert_eq!(
+            parsed.channel_allowed_users.get("#ops"),
+            Some(&vec!["carol".to_string(), "dave".to_string()])
+        );
+        assert!(!parsed.channel_allowed_users.contains_key("#dev"));
     }
 
     #[test]
@@ -1015,10 +1388,12 @@ nickname = "bot"
             username: None,
             channels: vec!["#zeroclaw".into()],
             allowed_users: vec!["*".into()],
+            channel_allowed_users: std::collections::HashMap::new(),
             server_password: None,
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            tls_fingerprint: None,
         })
     }
 }
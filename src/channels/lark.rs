@@ -1030,6 +1030,7 @@ impl LarkChannel {
                         reply_to_message_id: None,
                         interruption_scope_id: None,
                         attachments: vec![],
+                        metadata: None,
                     };
 
                     tracing::debug!("Lark WS: message in {}", lark_msg.chat_id);
@@ -1558,6 +1559,7 @@ impl LarkChannel {
             reply_to_message_id: None,
             interruption_scope_id: None,
             attachments: vec![],
+            metadata: None,
         }]
     }
 
@@ -1775,6 +1777,7 @@ impl LarkChannel {
             reply_to_message_id: None,
             interruption_scope_id: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
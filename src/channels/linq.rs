@@ -269,7 +269,11 @@ impl LinqChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
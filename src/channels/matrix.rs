@@ -1199,6 +1199,7 @@ impl Channel for MatrixChannel {
                     reply_to_message_id: None,
                     interruption_scope_id: thread_ts,
                     attachments: vec![],
+                    metadata: None,
                 };
 
                 let _ = tx.send(msg).await;
@@ -1,11 +1,16 @@
+use super::media_markers::MediaMarker;
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use parking_lot::Mutex;
+use std::path::Path;
 use std::sync::Arc;
 
 const MAX_MATTERMOST_AUDIO_BYTES: u64 = 25 * 1024 * 1024;
 
+/// Mattermost's default post length limit (16383 characters).
+const MATTERMOST_MAX_MESSAGE_LENGTH: usize = 16383;
+
 /// Mattermost channel — polls channel posts via REST API v4.
 /// Mattermost is API-compatible with many Slack patterns but uses a dedicated v4 structure.
 pub struct MattermostChannel {
@@ -24,6 +29,8 @@ pub struct MattermostChannel {
     proxy_url: Option<String>,
     transcription: Option<crate::config::TranscriptionConfig>,
     transcription_manager: Option<Arc<super::transcription::TranscriptionManager>>,
+    /// Cached `MaxFileSize` (bytes) advertised by the server's client config.
+    max_file_size: Mutex<Option<u64>>,
 }
 
 impl MattermostChannel {
@@ -48,6 +55,7 @@ impl MattermostChannel {
             proxy_url: None,
             transcription: None,
             transcription_manager: None,
+            max_file_size: Mutex::new(None),
         }
     }
 
@@ -85,6 +93,45 @@ impl MattermostChannel {
         self.allowed_users.iter().any(|u| u == "*" || u == user_id)
     }
 
+    /// Inspect a post's `metadata.reactions` for 👍/👎 approval shortcuts.
+    ///
+    /// The posts-since poll returns updated posts as well as new ones, so a
+    /// reaction added to an approval prompt shows up here on the next poll.
+    /// The pending-approvals registry makes repeat sightings of the same
+    /// reaction idempotent.
+    fn process_post_reactions(&self, post: &serde_json::Value) {
+        let Some(post_id) = post.get("id").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let Some(reactions) = post
+            .get("metadata")
+            .and_then(|m| m.get("reactions"))
+            .and_then(|r| r.as_array())
+        else {
+            return;
+        };
+
+        for reaction in reactions {
+            let Some(user_id) = reaction.get("user_id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(emoji_name) = reaction.get("emoji_name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if !self.is_user_allowed(user_id) {
+                tracing::debug!("Mattermost: ignoring reaction from unauthorized user: {user_id}");
+                continue;
+            }
+
+            super::handle_approval_reaction(&super::traits::ReactionEvent {
+                channel: "mattermost".to_string(),
+                message_id: post_id.to_string(),
+                actor: user_id.to_string(),
+                emoji: emoji_name.to_string(),
+            });
+        }
+    }
+
     /// Get the bot's own user ID and username so we can ignore our own messages
     /// and detect @-mentions by username.
     async fn get_bot_identity(&self) -> (String, String) {
@@ -200,6 +247,118 @@ impl MattermostChannel {
             }
         }
     }
+
+    /// Fetch (and cache) the server's advertised `MaxFileSize` from the
+    /// client config. `None` when the server doesn't expose a limit or the
+    /// config fetch fails — uploads then rely on the server's own rejection.
+    async fn server_max_file_size(&self) -> Option<u64> {
+        if let Some(limit) = *self.max_file_size.lock() {
+            return Some(limit);
+        }
+
+        let config: serde_json::Value = self
+            .http_client()
+            .get(format!("{}/api/v4/config/client?format=old", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+
+        let limit = config
+            .get("MaxFileSize")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())?;
+        *self.max_file_size.lock() = Some(limit);
+        Some(limit)
+    }
+
+    /// Upload a local file via the files API and return its file id.
+    ///
+    /// The size is checked against the server's advertised limit first so
+    /// oversized files fail with a readable error instead of an opaque 413.
+    async fn upload_file(&self, channel_id: &str, path: &Path) -> Result<String> {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("attachment");
+
+        let bytes = tokio::fs::read(path).await?;
+        check_upload_size(
+            file_name,
+            bytes.len() as u64,
+            self.server_max_file_size().await,
+        )?;
+
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v4/files", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .query(&[("channel_id", channel_id), ("filename", file_name)])
+            .body(bytes)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+            bail!("Mattermost file upload failed ({status}): {body}");
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        json.get("file_infos")
+            .and_then(|f| f.as_array())
+            .and_then(|a| a.first())
+            .and_then(|f| f.get("id"))
+            .and_then(|i| i.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("Mattermost file upload response missing file id"))
+    }
+}
+
+/// Reject an upload that exceeds the server's advertised size limit.
+fn check_upload_size(file_name: &str, size: u64, limit: Option<u64>) -> Result<()> {
+    if let Some(limit) = limit {
+        if size > limit {
+            bail!(
+                "Mattermost upload rejected: {file_name} is {size} bytes, \
+                 server limit is {limit} bytes"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Split parsed markers into local files to upload and lines to append to
+/// the message text (URLs pass through as plain links; markers whose target
+/// doesn't resolve to a file are kept verbatim so nothing is silently lost).
+fn classify_outgoing_attachments(
+    markers: &[MediaMarker],
+) -> (Vec<std::path::PathBuf>, Vec<String>) {
+    let mut local_files = Vec::new();
+    let mut inline_lines = Vec::new();
+
+    for marker in markers {
+        let target = marker.target.trim();
+        if target.starts_with("https://") || target.starts_with("http://") {
+            inline_lines.push(target.to_string());
+            continue;
+        }
+
+        let path = Path::new(target);
+        if path.exists() && path.is_file() {
+            local_files.push(path.to_path_buf());
+        } else {
+            inline_lines.push(format!("[{}:{}]", marker.kind.marker_name(), target));
+        }
+    }
+
+    (local_files, inline_lines)
 }
 
 #[async_trait]
@@ -208,6 +367,10 @@ impl Channel for MattermostChannel {
         "mattermost"
     }
 
+    fn outbound_message_limit(&self) -> usize {
+        MATTERMOST_MAX_MESSAGE_LENGTH
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
         // Mattermost supports threading via 'root_id'.
         // We pack 'channel_id:root_id' into recipient if it's a thread.
@@ -217,33 +380,66 @@ impl Channel for MattermostChannel {
             (message.recipient.as_str(), None)
         };
 
-        let mut body_map = serde_json::json!({
-            "channel_id": channel_id,
-            "message": message.content
-        });
+        let (mut content, markers) = super::media_markers::parse_media_markers(&message.content);
+        let (local_files, inline_lines) = classify_outgoing_attachments(&markers);
+        if !inline_lines.is_empty() {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&inline_lines.join("\n"));
+        }
 
-        if let Some(root) = root_id {
-            body_map.as_object_mut().unwrap().insert(
-                "root_id".to_string(),
-                serde_json::Value::String(root.to_string()),
-            );
+        let mut file_ids = Vec::new();
+        for path in &local_files {
+            file_ids.push(self.upload_file(channel_id, path).await?);
         }
 
-        let resp = self
-            .http_client()
-            .post(format!("{}/api/v4/posts", self.base_url))
-            .bearer_auth(&self.bot_token)
-            .json(&body_map)
-            .send()
-            .await?;
+        let chunks = super::split_outbound_message(
+            &content,
+            MATTERMOST_MAX_MESSAGE_LENGTH,
+            super::OutboundFormat::Markdown,
+        );
 
-        let status = resp.status();
-        if !status.is_success() {
-            let body = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
-            bail!("Mattermost post failed ({status}): {body}");
+        for (i, chunk) in chunks.iter().enumerate() {
+            let mut body_map = serde_json::json!({
+                "channel_id": channel_id,
+                "message": chunk
+            });
+
+            if let Some(root) = root_id {
+                body_map.as_object_mut().unwrap().insert(
+                    "root_id".to_string(),
+                    serde_json::Value::String(root.to_string()),
+                );
+            }
+
+            if i == 0 && !file_ids.is_empty() {
+                body_map
+                    .as_object_mut()
+                    .unwrap()
+                    .insert("file_ids".to_string(), serde_json::json!(file_ids));
+            }
+
+            let resp = self
+                .http_client()
+                .post(format!("{}/api/v4/posts", self.base_url))
+                .bearer_auth(&self.bot_token)
+                .json(&body_map)
+                .send()
+                .await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let body = resp
+                    .text()
+                    .await
+                    .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+                bail!("Mattermost post failed ({status}): {body}");
+            }
+
+            if i < chunks.len() - 1 {
+                tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            }
         }
 
         Ok(())
@@ -319,6 +515,9 @@ impl Channel for MattermostChannel {
                         None
                     };
 
+                    // Reactions ride along on updated posts in the since poll.
+                    self.process_post_reactions(post);
+
                     if let Some(channel_msg) = self.parse_mattermost_post(
                         post,
                         &bot_user_id,
@@ -465,7 +664,11 @@ impl MattermostChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
     }
 }
@@ -1214,6 +1417,7 @@ mod tests {
                 google: None,
                 local_whisper: None,
                 transcribe_non_ptt_audio: false,
+                whisper_cpp: None,
             },
         );
         assert!(ch.transcription_manager.is_some());
@@ -1237,6 +1441,7 @@ mod tests {
                 google: None,
                 local_whisper: None,
                 transcribe_non_ptt_audio: false,
+                whisper_cpp: None,
             },
         );
         assert!(ch.transcription_manager.is_none());
@@ -1380,6 +1585,7 @@ mod tests {
                 google: None,
                 local_whisper: None,
                 transcribe_non_ptt_audio: false,
+                whisper_cpp: None,
             },
         );
 
@@ -1400,6 +1606,69 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn mattermost_marker_extraction_preserves_text_and_order() {
+        let (cleaned, markers) = crate::channels::media_markers::parse_media_markers(
+            "Here's the chart [IMAGE:/tmp/chart.png] and the raw data [FILE:/tmp/data.csv]",
+        );
+        assert_eq!(cleaned, "Here's the chart  and the raw data");
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].target, "/tmp/chart.png");
+        assert_eq!(markers[1].target, "/tmp/data.csv");
+    }
+
+    #[test]
+    fn mattermost_classify_keeps_upload_order_and_inlines_urls() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let first = temp.path().join("a.png");
+        let second = temp.path().join("b.pdf");
+        std::fs::write(&first, b"png").unwrap();
+        std::fs::write(&second, b"pdf").unwrap();
+
+        let markers = vec![
+            crate::channels::media_markers::MediaMarker {
+                kind: crate::channels::media_markers::MediaMarkerKind::Image,
+                target: first.to_string_lossy().into_owned(),
+            },
+            crate::channels::media_markers::MediaMarker {
+                kind: crate::channels::media_markers::MediaMarkerKind::Document,
+                target: "https://example.com/report.pdf".to_string(),
+            },
+            crate::channels::media_markers::MediaMarker {
+                kind: crate::channels::media_markers::MediaMarkerKind::Document,
+                target: second.to_string_lossy().into_owned(),
+            },
+            crate::channels::media_markers::MediaMarker {
+                kind: crate::channels::media_markers::MediaMarkerKind::Image,
+                target: "/nonexistent/missing.png".to_string(),
+            },
+        ];
+
+        let (local_files, inline_lines) = classify_outgoing_attachments(&markers);
+        assert_eq!(local_files, vec![first, second]);
+        assert_eq!(
+            inline_lines,
+            vec![
+                "https://example.com/report.pdf".to_string(),
+                "[IMAGE:/nonexistent/missing.png]".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn mattermost_oversized_upload_fails_with_readable_error() {
+        let err = check_upload_size("big.bin", 200, Some(100)).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("big.bin"), "got: {message}");
+        assert!(
+            message.contains("server limit is 100 bytes"),
+            "got: {message}"
+        );
+
+        assert!(check_upload_size("ok.bin", 100, Some(100)).is_ok());
+        assert!(check_upload_size("any.bin", u64::MAX, None).is_ok());
+    }
+
     #[cfg(test)]
     mod http_tests {
         use super::*;
@@ -1453,6 +1722,7 @@ mod tests {
                     timeout_secs: 300,
                 }),
                 transcribe_non_ptt_audio: false,
+                whisper_cpp: None,
             });
 
             let post = json!({
@@ -1503,6 +1773,7 @@ mod tests {
                     timeout_secs: 300,
                 }),
                 transcribe_non_ptt_audio: false,
+                whisper_cpp: None,
             });
 
             let post = json!({
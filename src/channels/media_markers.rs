@@ -0,0 +1,191 @@
+//! Shared outbound media-marker parsing.
+//!
+//! Agent replies reference media with inline markers like `[IMAGE:/path/to/pic.png]`
+//! or `[DOCUMENT:https://example.com/report.pdf]`. Each channel decides how to
+//! deliver the referenced media (native upload, URL pass-through, …), but the
+//! marker grammar itself is channel-independent, so the parser lives here and
+//! Telegram, Discord, and Mattermost all share one implementation.
+
+/// Media kind named by a marker. `IMAGE`/`PHOTO` and `DOCUMENT`/`FILE` are
+/// accepted as aliases; the canonical name is what `marker_name` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaMarkerKind {
+    Image,
+    Document,
+    Video,
+    Audio,
+    Voice,
+}
+
+impl MediaMarkerKind {
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker.trim().to_ascii_uppercase().as_str() {
+            "IMAGE" | "PHOTO" => Some(Self::Image),
+            "DOCUMENT" | "FILE" => Some(Self::Document),
+            "VIDEO" => Some(Self::Video),
+            "AUDIO" => Some(Self::Audio),
+            "VOICE" => Some(Self::Voice),
+            _ => None,
+        }
+    }
+
+    /// Canonical marker name, used to reconstruct unresolved markers verbatim.
+    pub fn marker_name(self) -> &'static str {
+        match self {
+            Self::Image => "IMAGE",
+            Self::Document => "DOCUMENT",
+            Self::Video => "VIDEO",
+            Self::Audio => "AUDIO",
+            Self::Voice => "VOICE",
+        }
+    }
+}
+
+/// A single parsed marker: the media kind plus its target (local path or URL).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaMarker {
+    pub kind: MediaMarkerKind,
+    pub target: String,
+}
+
+/// Find the `]` that closes the `[` just before `s`, honoring nesting so
+/// targets containing brackets (e.g. `[DOCUMENT:/tmp/report [v2].pdf]`)
+/// parse as one marker.
+fn find_matching_close(s: &str) -> Option<usize> {
+    let mut depth = 1usize;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Extract media markers from an outbound message.
+///
+/// Returns the message with recognized markers removed (trimmed) plus the
+/// markers in the order they appeared. Bracketed text that isn't a valid
+/// marker — unknown kind, empty target, no `kind:target` shape — is left in
+/// the message untouched.
+pub fn parse_media_markers(message: &str) -> (String, Vec<MediaMarker>) {
+    let mut cleaned = String::with_capacity(message.len());
+    let mut markers = Vec::new();
+    let mut cursor = 0;
+
+    while cursor < message.len() {
+        let Some(open_rel) = message[cursor..].find('[') else {
+            cleaned.push_str(&message[cursor..]);
+            break;
+        };
+
+        let open = cursor + open_rel;
+        cleaned.push_str(&message[cursor..open]);
+
+        let Some(close_rel) = find_matching_close(&message[open + 1..]) else {
+            cleaned.push_str(&message[open..]);
+            break;
+        };
+
+        let close = open + 1 + close_rel;
+        let marker = &message[open + 1..close];
+
+        let parsed = marker.split_once(':').and_then(|(kind, target)| {
+            let kind = MediaMarkerKind::from_marker(kind)?;
+            let target = target.trim();
+            if target.is_empty() {
+                return None;
+            }
+            Some(MediaMarker {
+                kind,
+                target: target.to_string(),
+            })
+        });
+
+        if let Some(marker) = parsed {
+            markers.push(marker);
+        } else {
+            cleaned.push_str(&message[open..=close]);
+        }
+
+        cursor = close + 1;
+    }
+
+    (cleaned.trim().to_string(), markers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_multiple_kinds() {
+        let message = "Here you go [IMAGE:/tmp/chart.png] and [DOCUMENT:/tmp/report.pdf]";
+        let (cleaned, markers) = parse_media_markers(message);
+        assert_eq!(cleaned, "Here you go  and");
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].kind, MediaMarkerKind::Image);
+        assert_eq!(markers[0].target, "/tmp/chart.png");
+        assert_eq!(markers[1].kind, MediaMarkerKind::Document);
+        assert_eq!(markers[1].target, "/tmp/report.pdf");
+    }
+
+    #[test]
+    fn parse_preserves_marker_order() {
+        let message = "[VIDEO:/a.mp4][IMAGE:/b.png][AUDIO:/c.mp3]";
+        let (cleaned, markers) = parse_media_markers(message);
+        assert!(cleaned.is_empty());
+        let kinds: Vec<_> = markers.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MediaMarkerKind::Video,
+                MediaMarkerKind::Image,
+                MediaMarkerKind::Audio
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_accepts_kind_aliases() {
+        let (_, markers) = parse_media_markers("[PHOTO:/a.jpg] [FILE:/b.txt]");
+        assert_eq!(markers[0].kind, MediaMarkerKind::Image);
+        assert_eq!(markers[1].kind, MediaMarkerKind::Document);
+    }
+
+    #[test]
+    fn parse_leaves_unknown_markers_in_place() {
+        let message = "Result: [OK] and [THING:/x] stay, [note] too";
+        let (cleaned, markers) = parse_media_markers(message);
+        assert!(markers.is_empty());
+        assert_eq!(cleaned, message);
+    }
+
+    #[test]
+    fn parse_rejects_empty_target() {
+        let (cleaned, markers) = parse_media_markers("[IMAGE:  ]");
+        assert!(markers.is_empty());
+        assert_eq!(cleaned, "[IMAGE:  ]");
+    }
+
+    #[test]
+    fn parse_handles_nested_brackets_in_target() {
+        let (cleaned, markers) = parse_media_markers("[DOCUMENT:/tmp/report [v2].pdf] done");
+        assert_eq!(cleaned, "done");
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].target, "/tmp/report [v2].pdf");
+    }
+
+    #[test]
+    fn parse_keeps_unterminated_bracket_text() {
+        let (cleaned, markers) = parse_media_markers("trailing [IMAGE:/a.png");
+        assert!(markers.is_empty());
+        assert_eq!(cleaned, "trailing [IMAGE:/a.png");
+    }
+}
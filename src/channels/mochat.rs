@@ -200,7 +200,11 @@ impl Channel for MochatChannel {
                                 thread_ts: None,
                                 reply_to_message_id: None,
                                 interruption_scope_id: None,
+                                is_edit: false,
+                                is_group: false,
+                                payload: None,
                                 attachments: vec![],
+                                metadata: None,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -19,6 +19,7 @@ pub mod bluesky;
 pub mod clawdtalk;
 pub mod cli;
 pub mod debounce;
+pub mod dedup;
 pub mod dingtalk;
 pub mod discord;
 pub mod discord_history;
@@ -33,6 +34,7 @@ pub mod linq;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+pub mod media_markers;
 pub mod media_pipeline;
 pub mod mochat;
 pub mod nextcloud_talk;
@@ -40,7 +42,10 @@ pub mod nextcloud_talk;
 pub mod nostr;
 pub mod notion;
 pub mod qq;
+pub mod rate_limit;
 pub mod reddit;
+pub mod respond_policy;
+pub mod scheduler;
 pub mod session_backend;
 pub mod session_sqlite;
 pub mod session_store;
@@ -89,7 +94,7 @@ pub use reddit::RedditChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
-pub use traits::{Channel, SendMessage};
+pub use traits::{Channel, ChannelHealthReport, SendMessage};
 #[allow(unused_imports)]
 pub use tts::{TtsManager, TtsProvider};
 pub use twitter::TwitterChannel;
@@ -280,6 +285,14 @@ pub(crate) fn set_global_session_store(store: Arc<session_store::SessionStore>)
         .unwrap_or_else(|e| e.into_inner()) = Some(store);
 }
 
+/// Process-wide gauge of messages currently being processed by dispatch
+/// workers. Incremented/decremented in `dispatch_worker`; read by `/status`.
+static IN_FLIGHT_MESSAGE_GAUGE: AtomicU64 = AtomicU64::new(0);
+
+fn in_flight_message_count() -> u64 {
+    IN_FLIGHT_MESSAGE_GAUGE.load(Ordering::Relaxed)
+}
+
 /// Process-wide shared per-chat route overrides. Persisted to `routes.json` in the workspace.
 static GLOBAL_ROUTE_OVERRIDES: std::sync::LazyLock<RouteSelectionMap> =
     std::sync::LazyLock::new(|| Arc::new(Mutex::new(HashMap::new())));
@@ -298,7 +311,8 @@ fn init_route_overrides(workspace_dir: &std::path::Path) {
     *GLOBAL_ROUTES_FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some(routes_file.clone());
 
     if let Ok(text) = std::fs::read_to_string(&routes_file) {
-        if let Ok(map) = serde_json::from_str::<HashMap<String, ChannelRouteSelection>>(&text) {
+        if let Ok(mut map) = serde_json::from_str::<HashMap<String, ChannelRouteSelection>>(&text) {
+            let pruned = prune_stale_route_overrides(&mut map);
             let mut overrides = GLOBAL_ROUTE_OVERRIDES
                 .lock()
                 .unwrap_or_else(|e| e.into_inner());
@@ -308,11 +322,44 @@ fn init_route_overrides(workspace_dir: &std::path::Path) {
                 count = overrides.len(),
                 "Loaded per-chat route overrides from disk"
             );
+            if pruned > 0 {
+                save_route_overrides(&overrides);
+            }
         }
     }
 }
 
+/// Drop persisted overrides that reference providers missing from the
+/// catalog (renamed or removed since the entry was written). Returns how
+/// many entries were dropped.
+fn prune_stale_route_overrides(map: &mut HashMap<String, ChannelRouteSelection>) -> usize {
+    let catalog = crate::providers::list_providers();
+    let before = map.len();
+    map.retain(|sender_key, selection| {
+        let known = catalog.iter().any(|info| {
+            info.name.eq_ignore_ascii_case(&selection.provider)
+                || info
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(&selection.provider))
+        });
+        if !known {
+            tracing::warn!(
+                sender = %sender_key,
+                provider = %selection.provider,
+                "Dropping persisted route override for unknown provider"
+            );
+        }
+        known
+    });
+    before - map.len()
+}
+
 /// Persist current route overrides to routes.json (best-effort; logs on failure).
+///
+/// Callers hold the overrides mutex, so writes from concurrent in-flight
+/// messages are serialized; the temp-file + rename keeps the file whole if
+/// the process dies mid-write.
 fn save_route_overrides(overrides: &HashMap<String, ChannelRouteSelection>) {
     let path_guard = GLOBAL_ROUTES_FILE.lock().unwrap_or_else(|e| e.into_inner());
     let Some(ref path) = *path_guard else { return };
@@ -330,7 +377,8 @@ fn save_route_overrides(overrides: &HashMap<String, ChannelRouteSelection>) {
                     return;
                 }
             }
-            match std::fs::write(path, json) {
+            let tmp_path = path.with_extension("json.tmp");
+            match std::fs::write(&tmp_path, json).and_then(|()| std::fs::rename(&tmp_path, path)) {
                 Ok(()) => {
                     if !file_existed {
                         tracing::info!(
@@ -503,8 +551,52 @@ enum ChannelRuntimeCommand {
     ShowConfig,
     NewSession,
     Skills,
+    Status,
+    Usage,
     PiSteer(Option<String>), // /ps [text] — abort + optional followup message
     PiFollowup(String),      // /pf <text> — queue message while Pi busy
+    Cron(CronRuntimeCommand),
+    /// `/respondmode <mention|prefix|all|silent>` — flip the group respond
+    /// mode for the group the command was sent from.
+    RespondMode(String),
+    /// `/topic <new|close|reopen>` — Telegram forum topic administration.
+    Topic(TopicRuntimeCommand),
+}
+
+/// Subcommands of `/cron` — manage scheduled tasks from chat without shell
+/// access. Gated by the same non-CLI approver allowlist as approval prompts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CronRuntimeCommand {
+    List,
+    /// `/cron add <expr> | <message>` — recurring agent reminder.
+    Add {
+        expr: String,
+        message: String,
+    },
+    /// `/cron once <delay> <message>` — one-shot agent reminder (e.g. `30m`).
+    Once {
+        delay: String,
+        message: String,
+    },
+    Pause(String),
+    Resume(String),
+    Remove(String),
+    /// Unrecognized or incomplete subcommand — reply with usage help.
+    Usage,
+}
+
+/// Subcommands of `/topic` — manage Telegram forum topics from chat.
+/// Gated by the same non-CLI approver allowlist as `/cron`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TopicRuntimeCommand {
+    /// `/topic new <name>` — create a topic and announce its thread id.
+    New(String),
+    /// `/topic close` — close the topic the command was sent in.
+    Close,
+    /// `/topic reopen` — reopen the topic the command was sent in.
+    Reopen,
+    /// Unrecognized or incomplete subcommand — reply with usage help.
+    Usage,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -637,15 +729,67 @@ struct ChannelRuntimeContext {
     pacing: crate::config::PacingConfig,
     context_token_budget: usize,
     debouncer: Arc<debounce::MessageDebouncer>,
+    message_dedup: Arc<dedup::MessageDeduper>,
+    /// Live group respond policies by base channel name, so `/respondmode`
+    /// can flip the mode on the same object the listener consults.
+    respond_policies: Arc<HashMap<String, Arc<respond_policy::GroupRespondPolicy>>>,
 }
 
 #[derive(Clone)]
 struct InFlightSenderTaskState {
     task_id: u64,
+    /// Platform message id of the message being processed, so an edit of
+    /// that specific message can find and cancel the right task.
+    message_id: String,
+    /// Original message content, for rolling back the stale user turn when
+    /// an edit cancels this task.
+    content: String,
     cancellation: CancellationToken,
     completion: Arc<InFlightTaskCompletion>,
 }
 
+/// Completion record for a processed message, kept briefly so a later edit
+/// of the same message can decide between reprocessing and ignoring.
+struct RecentTurnCompletion {
+    content: String,
+    completed_at: Instant,
+}
+
+type RecentCompletionMap = Arc<std::sync::Mutex<HashMap<String, RecentTurnCompletion>>>;
+
+fn edited_message_key(msg: &traits::ChannelMessage) -> String {
+    format!("{}_{}_{}", msg.channel, msg.sender, msg.id)
+}
+
+/// What to do with an edited message, given the state of its original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditDisposition {
+    /// The original is still being processed — cancel it and reprocess the
+    /// edited content as a fresh turn.
+    CancelInFlight,
+    /// The reply already went out within the edit window — roll back the
+    /// stale turn and reprocess, prefixing the new reply.
+    Reprocess,
+    /// Outside the window (or never seen): ignore the edit.
+    Ignore,
+}
+
+fn classify_edited_message(
+    original_in_flight: bool,
+    completed_at: Option<Instant>,
+    window_secs: u64,
+) -> EditDisposition {
+    if original_in_flight {
+        return EditDisposition::CancelInFlight;
+    }
+    match completed_at {
+        Some(done) if window_secs > 0 && done.elapsed() <= Duration::from_secs(window_secs) => {
+            EditDisposition::Reprocess
+        }
+        _ => EditDisposition::Ignore,
+    }
+}
+
 struct InFlightTaskCompletion {
     done: AtomicBool,
     notify: tokio::sync::Notify,
@@ -874,6 +1018,313 @@ fn strip_tool_call_tags(message: &str) -> String {
     result.trim().to_string()
 }
 
+/// Extract `[LOCATION:lat,lon]` markers from an outbound message, mirroring
+/// the media markers (`[IMAGE:…]` etc.). Returns the text with markers
+/// removed plus the parsed coordinates; malformed markers are left in place.
+fn parse_location_markers(message: &str) -> (String, Vec<(f64, f64)>) {
+    const PREFIX: &str = "[LOCATION:";
+    let mut cleaned = String::with_capacity(message.len());
+    let mut locations = Vec::new();
+    let mut rest = message;
+
+    while let Some(open) = rest.find(PREFIX) {
+        let after_prefix = &rest[open + PREFIX.len()..];
+        let Some(close) = after_prefix.find(']') else {
+            break;
+        };
+        let coords = after_prefix[..close]
+            .split_once(',')
+            .and_then(|(lat, lon)| {
+                Some((
+                    lat.trim().parse::<f64>().ok()?,
+                    lon.trim().parse::<f64>().ok()?,
+                ))
+            });
+        match coords {
+            Some(pair) => {
+                cleaned.push_str(&rest[..open]);
+                locations.push(pair);
+            }
+            None => cleaned.push_str(&rest[..=open + PREFIX.len() + close]),
+        }
+        rest = &after_prefix[close + 1..];
+    }
+    cleaned.push_str(rest);
+
+    (cleaned.trim().to_string(), locations)
+}
+
+// ── Outbound message splitting ───────────────────────────────────
+
+/// How outbound content is formatted, for split-point decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutboundFormat {
+    /// Markdown: prefer paragraph boundaries and never split inside a fenced
+    /// code block — the fence is closed and reopened across parts instead.
+    Markdown,
+    /// Plain text: split at newlines/spaces only.
+    Plain,
+}
+
+/// Room reserved per part for the `"[i/n] "` numbering prefix.
+const OUTBOUND_PART_NUMBER_RESERVE: usize = 10;
+
+/// Split an outbound message into parts of at most `limit` characters.
+///
+/// Content at or under the limit is returned untouched as a single part.
+/// Longer content is split on paragraph boundaries where possible (newline,
+/// then space, as fallbacks), fenced code blocks are closed and reopened
+/// across parts rather than broken mid-fence, and when more than one part
+/// results each is prefixed with its position (`"[1/3] "`).
+pub(crate) fn split_outbound_message(
+    content: &str,
+    limit: usize,
+    format: OutboundFormat,
+) -> Vec<String> {
+    if content.chars().count() <= limit {
+        return vec![content.to_string()];
+    }
+
+    let effective = limit.saturating_sub(OUTBOUND_PART_NUMBER_RESERVE).max(1);
+    let parts = match format {
+        OutboundFormat::Markdown => split_markdown_parts(content, effective),
+        OutboundFormat::Plain => split_plain_parts(content, effective),
+    };
+    if parts.len() <= 1 {
+        return parts;
+    }
+
+    let total = parts.len();
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, part)| format!("[{}/{total}] {part}", i + 1))
+        .collect()
+}
+
+/// A markdown block: either free text (one paragraph) or a fenced code block.
+struct OutboundSegment {
+    text: String,
+    /// `Some(opening fence line)` for fenced code blocks (e.g. "```rust").
+    fence: Option<String>,
+}
+
+/// Split markdown content into parts, assembling whole paragraphs and fenced
+/// code blocks greedily up to `limit` characters per part.
+fn split_markdown_parts(content: &str, limit: usize) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for segment in segment_markdown(content) {
+        let segment_len = segment.text.chars().count();
+        let separator = if current.is_empty() { 0 } else { 2 };
+
+        if current.chars().count() + separator + segment_len <= limit {
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&segment.text);
+            continue;
+        }
+
+        if !current.is_empty() {
+            parts.push(std::mem::take(&mut current));
+        }
+
+        if segment_len <= limit {
+            current = segment.text;
+            continue;
+        }
+
+        // Oversized segment: split it internally. The last piece stays open
+        // so smaller trailing segments can share its part.
+        let pieces = match segment.fence {
+            Some(_) => split_fenced_block(&segment.text, limit),
+            None => split_plain_parts(&segment.text, limit),
+        };
+        let last = pieces.len().saturating_sub(1);
+        for (i, piece) in pieces.into_iter().enumerate() {
+            if i == last {
+                current = piece;
+            } else {
+                parts.push(piece);
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Break markdown content into paragraph and fenced-code-block segments.
+fn segment_markdown(content: &str) -> Vec<OutboundSegment> {
+    fn flush_paragraphs(buffer: &mut String, segments: &mut Vec<OutboundSegment>) {
+        for paragraph in buffer.split("\n\n") {
+            let trimmed = paragraph.trim_matches('\n');
+            if !trimmed.trim().is_empty() {
+                segments.push(OutboundSegment {
+                    text: trimmed.to_string(),
+                    fence: None,
+                });
+            }
+        }
+        buffer.clear();
+    }
+
+    let mut segments = Vec::new();
+    let mut buffer = String::new();
+    let mut fence: Option<String> = None;
+
+    for line in content.lines() {
+        let is_fence_line = line.trim_start().starts_with("```");
+        match (&fence, is_fence_line) {
+            (None, true) => {
+                flush_paragraphs(&mut buffer, &mut segments);
+                fence = Some(line.trim().to_string());
+                buffer = line.to_string();
+            }
+            (Some(open), true) => {
+                buffer.push('\n');
+                buffer.push_str(line);
+                segments.push(OutboundSegment {
+                    text: std::mem::take(&mut buffer),
+                    fence: Some(open.clone()),
+                });
+                fence = None;
+            }
+            (Some(_), false) => {
+                buffer.push('\n');
+                buffer.push_str(line);
+            }
+            (None, false) => {
+                if !buffer.is_empty() {
+                    buffer.push('\n');
+                }
+                buffer.push_str(line);
+            }
+        }
+    }
+
+    // Tail: either trailing paragraphs or an unclosed fence.
+    if let Some(open) = fence {
+        if !buffer.is_empty() {
+            segments.push(OutboundSegment {
+                text: buffer,
+                fence: Some(open),
+            });
+        }
+    } else {
+        flush_paragraphs(&mut buffer, &mut segments);
+    }
+    segments
+}
+
+/// Split a fenced code block larger than `limit`, closing the fence at each
+/// part boundary and reopening it (with its language tag) on the next part.
+fn split_fenced_block(block: &str, limit: usize) -> Vec<String> {
+    let mut lines: Vec<&str> = block.lines().collect();
+    let open = lines
+        .first()
+        .map_or_else(|| "```".to_string(), |l| l.trim().to_string());
+    if !lines.is_empty() {
+        lines.remove(0);
+    }
+    if lines.last().is_some_and(|l| l.trim() == "```") {
+        lines.pop();
+    }
+
+    // "open\n" before the code and "\n```" after it.
+    let overhead = open.chars().count() + 5;
+    let budget = limit.saturating_sub(overhead).max(1);
+
+    let mut groups: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        for chunk in chunk_by_chars(line, budget) {
+            let separator = if current.is_empty() { 0 } else { 1 };
+            if !current.is_empty()
+                && current.chars().count() + separator + chunk.chars().count() > budget
+            {
+                groups.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(chunk);
+        }
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    groups
+        .iter()
+        .map(|group| format!("{open}\n{group}\n```"))
+        .collect()
+}
+
+/// Split plain text into parts of at most `limit` characters, preferring
+/// newline then space boundaries. Content is preserved verbatim — parts
+/// concatenate back to the input.
+fn split_plain_parts(content: &str, limit: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut remaining = content;
+
+    while !remaining.is_empty() {
+        if remaining.chars().count() <= limit {
+            parts.push(remaining.to_string());
+            break;
+        }
+
+        let hard_split = remaining
+            .char_indices()
+            .nth(limit)
+            .map_or(remaining.len(), |(idx, _)| idx);
+        let search_area = &remaining[..hard_split];
+
+        // Prefer a newline break, then a space, as long as the break isn't
+        // so early that parts degenerate.
+        let cut = search_area
+            .rfind('\n')
+            .filter(|&pos| search_area[..pos].chars().count() >= limit / 2)
+            .map(|pos| pos + 1)
+            .or_else(|| {
+                search_area
+                    .rfind(' ')
+                    .filter(|&pos| search_area[..pos].chars().count() >= limit / 2)
+                    .map(|pos| pos + 1)
+            })
+            .unwrap_or(hard_split);
+
+        parts.push(remaining[..cut].to_string());
+        remaining = &remaining[cut..];
+    }
+    parts
+}
+
+/// Hard-split a single line at character boundaries every `budget` chars.
+fn chunk_by_chars(line: &str, budget: usize) -> Vec<&str> {
+    if line.chars().count() <= budget {
+        return vec![line];
+    }
+    let mut chunks = Vec::new();
+    let mut rest = line;
+    while rest.chars().count() > budget {
+        let cut = rest
+            .char_indices()
+            .nth(budget)
+            .map_or(rest.len(), |(idx, _)| idx);
+        chunks.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
 /// Static delivery instructions for gateway-only channels that lack a `Channel` trait impl.
 ///
 /// Channels with their own `Channel` impl should override `delivery_instructions()`
@@ -1433,6 +1884,84 @@ async fn handle_oc_bypass_if_needed(
     true
 }
 
+/// Map a reaction emoji (or platform emoji name) to an approval decision.
+/// 👍 counts as `/approve-confirm`, 👎 as `/approve-deny`.
+fn reaction_approval_decision(emoji: &str) -> Option<crate::approval::ApprovalResponse> {
+    match emoji {
+        "\u{1F44D}" | "+1" | "thumbsup" => Some(crate::approval::ApprovalResponse::Yes),
+        "\u{1F44E}" | "-1" | "thumbsdown" => Some(crate::approval::ApprovalResponse::No),
+        _ => None,
+    }
+}
+
+/// Handle an inbound emoji reaction as a possible approval shortcut.
+///
+/// Reactions on messages that aren't recorded approval prompts, and emojis
+/// other than 👍/👎, are ignored. Approver-allowlist and expiry checks are
+/// the same as the slash-command path — both are enforced by the
+/// pending-approvals registry. Reactions from non-approvers are ignored
+/// silently but recorded in the runtime trace.
+pub(crate) fn handle_approval_reaction(event: &traits::ReactionEvent) {
+    use crate::approval::ResolveOutcome;
+
+    let Some(decision) = reaction_approval_decision(&event.emoji) else {
+        return;
+    };
+    let Some(request_id) =
+        crate::approval::approval_prompt_index().request_id_for(&event.channel, &event.message_id)
+    else {
+        return;
+    };
+
+    match crate::approval::pending_approvals().resolve(&request_id, &event.actor, decision) {
+        ResolveOutcome::Resolved(resolved) => {
+            tracing::info!(
+                "Approval request {request_id} resolved as {resolved:?} via {} reaction from {}",
+                event.emoji,
+                event.actor
+            );
+            runtime_trace::record_event(
+                "approval_reaction_resolved",
+                Some(event.channel.as_str()),
+                None,
+                None,
+                None,
+                Some(true),
+                Some(&format!("{request_id} resolved via reaction")),
+                serde_json::json!({
+                    "request_id": request_id,
+                    "actor": event.actor,
+                    "emoji": event.emoji,
+                    "decision": format!("{resolved:?}"),
+                }),
+            );
+        }
+        ResolveOutcome::ActorNotAllowed => {
+            runtime_trace::record_event(
+                "approval_reaction_rejected",
+                Some(event.channel.as_str()),
+                None,
+                None,
+                None,
+                Some(false),
+                Some("actor not on approver allowlist"),
+                serde_json::json!({
+                    "request_id": request_id,
+                    "actor": event.actor,
+                    "emoji": event.emoji,
+                }),
+            );
+        }
+        outcome => {
+            tracing::debug!(
+                "Ignoring {} reaction from {} on approval request {request_id}: {outcome:?}",
+                event.emoji,
+                event.actor
+            );
+        }
+    }
+}
+
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
     let trimmed = strip_reply_quote(content).trim();
     if !trimmed.starts_with('/') {
@@ -1451,6 +1980,10 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         // `/new` is available on every channel — no model-switch gate.
         "/new" => Some(ChannelRuntimeCommand::NewSession),
         "/skills" => Some(ChannelRuntimeCommand::Skills),
+        // `/status` and `/usage` likewise work everywhere — they only read
+        // daemon state.
+        "/status" => Some(ChannelRuntimeCommand::Status),
+        "/usage" => Some(ChannelRuntimeCommand::Usage),
         // Our combined /models handler (Pi mode, provider selection, etc.)
         "/models" | "/model" if supports_runtime_model_switch(channel_name) => {
             let arg = parts.collect::<Vec<_>>().join(" ").trim().to_string();
@@ -1481,104 +2014,540 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::PiFollowup(text))
             }
         }
+        "/cron" => {
+            // Cron expressions contain spaces, so parse the raw remainder
+            // instead of the whitespace-split parts.
+            let rest = trimmed.strip_prefix(command_token).unwrap_or("").trim();
+            Some(ChannelRuntimeCommand::Cron(parse_cron_subcommand(rest)))
+        }
+        "/respondmode" => {
+            let arg = parts.collect::<Vec<_>>().join(" ").trim().to_string();
+            Some(ChannelRuntimeCommand::RespondMode(arg))
+        }
+        // Forum topics only exist on Telegram; other channels treat /topic
+        // as ordinary text.
+        "/topic" if channel_name == "telegram" => {
+            let rest = trimmed.strip_prefix(command_token).unwrap_or("").trim();
+            Some(ChannelRuntimeCommand::Topic(parse_topic_subcommand(rest)))
+        }
         _ => None,
     }
 }
 
-/// Format loaded skills as a numbered list for the `/skills` command response.
-fn format_skills_list(skills: &[(String, String)]) -> String {
-    if skills.is_empty() {
-        return "No skills loaded.".to_string();
-    }
-    let mut out = String::from("Available skills:\n");
-    for (i, (name, desc)) in skills.iter().enumerate() {
-        let cmd_name = name.replace('-', "_");
-        use std::fmt::Write as _;
-        let _ = write!(out, "\n{}. /{} — {}", i + 1, cmd_name, desc);
-    }
-    out
-}
+fn parse_topic_subcommand(rest: &str) -> TopicRuntimeCommand {
+    let (sub, args) = rest
+        .split_once(char::is_whitespace)
+        .map_or((rest, ""), |(sub, args)| (sub, args.trim()));
 
-/// Try to rewrite a `/skill_name args` message into `[Skill: skill-name] args`.
-/// Returns `Some(rewritten)` if the command matches a loaded skill, `None` otherwise.
-fn try_rewrite_skill_command(content: &str, skills: &[(String, String)]) -> Option<String> {
-    let trimmed = strip_reply_quote(content).trim();
-    if !trimmed.starts_with('/') {
-        return None;
+    match sub.to_ascii_lowercase().as_str() {
+        "new" if !args.is_empty() => TopicRuntimeCommand::New(args.to_string()),
+        "close" if args.is_empty() => TopicRuntimeCommand::Close,
+        "reopen" if args.is_empty() => TopicRuntimeCommand::Reopen,
+        _ => TopicRuntimeCommand::Usage,
     }
+}
 
-    let mut parts = trimmed.splitn(2, char::is_whitespace);
-    let command_token = parts.next()?;
-    let args = parts.next().unwrap_or("").trim();
-
-    // Strip leading '/' and optional @bot_name suffix
-    let cmd = command_token
-        .trim_start_matches('/')
-        .split('@')
-        .next()
-        .unwrap_or("")
-        .to_ascii_lowercase();
-
-    // Map underscores back to hyphens for skill lookup
-    let skill_name = cmd.replace('_', "-");
+const TOPIC_COMMAND_USAGE: &str = "Topic commands (forum supergroups only):\n\
+    /topic new <name> — create a topic and announce its thread id\n\
+    /topic close — close the topic this command was sent in\n\
+    /topic reopen — reopen the topic this command was sent in";
 
-    // Check if it matches a loaded skill
-    if skills.iter().any(|(name, _)| *name == skill_name) {
-        if args.is_empty() {
-            Some(format!("[Skill: {skill_name}]"))
-        } else {
-            Some(format!("[Skill: {skill_name}] {args}"))
+/// Execute a `/topic` subcommand against the channel's forum-topic API.
+/// Close/reopen act on the topic the command arrived in, so they require a
+/// thread context.
+async fn handle_topic_command(
+    channel: &Arc<dyn Channel>,
+    msg: &traits::ChannelMessage,
+    command: &TopicRuntimeCommand,
+) -> String {
+    let action = match command {
+        TopicRuntimeCommand::New(name) => traits::ForumTopicAction::Create { name: name.clone() },
+        TopicRuntimeCommand::Close | TopicRuntimeCommand::Reopen => {
+            let Some(thread_id) = msg.thread_ts.as_deref().and_then(|t| t.parse::<i64>().ok())
+            else {
+                return "Send this command inside the topic you want to manage.".to_string();
+            };
+            match command {
+                TopicRuntimeCommand::Close => traits::ForumTopicAction::Close { thread_id },
+                _ => traits::ForumTopicAction::Reopen { thread_id },
+            }
         }
-    } else {
-        None
+        TopicRuntimeCommand::Usage => return TOPIC_COMMAND_USAGE.to_string(),
+    };
+
+    match channel.manage_forum_topic(&msg.reply_target, &action).await {
+        Ok(confirmation) => confirmation,
+        Err(e) => format!("Topic command failed: {e}"),
     }
 }
 
-fn resolve_provider_alias(name: &str) -> Option<String> {
-    let candidate = name.trim();
-    if candidate.is_empty() {
-        return None;
+fn parse_cron_subcommand(rest: &str) -> CronRuntimeCommand {
+    let (sub, args) = rest
+        .split_once(char::is_whitespace)
+        .map_or((rest, ""), |(sub, args)| (sub, args.trim()));
+
+    match sub.to_ascii_lowercase().as_str() {
+        "list" if args.is_empty() => CronRuntimeCommand::List,
+        "add" => match args.split_once('|') {
+            Some((expr, message)) if !expr.trim().is_empty() && !message.trim().is_empty() => {
+                CronRuntimeCommand::Add {
+                    expr: expr.trim().to_string(),
+                    message: message.trim().to_string(),
+                }
+            }
+            _ => CronRuntimeCommand::Usage,
+        },
+        "once" => match args.split_once(char::is_whitespace) {
+            Some((delay, message)) if !message.trim().is_empty() => CronRuntimeCommand::Once {
+                delay: delay.to_string(),
+                message: message.trim().to_string(),
+            },
+            _ => CronRuntimeCommand::Usage,
+        },
+        "pause" if !args.is_empty() => CronRuntimeCommand::Pause(args.to_string()),
+        "resume" if !args.is_empty() => CronRuntimeCommand::Resume(args.to_string()),
+        "remove" if !args.is_empty() => CronRuntimeCommand::Remove(args.to_string()),
+        _ => CronRuntimeCommand::Usage,
     }
+}
 
-    let providers_list = providers::list_providers();
-    for provider in providers_list {
-        if provider.name.eq_ignore_ascii_case(candidate)
-            || provider
-                .aliases
-                .iter()
-                .any(|alias| alias.eq_ignore_ascii_case(candidate))
-        {
-            return Some(provider.name.to_string());
-        }
+const CRON_COMMAND_USAGE: &str = "Cron commands:\n\
+    /cron list — show scheduled tasks\n\
+    /cron add <expr or phrase> | <message> — recurring reminder (e.g. /cron add 0 9 * * 1-5 | stand-up, or /cron add every weekday at 9am | stand-up)\n\
+    /cron once <delay> <message> — one-shot reminder (delay like 30s, 15m, 2h, 1d)\n\
+    /cron pause <id> / /cron resume <id> / /cron remove <id>";
+
+/// Delivery config for tasks created from chat: announce back to the
+/// conversation the command came from.
+fn cron_announce_delivery(msg: &traits::ChannelMessage) -> crate::cron::DeliveryConfig {
+    crate::cron::DeliveryConfig {
+        mode: "announce".to_string(),
+        channel: Some(msg.channel.clone()),
+        to: Some(msg.reply_target.clone()),
+        best_effort: true,
     }
-
-    None
 }
 
-fn resolved_default_provider(config: &Config) -> String {
-    config
-        .default_provider
-        .clone()
-        .unwrap_or_else(|| "openrouter".to_string())
+/// Whether `msg`'s sender may manage scheduled tasks. CLI is implicitly
+/// trusted; channel senders must pass the same `autonomy.approval_actors`
+/// allowlist used for answering approval prompts.
+fn may_manage_cron(msg: &traits::ChannelMessage, approval: &ApprovalManager) -> bool {
+    msg.channel == "cli" || approval.is_non_cli_approval_actor_allowed(&msg.sender)
 }
 
-fn resolved_default_model(config: &Config) -> String {
-    config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4.6".to_string())
-}
+/// Execute a `/cron` subcommand against the cron store. Errors from cron
+/// parsing/validation are relayed verbatim so users can fix their expressions.
+fn handle_cron_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    command: &CronRuntimeCommand,
+) -> String {
+    let config = ctx.prompt_config.as_ref();
+    if !config.cron.enabled {
+        return "Cron is disabled by config (cron.enabled = false).".to_string();
+    }
 
-fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
-    ChannelRuntimeDefaults {
-        default_provider: resolved_default_provider(config),
-        model: resolved_default_model(config),
-        temperature: config.default_temperature,
-        api_key: config.api_key.clone(),
-        api_url: config.api_url.clone(),
-        reliability: config.reliability.clone(),
-        session_report_dir: config.observability.session_report_dir.clone(),
-        session_report_max_files: config.observability.session_report_max_files,
+    match command {
+        CronRuntimeCommand::List => match crate::cron::list_jobs(config) {
+            Ok(jobs) if jobs.is_empty() => "No scheduled tasks.".to_string(),
+            Ok(jobs) => {
+                let mut out = format!("Scheduled tasks ({}):", jobs.len());
+                for job in jobs {
+                    use std::fmt::Write as _;
+                    let what = job
+                        .prompt
+                        .as_deref()
+                        .filter(|p| !p.is_empty())
+                        .unwrap_or(&job.command);
+                    let state = if job.enabled { "" } else { " [paused]" };
+                    let _ = write!(
+                        out,
+                        "\n- {}{state} | {} | next {} | {what}",
+                        job.id,
+                        job.expression,
+                        job.next_run.to_rfc3339()
+                    );
+                }
+                out
+            }
+            Err(e) => format!("Failed to list cron jobs: {e:#}"),
+        },
+        CronRuntimeCommand::Add { expr, message } => {
+            // Accept either a raw cron expression or a natural-language
+            // phrase ("every weekday at 9am"); valid cron syntax wins.
+            let now = chrono::Utc::now();
+            let cron_schedule = crate::cron::Schedule::Cron {
+                expr: expr.clone(),
+                tz: None,
+            };
+            let schedule = if crate::cron::validate_schedule(&cron_schedule, now).is_ok() {
+                cron_schedule
+            } else {
+                match crate::cron::parse_natural_schedule(
+                    expr,
+                    config.cron.default_timezone.as_deref(),
+                    now,
+                ) {
+                    Ok(schedule) => schedule,
+                    Err(e) => return format!("Could not parse schedule: {e:#}"),
+                }
+            };
+            let interpreted = crate::cron::describe_schedule(&schedule);
+            let one_shot = matches!(schedule, crate::cron::Schedule::At { .. });
+            match crate::cron::add_agent_job(
+                config,
+                None,
+                schedule,
+                message,
+                crate::cron::SessionTarget::Isolated,
+                None,
+                Some(cron_announce_delivery(msg)),
+                one_shot,
+                None,
+            ) {
+                Ok(job) => format!(
+                    "Scheduled task {} (interpreted as: {interpreted}), next run {}.",
+                    job.id,
+                    job.next_run.to_rfc3339()
+                ),
+                Err(e) => format!("Failed to create cron job: {e:#}"),
+            }
+        }
+        CronRuntimeCommand::Once { delay, message } => {
+            let at = match crate::cron::parse_delay(delay) {
+                Ok(duration) => chrono::Utc::now() + duration,
+                Err(e) => return format!("Invalid delay: {e:#}"),
+            };
+            match crate::cron::add_agent_job(
+                config,
+                None,
+                crate::cron::Schedule::At { at },
+                message,
+                crate::cron::SessionTarget::Isolated,
+                None,
+                Some(cron_announce_delivery(msg)),
+                true,
+                None,
+            ) {
+                Ok(job) => format!(
+                    "One-shot task {} scheduled for {}.",
+                    job.id,
+                    at.to_rfc3339()
+                ),
+                Err(e) => format!("Failed to create one-shot job: {e:#}"),
+            }
+        }
+        CronRuntimeCommand::Pause(id) => match crate::cron::pause_job(config, id) {
+            Ok(job) => format!("Paused task {}.", job.id),
+            Err(e) => format!("Failed to pause task: {e:#}"),
+        },
+        CronRuntimeCommand::Resume(id) => match crate::cron::resume_job(config, id) {
+            Ok(job) => format!(
+                "Resumed task {}, next run {}.",
+                job.id,
+                job.next_run.to_rfc3339()
+            ),
+            Err(e) => format!("Failed to resume task: {e:#}"),
+        },
+        CronRuntimeCommand::Remove(id) => match crate::cron::remove_job(config, id) {
+            Ok(()) => format!("Removed task {id}."),
+            Err(e) => format!("Failed to remove task: {e:#}"),
+        },
+        CronRuntimeCommand::Usage => CRON_COMMAND_USAGE.to_string(),
+    }
+}
+
+/// Execute `/respondmode <mode>`: flip the group respond mode for the group
+/// the command came from, on the live policy object shared with the listener.
+/// The override lasts until the daemon restarts; config is not rewritten.
+fn handle_respond_mode_command(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    arg: &str,
+) -> String {
+    let Some(mode) = respond_policy::RespondMode::parse_opt(arg) else {
+        return "Usage: /respondmode <mention|prefix|all|silent>".to_string();
+    };
+    let Some(group_id) = respond_policy::message_group_id(msg.metadata.as_ref()) else {
+        return "DMs always get replies; /respondmode only applies in group chats.".to_string();
+    };
+    // Rate-limited channels report e.g. "telegram" here too, so this matches
+    // the key used when the policy map was built.
+    let base_channel = msg.channel.split(':').next().unwrap_or(&msg.channel);
+    let Some(policy) = ctx.respond_policies.get(base_channel) else {
+        return format!("Channel {base_channel} does not support group respond modes.");
+    };
+    policy.set_override(group_id, mode);
+    format!("Respond mode for this group is now \"{}\".", mode.as_str())
+}
+
+/// Format loaded skills as a numbered list for the `/skills` command response.
+fn format_skills_list(skills: &[(String, String)]) -> String {
+    if skills.is_empty() {
+        return "No skills loaded.".to_string();
+    }
+    let mut out = String::from("Available skills:\n");
+    for (i, (name, desc)) in skills.iter().enumerate() {
+        let cmd_name = name.replace('-', "_");
+        use std::fmt::Write as _;
+        let _ = write!(out, "\n{}. /{} — {}", i + 1, cmd_name, desc);
+    }
+    out
+}
+
+/// Snapshot of daemon/runtime state behind the `/status` command.
+///
+/// Gathering is separated from formatting so tests can assert individual
+/// fields without string-matching the rendered reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RuntimeStatusReport {
+    uptime_seconds: u64,
+    /// Provider/model currently routing this sender's messages.
+    provider: String,
+    model: String,
+    memory_backend: String,
+    in_flight_messages: u64,
+    /// `(component, status, restart_count)` from the health registry.
+    components: Vec<(String, String, u64)>,
+    /// `None` when estop is disabled or its state can't be read.
+    estop_engaged: Option<bool>,
+    /// `(hits, misses)` from the provider response cache; `None` when the
+    /// cache is disabled.
+    response_cache: Option<(u64, u64)>,
+}
+
+/// Collect the data for a `/status` reply from the runtime context and the
+/// sender's current route selection.
+fn gather_runtime_status(
+    ctx: &ChannelRuntimeContext,
+    route: &ChannelRouteSelection,
+) -> RuntimeStatusReport {
+    let health = crate::health::snapshot();
+    let components = health
+        .components
+        .into_iter()
+        .map(|(name, component)| (name, component.status, component.restart_count))
+        .collect();
+
+    let estop_engaged = if ctx.prompt_config.security.estop.enabled {
+        ctx.prompt_config.config_path.parent().and_then(|dir| {
+            crate::security::EstopManager::load(&ctx.prompt_config.security.estop, dir)
+                .ok()
+                .map(|manager| manager.status().is_engaged())
+        })
+    } else {
+        None
+    };
+
+    RuntimeStatusReport {
+        uptime_seconds: health.uptime_seconds,
+        provider: route.provider.clone(),
+        model: route.model.clone(),
+        memory_backend: ctx.memory.name().to_string(),
+        in_flight_messages: in_flight_message_count(),
+        components,
+        estop_engaged,
+        response_cache: crate::providers::response_cache::global_counters(),
+    }
+}
+
+/// Render seconds as a compact `1d 2h 3m` / `5m 12s` string.
+fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = (seconds % 86_400) / 3_600;
+    let minutes = (seconds % 3_600) / 60;
+    let secs = seconds % 60;
+
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m {secs}s")
+    }
+}
+
+/// Format a [`RuntimeStatusReport`] as the compact `/status` reply.
+fn format_runtime_status(report: &RuntimeStatusReport) -> String {
+    let mut out = String::from("ZeroClaw status:\n");
+    let _ = writeln!(out, "• Uptime: {}", format_uptime(report.uptime_seconds));
+    let _ = writeln!(out, "• Route: {} ({})", report.model, report.provider);
+    let _ = writeln!(out, "• Memory: {}", report.memory_backend);
+    let _ = writeln!(out, "• In-flight messages: {}", report.in_flight_messages);
+    if let Some((hits, misses)) = report.response_cache {
+        let _ = writeln!(out, "• Response cache: {hits} hits / {misses} misses");
+    }
+    match report.estop_engaged {
+        Some(true) => {
+            let _ = writeln!(out, "• Estop: ⛔ ENGAGED");
+        }
+        Some(false) => {
+            let _ = writeln!(out, "• Estop: clear");
+        }
+        None => {}
+    }
+
+    if report.components.is_empty() {
+        out.push_str("• Components: none registered");
+    } else {
+        out.push_str("• Components:");
+        for (name, status, restarts) in &report.components {
+            let _ = write!(out, "\n  - {name}: {status}");
+            if *restarts > 0 {
+                let _ = write!(out, " (restarts: {restarts})");
+            }
+        }
+    }
+
+    crate::agent::loop_::scrub_credentials(out.trim_end())
+}
+
+/// Data behind the `/usage` command reply, separated from formatting so
+/// tests can assert fields directly.
+#[derive(Debug, Clone, PartialEq)]
+struct SenderUsageReport {
+    stats: crate::cost::SenderUsageStats,
+    /// Cost recorded across all senders today.
+    global_daily_cost_usd: f64,
+    /// `autonomy.max_cost_per_day_cents`, converted to USD.
+    daily_limit_usd: f64,
+}
+
+/// Collect the data for a `/usage` reply for one sender.
+///
+/// Per-sender numbers come from the in-process ledger; the global daily
+/// total prefers the persistent cost tracker when configured.
+fn gather_usage_report(ctx: &ChannelRuntimeContext, sender_key: &str) -> SenderUsageReport {
+    let ledger = crate::cost::sender_usage_ledger();
+    let global_daily_cost_usd = ctx
+        .cost_tracking
+        .as_ref()
+        .and_then(|state| {
+            state
+                .tracker
+                .get_daily_cost(chrono::Local::now().date_naive())
+                .ok()
+        })
+        .unwrap_or_else(|| ledger.global_daily_cost_usd());
+
+    SenderUsageReport {
+        stats: ledger.stats_for(sender_key),
+        global_daily_cost_usd,
+        daily_limit_usd: f64::from(ctx.autonomy_config.max_cost_per_day_cents) / 100.0,
+    }
+}
+
+/// Format a [`SenderUsageReport`] as the compact `/usage` reply.
+fn format_usage_report(report: &SenderUsageReport) -> String {
+    fn window_line(totals: &crate::cost::UsageWindowTotals) -> String {
+        format!(
+            "{} message{}, {} in / {} out tokens, ${:.4}",
+            totals.messages,
+            if totals.messages == 1 { "" } else { "s" },
+            totals.input_tokens,
+            totals.output_tokens,
+            totals.cost_usd
+        )
+    }
+
+    let mut out = String::from("Usage for this chat:\n");
+    let _ = writeln!(out, "• Today: {}", window_line(&report.stats.today));
+    let _ = writeln!(
+        out,
+        "• This month: {}",
+        window_line(&report.stats.this_month)
+    );
+    if report.stats.estimated {
+        out.push_str("• Token counts are estimated (provider returned no usage)\n");
+    }
+    let _ = write!(
+        out,
+        "• Global today: ${:.4} of ${:.2} daily limit",
+        report.global_daily_cost_usd, report.daily_limit_usd
+    );
+    out
+}
+
+/// Try to rewrite a `/skill_name args` message into `[Skill: skill-name] args`.
+/// Returns `Some(rewritten)` if the command matches a loaded skill, `None` otherwise.
+fn try_rewrite_skill_command(content: &str, skills: &[(String, String)]) -> Option<String> {
+    let trimmed = strip_reply_quote(content).trim();
+    if !trimmed.starts_with('/') {
+        return None;
+    }
+
+    let mut parts = trimmed.splitn(2, char::is_whitespace);
+    let command_token = parts.next()?;
+    let args = parts.next().unwrap_or("").trim();
+
+    // Strip leading '/' and optional @bot_name suffix
+    let cmd = command_token
+        .trim_start_matches('/')
+        .split('@')
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    // Map underscores back to hyphens for skill lookup
+    let skill_name = cmd.replace('_', "-");
+
+    // Check if it matches a loaded skill
+    if skills.iter().any(|(name, _)| *name == skill_name) {
+        if args.is_empty() {
+            Some(format!("[Skill: {skill_name}]"))
+        } else {
+            Some(format!("[Skill: {skill_name}] {args}"))
+        }
+    } else {
+        None
+    }
+}
+
+fn resolve_provider_alias(name: &str) -> Option<String> {
+    let candidate = name.trim();
+    if candidate.is_empty() {
+        return None;
+    }
+
+    let providers_list = providers::list_providers();
+    for provider in providers_list {
+        if provider.name.eq_ignore_ascii_case(candidate)
+            || provider
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(candidate))
+        {
+            return Some(provider.name.to_string());
+        }
+    }
+
+    None
+}
+
+fn resolved_default_provider(config: &Config) -> String {
+    config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".to_string())
+}
+
+fn resolved_default_model(config: &Config) -> String {
+    config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4.6".to_string())
+}
+
+fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
+    ChannelRuntimeDefaults {
+        default_provider: resolved_default_provider(config),
+        model: resolved_default_model(config),
+        temperature: config.default_temperature,
+        api_key: config.api_key.clone(),
+        api_url: config.api_url.clone(),
+        reliability: config.reliability.clone(),
+        session_report_dir: config.observability.session_report_dir.clone(),
+        session_report_max_files: config.observability.session_report_max_files,
         session_report_debug: config.observability.session_report_debug,
     }
 }
@@ -1798,6 +2767,17 @@ fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: Chan
     save_route_overrides(&routes);
 }
 
+/// Remove any stored override for `sender_key` (the `/model reset` path) and
+/// write through so the persisted entry is deleted too. `/new` deliberately
+/// does not call this — history and route are different concerns.
+fn clear_route_selection(sender_key: &str) {
+    let global = global_route_overrides();
+    let mut routes = global.lock().unwrap_or_else(|e| e.into_inner());
+    if routes.remove(sender_key).is_some() {
+        save_route_overrides(&routes);
+    }
+}
+
 /// Tools retained in the compact system prompt for small-context providers.
 const COMPACT_CORE_TOOLS: &[&str] = &[
     "shell",
@@ -2033,6 +3013,77 @@ fn proactive_trim_turns(turns: &mut Vec<ChatMessage>, budget: usize) -> usize {
     drop_count
 }
 
+/// The user-configured max_history_messages (falling back to
+/// MAX_CHANNEL_HISTORY when the config value is 0 or absent).
+fn effective_max_history(ctx: &ChannelRuntimeContext) -> usize {
+    let configured = ctx.prompt_config.agent.max_history_messages;
+    if configured > 0 {
+        configured
+    } else {
+        MAX_CHANNEL_HISTORY
+    }
+}
+
+/// Restore a sender's conversation history from the persisted session store
+/// on their first message after a daemon restart.
+///
+/// No-op when persistence is disabled or the history is already in memory.
+/// Sessions older than `channels.session_ttl_hours` are deleted instead of
+/// resurrected; an orphaned trailing user turn (crash mid-query) is closed
+/// with a marker so the LLM doesn't try to continue the old request. Loaded
+/// turns are normalized and truncated to the configured history cap.
+fn hydrate_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+    let Some(ref store) = ctx.session_store else {
+        return;
+    };
+    {
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if histories.contains_key(sender_key) {
+            return;
+        }
+    }
+
+    let ttl_hours = ctx.prompt_config.channels_config.session_ttl_hours;
+    if store.is_stale(sender_key, ttl_hours) {
+        if let Err(e) = store.delete_session(sender_key) {
+            tracing::debug!("Failed to delete stale session for {sender_key}: {e}");
+        }
+        return;
+    }
+
+    let mut msgs = store.load(sender_key);
+    if msgs.is_empty() {
+        return;
+    }
+    if msgs.last().is_some_and(|m| m.role == "user") {
+        let closure = ChatMessage::assistant("[Session interrupted — not continuing this request]");
+        if let Err(e) = store.append(sender_key, &closure) {
+            tracing::debug!("Failed to persist orphan closure for {sender_key}: {e}");
+        }
+        msgs.push(closure);
+    }
+
+    let mut turns = normalize_cached_channel_turns(msgs);
+    let max_history = effective_max_history(ctx);
+    if turns.len() > max_history {
+        turns.drain(..turns.len() - max_history);
+    }
+
+    let mut histories = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    // A concurrent in-flight message may have hydrated (or written) first;
+    // keep whatever is already there.
+    histories.entry(sender_key.to_string()).or_insert_with(|| {
+        tracing::debug!("📂 Restored session for {sender_key} from disk");
+        turns
+    });
+}
+
 fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatMessage) {
     // Persist to JSONL before adding to in-memory history.
     if let Some(ref store) = ctx.session_store {
@@ -2041,16 +3092,7 @@ fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatM
         }
     }
 
-    // Use the user-configured max_history_messages (fall back to
-    // MAX_CHANNEL_HISTORY when the config value is 0 or absent).
-    let max_history = {
-        let configured = ctx.prompt_config.agent.max_history_messages;
-        if configured > 0 {
-            configured
-        } else {
-            MAX_CHANNEL_HISTORY
-        }
-    };
+    let max_history = effective_max_history(ctx);
 
     let mut histories = ctx
         .conversation_histories
@@ -2099,6 +3141,58 @@ fn rollback_orphan_user_turn(
     true
 }
 
+/// Remove the stale user turn (and its reply, if one followed) left by a
+/// message that was later edited, so the history reflects the edited text
+/// once the edit is reprocessed. Same contract as
+/// `rollback_orphan_user_turn`, but tolerates an assistant reply already
+/// sitting after the user turn.
+fn rollback_edited_user_turn(
+    ctx: &ChannelRuntimeContext,
+    sender_key: &str,
+    original_content: &str,
+) -> bool {
+    let mut histories = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let Some(turns) = histories.get_mut(sender_key) else {
+        return false;
+    };
+    let Some(idx) = turns
+        .iter()
+        .rposition(|turn| turn.role == "user" && turn.content == original_content)
+    else {
+        return false;
+    };
+
+    let reply_follows = turns.get(idx + 1).is_some_and(|turn| turn.role == "assistant");
+    let removed = if reply_follows { 2 } else { 1 };
+    let removed_tail = idx + removed == turns.len();
+    if reply_follows {
+        turns.remove(idx + 1);
+    }
+    turns.remove(idx);
+    if turns.is_empty() {
+        histories.remove(sender_key);
+    }
+
+    // The JSONL session store can only pop from the tail; when later turns
+    // already exist the persisted copy keeps the stale pair (harmless — the
+    // in-memory history is what feeds the next request).
+    if removed_tail {
+        if let Some(ref store) = ctx.session_store {
+            for _ in 0..removed {
+                if let Err(e) = store.remove_last(sender_key) {
+                    tracing::warn!("Failed to rollback session store entry: {e}");
+                    break;
+                }
+            }
+        }
+    }
+
+    true
+}
+
 fn should_rollback_failed_user_turn(error: &anyhow::Error) -> bool {
     if error
         .downcast_ref::<providers::ProviderCapabilityError>()
@@ -2123,6 +3217,11 @@ fn should_skip_memory_context_entry(key: &str, content: &str) -> bool {
         return true;
     }
 
+    // Distillation provenance records are JSON bookkeeping, not facts.
+    if key.starts_with(crate::memory::distill::PROVENANCE_KEY_PREFIX) {
+        return true;
+    }
+
     // Skip entries containing image markers to prevent duplication.
     // When auto_save stores a photo message to memory, a subsequent
     // memory recall on the same turn would surface the marker again,
@@ -2143,19 +3242,40 @@ fn should_skip_memory_context_entry(key: &str, content: &str) -> bool {
 }
 
 fn is_context_window_overflow_error(err: &anyhow::Error) -> bool {
-    let lower = err.to_string().to_lowercase();
-    [
-        "exceeds the context window",
-        "context window of this model",
-        "maximum context length",
-        "context length exceeded",
-        "too many tokens",
-        "token limit exceeded",
-        "prompt is too long",
-        "input is too long",
-    ]
-    .iter()
-    .any(|hint| lower.contains(hint))
+    // Typed `ProviderError` classification plus the shared string fallback
+    // for errors raised outside the provider HTTP path.
+    providers::reliable::is_context_window_exceeded(err)
+}
+
+/// Short user-facing message for a classified provider error, or `None` when
+/// the error is unclassified (or context overflow, which has its own
+/// compaction-aware handling upstream).
+fn user_facing_provider_error(err: &anyhow::Error) -> Option<String> {
+    use providers::ProviderErrorKind;
+
+    let provider_error = err.downcast_ref::<providers::ProviderError>()?;
+    Some(match provider_error.kind() {
+        ProviderErrorKind::RateLimited { retry_after } => match retry_after {
+            Some(delay) => format!(
+                "⚠️ Provider rate limit hit — try again in about {}s.",
+                delay.as_secs().max(1)
+            ),
+            None => "⚠️ Provider rate limit hit — try again in a minute.".to_string(),
+        },
+        ProviderErrorKind::QuotaExhausted => {
+            "⚠️ Provider quota exhausted — check your plan or billing.".to_string()
+        }
+        ProviderErrorKind::AuthFailed => {
+            "⚠️ Provider authentication failed — check the configured API key.".to_string()
+        }
+        ProviderErrorKind::Overloaded => {
+            "⚠️ Provider is overloaded right now — please try again shortly.".to_string()
+        }
+        ProviderErrorKind::Network => {
+            "⚠️ Network error reaching the provider — please try again.".to_string()
+        }
+        ProviderErrorKind::ContextTooLarge | ProviderErrorKind::Other => return None,
+    })
 }
 
 fn load_all_cached_models(workspace_dir: &Path, provider_name: &str) -> Vec<String> {
@@ -2676,6 +3796,17 @@ fn handle_models_command(
                 }
             }
 
+            // Explicit return to configured defaults — also deletes the
+            // persisted routes.json entry for this conversation.
+            if hint.eq_ignore_ascii_case("reset") {
+                *current = default_route_selection(ctx);
+                clear_route_selection(sender_key);
+                return format!(
+                    "\u{2705} Route reset to default: {} ({})",
+                    current.model, current.provider
+                );
+            }
+
             if let Some(route) = ctx
                 .model_routes
                 .iter()
@@ -2948,8 +4079,33 @@ async fn handle_runtime_command_if_needed(
             "Conversation history cleared. Starting fresh.".to_string()
         }
         ChannelRuntimeCommand::Skills => format_skills_list(&ctx.loaded_skills),
+        ChannelRuntimeCommand::Status => {
+            format_runtime_status(&gather_runtime_status(ctx, &current))
+        }
+        ChannelRuntimeCommand::Usage => format_usage_report(&gather_usage_report(ctx, &sender_key)),
         ChannelRuntimeCommand::PiSteer(text) => handle_ps_command(ctx, &sender_key, text),
         ChannelRuntimeCommand::PiFollowup(text) => handle_pf_command(ctx, &sender_key, text),
+        ChannelRuntimeCommand::Cron(ref cron_command) => {
+            if may_manage_cron(msg, &ctx.approval_manager) {
+                handle_cron_command(ctx, msg, cron_command)
+            } else {
+                "You're not authorized to manage scheduled tasks.".to_string()
+            }
+        }
+        ChannelRuntimeCommand::RespondMode(ref arg) => {
+            if may_manage_cron(msg, &ctx.approval_manager) {
+                handle_respond_mode_command(ctx, msg, arg)
+            } else {
+                "You're not authorized to change the respond mode.".to_string()
+            }
+        }
+        ChannelRuntimeCommand::Topic(ref topic_command) => {
+            if may_manage_cron(msg, &ctx.approval_manager) {
+                handle_topic_command(channel, msg, topic_command).await
+            } else {
+                "You're not authorized to manage forum topics.".to_string()
+            }
+        }
         // Upstream granular provider/model commands — delegate to our unified handler.
         ChannelRuntimeCommand::ShowProviders => {
             handle_models_command(ctx, &sender_key, &mut current, None)
@@ -2987,10 +4143,69 @@ async fn build_memory_context(
     user_msg: &str,
     min_relevance_score: f64,
     session_id: Option<&str>,
+    namespaces: Option<&[String]>,
+    pinned_max_chars: usize,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, session_id, None, None).await {
+    // Pinned entries bypass recall scoring and are injected first, under
+    // their own char budget (0 disables pinned injection entirely).
+    let mut pinned_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
+    if pinned_max_chars > 0 {
+        if let Ok(entries) = mem.list(None, None).await {
+            let mut pinned: Vec<_> = entries.into_iter().filter(|e| e.pinned).collect();
+            if let Some(namespaces) = namespaces {
+                pinned.retain(|e| namespaces.contains(&e.namespace));
+            }
+            // Oldest first, so over-budget sets drop their oldest entries.
+            pinned.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+            let mut lines: Vec<(String, String)> = Vec::new();
+            for entry in &pinned {
+                if should_skip_memory_context_entry(&entry.key, &entry.content) {
+                    continue;
+                }
+                let content = if entry.content.chars().count() > MEMORY_CONTEXT_ENTRY_MAX_CHARS {
+                    truncate_with_ellipsis(&entry.content, MEMORY_CONTEXT_ENTRY_MAX_CHARS)
+                } else {
+                    entry.content.clone()
+                };
+                lines.push((entry.key.clone(), format!("- {}: {}\n", entry.key, content)));
+            }
+
+            let mut total_chars: usize = lines.iter().map(|(_, l)| l.chars().count()).sum();
+            let mut dropped = 0usize;
+            while total_chars > pinned_max_chars && !lines.is_empty() {
+                let (_, line) = lines.remove(0);
+                total_chars -= line.chars().count();
+                dropped += 1;
+            }
+            if dropped > 0 {
+                tracing::warn!(
+                    dropped,
+                    budget = pinned_max_chars,
+                    "Pinned memory set over budget; oldest entries dropped from context"
+                );
+            }
+
+            if !lines.is_empty() {
+                context.push_str("[Memory context]\n");
+                for (key, line) in lines {
+                    context.push_str(&line);
+                    pinned_keys.insert(key);
+                }
+            }
+        }
+    }
+
+    let recalled = match namespaces {
+        Some(namespaces) => {
+            mem.recall_in_namespaces(namespaces, user_msg, 5, session_id, None, None)
+                .await
+        }
+        None => mem.recall(user_msg, 5, session_id, None, None).await,
+    };
+    if let Ok(entries) = recalled {
         let mut included = 0usize;
         let mut used_chars = 0usize;
 
@@ -3002,6 +4217,11 @@ async fn build_memory_context(
                 break;
             }
 
+            // Already injected as a pinned entry.
+            if pinned_keys.contains(&entry.key) {
+                continue;
+            }
+
             if should_skip_memory_context_entry(&entry.key, &entry.content) {
                 continue;
             }
@@ -3018,7 +4238,7 @@ async fn build_memory_context(
                 break;
             }
 
-            if included == 0 {
+            if context.is_empty() {
                 context.push_str("[Memory context]\n");
             }
 
@@ -3026,10 +4246,10 @@ async fn build_memory_context(
             used_chars += line_chars;
             included += 1;
         }
+    }
 
-        if included > 0 {
-            context.push_str("[/Memory context]\n\n");
-        }
+    if !context.is_empty() {
+        context.push_str("[/Memory context]\n\n");
     }
 
     context
@@ -3663,13 +4883,20 @@ async fn process_channel_message(
         && !memory::should_skip_autosave_content(&msg.content)
     {
         let autosave_key = conversation_memory_key(&msg);
+        let namespace = crate::memory::namespacing::derive(
+            &ctx.prompt_config.memory.namespacing,
+            &msg.channel,
+            &msg.sender,
+        );
         let _ = ctx
             .memory
-            .store(
+            .store_with_metadata(
                 &autosave_key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
                 Some(&history_key),
+                namespace.as_deref(),
+                None,
             )
             .await;
     }
@@ -3684,6 +4911,12 @@ async fn process_channel_message(
         clear_sender_history(ctx.as_ref(), &history_key);
     }
 
+    // First message from this sender since startup? Restore their persisted
+    // history before we read or append anything.
+    if !force_fresh_session {
+        hydrate_sender_history(ctx.as_ref(), &history_key);
+    }
+
     let had_prior_history = if force_fresh_session {
         false
     } else {
@@ -3770,12 +5003,22 @@ async fn process_channel_message(
     let is_group_chat =
         msg.reply_target.contains("@g.us") || msg.reply_target.starts_with("group:");
 
+    // Per-sender namespace isolation: with [memory.namespacing] enabled,
+    // recall is restricted to this sender's namespace plus global.
+    let memory_namespaces = crate::memory::namespacing::read_set(
+        &ctx.prompt_config.memory.namespacing,
+        &msg.channel,
+        &msg.sender,
+    );
+
     let mem_recall_start = Instant::now();
     let sender_memory_fut = build_memory_context(
         ctx.memory.as_ref(),
         &msg.content,
         ctx.min_relevance_score,
         Some(&msg.sender),
+        memory_namespaces.as_deref(),
+        ctx.prompt_config.memory.pinned_context_max_chars,
     );
 
     let (sender_memory, group_memory) = if is_group_chat {
@@ -3784,6 +5027,8 @@ async fn process_channel_message(
             &msg.content,
             ctx.min_relevance_score,
             Some(&history_key),
+            memory_namespaces.as_deref(),
+            0,
         );
         tokio::join!(sender_memory_fut, group_memory_fut)
     } else {
@@ -4337,7 +5582,13 @@ async fn process_channel_message(
         }
         LlmExecutionResult::Completed(Ok(Ok(response))) => {
             // ── Hook: on_message_sending (modifying) ─────────
-            let mut outbound_response = response;
+            // Replies to reprocessed edits are labelled so the user can tell
+            // them apart from the answer to the original text.
+            let mut outbound_response = if msg.is_edit {
+                format!("re: your edit\n\n{response}")
+            } else {
+                response
+            };
             if let Some(hooks) = &ctx.hooks {
                 match hooks
                     .run_on_message_sending(
@@ -4444,6 +5695,10 @@ async fn process_channel_message(
                     "sender": msg.sender,
                     "elapsed_ms": started_at.elapsed().as_millis(),
                     "response": scrub_credentials(&delivered_response),
+                    // Present only when ReliableProvider failed over — records
+                    // which provider/model actually served the reply.
+                    "served_by_provider": fallback_info.as_ref().map(|fb| fb.actual_provider.as_str()),
+                    "served_by_model": fallback_info.as_ref().map(|fb| fb.actual_model.as_str()),
                 }),
             );
 
@@ -4679,7 +5934,8 @@ async fn process_channel_message(
 
                 if let Some(channel) = target_channel.as_ref() {
                     let error_str = e.to_string();
-                    let user_error = sanitize_provider_errors(&error_str)
+                    let user_error = user_facing_provider_error(&e)
+                        .or_else(|| sanitize_provider_errors(&error_str))
                         .unwrap_or_else(|| format!("⚠️ Error: {safe_error}"));
                     let full_error = format!("{user_error}{rollback_notice}");
                     if let Some(ref draft_id) = draft_message_id {
@@ -4789,6 +6045,7 @@ async fn dispatch_worker(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
     in_flight: Arc<tokio::sync::Mutex<HashMap<String, InFlightSenderTaskState>>>,
+    recent_completions: RecentCompletionMap,
     task_sequence: Arc<AtomicU64>,
     permit: tokio::sync::OwnedSemaphorePermit,
 ) {
@@ -4804,12 +6061,15 @@ async fn dispatch_worker(
     let register_in_flight = msg.channel != "cli";
 
     if register_in_flight {
+        IN_FLIGHT_MESSAGE_GAUGE.fetch_add(1, Ordering::Relaxed);
         let previous = {
             let mut active = in_flight.lock().await;
             active.insert(
                 sender_scope_key.clone(),
                 InFlightSenderTaskState {
                     task_id,
+                    message_id: msg.id.clone(),
+                    content: msg.content.clone(),
                     cancellation: cancellation_token.clone(),
                     completion: Arc::clone(&completion),
                 },
@@ -4829,9 +6089,15 @@ async fn dispatch_worker(
         }
     }
 
+    let completion_key = edited_message_key(&msg);
+    let completion_content = msg.content.clone();
+    let was_cancelled = cancellation_token.clone();
+    let edit_window_secs = ctx.prompt_config.channels_config.edit_reprocess_window_secs;
+
     process_channel_message(ctx, msg, cancellation_token).await;
 
     if register_in_flight {
+        IN_FLIGHT_MESSAGE_GAUGE.fetch_sub(1, Ordering::Relaxed);
         let mut active = in_flight.lock().await;
         if active
             .get(&sender_scope_key)
@@ -4841,9 +6107,39 @@ async fn dispatch_worker(
         }
     }
 
+    if register_in_flight && edit_window_secs > 0 && !was_cancelled.is_cancelled() {
+        record_turn_completion(
+            &recent_completions,
+            completion_key,
+            completion_content,
+            edit_window_secs,
+        );
+    }
+
     completion.mark_done();
 }
 
+/// Remember that a message finished processing, so a later edit of it can
+/// roll back the turn. Entries outside the edit window are pruned on insert.
+fn record_turn_completion(
+    recent_completions: &RecentCompletionMap,
+    key: String,
+    content: String,
+    window_secs: u64,
+) {
+    let mut recent = recent_completions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    recent.retain(|_, r| r.completed_at.elapsed() <= Duration::from_secs(window_secs));
+    recent.insert(
+        key,
+        RecentTurnCompletion {
+            content,
+            completed_at: Instant::now(),
+        },
+    );
+}
+
 async fn run_message_dispatch_loop(
     mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
     ctx: Arc<ChannelRuntimeContext>,
@@ -4855,9 +6151,79 @@ async fn run_message_dispatch_loop(
         String,
         InFlightSenderTaskState,
     >::new()));
+    let recent_completions: RecentCompletionMap =
+        Arc::new(std::sync::Mutex::new(HashMap::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
+    // Two-tier scheduler: when all workers are busy, queued DMs and VIP
+    // senders dispatch before group chatter (see `channels::scheduler`).
+    let mut scheduler =
+        scheduler::PriorityScheduler::new(ctx.prompt_config.channels_config.vip_senders.clone());
+    let mut rx_open = true;
+
+    while rx_open || !scheduler.is_empty() {
+        let msg = tokio::select! {
+            maybe_msg = rx.recv(), if rx_open => {
+                match maybe_msg {
+                    Some(msg) => msg,
+                    None => {
+                        rx_open = false;
+                        continue;
+                    }
+                }
+            }
+            permit = Arc::clone(&semaphore).acquire_owned(), if !scheduler.is_empty() => {
+                let Ok(permit) = permit else { break };
+                let msg = scheduler.pop().expect("scheduler checked non-empty");
+                let worker_ctx = Arc::clone(&ctx);
+                let in_flight = Arc::clone(&in_flight_by_sender);
+                let recent = Arc::clone(&recent_completions);
+                let task_sequence = Arc::clone(&task_sequence);
+                workers.spawn(async move {
+                    Box::pin(dispatch_worker(
+                        worker_ctx,
+                        msg,
+                        in_flight,
+                        recent,
+                        task_sequence,
+                        permit,
+                    ))
+                    .await;
+                });
+
+                while let Some(result) = workers.try_join_next() {
+                    log_worker_join_result(result);
+                }
+                continue;
+            }
+        };
+
+        // Drop re-delivered messages (e.g. a Telegram update resent after an ack
+        // timeout) before any dispatch work. Edits intentionally bypass dedup:
+        // they reuse the original message id and must reach the edit handling
+        // below instead of being swallowed as duplicates.
+        if msg.channel != "cli"
+            && !msg.is_edit
+            && !ctx.message_dedup.record_if_new(&msg.channel, &msg.id)
+        {
+            tracing::debug!(
+                "Dropping duplicate {} message {} from {}",
+                msg.channel,
+                msg.id,
+                msg.sender
+            );
+            runtime_trace::record_event(
+                "channel_message_duplicate",
+                Some(msg.channel.as_str()),
+                None,
+                None,
+                None,
+                None,
+                None,
+                serde_json::json!({ "sender": msg.sender, "message_id": msg.id }),
+            );
+            continue;
+        }
 
-    while let Some(msg) = rx.recv().await {
         // Fast path: /stop cancels the in-flight task for this sender scope without
         // spawning a worker or registering a new task. Handled here — before semaphore
         // acquisition — so the target task is still in the store and is never replaced.
@@ -4901,6 +6267,127 @@ async fn run_message_dispatch_loop(
             continue;
         }
 
+        // ── Silent groups: record, never reply ──────────────────────
+        // Messages a "silent" respond mode tagged as record-only still reach
+        // memory auto-save, but are dropped before a worker is spawned.
+        // /respondmode passes through so an approver can turn replies back on.
+        if msg.channel != "cli"
+            && respond_policy::is_record_only(msg.metadata.as_ref())
+            && parse_runtime_command(&msg.channel, &msg.content).is_none()
+        {
+            if ctx.auto_save_memory
+                && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS
+                && !memory::should_skip_autosave_content(&msg.content)
+            {
+                let ctx = Arc::clone(&ctx);
+                let autosave_key = conversation_memory_key(&msg);
+                let history_key = conversation_history_key(&msg);
+                let content = msg.content.clone();
+                let namespace = crate::memory::namespacing::derive(
+                    &ctx.prompt_config.memory.namespacing,
+                    &msg.channel,
+                    &msg.sender,
+                );
+                tokio::spawn(async move {
+                    let _ = ctx
+                        .memory
+                        .store_with_metadata(
+                            &autosave_key,
+                            &content,
+                            crate::memory::MemoryCategory::Conversation,
+                            Some(&history_key),
+                            namespace.as_deref(),
+                            None,
+                        )
+                        .await;
+                });
+            }
+            continue;
+        }
+
+        // ── Edited messages: cancel, reprocess, or ignore ───────────
+        // An edit of an in-flight message cancels it via the same scope
+        // token interruption uses; an edit of a recently answered message
+        // rolls back the stale turn before reprocessing; older edits are
+        // dropped.
+        let msg = if msg.channel != "cli" && msg.is_edit {
+            let window = ctx
+                .prompt_config
+                .channels_config
+                .edit_reprocess_window_secs;
+            let scope_key = interruption_scope_key(&msg);
+            let in_flight_original = {
+                let active = in_flight_by_sender.lock().await;
+                active
+                    .get(&scope_key)
+                    .filter(|state| state.message_id == msg.id)
+                    .cloned()
+            };
+            let completion = {
+                let recent = recent_completions
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                recent
+                    .get(&edited_message_key(&msg))
+                    .map(|r| (r.content.clone(), r.completed_at))
+            };
+
+            match classify_edited_message(
+                in_flight_original.is_some(),
+                completion.as_ref().map(|(_, at)| *at),
+                window,
+            ) {
+                EditDisposition::CancelInFlight => {
+                    let state = in_flight_original.expect("checked by classify");
+                    {
+                        let mut active = in_flight_by_sender.lock().await;
+                        if active
+                            .get(&scope_key)
+                            .is_some_and(|current| current.task_id == state.task_id)
+                        {
+                            active.remove(&scope_key);
+                        }
+                    }
+                    state.cancellation.cancel();
+                    state.completion.wait().await;
+                    // The cancelled turn left its user message as the history
+                    // tail; drop it so only the edited text remains.
+                    let history_key = conversation_history_key(&msg);
+                    rollback_orphan_user_turn(ctx.as_ref(), &history_key, &state.content);
+                    tracing::info!(
+                        channel = %msg.channel,
+                        sender = %msg.sender,
+                        "Edit cancelled the in-flight original; reprocessing"
+                    );
+                    let mut m = msg;
+                    // Fresh turn — the reply needs no "re: your edit" prefix.
+                    m.is_edit = false;
+                    m
+                }
+                EditDisposition::Reprocess => {
+                    let (original, _) = completion.expect("checked by classify");
+                    let history_key = conversation_history_key(&msg);
+                    rollback_edited_user_turn(ctx.as_ref(), &history_key, &original);
+                    tracing::info!(
+                        channel = %msg.channel,
+                        sender = %msg.sender,
+                        "Reprocessing recently answered message after edit"
+                    );
+                    msg
+                }
+                EditDisposition::Ignore => {
+                    tracing::debug!(
+                        channel = %msg.channel,
+                        sender = %msg.sender,
+                        "Ignoring edit outside the reprocess window"
+                    );
+                    continue;
+                }
+            }
+        } else {
+            msg
+        };
+
         // ── Debounce: accumulate rapid messages per sender ──────────
         // CLI messages bypass debouncing so the interactive loop stays responsive.
         let msg = if msg.channel != "cli" && ctx.debouncer.enabled() {
@@ -4912,6 +6399,7 @@ async fn run_message_dispatch_loop(
                     // worker path below.
                     let debounce_ctx = Arc::clone(&ctx);
                     let debounce_in_flight = Arc::clone(&in_flight_by_sender);
+                    let debounce_recent = Arc::clone(&recent_completions);
                     let debounce_semaphore = Arc::clone(&semaphore);
                     let debounce_task_seq = Arc::clone(&task_sequence);
                     let mut debounce_msg = msg;
@@ -4939,6 +6427,7 @@ async fn run_message_dispatch_loop(
                             debounce_ctx,
                             debounce_msg,
                             debounce_in_flight,
+                            debounce_recent,
                             debounce_task_seq,
                             permit,
                         )
@@ -4956,69 +6445,7 @@ async fn run_message_dispatch_loop(
             msg
         };
 
-        let permit = match Arc::clone(&semaphore).acquire_owned().await {
-            Ok(permit) => permit,
-            Err(_) => break,
-        };
-
-        let worker_ctx = Arc::clone(&ctx);
-        let in_flight = Arc::clone(&in_flight_by_sender);
-        let task_sequence = Arc::clone(&task_sequence);
-        workers.spawn(async move {
-            let _permit = permit;
-            let interrupt_enabled = worker_ctx
-                .interrupt_on_new_message
-                .enabled_for_channel(msg.channel.as_str());
-            let sender_scope_key = interruption_scope_key(&msg);
-            let cancellation_token = CancellationToken::new();
-            let completion = Arc::new(InFlightTaskCompletion::new());
-            let task_id = task_sequence.fetch_add(1, Ordering::Relaxed) as u64;
-
-            // Register all non-CLI tasks in the in-flight store so /stop can reach them.
-            // This is a deliberate broadening from the previous behaviour where only
-            // interrupt_enabled (Telegram/Slack) channels registered tasks.
-            let register_in_flight = msg.channel != "cli";
-
-            if register_in_flight {
-                let previous = {
-                    let mut active = in_flight.lock().await;
-                    active.insert(
-                        sender_scope_key.clone(),
-                        InFlightSenderTaskState {
-                            task_id,
-                            cancellation: cancellation_token.clone(),
-                            completion: Arc::clone(&completion),
-                        },
-                    )
-                };
-
-                if interrupt_enabled {
-                    if let Some(previous) = previous {
-                        tracing::info!(
-                            channel = %msg.channel,
-                            sender = %msg.sender,
-                            "Interrupting previous in-flight request for sender"
-                        );
-                        previous.cancellation.cancel();
-                        previous.completion.wait().await;
-                    }
-                }
-            }
-
-            Box::pin(process_channel_message(worker_ctx, msg, cancellation_token)).await;
-
-            if register_in_flight {
-                let mut active = in_flight.lock().await;
-                if active
-                    .get(&sender_scope_key)
-                    .is_some_and(|state| state.task_id == task_id)
-                {
-                    active.remove(&sender_scope_key);
-                }
-            }
-
-            completion.mark_done();
-        });
+        scheduler.push(msg);
 
         while let Some(result) = workers.try_join_next() {
             log_worker_join_result(result);
@@ -5565,9 +6992,12 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::Start => {
             anyhow::bail!("Start must be handled in main.rs (requires async runtime)")
         }
-        crate::ChannelCommands::Doctor => {
+        crate::ChannelCommands::Doctor { .. } => {
             anyhow::bail!("Doctor must be handled in main.rs (requires async runtime)")
         }
+        crate::ChannelCommands::PairWhatsapp { .. } => {
+            anyhow::bail!("PairWhatsapp must be handled in main.rs (requires async runtime)")
+        }
         crate::ChannelCommands::List => {
             println!("Channels:");
             println!("  ✅ CLI (always available)");
@@ -5618,6 +7048,34 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
             channel_id,
             recipient,
         } => send_channel_message(config, &channel_id, &recipient, &message).await,
+        crate::ChannelCommands::Broadcast { message, only } => {
+            let only: Option<Vec<String>> = only.map(|list| {
+                list.split(',')
+                    .map(|name| name.trim().to_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            });
+            let report = broadcast(config, &message, only.as_deref()).await;
+
+            if report.outcomes.is_empty() {
+                anyhow::bail!("No channels configured to broadcast to");
+            }
+            for outcome in &report.outcomes {
+                match &outcome.error {
+                    None => println!("  ✅ {}", outcome.channel),
+                    Some(error) => println!("  ❌ {}: {error}", outcome.channel),
+                }
+            }
+            println!(
+                "\nBroadcast delivered to {}/{} channel(s).",
+                report.sent(),
+                report.outcomes.len()
+            );
+            if report.sent() == 0 {
+                anyhow::bail!("Broadcast failed on every channel");
+            }
+            Ok(())
+        }
     }
 }
 
@@ -5633,17 +7091,21 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
             let ack = tg
                 .ack_reactions
                 .unwrap_or(config.channels_config.ack_reactions);
-            Ok(Arc::new(
-                TelegramChannel::new(
-                    tg.bot_token.clone(),
-                    tg.allowed_users.clone(),
-                    tg.mention_only,
-                )
-                .with_ack_reactions(ack)
-                .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
-                .with_transcription(config.transcription.clone())
-                .with_tts(config.tts.clone())
-                .with_workspace_dir(config.workspace_dir.clone()),
+            Ok(rate_limit::wrap_if_limited(
+                "telegram",
+                config.channels_config.rate_limits.get("telegram"),
+                Arc::new(
+                    TelegramChannel::new(
+                        tg.bot_token.clone(),
+                        tg.allowed_users.clone(),
+                        tg.mention_only,
+                    )
+                    .with_ack_reactions(ack)
+                    .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
+                    .with_transcription(config.transcription.clone())
+                    .with_tts(config.tts.clone())
+                    .with_workspace_dir(config.workspace_dir.clone()),
+                ),
             ))
         }
         "discord" => {
@@ -5652,20 +7114,24 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
                 .discord
                 .as_ref()
                 .context("Discord channel is not configured")?;
-            Ok(Arc::new(
-                DiscordChannel::new(
-                    dc.bot_token.clone(),
-                    dc.guild_id.clone(),
-                    dc.allowed_users.clone(),
-                    dc.listen_to_bots,
-                    dc.mention_only,
-                )
-                .with_streaming(
-                    dc.stream_mode,
-                    dc.draft_update_interval_ms,
-                    dc.multi_message_delay_ms,
-                )
-                .with_transcription(config.transcription.clone()),
+            Ok(rate_limit::wrap_if_limited(
+                "discord",
+                config.channels_config.rate_limits.get("discord"),
+                Arc::new(
+                    DiscordChannel::new(
+                        dc.bot_token.clone(),
+                        dc.guild_id.clone(),
+                        dc.allowed_users.clone(),
+                        dc.listen_to_bots,
+                        dc.mention_only,
+                    )
+                    .with_streaming(
+                        dc.stream_mode,
+                        dc.draft_update_interval_ms,
+                        dc.multi_message_delay_ms,
+                    )
+                    .with_transcription(config.transcription.clone()),
+                ),
             ))
         }
         "slack" => {
@@ -5674,18 +7140,22 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
                 .slack
                 .as_ref()
                 .context("Slack channel is not configured")?;
-            Ok(Arc::new(
-                SlackChannel::new(
-                    sl.bot_token.clone(),
-                    sl.app_token.clone(),
-                    sl.channel_id.clone(),
-                    sl.channel_ids.clone(),
-                    sl.allowed_users.clone(),
-                )
-                .with_workspace_dir(config.workspace_dir.clone())
-                .with_markdown_blocks(sl.use_markdown_blocks)
-                .with_transcription(config.transcription.clone())
-                .with_streaming(sl.stream_drafts, sl.draft_update_interval_ms),
+            Ok(rate_limit::wrap_if_limited(
+                "slack",
+                config.channels_config.rate_limits.get("slack"),
+                Arc::new(
+                    SlackChannel::new(
+                        sl.bot_token.clone(),
+                        sl.app_token.clone(),
+                        sl.channel_id.clone(),
+                        sl.channel_ids.clone(),
+                        sl.allowed_users.clone(),
+                    )
+                    .with_workspace_dir(config.workspace_dir.clone())
+                    .with_markdown_blocks(sl.use_markdown_blocks)
+                    .with_transcription(config.transcription.clone())
+                    .with_streaming(sl.stream_drafts, sl.draft_update_interval_ms),
+                ),
             ))
         }
         "mattermost" => {
@@ -5709,14 +7179,27 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
                 .signal
                 .as_ref()
                 .context("Signal channel is not configured")?;
-            Ok(Arc::new(SignalChannel::new(
-                sg.http_url.clone(),
-                sg.account.clone(),
-                sg.group_id.clone(),
-                sg.allowed_from.clone(),
-                sg.ignore_attachments,
-                sg.ignore_stories,
-            )))
+            Ok(Arc::new(
+                SignalChannel::new(
+                    sg.http_url.clone(),
+                    sg.account.clone(),
+                    sg.group_id.clone(),
+                    sg.allowed_from.clone(),
+                    sg.ignore_attachments,
+                    sg.ignore_stories,
+                )
+                .with_group_responding(
+                    sg.respond_mode
+                        .as_deref()
+                        .map_or_else(signal::RespondMode::default, signal::RespondMode::parse),
+                    sg.group_respond_modes
+                        .iter()
+                        .map(|(gid, mode)| (gid.clone(), signal::RespondMode::parse(mode)))
+                        .collect(),
+                    sg.trigger_prefix.clone(),
+                    sg.profile_name.clone(),
+                ),
+            ))
         }
         "matrix" => {
             #[cfg(feature = "channel-matrix")]
@@ -5785,6 +7268,197 @@ fn build_channel_by_id(config: &Config, channel_id: &str) -> Result<Arc<dyn Chan
     }
 }
 
+/// Per-channel delivery timeout for broadcasts so one stuck channel can't
+/// hold up the rest of the fan-out.
+const BROADCAST_SEND_TIMEOUT_SECS: u64 = 30;
+
+/// Channel ids eligible for broadcast (the set `build_channel_by_id` knows).
+const BROADCAST_CHANNEL_IDS: &[&str] = &[
+    "telegram",
+    "discord",
+    "slack",
+    "mattermost",
+    "signal",
+    "matrix",
+    "whatsapp",
+    "qq",
+];
+
+/// Outcome of one channel's delivery in a broadcast.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BroadcastChannelOutcome {
+    pub channel: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregated result of a broadcast across channels.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BroadcastReport {
+    pub outcomes: Vec<BroadcastChannelOutcome>,
+}
+
+impl BroadcastReport {
+    pub fn sent(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.ok).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.sent()
+    }
+
+    fn failure(channel: &str, error: impl Into<String>) -> BroadcastChannelOutcome {
+        BroadcastChannelOutcome {
+            channel: channel.to_string(),
+            ok: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Resolve a channel's default announce target from its config section:
+/// the configured channel/room/group where the bot normally operates, or
+/// the first allowlisted identity for DM-style channels.
+fn default_announce_target(config: &Config, channel_id: &str) -> Option<String> {
+    let channels = &config.channels_config;
+    match channel_id {
+        "telegram" => channels
+            .telegram
+            .as_ref()
+            .and_then(|tg| tg.allowed_users.first().cloned()),
+        "discord" => channels
+            .discord
+            .as_ref()
+            .and_then(|dc| dc.broadcast_channel_id.clone()),
+        "slack" => channels.slack.as_ref().and_then(|sl| {
+            sl.channel_ids
+                .first()
+                .cloned()
+                .or_else(|| sl.channel_id.clone())
+                .filter(|id| id != "*")
+        }),
+        "mattermost" => channels
+            .mattermost
+            .as_ref()
+            .and_then(|mm| mm.channel_id.clone()),
+        "signal" => channels.signal.as_ref().and_then(|sg| {
+            sg.group_id
+                .clone()
+                .filter(|id| !id.eq_ignore_ascii_case("dm"))
+                .or_else(|| sg.allowed_from.iter().find(|n| *n != "*").cloned())
+        }),
+        "matrix" => channels.matrix.as_ref().map(|mx| mx.room_id.clone()),
+        "whatsapp" => channels
+            .whatsapp
+            .as_ref()
+            .and_then(|wa| wa.allowed_numbers.first().cloned()),
+        "qq" => channels
+            .qq
+            .as_ref()
+            .and_then(|qq| qq.allowed_users.first().cloned()),
+        _ => None,
+    }
+}
+
+fn channel_is_configured(config: &Config, channel_id: &str) -> bool {
+    let channels = &config.channels_config;
+    match channel_id {
+        "telegram" => channels.telegram.is_some(),
+        "discord" => channels.discord.is_some(),
+        "slack" => channels.slack.is_some(),
+        "mattermost" => channels.mattermost.is_some(),
+        "signal" => channels.signal.is_some(),
+        "matrix" => channels.matrix.is_some(),
+        "whatsapp" => channels.whatsapp.is_some(),
+        "qq" => channels.qq.is_some(),
+        _ => false,
+    }
+}
+
+/// Fan a message out to prepared `(name, channel, recipient)` triples.
+///
+/// Every send runs concurrently with its own timeout; one channel failing
+/// or hanging never blocks the others.
+async fn broadcast_to_channels(
+    targets: Vec<(String, Arc<dyn Channel>, String)>,
+    message: &str,
+    timeout: Duration,
+) -> BroadcastReport {
+    let sends = targets.into_iter().map(|(name, channel, recipient)| {
+        let msg = SendMessage::new(message, recipient);
+        async move {
+            match tokio::time::timeout(timeout, channel.send(&msg)).await {
+                Ok(Ok(())) => BroadcastChannelOutcome {
+                    channel: name,
+                    ok: true,
+                    error: None,
+                },
+                Ok(Err(e)) => BroadcastReport::failure(&name, e.to_string()),
+                Err(_) => BroadcastReport::failure(
+                    &name,
+                    format!("timed out after {}s", timeout.as_secs()),
+                ),
+            }
+        }
+    });
+
+    BroadcastReport {
+        outcomes: futures_util::future::join_all(sends).await,
+    }
+}
+
+/// Broadcast an announcement through every configured channel (optionally
+/// restricted to `only`), sending to each channel's default announce target.
+///
+/// Returns a per-channel report; channels that aren't configured, have no
+/// resolvable target, or fail to build are recorded as failures rather than
+/// aborting the rest of the fan-out.
+pub async fn broadcast(config: &Config, message: &str, only: Option<&[String]>) -> BroadcastReport {
+    let mut targets: Vec<(String, Arc<dyn Channel>, String)> = Vec::new();
+    let mut report = BroadcastReport::default();
+
+    let selected: Vec<&str> = match only {
+        Some(names) if !names.is_empty() => names.iter().map(String::as_str).collect(),
+        _ => BROADCAST_CHANNEL_IDS
+            .iter()
+            .copied()
+            .filter(|id| channel_is_configured(config, id))
+            .collect(),
+    };
+
+    for channel_id in selected {
+        if !channel_is_configured(config, channel_id) {
+            report
+                .outcomes
+                .push(BroadcastReport::failure(channel_id, "not configured"));
+            continue;
+        }
+        let Some(target) = default_announce_target(config, channel_id) else {
+            report.outcomes.push(BroadcastReport::failure(
+                channel_id,
+                "no default announce target in config",
+            ));
+            continue;
+        };
+        match build_channel_by_id(config, channel_id) {
+            Ok(channel) => targets.push((channel_id.to_string(), channel, target)),
+            Err(e) => report
+                .outcomes
+                .push(BroadcastReport::failure(channel_id, e.to_string())),
+        }
+    }
+
+    let sent = broadcast_to_channels(
+        targets,
+        message,
+        Duration::from_secs(BROADCAST_SEND_TIMEOUT_SECS),
+    )
+    .await;
+    report.outcomes.extend(sent.outcomes);
+    report
+}
+
 /// Send a one-off message to a configured channel.
 async fn send_channel_message(
     config: &Config,
@@ -5809,12 +7483,22 @@ enum ChannelHealthState {
     Timeout,
 }
 
+impl ChannelHealthState {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChannelHealthState::Healthy => "healthy",
+            ChannelHealthState::Unhealthy => "unhealthy",
+            ChannelHealthState::Timeout => "timeout",
+        }
+    }
+}
+
 fn classify_health_result(
-    result: &std::result::Result<bool, tokio::time::error::Elapsed>,
+    result: &std::result::Result<ChannelHealthReport, tokio::time::error::Elapsed>,
 ) -> ChannelHealthState {
     match result {
-        Ok(true) => ChannelHealthState::Healthy,
-        Ok(false) => ChannelHealthState::Unhealthy,
+        Ok(report) if report.healthy => ChannelHealthState::Healthy,
+        Ok(_) => ChannelHealthState::Unhealthy,
         Err(_) => ChannelHealthState::Timeout,
     }
 }
@@ -5837,18 +7521,28 @@ fn collect_configured_channels(
             .unwrap_or(config.channels_config.ack_reactions);
         channels.push(ConfiguredChannel {
             display_name: "Telegram",
-            channel: Arc::new(
-                TelegramChannel::new(
-                    tg.bot_token.clone(),
-                    tg.allowed_users.clone(),
-                    tg.mention_only,
-                )
-                .with_ack_reactions(ack)
-                .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
-                .with_transcription(config.transcription.clone())
-                .with_tts(config.tts.clone())
-                .with_workspace_dir(config.workspace_dir.clone())
-                .with_proxy_url(tg.proxy_url.clone()),
+            channel: rate_limit::wrap_if_limited(
+                "telegram",
+                config.channels_config.rate_limits.get("telegram"),
+                Arc::new(
+                    TelegramChannel::new(
+                        tg.bot_token.clone(),
+                        tg.allowed_users.clone(),
+                        tg.mention_only,
+                    )
+                    .with_respond_policy(Arc::new(respond_policy::GroupRespondPolicy::from_config(
+                        tg.respond_mode.as_deref(),
+                        tg.mention_only,
+                        &tg.group_respond_modes,
+                        tg.trigger_prefix.clone(),
+                    )))
+                    .with_ack_reactions(ack)
+                    .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
+                    .with_transcription(config.transcription.clone())
+                    .with_tts(config.tts.clone())
+                    .with_workspace_dir(config.workspace_dir.clone())
+                    .with_proxy_url(tg.proxy_url.clone()),
+                ),
             ),
         });
     }
@@ -5856,21 +7550,31 @@ fn collect_configured_channels(
     if let Some(ref dc) = config.channels_config.discord {
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(
-                DiscordChannel::new(
-                    dc.bot_token.clone(),
-                    dc.guild_id.clone(),
-                    dc.allowed_users.clone(),
-                    dc.listen_to_bots,
-                    dc.mention_only,
-                )
-                .with_streaming(
-                    dc.stream_mode,
-                    dc.draft_update_interval_ms,
-                    dc.multi_message_delay_ms,
-                )
-                .with_proxy_url(dc.proxy_url.clone())
-                .with_transcription(config.transcription.clone()),
+            channel: rate_limit::wrap_if_limited(
+                "discord",
+                config.channels_config.rate_limits.get("discord"),
+                Arc::new(
+                    DiscordChannel::new(
+                        dc.bot_token.clone(),
+                        dc.guild_id.clone(),
+                        dc.allowed_users.clone(),
+                        dc.listen_to_bots,
+                        dc.mention_only,
+                    )
+                    .with_respond_policy(Arc::new(respond_policy::GroupRespondPolicy::from_config(
+                        dc.respond_mode.as_deref(),
+                        dc.mention_only,
+                        &dc.group_respond_modes,
+                        dc.trigger_prefix.clone(),
+                    )))
+                    .with_streaming(
+                        dc.stream_mode,
+                        dc.draft_update_interval_ms,
+                        dc.multi_message_delay_ms,
+                    )
+                    .with_proxy_url(dc.proxy_url.clone())
+                    .with_transcription(config.transcription.clone()),
+                ),
             ),
         });
     }
@@ -5903,21 +7607,31 @@ fn collect_configured_channels(
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(
-                SlackChannel::new(
-                    sl.bot_token.clone(),
-                    sl.app_token.clone(),
-                    sl.channel_id.clone(),
-                    sl.channel_ids.clone(),
-                    sl.allowed_users.clone(),
-                )
-                .with_thread_replies(sl.thread_replies.unwrap_or(true))
-                .with_group_reply_policy(sl.mention_only, Vec::new())
-                .with_workspace_dir(config.workspace_dir.clone())
-                .with_markdown_blocks(sl.use_markdown_blocks)
-                .with_proxy_url(sl.proxy_url.clone())
-                .with_transcription(config.transcription.clone())
-                .with_streaming(sl.stream_drafts, sl.draft_update_interval_ms),
+            channel: rate_limit::wrap_if_limited(
+                "slack",
+                config.channels_config.rate_limits.get("slack"),
+                Arc::new(
+                    SlackChannel::new(
+                        sl.bot_token.clone(),
+                        sl.app_token.clone(),
+                        sl.channel_id.clone(),
+                        sl.channel_ids.clone(),
+                        sl.allowed_users.clone(),
+                    )
+                    .with_thread_replies(sl.thread_replies.unwrap_or(true))
+                    .with_group_reply_policy(sl.mention_only, Vec::new())
+                    .with_respond_policy(Arc::new(respond_policy::GroupRespondPolicy::from_config(
+                        sl.respond_mode.as_deref(),
+                        sl.mention_only,
+                        &sl.group_respond_modes,
+                        sl.trigger_prefix.clone(),
+                    )))
+                    .with_workspace_dir(config.workspace_dir.clone())
+                    .with_markdown_blocks(sl.use_markdown_blocks)
+                    .with_proxy_url(sl.proxy_url.clone())
+                    .with_transcription(config.transcription.clone())
+                    .with_streaming(sl.stream_drafts, sl.draft_update_interval_ms),
+                ),
             ),
         });
     }
@@ -5993,7 +7707,18 @@ fn collect_configured_channels(
                     sig.ignore_attachments,
                     sig.ignore_stories,
                 )
-                .with_proxy_url(sig.proxy_url.clone()),
+                .with_proxy_url(sig.proxy_url.clone())
+                .with_group_responding(
+                    sig.respond_mode
+                        .as_deref()
+                        .map_or_else(signal::RespondMode::default, signal::RespondMode::parse),
+                    sig.group_respond_modes
+                        .iter()
+                        .map(|(gid, mode)| (gid.clone(), signal::RespondMode::parse(mode)))
+                        .collect(),
+                    sig.trigger_prefix.clone(),
+                    sig.profile_name.clone(),
+                ),
             ),
         });
     }
@@ -6109,7 +7834,10 @@ fn collect_configured_channels(
     if let Some(ref email_cfg) = config.channels_config.email {
         channels.push(ConfiguredChannel {
             display_name: "Email",
-            channel: Arc::new(EmailChannel::new(email_cfg.clone())),
+            channel: Arc::new(
+                EmailChannel::new(email_cfg.clone())
+                    .with_workspace_dir(config.workspace_dir.clone()),
+            ),
         });
     }
 
@@ -6132,10 +7860,12 @@ fn collect_configured_channels(
                 username: irc.username.clone(),
                 channels: irc.channels.clone(),
                 allowed_users: irc.allowed_users.clone(),
+                channel_allowed_users: irc.channel_allowed_users.clone(),
                 server_password: irc.server_password.clone(),
                 nickserv_password: irc.nickserv_password.clone(),
                 sasl_password: irc.sasl_password.clone(),
                 verify_tls: irc.verify_tls.unwrap_or(true),
+                tls_fingerprint: irc.tls_fingerprint.clone(),
             })),
         });
     }
@@ -6335,8 +8065,63 @@ fn collect_configured_channels(
     channels
 }
 
-/// Run health checks for configured channels.
-pub async fn doctor_channels(config: Config) -> Result<()> {
+/// How long a single doctor probe may run before being reported as timed out.
+const CHANNEL_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Per-channel result of a doctor probe, serializable for `--format json`.
+#[derive(Debug, serde::Serialize)]
+struct ChannelProbeOutcome {
+    channel: &'static str,
+    status: &'static str,
+    latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    identity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_expiry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// Probe every channel concurrently, each with its own timeout, so one
+/// dead service never stalls the rest of the report.
+async fn probe_channels(
+    channels: Vec<ConfiguredChannel>,
+    timeout: Duration,
+) -> Vec<ChannelProbeOutcome> {
+    let probes = channels.into_iter().map(|configured| async move {
+        let result =
+            tokio::time::timeout(timeout, configured.channel.health_check_detailed()).await;
+        let state = classify_health_result(&result);
+        match result {
+            Ok(report) => ChannelProbeOutcome {
+                channel: configured.display_name,
+                status: state.as_str(),
+                latency_ms: report.latency_ms,
+                identity: report.identity,
+                auth_expiry: report.auth_expiry,
+                detail: report.detail,
+            },
+            Err(_) => ChannelProbeOutcome {
+                channel: configured.display_name,
+                status: state.as_str(),
+                latency_ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+                identity: None,
+                auth_expiry: None,
+                detail: Some(format!("timed out after {}s", timeout.as_secs())),
+            },
+        }
+    });
+    futures_util::future::join_all(probes).await
+}
+
+/// Run active connectivity probes for configured channels.
+///
+/// Each probe measures round-trip latency and, where the platform exposes it,
+/// reports the authenticated identity and credential expiry. Probes run
+/// concurrently with individual timeouts. With `json`, prints a
+/// machine-readable report instead of the human-readable table. Returns an
+/// error (non-zero exit) if any configured channel fails its probe.
+pub async fn doctor_channels(config: Config, json: bool) -> Result<()> {
     #[allow(unused_mut)]
     let mut channels = collect_configured_channels(&config, "health check");
 
@@ -6351,50 +8136,128 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     }
 
     if channels.is_empty() {
-        println!("No real-time channels configured. Run `zeroclaw onboard` first.");
+        if json {
+            println!("[]");
+        } else {
+            println!("No real-time channels configured. Run `zeroclaw onboard` first.");
+        }
         return Ok(());
     }
 
-    println!("🩺 ZeroClaw Channel Doctor");
-    println!();
+    let outcomes = probe_channels(channels, CHANNEL_PROBE_TIMEOUT).await;
 
-    let mut healthy = 0_u32;
-    let mut unhealthy = 0_u32;
-    let mut timeout = 0_u32;
+    let healthy = outcomes.iter().filter(|o| o.status == "healthy").count();
+    let unhealthy = outcomes.iter().filter(|o| o.status == "unhealthy").count();
+    let timeout = outcomes.iter().filter(|o| o.status == "timeout").count();
 
-    for configured in channels {
-        let result =
-            tokio::time::timeout(Duration::from_secs(10), configured.channel.health_check()).await;
-        let state = classify_health_result(&result);
-
-        match state {
-            ChannelHealthState::Healthy => {
-                healthy += 1;
-                println!("  ✅ {:<9} healthy", configured.display_name);
-            }
-            ChannelHealthState::Unhealthy => {
-                unhealthy += 1;
-                println!(
-                    "  ❌ {:<9} unhealthy (auth/config/network)",
-                    configured.display_name
-                );
+    if json {
+        println!("{}", serde_json::to_string_pretty(&outcomes)?);
+    } else {
+        println!("🩺 ZeroClaw Channel Doctor");
+        println!();
+
+        for outcome in &outcomes {
+            match outcome.status {
+                "healthy" => {
+                    let identity = outcome
+                        .identity
+                        .as_deref()
+                        .map(|id| format!(", {id}"))
+                        .unwrap_or_default();
+                    println!(
+                        "  ✅ {:<9} healthy ({} ms{identity})",
+                        outcome.channel, outcome.latency_ms
+                    );
+                }
+                "timeout" => {
+                    println!(
+                        "  ⏱️  {:<9} timed out (>{}s)",
+                        outcome.channel,
+                        CHANNEL_PROBE_TIMEOUT.as_secs()
+                    );
+                }
+                _ => {
+                    println!(
+                        "  ❌ {:<9} unhealthy ({})",
+                        outcome.channel,
+                        outcome.detail.as_deref().unwrap_or("auth/config/network")
+                    );
+                }
             }
-            ChannelHealthState::Timeout => {
-                timeout += 1;
-                println!("  ⏱️  {:<9} timed out (>10s)", configured.display_name);
+            if let Some(expiry) = &outcome.auth_expiry {
+                println!("     ⚠️  {expiry}");
             }
         }
-    }
 
-    if config.channels_config.webhook.is_some() {
-        println!("  ℹ️  Webhook   check via `zeroclaw gateway` then GET /health");
+        if config.channels_config.webhook.is_some() {
+            println!("  ℹ️  Webhook   check via `zeroclaw gateway` then GET /health");
+        }
+
+        println!();
+        println!("Summary: {healthy} healthy, {unhealthy} unhealthy, {timeout} timed out");
     }
 
-    println!();
-    println!("Summary: {healthy} healthy, {unhealthy} unhealthy, {timeout} timed out");
+    let failed = unhealthy + timeout;
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} configured channels failed health checks",
+            outcomes.len()
+        );
+    }
     Ok(())
 }
 
+/// Run the interactive WhatsApp Web QR pairing flow.
+///
+/// Prints the QR code to the terminal (optionally also writing it as a PNG
+/// into the workspace) and blocks until pairing completes or `timeout_secs`
+/// elapses.
+#[cfg_attr(not(feature = "whatsapp-web"), allow(clippy::unused_async))]
+pub async fn pair_whatsapp(config: Config, write_png: bool, timeout_secs: u64) -> Result<()> {
+    #[cfg(feature = "whatsapp-web")]
+    {
+        let wa = config
+            .channels_config
+            .whatsapp
+            .as_ref()
+            .context("WhatsApp channel is not configured")?;
+        if !wa.is_web_config() {
+            anyhow::bail!("WhatsApp pairing requires Web mode (session_path must be set)");
+        }
+
+        let mut channel = WhatsAppWebChannel::new(
+            wa.session_path.clone().unwrap_or_default(),
+            wa.pair_phone.clone(),
+            wa.pair_code.clone(),
+            wa.allowed_numbers.clone(),
+            wa.mode.clone(),
+            wa.dm_policy.clone(),
+            wa.group_policy.clone(),
+            wa.self_chat_mode,
+        );
+        if write_png {
+            let png_path = config.workspace_dir.join("whatsapp-pairing-qr.png");
+            println!("QR code will also be written to {}", png_path.display());
+            channel = channel.with_qr_png_path(png_path);
+        }
+
+        println!("Waiting for WhatsApp pairing (timeout: {timeout_secs}s)...");
+        Arc::new(channel)
+            .pair(Duration::from_secs(timeout_secs))
+            .await?;
+        println!("✅ WhatsApp Web paired successfully.");
+        Ok(())
+    }
+    #[cfg(not(feature = "whatsapp-web"))]
+    {
+        let _ = (config, write_png, timeout_secs);
+        anyhow::bail!(
+            "WhatsApp pairing requires the 'whatsapp-web' feature. \
+            Enable with: cargo build --features whatsapp-web"
+        );
+    }
+}
+
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
@@ -6410,6 +8273,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         extra_headers: config.extra_headers.clone(),
         api_path: config.api_path.clone(),
         provider_max_tokens: config.provider_max_tokens,
+        anthropic_prompt_caching: config.providers.anthropic.prompt_caching,
+        gemini_context_cache: config.providers.gemini.clone(),
+        openrouter_routing: config.providers.openrouter.routing.clone(),
+        custom_provider: config.providers.custom.clone(),
     };
     // Enrich model_fallbacks from model_routes: for each model in routes,
     // add other models of the same provider as fallback (if no explicit fallback exists).
@@ -6460,6 +8327,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
         &config.memory,
         &config.embedding_routes,
+        &config.embeddings,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -6839,6 +8707,28 @@ pub async fn start_channels(config: Config) -> Result<()> {
     // Load persisted per-chat route overrides (once per process; idempotent).
     init_route_overrides(&config.workspace_dir);
 
+    // Quota history — lets the reliability layer record per-provider daily usage
+    crate::providers::quota::get_or_init_global(
+        &config.workspace_dir,
+        config.reliability.quota_history_days,
+    );
+
+    // Cached model metadata: context windows feed the agent loop's compaction
+    // thresholds; cached pricing fills cost-tracking gaps for models without
+    // an explicit `[cost.prices]` entry.
+    crate::onboard::load_runtime_model_metadata(&config.workspace_dir).await;
+    let cost_prices = {
+        let mut prices = config.cost.prices.clone();
+        for (model, pricing) in crate::onboard::cached_model_prices(&config.workspace_dir).await {
+            prices.entry(model).or_insert(pricing);
+        }
+        prices
+    };
+
+    let respond_policies: HashMap<_, _> = channels_by_name
+        .iter()
+        .filter_map(|(name, ch)| ch.group_respond_policy().map(|p| (name.clone(), p)))
+        .collect();
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -6923,51 +8813,33 @@ pub async fn start_channels(config: Config) -> Result<()> {
         )
         .map(|tracker| ChannelCostTrackingState {
             tracker,
-            prices: Arc::new(config.cost.prices.clone()),
+            prices: Arc::new(cost_prices),
         }),
         pacing: config.pacing.clone(),
         context_token_budget: config.agent.max_context_tokens,
         debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::from_millis(
             config.channels_config.debounce_ms,
         ))),
+        message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::from_secs(
+            config.channels_config.dedup_ttl_secs,
+        ))),
+        respond_policies: Arc::new(respond_policies),
     });
 
-    // Hydrate in-memory conversation histories from persisted JSONL session files.
-    // If the last persisted turn is a user message (orphan from a crash mid-query),
-    // close it with a marker so the LLM doesn't try to continue the old request.
+    // Persisted sessions are hydrated lazily on the first message from each
+    // sender (see `hydrate_sender_history`), so startup only prunes sessions
+    // past the configured max-age.
     if let Some(ref store) = runtime_ctx.session_store {
-        let mut hydrated = 0usize;
-        let mut orphans_closed = 0usize;
-        let mut histories = runtime_ctx
-            .conversation_histories
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
-        for key in store.list_sessions() {
-            let mut msgs = store.load(&key);
-            if msgs.is_empty() {
-                continue;
-            }
-            // Close orphaned user turns from crashed sessions.
-            if msgs.last().is_some_and(|m| m.role == "user") {
-                let closure =
-                    ChatMessage::assistant("[Session interrupted — not continuing this request]");
-                if let Err(e) = store.append(&key, &closure) {
-                    tracing::debug!("Failed to persist orphan closure for {key}: {e}");
+        let ttl_hours = config.channels_config.session_ttl_hours;
+        if ttl_hours > 0 {
+            use session_backend::SessionBackend as _;
+            match store.cleanup_stale(ttl_hours) {
+                Ok(cleaned) if cleaned > 0 => {
+                    tracing::info!("🧹 Pruned {cleaned} session(s) older than {ttl_hours}h");
                 }
-                msgs.push(closure);
-                orphans_closed += 1;
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to prune stale sessions: {e}"),
             }
-            hydrated += 1;
-            histories.insert(key, msgs);
-        }
-        drop(histories);
-        if hydrated > 0 {
-            tracing::info!("📂 Restored {hydrated} session(s) from disk");
-        }
-        if orphans_closed > 0 {
-            tracing::info!(
-                "🔒 Closed {orphans_closed} orphaned session turn(s) from previous crash"
-            );
         }
     }
 
@@ -7098,6 +8970,41 @@ mod tests {
         let other_err =
             anyhow::anyhow!("OpenAI Codex API error (502 Bad Gateway): error code: 502");
         assert!(!is_context_window_overflow_error(&other_err));
+
+        // Typed path: a classified 413 carries no hint text at all.
+        let typed_err = anyhow::Error::new(providers::ProviderError::from_response_parts(
+            "OpenAI",
+            reqwest::StatusCode::PAYLOAD_TOO_LARGE,
+            None,
+            "payload too large",
+        ));
+        assert!(is_context_window_overflow_error(&typed_err));
+    }
+
+    #[test]
+    fn user_facing_provider_error_tailors_classified_failures() {
+        let rate_limited = anyhow::Error::new(providers::ProviderError::from_response_parts(
+            "OpenAI",
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            Some(std::time::Duration::from_secs(20)),
+            r#"{"error":{"message":"Rate limit reached.","code":"rate_limit_exceeded"}}"#,
+        ));
+        let message = user_facing_provider_error(&rate_limited).unwrap();
+        assert!(message.contains("20s"), "got: {message}");
+
+        let auth = anyhow::Error::new(providers::ProviderError::from_response_parts(
+            "Anthropic",
+            reqwest::StatusCode::UNAUTHORIZED,
+            None,
+            r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#,
+        ));
+        assert!(user_facing_provider_error(&auth)
+            .unwrap()
+            .contains("authentication failed"));
+
+        // Unclassified errors fall through to the generic path.
+        let plain = anyhow::anyhow!("something else went wrong");
+        assert!(user_facing_provider_error(&plain).is_none());
     }
 
     #[test]
@@ -7345,6 +9252,8 @@ mod tests {
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -7472,6 +9381,8 @@ mod tests {
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -7511,7 +9422,787 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
-            conversation_histories: Arc::new(Mutex::new(histories)),
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            pending_new_sessions: Arc::new(Mutex::new(HashSet::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            pending_selections: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+                discord: false,
+                mattermost: false,
+                matrix: false,
+            },
+            multimodal: crate::config::MultimodalConfig::default(),
+            media_pipeline: crate::config::MediaPipelineConfig::default(),
+            transcription_config: crate::config::TranscriptionConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            prompt_config: Arc::new(crate::config::Config::default()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            autonomy_level: AutonomyLevel::default(),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            max_parallel_tool_calls: 5,
+            max_tool_result_chars: 4000,
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: None,
+            autonomy_config: Arc::new(crate::config::AutonomyConfig::default()),
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            loaded_skills: Arc::new(Vec::new()),
+            activated_tools: None,
+            cost_tracking: None,
+            pacing: crate::config::PacingConfig::default(),
+            max_tool_result_chars: 0,
+            context_token_budget: 0,
+            debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
+        };
+
+        assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
+
+        let locked_histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = locked_histories
+            .get(&sender)
+            .expect("sender history should remain");
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].content, "first");
+        assert_eq!(turns[1].content, "ok");
+    }
+
+    #[test]
+    fn edit_of_in_flight_message_cancels_the_original() {
+        assert_eq!(
+            classify_edited_message(true, None, 300),
+            EditDisposition::CancelInFlight
+        );
+        // In-flight wins even when an earlier completion record exists.
+        assert_eq!(
+            classify_edited_message(true, Some(Instant::now()), 300),
+            EditDisposition::CancelInFlight
+        );
+    }
+
+    #[test]
+    fn edit_within_window_reprocesses_the_turn() {
+        assert_eq!(
+            classify_edited_message(false, Some(Instant::now()), 300),
+            EditDisposition::Reprocess
+        );
+    }
+
+    #[test]
+    fn edit_outside_window_is_ignored() {
+        let long_ago = Instant::now()
+            .checked_sub(Duration::from_secs(600))
+            .expect("clock predates test");
+        assert_eq!(
+            classify_edited_message(false, Some(long_ago), 300),
+            EditDisposition::Ignore
+        );
+        // Window of 0 disables edit reprocessing entirely.
+        assert_eq!(
+            classify_edited_message(false, Some(Instant::now()), 0),
+            EditDisposition::Ignore
+        );
+        // Never-seen messages are ignored too.
+        assert_eq!(
+            classify_edited_message(false, None, 300),
+            EditDisposition::Ignore
+        );
+    }
+
+    #[test]
+    fn rollback_edited_user_turn_removes_user_and_reply_pair() {
+        let sender = "telegram_edit1".to_string();
+        let mut histories = HashMap::new();
+        histories.insert(
+            sender.clone(),
+            vec![
+                ChatMessage::user("first"),
+                ChatMessage::assistant("ok"),
+                ChatMessage::user("whats the wether"),
+                ChatMessage::assistant("I could not parse that."),
+            ],
+        );
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            pending_new_sessions: Arc::new(Mutex::new(HashSet::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            pending_selections: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+                discord: false,
+                mattermost: false,
+                matrix: false,
+            },
+            multimodal: crate::config::MultimodalConfig::default(),
+            media_pipeline: crate::config::MediaPipelineConfig::default(),
+            transcription_config: crate::config::TranscriptionConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            prompt_config: Arc::new(crate::config::Config::default()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            autonomy_level: AutonomyLevel::default(),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            max_parallel_tool_calls: 5,
+            max_tool_result_chars: 4000,
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: None,
+            autonomy_config: Arc::new(crate::config::AutonomyConfig::default()),
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            loaded_skills: Arc::new(Vec::new()),
+            activated_tools: None,
+            cost_tracking: None,
+            pacing: crate::config::PacingConfig::default(),
+            context_token_budget: 0,
+            debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
+        };
+
+        assert!(rollback_edited_user_turn(&ctx, &sender, "whats the wether"));
+        assert!(
+            !rollback_edited_user_turn(&ctx, &sender, "whats the wether"),
+            "second rollback of the same content must be a no-op"
+        );
+
+        let locked_histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = locked_histories
+            .get(&sender)
+            .expect("sender history should remain");
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].content, "first");
+        assert_eq!(turns[1].content, "ok");
+    }
+
+    #[test]
+    fn approval_reaction_resolves_recorded_prompt() {
+        let registry = crate::approval::pending_approvals();
+        let (id, mut rx) = registry.create("shell", HashSet::new());
+        crate::approval::approval_prompt_index().record("telegram", "reaction-9001", &id);
+
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "telegram".into(),
+            message_id: "reaction-9001".into(),
+            actor: "alice".into(),
+            emoji: "\u{1F44D}".into(),
+        });
+        assert_eq!(rx.try_recv(), Ok(crate::approval::ApprovalResponse::Yes));
+    }
+
+    #[test]
+    fn approval_reaction_ignores_unknown_messages_and_other_emoji() {
+        let registry = crate::approval::pending_approvals();
+        let (id, mut rx) = registry.create("shell", HashSet::new());
+        crate::approval::approval_prompt_index().record("telegram", "reaction-9002", &id);
+
+        // 🎉 on the prompt is not a decision.
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "telegram".into(),
+            message_id: "reaction-9002".into(),
+            actor: "alice".into(),
+            emoji: "\u{1F389}".into(),
+        });
+        // 👍 on an unrelated message does not touch the request.
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "telegram".into(),
+            message_id: "reaction-unknown".into(),
+            actor: "alice".into(),
+            emoji: "\u{1F44D}".into(),
+        });
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn approval_reaction_respects_approver_allowlist() {
+        let registry = crate::approval::pending_approvals();
+        let allowed: HashSet<String> = ["alice".to_string()].into_iter().collect();
+        let (id, mut rx) = registry.create("shell", allowed);
+        crate::approval::approval_prompt_index().record("discord", "reaction-9003", &id);
+
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "discord".into(),
+            message_id: "reaction-9003".into(),
+            actor: "mallory".into(),
+            emoji: "\u{1F44D}".into(),
+        });
+        assert!(rx.try_recv().is_err(), "non-approver must be ignored");
+
+        // Allowed actor identities are normalized (`@Alice` → `alice`).
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "discord".into(),
+            message_id: "reaction-9003".into(),
+            actor: "@Alice".into(),
+            emoji: "\u{1F44E}".into(),
+        });
+        assert_eq!(rx.try_recv(), Ok(crate::approval::ApprovalResponse::No));
+    }
+
+    #[test]
+    fn approval_reaction_expired_request_is_not_resolved() {
+        let registry = crate::approval::pending_approvals();
+        let (id, mut rx) = registry.create_with_ttl("shell", HashSet::new(), Duration::ZERO);
+        crate::approval::approval_prompt_index().record("slack", "reaction-9004", &id);
+
+        handle_approval_reaction(&traits::ReactionEvent {
+            channel: "slack".into(),
+            message_id: "reaction-9004".into(),
+            actor: "alice".into(),
+            emoji: "+1".into(),
+        });
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn reaction_approval_decision_maps_platform_emoji_names() {
+        use crate::approval::ApprovalResponse;
+
+        for yes in ["\u{1F44D}", "+1", "thumbsup"] {
+            assert_eq!(reaction_approval_decision(yes), Some(ApprovalResponse::Yes));
+        }
+        for no in ["\u{1F44E}", "-1", "thumbsdown"] {
+            assert_eq!(reaction_approval_decision(no), Some(ApprovalResponse::No));
+        }
+        assert_eq!(reaction_approval_decision("\u{1F389}"), None);
+        assert_eq!(reaction_approval_decision(""), None);
+    }
+
+    // ── Outbound location markers ────────────────────────────────────
+
+    #[test]
+    fn location_marker_is_extracted_and_text_cleaned() {
+        let (text, locations) = parse_location_markers("Meet here [LOCATION:52.52,13.405] at noon");
+        assert_eq!(text, "Meet here  at noon");
+        assert_eq!(locations, vec![(52.52, 13.405)]);
+    }
+
+    #[test]
+    fn location_marker_allows_spaces_and_negatives() {
+        let (text, locations) = parse_location_markers("[LOCATION: -33.9, 151.2 ]");
+        assert!(text.is_empty());
+        assert_eq!(locations, vec![(-33.9, 151.2)]);
+    }
+
+    #[test]
+    fn multiple_location_markers_all_parsed() {
+        let (text, locations) =
+            parse_location_markers("A [LOCATION:1.0,2.0] B [LOCATION:3.5,4.5] C");
+        assert_eq!(text, "A  B  C");
+        assert_eq!(locations, vec![(1.0, 2.0), (3.5, 4.5)]);
+    }
+
+    #[test]
+    fn malformed_location_markers_are_left_in_place() {
+        for content in [
+            "[LOCATION:not,numbers]",
+            "[LOCATION:52.52]",
+            "[LOCATION:52.52,13.405",
+        ] {
+            let (text, locations) = parse_location_markers(content);
+            assert_eq!(text, content, "malformed marker should survive");
+            assert!(locations.is_empty());
+        }
+    }
+
+    // ── Outbound message splitting ───────────────────────────────────
+
+    #[test]
+    fn split_outbound_under_limit_is_untouched() {
+        let parts = split_outbound_message("short message", 100, OutboundFormat::Markdown);
+        assert_eq!(parts, vec!["short message"]);
+    }
+
+    #[test]
+    fn split_outbound_exactly_at_limit_is_single_unnumbered_part() {
+        let msg = "a".repeat(100);
+        let parts = split_outbound_message(&msg, 100, OutboundFormat::Markdown);
+        assert_eq!(parts, vec![msg]);
+    }
+
+    #[test]
+    fn split_outbound_one_over_limit_numbers_parts() {
+        let msg = "a".repeat(101);
+        let parts = split_outbound_message(&msg, 100, OutboundFormat::Markdown);
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("[1/2] "));
+        assert!(parts[1].starts_with("[2/2] "));
+        assert!(parts.iter().all(|p| p.chars().count() <= 100));
+    }
+
+    #[test]
+    fn split_outbound_prefers_paragraph_boundaries() {
+        let msg = format!("{}\n\n{}", "a".repeat(60), "b".repeat(60));
+        let parts = split_outbound_message(&msg, 100, OutboundFormat::Markdown);
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].ends_with('a'));
+        assert!(parts[1].ends_with('b'));
+    }
+
+    #[test]
+    fn split_outbound_packs_small_paragraphs_together() {
+        let msg = "one\n\ntwo\n\nthree";
+        let parts = split_outbound_message(msg, 100, OutboundFormat::Markdown);
+        assert_eq!(parts, vec![msg]);
+    }
+
+    #[test]
+    fn split_outbound_reopens_code_fences_across_parts() {
+        let body = "let value = compute();\n".repeat(20);
+        let msg = format!("```rust\n{body}```");
+        let parts = split_outbound_message(&msg, 120, OutboundFormat::Markdown);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(part.chars().count() <= 120);
+            // Every part is a self-contained, balanced fence with its tag.
+            let text = part.split_once("] ").map_or(part.as_str(), |(_, t)| t);
+            assert!(text.starts_with("```rust\n"));
+            assert!(text.ends_with("\n```"));
+            assert_eq!(text.matches("```").count(), 2);
+        }
+    }
+
+    #[test]
+    fn split_outbound_giant_code_block_keeps_all_lines() {
+        let body: String = (0..300).map(|i| format!("line_{i}\n")).collect();
+        let msg = format!("```\n{body}```");
+        let parts = split_outbound_message(&msg, 500, OutboundFormat::Markdown);
+        assert!(parts.len() > 1);
+        let joined = parts.join("\n");
+        for i in 0..300 {
+            assert!(joined.contains(&format!("line_{i}")), "lost line_{i}");
+        }
+    }
+
+    #[test]
+    fn split_outbound_handles_unclosed_fence() {
+        let body = "x\n".repeat(200);
+        let msg = format!("```python\n{body}");
+        let parts = split_outbound_message(&msg, 120, OutboundFormat::Markdown);
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|p| p.chars().count() <= 120));
+    }
+
+    #[test]
+    fn split_outbound_text_around_code_block() {
+        let msg = format!(
+            "Intro paragraph.\n\n```\n{}\n```\n\nClosing remarks.",
+            "code\n".repeat(50)
+        );
+        let parts = split_outbound_message(&msg, 150, OutboundFormat::Markdown);
+        assert!(parts.len() > 1);
+        let joined = parts.join("\n");
+        assert!(joined.contains("Intro paragraph."));
+        assert!(joined.contains("Closing remarks."));
+        assert!(parts.iter().all(|p| p.chars().count() <= 150));
+    }
+
+    #[test]
+    fn split_outbound_unicode_never_splits_mid_char() {
+        let msg = "\u{1F980}\u{4F60}\u{597D} ".repeat(200);
+        let parts = split_outbound_message(&msg, 100, OutboundFormat::Plain);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(std::str::from_utf8(part.as_bytes()).is_ok());
+            assert!(part.chars().count() <= 100);
+        }
+    }
+
+    #[test]
+    fn split_outbound_plain_parts_concatenate_to_input() {
+        let msg = "alpha beta gamma delta ".repeat(50);
+        let parts = split_plain_parts(&msg, 100);
+        assert!(parts.len() > 1);
+        assert_eq!(parts.concat(), msg);
+    }
+
+    #[test]
+    fn split_outbound_plain_format_hard_splits_unbroken_runs() {
+        let msg = "z".repeat(350);
+        let parts = split_outbound_message(&msg, 100, OutboundFormat::Plain);
+        assert_eq!(parts.len(), 4);
+        assert!(parts.iter().all(|p| p.chars().count() <= 100));
+    }
+
+    #[test]
+    fn split_outbound_numbering_reflects_total_part_count() {
+        let msg = "word ".repeat(500);
+        let parts = split_outbound_message(&msg, 200, OutboundFormat::Markdown);
+        let total = parts.len();
+        for (i, part) in parts.iter().enumerate() {
+            assert!(part.starts_with(&format!("[{}/{total}] ", i + 1)));
+        }
+    }
+
+    #[test]
+    fn split_outbound_empty_input_is_single_empty_part() {
+        let parts = split_outbound_message("", 100, OutboundFormat::Markdown);
+        assert_eq!(parts, vec![""]);
+    }
+
+    // ── /status runtime command ──────────────────────────────────────
+
+    #[test]
+    fn status_command_parses_on_every_channel() {
+        for channel in ["telegram", "discord", "slack", "mattermost", "email"] {
+            assert_eq!(
+                parse_runtime_command(channel, "/status"),
+                Some(ChannelRuntimeCommand::Status),
+                "/status should parse on {channel}"
+            );
+        }
+        assert_eq!(
+            parse_runtime_command("telegram", "/status@my_bot"),
+            Some(ChannelRuntimeCommand::Status)
+        );
+    }
+
+    #[test]
+    fn format_uptime_picks_largest_relevant_units() {
+        assert_eq!(format_uptime(12), "0m 12s");
+        assert_eq!(format_uptime(61), "1m 1s");
+        assert_eq!(format_uptime(3_661), "1h 1m");
+        assert_eq!(format_uptime(90_061), "1d 1h 1m");
+    }
+
+    #[test]
+    fn format_runtime_status_includes_all_report_fields() {
+        let report = RuntimeStatusReport {
+            uptime_seconds: 3_900,
+            provider: "openrouter".into(),
+            model: "anthropic/claude-sonnet-4".into(),
+            memory_backend: "sqlite".into(),
+            in_flight_messages: 2,
+            components: vec![
+                ("channel:telegram".into(), "ok".into(), 0),
+                ("channel:discord".into(), "error".into(), 3),
+            ],
+            estop_engaged: Some(false),
+            response_cache: Some((7, 3)),
+        };
+
+        let rendered = format_runtime_status(&report);
+        assert!(rendered.contains("Uptime: 1h 5m"));
+        assert!(rendered.contains("Route: anthropic/claude-sonnet-4 (openrouter)"));
+        assert!(rendered.contains("Memory: sqlite"));
+        assert!(rendered.contains("In-flight messages: 2"));
+        assert!(rendered.contains("Response cache: 7 hits / 3 misses"));
+        assert!(rendered.contains("Estop: clear"));
+        assert!(rendered.contains("channel:telegram: ok"));
+        assert!(rendered.contains("channel:discord: error (restarts: 3)"));
+    }
+
+    #[test]
+    fn format_runtime_status_engaged_estop_is_loud() {
+        let report = RuntimeStatusReport {
+            uptime_seconds: 10,
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            memory_backend: "markdown".into(),
+            in_flight_messages: 0,
+            components: vec![],
+            estop_engaged: Some(true),
+            response_cache: None,
+        };
+
+        let rendered = format_runtime_status(&report);
+        assert!(rendered.contains("ENGAGED"));
+        assert!(rendered.contains("Components: none registered"));
+    }
+
+    #[test]
+    fn format_runtime_status_omits_estop_when_disabled() {
+        let report = RuntimeStatusReport {
+            uptime_seconds: 10,
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            memory_backend: "none".into(),
+            in_flight_messages: 0,
+            components: vec![],
+            estop_engaged: None,
+            response_cache: None,
+        };
+
+        assert!(!format_runtime_status(&report).contains("Estop"));
+    }
+
+    #[test]
+    fn format_runtime_status_scrubs_credentials() {
+        let report = RuntimeStatusReport {
+            uptime_seconds: 10,
+            provider: "custom".into(),
+            model: "proxy?api_key=supersecretvalue123".into(),
+            memory_backend: "sqlite".into(),
+            in_flight_messages: 0,
+            components: vec![],
+            estop_engaged: None,
+            response_cache: None,
+        };
+
+        let rendered = format_runtime_status(&report);
+        assert!(!rendered.contains("supersecretvalue123"));
+        assert!(rendered.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn usage_command_parses_on_every_channel() {
+        for channel in ["telegram", "discord", "slack", "mattermost", "email"] {
+            assert_eq!(
+                parse_runtime_command(channel, "/usage"),
+                Some(ChannelRuntimeCommand::Usage),
+                "/usage should parse on {channel}"
+            );
+        }
+        assert_eq!(
+            parse_runtime_command("telegram", "/usage@my_bot"),
+            Some(ChannelRuntimeCommand::Usage)
+        );
+    }
+
+    // ── /cron runtime commands ───────────────────────────────────────
+
+    fn cron_test_message(channel: &str, sender: &str) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: "msg_1".into(),
+            sender: sender.into(),
+            reply_target: "chat42".into(),
+            content: "/cron list".into(),
+            channel: channel.into(),
+            timestamp: 1,
+            thread_ts: None,
+            reply_to_message_id: None,
+            interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
+            attachments: vec![],
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn cron_command_parses_subcommands() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron list"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::List))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron add 0 9 * * 1-5 | stand-up in 15 min"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Add {
+                expr: "0 9 * * 1-5".into(),
+                message: "stand-up in 15 min".into(),
+            }))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron once 30m take the pizza out"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Once {
+                delay: "30m".into(),
+                message: "take the pizza out".into(),
+            }))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron pause abc-123"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Pause(
+                "abc-123".into()
+            )))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron resume abc-123"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Resume(
+                "abc-123".into()
+            )))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/cron remove abc-123"),
+            Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Remove(
+                "abc-123".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn topic_command_parses_subcommands() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/topic new Release planning"),
+            Some(ChannelRuntimeCommand::Topic(TopicRuntimeCommand::New(
+                "Release planning".into()
+            )))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/topic close"),
+            Some(ChannelRuntimeCommand::Topic(TopicRuntimeCommand::Close))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/topic reopen"),
+            Some(ChannelRuntimeCommand::Topic(TopicRuntimeCommand::Reopen))
+        );
+        assert_eq!(
+            parse_runtime_command("telegram", "/topic@my_bot close"),
+            Some(ChannelRuntimeCommand::Topic(TopicRuntimeCommand::Close))
+        );
+    }
+
+    #[test]
+    fn topic_command_incomplete_forms_fall_back_to_usage() {
+        for content in ["/topic", "/topic new", "/topic close now", "/topic bogus"] {
+            assert_eq!(
+                parse_runtime_command("telegram", content),
+                Some(ChannelRuntimeCommand::Topic(TopicRuntimeCommand::Usage)),
+                "{content} should parse as usage"
+            );
+        }
+    }
+
+    #[test]
+    fn topic_command_is_telegram_only() {
+        for channel in ["discord", "slack", "matrix", "cli"] {
+            assert_eq!(parse_runtime_command(channel, "/topic new Releases"), None);
+        }
+    }
+
+    #[test]
+    fn cron_command_incomplete_forms_fall_back_to_usage() {
+        for content in [
+            "/cron",
+            "/cron bogus",
+            "/cron add 0 9 * * *",
+            "/cron add | message without expression",
+            "/cron once 30m",
+            "/cron pause",
+        ] {
+            assert_eq!(
+                parse_runtime_command("telegram", content),
+                Some(ChannelRuntimeCommand::Cron(CronRuntimeCommand::Usage)),
+                "{content} should parse as usage"
+            );
+        }
+    }
+
+    #[test]
+    fn cron_delivery_defaults_to_current_conversation() {
+        let msg = cron_test_message("telegram", "alice");
+        let delivery = cron_announce_delivery(&msg);
+        assert_eq!(delivery.mode, "announce");
+        assert_eq!(delivery.channel.as_deref(), Some("telegram"));
+        assert_eq!(delivery.to.as_deref(), Some("chat42"));
+        assert!(delivery.best_effort);
+    }
+
+    #[test]
+    fn cron_management_respects_approver_allowlist() {
+        let autonomy = crate::config::AutonomyConfig {
+            approval_actors: vec!["alice".into()],
+            ..Default::default()
+        };
+        let approval = ApprovalManager::for_non_interactive(&autonomy);
+
+        assert!(may_manage_cron(
+            &cron_test_message("telegram", "alice"),
+            &approval
+        ));
+        assert!(!may_manage_cron(
+            &cron_test_message("telegram", "mallory"),
+            &approval
+        ));
+        // CLI is implicitly trusted regardless of the allowlist.
+        assert!(may_manage_cron(
+            &cron_test_message("cli", "mallory"),
+            &approval
+        ));
+    }
+
+    #[test]
+    fn cron_management_open_when_allowlist_empty() {
+        let approval =
+            ApprovalManager::for_non_interactive(&crate::config::AutonomyConfig::default());
+        // Empty allowlist defers to the channel's own user allowlist,
+        // matching the approval-prompt semantics.
+        assert!(may_manage_cron(
+            &cron_test_message("telegram", "anyone"),
+            &approval
+        ));
+    }
+
+    // ── /respondmode runtime command ─────────────────────────────────
+
+    #[test]
+    fn respondmode_command_parses_argument() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/respondmode silent"),
+            Some(ChannelRuntimeCommand::RespondMode("silent".into()))
+        );
+        assert_eq!(
+            parse_runtime_command("discord", "/respondmode mention"),
+            Some(ChannelRuntimeCommand::RespondMode("mention".into()))
+        );
+        // Missing argument still parses; the handler replies with usage.
+        assert_eq!(
+            parse_runtime_command("telegram", "/respondmode"),
+            Some(ChannelRuntimeCommand::RespondMode(String::new()))
+        );
+    }
+
+    #[test]
+    fn respond_mode_command_overrides_live_policy_for_group() {
+        let policy = Arc::new(respond_policy::GroupRespondPolicy::from_mention_only(false));
+        let mut policies = HashMap::new();
+        policies.insert("telegram".to_string(), Arc::clone(&policy));
+
+        let ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             pending_new_sessions: Arc::new(Mutex::new(HashSet::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -7552,23 +10243,89 @@ mod tests {
             activated_tools: None,
             cost_tracking: None,
             pacing: crate::config::PacingConfig::default(),
-            max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(policies),
         };
 
-        assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
+        // DM (no group metadata): rejected with an explanation.
+        let dm = cron_test_message("telegram", "alice");
+        let reply = handle_respond_mode_command(&ctx, &dm, "silent");
+        assert!(reply.contains("group chats"), "unexpected reply: {reply}");
 
-        let locked_histories = ctx
-            .conversation_histories
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
-        let turns = locked_histories
-            .get(&sender)
-            .expect("sender history should remain");
-        assert_eq!(turns.len(), 2);
-        assert_eq!(turns[0].content, "first");
-        assert_eq!(turns[1].content, "ok");
+        // Group message: override lands on the shared policy object.
+        let mut group_msg = cron_test_message("telegram", "alice");
+        group_msg.metadata = Some(respond_policy::group_metadata("-100123", false));
+        let reply = handle_respond_mode_command(&ctx, &group_msg, "silent");
+        assert!(reply.contains("silent"), "unexpected reply: {reply}");
+        assert_eq!(
+            policy.mode_for("-100123"),
+            respond_policy::RespondMode::Silent
+        );
+        assert_eq!(policy.mode_for("-100999"), respond_policy::RespondMode::All);
+
+        // Unknown mode: usage string, no change.
+        let reply = handle_respond_mode_command(&ctx, &group_msg, "sometimes");
+        assert!(reply.starts_with("Usage:"), "unexpected reply: {reply}");
+    }
+
+    #[test]
+    fn format_usage_report_includes_all_windows_and_limit() {
+        let report = SenderUsageReport {
+            stats: crate::cost::SenderUsageStats {
+                today: crate::cost::UsageWindowTotals {
+                    messages: 3,
+                    input_tokens: 1200,
+                    output_tokens: 400,
+                    cost_usd: 0.0456,
+                },
+                this_month: crate::cost::UsageWindowTotals {
+                    messages: 40,
+                    input_tokens: 90_000,
+                    output_tokens: 30_000,
+                    cost_usd: 1.23,
+                },
+                estimated: false,
+            },
+            global_daily_cost_usd: 2.5,
+            daily_limit_usd: 10.0,
+        };
+
+        let rendered = format_usage_report(&report);
+        assert!(rendered.contains("• Today: 3 messages, 1200 in / 400 out tokens, $0.0456"));
+        assert!(
+            rendered.contains("• This month: 40 messages, 90000 in / 30000 out tokens, $1.2300")
+        );
+        assert!(rendered.contains("• Global today: $2.5000 of $10.00 daily limit"));
+        assert!(!rendered.contains("estimated"));
+    }
+
+    #[test]
+    fn format_usage_report_flags_estimated_counts() {
+        let report = SenderUsageReport {
+            stats: crate::cost::SenderUsageStats {
+                today: crate::cost::UsageWindowTotals {
+                    messages: 1,
+                    input_tokens: 100,
+                    output_tokens: 25,
+                    cost_usd: 0.0,
+                },
+                this_month: crate::cost::UsageWindowTotals {
+                    messages: 1,
+                    input_tokens: 100,
+                    output_tokens: 25,
+                    cost_usd: 0.0,
+                },
+                estimated: true,
+            },
+            global_daily_cost_usd: 0.0,
+            daily_limit_usd: 5.0,
+        };
+
+        let rendered = format_usage_report(&report);
+        assert!(rendered.contains("• Today: 1 message, 100 in / 25 out tokens"));
+        assert!(rendered.contains("Token counts are estimated"));
     }
 
     #[test]
@@ -7657,6 +10414,8 @@ mod tests {
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         };
 
         assert!(rollback_orphan_user_turn(
@@ -7684,6 +10443,195 @@ mod tests {
         assert_eq!(persisted[1].content, "ok");
     }
 
+    /// Minimal runtime context for exercising lazy session hydration.
+    fn hydration_test_ctx(
+        store: Arc<session_store::SessionStore>,
+        config: crate::config::Config,
+    ) -> ChannelRuntimeContext {
+        ChannelRuntimeContext {
+            channels_by_name: Arc::new(HashMap::new()),
+            provider: Arc::new(DummyProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("system".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            pending_new_sessions: Arc::new(Mutex::new(HashSet::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            pending_selections: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+                discord: false,
+                mattermost: false,
+                matrix: false,
+            },
+            multimodal: crate::config::MultimodalConfig::default(),
+            media_pipeline: crate::config::MediaPipelineConfig::default(),
+            transcription_config: crate::config::TranscriptionConfig::default(),
+            hooks: None,
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            prompt_config: Arc::new(config),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            autonomy_level: AutonomyLevel::default(),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            max_parallel_tool_calls: 5,
+            max_tool_result_chars: 4000,
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: Some(store),
+            autonomy_config: Arc::new(crate::config::AutonomyConfig::default()),
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            loaded_skills: Arc::new(Vec::new()),
+            activated_tools: None,
+            cost_tracking: None,
+            pacing: crate::config::PacingConfig::default(),
+            context_token_budget: 0,
+            debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn hydrate_sender_history_restores_and_closes_orphan_turn() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(session_store::SessionStore::new(tmp.path()).unwrap());
+        let sender = "telegram_lazy1";
+
+        store.append(sender, &ChatMessage::user("first")).unwrap();
+        store.append(sender, &ChatMessage::assistant("ok")).unwrap();
+        // Orphan user turn from a crash mid-query.
+        store
+            .append(sender, &ChatMessage::user("dangling"))
+            .unwrap();
+
+        let ctx = hydration_test_ctx(Arc::clone(&store), crate::config::Config::default());
+        hydrate_sender_history(&ctx, sender);
+
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = histories.get(sender).expect("history restored");
+        assert_eq!(turns.len(), 4);
+        assert_eq!(turns.last().unwrap().role, "assistant");
+        assert!(turns
+            .last()
+            .unwrap()
+            .content
+            .contains("Session interrupted"));
+        drop(histories);
+
+        // The closure marker is persisted too, so the next restart is clean.
+        assert_eq!(store.load(sender).len(), 4);
+    }
+
+    #[test]
+    fn hydrate_sender_history_truncates_to_history_cap() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(session_store::SessionStore::new(tmp.path()).unwrap());
+        let sender = "telegram_lazy2";
+
+        for i in 0..6 {
+            store
+                .append(sender, &ChatMessage::user(format!("q{i}")))
+                .unwrap();
+            store
+                .append(sender, &ChatMessage::assistant(format!("a{i}")))
+                .unwrap();
+        }
+
+        let mut config = crate::config::Config::default();
+        config.agent.max_history_messages = 4;
+        let ctx = hydration_test_ctx(Arc::clone(&store), config);
+        hydrate_sender_history(&ctx, sender);
+
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = histories.get(sender).expect("history restored");
+        assert_eq!(turns.len(), 4, "oldest turns dropped on load");
+        assert_eq!(turns.last().unwrap().content, "a5");
+    }
+
+    #[test]
+    fn hydrate_sender_history_deletes_stale_session_instead_of_loading() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(session_store::SessionStore::new(tmp.path()).unwrap());
+        let sender = "telegram_lazy3";
+
+        store
+            .append(sender, &ChatMessage::user("ancient context"))
+            .unwrap();
+        store.append(sender, &ChatMessage::assistant("ok")).unwrap();
+
+        // Backdate the session past the TTL.
+        let path = tmp.path().join("sessions").join(format!("{sender}.jsonl"));
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(72 * 3600))
+            .unwrap();
+
+        let mut config = crate::config::Config::default();
+        config.channels_config.session_ttl_hours = 24;
+        let ctx = hydration_test_ctx(Arc::clone(&store), config);
+        hydrate_sender_history(&ctx, sender);
+
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert!(
+            !histories.contains_key(sender),
+            "stale session must not resurrect"
+        );
+        drop(histories);
+        assert!(store.load(sender).is_empty(), "stale file deleted");
+    }
+
+    #[test]
+    fn hydrate_sender_history_never_overwrites_in_memory_turns() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(session_store::SessionStore::new(tmp.path()).unwrap());
+        let sender = "telegram_lazy4";
+
+        store.append(sender, &ChatMessage::user("on disk")).unwrap();
+
+        let ctx = hydration_test_ctx(Arc::clone(&store), crate::config::Config::default());
+        ctx.conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(sender.to_string(), vec![ChatMessage::user("in memory")]);
+
+        hydrate_sender_history(&ctx, sender);
+
+        let histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(histories.get(sender).unwrap()[0].content, "in memory");
+    }
+
     struct DummyProvider;
 
     #[async_trait::async_trait]
@@ -8259,6 +11207,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         Box::pin(process_channel_message(
@@ -8273,7 +11223,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         ))
@@ -8353,6 +11307,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         Box::pin(process_channel_message(
@@ -8367,7 +11323,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         ))
@@ -8461,6 +11421,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -8475,7 +11437,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -8554,6 +11520,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -8568,7 +11536,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -8605,6 +11577,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: "openrouter".into(),
             model: "openrouter-fast".into(),
             api_key: None,
+            openrouter_routing: None,
         }];
 
         let runtime_ctx = Arc::new(ChannelRuntimeContext {
@@ -8664,6 +11637,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         // /models fast — hint shortcut switches provider+model without LLM call
@@ -8679,7 +11654,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -8813,6 +11792,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -8827,7 +11808,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -8930,6 +11915,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -8944,7 +11931,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -9059,6 +12050,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -9073,7 +12066,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -9167,6 +12164,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -9181,7 +12180,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -9265,6 +12268,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -9279,7 +12284,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -9391,6 +12400,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 namespace: "default".into(),
                 importance: None,
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             }])
         }
 
@@ -9486,6 +12497,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -9499,7 +12512,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
         .await
         .unwrap();
@@ -9513,7 +12530,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
         .await
         .unwrap();
@@ -9609,6 +12630,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -9623,7 +12646,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9638,7 +12665,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9741,6 +12772,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -9755,7 +12788,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: Some("1741234567.100001".to_string()),
                 reply_to_message_id: None,
                 interruption_scope_id: Some("1741234567.100001".to_string()),
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9770,7 +12807,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: Some("1741234567.100001".to_string()),
                 reply_to_message_id: None,
                 interruption_scope_id: Some("1741234567.100001".to_string()),
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9870,6 +12911,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -9884,7 +12927,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9899,7 +12946,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -9981,6 +13032,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -9995,7 +13048,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -10074,6 +13131,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -10088,7 +13147,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -10638,7 +13701,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -10656,7 +13723,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: Some("1741234567.123456".into()),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         assert_eq!(
@@ -10677,7 +13748,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         assert_eq!(followup_thread_id(&msg).as_deref(), Some("msg_abc123"));
@@ -10695,7 +13770,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -10707,7 +13786,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         assert_ne!(
@@ -10716,6 +13799,53 @@ BTC is currently around $65,000 based on latest tool output."#
         );
     }
 
+    #[test]
+    fn conversation_history_key_separates_signal_group_from_dm() {
+        let group_msg = traits::ChannelMessage {
+            id: "sig_1".into(),
+            sender: "+1111111111".into(),
+            reply_target: "group:abc123".into(),
+            content: "hello".into(),
+            channel: "signal".into(),
+            timestamp: 1,
+            thread_ts: None,
+            reply_to_message_id: None,
+            interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
+            attachments: vec![],
+            metadata: None,
+        };
+        let dm_msg = traits::ChannelMessage {
+            id: "sig_2".into(),
+            sender: "+1111111111".into(),
+            reply_target: "+1111111111".into(),
+            content: "hello".into(),
+            channel: "signal".into(),
+            timestamp: 2,
+            thread_ts: None,
+            reply_to_message_id: None,
+            interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
+            attachments: vec![],
+            metadata: None,
+        };
+
+        // Same sender, but the group conversation and the DM keep separate
+        // histories because reply_target is part of the key.
+        assert_ne!(
+            conversation_history_key(&group_msg),
+            conversation_history_key(&dm_msg)
+        );
+        assert_eq!(
+            conversation_history_key(&group_msg),
+            "signal_group:abc123_+1111111111"
+        );
+    }
+
     #[tokio::test]
     async fn autosave_keys_preserve_multiple_conversation_facts() {
         let tmp = TempDir::new().unwrap();
@@ -10731,7 +13861,11 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -10743,43 +13877,180 @@ BTC is currently around $65,000 based on latest tool output."#
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         mem.store(
-            &conversation_memory_key(&msg1),
-            &msg1.content,
-            MemoryCategory::Conversation,
+            &conversation_memory_key(&msg1),
+            &msg1.content,
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            &conversation_memory_key(&msg2),
+            &msg2.content,
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mem.count().await.unwrap(), 2);
+
+        let recalled = mem.recall("45", 5, None, None, None).await.unwrap();
+        assert!(recalled.iter().any(|entry| entry.content.contains("45")));
+    }
+
+    #[tokio::test]
+    async fn build_memory_context_includes_recalled_entries() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("age_fact", "Age is 45", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+
+        let context = build_memory_context(&mem, "age", 0.0, None, None, 1000).await;
+        assert!(context.contains("[Memory context]"));
+        assert!(context.contains("Age is 45"));
+    }
+
+    #[tokio::test]
+    async fn build_memory_context_honors_namespace_filter() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store_with_metadata(
+            "alice_deploy",
+            "Deploy approvals go through Alice",
+            MemoryCategory::Conversation,
+            None,
+            Some("sender:slack:alice"),
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store_with_metadata(
+            "bob_deploy",
+            "Bob prefers deploy announcements in Telegram",
+            MemoryCategory::Conversation,
+            None,
+            Some("sender:telegram:bob"),
+            None,
+        )
+        .await
+        .unwrap();
+        // Un-namespaced entries land in the legacy "default" namespace,
+        // which the read set treats as global.
+        mem.store(
+            "shared_deploy",
+            "Deploy freezes start Friday afternoon",
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let namespaces = vec![
+            "sender:slack:alice".to_string(),
+            "global".to_string(),
+            "default".to_string(),
+        ];
+        let context =
+            build_memory_context(&mem, "deploy", 0.0, None, Some(&namespaces), 1000).await;
+
+        assert!(context.contains("through Alice"));
+        assert!(context.contains("freezes start Friday"));
+        assert!(
+            !context.contains("Bob prefers"),
+            "another sender's namespace must not leak into context, got: {context}"
+        );
+    }
+
+    #[tokio::test]
+    async fn build_memory_context_injects_pinned_before_recall() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "timezone",
+            "User timezone is Europe/Lisbon",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(mem.set_pinned("timezone", true).await.unwrap());
+        mem.store("age_fact", "Age is 45", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+
+        // The pinned entry does not match the query but is injected anyway,
+        // ahead of the recalled entry.
+        let context = build_memory_context(&mem, "age", 0.0, None, None, 1000).await;
+        let pinned_pos = context.find("Europe/Lisbon").expect("pinned entry missing");
+        let recalled_pos = context.find("Age is 45").expect("recalled entry missing");
+        assert!(
+            pinned_pos < recalled_pos,
+            "pinned must precede recall: {context}"
+        );
+
+        // A zero budget disables pinned injection.
+        let context = build_memory_context(&mem, "age", 0.0, None, None, 0).await;
+        assert!(!context.contains("Europe/Lisbon"));
+        assert!(context.contains("Age is 45"));
+    }
+
+    #[tokio::test]
+    async fn build_memory_context_pinned_budget_drops_oldest() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "older_pin",
+            "Oldest pinned fact",
+            MemoryCategory::Core,
             None,
         )
         .await
         .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
         mem.store(
-            &conversation_memory_key(&msg2),
-            &msg2.content,
-            MemoryCategory::Conversation,
+            "newer_pin",
+            "Newest pinned fact",
+            MemoryCategory::Core,
             None,
         )
         .await
         .unwrap();
+        assert!(mem.set_pinned("older_pin", true).await.unwrap());
+        assert!(mem.set_pinned("newer_pin", true).await.unwrap());
 
-        assert_eq!(mem.count().await.unwrap(), 2);
-
-        let recalled = mem.recall("45", 5, None, None, None).await.unwrap();
-        assert!(recalled.iter().any(|entry| entry.content.contains("45")));
+        // Budget fits one line, so the older entry is dropped.
+        let context = build_memory_context(&mem, "unrelated query", 1.0, None, None, 40).await;
+        assert!(context.contains("Newest pinned fact"), "got: {context}");
+        assert!(!context.contains("Oldest pinned fact"), "got: {context}");
     }
 
     #[tokio::test]
-    async fn build_memory_context_includes_recalled_entries() {
+    async fn build_memory_context_dedups_pinned_recall_matches() {
         let tmp = TempDir::new().unwrap();
         let mem = SqliteMemory::new(tmp.path()).unwrap();
-        mem.store("age_fact", "Age is 45", MemoryCategory::Conversation, None)
-            .await
-            .unwrap();
+        mem.store(
+            "wifi_quirk",
+            "House wifi drops on channel 6",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(mem.set_pinned("wifi_quirk", true).await.unwrap());
 
-        let context = build_memory_context(&mem, "age", 0.0, None).await;
-        assert!(context.contains("[Memory context]"));
-        assert!(context.contains("Age is 45"));
+        // The pinned entry also matches recall; it must appear exactly once.
+        let context = build_memory_context(&mem, "wifi", 0.0, None, None, 1000).await;
+        assert_eq!(context.matches("channel 6").count(), 1, "got: {context}");
     }
 
     /// Auto-saved photo messages must not surface through memory context,
@@ -10809,7 +14080,7 @@ BTC is currently around $65,000 based on latest tool output."#
         .await
         .unwrap();
 
-        let context = build_memory_context(&mem, "screenshot", 0.0, None).await;
+        let context = build_memory_context(&mem, "screenshot", 0.0, None, None, 1000).await;
 
         // The image-marker entry must be excluded to prevent duplication.
         assert!(
@@ -10890,6 +14161,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -10904,7 +14177,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -10922,7 +14199,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11036,6 +14317,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -11050,7 +14333,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11084,7 +14371,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11124,7 +14415,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11224,6 +14519,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -11238,7 +14535,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11348,6 +14649,8 @@ BTC is currently around $65,000 based on latest tool output."#
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -11362,7 +14665,11 @@ BTC is currently around $65,000 based on latest tool output."#
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -11637,13 +14944,16 @@ This is an example JSON object for profile settings."#;
 
     #[test]
     fn classify_health_ok_true() {
-        let state = classify_health_result(&Ok(true));
+        let state = classify_health_result(&Ok(ChannelHealthReport {
+            healthy: true,
+            ..ChannelHealthReport::default()
+        }));
         assert_eq!(state, ChannelHealthState::Healthy);
     }
 
     #[test]
     fn classify_health_ok_false() {
-        let state = classify_health_result(&Ok(false));
+        let state = classify_health_result(&Ok(ChannelHealthReport::default()));
         assert_eq!(state, ChannelHealthState::Unhealthy);
     }
 
@@ -11651,7 +14961,7 @@ This is an example JSON object for profile settings."#;
     async fn classify_health_timeout() {
         let result = tokio::time::timeout(Duration::from_millis(1), async {
             tokio::time::sleep(Duration::from_millis(20)).await;
-            true
+            ChannelHealthReport::default()
         })
         .await;
         let state = classify_health_result(&result);
@@ -11682,6 +14992,360 @@ This is an example JSON object for profile settings."#;
             .any(|entry| entry.channel.name() == "mattermost"));
     }
 
+    /// Records every broadcast delivery for fan-out assertions.
+    struct BroadcastRecordingChannel {
+        name: &'static str,
+        deliveries: Arc<tokio::sync::Mutex<Vec<(String, String)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for BroadcastRecordingChannel {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.deliveries
+                .lock()
+                .await
+                .push((message.recipient.clone(), message.content.clone()));
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Always errors on send; broadcast must report it without blocking others.
+    struct FailingSendChannel;
+
+    #[async_trait::async_trait]
+    impl Channel for FailingSendChannel {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            anyhow::bail!("send boom")
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Never completes a send; broadcast must cut it off with its timeout.
+    struct HangingSendChannel;
+
+    #[async_trait::async_trait]
+    impl Channel for HangingSendChannel {
+        fn name(&self) -> &str {
+            "hanging"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn broadcast_fans_out_to_every_channel() {
+        let deliveries = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let targets: Vec<(String, Arc<dyn Channel>, String)> = vec![
+            (
+                "telegram".into(),
+                Arc::new(BroadcastRecordingChannel {
+                    name: "telegram",
+                    deliveries: Arc::clone(&deliveries),
+                }),
+                "chat-1".into(),
+            ),
+            (
+                "slack".into(),
+                Arc::new(BroadcastRecordingChannel {
+                    name: "slack",
+                    deliveries: Arc::clone(&deliveries),
+                }),
+                "C123".into(),
+            ),
+        ];
+
+        let report =
+            broadcast_to_channels(targets, "maintenance at 22:00", Duration::from_secs(5)).await;
+
+        assert_eq!(report.sent(), 2);
+        assert_eq!(report.failed(), 0);
+        let mut got = deliveries.lock().await.clone();
+        got.sort();
+        assert_eq!(
+            got,
+            vec![
+                ("C123".to_string(), "maintenance at 22:00".to_string()),
+                ("chat-1".to_string(), "maintenance at 22:00".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcast_aggregates_failures_without_blocking_others() {
+        let deliveries = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let targets: Vec<(String, Arc<dyn Channel>, String)> = vec![
+            ("failing".into(), Arc::new(FailingSendChannel), "x".into()),
+            (
+                "telegram".into(),
+                Arc::new(BroadcastRecordingChannel {
+                    name: "telegram",
+                    deliveries: Arc::clone(&deliveries),
+                }),
+                "chat-1".into(),
+            ),
+        ];
+
+        let report = broadcast_to_channels(targets, "announce", Duration::from_secs(5)).await;
+
+        assert_eq!(report.sent(), 1);
+        assert_eq!(report.failed(), 1);
+        let failure = report
+            .outcomes
+            .iter()
+            .find(|o| o.channel == "failing")
+            .unwrap();
+        assert!(failure.error.as_deref().unwrap().contains("send boom"));
+        assert_eq!(
+            deliveries.lock().await.len(),
+            1,
+            "healthy channel delivered"
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcast_times_out_hanging_channels_individually() {
+        let deliveries = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let targets: Vec<(String, Arc<dyn Channel>, String)> = vec![
+            ("hanging".into(), Arc::new(HangingSendChannel), "x".into()),
+            (
+                "slack".into(),
+                Arc::new(BroadcastRecordingChannel {
+                    name: "slack",
+                    deliveries: Arc::clone(&deliveries),
+                }),
+                "C123".into(),
+            ),
+        ];
+
+        let report = broadcast_to_channels(targets, "announce", Duration::from_millis(50)).await;
+
+        let hung = report
+            .outcomes
+            .iter()
+            .find(|o| o.channel == "hanging")
+            .unwrap();
+        assert!(hung.error.as_deref().unwrap().contains("timed out"));
+        assert_eq!(report.sent(), 1);
+        assert_eq!(deliveries.lock().await.len(), 1);
+    }
+
+    /// Reports a fixed detailed probe result for doctor assertions.
+    struct DetailedHealthChannel {
+        healthy: bool,
+        identity: Option<&'static str>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for DetailedHealthChannel {
+        fn name(&self) -> &str {
+            "detailed"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn health_check_detailed(&self) -> ChannelHealthReport {
+            ChannelHealthReport {
+                healthy: self.healthy,
+                latency_ms: 42,
+                identity: self.identity.map(String::from),
+                detail: (!self.healthy).then(|| "auth failed".to_string()),
+                ..ChannelHealthReport::default()
+            }
+        }
+    }
+
+    /// Never completes its probe; doctor must cut it off individually.
+    struct HangingProbeChannel;
+
+    #[async_trait::async_trait]
+    impl Channel for HangingProbeChannel {
+        fn name(&self) -> &str {
+            "hanging-probe"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> bool {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn probe_channels_reports_identity_latency_and_detail() {
+        let channels = vec![
+            ConfiguredChannel {
+                display_name: "Telegram",
+                channel: Arc::new(DetailedHealthChannel {
+                    healthy: true,
+                    identity: Some("@doctor_bot"),
+                }),
+            },
+            ConfiguredChannel {
+                display_name: "Slack",
+                channel: Arc::new(DetailedHealthChannel {
+                    healthy: false,
+                    identity: None,
+                }),
+            },
+        ];
+
+        let outcomes = probe_channels(channels, Duration::from_secs(5)).await;
+
+        assert_eq!(outcomes[0].channel, "Telegram");
+        assert_eq!(outcomes[0].status, "healthy");
+        assert_eq!(outcomes[0].latency_ms, 42);
+        assert_eq!(outcomes[0].identity.as_deref(), Some("@doctor_bot"));
+        assert_eq!(outcomes[1].channel, "Slack");
+        assert_eq!(outcomes[1].status, "unhealthy");
+        assert_eq!(outcomes[1].detail.as_deref(), Some("auth failed"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn probe_channels_times_out_hanging_probes_concurrently() {
+        let channels = vec![
+            ConfiguredChannel {
+                display_name: "Matrix",
+                channel: Arc::new(HangingProbeChannel),
+            },
+            ConfiguredChannel {
+                display_name: "IRC",
+                channel: Arc::new(HangingProbeChannel),
+            },
+        ];
+
+        let started = tokio::time::Instant::now();
+        let outcomes = probe_channels(channels, Duration::from_secs(10)).await;
+
+        // Two hung probes finish in one timeout window, not two.
+        assert_eq!(started.elapsed(), Duration::from_secs(10));
+        assert!(outcomes.iter().all(|o| o.status == "timeout"));
+        assert!(outcomes[0]
+            .detail
+            .as_deref()
+            .unwrap()
+            .contains("timed out after 10s"));
+    }
+
+    #[test]
+    fn probe_outcome_json_omits_empty_fields() {
+        let outcome = ChannelProbeOutcome {
+            channel: "Telegram",
+            status: "healthy",
+            latency_ms: 12,
+            identity: Some("@doctor_bot".into()),
+            auth_expiry: None,
+            detail: None,
+        };
+
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["channel"], "Telegram");
+        assert_eq!(json["latency_ms"], 12);
+        assert_eq!(json["identity"], "@doctor_bot");
+        assert!(json.get("auth_expiry").is_none());
+        assert!(json.get("detail").is_none());
+    }
+
+    #[test]
+    fn default_announce_target_resolves_per_channel() {
+        let mut config = crate::config::Config::default();
+        config.channels_config.telegram = Some(
+            serde_json::from_value(serde_json::json!({
+                "bot_token": "t",
+                "allowed_users": ["42", "43"]
+            }))
+            .unwrap(),
+        );
+        config.channels_config.discord = Some(
+            serde_json::from_value(serde_json::json!({
+                "bot_token": "d",
+                "broadcast_channel_id": "999"
+            }))
+            .unwrap(),
+        );
+        config.channels_config.slack = Some(
+            serde_json::from_value(serde_json::json!({
+                "bot_token": "s",
+                "channel_id": "*",
+                "channel_ids": ["C1", "C2"]
+            }))
+            .unwrap(),
+        );
+
+        assert_eq!(
+            default_announce_target(&config, "telegram").as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            default_announce_target(&config, "discord").as_deref(),
+            Some("999")
+        );
+        assert_eq!(
+            default_announce_target(&config, "slack").as_deref(),
+            Some("C1")
+        );
+        assert_eq!(default_announce_target(&config, "mattermost"), None);
+    }
+
+    #[tokio::test]
+    async fn broadcast_reports_unconfigured_only_filter_entries() {
+        let config = crate::config::Config::default();
+        let report = broadcast(&config, "hello", Some(&["telegram".to_string()])).await;
+
+        assert_eq!(report.sent(), 0);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.outcomes[0].error.as_deref(), Some("not configured"));
+    }
+
     struct AlwaysFailChannel {
         name: &'static str,
         calls: Arc<AtomicUsize>,
@@ -11935,6 +15599,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -11950,7 +15616,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12035,6 +15705,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -12049,7 +15721,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12067,7 +15743,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12163,6 +15843,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 50000,
             context_token_budget: 128_000,
             debouncer: Arc::new(debounce::MessageDebouncer::new(std::time::Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(std::time::Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
             media_pipeline: crate::config::MediaPipelineConfig::default(),
             transcription_config: crate::config::TranscriptionConfig::default(),
         });
@@ -12178,7 +15860,11 @@ This is an example JSON object for profile settings."#;
                 timestamp: 1,
                 thread_ts: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12195,7 +15881,11 @@ This is an example JSON object for profile settings."#;
                 timestamp: 2,
                 thread_ts: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12283,6 +15973,7 @@ This is an example JSON object for profile settings."#;
             provider: "vision-provider".into(),
             model: "gpt-4-vision".into(),
             api_key: None,
+            openrouter_routing: None,
         }];
 
         let runtime_ctx = Arc::new(ChannelRuntimeContext {
@@ -12342,6 +16033,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -12356,7 +16049,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12407,6 +16104,7 @@ This is an example JSON object for profile settings."#;
             provider: "vision-provider".into(),
             model: "gpt-4-vision".into(),
             api_key: None,
+            openrouter_routing: None,
         }];
 
         let runtime_ctx = Arc::new(ChannelRuntimeContext {
@@ -12466,6 +16164,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -12480,7 +16180,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12523,6 +16227,7 @@ This is an example JSON object for profile settings."#;
             provider: "vision-provider".into(),
             model: "gpt-4-vision".into(),
             api_key: None,
+            openrouter_routing: None,
         }];
 
         let runtime_ctx = Arc::new(ChannelRuntimeContext {
@@ -12582,6 +16287,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -12596,7 +16303,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12652,12 +16363,14 @@ This is an example JSON object for profile settings."#;
                 provider: "fast-provider".into(),
                 model: "fast-model".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
             crate::config::ModelRouteConfig {
                 hint: "code".into(),
                 provider: "code-provider".into(),
                 model: "code-model".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
         ];
 
@@ -12718,6 +16431,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         process_channel_message(
@@ -12732,7 +16447,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             },
             CancellationToken::new(),
         )
@@ -12777,6 +16496,9 @@ This is an example JSON object for profile settings."#;
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
@@ -12831,18 +16553,21 @@ This is an example JSON object for profile settings."#;
                 provider: "gemini".into(),
                 model: "gemini-3-flash".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
             crate::config::ModelRouteConfig {
                 hint: "pro".into(),
                 provider: "gemini".into(),
                 model: "gemini-3-pro".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
             crate::config::ModelRouteConfig {
                 hint: "codex".into(),
                 provider: "openai".into(),
                 model: "gpt-5".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
         ];
         let mut fallbacks = HashMap::new();
@@ -12864,12 +16589,14 @@ This is an example JSON object for profile settings."#;
                 provider: "gemini".into(),
                 model: "gemini-3-flash".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
             crate::config::ModelRouteConfig {
                 hint: "pro".into(),
                 provider: "gemini".into(),
                 model: "gemini-3-pro".into(),
                 api_key: None,
+                openrouter_routing: None,
             },
         ];
         let mut fallbacks = HashMap::new();
@@ -13010,7 +16737,11 @@ This is an example JSON object for profile settings."#;
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         assert_eq!(interruption_scope_key(&msg), "matrix_room_alice");
     }
@@ -13027,7 +16758,11 @@ This is an example JSON object for profile settings."#;
             thread_ts: Some("$thread1".into()),
             reply_to_message_id: None,
             interruption_scope_id: Some("$thread1".into()),
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         assert_eq!(interruption_scope_key(&msg), "matrix_room_alice_$thread1");
     }
@@ -13036,6 +16771,9 @@ This is an example JSON object for profile settings."#;
     fn interruption_scope_key_thread_ts_alone_does_not_affect_key() {
         // thread_ts used for reply anchoring should not bleed into scope key
         let msg = traits::ChannelMessage {
+            is_edit: false,
+            is_group: false,
+            payload: None,
             id: "1".into(),
             sender: "alice".into(),
             reply_target: "C123".into(),
@@ -13046,6 +16784,7 @@ This is an example JSON object for profile settings."#;
             reply_to_message_id: None,
             interruption_scope_id: None, // but NOT a thread reply
             attachments: vec![],
+            metadata: None,
         };
         assert_eq!(interruption_scope_key(&msg), "slack_C123_alice");
     }
@@ -13117,6 +16856,8 @@ This is an example JSON object for profile settings."#;
             max_tool_result_chars: 0,
             context_token_budget: 0,
             debouncer: Arc::new(debounce::MessageDebouncer::new(Duration::ZERO)),
+            message_dedup: Arc::new(dedup::MessageDeduper::new(Duration::ZERO)),
+            respond_policies: Arc::new(HashMap::new()),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -13133,7 +16874,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: Some("1741234567.100001".to_string()),
                 reply_to_message_id: None,
                 interruption_scope_id: Some("1741234567.100001".to_string()),
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -13148,7 +16893,11 @@ This is an example JSON object for profile settings."#;
                 thread_ts: Some("1741234567.200002".to_string()),
                 reply_to_message_id: None,
                 interruption_scope_id: Some("1741234567.200002".to_string()),
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .unwrap();
@@ -13562,6 +17311,106 @@ This is an example JSON object for profile settings."#;
         *GLOBAL_ROUTES_FILE.lock().unwrap() = None;
     }
 
+    #[test]
+    fn prune_stale_route_overrides_drops_unknown_providers() {
+        let mut map = HashMap::new();
+        map.insert(
+            "telegram_chat-1_alice".to_string(),
+            ChannelRouteSelection {
+                provider: "openrouter".to_string(),
+                model: "kept-model".to_string(),
+                api_key: None,
+                pi_mode: false,
+            },
+        );
+        map.insert(
+            "telegram_chat-2_bob".to_string(),
+            ChannelRouteSelection {
+                provider: "retired-provider".to_string(),
+                model: "gone-model".to_string(),
+                api_key: None,
+                pi_mode: false,
+            },
+        );
+
+        let pruned = prune_stale_route_overrides(&mut map);
+
+        assert_eq!(pruned, 1);
+        assert!(map.contains_key("telegram_chat-1_alice"));
+        assert!(!map.contains_key("telegram_chat-2_bob"));
+    }
+
+    #[test]
+    fn route_overrides_round_trip_and_reset() {
+        // This test mutates GLOBAL_ROUTES_FILE and GLOBAL_ROUTE_OVERRIDES,
+        // so all assertions live in one test to avoid races with parallel
+        // tests.
+
+        let tmp = TempDir::new().unwrap();
+        let route_key = "telegram_chat-7_carol".to_string();
+        let selection = ChannelRouteSelection {
+            provider: "openrouter".to_string(),
+            model: "persisted-model".to_string(),
+            api_key: None,
+            pi_mode: false,
+        };
+
+        // --- Part 1: save then reload must round-trip the entry ---
+        let mut on_disk = HashMap::new();
+        on_disk.insert(route_key.clone(), selection.clone());
+        // Add a stale entry that must be dropped at load.
+        on_disk.insert(
+            "telegram_chat-8_dave".to_string(),
+            ChannelRouteSelection {
+                provider: "retired-provider".to_string(),
+                model: "gone-model".to_string(),
+                api_key: None,
+                pi_mode: false,
+            },
+        );
+        std::fs::write(
+            tmp.path().join("routes.json"),
+            serde_json::to_string_pretty(&on_disk).unwrap(),
+        )
+        .unwrap();
+
+        init_route_overrides(tmp.path());
+
+        {
+            let global = global_route_overrides();
+            let routes = global.lock().unwrap_or_else(|e| e.into_inner());
+            assert_eq!(
+                routes.get(&route_key).map(|r| r.model.as_str()),
+                Some("persisted-model")
+            );
+            assert!(!routes.contains_key("telegram_chat-8_dave"));
+        }
+
+        // The pruned map must have been written back without the stale entry.
+        let text = std::fs::read_to_string(tmp.path().join("routes.json")).unwrap();
+        let reloaded: HashMap<String, ChannelRouteSelection> = serde_json::from_str(&text).unwrap();
+        assert_eq!(reloaded.len(), 1);
+
+        // --- Part 2: /model reset path deletes the persisted entry ---
+        clear_route_selection(&route_key);
+
+        {
+            let global = global_route_overrides();
+            let routes = global.lock().unwrap_or_else(|e| e.into_inner());
+            assert!(!routes.contains_key(&route_key));
+        }
+        let text = std::fs::read_to_string(tmp.path().join("routes.json")).unwrap();
+        let reloaded: HashMap<String, ChannelRouteSelection> = serde_json::from_str(&text).unwrap();
+        assert!(reloaded.is_empty());
+
+        // Cleanup: reset globals so other tests are unaffected.
+        *GLOBAL_ROUTES_FILE.lock().unwrap() = None;
+        global_route_overrides()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
     // ── Pi bypass unit tests ──────────────────────────────────────────
 
     #[test]
@@ -251,7 +251,11 @@ impl NextcloudTalkChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
@@ -365,7 +369,11 @@ impl NextcloudTalkChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
@@ -1,11 +1,21 @@
+use crate::channels::dedup::MessageDeduper;
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use nostr_sdk::prelude::*;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// How long a seen event id suppresses copies of the same event arriving
+/// from other relays in the pool.
+const EVENT_DEDUP_TTL: Duration = Duration::from_secs(600);
+
+/// Consecutive failed publishes before a relay is forced through a
+/// disconnect/reconnect cycle.
+const RELAY_FAILURE_THRESHOLD: u32 = 3;
+
 /// Protocol used by a sender, tracked so replies use the same protocol.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum NostrProtocol {
@@ -47,14 +57,60 @@ impl AllowList {
     }
 }
 
-/// Nostr channel supporting NIP-04 (legacy) and NIP-17 (gift-wrapped) private messages.
-/// Replies use the same protocol the sender used. Unsolicited sends default to NIP-17.
+/// Consecutive publish failures per relay, so a relay that keeps rejecting
+/// events can be rotated (reconnected) instead of silently dropping traffic.
+struct RelayHealth {
+    failures: std::sync::Mutex<HashMap<String, u32>>,
+}
+
+impl RelayHealth {
+    fn new() -> Self {
+        Self {
+            failures: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a failed publish. Returns `true` when the relay has crossed
+    /// [`RELAY_FAILURE_THRESHOLD`] and should be reconnected; the counter
+    /// resets so another rotation requires a fresh run of failures.
+    fn note_failure(&self, relay: &str) -> bool {
+        let mut map = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let count = map.entry(relay.to_string()).or_insert(0);
+        *count += 1;
+        if *count >= RELAY_FAILURE_THRESHOLD {
+            map.remove(relay);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// A successful publish clears the relay's failure streak.
+    fn note_success(&self, relay: &str) {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(relay);
+    }
+}
+
+/// Nostr channel supporting NIP-04 (legacy) and NIP-17 (gift-wrapped, NIP-44
+/// encrypted) private messages. Replies use the same protocol the sender used.
+/// Unsolicited sends default to NIP-17.
+///
+/// Events are published to and received from every configured relay; inbound
+/// copies of the same event are deduplicated by event id, and a relay that
+/// repeatedly fails to accept publishes is reconnected. The private key is
+/// parsed once at construction and never logged.
 pub struct NostrChannel {
     client: Client,
     public_key: PublicKey,
     allowed: AllowList,
     /// Tracks last-seen protocol per sender pubkey so replies match.
     sender_protocols: Arc<RwLock<HashMap<PublicKey, NostrProtocol>>>,
+    /// Drops copies of an event re-broadcast by multiple relays.
+    deduper: MessageDeduper,
+    relay_health: RelayHealth,
 }
 
 impl NostrChannel {
@@ -84,8 +140,33 @@ impl NostrChannel {
             public_key,
             allowed,
             sender_protocols: Arc::new(RwLock::new(HashMap::new())),
+            deduper: MessageDeduper::new(EVENT_DEDUP_TTL),
+            relay_health: RelayHealth::new(),
         })
     }
+
+    /// Record per-relay publish outcomes and rotate any relay that has hit
+    /// [`RELAY_FAILURE_THRESHOLD`] consecutive failures.
+    async fn note_send_outcome(&self, output: &Output<EventId>) {
+        for url in &output.success {
+            self.relay_health.note_success(url.as_str());
+        }
+        for (url, error) in &output.failed {
+            if self.relay_health.note_failure(url.as_str()) {
+                tracing::warn!(
+                    "Nostr relay {url} failed {RELAY_FAILURE_THRESHOLD} consecutive publishes \
+                     (last error: {error}), reconnecting"
+                );
+                if let Err(e) = self.client.disconnect_relay(url.clone()).await {
+                    tracing::warn!("Failed to disconnect Nostr relay {url}: {e}");
+                    continue;
+                }
+                if let Err(e) = self.client.connect_relay(url.clone()).await {
+                    tracing::warn!("Failed to reconnect Nostr relay {url}: {e}");
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -106,11 +187,13 @@ impl Channel for NostrChannel {
 
         match protocol {
             NostrProtocol::Nip17 => {
-                // NIP-17: gift-wrapped private message
-                self.client
+                // NIP-17: gift-wrapped private message (NIP-44 encrypted)
+                let output = self
+                    .client
                     .send_private_msg(recipient, &message.content, None)
                     .await
                     .context("Failed to send NIP-17 message")?;
+                self.note_send_outcome(&output).await;
                 tracing::debug!(
                     "Sent NIP-17 message to {}",
                     recipient.to_bech32().unwrap_or_default()
@@ -125,10 +208,12 @@ impl Channel for NostrChannel {
                     .context("NIP-04 encryption failed")?;
                 let builder = EventBuilder::new(Kind::EncryptedDirectMessage, encrypted)
                     .tag(Tag::public_key(recipient));
-                self.client
+                let output = self
+                    .client
                     .send_event_builder(builder)
                     .await
                     .context("Failed to send NIP-04 message")?;
+                self.note_send_outcome(&output).await;
                 tracing::debug!(
                     "Sent NIP-04 message to {}",
                     recipient.to_bech32().unwrap_or_default()
@@ -174,6 +259,11 @@ impl Channel for NostrChannel {
 
             match notification {
                 RelayPoolNotification::Event { event, .. } => {
+                    // Every relay that carries an event surfaces its own copy;
+                    // drop re-broadcasts by event id before any decryption work.
+                    if !self.deduper.record_if_new("nostr", &event.id.to_hex()) {
+                        continue;
+                    }
                     let result = match event.kind {
                         Kind::EncryptedDirectMessage => {
                             // NIP-04: created_at is the real timestamp (no jitter)
@@ -256,6 +346,10 @@ impl Channel for NostrChannel {
                             reply_to_message_id: None,
                             interruption_scope_id: None,
                             attachments: vec![],
+                            metadata: None,
+                            is_edit: false,
+                            is_group: false,
+                            payload: None,
                         };
                         if tx.send(msg).await.is_err() {
                             tracing::info!("Nostr listener: message bus closed, stopping");
@@ -374,6 +468,66 @@ mod tests {
         assert_eq!(map.get(&pk), None);
     }
 
+    #[tokio::test]
+    async fn nip44_envelope_round_trip() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+
+        let ciphertext = alice
+            .nip44_encrypt(&bob.public_key(), "sealed for bob")
+            .await
+            .unwrap();
+        assert_ne!(ciphertext, "sealed for bob");
+
+        let plaintext = bob
+            .nip44_decrypt(&alice.public_key(), &ciphertext)
+            .await
+            .unwrap();
+        assert_eq!(plaintext, "sealed for bob");
+    }
+
+    #[tokio::test]
+    async fn duplicate_events_across_relays_are_deduped() {
+        let keys = Keys::generate();
+        let ch = NostrChannel::new(&keys.secret_key().to_secret_hex(), vec![], &[])
+            .await
+            .unwrap();
+
+        let event_id = EventId::all_zeros();
+        assert!(ch.deduper.record_if_new("nostr", &event_id.to_hex()));
+        assert!(
+            !ch.deduper.record_if_new("nostr", &event_id.to_hex()),
+            "same event from a second relay must be dropped"
+        );
+
+        // A different event id still passes.
+        let other = EventId::from_byte_array([1u8; 32]);
+        assert!(ch.deduper.record_if_new("nostr", &other.to_hex()));
+    }
+
+    #[test]
+    fn relay_failure_threshold_triggers_rotation_and_resets() {
+        let health = RelayHealth::new();
+        assert!(!health.note_failure("wss://relay.damus.io"));
+        assert!(!health.note_failure("wss://relay.damus.io"));
+        assert!(
+            health.note_failure("wss://relay.damus.io"),
+            "third consecutive failure rotates the relay"
+        );
+        // The counter resets after a rotation.
+        assert!(!health.note_failure("wss://relay.damus.io"));
+    }
+
+    #[test]
+    fn relay_success_resets_failure_streak() {
+        let health = RelayHealth::new();
+        health.note_failure("wss://nos.lol");
+        health.note_failure("wss://nos.lol");
+        health.note_success("wss://nos.lol");
+        assert!(!health.note_failure("wss://nos.lol"));
+        assert!(!health.note_failure("wss://nos.lol"));
+    }
+
     #[tokio::test]
     async fn sender_protocol_tracks_updates() {
         let keys = Keys::generate();
@@ -362,7 +362,11 @@ impl Channel for NotionChannel {
                                 thread_ts: None,
                                 reply_to_message_id: None,
                                 interruption_scope_id: None,
+                                is_edit: false,
+                                is_group: false,
+                                payload: None,
                                 attachments: vec![],
+                                metadata: None,
                             })
                             .await
                             .is_err()
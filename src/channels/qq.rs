@@ -292,7 +292,11 @@ fn build_channel_message(
         thread_ts: (!msg_id.is_empty()).then(|| msg_id.to_string()),
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     }
 }
 
@@ -1419,7 +1423,11 @@ impl Channel for QQChannel {
                                 thread_ts: None,
                                 reply_to_message_id: None,
                                 interruption_scope_id: None,
+                                is_edit: false,
+                                is_group: false,
+                                payload: None,
                     attachments: vec![],
+                    metadata: None,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -1461,7 +1469,11 @@ impl Channel for QQChannel {
                                 thread_ts: None,
                                 reply_to_message_id: None,
                                 interruption_scope_id: None,
+                                is_edit: false,
+                                is_group: false,
+                                payload: None,
                     attachments: vec![],
+                    metadata: None,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
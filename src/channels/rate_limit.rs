@@ -0,0 +1,786 @@
+//! Outbound rate limiting for channels.
+//!
+//! Bulk memory recalls or cron announcements can fire a dozen messages at
+//! once, and platforms like Telegram respond with 429s. [`RateLimitedChannel`]
+//! wraps any [`Channel`] with a token-bucket limiter around `send`, draft
+//! updates, and reaction calls. Excess calls queue (in FIFO order per
+//! recipient) instead of being dropped, and 429 responses back off the
+//! specific bucket honoring `Retry-After` when the platform provides one.
+//!
+//! Built-in platform defaults live in [`RateLimitPolicy::for_channel`];
+//! operators can override them per channel via
+//! `[channels_config.rate_limits.<channel>]`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use tokio::time::Instant;
+
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::RateLimitSettings;
+
+/// Cap on distinct per-recipient buckets tracked at once. When exceeded the
+/// map is cleared wholesale (buckets restart full), which only matters for
+/// channels fanning out to thousands of recipients.
+const MAX_TRACKED_TARGETS: usize = 1024;
+
+/// Backoff applied on a 429 when the platform doesn't say how long to wait.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Attempts per call: the initial send plus retries after 429 backoff.
+const MAX_RATE_LIMIT_ATTEMPTS: usize = 3;
+
+/// A single token bucket's shape: capacity and refill rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    /// Maximum tokens (burst size).
+    pub burst: u32,
+    /// Tokens added per second.
+    pub refill_per_sec: f64,
+}
+
+/// Rate limit policy for one channel: an optional channel-wide bucket and an
+/// optional per-recipient bucket. Both `None` means unlimited (no wrapping).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RateLimitPolicy {
+    /// Channel-wide bucket shared by all recipients.
+    pub global: Option<BucketConfig>,
+    /// Bucket applied per recipient (chat, channel, user).
+    pub per_target: Option<BucketConfig>,
+}
+
+impl RateLimitPolicy {
+    /// Built-in platform defaults: Telegram 30/s global and 1/s per chat,
+    /// Discord 5 per 5s per channel, Slack 1/s per channel. Unknown channels
+    /// are unlimited.
+    #[must_use]
+    pub fn for_channel(name: &str) -> Self {
+        match name {
+            "telegram" => Self {
+                global: Some(BucketConfig {
+                    burst: 30,
+                    refill_per_sec: 30.0,
+                }),
+                per_target: Some(BucketConfig {
+                    burst: 1,
+                    refill_per_sec: 1.0,
+                }),
+            },
+            "discord" => Self {
+                global: None,
+                per_target: Some(BucketConfig {
+                    burst: 5,
+                    refill_per_sec: 1.0,
+                }),
+            },
+            "slack" => Self {
+                global: None,
+                per_target: Some(BucketConfig {
+                    burst: 1,
+                    refill_per_sec: 1.0,
+                }),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Apply operator overrides from `[channels_config.rate_limits.<channel>]`.
+    /// Unset fields keep the built-in defaults; `burst = 0` disables a bucket.
+    #[must_use]
+    pub fn with_settings(mut self, settings: Option<&RateLimitSettings>) -> Self {
+        let Some(settings) = settings else {
+            return self;
+        };
+        apply_override(&mut self.global, settings.burst, settings.per_sec);
+        apply_override(
+            &mut self.per_target,
+            settings.per_target_burst,
+            settings.per_target_per_sec,
+        );
+        self
+    }
+
+    /// `true` when neither bucket is configured — the channel needs no wrapper.
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.global.is_none() && self.per_target.is_none()
+    }
+}
+
+fn apply_override(spec: &mut Option<BucketConfig>, burst: Option<u32>, per_sec: Option<f64>) {
+    if burst.is_none() && per_sec.is_none() {
+        return;
+    }
+    let base = spec.unwrap_or(BucketConfig {
+        burst: 1,
+        refill_per_sec: 1.0,
+    });
+    let merged = BucketConfig {
+        burst: burst.unwrap_or(base.burst),
+        refill_per_sec: per_sec.unwrap_or(base.refill_per_sec),
+    };
+    *spec = if merged.burst == 0 || merged.refill_per_sec <= 0.0 {
+        None
+    } else {
+        Some(merged)
+    };
+}
+
+/// Token bucket state. Starts full so normal traffic is never delayed.
+struct Bucket {
+    config: BucketConfig,
+    tokens: f64,
+    last_refill: Instant,
+    /// Set on a 429: no tokens are handed out until this instant.
+    backoff_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(config: BucketConfig) -> Self {
+        Self {
+            config,
+            tokens: f64::from(config.burst),
+            last_refill: Instant::now(),
+            backoff_until: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.config.refill_per_sec).min(f64::from(self.config.burst));
+        self.last_refill = now;
+    }
+}
+
+/// Waits until the bucket hands out a token. The bucket mutex is held across
+/// the wait — `tokio::sync::Mutex` queues waiters in FIFO order, which is
+/// what preserves send ordering per bucket.
+async fn wait_for_token(bucket: &tokio::sync::Mutex<Bucket>) {
+    let mut b = bucket.lock().await;
+    loop {
+        let now = Instant::now();
+        if let Some(until) = b.backoff_until {
+            if now < until {
+                tokio::time::sleep_until(until).await;
+                continue;
+            }
+            b.backoff_until = None;
+        }
+        b.refill(now);
+        if b.tokens >= 1.0 {
+            b.tokens -= 1.0;
+            return;
+        }
+        let wait = (1.0 - b.tokens) / b.config.refill_per_sec;
+        tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+    }
+}
+
+/// Token-bucket limiter with an optional channel-wide bucket and lazily
+/// created per-recipient buckets.
+pub struct OutboundRateLimiter {
+    policy: RateLimitPolicy,
+    global: Option<tokio::sync::Mutex<Bucket>>,
+    per_target: std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<Bucket>>>>,
+    /// Calls currently queued in (or passing through) `acquire`.
+    queued: AtomicUsize,
+}
+
+impl OutboundRateLimiter {
+    #[must_use]
+    pub fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            global: policy
+                .global
+                .map(|cfg| tokio::sync::Mutex::new(Bucket::new(cfg))),
+            per_target: std::sync::Mutex::new(HashMap::new()),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of calls currently waiting on the limiter — exposed as a
+    /// health metric so operators can see outbound backpressure.
+    #[must_use]
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Wait until both the per-recipient and channel-wide buckets allow one
+    /// send to `target`. Returns immediately for unlimited policies.
+    pub async fn acquire(&self, target: &str) {
+        if self.policy.is_unlimited() {
+            return;
+        }
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        if let Some(bucket) = self.target_bucket(target) {
+            wait_for_token(&bucket).await;
+        }
+        if let Some(ref bucket) = self.global {
+            wait_for_token(bucket).await;
+        }
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Back off the bucket for `target` after a 429: the per-recipient bucket
+    /// when per-recipient limiting is on, otherwise the channel-wide one.
+    pub async fn backoff(&self, target: &str, retry_after: Duration) {
+        let until = Instant::now() + retry_after;
+        if let Some(bucket) = self.target_bucket(target) {
+            bucket.lock().await.backoff_until = Some(until);
+        } else if let Some(ref bucket) = self.global {
+            bucket.lock().await.backoff_until = Some(until);
+        }
+    }
+
+    fn target_bucket(&self, target: &str) -> Option<Arc<tokio::sync::Mutex<Bucket>>> {
+        let cfg = self.policy.per_target?;
+        let mut map = self
+            .per_target
+            .lock()
+            .expect("rate limiter target map poisoned");
+        if map.len() >= MAX_TRACKED_TARGETS && !map.contains_key(target) {
+            map.clear();
+        }
+        Some(Arc::clone(map.entry(target.to_string()).or_insert_with(
+            || Arc::new(tokio::sync::Mutex::new(Bucket::new(cfg))),
+        )))
+    }
+}
+
+/// `true` when an error from a channel's HTTP call looks like a rate limit
+/// rejection. Channels surface platform errors as anyhow strings, so this is
+/// a text match on the status code / canonical reason phrase.
+fn is_rate_limit_error(text: &str) -> bool {
+    text.contains("429") || text.to_ascii_lowercase().contains("too many requests")
+}
+
+/// Extract a `Retry-After` duration from an error string, e.g. Telegram's
+/// "Too Many Requests: retry after 5" or a raw "Retry-After: 30" header echo.
+fn parse_retry_after(text: &str) -> Option<Duration> {
+    let lower = text.to_ascii_lowercase();
+    for marker in ["retry after", "retry_after", "retry-after"] {
+        let Some(pos) = lower.find(marker) else {
+            continue;
+        };
+        let rest = lower[pos + marker.len()..].trim_start_matches([' ', ':', '=']);
+        let digits: String = rest
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        if let Ok(secs) = digits.parse::<f64>() {
+            if secs > 0.0 {
+                return Some(Duration::from_secs_f64(secs));
+            }
+        }
+    }
+    None
+}
+
+/// Wrap a channel in a [`RateLimitedChannel`] when its effective policy has
+/// any buckets configured; unlimited channels are returned unchanged.
+pub fn wrap_if_limited(
+    channel_name: &str,
+    settings: Option<&RateLimitSettings>,
+    inner: Arc<dyn Channel>,
+) -> Arc<dyn Channel> {
+    let policy = RateLimitPolicy::for_channel(channel_name).with_settings(settings);
+    if policy.is_unlimited() {
+        inner
+    } else {
+        Arc::new(RateLimitedChannel::new(inner, policy))
+    }
+}
+
+/// A [`Channel`] wrapper that routes outbound calls through an
+/// [`OutboundRateLimiter`]. Inbound (`listen`) and capability queries pass
+/// straight through to the wrapped channel.
+pub struct RateLimitedChannel {
+    inner: Arc<dyn Channel>,
+    limiter: Arc<OutboundRateLimiter>,
+}
+
+impl RateLimitedChannel {
+    #[must_use]
+    pub fn new(inner: Arc<dyn Channel>, policy: RateLimitPolicy) -> Self {
+        Self {
+            inner,
+            limiter: Arc::new(OutboundRateLimiter::new(policy)),
+        }
+    }
+
+    /// Handle to the limiter for health/metrics (queue depth).
+    #[must_use]
+    pub fn limiter(&self) -> Arc<OutboundRateLimiter> {
+        Arc::clone(&self.limiter)
+    }
+
+    /// Acquire a token for `target`, run the call, and on a 429 back off the
+    /// bucket (honoring `Retry-After` when present) and retry.
+    async fn with_limit<'a, T, F>(&self, target: &str, mut op: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> BoxFuture<'a, anyhow::Result<T>> + Send,
+    {
+        let mut last_err = None;
+        for attempt in 1..=MAX_RATE_LIMIT_ATTEMPTS {
+            self.limiter.acquire(target).await;
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    let text = format!("{e:#}");
+                    if attempt < MAX_RATE_LIMIT_ATTEMPTS && is_rate_limit_error(&text) {
+                        let delay = parse_retry_after(&text).unwrap_or(DEFAULT_RETRY_AFTER);
+                        tracing::warn!(
+                            channel = %self.inner.name(),
+                            %target,
+                            delay_secs = delay.as_secs_f64(),
+                            "Rate limited by platform; backing off"
+                        );
+                        self.limiter.backoff(target, delay).await;
+                        last_err = Some(e);
+                    } else {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Err(last_err.expect("retry loop exits early unless an error was stored"))
+    }
+}
+
+#[async_trait]
+impl Channel for RateLimitedChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn outbound_message_limit(&self) -> usize {
+        self.inner.outbound_message_limit()
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.with_limit(&message.recipient, || Box::pin(self.inner.send(message)))
+            .await
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        self.inner.listen(tx).await
+    }
+
+    fn delivery_instructions(&self) -> Option<&str> {
+        self.inner.delivery_instructions()
+    }
+
+    fn format_incoming_content(&self, msg: &ChannelMessage) -> String {
+        self.inner.format_incoming_content(msg)
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    async fn health_check_detailed(&self) -> super::traits::ChannelHealthReport {
+        self.inner.health_check_detailed().await
+    }
+
+    fn group_respond_policy(
+        &self,
+    ) -> Option<std::sync::Arc<super::respond_policy::GroupRespondPolicy>> {
+        self.inner.group_respond_policy()
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.inner.start_typing(recipient).await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.inner.stop_typing(recipient).await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.inner.supports_draft_updates()
+    }
+
+    fn supports_multi_message_streaming(&self) -> bool {
+        self.inner.supports_multi_message_streaming()
+    }
+
+    fn multi_message_delay_ms(&self) -> u64 {
+        self.inner.multi_message_delay_ms()
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        self.with_limit(&message.recipient, || {
+            Box::pin(self.inner.send_draft(message))
+        })
+        .await
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.with_limit(recipient, || {
+            Box::pin(self.inner.update_draft(recipient, message_id, text))
+        })
+        .await
+    }
+
+    async fn delete_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.with_limit(recipient, || {
+            Box::pin(self.inner.delete_draft(recipient, message_id))
+        })
+        .await
+    }
+
+    async fn update_draft_progress(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.with_limit(recipient, || {
+            Box::pin(
+                self.inner
+                    .update_draft_progress(recipient, message_id, text),
+            )
+        })
+        .await
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.with_limit(recipient, || {
+            Box::pin(self.inner.finalize_draft(recipient, message_id, text))
+        })
+        .await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.with_limit(recipient, || {
+            Box::pin(self.inner.cancel_draft(recipient, message_id))
+        })
+        .await
+    }
+
+    async fn add_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        self.with_limit(channel_id, || {
+            Box::pin(self.inner.add_reaction(channel_id, message_id, emoji))
+        })
+        .await
+    }
+
+    async fn remove_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        self.with_limit(channel_id, || {
+            Box::pin(self.inner.remove_reaction(channel_id, message_id, emoji))
+        })
+        .await
+    }
+
+    async fn pin_message(&self, channel_id: &str, message_id: &str) -> anyhow::Result<()> {
+        self.inner.pin_message(channel_id, message_id).await
+    }
+
+    async fn unpin_message(&self, channel_id: &str, message_id: &str) -> anyhow::Result<()> {
+        self.inner.unpin_message(channel_id, message_id).await
+    }
+
+    async fn redact_message(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .redact_message(channel_id, message_id, reason)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RecordingChannel {
+        sent: tokio::sync::Mutex<Vec<String>>,
+        /// Errors to return (in order) before succeeding.
+        fail_with: tokio::sync::Mutex<Vec<String>>,
+        attempts: AtomicUsize,
+    }
+
+    impl RecordingChannel {
+        fn new() -> Self {
+            Self {
+                sent: tokio::sync::Mutex::new(Vec::new()),
+                fail_with: tokio::sync::Mutex::new(Vec::new()),
+                attempts: AtomicUsize::new(0),
+            }
+        }
+
+        fn failing_first(error: &str) -> Self {
+            let ch = Self::new();
+            ch.fail_with.try_lock().unwrap().push(error.to_string());
+            ch
+        }
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let mut failures = self.fail_with.lock().await;
+            if !failures.is_empty() {
+                return Err(anyhow::anyhow!(failures.remove(0)));
+            }
+            drop(failures);
+            self.sent.lock().await.push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn per_target_policy(burst: u32, refill_per_sec: f64) -> RateLimitPolicy {
+        RateLimitPolicy {
+            global: None,
+            per_target: Some(BucketConfig {
+                burst,
+                refill_per_sec,
+            }),
+        }
+    }
+
+    #[test]
+    fn platform_defaults_match_documented_limits() {
+        let tg = RateLimitPolicy::for_channel("telegram");
+        assert_eq!(
+            tg.global,
+            Some(BucketConfig {
+                burst: 30,
+                refill_per_sec: 30.0
+            })
+        );
+        assert_eq!(
+            tg.per_target,
+            Some(BucketConfig {
+                burst: 1,
+                refill_per_sec: 1.0
+            })
+        );
+
+        let dc = RateLimitPolicy::for_channel("discord");
+        assert_eq!(dc.global, None);
+        assert_eq!(
+            dc.per_target,
+            Some(BucketConfig {
+                burst: 5,
+                refill_per_sec: 1.0
+            })
+        );
+
+        assert!(RateLimitPolicy::for_channel("irc").is_unlimited());
+    }
+
+    #[test]
+    fn settings_override_defaults_and_zero_burst_disables() {
+        let settings = RateLimitSettings {
+            burst: Some(0),
+            per_sec: None,
+            per_target_burst: Some(3),
+            per_target_per_sec: Some(0.5),
+        };
+        let policy = RateLimitPolicy::for_channel("telegram").with_settings(Some(&settings));
+        assert_eq!(policy.global, None);
+        assert_eq!(
+            policy.per_target,
+            Some(BucketConfig {
+                burst: 3,
+                refill_per_sec: 0.5
+            })
+        );
+    }
+
+    #[test]
+    fn settings_can_enable_limits_on_unlimited_channels() {
+        let settings = RateLimitSettings {
+            burst: None,
+            per_sec: None,
+            per_target_burst: Some(2),
+            per_target_per_sec: Some(1.0),
+        };
+        let policy = RateLimitPolicy::for_channel("irc").with_settings(Some(&settings));
+        assert_eq!(
+            policy.per_target,
+            Some(BucketConfig {
+                burst: 2,
+                refill_per_sec: 1.0
+            })
+        );
+    }
+
+    #[test]
+    fn parse_retry_after_handles_common_formats() {
+        assert_eq!(
+            parse_retry_after("Telegram API error 429: Too Many Requests: retry after 5"),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            parse_retry_after("rate limited, Retry-After: 30"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("{\"retry_after\": 2.5}"),
+            Some(Duration::from_secs_f64(2.5))
+        );
+        assert_eq!(parse_retry_after("500 Internal Server Error"), None);
+    }
+
+    #[test]
+    fn rate_limit_error_detection() {
+        assert!(is_rate_limit_error("HTTP 429"));
+        assert!(is_rate_limit_error("Slack API error: too many requests"));
+        assert!(!is_rate_limit_error("HTTP 502 Bad Gateway"));
+    }
+
+    #[test]
+    fn wrap_if_limited_leaves_unlimited_channels_unwrapped() {
+        let inner: Arc<dyn Channel> = Arc::new(RecordingChannel::new());
+        let wrapped = wrap_if_limited("irc", None, Arc::clone(&inner));
+        assert!(Arc::ptr_eq(&inner, &wrapped));
+
+        let limited = wrap_if_limited("telegram", None, inner);
+        assert_eq!(limited.name(), "recording");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn queued_sends_preserve_order() {
+        let inner = Arc::new(RecordingChannel::new());
+        let channel = Arc::new(RateLimitedChannel::new(
+            inner.clone(),
+            per_target_policy(1, 1.0),
+        ));
+
+        let mut handles = Vec::new();
+        for i in 1..=3 {
+            let ch = Arc::clone(&channel);
+            handles.push(tokio::spawn(async move {
+                // Stagger arrival so queue order is deterministic.
+                tokio::time::sleep(Duration::from_millis(i)).await;
+                ch.send(&SendMessage::new(format!("msg{i}"), "chat1")).await
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(*inner.sent.lock().await, vec!["msg1", "msg2", "msg3"]);
+        assert_eq!(channel.limiter().queue_depth(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_then_throttle_takes_refill_time() {
+        let inner = Arc::new(RecordingChannel::new());
+        let channel = RateLimitedChannel::new(inner.clone(), per_target_policy(2, 1.0));
+
+        let start = Instant::now();
+        for i in 1..=3 {
+            channel
+                .send(&SendMessage::new(format!("msg{i}"), "chat1"))
+                .await
+                .unwrap();
+        }
+        // Two burst tokens are free; the third waits ~1s for a refill.
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        assert_eq!(inner.sent.lock().await.len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn distinct_targets_do_not_block_each_other() {
+        let inner = Arc::new(RecordingChannel::new());
+        let channel = RateLimitedChannel::new(inner.clone(), per_target_policy(1, 0.1));
+
+        let start = Instant::now();
+        channel.send(&SendMessage::new("a", "chat1")).await.unwrap();
+        channel.send(&SendMessage::new("b", "chat2")).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limited_send_backs_off_and_retries() {
+        let inner = Arc::new(RecordingChannel::failing_first(
+            "Telegram API error 429: Too Many Requests: retry after 5",
+        ));
+        let channel = RateLimitedChannel::new(inner.clone(), per_target_policy(5, 5.0));
+
+        let start = Instant::now();
+        channel
+            .send(&SendMessage::new("hello", "chat1"))
+            .await
+            .unwrap();
+
+        assert_eq!(inner.attempts.load(Ordering::SeqCst), 2);
+        // The retry waited out the platform-provided Retry-After.
+        assert!(start.elapsed() >= Duration::from_secs(5));
+        assert_eq!(*inner.sent.lock().await, vec!["hello"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn non_rate_limit_errors_propagate_without_retry() {
+        let inner = Arc::new(RecordingChannel::failing_first("HTTP 502 Bad Gateway"));
+        let channel = RateLimitedChannel::new(inner.clone(), per_target_policy(5, 5.0));
+
+        let err = channel
+            .send(&SendMessage::new("hello", "chat1"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("502"));
+        assert_eq!(inner.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn queue_depth_reflects_waiting_sends() {
+        let limiter = Arc::new(OutboundRateLimiter::new(per_target_policy(1, 1.0)));
+
+        limiter.acquire("chat1").await;
+        let waiting = Arc::clone(&limiter);
+        let handle = tokio::spawn(async move {
+            waiting.acquire("chat1").await;
+        });
+        // Let the second acquire reach the bucket wait.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(limiter.queue_depth(), 1);
+
+        handle.await.unwrap();
+        assert_eq!(limiter.queue_depth(), 0);
+    }
+}
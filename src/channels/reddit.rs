@@ -227,7 +227,11 @@ impl RedditChannel {
             thread_ts: item.parent_id.clone(),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
     }
 }
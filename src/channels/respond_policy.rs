@@ -0,0 +1,386 @@
+//! Group-chat respond policy shared by the channel listeners.
+//!
+//! In a busy group chat answering every message is noise. Each channel keeps
+//! its own mention *detection* (Telegram `@username` spans, Discord `<@id>`
+//! tags, Slack `<@id>`), but the *decision* — answer everything, only when
+//! mentioned, only on a trigger prefix, or never — lives here so it can be
+//! configured per channel, overridden per group, and flipped live with
+//! `/respondmode`. Direct messages are never filtered.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Default trigger for [`RespondMode::Prefix`] when none is configured.
+pub const DEFAULT_TRIGGER_PREFIX: &str = "!";
+
+/// How the bot decides to respond in a group chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RespondMode {
+    /// Respond to every message.
+    #[default]
+    All,
+    /// Respond only when the bot is @-mentioned.
+    Mention,
+    /// Respond only when the message starts with the trigger prefix.
+    Prefix,
+    /// Never respond; messages are still recorded into memory when
+    /// auto-save is enabled.
+    Silent,
+}
+
+impl RespondMode {
+    /// Strict parse for user input (`/respondmode`). Returns `None` for
+    /// unknown values.
+    pub fn parse_opt(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "all" => Some(Self::All),
+            "mention" => Some(Self::Mention),
+            "prefix" => Some(Self::Prefix),
+            "silent" => Some(Self::Silent),
+            _ => None,
+        }
+    }
+
+    /// Lenient parse for config values: an unknown mode warns and falls
+    /// back to `all` so a typo doesn't silently mute a channel.
+    pub fn parse(raw: &str) -> Self {
+        Self::parse_opt(raw).unwrap_or_else(|| {
+            tracing::warn!("Unknown group respond_mode {raw:?}, using \"all\"");
+            Self::All
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Mention => "mention",
+            Self::Prefix => "prefix",
+            Self::Silent => "silent",
+        }
+    }
+}
+
+/// Outcome of [`GroupRespondPolicy::evaluate`] for one inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RespondDecision {
+    /// Dispatch the message. `require_mention` tells the channel to apply
+    /// its own mention gate (and mention stripping); `strip_trigger` means
+    /// the leading trigger prefix should be removed from the content.
+    Respond {
+        require_mention: bool,
+        strip_trigger: bool,
+    },
+    /// Forward for memory capture only; the dispatcher never replies.
+    RecordOnly,
+    /// Drop the message.
+    Ignore,
+}
+
+impl RespondDecision {
+    /// Shorthand for "process unconditionally" (DMs, exempted paths).
+    pub fn respond() -> Self {
+        Self::Respond {
+            require_mention: false,
+            strip_trigger: false,
+        }
+    }
+}
+
+/// Per-channel respond policy: a default mode, per-group config overrides,
+/// and live overrides set with `/respondmode`.
+pub struct GroupRespondPolicy {
+    default_mode: RespondMode,
+    group_overrides: HashMap<String, RespondMode>,
+    trigger_prefix: String,
+    /// Live `/respondmode` overrides keyed by group id. Checked before the
+    /// config overrides; intentionally reset on daemon restart.
+    runtime_overrides: Mutex<HashMap<String, RespondMode>>,
+}
+
+impl GroupRespondPolicy {
+    pub fn new(
+        default_mode: RespondMode,
+        group_overrides: HashMap<String, RespondMode>,
+        trigger_prefix: Option<String>,
+    ) -> Self {
+        Self {
+            default_mode,
+            group_overrides,
+            trigger_prefix: trigger_prefix.unwrap_or_else(|| DEFAULT_TRIGGER_PREFIX.to_string()),
+            runtime_overrides: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Mapping for channels constructed with the older boolean
+    /// `mention_only` flag.
+    pub fn from_mention_only(mention_only: bool) -> Self {
+        let mode = if mention_only {
+            RespondMode::Mention
+        } else {
+            RespondMode::All
+        };
+        Self::new(mode, HashMap::new(), None)
+    }
+
+    /// Build from the per-channel config fields. An explicit `respond_mode`
+    /// wins over the legacy `mention_only` flag.
+    pub fn from_config(
+        respond_mode: Option<&str>,
+        mention_only: bool,
+        group_respond_modes: &HashMap<String, String>,
+        trigger_prefix: Option<String>,
+    ) -> Self {
+        let default_mode = respond_mode.map_or_else(
+            || {
+                if mention_only {
+                    RespondMode::Mention
+                } else {
+                    RespondMode::All
+                }
+            },
+            RespondMode::parse,
+        );
+        let overrides = group_respond_modes
+            .iter()
+            .map(|(gid, mode)| (gid.clone(), RespondMode::parse(mode)))
+            .collect();
+        Self::new(default_mode, overrides, trigger_prefix)
+    }
+
+    /// Whether any active mode (config or live override) needs the channel
+    /// to resolve its own bot identity for mention detection.
+    pub fn requires_mention_detection(&self) -> bool {
+        self.default_mode == RespondMode::Mention
+            || self
+                .group_overrides
+                .values()
+                .any(|m| *m == RespondMode::Mention)
+            || self
+                .runtime_overrides
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .values()
+                .any(|m| *m == RespondMode::Mention)
+    }
+
+    /// Effective mode for a group: live override, then config override,
+    /// then the channel default.
+    pub fn mode_for(&self, group_id: &str) -> RespondMode {
+        if let Some(mode) = self
+            .runtime_overrides
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(group_id)
+        {
+            return *mode;
+        }
+        self.group_overrides
+            .get(group_id)
+            .copied()
+            .unwrap_or(self.default_mode)
+    }
+
+    /// Set a live override for a group (`/respondmode`).
+    pub fn set_override(&self, group_id: &str, mode: RespondMode) {
+        self.runtime_overrides
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(group_id.to_string(), mode);
+    }
+
+    /// Decide what to do with an inbound message. `group_id` is `None` for
+    /// DMs, which always respond regardless of mode.
+    pub fn evaluate(&self, group_id: Option<&str>, content: &str) -> RespondDecision {
+        let Some(group_id) = group_id else {
+            return RespondDecision::respond();
+        };
+        match self.mode_for(group_id) {
+            RespondMode::All => RespondDecision::respond(),
+            RespondMode::Mention => RespondDecision::Respond {
+                require_mention: true,
+                strip_trigger: false,
+            },
+            RespondMode::Prefix => {
+                if content.trim_start().starts_with(&self.trigger_prefix) {
+                    RespondDecision::Respond {
+                        require_mention: false,
+                        strip_trigger: true,
+                    }
+                } else {
+                    RespondDecision::Ignore
+                }
+            }
+            RespondMode::Silent => RespondDecision::RecordOnly,
+        }
+    }
+
+    /// Remove the leading trigger prefix from content that matched
+    /// [`RespondMode::Prefix`].
+    pub fn strip_trigger(&self, content: &str) -> String {
+        content
+            .trim_start()
+            .strip_prefix(&self.trigger_prefix)
+            .map_or_else(
+                || content.trim().to_string(),
+                |rest| rest.trim().to_string(),
+            )
+    }
+}
+
+/// Metadata a channel attaches to group messages so the dispatcher can
+/// route `/respondmode` to the right group and honor record-only (silent)
+/// delivery.
+pub fn group_metadata(group_id: &str, record_only: bool) -> serde_json::Value {
+    let mut meta = serde_json::json!({ "group_id": group_id });
+    if record_only {
+        meta["respond"] = serde_json::Value::from("record_only");
+    }
+    meta
+}
+
+/// Whether a message was marked record-only (silent mode) by its channel.
+pub fn is_record_only(metadata: Option<&serde_json::Value>) -> bool {
+    metadata
+        .and_then(|m| m.get("respond"))
+        .and_then(serde_json::Value::as_str)
+        == Some("record_only")
+}
+
+/// The group id a channel attached to a message, if any.
+pub fn message_group_id(metadata: Option<&serde_json::Value>) -> Option<&str> {
+    metadata?.get("group_id")?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_metadata_round_trips_through_accessors() {
+        let meta = group_metadata("-100123", true);
+        assert!(is_record_only(Some(&meta)));
+        assert_eq!(message_group_id(Some(&meta)), Some("-100123"));
+
+        let meta = group_metadata("-100123", false);
+        assert!(!is_record_only(Some(&meta)));
+        assert!(!is_record_only(None));
+        assert_eq!(message_group_id(None), None);
+    }
+
+    #[test]
+    fn parse_recognizes_known_modes() {
+        assert_eq!(
+            RespondMode::parse_opt("mention"),
+            Some(RespondMode::Mention)
+        );
+        assert_eq!(RespondMode::parse_opt("PREFIX"), Some(RespondMode::Prefix));
+        assert_eq!(RespondMode::parse_opt(" all "), Some(RespondMode::All));
+        assert_eq!(RespondMode::parse_opt("silent"), Some(RespondMode::Silent));
+        assert_eq!(RespondMode::parse_opt("shout"), None);
+        // Lenient config parse falls back to All instead of muting the channel.
+        assert_eq!(RespondMode::parse("shout"), RespondMode::All);
+    }
+
+    #[test]
+    fn dms_always_respond_in_every_mode() {
+        for mode in [
+            RespondMode::All,
+            RespondMode::Mention,
+            RespondMode::Prefix,
+            RespondMode::Silent,
+        ] {
+            let policy = GroupRespondPolicy::new(mode, HashMap::new(), None);
+            assert_eq!(
+                policy.evaluate(None, "hello"),
+                RespondDecision::respond(),
+                "DM must bypass {mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn mention_mode_defers_detection_to_the_channel() {
+        let policy = GroupRespondPolicy::from_mention_only(true);
+        assert_eq!(
+            policy.evaluate(Some("-100123"), "hello"),
+            RespondDecision::Respond {
+                require_mention: true,
+                strip_trigger: false,
+            }
+        );
+    }
+
+    #[test]
+    fn prefix_mode_matches_trigger_and_strips_it() {
+        let policy = GroupRespondPolicy::new(RespondMode::Prefix, HashMap::new(), None);
+        assert_eq!(
+            policy.evaluate(Some("g1"), "  !deploy now"),
+            RespondDecision::Respond {
+                require_mention: false,
+                strip_trigger: true,
+            }
+        );
+        assert_eq!(
+            policy.evaluate(Some("g1"), "deploy now"),
+            RespondDecision::Ignore
+        );
+        assert_eq!(policy.strip_trigger("  !deploy now"), "deploy now");
+    }
+
+    #[test]
+    fn custom_trigger_prefix_is_honored() {
+        let policy =
+            GroupRespondPolicy::new(RespondMode::Prefix, HashMap::new(), Some("zc:".to_string()));
+        assert_eq!(
+            policy.evaluate(Some("g1"), "zc: status"),
+            RespondDecision::Respond {
+                require_mention: false,
+                strip_trigger: true,
+            }
+        );
+        assert_eq!(
+            policy.evaluate(Some("g1"), "!status"),
+            RespondDecision::Ignore
+        );
+        assert_eq!(policy.strip_trigger("zc: status"), "status");
+    }
+
+    #[test]
+    fn silent_mode_records_without_replying() {
+        let policy = GroupRespondPolicy::new(RespondMode::Silent, HashMap::new(), None);
+        assert_eq!(
+            policy.evaluate(Some("g1"), "chatter"),
+            RespondDecision::RecordOnly
+        );
+    }
+
+    #[test]
+    fn per_group_config_override_beats_default() {
+        let mut overrides = HashMap::new();
+        overrides.insert("busy".to_string(), RespondMode::Silent);
+        let policy = GroupRespondPolicy::new(RespondMode::All, overrides, None);
+        assert_eq!(policy.mode_for("busy"), RespondMode::Silent);
+        assert_eq!(policy.mode_for("quiet"), RespondMode::All);
+    }
+
+    #[test]
+    fn runtime_override_beats_config_override() {
+        let mut overrides = HashMap::new();
+        overrides.insert("busy".to_string(), RespondMode::Silent);
+        let policy = GroupRespondPolicy::new(RespondMode::All, overrides, None);
+        policy.set_override("busy", RespondMode::Mention);
+        assert_eq!(policy.mode_for("busy"), RespondMode::Mention);
+        // Other groups are unaffected.
+        assert_eq!(policy.mode_for("quiet"), RespondMode::All);
+    }
+
+    #[test]
+    fn from_config_prefers_respond_mode_over_legacy_flag() {
+        let policy = GroupRespondPolicy::from_config(Some("silent"), true, &HashMap::new(), None);
+        assert_eq!(policy.mode_for("g1"), RespondMode::Silent);
+
+        let legacy = GroupRespondPolicy::from_config(None, true, &HashMap::new(), None);
+        assert_eq!(legacy.mode_for("g1"), RespondMode::Mention);
+        assert!(legacy.requires_mention_detection());
+    }
+}
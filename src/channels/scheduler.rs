@@ -0,0 +1,224 @@
+//! Two-tier priority scheduling for the channel dispatch loop.
+//!
+//! When the bounded worker pool is saturated, messages queue here instead of
+//! in arrival order: direct messages and messages from configured VIP senders
+//! ([`ChannelsConfig::vip_senders`](crate::config::ChannelsConfig)) dispatch
+//! first when a worker frees up. Within the high tier, senders take turns
+//! round-robin so one chatty DM user cannot monopolize workers, and a burst
+//! cap guarantees queued group messages keep making progress under sustained
+//! DM load.
+
+use super::traits::ChannelMessage;
+use std::collections::{HashMap, VecDeque};
+
+/// How many consecutive high-tier dispatches may run while normal-tier
+/// messages wait. After a full burst the next dispatch takes from the
+/// normal tier, so group messages are starvation-free.
+const HIGH_TIER_BURST: usize = 4;
+
+/// Dispatch tier of a queued message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessagePriority {
+    /// DMs and VIP senders — dispatched first.
+    High,
+    /// Group chatter — dispatched when the high tier is empty or after a
+    /// full high-tier burst.
+    Normal,
+}
+
+impl MessagePriority {
+    /// Classify a message from fields every channel populates: anything that
+    /// isn't a group message is a DM (high), and VIP senders are high even
+    /// in groups.
+    pub fn classify(msg: &ChannelMessage, vip_senders: &[String]) -> Self {
+        if !msg.is_group || vip_senders.iter().any(|vip| vip == &msg.sender) {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Buffer between the inbound channel receiver and the worker pool.
+///
+/// `push` classifies and enqueues; `pop` picks the next message to dispatch
+/// according to tier, per-sender rotation, and the anti-starvation burst cap.
+pub struct PriorityScheduler {
+    vip_senders: Vec<String>,
+    /// Rotation of high-tier senders that currently have queued messages.
+    high_rotation: VecDeque<String>,
+    high_by_sender: HashMap<String, VecDeque<ChannelMessage>>,
+    normal: VecDeque<ChannelMessage>,
+    /// Consecutive high-tier pops since the last normal-tier pop.
+    high_streak: usize,
+}
+
+impl PriorityScheduler {
+    pub fn new(vip_senders: Vec<String>) -> Self {
+        Self {
+            vip_senders,
+            high_rotation: VecDeque::new(),
+            high_by_sender: HashMap::new(),
+            normal: VecDeque::new(),
+            high_streak: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.high_rotation.is_empty() && self.normal.is_empty()
+    }
+
+    pub fn push(&mut self, msg: ChannelMessage) {
+        match MessagePriority::classify(&msg, &self.vip_senders) {
+            MessagePriority::High => {
+                let queue = self.high_by_sender.entry(msg.sender.clone()).or_default();
+                if queue.is_empty() {
+                    self.high_rotation.push_back(msg.sender.clone());
+                }
+                queue.push_back(msg);
+            }
+            MessagePriority::Normal => self.normal.push_back(msg),
+        }
+    }
+
+    /// Take the next message to dispatch, or `None` when empty.
+    pub fn pop(&mut self) -> Option<ChannelMessage> {
+        let take_normal = self.high_rotation.is_empty()
+            || (!self.normal.is_empty() && self.high_streak >= HIGH_TIER_BURST);
+        if take_normal {
+            if let Some(msg) = self.normal.pop_front() {
+                self.high_streak = 0;
+                return Some(msg);
+            }
+        }
+
+        let sender = self.high_rotation.pop_front()?;
+        let queue = self.high_by_sender.get_mut(&sender)?;
+        let msg = queue.pop_front();
+        if queue.is_empty() {
+            self.high_by_sender.remove(&sender);
+        } else {
+            self.high_rotation.push_back(sender);
+        }
+        self.high_streak += 1;
+        msg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(sender: &str, is_group: bool, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: format!("{sender}_{content}"),
+            sender: sender.to_string(),
+            reply_target: sender.to_string(),
+            content: content.to_string(),
+            channel: "test".to_string(),
+            timestamp: 0,
+            thread_ts: None,
+            reply_to_message_id: None,
+            interruption_scope_id: None,
+            is_edit: false,
+            is_group,
+            payload: None,
+            attachments: vec![],
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn dms_dispatch_before_queued_group_messages() {
+        let mut sched = PriorityScheduler::new(vec![]);
+        sched.push(msg("group", true, "g1"));
+        sched.push(msg("group", true, "g2"));
+        sched.push(msg("alice", false, "dm1"));
+
+        assert_eq!(sched.pop().unwrap().content, "dm1");
+        assert_eq!(sched.pop().unwrap().content, "g1");
+        assert_eq!(sched.pop().unwrap().content, "g2");
+        assert!(sched.pop().is_none());
+        assert!(sched.is_empty());
+    }
+
+    #[test]
+    fn vip_sender_outranks_group_chatter() {
+        let mut sched = PriorityScheduler::new(vec!["boss".to_string()]);
+        sched.push(msg("group", true, "g1"));
+        sched.push(msg("boss", true, "vip1"));
+
+        assert_eq!(sched.pop().unwrap().content, "vip1");
+        assert_eq!(sched.pop().unwrap().content, "g1");
+    }
+
+    #[test]
+    fn high_tier_rotates_between_senders() {
+        let mut sched = PriorityScheduler::new(vec![]);
+        sched.push(msg("alice", false, "a1"));
+        sched.push(msg("alice", false, "a2"));
+        sched.push(msg("alice", false, "a3"));
+        sched.push(msg("bob", false, "b1"));
+
+        let order: Vec<String> = std::iter::from_fn(|| sched.pop())
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(order, vec!["a1", "b1", "a2", "a3"]);
+    }
+
+    #[test]
+    fn burst_cap_keeps_group_messages_starvation_free() {
+        let mut sched = PriorityScheduler::new(vec![]);
+        sched.push(msg("group", true, "g1"));
+        for i in 0..10 {
+            sched.push(msg("alice", false, &format!("dm{i}")));
+        }
+
+        let order: Vec<String> = std::iter::from_fn(|| sched.pop())
+            .map(|m| m.content)
+            .collect();
+        let group_pos = order.iter().position(|c| c == "g1").unwrap();
+        assert_eq!(
+            group_pos, HIGH_TIER_BURST,
+            "group message dispatched after one full burst"
+        );
+    }
+
+    #[test]
+    fn sustained_dm_influx_cannot_starve_group_tier() {
+        let mut sched = PriorityScheduler::new(vec![]);
+        sched.push(msg("group", true, "g1"));
+        sched.push(msg("group", true, "g2"));
+
+        // Keep refilling the high tier between pops; both group messages must
+        // still dispatch within a bounded number of pops.
+        let mut dispatched_groups = 0;
+        for i in 0..50 {
+            sched.push(msg("alice", false, &format!("dm{i}")));
+            if sched.pop().unwrap().is_group {
+                dispatched_groups += 1;
+            }
+            if dispatched_groups == 2 {
+                break;
+            }
+        }
+        assert_eq!(dispatched_groups, 2);
+    }
+
+    #[test]
+    fn classify_treats_non_group_as_high() {
+        let vips = vec!["boss".to_string()];
+        assert_eq!(
+            MessagePriority::classify(&msg("alice", false, "hi"), &vips),
+            MessagePriority::High
+        );
+        assert_eq!(
+            MessagePriority::classify(&msg("alice", true, "hi"), &vips),
+            MessagePriority::Normal
+        );
+        assert_eq!(
+            MessagePriority::classify(&msg("boss", true, "hi"), &vips),
+            MessagePriority::High
+        );
+    }
+}
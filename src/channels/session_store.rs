@@ -65,6 +65,10 @@ impl SessionStore {
     }
 
     /// Append a single message to the session JSONL file.
+    ///
+    /// The line (including its newline) is written with a single `write` on a
+    /// file opened in append mode, so concurrent writers from different tasks
+    /// interleave at line granularity instead of corrupting each other.
     pub fn append(&self, session_key: &str, message: &ChatMessage) -> std::io::Result<()> {
         let path = self.session_path(session_key);
         let mut file = std::fs::OpenOptions::new()
@@ -72,13 +76,46 @@ impl SessionStore {
             .append(true)
             .open(&path)?;
 
-        let json = serde_json::to_string(message)
+        let mut json = serde_json::to_string(message)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        json.push('\n');
 
-        writeln!(file, "{json}")?;
+        file.write_all(json.as_bytes())?;
         Ok(())
     }
 
+    /// When the session file was last appended to, from filesystem mtime.
+    /// Returns `None` when the session has no file.
+    pub fn last_activity(&self, session_key: &str) -> Option<std::time::SystemTime> {
+        std::fs::metadata(self.session_path(session_key))
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+
+    /// Whether a session's last activity is older than `ttl_hours`.
+    /// A TTL of `0` disables the check; sessions without a file are not stale.
+    pub fn is_stale(&self, session_key: &str, ttl_hours: u32) -> bool {
+        if ttl_hours == 0 {
+            return false;
+        }
+        let Some(modified) = self.last_activity(session_key) else {
+            return false;
+        };
+        let max_age = std::time::Duration::from_secs(u64::from(ttl_hours) * 3600);
+        modified.elapsed().is_ok_and(|elapsed| elapsed > max_age)
+    }
+
+    /// Delete every persisted session. Returns the number of sessions removed.
+    pub fn delete_all_sessions(&self) -> std::io::Result<usize> {
+        let mut deleted = 0usize;
+        for key in self.list_sessions() {
+            if self.delete_session(&key)? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
     /// Remove the last message from a session's JSONL file.
     ///
     /// Rewrite approach: load all messages, drop the last, rewrite. This is
@@ -161,6 +198,19 @@ impl SessionBackend for SessionStore {
     fn delete_session(&self, session_key: &str) -> std::io::Result<bool> {
         self.delete_session(session_key)
     }
+
+    fn cleanup_stale(&self, ttl_hours: u32) -> std::io::Result<usize> {
+        if ttl_hours == 0 {
+            return Ok(0);
+        }
+        let mut cleaned = 0usize;
+        for key in self.list_sessions() {
+            if self.is_stale(&key, ttl_hours) && self.delete_session(&key)? {
+                cleaned += 1;
+            }
+        }
+        Ok(cleaned)
+    }
 }
 
 #[cfg(test)]
@@ -347,6 +397,82 @@ mod tests {
         assert!(!deleted);
     }
 
+    #[test]
+    fn concurrent_appends_do_not_corrupt_the_file() {
+        let tmp = TempDir::new().unwrap();
+        let store = std::sync::Arc::new(SessionStore::new(tmp.path()).unwrap());
+        let key = "hammer_test";
+
+        let threads = 8;
+        let appends_per_thread = 50;
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let store = std::sync::Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for i in 0..appends_per_thread {
+                        store
+                            .append(key, &ChatMessage::user(format!("thread {t} msg {i}")))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Every appended line must survive intact and parse back.
+        let messages = store.load(key);
+        assert_eq!(messages.len(), threads * appends_per_thread);
+
+        // And the raw file must contain no partial/corrupt lines.
+        let raw = std::fs::read_to_string(store.session_path(key)).unwrap();
+        for line in raw.trim().lines() {
+            serde_json::from_str::<ChatMessage>(line).expect("interleaved line");
+        }
+    }
+
+    #[test]
+    fn cleanup_stale_removes_old_jsonl_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+
+        store.append("old_chat", &ChatMessage::user("hi")).unwrap();
+        store
+            .append("fresh_chat", &ChatMessage::user("hello"))
+            .unwrap();
+
+        // Backdate the old session's mtime past a 24h TTL.
+        let old = std::fs::OpenOptions::new()
+            .write(true)
+            .open(store.session_path("old_chat"))
+            .unwrap();
+        old.set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(48 * 3600))
+            .unwrap();
+
+        assert!(store.is_stale("old_chat", 24));
+        assert!(!store.is_stale("fresh_chat", 24));
+        assert!(!store.is_stale("old_chat", 0), "TTL 0 disables staleness");
+
+        let cleaned = SessionBackend::cleanup_stale(&store, 24).unwrap();
+        assert_eq!(cleaned, 1);
+        assert!(store.load("old_chat").is_empty());
+        assert_eq!(store.load("fresh_chat").len(), 1);
+    }
+
+    #[test]
+    fn delete_all_sessions_wipes_every_file() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::new(tmp.path()).unwrap();
+
+        store.append("chat_a", &ChatMessage::user("a")).unwrap();
+        store.append("chat_b", &ChatMessage::user("b")).unwrap();
+
+        assert_eq!(store.delete_all_sessions().unwrap(), 2);
+        assert!(store.list_sessions().is_empty());
+        assert_eq!(store.delete_all_sessions().unwrap(), 0);
+    }
+
     #[test]
     fn delete_session_via_trait() {
         let tmp = TempDir::new().unwrap();
@@ -9,12 +9,43 @@ use uuid::Uuid;
 
 const GROUP_TARGET_PREFIX: &str = "group:";
 
+/// Default trigger for `RespondMode::Prefix` when none is configured.
+const DEFAULT_TRIGGER_PREFIX: &str = "!";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum RecipientTarget {
     Direct(String),
     Group(String),
 }
 
+/// How the bot decides whether to answer a group message.
+/// DMs always get a response regardless of mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RespondMode {
+    /// Only when the bot is mentioned by number or profile name.
+    Mention,
+    /// Only when the message starts with the trigger prefix.
+    Prefix,
+    /// Every group message (default).
+    #[default]
+    All,
+}
+
+impl RespondMode {
+    /// Parse a config string; unknown values fall back to `All` with a warning.
+    pub fn parse(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "mention" => Self::Mention,
+            "prefix" => Self::Prefix,
+            "all" => Self::All,
+            other => {
+                tracing::warn!("Unknown Signal respond_mode {other:?}, using \"all\"");
+                Self::All
+            }
+        }
+    }
+}
+
 /// Signal channel using signal-cli daemon's native JSON-RPC + SSE API.
 ///
 /// Connects to a running `signal-cli daemon --http <host:port>`.
@@ -30,6 +61,14 @@ pub struct SignalChannel {
     ignore_stories: bool,
     /// Per-channel proxy URL override.
     proxy_url: Option<String>,
+    /// Global group respond mode; per-group overrides take precedence.
+    respond_mode: RespondMode,
+    /// Per-group respond-mode overrides keyed by group id.
+    group_respond_modes: std::collections::HashMap<String, RespondMode>,
+    /// Trigger for `RespondMode::Prefix` (default: "!").
+    trigger_prefix: String,
+    /// Profile name used for "@name" mention detection in message text.
+    profile_name: Option<String>,
 }
 
 // ── signal-cli SSE event JSON shapes ────────────────────────────
@@ -64,6 +103,17 @@ struct DataMessage {
     group_info: Option<GroupInfo>,
     #[serde(default)]
     attachments: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
+    mentions: Option<Vec<Mention>>,
+}
+
+/// An explicit @-mention entry from signal-cli.
+#[derive(Debug, Deserialize)]
+struct Mention {
+    #[serde(default)]
+    number: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -90,6 +140,10 @@ impl SignalChannel {
             ignore_attachments,
             ignore_stories,
             proxy_url: None,
+            respond_mode: RespondMode::default(),
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: DEFAULT_TRIGGER_PREFIX.to_string(),
+            profile_name: None,
         }
     }
 
@@ -99,6 +153,24 @@ impl SignalChannel {
         self
     }
 
+    /// Configure group responding: global mode, per-group overrides, the
+    /// trigger for `Prefix` mode, and the profile name for mention detection.
+    pub fn with_group_responding(
+        mut self,
+        respond_mode: RespondMode,
+        group_respond_modes: std::collections::HashMap<String, RespondMode>,
+        trigger_prefix: Option<String>,
+        profile_name: Option<String>,
+    ) -> Self {
+        self.respond_mode = respond_mode;
+        self.group_respond_modes = group_respond_modes;
+        if let Some(prefix) = trigger_prefix {
+            self.trigger_prefix = prefix;
+        }
+        self.profile_name = profile_name;
+        self
+    }
+
     fn http_client(&self) -> Client {
         let builder = Client::builder().connect_timeout(Duration::from_secs(10));
         let builder = crate::config::apply_channel_proxy_to_builder(
@@ -167,6 +239,64 @@ impl SignalChannel {
         }
     }
 
+    /// Respond mode in effect for a group: per-group override, else global.
+    fn respond_mode_for(&self, group_id: &str) -> RespondMode {
+        self.group_respond_modes
+            .get(group_id)
+            .copied()
+            .unwrap_or(self.respond_mode)
+    }
+
+    /// Whether a group message mentions the bot: an explicit mention entry
+    /// for the account number, the account number in the text, or
+    /// "@<profile name>" (case-insensitive).
+    fn is_bot_mentioned(&self, data_msg: &DataMessage, text: &str) -> bool {
+        if let Some(ref mentions) = data_msg.mentions {
+            let hit = mentions.iter().any(|m| {
+                m.number.as_deref() == Some(self.account.as_str())
+                    || m.name.as_deref() == Some(self.account.as_str())
+            });
+            if hit {
+                return true;
+            }
+        }
+        if text.contains(self.account.as_str()) {
+            return true;
+        }
+        if let Some(ref name) = self.profile_name {
+            let needle = format!("@{}", name.to_lowercase());
+            if text.to_lowercase().contains(&needle) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Apply the group respond gate, returning the text to forward (with the
+    /// trigger prefix stripped in `Prefix` mode) or `None` when the message
+    /// should be ignored.
+    fn gate_group_message(
+        &self,
+        data_msg: &DataMessage,
+        group_id: &str,
+        text: &str,
+    ) -> Option<String> {
+        match self.respond_mode_for(group_id) {
+            RespondMode::All => Some(text.to_string()),
+            RespondMode::Mention => {
+                if self.is_bot_mentioned(data_msg, text) {
+                    Some(text.to_string())
+                } else {
+                    None
+                }
+            }
+            RespondMode::Prefix => text
+                .strip_prefix(&self.trigger_prefix)
+                .map(|rest| rest.trim_start().to_string())
+                .filter(|rest| !rest.is_empty()),
+        }
+    }
+
     /// Determine the send target: group id or the sender's number.
     fn reply_target(&self, data_msg: &DataMessage, sender: &str) -> String {
         if let Some(group_id) = data_msg
@@ -256,6 +386,16 @@ impl SignalChannel {
             return None;
         }
 
+        // Group messages pass through the respond gate; DMs always respond.
+        let text = match data_msg
+            .group_info
+            .as_ref()
+            .and_then(|g| g.group_id.as_deref())
+        {
+            Some(group_id) => self.gate_group_message(data_msg, group_id, text)?,
+            None => text.to_string(),
+        };
+
         let target = self.reply_target(data_msg, &sender);
 
         let timestamp = data_msg
@@ -275,13 +415,17 @@ impl SignalChannel {
             id: format!("sig_{timestamp}"),
             sender: sender.clone(),
             reply_target: target,
-            content: text.to_string(),
+            content: text,
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
     }
 }
@@ -443,17 +587,26 @@ impl Channel for SignalChannel {
     }
 
     async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
-        let params = match Self::parse_recipient_target(recipient) {
-            RecipientTarget::Direct(number) => serde_json::json!({
-                "recipient": [number],
-                "account": &self.account,
-            }),
-            RecipientTarget::Group(group_id) => serde_json::json!({
-                "groupId": group_id,
-                "account": &self.account,
-            }),
-        };
-        self.rpc_request("sendTyping", params).await?;
+        match Self::parse_recipient_target(recipient) {
+            RecipientTarget::Direct(number) => {
+                let params = serde_json::json!({
+                    "recipient": [number],
+                    "account": &self.account,
+                });
+                self.rpc_request("sendTyping", params).await?;
+            }
+            RecipientTarget::Group(group_id) => {
+                // Older signal-cli daemons don't support group typing
+                // indicators — skip on failure instead of surfacing an error.
+                let params = serde_json::json!({
+                    "groupId": group_id,
+                    "account": &self.account,
+                });
+                if let Err(e) = self.rpc_request("sendTyping", params).await {
+                    tracing::debug!("Signal group typing indicator not supported: {e}");
+                }
+            }
+        }
         Ok(())
     }
 
@@ -499,6 +652,7 @@ mod tests {
                 timestamp: Some(1_700_000_000_000),
                 group_info: None,
                 attachments: None,
+                mentions: None,
             }),
             story_message: None,
             timestamp: Some(1_700_000_000_000),
@@ -574,6 +728,7 @@ mod tests {
             timestamp: Some(1000),
             group_info: None,
             attachments: None,
+            mentions: None,
         };
         assert!(ch.matches_group(&dm));
 
@@ -584,6 +739,7 @@ mod tests {
                 group_id: Some("group123".to_string()),
             }),
             attachments: None,
+            mentions: None,
         };
         assert!(ch.matches_group(&group));
     }
@@ -598,6 +754,7 @@ mod tests {
                 group_id: Some("group123".to_string()),
             }),
             attachments: None,
+            mentions: None,
         };
         assert!(ch.matches_group(&matching));
 
@@ -608,6 +765,7 @@ mod tests {
                 group_id: Some("other_group".to_string()),
             }),
             attachments: None,
+            mentions: None,
         };
         assert!(!ch.matches_group(&non_matching));
     }
@@ -620,6 +778,7 @@ mod tests {
             timestamp: Some(1000),
             group_info: None,
             attachments: None,
+            mentions: None,
         };
         assert!(ch.matches_group(&dm));
 
@@ -630,6 +789,7 @@ mod tests {
                 group_id: Some("group123".to_string()),
             }),
             attachments: None,
+            mentions: None,
         };
         assert!(!ch.matches_group(&group));
     }
@@ -642,6 +802,7 @@ mod tests {
             timestamp: Some(1000),
             group_info: None,
             attachments: None,
+            mentions: None,
         };
         assert_eq!(ch.reply_target(&dm, "+1111111111"), "+1111111111");
     }
@@ -656,10 +817,147 @@ mod tests {
                 group_id: Some("group123".to_string()),
             }),
             attachments: None,
+            mentions: None,
         };
         assert_eq!(ch.reply_target(&group, "+1111111111"), "group:group123");
     }
 
+    fn make_gated_channel(
+        respond_mode: RespondMode,
+        overrides: &[(&str, RespondMode)],
+        profile_name: Option<&str>,
+    ) -> SignalChannel {
+        SignalChannel::new(
+            "http://127.0.0.1:8686".to_string(),
+            "+1234567890".to_string(),
+            None,
+            vec!["*".to_string()],
+            false,
+            false,
+        )
+        .with_group_responding(
+            respond_mode,
+            overrides
+                .iter()
+                .map(|(gid, mode)| ((*gid).to_string(), *mode))
+                .collect(),
+            None,
+            profile_name.map(String::from),
+        )
+    }
+
+    fn make_group_msg(group_id: &str, text: &str, mentions: Option<Vec<Mention>>) -> DataMessage {
+        DataMessage {
+            message: Some(text.to_string()),
+            timestamp: Some(1000),
+            group_info: Some(GroupInfo {
+                group_id: Some(group_id.to_string()),
+            }),
+            attachments: None,
+            mentions,
+        }
+    }
+
+    #[test]
+    fn respond_mode_parse_recognizes_known_values() {
+        assert_eq!(RespondMode::parse("mention"), RespondMode::Mention);
+        assert_eq!(RespondMode::parse("Prefix"), RespondMode::Prefix);
+        assert_eq!(RespondMode::parse("ALL"), RespondMode::All);
+    }
+
+    #[test]
+    fn respond_mode_parse_unknown_falls_back_to_all() {
+        assert_eq!(RespondMode::parse("shout"), RespondMode::All);
+    }
+
+    #[test]
+    fn respond_mode_for_prefers_per_group_override() {
+        let ch = make_gated_channel(RespondMode::All, &[("g1", RespondMode::Mention)], None);
+        assert_eq!(ch.respond_mode_for("g1"), RespondMode::Mention);
+        assert_eq!(ch.respond_mode_for("g2"), RespondMode::All);
+    }
+
+    #[test]
+    fn mention_detected_via_explicit_entry() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], None);
+        let msg = make_group_msg(
+            "g1",
+            "hey there",
+            Some(vec![Mention {
+                number: Some("+1234567890".to_string()),
+                name: None,
+            }]),
+        );
+        assert!(ch.is_bot_mentioned(&msg, "hey there"));
+    }
+
+    #[test]
+    fn mention_detected_via_number_in_text() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], None);
+        let msg = make_group_msg("g1", "ping +1234567890 please", None);
+        assert!(ch.is_bot_mentioned(&msg, "ping +1234567890 please"));
+    }
+
+    #[test]
+    fn mention_detected_via_profile_name_case_insensitive() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], Some("ZeroClaw"));
+        let msg = make_group_msg("g1", "hey @zeroclaw, status?", None);
+        assert!(ch.is_bot_mentioned(&msg, "hey @zeroclaw, status?"));
+    }
+
+    #[test]
+    fn unmentioned_group_message_is_not_a_mention() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], Some("ZeroClaw"));
+        let msg = make_group_msg("g1", "just chatting", None);
+        assert!(!ch.is_bot_mentioned(&msg, "just chatting"));
+    }
+
+    #[test]
+    fn gate_mention_mode_drops_unmentioned() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], None);
+        let msg = make_group_msg("g1", "just chatting", None);
+        assert_eq!(ch.gate_group_message(&msg, "g1", "just chatting"), None);
+    }
+
+    #[test]
+    fn gate_mention_mode_passes_mentioned() {
+        let ch = make_gated_channel(RespondMode::Mention, &[], None);
+        let msg = make_group_msg("g1", "+1234567890 status?", None);
+        assert_eq!(
+            ch.gate_group_message(&msg, "g1", "+1234567890 status?"),
+            Some("+1234567890 status?".to_string())
+        );
+    }
+
+    #[test]
+    fn gate_prefix_mode_strips_trigger() {
+        let ch = make_gated_channel(RespondMode::Prefix, &[], None);
+        let msg = make_group_msg("g1", "! status", None);
+        assert_eq!(
+            ch.gate_group_message(&msg, "g1", "! status"),
+            Some("status".to_string())
+        );
+    }
+
+    #[test]
+    fn gate_prefix_mode_drops_unprefixed_and_empty() {
+        let ch = make_gated_channel(RespondMode::Prefix, &[], None);
+        let plain = make_group_msg("g1", "status", None);
+        assert_eq!(ch.gate_group_message(&plain, "g1", "status"), None);
+        let bare = make_group_msg("g1", "!  ", None);
+        assert_eq!(ch.gate_group_message(&bare, "g1", "!  "), None);
+    }
+
+    #[test]
+    fn gate_all_mode_passes_everything() {
+        let ch = make_gated_channel(RespondMode::All, &[], None);
+        let msg = make_group_msg("g1", "anything", None);
+        assert_eq!(
+            ch.gate_group_message(&msg, "g1", "anything"),
+            Some("anything".to_string())
+        );
+    }
+
     #[test]
     fn parse_recipient_target_e164_is_direct() {
         assert_eq!(
@@ -754,6 +1052,7 @@ mod tests {
                 timestamp: Some(1_700_000_000_000),
                 group_info: None,
                 attachments: None,
+                mentions: None,
             }),
             story_message: None,
             timestamp: Some(1_700_000_000_000),
@@ -789,6 +1088,7 @@ mod tests {
                     group_id: Some("testgroup".to_string()),
                 }),
                 attachments: None,
+                mentions: None,
             }),
             story_message: None,
             timestamp: Some(1_700_000_000_000),
@@ -864,6 +1164,7 @@ mod tests {
                 timestamp: Some(1_700_000_000_000),
                 group_info: None,
                 attachments: Some(vec![serde_json::json!({"contentType": "image/png"})]),
+                mentions: None,
             }),
             story_message: None,
             timestamp: Some(1_700_000_000_000),
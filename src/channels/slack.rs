@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::respond_policy::{GroupRespondPolicy, RespondDecision};
+use super::traits::{Channel, ChannelHealthReport, ChannelMessage, SendMessage};
+use crate::approval::{ApprovalResponse, ResolveOutcome};
 use anyhow::Context;
 use async_trait::async_trait;
 use base64::Engine as _;
@@ -27,7 +29,7 @@ pub struct SlackChannel {
     channel_ids: Vec<String>,
     allowed_users: Vec<String>,
     thread_replies: bool,
-    mention_only: bool,
+    respond_policy: std::sync::Arc<GroupRespondPolicy>,
     group_reply_allowed_sender_ids: Vec<String>,
     user_display_name_cache: Mutex<HashMap<String, CachedSlackDisplayName>>,
     workspace_dir: Option<PathBuf>,
@@ -37,6 +39,8 @@ pub struct SlackChannel {
     use_markdown_blocks: bool,
     /// Per-channel proxy URL override.
     proxy_url: Option<String>,
+    /// Slack Web API base URL (overridable for tests).
+    api_base: String,
     /// Voice transcription config — when set, audio file attachments are
     /// downloaded, transcribed, and their text inlined into the message.
     transcription: Option<crate::config::TranscriptionConfig>,
@@ -91,6 +95,22 @@ enum SlackPermalinkLookup {
     NotFound,
 }
 
+/// A decoded approval button press from a `block_actions` payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlackApprovalAction {
+    decision: ApprovalResponse,
+    request_id: String,
+    user_id: String,
+    /// Username for the approver allowlist check (falls back to the user ID).
+    username: String,
+    channel_id: String,
+    message_ts: String,
+    /// Text of the original prompt message, for the post-decision update.
+    message_text: String,
+    /// Ephemeral feedback URL supplied by Slack with every interaction.
+    response_url: Option<String>,
+}
+
 /// Extract the Slack message timestamp from a ZeroClaw message ID.
 ///
 /// Message IDs follow the format `slack_{channel_id}_{ts}` where `ts`
@@ -166,13 +186,14 @@ impl SlackChannel {
             channel_ids,
             allowed_users,
             thread_replies: true,
-            mention_only: false,
+            respond_policy: std::sync::Arc::new(GroupRespondPolicy::from_mention_only(false)),
             group_reply_allowed_sender_ids: Vec::new(),
             user_display_name_cache: Mutex::new(HashMap::new()),
             workspace_dir: None,
             active_assistant_thread: Mutex::new(HashMap::new()),
             use_markdown_blocks: false,
             proxy_url: None,
+            api_base: "https://slack.com/api".to_string(),
             transcription: None,
             transcription_manager: None,
             stream_drafts: false,
@@ -188,12 +209,20 @@ impl SlackChannel {
         mention_only: bool,
         allowed_sender_ids: Vec<String>,
     ) -> Self {
-        self.mention_only = mention_only;
+        self.respond_policy =
+            std::sync::Arc::new(GroupRespondPolicy::from_mention_only(mention_only));
         self.group_reply_allowed_sender_ids =
             Self::normalize_group_reply_allowed_sender_ids(allowed_sender_ids);
         self
     }
 
+    /// Replace the group respond policy (overrides the legacy `mention_only`
+    /// flag set by [`SlackChannel::with_group_reply_policy`]).
+    pub fn with_respond_policy(mut self, policy: std::sync::Arc<GroupRespondPolicy>) -> Self {
+        self.respond_policy = policy;
+        self
+    }
+
     /// Configure whether outbound replies stay in the originating Slack thread.
     pub fn with_thread_replies(mut self, thread_replies: bool) -> Self {
         self.thread_replies = thread_replies;
@@ -219,6 +248,12 @@ impl SlackChannel {
         self
     }
 
+    /// Override the Slack Web API base URL. Useful for testing.
+    pub fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
     /// Configure voice transcription for audio file attachments.
     pub fn with_transcription(mut self, config: crate::config::TranscriptionConfig) -> Self {
         if !config.enabled {
@@ -256,7 +291,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.delete")
+            .post(self.api_url("chat.delete"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -318,7 +353,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -369,7 +404,7 @@ impl SlackChannel {
 
         let _ = self
             .http_client()
-            .post("https://slack.com/api/assistant.threads.setStatus")
+            .post(self.api_url("assistant.threads.setStatus"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -385,6 +420,10 @@ impl SlackChannel {
         )
     }
 
+    fn api_url(&self, method: &str) -> String {
+        format!("{}/{method}", self.api_base)
+    }
+
     /// Post a new Slack message and return the message timestamp (`ts`).
     ///
     /// This is a lower-level helper that exposes the `ts` value needed for
@@ -398,7 +437,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -444,7 +483,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.update")
+            .post(self.api_url("chat.update"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -503,7 +542,7 @@ impl SlackChannel {
     async fn get_bot_user_id(&self) -> Option<String> {
         let resp: serde_json::Value = self
             .http_client()
-            .get("https://slack.com/api/auth.test")
+            .get(self.api_url("auth.test"))
             .bearer_auth(&self.bot_token)
             .send()
             .await
@@ -679,7 +718,7 @@ impl SlackChannel {
     async fn fetch_sender_display_name(&self, user_id: &str) -> Option<String> {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/users.info")
+            .get(self.api_url("users.info"))
             .bearer_auth(&self.bot_token)
             .query(&[("user", user_id)])
             .send()
@@ -989,7 +1028,7 @@ impl SlackChannel {
     ) -> SlackPermalinkLookup {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/conversations.history")
+            .get(self.api_url("conversations.history"))
             .bearer_auth(&self.bot_token)
             .query(&[
                 ("channel", channel_id),
@@ -1271,7 +1310,7 @@ impl SlackChannel {
     async fn fetch_file_info(&self, file_id: &str) -> Option<serde_json::Value> {
         let resp = match self
             .http_client()
-            .get("https://slack.com/api/files.info")
+            .get(self.api_url("files.info"))
             .bearer_auth(&self.bot_token)
             .query(&[("file", file_id)])
             .send()
@@ -2365,7 +2404,7 @@ impl SlackChannel {
 
             let resp = self
                 .http_client()
-                .get("https://slack.com/api/conversations.list")
+                .get(self.api_url("conversations.list"))
                 .bearer_auth(&self.bot_token)
                 .query(&query_params)
                 .send()
@@ -2499,10 +2538,318 @@ impl SlackChannel {
                 .map(str::to_string),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
+        })
+    }
+
+    /// Block Kit blocks for a tool-approval prompt: a section describing the
+    /// call and Approve/Deny buttons whose `action_id` encodes the request id.
+    fn approval_blocks(request_id: &str, prompt: &str) -> serde_json::Value {
+        serde_json::json!([
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": prompt }
+            },
+            {
+                "type": "actions",
+                "block_id": "zeroclaw_approval",
+                "elements": [
+                    {
+                        "type": "button",
+                        "action_id": format!("zeroclaw_approval_yes:{request_id}"),
+                        "text": { "type": "plain_text", "text": "✅ Approve" },
+                        "style": "primary",
+                        "value": request_id
+                    },
+                    {
+                        "type": "button",
+                        "action_id": format!("zeroclaw_approval_no:{request_id}"),
+                        "text": { "type": "plain_text", "text": "❌ Deny" },
+                        "style": "danger",
+                        "value": request_id
+                    }
+                ]
+            }
+        ])
+    }
+
+    /// Decode an approval button `action_id` back into (decision, request id).
+    fn parse_approval_action(action_id: &str) -> Option<(ApprovalResponse, &str)> {
+        let rest = action_id.strip_prefix("zeroclaw_approval_")?;
+        let (decision, request_id) = rest.split_once(':')?;
+        if request_id.is_empty() {
+            return None;
+        }
+        match decision {
+            "yes" => Some((ApprovalResponse::Yes, request_id)),
+            "no" => Some((ApprovalResponse::No, request_id)),
+            _ => None,
+        }
+    }
+
+    /// Extract an approval button press from a `block_actions` payload.
+    /// Returns `None` for pings, other interactive payloads, and the
+    /// `/config` UI actions handled by [`Self::parse_block_action_as_command`].
+    fn extract_approval_action(payload: &serde_json::Value) -> Option<SlackApprovalAction> {
+        if payload.get("type").and_then(|v| v.as_str()) != Some("block_actions") {
+            return None;
+        }
+        let action = payload.get("actions").and_then(|v| v.as_array())?.first()?;
+        let action_id = action.get("action_id").and_then(|v| v.as_str())?;
+        let (decision, request_id) = Self::parse_approval_action(action_id)?;
+
+        let user = payload.get("user")?;
+        let user_id = user.get("id").and_then(|v| v.as_str())?.to_string();
+        let username = user
+            .get("username")
+            .or_else(|| user.get("name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(&user_id)
+            .to_string();
+
+        Some(SlackApprovalAction {
+            decision,
+            request_id: request_id.to_string(),
+            user_id,
+            username,
+            channel_id: payload
+                .get("channel")
+                .and_then(|c| c.get("id"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            message_ts: payload
+                .get("message")
+                .and_then(|m| m.get("ts"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            message_text: payload
+                .get("message")
+                .and_then(|m| m.get("text"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            response_url: payload
+                .get("response_url")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
         })
     }
 
+    /// Post a tool-approval prompt with Approve/Deny buttons.
+    ///
+    /// `prompt` is passed through the agent loop's credential scrubber before
+    /// it leaves the process. Button presses arrive as `block_actions`
+    /// payloads over the Socket Mode websocket, which is authenticated by the
+    /// app token — Slack's HTTP signing-secret verification only applies to
+    /// public request URLs, which this channel does not expose.
+    pub async fn send_approval_prompt(
+        &self,
+        channel: &str,
+        request_id: &str,
+        prompt: &str,
+    ) -> anyhow::Result<()> {
+        let prompt = crate::agent::loop_::scrub_credentials(prompt);
+        let body = serde_json::json!({
+            "channel": channel,
+            "text": prompt,
+            "blocks": Self::approval_blocks(request_id, &prompt),
+        });
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("chat.postMessage"))
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let raw = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            let sanitized = crate::providers::sanitize_api_error(&raw);
+            anyhow::bail!("Slack approval prompt failed ({status}): {sanitized}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&raw).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack approval prompt failed: {err}");
+        }
+
+        // Remember the prompt's ts so a 👍/👎 reaction on it can be mapped
+        // back to this request.
+        if let Some(ts) = parsed.get("ts").and_then(|v| v.as_str()) {
+            crate::approval::approval_prompt_index().record("slack", ts, request_id);
+        }
+
+        Ok(())
+    }
+
+    /// Handle a `reaction_added` event: a 👍/👎 on an approval prompt acts
+    /// as approve/deny. Gated on the channel allowlist here; the approver
+    /// allowlist and expiry are enforced by the shared reaction handler.
+    /// Slack reaction events only carry the user id, so approver allowlists
+    /// that should match reactions must list user IDs.
+    fn handle_reaction_added(&self, event: &serde_json::Value) {
+        let Some(user) = event.get("user").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let Some(reaction) = event.get("reaction").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let Some(ts) = event
+            .get("item")
+            .and_then(|i| i.get("ts"))
+            .and_then(|v| v.as_str())
+        else {
+            return;
+        };
+
+        if !self.is_user_allowed(user) {
+            tracing::debug!("Slack: ignoring reaction from unauthorized user: {user}");
+            return;
+        }
+
+        super::handle_approval_reaction(&super::traits::ReactionEvent {
+            channel: "slack".to_string(),
+            message_id: ts.to_string(),
+            actor: user.to_string(),
+            emoji: reaction.to_string(),
+        });
+    }
+
+    /// Replace an approval prompt's blocks with a plain section reflecting
+    /// the decision, removing the buttons so it cannot be pressed again.
+    async fn finalize_approval_prompt(
+        &self,
+        channel: &str,
+        ts: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "channel": channel,
+            "ts": ts,
+            "text": text,
+            "blocks": [{
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": text }
+            }],
+        });
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("chat.update"))
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            anyhow::bail!("Slack approval prompt update failed ({status})");
+        }
+        Ok(())
+    }
+
+    /// Send ephemeral feedback to the pressing user via the interaction's
+    /// `response_url`. Best-effort.
+    async fn post_approval_feedback(&self, response_url: &str, text: &str) {
+        let body = serde_json::json!({
+            "text": text,
+            "response_type": "ephemeral",
+            "replace_original": false,
+        });
+        if let Err(e) = self
+            .http_client()
+            .post(response_url)
+            .json(&body)
+            .send()
+            .await
+        {
+            tracing::debug!("Slack approval feedback post failed: {e}");
+        }
+    }
+
+    /// Handle a `block_actions` payload from an approval prompt.
+    ///
+    /// Returns `true` when the payload was an approval button press (whether
+    /// or not the decision was accepted), so the caller can stop routing it.
+    /// The acting user must pass the channel allowlist; the non-CLI approver
+    /// allowlist is enforced by the pending-approvals registry itself.
+    async fn handle_approval_block_action(&self, envelope: &serde_json::Value) -> bool {
+        let Some(payload) = envelope.get("payload") else {
+            return false;
+        };
+        let Some(action) = Self::extract_approval_action(payload) else {
+            return false;
+        };
+
+        if !self.is_user_allowed(&action.user_id) {
+            tracing::warn!(
+                "Slack: ignoring approval press from unauthorized user: {}",
+                action.user_id
+            );
+            if let Some(ref url) = action.response_url {
+                self.post_approval_feedback(url, "You are not authorized to approve tool calls.")
+                    .await;
+            }
+            return true;
+        }
+
+        let outcome = crate::approval::pending_approvals().resolve(
+            &action.request_id,
+            &action.username,
+            action.decision,
+        );
+        let feedback = match outcome {
+            ResolveOutcome::Resolved(decision) => {
+                let verdict = match decision {
+                    ApprovalResponse::Yes => "✅ Approved",
+                    ApprovalResponse::Always => "✅ Approved for all tools (this session)",
+                    ApprovalResponse::No => "❌ Denied",
+                };
+                if !action.channel_id.is_empty() && !action.message_ts.is_empty() {
+                    let updated = if action.message_text.is_empty() {
+                        format!("{verdict} by <@{}>", action.user_id)
+                    } else {
+                        format!(
+                            "{}\n\n{verdict} by <@{}>",
+                            action.message_text, action.user_id
+                        )
+                    };
+                    if let Err(e) = self
+                        .finalize_approval_prompt(&action.channel_id, &action.message_ts, &updated)
+                        .await
+                    {
+                        tracing::debug!("Slack approval prompt finalize failed: {e}");
+                    }
+                }
+                return true;
+            }
+            ResolveOutcome::AlreadyResolved => "Already handled.",
+            ResolveOutcome::Expired => "This approval request has expired.",
+            ResolveOutcome::ActorNotAllowed => "You are not on the approver allowlist.",
+            ResolveOutcome::Unknown => "Unknown or expired approval request.",
+        };
+        if let Some(ref url) = action.response_url {
+            self.post_approval_feedback(url, feedback).await;
+        }
+        true
+    }
+
     async fn open_socket_mode_url(&self) -> anyhow::Result<String> {
         let app_token = self
             .configured_app_token()
@@ -2510,7 +2857,7 @@ impl SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/apps.connections.open")
+            .post(self.api_url("apps.connections.open"))
             .bearer_auth(app_token)
             .send()
             .await?;
@@ -2644,8 +2991,12 @@ impl SlackChannel {
                     break;
                 }
 
-                // Handle interactive payloads (block_actions from /config UI).
+                // Handle interactive payloads (approval buttons and
+                // block_actions from the /config UI).
                 if envelope_type == "interactive" {
+                    if self.handle_approval_block_action(&envelope).await {
+                        continue;
+                    }
                     if let Some(msg) = Self::parse_block_action_as_command(&envelope, bot_user_id) {
                         if tx.send(msg).await.is_err() {
                             return Ok(());
@@ -2691,6 +3042,13 @@ impl SlackChannel {
                     continue;
                 }
 
+                // Emoji reactions on approval prompts act as approve/deny
+                // shortcuts.
+                if event_type == "reaction_added" {
+                    self.handle_reaction_added(event);
+                    continue;
+                }
+
                 if event_type != "message" {
                     continue;
                 }
@@ -2741,10 +3099,23 @@ impl SlackChannel {
                 let is_thread_reply = event.get("thread_ts").and_then(|v| v.as_str()).is_some();
                 let allow_sender_without_mention =
                     is_group_message && self.is_group_sender_trigger_enabled(user);
-                let require_mention = self.mention_only
-                    && is_group_message
-                    && !allow_sender_without_mention
-                    && !is_thread_reply;
+                // Thread replies and allow-listed senders bypass the gate; the
+                // policy decides for everything else in a group.
+                let raw_text = event.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                let decision =
+                    if !is_group_message || allow_sender_without_mention || is_thread_reply {
+                        RespondDecision::respond()
+                    } else {
+                        self.respond_policy.evaluate(Some(&channel_id), raw_text)
+                    };
+                let (require_mention, strip_trigger, record_only) = match decision {
+                    RespondDecision::Ignore => continue,
+                    RespondDecision::RecordOnly => (false, false, true),
+                    RespondDecision::Respond {
+                        require_mention,
+                        strip_trigger,
+                    } => (require_mention, strip_trigger, false),
+                };
 
                 let Some(normalized_text) = self
                     .build_incoming_content(event, require_mention, bot_user_id)
@@ -2752,6 +3123,15 @@ impl SlackChannel {
                 else {
                     continue;
                 };
+                let normalized_text = if strip_trigger {
+                    let stripped = self.respond_policy.strip_trigger(&normalized_text);
+                    if stripped.is_empty() {
+                        continue;
+                    }
+                    stripped
+                } else {
+                    normalized_text
+                };
 
                 last_ts_by_channel.insert(channel_id.clone(), ts.to_string());
                 let sender = self.resolve_sender_identity(user).await;
@@ -2773,7 +3153,12 @@ impl SlackChannel {
                     },
                     reply_to_message_id: None,
                     interruption_scope_id: Self::inbound_interruption_scope_id(event, ts),
+                    is_edit: false,
+                    is_group: is_group_message,
+                    payload: None,
                     attachments: vec![],
+                    metadata: is_group_message
+                        .then(|| super::respond_policy::group_metadata(&channel_id, record_only)),
                 };
 
                 // Track thread context so start_typing can set assistant status.
@@ -2902,7 +3287,7 @@ impl SlackChannel {
         for attempt in 0..=SLACK_HISTORY_MAX_RETRIES {
             let resp = match self
                 .http_client()
-                .get("https://slack.com/api/conversations.history")
+                .get(self.api_url("conversations.history"))
                 .bearer_auth(&self.bot_token)
                 .query(params)
                 .send()
@@ -2996,7 +3381,7 @@ impl SlackChannel {
         for attempt in 0..=SLACK_HISTORY_MAX_RETRIES {
             let resp = match self
                 .http_client()
-                .get("https://slack.com/api/conversations.replies")
+                .get(self.api_url("conversations.replies"))
                 .bearer_auth(&self.bot_token)
                 .query(&[
                     ("channel", channel_id),
@@ -3203,6 +3588,10 @@ impl Channel for SlackChannel {
         "slack"
     }
 
+    fn group_respond_policy(&self) -> Option<std::sync::Arc<GroupRespondPolicy>> {
+        Some(std::sync::Arc::clone(&self.respond_policy))
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         // Detect Block Kit payloads produced by the `/config` command.
         let body = if let Some(blocks_json) = message.content.strip_prefix(super::BLOCK_KIT_PREFIX)
@@ -3269,7 +3658,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
+            .post(self.api_url("chat.postMessage"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3375,6 +3764,7 @@ impl Channel for SlackChannel {
         let client = self.http_client();
         let token = self.bot_token.clone();
         let channel = recipient.to_string();
+        let update_url = self.api_url("chat.update");
         tokio::spawn(async move {
             let mut body = serde_json::json!({
                 "channel": channel,
@@ -3388,7 +3778,7 @@ impl Channel for SlackChannel {
                 }]);
             }
             match client
-                .post("https://slack.com/api/chat.update")
+                .post(update_url)
                 .bearer_auth(&token)
                 .json(&body)
                 .send()
@@ -3474,7 +3864,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.update")
+            .post(self.api_url("chat.update"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3527,7 +3917,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/reactions.add")
+            .post(self.api_url("reactions.add"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3572,7 +3962,7 @@ impl Channel for SlackChannel {
 
         let resp = self
             .http_client()
-            .post("https://slack.com/api/reactions.remove")
+            .post(self.api_url("reactions.remove"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3734,16 +4124,37 @@ impl Channel for SlackChannel {
                             msg.get("thread_ts").and_then(|v| v.as_str()).is_some();
                         let allow_sender_without_mention =
                             is_group_message && self.is_group_sender_trigger_enabled(user);
-                        let require_mention = self.mention_only
-                            && is_group_message
-                            && !allow_sender_without_mention
-                            && !is_thread_reply;
+                        let raw_text = msg.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                        let decision =
+                            if !is_group_message || allow_sender_without_mention || is_thread_reply
+                            {
+                                RespondDecision::respond()
+                            } else {
+                                self.respond_policy.evaluate(Some(&channel_id), raw_text)
+                            };
+                        let (require_mention, strip_trigger, record_only) = match decision {
+                            RespondDecision::Ignore => continue,
+                            RespondDecision::RecordOnly => (false, false, true),
+                            RespondDecision::Respond {
+                                require_mention,
+                                strip_trigger,
+                            } => (require_mention, strip_trigger, false),
+                        };
                         let Some(normalized_text) = self
                             .build_incoming_content(msg, require_mention, &bot_user_id)
                             .await
                         else {
                             continue;
                         };
+                        let normalized_text = if strip_trigger {
+                            let stripped = self.respond_policy.strip_trigger(&normalized_text);
+                            if stripped.is_empty() {
+                                continue;
+                            }
+                            stripped
+                        } else {
+                            normalized_text
+                        };
 
                         last_ts_by_channel.insert(channel_id.clone(), ts.to_string());
                         let sender = self.resolve_sender_identity(user).await;
@@ -3765,7 +4176,13 @@ impl Channel for SlackChannel {
                             },
                             reply_to_message_id: None,
                             interruption_scope_id: Self::inbound_interruption_scope_id(msg, ts),
+                            is_edit: false,
+                            is_group: is_group_message,
+                            payload: None,
                             attachments: vec![],
+                            metadata: is_group_message.then(|| {
+                                super::respond_policy::group_metadata(&channel_id, record_only)
+                            }),
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -3850,7 +4267,11 @@ impl Channel for SlackChannel {
                         thread_ts: Some(thread_ts.clone()),
                         reply_to_message_id: None,
                         interruption_scope_id: Some(thread_ts.clone()),
+                        is_edit: false,
+                        is_group: Self::is_group_channel_id(&thread_channel_id),
+                        payload: None,
                         attachments: vec![],
+                        metadata: None,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -3864,7 +4285,7 @@ impl Channel for SlackChannel {
     async fn health_check(&self) -> bool {
         let bot_ok = match self
             .http_client()
-            .get("https://slack.com/api/auth.test")
+            .get(self.api_url("auth.test"))
             .bearer_auth(&self.bot_token)
             .send()
             .await
@@ -3885,6 +4306,65 @@ impl Channel for SlackChannel {
         Self::evaluate_health(bot_ok, socket_mode_enabled, socket_mode_ok)
     }
 
+    async fn health_check_detailed(&self) -> ChannelHealthReport {
+        let started = std::time::Instant::now();
+        let response = self
+            .http_client()
+            .get(self.api_url("auth.test"))
+            .bearer_auth(&self.bot_token)
+            .send()
+            .await;
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        let (bot_ok, identity, detail) = match response {
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                let ok = Self::slack_api_call_succeeded(status, &body);
+                let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+                let identity = parsed
+                    .get("user")
+                    .and_then(serde_json::Value::as_str)
+                    .map(
+                        |user| match parsed.get("team").and_then(serde_json::Value::as_str) {
+                            Some(team) => format!("{user} @ {team}"),
+                            None => user.to_string(),
+                        },
+                    );
+                let detail = if ok {
+                    None
+                } else {
+                    let api_error = parsed
+                        .get("error")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or("unknown error");
+                    Some(format!("auth.test failed: {api_error} (HTTP {status})"))
+                };
+                (ok, identity, detail)
+            }
+            Err(e) => (false, None, Some(format!("auth.test request failed: {e}"))),
+        };
+
+        let socket_mode_enabled = self.configured_app_token().is_some();
+        let socket_mode_ok = if socket_mode_enabled {
+            self.open_socket_mode_url().await.is_ok()
+        } else {
+            true
+        };
+        let detail = match (detail, socket_mode_enabled && !socket_mode_ok) {
+            (None, true) => Some("socket mode connection failed (check app_token)".to_string()),
+            (detail, _) => detail,
+        };
+
+        ChannelHealthReport {
+            healthy: Self::evaluate_health(bot_ok, socket_mode_enabled, socket_mode_ok),
+            latency_ms,
+            identity,
+            detail,
+            ..ChannelHealthReport::default()
+        }
+    }
+
     async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
         let thread_ts = {
             let map = self
@@ -3906,7 +4386,7 @@ impl Channel for SlackChannel {
         // Gracefully ignore errors — non-assistant contexts will return errors.
         if let Ok(resp) = self
             .http_client()
-            .post("https://slack.com/api/assistant.threads.setStatus")
+            .post(self.api_url("assistant.threads.setStatus"))
             .bearer_auth(&self.bot_token)
             .json(&body)
             .send()
@@ -3960,7 +4440,7 @@ mod tests {
     fn slack_group_reply_policy_defaults_to_all_messages() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec!["*".into()]);
         assert!(ch.thread_replies);
-        assert!(!ch.mention_only);
+        assert!(!ch.respond_policy.requires_mention_detection());
         assert!(ch.group_reply_allowed_sender_ids.is_empty());
     }
 
@@ -3998,7 +4478,7 @@ mod tests {
         let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec!["*".into()])
             .with_group_reply_policy(true, vec![" U111 ".into(), "U111".into(), "U222".into()]);
 
-        assert!(ch.mention_only);
+        assert!(ch.respond_policy.requires_mention_detection());
         assert_eq!(
             ch.group_reply_allowed_sender_ids,
             vec!["U111".to_string(), "U222".to_string()]
@@ -4863,7 +5343,11 @@ mod tests {
             thread_ts: None, // thread_replies=false → no fallback to ts
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         let msg1 = make_msg("100.000");
@@ -4890,7 +5374,11 @@ mod tests {
             thread_ts: Some(ts.to_string()), // thread_replies=true → ts as thread_ts
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         let msg1 = make_msg("100.000");
@@ -4987,4 +5475,161 @@ mod tests {
             assert_eq!(map.get("C999"), None);
         }
     }
+
+    // ── Approval prompt tests ─────────────────────────────────────────
+
+    #[test]
+    fn approval_blocks_action_ids_round_trip() {
+        let blocks = SlackChannel::approval_blocks("apr-abc123", "Execute: shell");
+        let elements = blocks[1]["elements"].as_array().unwrap();
+        assert_eq!(elements.len(), 2);
+
+        let approve_id = elements[0]["action_id"].as_str().unwrap();
+        assert_eq!(
+            SlackChannel::parse_approval_action(approve_id),
+            Some((ApprovalResponse::Yes, "apr-abc123"))
+        );
+
+        let deny_id = elements[1]["action_id"].as_str().unwrap();
+        assert_eq!(
+            SlackChannel::parse_approval_action(deny_id),
+            Some((ApprovalResponse::No, "apr-abc123"))
+        );
+    }
+
+    #[test]
+    fn parse_approval_action_rejects_garbage() {
+        assert_eq!(SlackChannel::parse_approval_action(""), None);
+        assert_eq!(
+            SlackChannel::parse_approval_action("zeroclaw_config_provider"),
+            None
+        );
+        assert_eq!(
+            SlackChannel::parse_approval_action("zeroclaw_approval_yes:"),
+            None
+        );
+        assert_eq!(
+            SlackChannel::parse_approval_action("zeroclaw_approval_maybe:apr-1"),
+            None
+        );
+    }
+
+    #[test]
+    fn extract_approval_action_reads_block_actions_payload() {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123", "username": "alice" },
+            "channel": { "id": "C456" },
+            "message": { "ts": "1741234567.000100", "text": "Execute: shell" },
+            "response_url": "https://hooks.slack.com/actions/T1/abc",
+            "actions": [{ "action_id": "zeroclaw_approval_yes:apr-xyz" }]
+        });
+
+        let action = SlackChannel::extract_approval_action(&payload).unwrap();
+        assert_eq!(action.decision, ApprovalResponse::Yes);
+        assert_eq!(action.request_id, "apr-xyz");
+        assert_eq!(action.user_id, "U123");
+        assert_eq!(action.username, "alice");
+        assert_eq!(action.channel_id, "C456");
+        assert_eq!(action.message_ts, "1741234567.000100");
+        assert_eq!(action.message_text, "Execute: shell");
+        assert_eq!(
+            action.response_url.as_deref(),
+            Some("https://hooks.slack.com/actions/T1/abc")
+        );
+    }
+
+    #[test]
+    fn extract_approval_action_ignores_other_interactions() {
+        // /config UI dropdown — handled by parse_block_action_as_command.
+        let config_ui = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "actions": [{
+                "action_id": "zeroclaw_config_provider",
+                "selected_option": { "value": "openrouter" }
+            }]
+        });
+        assert_eq!(SlackChannel::extract_approval_action(&config_ui), None);
+
+        // Non-block_actions payloads.
+        let view_submission = serde_json::json!({
+            "type": "view_submission",
+            "actions": [{ "action_id": "zeroclaw_approval_yes:apr-1" }]
+        });
+        assert_eq!(
+            SlackChannel::extract_approval_action(&view_submission),
+            None
+        );
+
+        // Missing user — cannot attribute the decision.
+        let no_user = serde_json::json!({
+            "type": "block_actions",
+            "actions": [{ "action_id": "zeroclaw_approval_yes:apr-1" }]
+        });
+        assert_eq!(SlackChannel::extract_approval_action(&no_user), None);
+    }
+
+    #[test]
+    fn extract_approval_action_falls_back_to_user_id_for_username() {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "actions": [{ "action_id": "zeroclaw_approval_no:apr-xyz" }]
+        });
+
+        let action = SlackChannel::extract_approval_action(&payload).unwrap();
+        assert_eq!(action.decision, ApprovalResponse::No);
+        assert_eq!(action.username, "U123");
+        assert!(action.channel_id.is_empty());
+        assert_eq!(action.response_url, None);
+    }
+
+    #[tokio::test]
+    async fn health_check_detailed_reports_workspace_identity() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/auth.test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "user": "doctor_bot",
+                "team": "ZeroClaw HQ"
+            })))
+            .mount(&server)
+            .await;
+
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec![])
+            .with_api_base(server.uri());
+
+        let report = ch.health_check_detailed().await;
+        assert!(report.healthy);
+        assert_eq!(report.identity.as_deref(), Some("doctor_bot @ ZeroClaw HQ"));
+        assert!(report.detail.is_none());
+    }
+
+    #[tokio::test]
+    async fn health_check_detailed_surfaces_slack_api_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/auth.test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": false,
+                "error": "invalid_auth"
+            })))
+            .mount(&server)
+            .await;
+
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec![])
+            .with_api_base(server.uri());
+
+        let report = ch.health_check_detailed().await;
+        assert!(!report.healthy);
+        assert!(report.detail.as_deref().unwrap().contains("invalid_auth"));
+    }
 }
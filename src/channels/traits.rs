@@ -1,6 +1,39 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
+/// Structured non-text payload of an inbound message (a shared pin, a
+/// contact card). Channels that receive one populate both this field and a
+/// textual summary in `content`, so providers without structured input
+/// still see something useful while hooks and tools get the typed form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessagePayload {
+    /// A shared location or venue pin.
+    Location {
+        lat: f64,
+        lon: f64,
+        /// Venue/place name when the platform provides one.
+        name: Option<String>,
+    },
+    /// A shared contact card.
+    Contact { name: String, phone: String },
+}
+
+impl MessagePayload {
+    /// Render the payload as the bracketed text summary that goes into
+    /// `ChannelMessage::content` (e.g. `[location: 52.52,13.40 — Alexanderplatz]`).
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Location { lat, lon, name } => match name {
+                Some(name) => format!("[location: {lat:.2},{lon:.2} — {name}]"),
+                None => format!("[location: {lat:.2},{lon:.2}]"),
+            },
+            Self::Contact { name, phone } => format!("[contact: {name} — {phone}]"),
+        }
+    }
+}
+
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
 pub struct ChannelMessage {
@@ -22,10 +55,48 @@ pub struct ChannelMessage {
     /// is genuinely inside a reply thread and should be isolated from other threads.
     /// `None` means top-level — scope is sender+channel only.
     pub interruption_scope_id: Option<String>,
+    /// `true` when this message is an edit of an earlier message with the
+    /// same `id` (e.g. Telegram `edited_message`). The dispatcher cancels or
+    /// rolls back the original turn and reprocesses instead of treating the
+    /// edit as a brand-new message.
+    pub is_edit: bool,
+    /// `true` when the message arrived in a group/channel context rather than
+    /// a direct message. Populated by each channel's `listen()`; the dispatch
+    /// scheduler uses it to let DMs preempt group chatter. Defaults to `false`
+    /// (treated as a DM) for channels without a group concept.
+    pub is_group: bool,
     /// Media attachments (audio, images, video) for the media pipeline.
     /// Channels populate this when they receive media alongside a text message.
     /// Defaults to empty — existing channels are unaffected.
     pub attachments: Vec<super::media_pipeline::MediaAttachment>,
+    /// Channel-specific extras for hooks (e.g. the raw email body before
+    /// quoted-reply stripping). Defaults to `None` — existing channels are
+    /// unaffected.
+    pub metadata: Option<serde_json::Value>,
+    /// Structured payload (location, contact) when the message carried one.
+    /// `content` always contains a textual summary as well.
+    pub payload: Option<MessagePayload>,
+}
+
+/// An inbound emoji reaction on a previously sent message.
+///
+/// Channels that support reactions emit these from their `listen()` loops
+/// (currently for approval shortcuts: 👍 / 👎 on an approval prompt).
+/// Channels without reaction support simply never produce them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionEvent {
+    /// Channel name (e.g. `"telegram"`).
+    pub channel: String,
+    /// Platform message id of the message that was reacted to, in the same
+    /// form the channel uses when it sends messages (so outbound bookkeeping
+    /// can match it).
+    pub message_id: String,
+    /// Identity of the reacting user — username where the platform provides
+    /// one, otherwise the platform user id.
+    pub actor: String,
+    /// The reaction emoji, either as a Unicode emoji (`"👍"`) or a platform
+    /// emoji name (`"+1"`, `"thumbsup"`).
+    pub emoji: String,
 }
 
 /// Message to send through a channel
@@ -90,12 +161,55 @@ impl SendMessage {
     }
 }
 
+/// Default outbound message size (characters) for channels that don't
+/// override [`Channel::outbound_message_limit`]. Conservative enough for
+/// most platforms; channels with stricter or looser caps override it
+/// (e.g. Telegram 4096, Discord 2000).
+pub const DEFAULT_OUTBOUND_MESSAGE_LIMIT: usize = 4000;
+
+/// Detailed outcome of an active channel connectivity probe.
+///
+/// Produced by [`Channel::health_check_detailed`] and consumed by
+/// `zeroclaw channel doctor` for latency, identity, and expiry reporting.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ChannelHealthReport {
+    /// Whether the probe succeeded.
+    pub healthy: bool,
+    /// Round-trip latency of the probe in milliseconds.
+    pub latency_ms: u64,
+    /// Authenticated identity reported by the platform (e.g. bot username).
+    pub identity: Option<String>,
+    /// Credential expiry warning when the platform API exposes one.
+    pub auth_expiry: Option<String>,
+    /// Human-readable failure detail for unhealthy probes.
+    pub detail: Option<String>,
+}
+
+/// A forum-topic (thread) administration action, issued via the `/topic`
+/// runtime command. Only channels with a native topic concept (Telegram
+/// forum supergroups) implement it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForumTopicAction {
+    /// Create a new topic with the given display name.
+    Create { name: String },
+    /// Close the topic identified by the platform thread id.
+    Close { thread_id: i64 },
+    /// Reopen a previously closed topic.
+    Reopen { thread_id: i64 },
+}
+
 /// Core channel trait — implement for any messaging platform
 #[async_trait]
 pub trait Channel: Send + Sync {
     /// Human-readable channel name
     fn name(&self) -> &str;
 
+    /// Maximum size (characters) of a single outbound message on this
+    /// platform. Longer content is split via `split_outbound_message`.
+    fn outbound_message_limit(&self) -> usize {
+        DEFAULT_OUTBOUND_MESSAGE_LIMIT
+    }
+
     /// Send a message through this channel
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()>;
 
@@ -121,6 +235,33 @@ pub trait Channel: Send + Sync {
         true
     }
 
+    /// Run an active connectivity probe, measuring round-trip latency.
+    ///
+    /// Default: times a plain [`Channel::health_check`] call. Channels whose
+    /// platform exposes an identity endpoint (Telegram `getMe`, Slack
+    /// `auth.test`, ...) override this to also report who the credentials
+    /// authenticate as and any token expiry the API surfaces.
+    async fn health_check_detailed(&self) -> ChannelHealthReport {
+        let started = std::time::Instant::now();
+        let healthy = self.health_check().await;
+        ChannelHealthReport {
+            healthy,
+            latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            ..ChannelHealthReport::default()
+        }
+    }
+
+    /// Live group respond policy for this channel, when it has one.
+    ///
+    /// The dispatcher uses this to apply `/respondmode` overrides to the
+    /// same policy object the listener consults. Channels without group
+    /// chats (or without the policy wired up) return `None`.
+    fn group_respond_policy(
+        &self,
+    ) -> Option<std::sync::Arc<super::respond_policy::GroupRespondPolicy>> {
+        None
+    }
+
     /// Signal that the bot is processing a response (e.g. "typing" indicator).
     /// Implementations should repeat the indicator as needed for their platform.
     async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
@@ -244,6 +385,20 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Execute a forum-topic administration action in `chat_id`, returning
+    /// a user-facing confirmation. Channels without a topic concept keep
+    /// the default, which reports the action as unsupported.
+    async fn manage_forum_topic(
+        &self,
+        _chat_id: &str,
+        _action: &ForumTopicAction,
+    ) -> anyhow::Result<String> {
+        anyhow::bail!(
+            "{}: forum topics are not supported on this channel",
+            self.name()
+        )
+    }
 }
 
 #[cfg(test)]
@@ -276,7 +431,11 @@ mod tests {
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -295,7 +454,11 @@ mod tests {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         let cloned = message.clone();
@@ -307,6 +470,55 @@ mod tests {
         assert_eq!(cloned.timestamp, 999);
     }
 
+    #[test]
+    fn message_payload_serde_round_trip() {
+        let location = MessagePayload::Location {
+            lat: 52.52,
+            lon: 13.405,
+            name: Some("Alexanderplatz".into()),
+        };
+        let json = serde_json::to_string(&location).unwrap();
+        assert!(json.contains("\"type\":\"location\""));
+        assert_eq!(
+            serde_json::from_str::<MessagePayload>(&json).unwrap(),
+            location
+        );
+
+        let contact = MessagePayload::Contact {
+            name: "Ada Lovelace".into(),
+            phone: "+441234567890".into(),
+        };
+        let json = serde_json::to_string(&contact).unwrap();
+        assert!(json.contains("\"type\":\"contact\""));
+        assert_eq!(
+            serde_json::from_str::<MessagePayload>(&json).unwrap(),
+            contact
+        );
+    }
+
+    #[test]
+    fn message_payload_summary_renders_text_form() {
+        let named = MessagePayload::Location {
+            lat: 52.52,
+            lon: 13.405,
+            name: Some("Alexanderplatz".into()),
+        };
+        assert_eq!(named.summary(), "[location: 52.52,13.40 — Alexanderplatz]");
+
+        let bare = MessagePayload::Location {
+            lat: -33.9,
+            lon: 151.2,
+            name: None,
+        };
+        assert_eq!(bare.summary(), "[location: -33.90,151.20]");
+
+        let contact = MessagePayload::Contact {
+            name: "Ada Lovelace".into(),
+            phone: "+441234567890".into(),
+        };
+        assert_eq!(contact.summary(), "[contact: Ada Lovelace — +441234567890]");
+    }
+
     #[tokio::test]
     async fn default_trait_methods_return_success() {
         let channel = DummyChannel;
@@ -320,6 +532,17 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    async fn default_health_check_detailed_wraps_health_check() {
+        let channel = DummyChannel;
+
+        let report = channel.health_check_detailed().await;
+        assert!(report.healthy);
+        assert!(report.identity.is_none());
+        assert!(report.auth_expiry.is_none());
+        assert!(report.detail.is_none());
+    }
+
     #[tokio::test]
     async fn default_reaction_methods_return_success() {
         let channel = DummyChannel;
@@ -687,6 +687,193 @@ impl TranscriptionProvider for LocalWhisperProvider {
     }
 }
 
+// ── whisper.cpp provider ────────────────────────────────────────
+
+/// Fully local transcription via the whisper.cpp CLI — audio never leaves
+/// the host. Voice notes are converted to the 16 kHz mono WAV whisper.cpp
+/// expects with an `ffmpeg` invocation, then fed to the binary with the
+/// configured model. Concurrency is bounded by a semaphore so a flood of
+/// voice notes queues instead of loading the model N times at once.
+///
+/// On failure the channels keep their existing fallback (`[Voice message]`
+/// placeholder content), so a broken local setup degrades to "please type"
+/// rather than dropping the message.
+pub struct WhisperCppProvider {
+    binary_path: String,
+    model_path: String,
+    ffmpeg_path: String,
+    language: Option<String>,
+    timeout_secs: u64,
+    semaphore: tokio::sync::Semaphore,
+}
+
+impl WhisperCppProvider {
+    /// Build from config. Fails early — with a readable error — if the
+    /// binary or model path is missing on disk, or if `max_concurrency`
+    /// or `timeout_secs` is zero.
+    pub fn from_config(config: &crate::config::WhisperCppConfig) -> Result<Self> {
+        let binary_path = config.binary_path.trim().to_string();
+        anyhow::ensure!(
+            !binary_path.is_empty(),
+            "whisper_cpp: `binary_path` must not be empty"
+        );
+        anyhow::ensure!(
+            std::path::Path::new(&binary_path).is_file(),
+            "whisper_cpp: binary not found at {binary_path:?}"
+        );
+
+        let model_path = config.model_path.trim().to_string();
+        anyhow::ensure!(
+            !model_path.is_empty(),
+            "whisper_cpp: `model_path` must not be empty"
+        );
+        anyhow::ensure!(
+            std::path::Path::new(&model_path).is_file(),
+            "whisper_cpp: model not found at {model_path:?}"
+        );
+
+        anyhow::ensure!(
+            config.max_concurrency > 0,
+            "whisper_cpp: `max_concurrency` must be greater than zero"
+        );
+        anyhow::ensure!(
+            config.timeout_secs > 0,
+            "whisper_cpp: `timeout_secs` must be greater than zero"
+        );
+
+        Ok(Self {
+            binary_path,
+            model_path,
+            ffmpeg_path: config.ffmpeg_path.clone(),
+            language: config.language.clone(),
+            timeout_secs: config.timeout_secs,
+            semaphore: tokio::sync::Semaphore::new(config.max_concurrency),
+        })
+    }
+}
+
+/// ffmpeg arguments converting any input audio to the 16 kHz mono WAV
+/// whisper.cpp expects.
+fn ffmpeg_wav_args(input: &std::path::Path, output: &std::path::Path) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = vec![
+        "-hide_banner".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-i".into(),
+        input.into(),
+        "-ar".into(),
+        "16000".into(),
+        "-ac".into(),
+        "1".into(),
+        "-f".into(),
+        "wav".into(),
+    ];
+    args.push(output.into());
+    args
+}
+
+/// whisper.cpp CLI arguments: model, input WAV, plain stdout output
+/// (`-nt` no timestamps, `-np` no progress prints), optional language hint.
+fn whisper_cpp_args(
+    model_path: &str,
+    wav: &std::path::Path,
+    language: Option<&str>,
+) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = vec![
+        "-m".into(),
+        model_path.into(),
+        "-f".into(),
+        wav.into(),
+        "-nt".into(),
+        "-np".into(),
+    ];
+    if let Some(lang) = language {
+        args.push("-l".into());
+        args.push(lang.into());
+    }
+    args
+}
+
+/// Convert an audio file to 16 kHz mono WAV via ffmpeg, within `timeout`.
+async fn convert_to_wav_16k(
+    ffmpeg_path: &str,
+    input: &std::path::Path,
+    output: &std::path::Path,
+    timeout: std::time::Duration,
+) -> Result<()> {
+    let result = tokio::time::timeout(
+        timeout,
+        tokio::process::Command::new(ffmpeg_path)
+            .args(ffmpeg_wav_args(input, output))
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("ffmpeg conversion timed out after {timeout:?}"))?
+    .with_context(|| format!("failed to run ffmpeg at {ffmpeg_path:?} — is it installed?"))?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        bail!("ffmpeg conversion failed: {}", stderr.trim());
+    }
+    anyhow::ensure!(
+        output.is_file(),
+        "ffmpeg reported success but produced no output file"
+    );
+    Ok(())
+}
+
+#[async_trait]
+impl TranscriptionProvider for WhisperCppProvider {
+    fn name(&self) -> &str {
+        "whisper_cpp"
+    }
+
+    async fn transcribe(&self, audio_data: &[u8], file_name: &str) -> Result<String> {
+        // Queue behind in-flight transcriptions; each whisper.cpp process
+        // loads the full model, so unbounded parallelism OOMs small hosts.
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .context("whisper_cpp: semaphore closed")?;
+
+        let work_dir = tempfile::tempdir().context("whisper_cpp: failed to create temp dir")?;
+        let input_path = work_dir.path().join(normalize_audio_filename(file_name));
+        tokio::fs::write(&input_path, audio_data)
+            .await
+            .context("whisper_cpp: failed to write audio to temp file")?;
+        let wav_path = work_dir.path().join("audio-16k.wav");
+
+        // One deadline covers conversion + inference.
+        let deadline = std::time::Duration::from_secs(self.timeout_secs);
+        let started = std::time::Instant::now();
+        convert_to_wav_16k(&self.ffmpeg_path, &input_path, &wav_path, deadline).await?;
+
+        let remaining = deadline.saturating_sub(started.elapsed());
+        let result = tokio::time::timeout(
+            remaining,
+            tokio::process::Command::new(&self.binary_path)
+                .args(whisper_cpp_args(
+                    &self.model_path,
+                    &wav_path,
+                    self.language.as_deref(),
+                ))
+                .output(),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("whisper.cpp timed out after {}s", self.timeout_secs))?
+        .with_context(|| format!("failed to run whisper.cpp at {:?}", self.binary_path))?;
+
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            bail!("whisper.cpp failed: {}", stderr.trim());
+        }
+
+        Ok(String::from_utf8_lossy(&result.stdout).trim().to_string())
+    }
+}
+
 // ── Shared response parsing ─────────────────────────────────────
 
 /// Parse a faster-whisper-compatible JSON response (`{ "text": "..." }`).
@@ -773,6 +960,17 @@ impl TranscriptionManager {
             }
         }
 
+        if let Some(ref whisper_cpp_cfg) = config.whisper_cpp {
+            match WhisperCppProvider::from_config(whisper_cpp_cfg) {
+                Ok(p) => {
+                    providers.insert("whisper_cpp".to_string(), Box::new(p));
+                }
+                Err(e) => {
+                    tracing::warn!("whisper_cpp config invalid, provider skipped: {e}");
+                }
+            }
+        }
+
         let default_provider = config.default_provider.clone();
 
         if config.enabled && !providers.contains_key(&default_provider) {
@@ -876,6 +1074,13 @@ pub async fn transcribe_audio(
             let google = GoogleSttProvider::from_config(google_cfg)?;
             google.transcribe(&audio_data, file_name).await
         }
+        "whisper_cpp" => {
+            let whisper_cpp_cfg = config.whisper_cpp.as_ref().context(
+                "Default transcription provider 'whisper_cpp' is not configured. Add [transcription.whisper_cpp]",
+            )?;
+            let whisper_cpp = WhisperCppProvider::from_config(whisper_cpp_cfg)?;
+            whisper_cpp.transcribe(&audio_data, file_name).await
+        }
         other => bail!("Unsupported transcription provider '{other}'"),
     }
 }
@@ -1420,4 +1625,123 @@ mod tests {
             "expected plain-text body in error, got: {err}"
         );
     }
+
+    // ── whisper.cpp provider ────────────────────────────────────
+
+    fn whisper_cpp_config(binary: &str, model: &str) -> crate::config::WhisperCppConfig {
+        crate::config::WhisperCppConfig {
+            binary_path: binary.to_string(),
+            model_path: model.to_string(),
+            ffmpeg_path: "ffmpeg".to_string(),
+            max_concurrency: 1,
+            timeout_secs: 120,
+            language: None,
+        }
+    }
+
+    #[test]
+    fn whisper_cpp_rejects_missing_binary() {
+        let err = WhisperCppProvider::from_config(&whisper_cpp_config(
+            "/nonexistent/whisper-cli",
+            "/nonexistent/model.bin",
+        ))
+        .map(|_| ())
+        .unwrap_err();
+        assert!(err.to_string().contains("binary not found"), "got: {err}");
+    }
+
+    #[test]
+    fn whisper_cpp_rejects_missing_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("whisper-cli");
+        std::fs::write(&binary, b"#!/bin/sh\n").unwrap();
+
+        let err = WhisperCppProvider::from_config(&whisper_cpp_config(
+            binary.to_str().unwrap(),
+            "/nonexistent/model.bin",
+        ))
+        .map(|_| ())
+        .unwrap_err();
+        assert!(err.to_string().contains("model not found"), "got: {err}");
+    }
+
+    #[test]
+    fn ffmpeg_wav_args_request_16k_mono_wav() {
+        let args = ffmpeg_wav_args(
+            std::path::Path::new("/tmp/in.ogg"),
+            std::path::Path::new("/tmp/out.wav"),
+        );
+        let rendered: Vec<String> = args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let joined = rendered.join(" ");
+        assert!(joined.contains("-ar 16000"), "got: {joined}");
+        assert!(joined.contains("-ac 1"), "got: {joined}");
+        assert!(joined.contains("-f wav"), "got: {joined}");
+        assert_eq!(rendered.last().unwrap(), "/tmp/out.wav");
+    }
+
+    #[test]
+    fn whisper_cpp_args_include_language_hint_when_set() {
+        let wav = std::path::Path::new("/tmp/audio-16k.wav");
+        let without: Vec<String> = whisper_cpp_args("/opt/model.bin", wav, None)
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(!without.contains(&"-l".to_string()));
+
+        let with: Vec<String> = whisper_cpp_args("/opt/model.bin", wav, Some("de"))
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let lang_flag = with.iter().position(|a| a == "-l").unwrap();
+        assert_eq!(with[lang_flag + 1], "de");
+    }
+
+    /// Converts the repo's small MP3 fixture to 16 kHz WAV via a real ffmpeg
+    /// run. Skipped when ffmpeg isn't on `PATH` (e.g. minimal CI images).
+    #[tokio::test]
+    async fn convert_to_wav_16k_converts_fixture() {
+        if std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .is_err()
+        {
+            eprintln!("ffmpeg not found on PATH — skipping conversion fixture test");
+            return;
+        }
+
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hello.mp3");
+        let dir = tempfile::tempdir().unwrap();
+        let wav = dir.path().join("audio-16k.wav");
+
+        convert_to_wav_16k("ffmpeg", &fixture, &wav, std::time::Duration::from_secs(30))
+            .await
+            .expect("conversion should succeed");
+
+        let bytes = std::fs::read(&wav).unwrap();
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        // fmt chunk: sample rate lives at byte offset 24 in a canonical header.
+        let sample_rate = u32::from_le_bytes(bytes[24..28].try_into().unwrap());
+        assert_eq!(sample_rate, 16_000);
+        let channels = u16::from_le_bytes(bytes[22..24].try_into().unwrap());
+        assert_eq!(channels, 1);
+    }
+
+    #[tokio::test]
+    async fn convert_to_wav_16k_reports_missing_ffmpeg() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = convert_to_wav_16k(
+            "/nonexistent/ffmpeg",
+            &dir.path().join("in.ogg"),
+            &dir.path().join("out.wav"),
+            std::time::Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("is it installed"), "got: {err}");
+    }
 }
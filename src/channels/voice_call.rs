@@ -367,7 +367,11 @@ impl VoiceCallChannel {
             thread_ts: Some(call_id.to_string()),
             reply_to_message_id: None,
             interruption_scope_id: Some(call_id.to_string()),
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
         tx.send(msg)
             .await
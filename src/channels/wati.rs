@@ -212,7 +212,11 @@ impl WatiChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
@@ -356,7 +360,11 @@ impl WatiChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         });
 
         messages
@@ -726,6 +734,7 @@ mod tests {
             google: None,
             local_whisper: None,
             transcribe_non_ptt_audio: false,
+            whisper_cpp: None,
         };
 
         let ch = WatiChannel::new(
@@ -756,6 +765,7 @@ mod tests {
             google: None,
             local_whisper: None,
             transcribe_non_ptt_audio: false,
+            whisper_cpp: None,
         };
 
         let ch = WatiChannel::new(
@@ -799,6 +809,7 @@ mod tests {
             google: None,
             local_whisper: None,
             transcribe_non_ptt_audio: false,
+            whisper_cpp: None,
         };
 
         let ch = WatiChannel::new(
@@ -946,6 +957,7 @@ mod tests {
                 timeout_secs: 300,
             }),
             transcribe_non_ptt_audio: false,
+            whisper_cpp: None,
         };
 
         let ch = WatiChannel::new(
@@ -999,6 +1011,7 @@ mod tests {
                 timeout_secs: 300,
             }),
             transcribe_non_ptt_audio: false,
+            whisper_cpp: None,
         };
 
         let ch = WatiChannel::new(
@@ -2,6 +2,16 @@ use super::traits::{Channel, ChannelMessage, SendMessage};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Maximum delivery attempts for an outbound callback.
+const SEND_MAX_ATTEMPTS: u32 = 3;
+
+/// Base delay before the first retry; doubles per attempt.
+const SEND_RETRY_BASE: Duration = Duration::from_millis(250);
+
+/// How long the health-check ping of the callback URL may take.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Generic Webhook channel — receives messages via HTTP POST and sends replies
 /// to a configurable outbound URL. This is the "universal adapter" for any system
@@ -20,7 +30,7 @@ pub struct WebhookChannel {
 struct IncomingWebhook {
     sender: String,
     content: String,
-    #[serde(default)]
+    #[serde(default, alias = "reply_to")]
     thread_id: Option<String>,
 }
 
@@ -34,6 +44,25 @@ struct OutgoingWebhook {
     recipient: Option<String>,
 }
 
+/// HMAC-SHA256 signature over `body`, formatted as the `sha256=<hex>` header
+/// value used for both inbound verification and outbound signing.
+fn sign_body(secret: &str, body: &[u8]) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether a failed delivery is worth retrying: server errors and rate limits
+/// are transient, everything else (auth, bad payload) will fail again.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
 impl WebhookChannel {
     pub fn new(
         listen_port: u16,
@@ -95,55 +124,11 @@ impl WebhookChannel {
 
         mac.verify_slice(&expected).is_ok()
     }
-}
-
-#[async_trait]
-impl Channel for WebhookChannel {
-    fn name(&self) -> &str {
-        "webhook"
-    }
-
-    async fn send(&self, message: &SendMessage) -> Result<()> {
-        let Some(ref send_url) = self.send_url else {
-            tracing::debug!("Webhook channel: no send_url configured, skipping outbound message");
-            return Ok(());
-        };
-
-        let client = self.http_client();
-        let payload = OutgoingWebhook {
-            content: message.content.clone(),
-            thread_id: message.thread_ts.clone(),
-            recipient: if message.recipient.is_empty() {
-                None
-            } else {
-                Some(message.recipient.clone())
-            },
-        };
-
-        let mut request = match self.send_method.as_str() {
-            "PUT" => client.put(send_url),
-            _ => client.post(send_url),
-        };
-
-        if let Some(ref auth) = self.auth_header {
-            request = request.header("Authorization", auth);
-        }
-
-        let resp = request.json(&payload).send().await?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let body = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
-            bail!("Webhook send failed ({status}): {body}");
-        }
-
-        Ok(())
-    }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    /// Build the axum router serving the inbound webhook endpoint. Split out
+    /// of [`Channel::listen`] so integration tests can drive the handler
+    /// in-process without binding the configured port.
+    pub fn router(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> axum::Router {
         use axum::{
             body::Bytes,
             extract::State,
@@ -154,22 +139,18 @@ impl Channel for WebhookChannel {
         use portable_atomic::{AtomicU64, Ordering};
         use std::sync::Arc;
 
-        let counter = Arc::new(AtomicU64::new(0));
-
         struct WebhookState {
             tx: tokio::sync::mpsc::Sender<ChannelMessage>,
             secret: Option<String>,
-            counter: Arc<AtomicU64>,
+            counter: AtomicU64,
         }
 
         let state = Arc::new(WebhookState {
-            tx: tx.clone(),
+            tx,
             secret: self.secret.clone(),
-            counter: counter.clone(),
+            counter: AtomicU64::new(0),
         });
 
-        let listen_path = self.listen_path.clone();
-
         async fn handle_webhook(
             State(state): State<Arc<WebhookState>>,
             headers: HeaderMap,
@@ -239,7 +220,11 @@ impl Channel for WebhookChannel {
                 thread_ts: payload.thread_id,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             };
 
             if state.tx.send(msg).await.is_err() {
@@ -249,9 +234,91 @@ impl Channel for WebhookChannel {
             StatusCode::OK
         }
 
-        let app = Router::new()
-            .route(&listen_path, post(handle_webhook))
-            .with_state(state);
+        Router::new()
+            .route(&self.listen_path, post(handle_webhook))
+            .with_state(state)
+    }
+}
+
+#[async_trait]
+impl Channel for WebhookChannel {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let Some(ref send_url) = self.send_url else {
+            tracing::debug!("Webhook channel: no send_url configured, skipping outbound message");
+            return Ok(());
+        };
+
+        let client = self.http_client();
+        let payload = OutgoingWebhook {
+            content: message.content.clone(),
+            thread_id: message.thread_ts.clone(),
+            recipient: if message.recipient.is_empty() {
+                None
+            } else {
+                Some(message.recipient.clone())
+            },
+        };
+
+        // Serialize once so the signature covers the exact bytes sent.
+        let body = serde_json::to_vec(&payload)?;
+
+        let mut last_error = None;
+        for attempt in 1..=SEND_MAX_ATTEMPTS {
+            let mut request = match self.send_method.as_str() {
+                "PUT" => client.put(send_url),
+                _ => client.post(send_url),
+            };
+
+            request = request.header("Content-Type", "application/json");
+            if let Some(ref auth) = self.auth_header {
+                request = request.header("Authorization", auth);
+            }
+            if let Some(ref secret) = self.secret {
+                request = request.header("X-Webhook-Signature", sign_body(secret, &body));
+            }
+
+            match request.body(body.clone()).send().await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status.is_success() {
+                        return Ok(());
+                    }
+                    let resp_body = resp
+                        .text()
+                        .await
+                        .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+                    if !is_retryable_status(status) {
+                        bail!("Webhook send failed ({status}): {resp_body}");
+                    }
+                    last_error = Some(anyhow::anyhow!(
+                        "Webhook send failed ({status}): {resp_body}"
+                    ));
+                }
+                Err(e) => {
+                    last_error = Some(anyhow::Error::new(e).context("Webhook send request failed"));
+                }
+            }
+
+            if attempt < SEND_MAX_ATTEMPTS {
+                let backoff = SEND_RETRY_BASE * 2u32.pow(attempt - 1);
+                tracing::warn!(
+                    "Webhook send attempt {attempt}/{SEND_MAX_ATTEMPTS} failed, retrying in {backoff:?}"
+                );
+                tokio::time::sleep(backoff).await;
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            anyhow::anyhow!("Webhook send failed after {SEND_MAX_ATTEMPTS} attempts")
+        }))
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let app = self.router(tx);
 
         let addr = std::net::SocketAddr::from(([0, 0, 0, 0], self.listen_port));
         tracing::info!(
@@ -269,9 +336,27 @@ impl Channel for WebhookChannel {
     }
 
     async fn health_check(&self) -> bool {
-        // Webhook channel is healthy if the port can be bound (basic check).
-        // In practice, once listen() starts the server is running.
-        true
+        // Healthy when the outbound callback URL is reachable. Any HTTP
+        // response counts (the endpoint may reject HEAD with 405); only a
+        // transport-level failure marks the channel unhealthy. Inbound-only
+        // configurations have nothing to probe.
+        let Some(ref send_url) = self.send_url else {
+            return true;
+        };
+
+        match self
+            .http_client()
+            .head(send_url)
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::warn!("Webhook health check failed for {send_url}: {e}");
+                false
+            }
+        }
     }
 }
 
@@ -350,6 +435,13 @@ mod tests {
         assert!(payload.thread_id.is_none());
     }
 
+    #[test]
+    fn incoming_payload_accepts_reply_to_alias() {
+        let json = r#"{"sender": "bob", "content": "hi", "reply_to": "t9"}"#;
+        let payload: IncomingWebhook = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.thread_id.as_deref(), Some("t9"));
+    }
+
     #[test]
     fn outgoing_payload_serializes_content() {
         let payload = OutgoingWebhook {
@@ -409,4 +501,26 @@ mod tests {
         let ch = make_channel_with_secret();
         assert!(!ch.verify_signature(b"body", Some("badhex")));
     }
+
+    #[test]
+    fn sign_body_round_trips_through_verify() {
+        let ch = make_channel_with_secret();
+        let body = b"outbound payload";
+        let sig = sign_body("mysecret", body);
+        assert!(sig.starts_with("sha256="));
+        assert!(ch.verify_signature(body, Some(&sig)));
+    }
+
+    #[test]
+    fn retryable_status_classification() {
+        assert!(is_retryable_status(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        ));
+        assert!(is_retryable_status(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(is_retryable_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(reqwest::StatusCode::UNAUTHORIZED));
+    }
 }
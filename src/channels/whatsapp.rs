@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, MessagePayload, SendMessage};
 use async_trait::async_trait;
 use regex::Regex;
 use uuid::Uuid;
@@ -173,6 +173,39 @@ impl WhatsAppChannel {
     }
 
     /// Parse an incoming webhook payload from Meta and extract messages
+    /// Extract a structured payload from a Cloud API `location` or
+    /// `contacts` message.
+    fn extract_message_payload(msg: &serde_json::Value) -> Option<MessagePayload> {
+        if let Some(location) = msg.get("location") {
+            let lat = location
+                .get("latitude")
+                .and_then(serde_json::Value::as_f64)?;
+            let lon = location
+                .get("longitude")
+                .and_then(serde_json::Value::as_f64)?;
+            let name = location
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string);
+            return Some(MessagePayload::Location { lat, lon, name });
+        }
+        let contact = msg.get("contacts").and_then(|c| c.as_array())?.first()?;
+        let name = contact
+            .get("name")
+            .and_then(|n| n.get("formatted_name"))
+            .and_then(serde_json::Value::as_str)?;
+        let phone = contact
+            .get("phones")
+            .and_then(|p| p.as_array())
+            .and_then(|p| p.first())
+            .and_then(|p| p.get("phone"))
+            .and_then(serde_json::Value::as_str)?;
+        Some(MessagePayload::Contact {
+            name: name.to_string(),
+            phone: phone.to_string(),
+        })
+    }
+
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
         let mut messages = Vec::new();
 
@@ -219,13 +252,17 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
+                    // Extract text content; location and contact shares carry
+                    // a structured payload plus a textual summary instead.
+                    let payload = Self::extract_message_payload(msg);
                     let content = if let Some(text_obj) = msg.get("text") {
                         text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
                             .to_string()
+                    } else if let Some(ref payload) = payload {
+                        payload.summary()
                     } else {
                         // Could be image, audio, etc. — skip for now
                         tracing::debug!("WhatsApp: skipping non-text message from {from}");
@@ -240,19 +277,25 @@ impl WhatsAppChannel {
                     // DMs and group_mention_patterns for groups. When the
                     // applicable pattern set is non-empty, messages without a
                     // match are dropped and matched fragments are stripped.
+                    // Shared pins/contacts are deliberate sends — they bypass
+                    // the gate since a summary can't contain a mention.
                     let is_group = Self::is_group_message(msg);
-                    let content = match Self::apply_mention_gating(
-                        &self.dm_mention_patterns,
-                        &self.group_mention_patterns,
-                        &content,
-                        is_group,
-                    ) {
-                        Some(c) => c,
-                        None => {
-                            tracing::debug!(
-                                "WhatsApp: message from {from} did not match mention patterns, dropping"
-                            );
-                            continue;
+                    let content = if payload.is_some() {
+                        content
+                    } else {
+                        match Self::apply_mention_gating(
+                            &self.dm_mention_patterns,
+                            &self.group_mention_patterns,
+                            &content,
+                            is_group,
+                        ) {
+                            Some(c) => c,
+                            None => {
+                                tracing::debug!(
+                                    "WhatsApp: message from {from} did not match mention patterns, dropping"
+                                );
+                                continue;
+                            }
                         }
                     };
 
@@ -278,7 +321,11 @@ impl WhatsAppChannel {
                         thread_ts: None,
                         reply_to_message_id: None,
                         interruption_scope_id: None,
+                        is_edit: false,
+                        is_group,
+                        payload,
                         attachments: vec![],
+                        metadata: None,
                     });
                 }
             }
@@ -321,33 +368,54 @@ impl Channel for WhatsAppChannel {
             .strip_prefix('+')
             .unwrap_or(&message.recipient);
 
-        let body = serde_json::json!({
-            "messaging_product": "whatsapp",
-            "recipient_type": "individual",
-            "to": to,
-            "type": "text",
-            "text": {
-                "preview_url": false,
-                "body": message.content
-            }
-        });
-
         ensure_https(&url)?;
 
-        let resp = self
-            .http_client()
-            .post(&url)
-            .bearer_auth(&self.access_token)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+        // `[LOCATION:lat,lon]` markers become real location messages; any
+        // remaining text is sent first as a normal text message.
+        let (text, locations) = super::parse_location_markers(&message.content);
+
+        let mut bodies = Vec::new();
+        if !text.is_empty() || locations.is_empty() {
+            bodies.push(serde_json::json!({
+                "messaging_product": "whatsapp",
+                "recipient_type": "individual",
+                "to": to,
+                "type": "text",
+                "text": {
+                    "preview_url": false,
+                    "body": text
+                }
+            }));
+        }
+        for (lat, lon) in locations {
+            bodies.push(serde_json::json!({
+                "messaging_product": "whatsapp",
+                "recipient_type": "individual",
+                "to": to,
+                "type": "location",
+                "location": {
+                    "latitude": lat,
+                    "longitude": lon
+                }
+            }));
+        }
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let error_body = resp.text().await.unwrap_or_default();
-            tracing::error!("WhatsApp send failed: {status} — {error_body}");
-            anyhow::bail!("WhatsApp API error: {status}");
+        for body in bodies {
+            let resp = self
+                .http_client()
+                .post(&url)
+                .bearer_auth(&self.access_token)
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let error_body = resp.text().await.unwrap_or_default();
+                tracing::error!("WhatsApp send failed: {status} — {error_body}");
+                anyhow::bail!("WhatsApp API error: {status}");
+            }
         }
 
         Ok(())
@@ -476,6 +544,82 @@ mod tests {
         assert_eq!(msgs[0].timestamp, 1_699_999_999);
     }
 
+    #[test]
+    fn whatsapp_parse_location_share() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [{
+                "id": "123",
+                "changes": [{
+                    "value": {
+                        "messages": [{
+                            "from": "1234567890",
+                            "id": "wamid.loc",
+                            "timestamp": "1699999999",
+                            "type": "location",
+                            "location": {
+                                "latitude": 52.52,
+                                "longitude": 13.405,
+                                "name": "Alexanderplatz"
+                            }
+                        }]
+                    },
+                    "field": "messages"
+                }]
+            }]
+        });
+
+        let msgs = ch.parse_webhook_payload(&payload);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].content, "[location: 52.52,13.40 — Alexanderplatz]");
+        assert_eq!(
+            msgs[0].payload,
+            Some(MessagePayload::Location {
+                lat: 52.52,
+                lon: 13.405,
+                name: Some("Alexanderplatz".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn whatsapp_parse_contact_share() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [{
+                "id": "123",
+                "changes": [{
+                    "value": {
+                        "messages": [{
+                            "from": "1234567890",
+                            "id": "wamid.contact",
+                            "timestamp": "1699999999",
+                            "type": "contacts",
+                            "contacts": [{
+                                "name": { "formatted_name": "Ada Lovelace" },
+                                "phones": [{ "phone": "+441234567890" }]
+                            }]
+                        }]
+                    },
+                    "field": "messages"
+                }]
+            }]
+        });
+
+        let msgs = ch.parse_webhook_payload(&payload);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].content, "[contact: Ada Lovelace — +441234567890]");
+        assert_eq!(
+            msgs[0].payload,
+            Some(MessagePayload::Contact {
+                name: "Ada Lovelace".into(),
+                phone: "+441234567890".into(),
+            })
+        );
+    }
+
     #[test]
     fn whatsapp_parse_unauthorized_number() {
         let ch = make_channel();
@@ -1344,4 +1344,34 @@ mod tests {
             .unwrap()
             .is_some());
     }
+
+    #[cfg(feature = "whatsapp-web")]
+    #[tokio::test]
+    async fn logout_session_purge_forces_fresh_pairing() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let store = RusqliteStore::new(tmp.path()).unwrap();
+
+        DeviceStoreTrait::save(&store, &CoreDevice::default())
+            .await
+            .unwrap();
+        assert!(DeviceStoreTrait::exists(&store).await.unwrap());
+
+        // Simulate Event::LoggedOut: the channel closes the store, then removes
+        // the session database plus its WAL/SHM sidecars.
+        drop(store);
+        let base = tmp.path().to_string_lossy().to_string();
+        for path in [base.clone(), format!("{base}-wal"), format!("{base}-shm")] {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => panic!("failed to remove {path}: {e}"),
+            }
+        }
+
+        // A fresh store on the same path must report no saved device — this is
+        // what sends the reconnect loop back through QR pairing.
+        let store = RusqliteStore::new(tmp.path()).unwrap();
+        assert!(!DeviceStoreTrait::exists(&store).await.unwrap());
+        assert!(DeviceStoreTrait::load(&store).await.unwrap().is_none());
+    }
 }
@@ -35,6 +35,18 @@ use std::sync::Arc;
 use tokio::select;
 use wa_rs_proto::whatsapp::device_props::PlatformType;
 
+/// Pairing/session lifecycle state for the WhatsApp Web channel.
+#[cfg(feature = "whatsapp-web")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// Connecting or restoring an existing session.
+    Connecting,
+    /// Logged in with a live connection.
+    Connected,
+    /// No session (or session revoked) — QR pairing required.
+    NeedsPairing,
+}
+
 /// WhatsApp Web channel using wa-rs with custom rusqlite storage
 ///
 /// # Status: Functional Implementation
@@ -91,6 +103,10 @@ pub struct WhatsAppWebChannel {
     /// When non-empty, only group messages matching at least one pattern are
     /// processed; matched fragments are stripped from the forwarded content.
     group_mention_patterns: Arc<Vec<regex::Regex>>,
+    /// Current pairing/session lifecycle state (shared with the event handler).
+    session_state: Arc<Mutex<SessionState>>,
+    /// When set, the pairing QR code is also written to this path as a PNG.
+    qr_png_path: Option<std::path::PathBuf>,
 }
 
 impl WhatsAppWebChannel {
@@ -136,9 +152,18 @@ impl WhatsAppWebChannel {
             voice_chats: Arc::new(std::sync::Mutex::new(std::collections::HashSet::new())),
             dm_mention_patterns: Arc::new(Vec::new()),
             group_mention_patterns: Arc::new(Vec::new()),
+            session_state: Arc::new(Mutex::new(SessionState::Connecting)),
+            qr_png_path: None,
         }
     }
 
+    /// Also write the pairing QR code as a PNG to this path when one is issued.
+    #[cfg(feature = "whatsapp-web")]
+    pub fn with_qr_png_path(mut self, path: std::path::PathBuf) -> Self {
+        self.qr_png_path = Some(path);
+        self
+    }
+
     /// Configure voice transcription (STT) for incoming voice notes.
     #[cfg(feature = "whatsapp-web")]
     pub fn with_transcription(mut self, config: crate::config::TranscriptionConfig) -> Self {
@@ -305,6 +330,139 @@ impl WhatsAppWebChannel {
             .build())
     }
 
+    /// Hint surfaced in errors and health reports while the session is unpaired.
+    #[cfg(feature = "whatsapp-web")]
+    const REPAIR_HINT: &'static str =
+        "channel needs re-pairing, run `zeroclaw channel pair-whatsapp`";
+
+    /// Whether the session currently requires QR pairing.
+    #[cfg(feature = "whatsapp-web")]
+    fn needs_pairing(&self) -> bool {
+        *self.session_state.lock() == SessionState::NeedsPairing
+    }
+
+    /// Record a session-state transition: updates the shared state, the
+    /// process-wide health registry, and the runtime trace. No-op when the
+    /// state is unchanged.
+    #[cfg(feature = "whatsapp-web")]
+    fn note_session_state(state: &Arc<Mutex<SessionState>>, new_state: SessionState) {
+        let previous = {
+            let mut guard = state.lock();
+            std::mem::replace(&mut *guard, new_state)
+        };
+        if previous == new_state {
+            return;
+        }
+
+        match new_state {
+            SessionState::Connected => {
+                crate::health::mark_component_ok("whatsapp_web");
+                crate::observability::runtime_trace::record_event(
+                    "channel_session",
+                    Some("whatsapp"),
+                    None,
+                    None,
+                    None,
+                    Some(true),
+                    Some("WhatsApp Web session connected"),
+                    serde_json::json!({ "state": "connected" }),
+                );
+            }
+            SessionState::NeedsPairing => {
+                crate::health::mark_component_error("whatsapp_web", Self::REPAIR_HINT);
+                crate::observability::runtime_trace::record_event(
+                    "channel_session",
+                    Some("whatsapp"),
+                    None,
+                    None,
+                    None,
+                    Some(false),
+                    Some(Self::REPAIR_HINT),
+                    serde_json::json!({ "state": "needs_pairing" }),
+                );
+            }
+            SessionState::Connecting => {}
+        }
+    }
+
+    /// Write the pairing QR payload as a PNG (8px per module, 4-module quiet zone).
+    #[cfg(feature = "whatsapp-web")]
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_qr_png(code: &str, path: &std::path::Path) -> Result<()> {
+        const SCALE: usize = 8;
+        const QUIET: usize = 4;
+
+        let payload = code.trim();
+        if payload.is_empty() {
+            anyhow::bail!("QR payload is empty");
+        }
+
+        let qr = qrcode::QrCode::new(payload.as_bytes())
+            .map_err(|err| anyhow!("Failed to encode WhatsApp Web QR payload: {err}"))?;
+        let width = qr.width();
+        let colors = qr.to_colors();
+
+        let size = ((width + 2 * QUIET) * SCALE) as u32;
+        let mut img = image::GrayImage::from_pixel(size, size, image::Luma([255u8]));
+        for (idx, color) in colors.iter().enumerate() {
+            if *color == qrcode::Color::Dark {
+                let x0 = ((idx % width + QUIET) * SCALE) as u32;
+                let y0 = ((idx / width + QUIET) * SCALE) as u32;
+                for dy in 0..SCALE as u32 {
+                    for dx in 0..SCALE as u32 {
+                        img.put_pixel(x0 + dx, y0 + dy, image::Luma([0u8]));
+                    }
+                }
+            }
+        }
+
+        img.save(path)
+            .map_err(|err| anyhow!("Failed to write QR PNG to {}: {err}", path.display()))
+    }
+
+    /// Run the interactive pairing flow: starts the bot, prints the QR code
+    /// (and optionally writes it as a PNG), and blocks until the session
+    /// connects or `timeout` elapses. Backs `zeroclaw channel pair-whatsapp`.
+    #[cfg(feature = "whatsapp-web")]
+    pub async fn pair(self: Arc<Self>, timeout: std::time::Duration) -> Result<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<ChannelMessage>(16);
+        let listener = {
+            let channel = self.clone();
+            tokio::spawn(async move { channel.listen(tx).await })
+        };
+        // Drain (and drop) inbound messages — pairing mode does not process them.
+        let drain = tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        let paired = async {
+            loop {
+                if *self.session_state.lock() == SessionState::Connected {
+                    return;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+            }
+        };
+
+        let mut listener = listener;
+        let outcome = select! {
+            () = paired => Ok(()),
+            res = &mut listener => match res {
+                Ok(Ok(())) => Err(anyhow!(
+                    "WhatsApp Web listener exited before pairing completed"
+                )),
+                Ok(Err(e)) => Err(e),
+                Err(join_err) => Err(anyhow!("WhatsApp Web listener crashed: {join_err}")),
+            },
+            () = tokio::time::sleep(timeout) => Err(anyhow!(
+                "Timed out after {}s waiting for WhatsApp pairing; scan the QR code and retry",
+                timeout.as_secs()
+            )),
+        };
+
+        listener.abort();
+        drain.abort();
+        outcome
+    }
+
     /// Convert a recipient to a wa-rs JID.
     ///
     /// Supports:
@@ -512,6 +670,9 @@ impl Channel for WhatsAppWebChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> Result<()> {
+        if self.needs_pairing() {
+            anyhow::bail!("WhatsApp Web session expired: {}", Self::REPAIR_HINT);
+        }
         let client = self.client.lock().clone();
         let Some(client) = client else {
             anyhow::bail!("WhatsApp Web client not connected. Initialize the bot first.");
@@ -662,10 +823,12 @@ impl Channel for WhatsAppWebChannel {
                 } else {
                     anyhow::bail!("Device exists but failed to load");
                 }
+                Self::note_session_state(&self.session_state, SessionState::Connecting);
             } else {
                 tracing::info!(
                     "WhatsApp Web: no existing session, new device will be created during pairing"
                 );
+                Self::note_session_state(&self.session_state, SessionState::NeedsPairing);
             };
 
             // Create transport factory
@@ -698,6 +861,8 @@ impl Channel for WhatsAppWebChannel {
             let wa_self_chat_mode = self.self_chat_mode;
             let wa_dm_mention_patterns = self.dm_mention_patterns.clone();
             let wa_group_mention_patterns = self.group_mention_patterns.clone();
+            let session_state = self.session_state.clone();
+            let qr_png_path = self.qr_png_path.clone();
 
             let mut builder = Bot::builder()
                 .with_backend(backend)
@@ -722,9 +887,20 @@ impl Channel for WhatsAppWebChannel {
                     let wa_group_policy = wa_group_policy.clone();
                     let wa_dm_mention_patterns = wa_dm_mention_patterns.clone();
                     let wa_group_mention_patterns = wa_group_mention_patterns.clone();
+                    let session_state = session_state.clone();
+                    let qr_png_path = qr_png_path.clone();
                     async move {
                         match event {
                             Event::Message(msg, info) => {
+                                // While unpaired, suspend inbound processing
+                                // instead of feeding messages to an agent that
+                                // cannot reply through this session.
+                                if *session_state.lock() == SessionState::NeedsPairing {
+                                    tracing::debug!(
+                                        "WhatsApp Web: dropping inbound message while unpaired"
+                                    );
+                                    return;
+                                }
                                 let sender_jid = info.source.sender.clone();
                                 let sender_alt = info.source.sender_alt.clone();
                                 let sender = sender_jid.user().to_string();
@@ -914,7 +1090,11 @@ impl Channel for WhatsAppWebChannel {
                                         thread_ts: None,
                                         reply_to_message_id: None,
                                         interruption_scope_id: None,
-                    attachments: vec![],
+                                        is_edit: false,
+                                        is_group: false,
+                                        payload: None,
+                                        attachments: vec![],
+                                        metadata: None,
                                     })
                                     .await
                                 {
@@ -924,18 +1104,27 @@ impl Channel for WhatsAppWebChannel {
                             Event::Connected(_) => {
                                 tracing::info!("WhatsApp Web connected successfully");
                                 WhatsAppWebChannel::reset_retry(&retry_count);
+                                Self::note_session_state(&session_state, SessionState::Connected);
                             }
                             Event::LoggedOut(_) => {
                                 session_revoked.store(true, std::sync::atomic::Ordering::Relaxed);
                                 tracing::warn!(
                                     "WhatsApp Web was logged out — will clear session and reconnect"
                                 );
+                                Self::note_session_state(
+                                    &session_state,
+                                    SessionState::NeedsPairing,
+                                );
                                 let _ = logout_tx.send(());
                             }
                             Event::StreamError(stream_error) => {
                                 tracing::error!("WhatsApp Web stream error: {:?}", stream_error);
                             }
                             Event::PairingCode { code, .. } => {
+                                Self::note_session_state(
+                                    &session_state,
+                                    SessionState::NeedsPairing,
+                                );
                                 tracing::info!("WhatsApp Web pair code received");
                                 tracing::info!(
                                     "Link your phone by entering this code in WhatsApp > Linked Devices"
@@ -945,9 +1134,26 @@ impl Channel for WhatsAppWebChannel {
                                 eprintln!();
                             }
                             Event::PairingQrCode { code, .. } => {
+                                Self::note_session_state(
+                                    &session_state,
+                                    SessionState::NeedsPairing,
+                                );
                                 tracing::info!(
                                     "WhatsApp Web QR code received (scan with WhatsApp > Linked Devices)"
                                 );
+                                if let Some(ref png_path) = qr_png_path {
+                                    match Self::write_qr_png(&code, png_path) {
+                                        Ok(()) => {
+                                            eprintln!(
+                                                "QR code also written to {}",
+                                                png_path.display()
+                                            );
+                                        }
+                                        Err(err) => tracing::warn!(
+                                            "WhatsApp Web: failed to write QR PNG: {err}"
+                                        ),
+                                    }
+                                }
                                 match Self::render_pairing_qr(&code) {
                                     Ok(rendered) => {
                                         eprintln!();
@@ -1078,10 +1284,24 @@ impl Channel for WhatsAppWebChannel {
     }
 
     async fn health_check(&self) -> bool {
+        if self.needs_pairing() {
+            return false;
+        }
         let bot_handle_guard = self.bot_handle.lock();
         bot_handle_guard.is_some()
     }
 
+    async fn health_check_detailed(&self) -> super::traits::ChannelHealthReport {
+        let started = std::time::Instant::now();
+        let healthy = self.health_check().await;
+        super::traits::ChannelHealthReport {
+            healthy,
+            latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            detail: (!healthy && self.needs_pairing()).then(|| Self::REPAIR_HINT.to_string()),
+            ..super::traits::ChannelHealthReport::default()
+        }
+    }
+
     async fn start_typing(&self, recipient: &str) -> Result<()> {
         let client = self.client.lock().clone();
         let Some(client) = client else {
@@ -1347,6 +1567,57 @@ mod tests {
         assert!(!ch.health_check().await);
     }
 
+    // ── Session-state / re-pairing tests ──
+
+    #[test]
+    #[cfg(feature = "whatsapp-web")]
+    fn note_session_state_tracks_pairing_requirement() {
+        let ch = make_channel();
+        assert!(!ch.needs_pairing());
+
+        WhatsAppWebChannel::note_session_state(&ch.session_state, SessionState::NeedsPairing);
+        assert!(ch.needs_pairing());
+
+        WhatsAppWebChannel::note_session_state(&ch.session_state, SessionState::Connected);
+        assert!(!ch.needs_pairing());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "whatsapp-web")]
+    async fn send_while_unpaired_returns_repair_hint() {
+        let ch = make_channel();
+        WhatsAppWebChannel::note_session_state(&ch.session_state, SessionState::NeedsPairing);
+
+        let err = ch
+            .send(&SendMessage::new("hello", "+1234567890"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("pair-whatsapp"));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "whatsapp-web")]
+    async fn health_check_detailed_surfaces_repair_hint_while_unpaired() {
+        let ch = make_channel();
+        WhatsAppWebChannel::note_session_state(&ch.session_state, SessionState::NeedsPairing);
+
+        let report = ch.health_check_detailed().await;
+        assert!(!report.healthy);
+        assert!(report.detail.as_deref().unwrap().contains("pair-whatsapp"));
+    }
+
+    #[test]
+    #[cfg(feature = "whatsapp-web")]
+    fn write_qr_png_produces_png_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("qr.png");
+
+        WhatsAppWebChannel::write_qr_png("2@abcdef,test,payload", &path).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
     // ── Reconnect retry state machine tests (exercise production helpers) ──
 
     #[test]
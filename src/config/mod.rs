@@ -7,33 +7,38 @@ pub use schema::{
     apply_channel_proxy_to_builder, apply_runtime_proxy_to_builder, build_channel_proxy_client,
     build_channel_proxy_client_with_timeouts, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    ws_connect_with_proxy, AgentConfig, AssemblyAiSttConfig, AuditConfig, AutonomyConfig,
-    BackupConfig, BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, ChannelsConfig,
-    ClassificationRule, ClaudeCodeConfig, ClaudeCodeRunnerConfig, CloudOpsConfig, CodexCliConfig,
-    ComposioConfig, Config, ConversationalAiConfig, CostConfig, CronConfig, CronJobDecl,
-    CronScheduleDecl, DataRetentionConfig, DeepgramSttConfig, DelegateAgentConfig,
-    DelegateToolConfig, DiscordConfig, DockerRuntimeConfig, EdgeTtsConfig, ElevenLabsTtsConfig,
-    EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig, GeminiCliConfig,
-    GoogleSttConfig, GoogleTtsConfig, GoogleWorkspaceAllowedOperation, GoogleWorkspaceConfig,
-    HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig, HttpRequestConfig,
-    IMessageConfig, IdentityConfig, ImageGenConfig, ImageProviderDalleConfig,
-    ImageProviderFluxConfig, ImageProviderImagenConfig, ImageProviderStabilityConfig, JiraConfig,
-    KnowledgeConfig, LarkConfig, LinkEnricherConfig, LinkedInConfig, LinkedInContentConfig,
-    LinkedInImageConfig, LocalWhisperConfig, MatrixConfig, McpConfig, McpServerConfig,
-    McpTransport, MediaPipelineConfig, MemoryConfig, MemoryPolicyConfig, Microsoft365Config,
-    ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, NodeTransportConfig, NodesConfig,
-    NotionConfig, ObservabilityConfig, OpenAiSttConfig, OpenAiTtsConfig, OpenCodeCliConfig,
-    OpenCodeConfig, OpenVpnTunnelConfig, OtpConfig, OtpMethod, PacingConfig, PeripheralBoardConfig,
-    PeripheralsConfig, PipelineConfig, PiperTtsConfig, PluginsConfig, ProjectIntelConfig,
-    ProxyConfig, ProxyScope, QdrantConfig, QueryClassificationConfig, ReliabilityConfig,
-    ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig,
-    SearchMode, SecretsConfig, SecurityConfig, SecurityOpsConfig, ShellToolConfig,
+    ws_connect_with_proxy, AgentConfig, AnthropicProviderConfig, AssemblyAiSttConfig, AuditConfig,
+    AuthConfig, AuthRotationConfig, AutonomyConfig, BackupConfig, BrowserComputerUseConfig,
+    BrowserConfig, BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ClaudeCodeConfig,
+    ClaudeCodeRunnerConfig, CloudOpsConfig, CodexCliConfig, ComposioConfig, Config,
+    ConversationalAiConfig, CostConfig, CronConfig, CronJobDecl, CronScheduleDecl,
+    CustomProviderConfig, DataRetentionConfig, DedupPolicy, DeepgramSttConfig, DelegateAgentConfig,
+    DelegateToolConfig, DiscordConfig, DistillConsumePolicy, DockerRuntimeConfig, EdgeTtsConfig,
+    ElevenLabsTtsConfig, EmbeddingRouteConfig, EmbeddingsConfig, EstopConfig, FeishuConfig,
+    GatewayConfig, GeminiCliConfig, GeminiProviderConfig, GoogleSttConfig, GoogleTtsConfig,
+    GoogleWorkspaceAllowedOperation, GoogleWorkspaceConfig, HardwareConfig, HardwareTransport,
+    HeartbeatConfig, HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig,
+    ImageGenConfig, ImageProviderDalleConfig, ImageProviderFluxConfig, ImageProviderImagenConfig,
+    ImageProviderStabilityConfig, JiraConfig, KnowledgeConfig, LarkConfig, LinkEnricherConfig,
+    LinkedInConfig, LinkedInContentConfig, LinkedInImageConfig, LocalWhisperConfig, MatrixConfig,
+    McpConfig, McpServerConfig, McpTransport, MediaPipelineConfig, MemoryConfig, MemoryDedupConfig,
+    MemoryDistillConfig, MemoryNamespacingConfig, MemoryPolicyConfig, MemoryRecallConfig,
+    Microsoft365Config, ModelRouteConfig, MultimodalConfig, NamespacingMode, NextcloudTalkConfig,
+    NodeTransportConfig, NodesConfig, NotionConfig, ObservabilityConfig, OpenAiSttConfig,
+    OpenAiTtsConfig, OpenCodeCliConfig, OpenCodeConfig, OpenRouterProviderConfig,
+    OpenRouterRoutingConfig, OpenVpnTunnelConfig, OtpConfig, OtpMethod, PacingConfig,
+    PeripheralBoardConfig, PeripheralBoardMetadata, PeripheralWatchConfig, PeripheralsConfig,
+    PipelineConfig, PiperTtsConfig, PluginsConfig, ProjectIntelConfig, ProvidersConfig,
+    ProxyConfig, ProxyScope, QdrantConfig, QueryClassificationConfig, RagChunkingConfig, RagConfig,
+    RagRerankConfig, RagWatchConfig, RateLimitSettings, ReliabilityConfig, ResourceLimitsConfig,
+    ResponseCacheConfig, RobotPeripheralConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
+    SchedulerConfig, SearchMode, SecretsConfig, SecurityConfig, SecurityOpsConfig, ShellToolConfig,
     SkillCreationConfig, SkillImprovementConfig, SkillsConfig, SkillsPromptInjectionMode,
     SlackConfig, SopConfig, StorageConfig, StorageProviderConfig, StorageProviderSection,
-    StreamMode, SwarmConfig, SwarmStrategy, TelegramConfig, TextBrowserConfig, ToolFilterGroup,
-    ToolFilterGroupMode, TranscriptionConfig, TtsConfig, TunnelConfig, VerifiableIntentConfig,
-    WebFetchConfig, WebSearchConfig, WebhookConfig, WhatsAppChatPolicy, WhatsAppWebMode,
-    WorkspaceConfig, DEFAULT_GWS_SERVICES,
+    StreamMode, SwarmConfig, SwarmStrategy, TelegramConfig, TextBrowserConfig, TokenRefreshConfig,
+    ToolFilterGroup, ToolFilterGroupMode, TranscriptionConfig, TtsConfig, TunnelConfig,
+    VerifiableIntentConfig, WebFetchConfig, WebSearchConfig, WebhookConfig, WhatsAppChatPolicy,
+    WhatsAppWebMode, WhisperCppConfig, WorkspaceConfig, DEFAULT_GWS_SERVICES,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
@@ -62,6 +67,9 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         };
@@ -73,10 +81,14 @@ mod tests {
             listen_to_bots: false,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             proxy_url: None,
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: 1000,
             multi_message_delay_ms: 800,
+            broadcast_channel_id: None,
         };
 
         let lark = LarkConfig {
@@ -88,6 +88,9 @@ pub struct Config {
     /// Optional named provider profiles keyed by id (Codex app-server compatible layout).
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderConfig>,
+    /// Per-provider tuning options (`[providers.anthropic]`, ...).
+    #[serde(default)]
+    pub providers: ProvidersConfig,
     /// Default model temperature (0.0–2.0). Default: `0.7`.
     #[serde(
         default = "default_temperature",
@@ -202,6 +205,10 @@ pub struct Config {
     #[serde(default)]
     pub embedding_routes: Vec<EmbeddingRouteConfig>,
 
+    /// Embedding backend selection shared by memory and rag (`[embeddings]`).
+    #[serde(default)]
+    pub embeddings: EmbeddingsConfig,
+
     /// Automatic query classification — maps user messages to model hints.
     #[serde(default)]
     pub query_classification: QueryClassificationConfig,
@@ -210,6 +217,14 @@ pub struct Config {
     #[serde(default)]
     pub heartbeat: HeartbeatConfig,
 
+    /// Auth behaviour such as profile rotation (`[auth]`).
+    #[serde(default)]
+    pub auth: AuthConfig,
+
+    /// Background OAuth token refresh (`[token_refresh]`).
+    #[serde(default)]
+    pub token_refresh: TokenRefreshConfig,
+
     /// Cron job configuration (`[cron]`).
     #[serde(default)]
     pub cron: CronConfig,
@@ -326,6 +341,14 @@ pub struct Config {
     #[serde(default)]
     pub peripherals: PeripheralsConfig,
 
+    /// Watch-folder auto-ingestion for the datasheet RAG index (`[rag_watch]`).
+    #[serde(default)]
+    pub rag_watch: RagWatchConfig,
+
+    /// Datasheet RAG retrieval configuration (`[rag]`).
+    #[serde(default)]
+    pub rag: RagConfig,
+
     /// Delegate tool global default configuration (`[delegate]`).
     #[serde(default)]
     pub delegate: DelegateToolConfig,
@@ -522,6 +545,248 @@ pub struct ModelProviderConfig {
     pub max_tokens: Option<u32>,
 }
 
+/// Per-provider tuning options (`[providers.*]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProvidersConfig {
+    /// Anthropic-specific options (`[providers.anthropic]`).
+    #[serde(default)]
+    pub anthropic: AnthropicProviderConfig,
+    /// Gemini-specific options (`[providers.gemini]`).
+    #[serde(default)]
+    pub gemini: GeminiProviderConfig,
+    /// OpenRouter-specific options (`[providers.openrouter]`).
+    #[serde(default)]
+    pub openrouter: OpenRouterProviderConfig,
+    /// Custom endpoint options (`[providers.custom]`), applied to
+    /// `custom:<URL>` and `anthropic-custom:<URL>` providers.
+    #[serde(default)]
+    pub custom: CustomProviderConfig,
+}
+
+/// Anthropic provider options (`[providers.anthropic]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnthropicProviderConfig {
+    /// Mark the system prompt and tool definitions with
+    /// `cache_control: {"type": "ephemeral"}` so Anthropic serves the stable
+    /// prefix from its prompt cache. Default: `true`. Disable only when a
+    /// proxy rejects cache_control blocks.
+    #[serde(default = "default_true")]
+    pub prompt_caching: bool,
+}
+
+impl Default for AnthropicProviderConfig {
+    fn default() -> Self {
+        Self {
+            prompt_caching: true,
+        }
+    }
+}
+
+/// Gemini provider options (`[providers.gemini]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GeminiProviderConfig {
+    /// Cache the static prefix (system instruction) as a Gemini
+    /// `cachedContent` resource instead of re-sending it inline each turn.
+    /// Only applies to API-key auth; failures fall back to inline content.
+    /// Default: `false`.
+    #[serde(default)]
+    pub context_caching: bool,
+    /// Minimum estimated prefix tokens (chars/4) before a cache is created.
+    /// Default: `4096`.
+    #[serde(default = "default_gemini_cache_min_tokens")]
+    pub context_cache_min_tokens: u32,
+    /// TTL for the cached content resource, in seconds. The cache is
+    /// refreshed shortly before expiry. Default: `3600`.
+    #[serde(default = "default_gemini_cache_ttl_secs")]
+    pub context_cache_ttl_secs: u64,
+}
+
+fn default_gemini_cache_min_tokens() -> u32 {
+    4096
+}
+
+fn default_gemini_cache_ttl_secs() -> u64 {
+    3600
+}
+
+impl Default for GeminiProviderConfig {
+    fn default() -> Self {
+        Self {
+            context_caching: false,
+            context_cache_min_tokens: default_gemini_cache_min_tokens(),
+            context_cache_ttl_secs: default_gemini_cache_ttl_secs(),
+        }
+    }
+}
+
+/// OpenRouter provider options (`[providers.openrouter]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct OpenRouterProviderConfig {
+    /// Upstream routing preferences (`[providers.openrouter.routing]`).
+    #[serde(default)]
+    pub routing: OpenRouterRoutingConfig,
+}
+
+/// OpenRouter upstream routing preferences, injected as the request-level
+/// `provider` object (<https://openrouter.ai/docs/features/provider-routing>).
+///
+/// ```toml
+/// [providers.openrouter.routing]
+/// order = ["anthropic", "openai"]
+/// allow_fallbacks = false
+/// data_collection = "deny"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OpenRouterRoutingConfig {
+    /// Try these upstreams first, in order (e.g. `["anthropic", "openai"]`).
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Restrict routing to these upstreams only.
+    #[serde(default)]
+    pub only: Vec<String>,
+    /// Never route to these upstreams.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Whether OpenRouter may fall back to upstreams beyond `order`.
+    /// `None` keeps the API default (fallbacks allowed).
+    #[serde(default)]
+    pub allow_fallbacks: Option<bool>,
+    /// Only route to upstreams that support every request parameter.
+    #[serde(default)]
+    pub require_parameters: Option<bool>,
+    /// Data-collection policy: `"allow"` or `"deny"`.
+    #[serde(default)]
+    pub data_collection: Option<String>,
+}
+
+impl OpenRouterRoutingConfig {
+    /// True when no preference is set — nothing to inject into requests.
+    pub fn is_unset(&self) -> bool {
+        self.order.is_empty()
+            && self.only.is_empty()
+            && self.ignore.is_empty()
+            && self.allow_fallbacks.is_none()
+            && self.require_parameters.is_none()
+            && self.data_collection.is_none()
+    }
+
+    /// Validate the combination of settings. `context` names the config
+    /// location (e.g. `providers.openrouter.routing`) in error messages.
+    pub fn validate(&self, context: &str) -> Result<()> {
+        if let Some(upstream) = self.only.iter().find(|u| self.ignore.contains(u)) {
+            anyhow::bail!("{context}: upstream '{upstream}' is listed in both `only` and `ignore`");
+        }
+        if let Some(upstream) = self.order.iter().find(|u| self.ignore.contains(u)) {
+            anyhow::bail!(
+                "{context}: upstream '{upstream}' is listed in both `order` and `ignore`"
+            );
+        }
+        if !self.only.is_empty() {
+            if let Some(upstream) = self.order.iter().find(|u| !self.only.contains(u)) {
+                anyhow::bail!(
+                    "{context}: `order` entry '{upstream}' is not in the `only` allowlist"
+                );
+            }
+        }
+        if let Some(ref policy) = self.data_collection {
+            if !matches!(policy.as_str(), "allow" | "deny") {
+                anyhow::bail!(
+                    "{context}: data_collection must be \"allow\" or \"deny\" (got '{policy}')"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Custom endpoint options (`[providers.custom]`) for `custom:<URL>` and
+/// `anthropic-custom:<URL>` providers: self-hosted gateways that need extra
+/// headers, a non-Bearer auth scheme, or internal-PKI TLS trust.
+///
+/// ```toml
+/// [providers.custom]
+/// auth_scheme = "header:X-Gateway-Key"
+/// ca_bundle = "/etc/ssl/internal-ca.pem"
+///
+/// [providers.custom.headers]
+/// X-Org-Token = "env:ORG_TOKEN"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CustomProviderConfig {
+    /// Extra HTTP headers sent on every request. Values may be literal,
+    /// SecretStore-encrypted (decrypted at config load), or `env:VAR`
+    /// references resolved from the environment at provider construction.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// How the API key is sent: `"bearer"` (default, `Authorization: Bearer`),
+    /// `"basic"` (`Authorization: Basic <base64(key)>`), or `"header:<name>"`
+    /// (the key as the value of a custom header).
+    #[serde(default)]
+    pub auth_scheme: Option<String>,
+    /// Path to a PEM bundle with additional trusted root certificates
+    /// (internal PKI).
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// Skip TLS certificate verification. Only for trusted internal networks.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl CustomProviderConfig {
+    /// Validate the configured settings. `context` names the config location
+    /// (e.g. `providers.custom`) in error messages.
+    pub fn validate(&self, context: &str) -> Result<()> {
+        if let Some(ref scheme) = self.auth_scheme {
+            let normalized = scheme.trim().to_ascii_lowercase();
+            let valid = matches!(normalized.as_str(), "bearer" | "basic")
+                || scheme
+                    .trim()
+                    .strip_prefix("header:")
+                    .is_some_and(|name| !name.trim().is_empty());
+            if !valid {
+                anyhow::bail!(
+                    "{context}: auth_scheme must be \"bearer\", \"basic\", or \
+                     \"header:<name>\" (got '{scheme}')"
+                );
+            }
+        }
+        for name in self.headers.keys() {
+            if name.trim().is_empty() {
+                anyhow::bail!("{context}: header names must not be empty");
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve header values for use in requests: `env:VAR` values are read
+    /// from the environment; anything else is passed through verbatim.
+    pub fn resolved_headers(&self) -> Result<HashMap<String, String>> {
+        let mut resolved = HashMap::new();
+        for (name, value) in &self.headers {
+            let resolved_value = match value.strip_prefix("env:") {
+                Some(var) => std::env::var(var.trim()).with_context(|| {
+                    format!(
+                        "providers.custom.headers.{name} references env var '{}' which is not set",
+                        var.trim()
+                    )
+                })?,
+                None => value.clone(),
+            };
+            resolved.insert(name.clone(), resolved_value);
+        }
+        Ok(resolved)
+    }
+
+    /// Header map safe for logs and runtime traces: names are kept, values
+    /// are replaced with a redaction marker.
+    pub fn redacted_headers(&self) -> HashMap<String, String> {
+        self.headers
+            .keys()
+            .map(|name| (name.clone(), "[REDACTED]".to_string()))
+            .collect()
+    }
+}
+
 // ── Delegate Tool Configuration ─────────────────────────────────
 
 /// Global delegate tool configuration for default timeout values.
@@ -878,6 +1143,9 @@ pub struct TranscriptionConfig {
     /// Local/self-hosted Whisper-compatible STT provider.
     #[serde(default)]
     pub local_whisper: Option<LocalWhisperConfig>,
+    /// Fully local whisper.cpp transcription (no network at all).
+    #[serde(default)]
+    pub whisper_cpp: Option<WhisperCppConfig>,
     /// Also transcribe non-PTT (forwarded/regular) audio messages on WhatsApp,
     /// not just voice notes.  Default: `false` (preserves legacy behavior).
     #[serde(default)]
@@ -900,6 +1168,7 @@ impl Default for TranscriptionConfig {
             assemblyai: None,
             google: None,
             local_whisper: None,
+            whisper_cpp: None,
             transcribe_non_ptt_audio: false,
         }
     }
@@ -1319,6 +1588,48 @@ fn default_local_whisper_timeout_secs() -> u64 {
     300
 }
 
+/// Fully local whisper.cpp transcription (`[transcription.whisper_cpp]`).
+///
+/// Runs the whisper.cpp CLI against a GGML/GGUF model on the local machine —
+/// voice notes never leave the host. Incoming OGG/OPUS audio is converted to
+/// the 16 kHz mono WAV whisper.cpp expects via an `ffmpeg` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WhisperCppConfig {
+    /// Path to the whisper.cpp CLI binary (e.g. `"/usr/local/bin/whisper-cli"`).
+    pub binary_path: String,
+    /// Path to the GGML/GGUF model file (e.g. `"/opt/models/ggml-base.bin"`).
+    pub model_path: String,
+    /// Path to the ffmpeg binary used for WAV conversion.
+    /// Default: `"ffmpeg"` (resolved via `PATH`).
+    #[serde(default = "default_whisper_cpp_ffmpeg_path")]
+    pub ffmpeg_path: String,
+    /// Maximum transcriptions running at once. A flood of voice notes queues
+    /// here instead of exhausting memory on small hosts (each whisper.cpp
+    /// process loads the full model). Default: `1`.
+    #[serde(default = "default_whisper_cpp_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Per-transcription timeout in seconds, covering both the WAV conversion
+    /// and the whisper.cpp run. Default: `120`.
+    #[serde(default = "default_whisper_cpp_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Language hint passed to whisper.cpp (`-l`), e.g. `"en"` or `"auto"`.
+    /// Default: unset (whisper.cpp auto-detects).
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_whisper_cpp_ffmpeg_path() -> String {
+    "ffmpeg".to_string()
+}
+
+fn default_whisper_cpp_max_concurrency() -> usize {
+    1
+}
+
+fn default_whisper_cpp_timeout_secs() -> u64 {
+    120
+}
+
 /// Agent orchestration configuration (`[agent]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
@@ -2067,7 +2378,7 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
 /// Peripheral board integration configuration (`[peripherals]` section).
 ///
 /// Boards become agent tools when enabled.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PeripheralsConfig {
     /// Enable peripheral support (boards become agent tools)
     #[serde(default)]
@@ -2079,6 +2390,261 @@ pub struct PeripheralsConfig {
     /// Place .md/.txt files named by board (e.g. nucleo-f401re.md, rpi-gpio.md).
     #[serde(default)]
     pub datasheet_dir: Option<String>,
+    /// Robot kit integration (`[peripherals.robot]`)
+    #[serde(default)]
+    pub robot: RobotPeripheralConfig,
+    /// Pin-watch settings (`[peripherals.watch]`) for the peripheral_watch tool.
+    #[serde(default)]
+    pub watch: PeripheralWatchConfig,
+    /// Max commands queued per board before callers get "peripheral busy".
+    #[serde(default = "default_peripheral_queue_depth")]
+    pub queue_depth: usize,
+}
+
+fn default_peripheral_queue_depth() -> usize {
+    16
+}
+
+/// Watch-folder auto-ingestion configuration (`[rag_watch]` section).
+///
+/// The daemon polls the watched directories and re-ingests new or changed
+/// datasheet files; see `crate::rag::watcher`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RagWatchConfig {
+    /// Enable the daemon watcher task.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directories to watch, relative to the workspace. Default: `["knowledge"]`
+    #[serde(default = "default_rag_watch_dirs")]
+    pub dirs: Vec<String>,
+    /// Seconds between scan passes. Default: 30
+    #[serde(default = "default_rag_watch_poll_secs")]
+    pub poll_secs: u64,
+    /// Quiet period after a file's last write before it is ingested, so
+    /// half-written files are not indexed. Default: 5
+    #[serde(default = "default_rag_watch_debounce_secs")]
+    pub debounce_secs: u64,
+    /// Glob patterns (`*`, `?`) matched against workspace-relative paths and
+    /// file names; matching files are skipped.
+    #[serde(default)]
+    pub ignore_globs: Vec<String>,
+    /// Skip files larger than this many kilobytes. Default: 10240 (10 MB)
+    #[serde(default = "default_rag_watch_max_file_kb")]
+    pub max_file_kb: u64,
+}
+
+fn default_rag_watch_dirs() -> Vec<String> {
+    vec!["knowledge".to_string()]
+}
+
+fn default_rag_watch_poll_secs() -> u64 {
+    30
+}
+
+fn default_rag_watch_debounce_secs() -> u64 {
+    5
+}
+
+fn default_rag_watch_max_file_kb() -> u64 {
+    10_240
+}
+
+impl Default for RagWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dirs: default_rag_watch_dirs(),
+            poll_secs: default_rag_watch_poll_secs(),
+            debounce_secs: default_rag_watch_debounce_secs(),
+            ignore_globs: Vec::new(),
+            max_file_kb: default_rag_watch_max_file_kb(),
+        }
+    }
+}
+
+/// Datasheet RAG retrieval configuration (`[rag]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RagConfig {
+    /// Inline citations: number the RAG context blocks, ask the model to
+    /// cite them with `[n]` markers, and expand cited markers into a
+    /// "Sources:" footer on the response. Default: true
+    #[serde(default = "default_rag_citations")]
+    pub citations: bool,
+    /// Chunking strategy for datasheet ingestion (`[rag.chunking]`).
+    #[serde(default)]
+    pub chunking: RagChunkingConfig,
+    /// Optional rerank stage for retrieval (`[rag.rerank]`).
+    #[serde(default)]
+    pub rerank: RagRerankConfig,
+}
+
+/// Ingestion chunking configuration (`[rag.chunking]` section).
+///
+/// Changing these values changes the chunking fingerprint stored alongside
+/// the index; `zeroclaw doctor` flags the mismatch and `zeroclaw rag
+/// reindex` rebuilds.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RagChunkingConfig {
+    /// Strategy: "fixed" (token windows), "sentence" (whole sentences packed
+    /// to size), or "heading" (heading-aware, never crossing an H1/H2
+    /// boundary). Default: "heading"
+    #[serde(default = "default_rag_chunking_strategy")]
+    pub strategy: String,
+    /// Target chunk size in estimated tokens. Default: 512
+    #[serde(default = "default_rag_chunking_max_tokens")]
+    pub max_tokens: usize,
+    /// Estimated tokens repeated from the end of the previous chunk.
+    /// Default: 0
+    #[serde(default)]
+    pub overlap_tokens: usize,
+}
+
+fn default_rag_chunking_strategy() -> String {
+    "heading".to_string()
+}
+
+fn default_rag_chunking_max_tokens() -> usize {
+    512
+}
+
+impl Default for RagChunkingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: default_rag_chunking_strategy(),
+            max_tokens: default_rag_chunking_max_tokens(),
+            overlap_tokens: 0,
+        }
+    }
+}
+
+fn default_rag_citations() -> bool {
+    true
+}
+
+impl Default for RagConfig {
+    fn default() -> Self {
+        Self {
+            citations: default_rag_citations(),
+            chunking: RagChunkingConfig::default(),
+            rerank: RagRerankConfig::default(),
+        }
+    }
+}
+
+/// Retrieval rerank configuration (`[rag.rerank]` section).
+///
+/// When enabled, retrieval over-fetches a candidate set, scores each
+/// candidate against the query with the configured backend, and keeps the
+/// top results; see `crate::rag::rerank`. Disabled by default — retrieval
+/// behaves exactly as without the stage.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RagRerankConfig {
+    /// Enable the rerank stage.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Scoring backend. Currently only "llm" (a cheap scoring call via the
+    /// configured provider).
+    #[serde(default = "default_rag_rerank_backend")]
+    pub backend: String,
+    /// Model for the "llm" backend. Falls back to the default model.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Candidates fetched per final result slot before reranking
+    /// (e.g. limit 5 × multiplier 3 = 15 candidates). Default: 3
+    #[serde(default = "default_rag_rerank_candidate_multiplier")]
+    pub candidate_multiplier: usize,
+    /// Drop candidates whose rerank score (0–10) falls below this, even if
+    /// that leaves fewer than the requested number of results. Default: 0.0
+    #[serde(default)]
+    pub score_threshold: f32,
+}
+
+fn default_rag_rerank_backend() -> String {
+    "llm".to_string()
+}
+
+fn default_rag_rerank_candidate_multiplier() -> usize {
+    3
+}
+
+impl Default for RagRerankConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_rag_rerank_backend(),
+            model: None,
+            candidate_multiplier: default_rag_rerank_candidate_multiplier(),
+            score_threshold: 0.0,
+        }
+    }
+}
+
+impl Default for PeripheralsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            boards: Vec::new(),
+            datasheet_dir: None,
+            robot: RobotPeripheralConfig::default(),
+            watch: PeripheralWatchConfig::default(),
+            queue_depth: default_peripheral_queue_depth(),
+        }
+    }
+}
+
+/// Pin-watch configuration (`[peripherals.watch]`).
+///
+/// The `peripheral_watch` tool polls a GPIO pin for edges at `poll_ms`
+/// and reports transitions into the runtime trace; when an announce
+/// channel is configured, each transition is also delivered there.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PeripheralWatchConfig {
+    /// Poll interval in milliseconds.
+    #[serde(default = "default_watch_poll_ms")]
+    pub poll_ms: u64,
+    /// Maximum number of concurrent watches.
+    #[serde(default = "default_watch_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Channel to announce transitions on (e.g. "telegram"); unset = trace only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announce_channel: Option<String>,
+    /// Delivery target on that channel (chat id / user).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announce_to: Option<String>,
+}
+
+fn default_watch_poll_ms() -> u64 {
+    250
+}
+
+fn default_watch_max_concurrent() -> usize {
+    4
+}
+
+impl Default for PeripheralWatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_ms: default_watch_poll_ms(),
+            max_concurrent: default_watch_max_concurrent(),
+            announce_channel: None,
+            announce_to: None,
+        }
+    }
+}
+
+/// Robot kit integration configuration (`[peripherals.robot]` section).
+///
+/// When enabled (and the binary is built with the `robot-kit` feature),
+/// the robot tools (drive, look, listen, speak, sense, emote) register as
+/// agent tools. The drive tool is always wrapped by the safety monitor.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct RobotPeripheralConfig {
+    /// Enable robot kit tools
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path to robot.toml (falls back to the robot kit's defaults: mock backends)
+    #[serde(default)]
+    pub config_path: Option<String>,
 }
 
 /// Configuration for a single peripheral board (e.g. STM32, RPi GPIO).
@@ -2095,6 +2661,38 @@ pub struct PeripheralBoardConfig {
     /// Baud rate for serial (default: 115200)
     #[serde(default = "default_peripheral_baud")]
     pub baud: u32,
+    /// Board metadata captured at `peripheral add` time (pin lists,
+    /// firmware version), so tool schemas can be built without re-probing
+    /// the device at every startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PeripheralBoardMetadata>,
+    /// Shared secret for network transports: sent as the first protocol
+    /// line at connect time so an open TCP port is not an open board.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Metadata probed from a board when it was registered.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PeripheralBoardMetadata {
+    /// GPIO pins as reported by `capabilities`.
+    #[serde(default)]
+    pub gpio: Vec<u64>,
+    /// PWM-capable pins as reported by `capabilities`.
+    #[serde(default)]
+    pub pwm: Vec<u64>,
+    /// ADC channels as reported by `capabilities`.
+    #[serde(default)]
+    pub adc: Vec<u64>,
+    /// On-board LED pin, if the firmware reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub led_pin: Option<u64>,
+    /// Firmware version at probe time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fw_version: Option<String>,
+    /// RFC3339 timestamp of the last successful probe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<String>,
 }
 
 fn default_peripheral_transport() -> String {
@@ -2112,6 +2710,8 @@ impl Default for PeripheralBoardConfig {
             transport: default_peripheral_transport(),
             path: None,
             baud: default_peripheral_baud(),
+            metadata: None,
+            secret: None,
         }
     }
 }
@@ -4934,6 +5534,176 @@ impl Default for QdrantConfig {
     }
 }
 
+/// Weights for blending lexical and vector scores during hybrid recall
+/// (`[memory.recall]` section).
+///
+/// Both component scores are normalized to [0, 1] before blending, and the
+/// weights are normalized to sum to 1.0, so the blended score stays within
+/// the range `min_relevance_score` expects.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MemoryRecallConfig {
+    /// Weight for the lexical score (FTS5 BM25 on sqlite, token overlap elsewhere).
+    #[serde(default = "default_recall_lexical_weight")]
+    pub lexical_weight: f64,
+    /// Weight for the vector-similarity score.
+    #[serde(default = "default_recall_vector_weight")]
+    pub vector_weight: f64,
+}
+
+impl Default for MemoryRecallConfig {
+    fn default() -> Self {
+        Self {
+            lexical_weight: default_recall_lexical_weight(),
+            vector_weight: default_recall_vector_weight(),
+        }
+    }
+}
+
+fn default_recall_lexical_weight() -> f64 {
+    0.4
+}
+fn default_recall_vector_weight() -> f64 {
+    0.6
+}
+
+/// What to do when a store is a near-duplicate of an existing entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupPolicy {
+    /// Drop the incoming entry, keep the existing one (default)
+    #[default]
+    Skip,
+    /// Keep the existing content but refresh its timestamp (and TTL, if any)
+    Refresh,
+    /// Fold the incoming content into the existing entry
+    Merge,
+}
+
+/// Near-duplicate handling for memory stores (`[memory.dedup]` section).
+///
+/// Auto-save tends to capture the same fact repeatedly with slightly
+/// different wording, bloating recall. When enabled, the store path compares
+/// new content against recent entries in the same category and applies the
+/// configured policy on a hit. Exact-key overwrites keep current semantics.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MemoryDedupConfig {
+    /// Enable near-duplicate detection on store. Default: false.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Policy applied when a near-duplicate is found.
+    #[serde(default)]
+    pub policy: DedupPolicy,
+    /// Similarity (0.0–1.0) at or above which two entries count as
+    /// duplicates. Embedding cosine is used when both vectors exist,
+    /// word-shingle Jaccard otherwise.
+    #[serde(default = "default_dedup_threshold")]
+    pub threshold: f64,
+}
+
+impl Default for MemoryDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            policy: DedupPolicy::default(),
+            threshold: default_dedup_threshold(),
+        }
+    }
+}
+
+fn default_dedup_threshold() -> f64 {
+    0.85
+}
+
+/// What happens to conversation entries consumed by a distillation run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DistillConsumePolicy {
+    /// Leave consumed entries in place (default)
+    #[default]
+    Keep,
+    /// Give consumed entries a short TTL so they age out
+    Expire,
+    /// Delete consumed entries once their facts are stored
+    Delete,
+}
+
+/// Conversation distillation (`[memory.distill]` section).
+///
+/// `zeroclaw memory distill` (run manually, or periodically from a shell
+/// cron job) groups recent Conversation-category entries by session, asks
+/// the configured provider to extract stable facts, and stores them as
+/// Core entries with provenance records. A per-run token budget bounds
+/// cost; sessions beyond the budget wait for the next run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MemoryDistillConfig {
+    /// Minimum conversation entries a session needs before it is distilled.
+    #[serde(default = "default_distill_min_entries")]
+    pub min_entries: usize,
+    /// Approximate prompt-token budget per run (4 chars ≈ 1 token).
+    /// Sessions that would exceed it are skipped. 0 = unlimited.
+    #[serde(default = "default_distill_max_tokens")]
+    pub max_tokens_per_run: usize,
+    /// What to do with consumed conversation entries once their facts
+    /// are stored.
+    #[serde(default)]
+    pub consume: DistillConsumePolicy,
+    /// TTL applied to consumed entries when `consume = "expire"`
+    /// (e.g. "7d", "12h").
+    #[serde(default = "default_distill_expire_after")]
+    pub expire_after: String,
+}
+
+impl Default for MemoryDistillConfig {
+    fn default() -> Self {
+        Self {
+            min_entries: default_distill_min_entries(),
+            max_tokens_per_run: default_distill_max_tokens(),
+            consume: DistillConsumePolicy::default(),
+            expire_after: default_distill_expire_after(),
+        }
+    }
+}
+
+fn default_distill_min_entries() -> usize {
+    3
+}
+fn default_distill_max_tokens() -> usize {
+    20_000
+}
+fn default_distill_expire_after() -> String {
+    "7d".into()
+}
+
+/// How memory namespaces are derived for channel messages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NamespacingMode {
+    /// No namespacing: all entries share the global namespace (default)
+    #[default]
+    Off,
+    /// One namespace per channel, e.g. "channel:slack"
+    Channel,
+    /// One namespace per sender within a channel, e.g. "sender:slack:alice"
+    Sender,
+}
+
+/// Per-channel/per-sender memory isolation (`[memory.namespacing]` section).
+///
+/// When enabled, channel auto-save stores entries under a namespace derived
+/// from the message's channel (and sender), and channel recall queries that
+/// namespace plus the global one — so memories from one surface stop leaking
+/// into answers on another.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MemoryNamespacingConfig {
+    /// Namespace derivation mode. Default: off.
+    #[serde(default)]
+    pub mode: NamespacingMode,
+    /// Namespaces allowed to read global entries in addition to their own.
+    /// Empty (the default) means every namespace may read global.
+    #[serde(default)]
+    pub global_read_allowlist: Vec<String>,
+}
+
 /// Search strategy for memory recall.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -4985,12 +5755,26 @@ pub struct MemoryConfig {
     pub keyword_weight: f64,
     /// Search strategy: bm25 (keyword only), embedding (vector only), or hybrid (both).
     #[serde(default)]
-    pub search_mode: SearchMode,
+    pub search_mode: SearchMode,
+    /// Hybrid recall scoring weights. When present, takes precedence over the
+    /// legacy `vector_weight` / `keyword_weight` fields above.
+    #[serde(default)]
+    pub recall: Option<MemoryRecallConfig>,
+    /// Near-duplicate detection on store (disabled unless configured).
+    #[serde(default)]
+    pub dedup: MemoryDedupConfig,
+    /// Conversation distillation knobs (`[memory.distill]` section).
+    #[serde(default)]
+    pub distill: MemoryDistillConfig,
     /// Minimum hybrid score (0.0–1.0) for a memory to be included in context.
     /// Memories scoring below this threshold are dropped to prevent irrelevant
     /// context from bleeding into conversations. Default: 0.4
     #[serde(default = "default_min_relevance_score")]
     pub min_relevance_score: f64,
+    /// Char budget for pinned entries injected ahead of scored recall in
+    /// channel context. Default: 1000
+    #[serde(default = "default_pinned_context_max_chars")]
+    pub pinned_context_max_chars: usize,
     /// Max embedding cache entries before LRU eviction
     #[serde(default = "default_cache_size")]
     pub embedding_cache_size: usize,
@@ -5041,6 +5825,9 @@ pub struct MemoryConfig {
     /// Default namespace for memory entries.
     #[serde(default = "default_namespace")]
     pub default_namespace: String,
+    /// Per-channel/per-sender namespace derivation (`[memory.namespacing]`).
+    #[serde(default)]
+    pub namespacing: MemoryNamespacingConfig,
 
     // ── Conflict Resolution ─────────────────────────────────────
     /// Cosine similarity threshold for conflict detection (0.0–1.0).
@@ -5060,6 +5847,16 @@ pub struct MemoryConfig {
     #[serde(default)]
     pub policy: MemoryPolicyConfig,
 
+    // ── TTL / automatic expiry ──────────────────────────────────
+    /// Default TTL per category for new entries (`[memory.ttl]` section).
+    /// Keys are category names ("conversation", "daily", or a custom name);
+    /// values are durations like "30d", "12h", or "90m". "never" (or an
+    /// absent category) means entries never expire. Expired entries are
+    /// hidden from recall immediately and physically deleted by hygiene
+    /// pruning or `zeroclaw memory prune`.
+    #[serde(default)]
+    pub ttl: std::collections::HashMap<String, String>,
+
     // ── SQLite backend options ─────────────────────────────────
     /// For sqlite backend: max seconds to wait when opening the DB (e.g. file locked).
     /// None = wait indefinitely (default). Recommended max: 300.
@@ -5139,6 +5936,9 @@ fn default_keyword_weight() -> f64 {
 fn default_min_relevance_score() -> f64 {
     0.4
 }
+fn default_pinned_context_max_chars() -> usize {
+    1_000
+}
 fn default_cache_size() -> usize {
     10_000
 }
@@ -5171,7 +5971,11 @@ impl Default for MemoryConfig {
             vector_weight: default_vector_weight(),
             keyword_weight: default_keyword_weight(),
             search_mode: SearchMode::default(),
+            recall: None,
+            dedup: MemoryDedupConfig::default(),
+            distill: MemoryDistillConfig::default(),
             min_relevance_score: default_min_relevance_score(),
+            pinned_context_max_chars: default_pinned_context_max_chars(),
             embedding_cache_size: default_cache_size(),
             chunk_max_tokens: default_chunk_size(),
             response_cache_enabled: false,
@@ -5186,16 +5990,40 @@ impl Default for MemoryConfig {
             rerank_threshold: default_rerank_threshold(),
             fts_early_return_score: default_fts_early_return_score(),
             default_namespace: default_namespace(),
+            namespacing: MemoryNamespacingConfig::default(),
             conflict_threshold: default_conflict_threshold(),
             audit_enabled: false,
             audit_retention_days: default_audit_retention_days(),
             policy: MemoryPolicyConfig::default(),
+            ttl: std::collections::HashMap::new(),
             sqlite_open_timeout_secs: None,
             qdrant: QdrantConfig::default(),
         }
     }
 }
 
+impl MemoryConfig {
+    /// Effective `(vector, lexical)` recall weights, normalized to sum to 1.0.
+    ///
+    /// Prefers the `[memory.recall]` section when present and falls back to
+    /// the legacy top-level `vector_weight` / `keyword_weight` fields.
+    #[must_use]
+    pub fn recall_weights(&self) -> (f64, f64) {
+        let (vector, lexical) = match &self.recall {
+            Some(recall) => (recall.vector_weight, recall.lexical_weight),
+            None => (self.vector_weight, self.keyword_weight),
+        };
+        let sum = vector + lexical;
+        if !sum.is_finite() || sum <= f64::EPSILON {
+            return (
+                default_recall_vector_weight(),
+                default_recall_lexical_weight(),
+            );
+        }
+        (vector / sum, lexical / sum)
+    }
+}
+
 // ── Observability ─────────────────────────────────────────────────
 
 /// Observability backend configuration (`[observability]` section).
@@ -5412,6 +6240,12 @@ pub struct AutonomyConfig {
     #[serde(default)]
     pub non_cli_excluded_tools: Vec<String>,
 
+    /// Usernames allowed to answer non-CLI approval prompts (e.g. Telegram
+    /// inline keyboard buttons). Empty means any user the channel already
+    /// allows may answer.
+    #[serde(default)]
+    pub approval_actors: Vec<String>,
+
     /// Per-user autonomy level overrides.
     ///
     /// Keys are usernames (e.g. Telegram `@handle` without `@`).
@@ -5519,6 +6353,7 @@ impl Default for AutonomyConfig {
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: Vec::new(),
+            approval_actors: Vec::new(),
             user_overrides: HashMap::new(),
         }
     }
@@ -5665,6 +6500,53 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// Response cache for byte-identical prompts (`[reliability.cache]` section).
+    #[serde(default)]
+    pub cache: ResponseCacheConfig,
+    /// Days of per-provider daily usage history kept for `providers-quota`.
+    #[serde(default = "default_quota_history_days")]
+    pub quota_history_days: u32,
+}
+
+fn default_quota_history_days() -> u32 {
+    30
+}
+
+/// Response cache configuration (`[reliability.cache]` section).
+///
+/// Opt-in cache for non-streamed responses to byte-identical requests
+/// (same provider, model, temperature, and message list). Heartbeat and
+/// repeated classification prompts hit the provider many times an hour;
+/// caching them within a short TTL saves quota without changing behavior.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseCacheConfig {
+    /// Enable the response cache. Off by default.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long a cached response stays valid, in seconds.
+    #[serde(default = "default_response_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Max cached entries; the oldest entry is evicted when full.
+    #[serde(default = "default_response_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_response_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_response_cache_max_entries() -> usize {
+    256
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: default_response_cache_ttl_secs(),
+            max_entries: default_response_cache_max_entries(),
+        }
+    }
 }
 
 fn default_provider_retries() -> u32 {
@@ -5704,6 +6586,8 @@ impl Default for ReliabilityConfig {
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            cache: ResponseCacheConfig::default(),
+            quota_history_days: default_quota_history_days(),
         }
     }
 }
@@ -5774,6 +6658,11 @@ pub struct ModelRouteConfig {
     /// Optional API key override for this route's provider
     #[serde(default)]
     pub api_key: Option<String>,
+    /// Optional OpenRouter routing override for this route — only meaningful
+    /// when `provider = "openrouter"`. Falls back to the global
+    /// `[providers.openrouter.routing]` block when omitted.
+    #[serde(default)]
+    pub openrouter_routing: Option<OpenRouterRoutingConfig>,
 }
 
 // ── Embedding routing ───────────────────────────────────────────
@@ -5806,6 +6695,34 @@ pub struct EmbeddingRouteConfig {
     pub api_key: Option<String>,
 }
 
+/// Global embedding backend selection (`[embeddings]`).
+///
+/// Overrides the legacy `[memory]` embedding settings when set, so vector
+/// memory, rag, and any future embedding consumers share one backend:
+///
+/// ```toml
+/// [embeddings]
+/// provider = "gemini"
+/// model = "text-embedding-004"
+/// dimensions = 768
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EmbeddingsConfig {
+    /// Embedding provider (`none`, `openai`, `gemini`, `ollama`,
+    /// `ollama:<url>`, or `custom:<url>`)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Embedding model to use with that provider
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Embedding vector dimensions
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    /// Optional API key override for the embedding provider
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
 // ── Query Classification ─────────────────────────────────────────
 
 /// Automatic query classification — classifies user messages by keyword/pattern
@@ -5944,6 +6861,92 @@ impl Default for HeartbeatConfig {
     }
 }
 
+// ── Auth ────────────────────────────────────────────────────────
+
+/// Auth behaviour configuration (`[auth]` section).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AuthConfig {
+    /// Automatic profile rotation (`[auth.rotation]`).
+    #[serde(default)]
+    pub rotation: AuthRotationConfig,
+}
+
+/// Automatic auth profile rotation (`[auth.rotation]` section).
+///
+/// Spreads requests across multiple auth profiles of the same provider and
+/// temporarily cools down profiles that hit quota errors. Rotation never
+/// crosses providers and is skipped entirely for explicit per-agent profile
+/// overrides. Disabled by default.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuthRotationConfig {
+    /// Enable automatic rotation. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Rotation strategy: `round_robin`, `least_recent`, or `on_quota_error`.
+    /// Default: `round_robin`.
+    #[serde(default = "default_rotation_strategy")]
+    pub strategy: String,
+    /// Seconds a profile is skipped after a quota error. Default: `300`.
+    #[serde(default = "default_rotation_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_rotation_strategy() -> String {
+    "round_robin".to_string()
+}
+
+fn default_rotation_cooldown_secs() -> u64 {
+    300
+}
+
+impl Default for AuthRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            strategy: default_rotation_strategy(),
+            cooldown_secs: default_rotation_cooldown_secs(),
+        }
+    }
+}
+
+// ── Token refresh ───────────────────────────────────────────────
+
+/// Background OAuth token refresh configuration (`[token_refresh]` section).
+///
+/// The daemon periodically scans stored auth profiles and refreshes access
+/// tokens that are about to expire, so long-lived sessions do not stall on
+/// an expired token mid-conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TokenRefreshConfig {
+    /// Enable the background refresh task in the daemon. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Seconds between profile scans. Default: `300`.
+    #[serde(default = "default_token_refresh_interval_secs")]
+    pub interval_secs: u64,
+    /// Refresh tokens that expire within this many seconds. Default: `900`.
+    #[serde(default = "default_token_refresh_window_secs")]
+    pub window_secs: u64,
+}
+
+fn default_token_refresh_interval_secs() -> u64 {
+    300
+}
+
+fn default_token_refresh_window_secs() -> u64 {
+    900
+}
+
+impl Default for TokenRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: default_token_refresh_interval_secs(),
+            window_secs: default_token_refresh_window_secs(),
+        }
+    }
+}
+
 // ── Cron ────────────────────────────────────────────────────────
 
 /// Cron job configuration (`[cron]` section).
@@ -5964,6 +6967,16 @@ pub struct CronConfig {
     /// Maximum number of historical cron run records to retain. Default: `50`.
     #[serde(default = "default_max_run_history")]
     pub max_run_history: u32,
+    /// Maximum number of cron jobs executed concurrently per scheduler tick.
+    ///
+    /// Defaults to the scheduler's `max_concurrent` when unset.
+    #[serde(default)]
+    pub max_concurrent_jobs: Option<usize>,
+    /// Default IANA timezone for natural-language schedules
+    /// (`cron add-nl`, channel `/cron add` phrases) when no `--tz` is given.
+    /// Falls back to UTC when unset.
+    #[serde(default)]
+    pub default_timezone: Option<String>,
     /// Declarative cron job definitions (`[[cron.jobs]]`).
     ///
     /// Jobs declared here are synced into the database at scheduler startup.
@@ -6062,6 +7075,8 @@ impl Default for CronConfig {
             enabled: true,
             catch_up_on_startup: true,
             max_run_history: default_max_run_history(),
+            max_concurrent_jobs: None,
+            default_timezone: None,
             jobs: Vec::new(),
         }
     }
@@ -6310,6 +7325,49 @@ pub struct ChannelsConfig {
     /// as a single concatenated message. `0` disables debouncing. Default: `0`.
     #[serde(default)]
     pub debounce_ms: u64,
+    /// How long (seconds) an inbound message id is remembered for dedup.
+    /// Telegram/Slack re-deliver updates after ack timeouts; re-deliveries
+    /// within this window are dropped. `0` disables dedup.
+    /// Default: `600` (10 minutes).
+    #[serde(default = "default_dedup_ttl_secs")]
+    pub dedup_ttl_secs: u64,
+    /// How long (seconds) after a reply an edited message (e.g. Telegram
+    /// `edited_message`) still triggers reprocessing of the turn. Edits older
+    /// than this window are ignored. `0` disables edit reprocessing.
+    /// Default: `300` (5 minutes).
+    #[serde(default = "default_edit_reprocess_window_secs")]
+    pub edit_reprocess_window_secs: u64,
+    /// Per-channel outbound rate limit overrides keyed by channel name
+    /// (e.g. `"telegram"`). Channels without an entry use built-in platform
+    /// defaults; see `channels::rate_limit`. Set `burst = 0` to disable a
+    /// bucket entirely.
+    #[serde(default)]
+    pub rate_limits: std::collections::HashMap<String, RateLimitSettings>,
+    /// Senders whose messages dispatch ahead of queued group chatter, same as
+    /// direct messages. Matched exactly against the inbound message's sender
+    /// identity (username or platform user id, whatever the channel reports).
+    /// Default: empty.
+    #[serde(default)]
+    pub vip_senders: Vec<String>,
+}
+
+/// Outbound rate limit overrides for a single channel
+/// (`[channels_config.rate_limits.<channel>]`).
+///
+/// Fields left unset keep the channel's built-in defaults
+/// (Telegram: 30/s global and 1/s per chat; Discord: 5 per 5s per channel;
+/// Slack: 1/s per channel; other channels: unlimited).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RateLimitSettings {
+    /// Channel-wide burst size (token bucket capacity). `0` disables the
+    /// channel-wide bucket.
+    pub burst: Option<u32>,
+    /// Channel-wide refill rate in messages per second.
+    pub per_sec: Option<f64>,
+    /// Per-recipient burst size. `0` disables the per-recipient bucket.
+    pub per_target_burst: Option<u32>,
+    /// Per-recipient refill rate in messages per second.
+    pub per_target_per_sec: Option<f64>,
 }
 
 impl ChannelsConfig {
@@ -6428,6 +7486,14 @@ impl ChannelsConfig {
     }
 }
 
+fn default_dedup_ttl_secs() -> u64 {
+    600
+}
+
+fn default_edit_reprocess_window_secs() -> u64 {
+    300
+}
+
 fn default_channel_message_timeout_secs() -> u64 {
     300
 }
@@ -6478,6 +7544,10 @@ impl Default for ChannelsConfig {
             session_backend: default_session_backend(),
             session_ttl_hours: 0,
             debounce_ms: 0,
+            dedup_ttl_secs: default_dedup_ttl_secs(),
+            edit_reprocess_window_secs: default_edit_reprocess_window_secs(),
+            rate_limits: std::collections::HashMap::new(),
+            vip_senders: Vec::new(),
         }
     }
 }
@@ -6529,6 +7599,16 @@ pub struct TelegramConfig {
     /// Direct messages are always processed.
     #[serde(default)]
     pub mention_only: bool,
+    /// Group respond mode: "mention", "prefix", "all" (default), or "silent".
+    /// Takes precedence over `mention_only` when set. DMs always respond.
+    #[serde(default)]
+    pub respond_mode: Option<String>,
+    /// Per-group respond-mode overrides keyed by chat id.
+    #[serde(default)]
+    pub group_respond_modes: std::collections::HashMap<String, String>,
+    /// Trigger prefix for `respond_mode = "prefix"` (default: "!").
+    #[serde(default)]
+    pub trigger_prefix: Option<String>,
     /// Override for the top-level `ack_reactions` setting. When `None`, the
     /// channel falls back to `[channels_config].ack_reactions`. When set
     /// explicitly, it takes precedence.
@@ -6571,6 +7651,17 @@ pub struct DiscordConfig {
     /// Other messages in the guild are silently ignored.
     #[serde(default)]
     pub mention_only: bool,
+    /// How the bot decides to respond in guild channels: "mention", "prefix",
+    /// "all" (default), or "silent" (record without replying). Wins over
+    /// `mention_only` when both are set; DMs are unaffected.
+    #[serde(default)]
+    pub respond_mode: Option<String>,
+    /// Per-channel respond-mode overrides keyed by Discord channel ID.
+    #[serde(default)]
+    pub group_respond_modes: std::collections::HashMap<String, String>,
+    /// Trigger prefix for `respond_mode = "prefix"` (default: "!").
+    #[serde(default)]
+    pub trigger_prefix: Option<String>,
     /// Per-channel proxy URL (http, https, socks5, socks5h).
     /// Overrides the global `[proxy]` setting for this channel only.
     #[serde(default)]
@@ -6588,6 +7679,10 @@ pub struct DiscordConfig {
     /// Only used when `stream_mode = "multi_message"`.
     #[serde(default = "default_multi_message_delay_ms")]
     pub multi_message_delay_ms: u64,
+    /// Default channel ID for broadcasts/announcements (`zeroclaw channel broadcast`).
+    /// Discord has no single natural default target, so it must be set explicitly.
+    #[serde(default)]
+    pub broadcast_channel_id: Option<String>,
 }
 
 impl ChannelConfig for DiscordConfig {
@@ -6662,6 +7757,16 @@ pub struct SlackConfig {
     /// Direct messages remain allowed.
     #[serde(default)]
     pub mention_only: bool,
+    /// Group respond mode ("mention" | "prefix" | "all" | "silent").
+    /// Supersedes `mention_only` when present; DMs always get replies.
+    #[serde(default)]
+    pub respond_mode: Option<String>,
+    /// Per-channel respond-mode overrides keyed by Slack channel ID.
+    #[serde(default)]
+    pub group_respond_modes: std::collections::HashMap<String, String>,
+    /// Trigger prefix for `respond_mode = "prefix"` (default: "!").
+    #[serde(default)]
+    pub trigger_prefix: Option<String>,
     /// Use the newer Slack `markdown` block type (12 000 char limit, richer formatting).
     /// Defaults to false (uses universally supported `section` blocks with `mrkdwn`).
     /// Enable this only if your Slack workspace supports the `markdown` block type.
@@ -6855,6 +7960,20 @@ pub struct SignalConfig {
     /// Overrides the global `[proxy]` setting for this channel only.
     #[serde(default)]
     pub proxy_url: Option<String>,
+    /// How the bot decides to respond in groups: "mention" (only when
+    /// mentioned by number or profile name), "prefix" (only when the message
+    /// starts with `trigger_prefix`), or "all" (default). DMs always respond.
+    #[serde(default)]
+    pub respond_mode: Option<String>,
+    /// Per-group respond-mode overrides keyed by group id.
+    #[serde(default)]
+    pub group_respond_modes: std::collections::HashMap<String, String>,
+    /// Trigger prefix for `respond_mode = "prefix"` (default: "!").
+    #[serde(default)]
+    pub trigger_prefix: Option<String>,
+    /// Bot profile name, used for "@name" mention detection in group text.
+    #[serde(default)]
+    pub profile_name: Option<String>,
 }
 
 impl ChannelConfig for SignalConfig {
@@ -7125,6 +8244,15 @@ pub struct IrcConfig {
     pub sasl_password: Option<String>,
     /// Verify TLS certificate (default: true)
     pub verify_tls: Option<bool>,
+    /// Pinned server certificate SHA-256 fingerprint (hex, colons optional).
+    /// When set, the presented certificate must match this fingerprint
+    /// exactly — replaces chain validation, so self-signed certs work.
+    pub tls_fingerprint: Option<String>,
+    /// Per-channel nick allowlists keyed by channel name (e.g. "#ops").
+    /// A channel listed here overrides `allowed_users` for messages from
+    /// that channel; DMs and unlisted channels use `allowed_users`.
+    #[serde(default)]
+    pub channel_allowed_users: std::collections::HashMap<String, Vec<String>>,
 }
 
 impl ChannelConfig for IrcConfig {
@@ -8369,6 +9497,7 @@ impl Default for Config {
             default_provider: Some("openrouter".to_string()),
             default_model: Some("anthropic/claude-sonnet-4.6".to_string()),
             model_providers: HashMap::new(),
+            providers: ProvidersConfig::default(),
             default_temperature: default_temperature(),
             provider_timeout_secs: default_provider_timeout_secs(),
             provider_max_tokens: None,
@@ -8392,7 +9521,10 @@ impl Default for Config {
             pipeline: PipelineConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            embeddings: EmbeddingsConfig::default(),
             heartbeat: HeartbeatConfig::default(),
+            auth: AuthConfig::default(),
+            token_refresh: TokenRefreshConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -8417,6 +9549,8 @@ impl Default for Config {
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            rag_watch: RagWatchConfig::default(),
+            rag: RagConfig::default(),
             delegate: DelegateToolConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
@@ -9065,6 +10199,10 @@ impl Config {
                 "config.storage.provider.config.db_url",
             )?;
 
+            for value in config.providers.custom.headers.values_mut() {
+                decrypt_secret(&store, value, "config.providers.custom.headers")?;
+            }
+
             for agent in config.agents.values_mut() {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
             }
@@ -9971,6 +11109,23 @@ impl Config {
         self.proxy.validate()?;
         self.cloud_ops.validate()?;
 
+        // OpenRouter routing preferences (global block + per-route overrides)
+        self.providers
+            .openrouter
+            .routing
+            .validate("providers.openrouter.routing")?;
+
+        // Custom endpoint options (headers, auth scheme)
+        self.providers.custom.validate("providers.custom")?;
+        for route in &self.model_routes {
+            if let Some(ref routing) = route.openrouter_routing {
+                routing.validate(&format!(
+                    "model_routes (hint '{}').openrouter_routing",
+                    route.hint
+                ))?;
+            }
+        }
+
         // Notion
         if self.notion.enabled {
             if self.notion.database_id.trim().is_empty() {
@@ -11315,6 +12470,8 @@ recipient = "42"
             enabled: false,
             catch_up_on_startup: false,
             max_run_history: 100,
+            max_concurrent_jobs: None,
+            default_timezone: None,
             jobs: Vec::new(),
         };
         let json = serde_json::to_string(&c).unwrap();
@@ -11426,6 +12583,156 @@ auto_save = true
         assert_eq!(parsed, SearchMode::Hybrid);
     }
 
+    #[test]
+    async fn memory_recall_section_deserialization() {
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[memory]
+backend = "sqlite"
+
+[memory.recall]
+lexical_weight = 0.5
+vector_weight = 1.5
+"#;
+        let parsed = parse_test_config(toml_str);
+        let recall = parsed
+            .memory
+            .recall
+            .as_ref()
+            .expect("recall section should parse");
+        assert!((recall.lexical_weight - 0.5).abs() < f64::EPSILON);
+        assert!((recall.vector_weight - 1.5).abs() < f64::EPSILON);
+        // Weights are normalized to sum to 1.0 before use.
+        let (vector, lexical) = parsed.memory.recall_weights();
+        assert!((vector - 0.75).abs() < f64::EPSILON);
+        assert!((lexical - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    async fn memory_recall_weights_default_and_fallback() {
+        // Empty [memory.recall] section uses the 0.4 lexical / 0.6 vector defaults.
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[memory]
+backend = "sqlite"
+
+[memory.recall]
+"#;
+        let parsed = parse_test_config(toml_str);
+        assert_eq!(parsed.memory.recall_weights(), (0.6, 0.4));
+
+        // Without the section, the legacy top-level weights still apply.
+        let legacy = MemoryConfig::default();
+        assert!(legacy.recall.is_none());
+        assert_eq!(legacy.recall_weights(), (0.7, 0.3));
+
+        // Degenerate weights fall back to the defaults instead of dividing by zero.
+        let degenerate = MemoryConfig {
+            recall: Some(MemoryRecallConfig {
+                lexical_weight: 0.0,
+                vector_weight: 0.0,
+            }),
+            ..MemoryConfig::default()
+        };
+        assert_eq!(degenerate.recall_weights(), (0.6, 0.4));
+    }
+
+    #[test]
+    async fn memory_dedup_section_deserialization() {
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[memory]
+backend = "sqlite"
+
+[memory.dedup]
+enabled = true
+policy = "merge"
+threshold = 0.9
+"#;
+        let parsed = parse_test_config(toml_str);
+        assert!(parsed.memory.dedup.enabled);
+        assert_eq!(parsed.memory.dedup.policy, DedupPolicy::Merge);
+        assert!((parsed.memory.dedup.threshold - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    async fn memory_dedup_disabled_by_default() {
+        let dedup = MemoryConfig::default().dedup;
+        assert!(!dedup.enabled);
+        assert_eq!(dedup.policy, DedupPolicy::Skip);
+        assert!((dedup.threshold - 0.85).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    async fn memory_distill_section_deserialization() {
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[memory]
+backend = "sqlite"
+
+[memory.distill]
+min_entries = 5
+max_tokens_per_run = 8000
+consume = "expire"
+expire_after = "3d"
+"#;
+        let parsed = parse_test_config(toml_str);
+        assert_eq!(parsed.memory.distill.min_entries, 5);
+        assert_eq!(parsed.memory.distill.max_tokens_per_run, 8000);
+        assert_eq!(parsed.memory.distill.consume, DistillConsumePolicy::Expire);
+        assert_eq!(parsed.memory.distill.expire_after, "3d");
+    }
+
+    #[test]
+    async fn memory_distill_defaults_keep_sources() {
+        let distill = MemoryConfig::default().distill;
+        assert_eq!(distill.min_entries, 3);
+        assert_eq!(distill.max_tokens_per_run, 20_000);
+        assert_eq!(distill.consume, DistillConsumePolicy::Keep);
+        assert_eq!(distill.expire_after, "7d");
+    }
+
+    #[test]
+    async fn memory_namespacing_section_deserialization() {
+        let toml_str = r#"
+workspace_dir = "/tmp/workspace"
+config_path = "/tmp/config.toml"
+default_temperature = 0.7
+
+[memory]
+backend = "sqlite"
+
+[memory.namespacing]
+mode = "sender"
+global_read_allowlist = ["sender:slack:alice"]
+"#;
+        let parsed = parse_test_config(toml_str);
+        assert_eq!(parsed.memory.namespacing.mode, NamespacingMode::Sender);
+        assert_eq!(
+            parsed.memory.namespacing.global_read_allowlist,
+            vec!["sender:slack:alice".to_string()]
+        );
+    }
+
+    #[test]
+    async fn memory_namespacing_off_by_default() {
+        let namespacing = MemoryConfig::default().namespacing;
+        assert_eq!(namespacing.mode, NamespacingMode::Off);
+        assert!(namespacing.global_read_allowlist.is_empty());
+    }
+
     #[test]
     async fn storage_provider_config_defaults() {
         let storage = StorageConfig::default();
@@ -11458,6 +12765,7 @@ auto_save = true
             default_provider: Some("openrouter".into()),
             default_model: Some("gpt-4o".into()),
             model_providers: HashMap::new(),
+            providers: ProvidersConfig::default(),
             default_temperature: 0.5,
             provider_timeout_secs: 120,
             provider_max_tokens: None,
@@ -11480,6 +12788,7 @@ auto_save = true
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
+                approval_actors: vec![],
                 user_overrides: HashMap::new(),
             },
             trust: crate::trust::TrustConfig::default(),
@@ -11500,6 +12809,7 @@ auto_save = true
             pipeline: PipelineConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            embeddings: EmbeddingsConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig {
                 enabled: true,
@@ -11510,6 +12820,8 @@ auto_save = true
                 to: Some("123456".into()),
                 ..HeartbeatConfig::default()
             },
+            auth: AuthConfig::default(),
+            token_refresh: TokenRefreshConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
@@ -11520,6 +12832,9 @@ auto_save = true
                     draft_update_interval_ms: default_draft_update_interval_ms(),
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    respond_mode: None,
+                    group_respond_modes: std::collections::HashMap::new(),
+                    trigger_prefix: None,
                     ack_reactions: None,
                     proxy_url: None,
                 }),
@@ -11560,6 +12875,10 @@ auto_save = true
                 session_backend: default_session_backend(),
                 session_ttl_hours: 0,
                 debounce_ms: 0,
+                dedup_ttl_secs: default_dedup_ttl_secs(),
+                edit_reprocess_window_secs: default_edit_reprocess_window_secs(),
+                rate_limits: std::collections::HashMap::new(),
+                vip_senders: Vec::new(),
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -11585,6 +12904,8 @@ auto_save = true
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            rag_watch: RagWatchConfig::default(),
+            rag: RagConfig::default(),
             delegate: DelegateToolConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
@@ -11864,6 +13185,73 @@ provider_timeout_secs = 300
         assert_eq!(headers[0], ("X-Valid".to_string(), "ok".to_string()));
     }
 
+    // ── Custom provider config ────────────────────────────────
+
+    #[test]
+    async fn custom_provider_resolves_env_header_values() {
+        let _env_guard = env_override_lock().await;
+        std::env::set_var("ZEROCLAW_TEST_ORG_TOKEN", "org-secret-value");
+
+        let mut config = CustomProviderConfig::default();
+        config.headers.insert(
+            "X-Org-Token".to_string(),
+            "env:ZEROCLAW_TEST_ORG_TOKEN".to_string(),
+        );
+        config
+            .headers
+            .insert("X-Title".to_string(), "zeroclaw".to_string());
+
+        let resolved = config.resolved_headers().unwrap();
+        assert_eq!(resolved.get("X-Org-Token").unwrap(), "org-secret-value");
+        assert_eq!(resolved.get("X-Title").unwrap(), "zeroclaw");
+
+        std::env::remove_var("ZEROCLAW_TEST_ORG_TOKEN");
+    }
+
+    #[test]
+    async fn custom_provider_missing_env_header_value_errors() {
+        let _env_guard = env_override_lock().await;
+        std::env::remove_var("ZEROCLAW_TEST_MISSING_TOKEN");
+
+        let mut config = CustomProviderConfig::default();
+        config.headers.insert(
+            "X-Org-Token".to_string(),
+            "env:ZEROCLAW_TEST_MISSING_TOKEN".to_string(),
+        );
+
+        let error = config.resolved_headers().unwrap_err().to_string();
+        assert!(error.contains("ZEROCLAW_TEST_MISSING_TOKEN"));
+    }
+
+    #[test]
+    async fn custom_provider_redacted_headers_hide_values() {
+        let mut config = CustomProviderConfig::default();
+        config
+            .headers
+            .insert("X-Org-Token".to_string(), "org-secret-value".to_string());
+
+        let redacted = config.redacted_headers();
+        assert_eq!(redacted.get("X-Org-Token").unwrap(), "[REDACTED]");
+        assert!(!format!("{redacted:?}").contains("org-secret-value"));
+    }
+
+    #[test]
+    async fn custom_provider_auth_scheme_validation() {
+        let mut config = CustomProviderConfig::default();
+        assert!(config.validate("providers.custom").is_ok());
+
+        for scheme in ["bearer", "basic", "header:X-Org-Token"] {
+            config.auth_scheme = Some(scheme.to_string());
+            assert!(config.validate("providers.custom").is_ok(), "{scheme}");
+        }
+
+        for scheme in ["digest", "header:", "header:   "] {
+            config.auth_scheme = Some(scheme.to_string());
+            let error = config.validate("providers.custom").unwrap_err().to_string();
+            assert!(error.contains("auth_scheme"), "{scheme}: {error}");
+        }
+    }
+
     #[test]
     async fn parse_extra_headers_env_allows_empty_value() {
         let headers = parse_extra_headers_env("X-Empty:");
@@ -12067,6 +13455,7 @@ default_temperature = 0.7
             default_provider: Some("openrouter".into()),
             default_model: Some("test-model".into()),
             model_providers: HashMap::new(),
+            providers: ProvidersConfig::default(),
             default_temperature: 0.9,
             provider_timeout_secs: 120,
             provider_max_tokens: None,
@@ -12088,8 +13477,11 @@ default_temperature = 0.7
             pipeline: PipelineConfig::default(),
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
+            embeddings: EmbeddingsConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
+            auth: AuthConfig::default(),
+            token_refresh: TokenRefreshConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -12116,6 +13508,8 @@ default_temperature = 0.7
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            rag_watch: RagWatchConfig::default(),
+            rag: RagConfig::default(),
             delegate: DelegateToolConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
@@ -12332,6 +13726,9 @@ default_temperature = 0.7
             draft_update_interval_ms: 500,
             interrupt_on_new_message: true,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         };
@@ -12362,10 +13759,14 @@ default_temperature = 0.7
             listen_to_bots: false,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             proxy_url: None,
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: 1000,
             multi_message_delay_ms: 800,
+            broadcast_channel_id: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -12382,10 +13783,14 @@ default_temperature = 0.7
             listen_to_bots: false,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             proxy_url: None,
             stream_mode: StreamMode::default(),
             draft_update_interval_ms: 1000,
             multi_message_delay_ms: 800,
+            broadcast_channel_id: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -12498,6 +13903,10 @@ allowed_users = ["@ops:matrix.org"]
             ignore_attachments: true,
             ignore_stories: false,
             proxy_url: None,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
+            profile_name: None,
         };
         let json = serde_json::to_string(&sc).unwrap();
         let parsed: SignalConfig = serde_json::from_str(&json).unwrap();
@@ -12519,6 +13928,10 @@ allowed_users = ["@ops:matrix.org"]
             ignore_attachments: false,
             ignore_stories: true,
             proxy_url: None,
+            respond_mode: Some("mention".into()),
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
+            profile_name: None,
         };
         let toml_str = toml::to_string(&sc).unwrap();
         let parsed: SignalConfig = toml::from_str(&toml_str).unwrap();
@@ -12526,6 +13939,7 @@ allowed_users = ["@ops:matrix.org"]
         assert_eq!(parsed.account, "+9876543210");
         assert!(parsed.group_id.is_none());
         assert!(parsed.ignore_stories);
+        assert_eq!(parsed.respond_mode.as_deref(), Some("mention"));
     }
 
     #[test]
@@ -12536,6 +13950,10 @@ allowed_users = ["@ops:matrix.org"]
         assert!(parsed.allowed_from.is_empty());
         assert!(!parsed.ignore_attachments);
         assert!(!parsed.ignore_stories);
+        assert!(parsed.respond_mode.is_none());
+        assert!(parsed.group_respond_modes.is_empty());
+        assert!(parsed.trigger_prefix.is_none());
+        assert!(parsed.profile_name.is_none());
     }
 
     #[test]
@@ -12595,6 +14013,10 @@ allowed_users = ["@ops:matrix.org"]
             session_backend: default_session_backend(),
             session_ttl_hours: 0,
             debounce_ms: 0,
+            dedup_ttl_secs: default_dedup_ttl_secs(),
+            edit_reprocess_window_secs: default_edit_reprocess_window_secs(),
+            rate_limits: std::collections::HashMap::new(),
+            vip_senders: Vec::new(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -12962,6 +14384,10 @@ channel_ids = ["C123", "D456"]
             session_backend: default_session_backend(),
             session_ttl_hours: 0,
             debounce_ms: 0,
+            dedup_ttl_secs: default_dedup_ttl_secs(),
+            edit_reprocess_window_secs: default_edit_reprocess_window_secs(),
+            rate_limits: std::collections::HashMap::new(),
+            vip_senders: Vec::new(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -13699,6 +15125,40 @@ requires_openai_auth = true
             .contains("wire_api must be one of: responses, chat_completions"));
     }
 
+    #[test]
+    async fn validate_rejects_conflicting_openrouter_routing() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        config.providers.openrouter.routing.only = vec!["anthropic".into()];
+        config.providers.openrouter.routing.ignore = vec!["anthropic".into()];
+
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("listed in both `only` and `ignore`"));
+    }
+
+    #[test]
+    async fn validate_rejects_bad_openrouter_data_collection_in_route() {
+        let _env_guard = env_override_lock().await;
+        let mut config = Config::default();
+        config.model_routes.push(ModelRouteConfig {
+            hint: "reasoning".into(),
+            provider: "openrouter".into(),
+            model: "anthropic/claude-sonnet-4".into(),
+            api_key: None,
+            openrouter_routing: Some(OpenRouterRoutingConfig {
+                data_collection: Some("maybe".into()),
+                ..OpenRouterRoutingConfig::default()
+            }),
+        });
+
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("data_collection must be \"allow\" or \"deny\""));
+    }
+
     #[test]
     async fn env_override_model_fallback() {
         let _env_guard = env_override_lock().await;
@@ -14639,6 +16099,8 @@ default_model = "persisted-profile"
         let p = PeripheralsConfig::default();
         assert!(!p.enabled);
         assert!(p.boards.is_empty());
+        assert!(!p.robot.enabled);
+        assert!(p.robot.config_path.is_none());
     }
 
     #[test]
@@ -14659,8 +16121,13 @@ default_model = "persisted-profile"
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                metadata: None,
+                secret: None,
             }],
             datasheet_dir: None,
+            robot: RobotPeripheralConfig::default(),
+            watch: PeripheralWatchConfig::default(),
+            queue_depth: 16,
         };
         let toml_str = toml::to_string(&p).unwrap();
         let parsed: PeripheralsConfig = toml::from_str(&toml_str).unwrap();
@@ -15036,6 +16503,9 @@ require_otp_to_resume = true
             draft_update_interval_ms: default_draft_update_interval_ms(),
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
@@ -0,0 +1,251 @@
+//! In-memory per-sender usage ledger behind the `/usage` channel command.
+//!
+//! Aggregates token usage and estimated cost per conversation-history key
+//! (e.g. `telegram_12345`), with daily and monthly windows that roll over at
+//! local midnight. Persistent daily totals continue to flow through
+//! [`CostTracker`](super::CostTracker); this ledger only answers the
+//! "how much did *my* conversation cost" question cheaply and in-process.
+
+use chrono::{Datelike, Local, NaiveDate};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Aggregated usage for one time window (today or this month).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageWindowTotals {
+    /// Number of counted LLM turns.
+    pub messages: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageWindowTotals {
+    fn add(&mut self, input_tokens: u64, output_tokens: u64, cost_usd: f64) {
+        self.messages = self.messages.saturating_add(1);
+        self.input_tokens = self.input_tokens.saturating_add(input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(output_tokens);
+        self.cost_usd += cost_usd;
+    }
+}
+
+/// Usage stats for a single sender, as reported by `/usage`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SenderUsageStats {
+    pub today: UsageWindowTotals,
+    pub this_month: UsageWindowTotals,
+    /// True when any counted turn had no provider-reported usage and token
+    /// counts were approximated from message sizes.
+    pub estimated: bool,
+}
+
+#[derive(Default)]
+struct SenderUsageEntry {
+    day: Option<NaiveDate>,
+    today: UsageWindowTotals,
+    month: Option<(i32, u32)>,
+    this_month: UsageWindowTotals,
+    estimated: bool,
+}
+
+impl SenderUsageEntry {
+    /// Reset any window the given date has moved past.
+    fn roll_over(&mut self, date: NaiveDate) {
+        if self.day != Some(date) {
+            self.day = Some(date);
+            self.today = UsageWindowTotals::default();
+        }
+        let month = (date.year(), date.month());
+        if self.month != Some(month) {
+            self.month = Some(month);
+            self.this_month = UsageWindowTotals::default();
+        }
+    }
+}
+
+#[derive(Default)]
+struct LedgerState {
+    senders: HashMap<String, SenderUsageEntry>,
+    global_day: Option<NaiveDate>,
+    global_daily_cost_usd: f64,
+}
+
+/// Process-wide per-sender usage accounting.
+#[derive(Default)]
+pub struct SenderUsageLedger {
+    state: Mutex<LedgerState>,
+}
+
+/// Global ledger instance fed from the agent loop.
+pub fn sender_usage_ledger() -> &'static SenderUsageLedger {
+    static LEDGER: OnceLock<SenderUsageLedger> = OnceLock::new();
+    LEDGER.get_or_init(SenderUsageLedger::default)
+}
+
+impl SenderUsageLedger {
+    /// Record one LLM turn for a sender.
+    ///
+    /// `estimated` marks token counts approximated from message sizes
+    /// because the provider returned no usage block.
+    pub fn record(
+        &self,
+        sender_key: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+        estimated: bool,
+    ) {
+        self.record_at(
+            Local::now().date_naive(),
+            sender_key,
+            input_tokens,
+            output_tokens,
+            cost_usd,
+            estimated,
+        );
+    }
+
+    fn record_at(
+        &self,
+        date: NaiveDate,
+        sender_key: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+        estimated: bool,
+    ) {
+        let mut state = self.state.lock();
+
+        if state.global_day != Some(date) {
+            state.global_day = Some(date);
+            state.global_daily_cost_usd = 0.0;
+        }
+        state.global_daily_cost_usd += cost_usd;
+
+        let entry = state.senders.entry(sender_key.to_string()).or_default();
+        entry.roll_over(date);
+        entry.today.add(input_tokens, output_tokens, cost_usd);
+        entry.this_month.add(input_tokens, output_tokens, cost_usd);
+        entry.estimated |= estimated;
+    }
+
+    /// Usage stats for a sender, with windows rolled over to the current day.
+    pub fn stats_for(&self, sender_key: &str) -> SenderUsageStats {
+        self.stats_at(Local::now().date_naive(), sender_key)
+    }
+
+    fn stats_at(&self, date: NaiveDate, sender_key: &str) -> SenderUsageStats {
+        let mut state = self.state.lock();
+        let Some(entry) = state.senders.get_mut(sender_key) else {
+            return SenderUsageStats::default();
+        };
+        entry.roll_over(date);
+        SenderUsageStats {
+            today: entry.today,
+            this_month: entry.this_month,
+            estimated: entry.estimated,
+        }
+    }
+
+    /// Total cost recorded across all senders today.
+    pub fn global_daily_cost_usd(&self) -> f64 {
+        self.global_daily_cost_at(Local::now().date_naive())
+    }
+
+    fn global_daily_cost_at(&self, date: NaiveDate) -> f64 {
+        let state = self.state.lock();
+        if state.global_day == Some(date) {
+            state.global_daily_cost_usd
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn record_accumulates_daily_and_monthly_totals() {
+        let ledger = SenderUsageLedger::default();
+        let today = date(2026, 8, 29);
+
+        ledger.record_at(today, "telegram_1", 100, 50, 0.01, false);
+        ledger.record_at(today, "telegram_1", 200, 80, 0.02, false);
+
+        let stats = ledger.stats_at(today, "telegram_1");
+        assert_eq!(stats.today.messages, 2);
+        assert_eq!(stats.today.input_tokens, 300);
+        assert_eq!(stats.today.output_tokens, 130);
+        assert!((stats.today.cost_usd - 0.03).abs() < 1e-9);
+        assert_eq!(stats.this_month, stats.today);
+        assert!(!stats.estimated);
+    }
+
+    #[test]
+    fn daily_window_resets_at_midnight_but_month_carries_over() {
+        let ledger = SenderUsageLedger::default();
+
+        ledger.record_at(date(2026, 8, 28), "slack_C1", 1000, 400, 0.10, false);
+        let stats = ledger.stats_at(date(2026, 8, 29), "slack_C1");
+
+        assert_eq!(stats.today, UsageWindowTotals::default());
+        assert_eq!(stats.this_month.messages, 1);
+        assert_eq!(stats.this_month.input_tokens, 1000);
+
+        // New month drops the monthly window too.
+        let next_month = ledger.stats_at(date(2026, 9, 1), "slack_C1");
+        assert_eq!(next_month.this_month, UsageWindowTotals::default());
+    }
+
+    #[test]
+    fn accumulation_continues_across_provider_switches() {
+        let ledger = SenderUsageLedger::default();
+        let today = date(2026, 8, 29);
+
+        // Same sender, different providers/models behind the scenes — the
+        // ledger is keyed by sender only, so totals keep accumulating.
+        ledger.record_at(today, "discord_9", 500, 100, 0.05, false);
+        ledger.record_at(today, "discord_9", 700, 300, 0.00, true);
+
+        let stats = ledger.stats_at(today, "discord_9");
+        assert_eq!(stats.today.messages, 2);
+        assert_eq!(stats.today.input_tokens, 1200);
+        assert_eq!(stats.today.output_tokens, 400);
+        assert!(stats.estimated, "one estimated turn marks the sender");
+    }
+
+    #[test]
+    fn senders_are_isolated_from_each_other() {
+        let ledger = SenderUsageLedger::default();
+        let today = date(2026, 8, 29);
+
+        ledger.record_at(today, "telegram_a", 10, 5, 0.001, false);
+        ledger.record_at(today, "telegram_b", 20, 10, 0.002, false);
+
+        assert_eq!(ledger.stats_at(today, "telegram_a").today.input_tokens, 10);
+        assert_eq!(ledger.stats_at(today, "telegram_b").today.input_tokens, 20);
+        assert_eq!(
+            ledger.stats_at(today, "telegram_c"),
+            SenderUsageStats::default()
+        );
+    }
+
+    #[test]
+    fn global_daily_cost_sums_all_senders_and_resets_daily() {
+        let ledger = SenderUsageLedger::default();
+        let today = date(2026, 8, 29);
+
+        ledger.record_at(today, "telegram_a", 10, 5, 0.01, false);
+        ledger.record_at(today, "slack_b", 20, 10, 0.02, false);
+        assert!((ledger.global_daily_cost_at(today) - 0.03).abs() < 1e-9);
+
+        assert!((ledger.global_daily_cost_at(date(2026, 8, 30))).abs() < f64::EPSILON);
+    }
+}
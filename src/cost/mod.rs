@@ -1,8 +1,11 @@
+pub mod ledger;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
+pub use ledger::{sender_usage_ledger, SenderUsageLedger, SenderUsageStats, UsageWindowTotals};
+#[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
 pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
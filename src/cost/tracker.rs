@@ -476,6 +476,26 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn daily_cost_accumulates_across_requests() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let first = TokenUsage::new("provider/model-a", 1000, 500, 3.0, 15.0);
+        let second = TokenUsage::new("provider/model-b", 2000, 100, 1.0, 2.0);
+        let expected = first.cost_usd + second.cost_usd;
+
+        tracker.record_usage(first).unwrap();
+        tracker.record_usage(second).unwrap();
+
+        let today_cost = tracker.get_daily_cost(Utc::now().date_naive()).unwrap();
+        assert!((today_cost - expected).abs() < f64::EPSILON);
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 2);
+        assert_eq!(summary.by_model.len(), 2);
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
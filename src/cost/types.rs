@@ -13,6 +13,13 @@ pub struct TokenUsage {
     pub total_tokens: u64,
     /// Calculated cost in USD
     pub cost_usd: f64,
+    /// Input tokens served from the provider's prompt cache (billed at a
+    /// reduced rate). Zero for providers that don't report cache usage.
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Input tokens written to the provider's prompt cache this request.
+    #[serde(default)]
+    pub cache_write_tokens: u64,
     /// Timestamp of the request
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -50,10 +57,20 @@ impl TokenUsage {
             output_tokens,
             total_tokens,
             cost_usd,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
             timestamp: chrono::Utc::now(),
         }
     }
 
+    /// Attach prompt-cache token counts reported by the provider, so cache
+    /// savings are visible in the persisted cost records.
+    pub fn with_cache_tokens(mut self, cache_read_tokens: u64, cache_write_tokens: u64) -> Self {
+        self.cache_read_tokens = cache_read_tokens;
+        self.cache_write_tokens = cache_write_tokens;
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
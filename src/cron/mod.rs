@@ -2,24 +2,29 @@ use crate::config::Config;
 use crate::security::SecurityPolicy;
 use anyhow::{anyhow, bail, Result};
 
+mod nl;
 mod schedule;
 mod store;
 mod types;
 
 pub mod scheduler;
 
+#[allow(unused_imports)]
+pub use nl::{describe_schedule, parse_natural_schedule};
 #[allow(unused_imports)]
 pub use schedule::{
     next_run_for_schedule, normalize_expression, schedule_cron_expression, validate_schedule,
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, all_overdue_jobs, due_jobs, get_job, list_jobs, list_runs, record_last_run,
-    record_run, remove_job, reschedule_after_run, sync_declarative_jobs, update_job,
+    add_agent_job, all_overdue_jobs, due_jobs, get_job, list_jobs, list_runs, mark_job_running,
+    queue_pending_run, record_last_run, record_run, remove_job, reschedule_after_run,
+    skip_overlapping_run, sync_declarative_jobs, take_pending_run, update_job,
 };
+#[allow(unused_imports)]
 pub use types::{
     deserialize_maybe_stringified, CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType,
-    Schedule, SessionTarget,
+    OverlapPolicy, Schedule, SessionTarget, RUNNING_STALE_AFTER_SECS,
 };
 
 /// Validate a shell command against the full security policy (allowlist + risk gate).
@@ -153,10 +158,17 @@ pub(crate) fn add_job(config: &Config, expression: &str, command: &str) -> Resul
 }
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
+pub async fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
     match command {
-        crate::CronCommands::List => {
+        crate::CronCommands::List { format } => {
             let jobs = list_jobs(config)?;
+            if format.eq_ignore_ascii_case("json") {
+                println!("{}", serde_json::to_string_pretty(&jobs)?);
+                return Ok(());
+            }
+            if !format.eq_ignore_ascii_case("text") {
+                bail!("unsupported format '{format}', expected 'text' or 'json'");
+            }
             if jobs.is_empty() {
                 println!("No scheduled tasks yet.");
                 println!("\nUsage:");
@@ -164,19 +176,25 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 return Ok(());
             }
 
+            let now = chrono::Utc::now();
             println!("🕒 Scheduled jobs ({}):", jobs.len());
             for job in jobs {
                 let last_run = job
                     .last_run
                     .map_or_else(|| "never".into(), |d| d.to_rfc3339());
-                let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
+                let last_status = if job.is_running(now) {
+                    "running".to_string()
+                } else {
+                    job.last_status.clone().unwrap_or_else(|| "n/a".into())
+                };
                 println!(
-                    "- {} | {:?} | next={} | last={} ({})",
+                    "- {} | {:?} | next={} | last={} ({}) | runs={}",
                     job.id,
                     job.schedule,
                     job.next_run.to_rfc3339(),
                     last_run,
                     last_status,
+                    job.run_count,
                 );
                 if !job.command.is_empty() {
                     println!("    cmd: {}", job.command);
@@ -187,6 +205,72 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             }
             Ok(())
         }
+        crate::CronCommands::Show { id } => {
+            let job = get_job(config, &id)?;
+            let now = chrono::Utc::now();
+            println!("🕒 Cron job {}", job.id);
+            if let Some(name) = &job.name {
+                println!("  Name     : {name}");
+            }
+            println!(
+                "  Type     : {}",
+                <JobType as Into<&str>>::into(job.job_type.clone())
+            );
+            println!("  Schedule : {:?}", job.schedule);
+            println!("  Enabled  : {}", job.enabled);
+            if !job.command.is_empty() {
+                println!("  Command  : {}", job.command);
+            }
+            if let Some(prompt) = &job.prompt {
+                println!("  Prompt   : {prompt}");
+            }
+            if !job.delivery.mode.eq_ignore_ascii_case("none") {
+                println!(
+                    "  Delivery : {} via {} to {}",
+                    job.delivery.mode,
+                    job.delivery.channel.as_deref().unwrap_or("-"),
+                    job.delivery.to.as_deref().unwrap_or("-"),
+                );
+            }
+            println!("  Overlap  : {}", job.overlap_policy.as_str());
+            println!("  Created  : {}", job.created_at.to_rfc3339());
+            println!("  Next run : {}", job.next_run.to_rfc3339());
+            println!("  Run count: {}", job.run_count);
+            if job.skip_count > 0 {
+                println!("  Skipped  : {}", job.skip_count);
+            }
+            if job.is_running(now) {
+                if let Some(started) = job.running_since {
+                    println!("  State    : running since {}", started.to_rfc3339());
+                }
+            }
+            println!(
+                "  Last run : {} ({})",
+                job.last_run
+                    .map_or_else(|| "never".to_string(), |d| d.to_rfc3339()),
+                job.last_status.as_deref().unwrap_or("n/a"),
+            );
+            if let Some(output) = job.last_output.as_deref().filter(|o| !o.trim().is_empty()) {
+                println!("  Last output:");
+                for line in output.lines() {
+                    println!("    {line}");
+                }
+            }
+            Ok(())
+        }
+        crate::CronCommands::Run { id } => {
+            println!("▶️  Running cron job {id} now…");
+            let (success, output) = scheduler::run_job_now(config, &id).await?;
+            if success {
+                println!("✅ Job completed");
+            } else {
+                println!("❌ Job failed");
+            }
+            if !output.trim().is_empty() {
+                println!("{output}");
+            }
+            Ok(())
+        }
         crate::CronCommands::Add {
             expression,
             tz,
@@ -230,6 +314,34 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             }
             Ok(())
         }
+        crate::CronCommands::AddNl {
+            phrase,
+            tz,
+            yes,
+            command,
+        } => {
+            let tz = tz.or_else(|| config.cron.default_timezone.clone());
+            let schedule = parse_natural_schedule(&phrase, tz.as_deref(), chrono::Utc::now())?;
+            println!("interpreted as: {}", describe_schedule(&schedule));
+
+            if !yes {
+                print!("Create this task? [y/N] ");
+                use std::io::Write as _;
+                std::io::stdout().flush().ok();
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer)?;
+                if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+
+            let job = add_shell_job(config, None, schedule, &command)?;
+            println!("✅ Added cron job {}", job.id);
+            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Cmd : {}", job.command);
+            Ok(())
+        }
         crate::CronCommands::AddAt {
             at,
             agent,
@@ -355,18 +467,26 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             command,
             name,
             allowed_tools,
+            overlap_policy,
         } => {
             if expression.is_none()
                 && tz.is_none()
                 && command.is_none()
                 && name.is_none()
                 && allowed_tools.is_empty()
+                && overlap_policy.is_none()
             {
                 bail!(
-                    "At least one of --expression, --tz, --command, --name, or --allowed-tool must be provided"
+                    "At least one of --expression, --tz, --command, --name, --allowed-tool, or --overlap-policy must be provided"
                 );
             }
 
+            let overlap_policy = overlap_policy
+                .as_deref()
+                .map(OverlapPolicy::try_from)
+                .transpose()
+                .map_err(|e| anyhow!(e))?;
+
             let existing = if expression.is_some() || tz.is_some() || !allowed_tools.is_empty() {
                 Some(get_job(config, &id)?)
             } else {
@@ -413,6 +533,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 } else {
                     Some(allowed_tools)
                 },
+                overlap_policy,
                 ..CronJobPatch::default()
             };
 
@@ -471,7 +592,7 @@ pub fn resume_job(config: &Config, id: &str) -> Result<CronJob> {
     )
 }
 
-fn parse_delay(input: &str) -> Result<chrono::Duration> {
+pub(crate) fn parse_delay(input: &str) -> Result<chrono::Duration> {
     let input = input.trim();
     if input.is_empty() {
         anyhow::bail!("delay must not be empty");
@@ -520,7 +641,7 @@ mod tests {
         .unwrap()
     }
 
-    fn run_update(
+    async fn run_update(
         config: &Config,
         id: &str,
         expression: Option<&str>,
@@ -536,50 +657,58 @@ mod tests {
                 command: command.map(Into::into),
                 name: name.map(Into::into),
                 allowed_tools: vec![],
+                overlap_policy: None,
             },
             config,
         )
+        .await
     }
 
-    #[test]
-    fn update_changes_command_via_handler() {
+    #[tokio::test]
+    async fn update_changes_command_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo original");
 
-        run_update(&config, &job.id, None, None, Some("echo updated"), None).unwrap();
+        run_update(&config, &job.id, None, None, Some("echo updated"), None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.command, "echo updated");
         assert_eq!(updated.id, job.id);
     }
 
-    #[test]
-    fn update_changes_expression_via_handler() {
+    #[tokio::test]
+    async fn update_changes_expression_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None).unwrap();
+        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.expression, "0 9 * * *");
     }
 
-    #[test]
-    fn update_changes_name_via_handler() {
+    #[tokio::test]
+    async fn update_changes_name_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        run_update(&config, &job.id, None, None, None, Some("new-name")).unwrap();
+        run_update(&config, &job.id, None, None, None, Some("new-name"))
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.name.as_deref(), Some("new-name"));
     }
 
-    #[test]
-    fn update_tz_alone_sets_timezone() {
+    #[tokio::test]
+    async fn update_tz_alone_sets_timezone() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
@@ -592,6 +721,7 @@ mod tests {
             None,
             None,
         )
+        .await
         .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
@@ -604,8 +734,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn update_expression_preserves_existing_tz() {
+    #[tokio::test]
+    async fn update_expression_preserves_existing_tz() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(
@@ -615,7 +745,9 @@ mod tests {
             "echo test",
         );
 
-        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None).unwrap();
+        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(
@@ -627,8 +759,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn update_preserves_unchanged_fields() {
+    #[tokio::test]
+    async fn update_preserves_unchanged_fields() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = add_shell_job(
@@ -642,7 +774,9 @@ mod tests {
         )
         .unwrap();
 
-        run_update(&config, &job.id, None, None, Some("echo changed"), None).unwrap();
+        run_update(&config, &job.id, None, None, Some("echo changed"), None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.command, "echo changed");
@@ -650,19 +784,19 @@ mod tests {
         assert_eq!(updated.expression, "*/5 * * * *");
     }
 
-    #[test]
-    fn update_no_flags_fails() {
+    #[tokio::test]
+    async fn update_no_flags_fails() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        let result = run_update(&config, &job.id, None, None, None, None);
+        let result = run_update(&config, &job.id, None, None, None, None).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("At least one of"));
     }
 
-    #[test]
-    fn update_nonexistent_job_fails() {
+    #[tokio::test]
+    async fn update_nonexistent_job_fails() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
 
@@ -673,7 +807,8 @@ mod tests {
             None,
             Some("echo test"),
             None,
-        );
+        )
+        .await;
         assert!(result.is_err());
     }
 
@@ -756,8 +891,8 @@ mod tests {
         assert_eq!(approved.command, "touch cron-medium-risk-update");
     }
 
-    #[test]
-    fn cli_update_requires_explicit_approval_for_medium_risk() {
+    #[tokio::test]
+    async fn cli_update_requires_explicit_approval_for_medium_risk() {
         let tmp = TempDir::new().unwrap();
         let mut config = test_config(&tmp);
         config.autonomy.allowed_commands = vec!["echo".into(), "touch".into()];
@@ -770,7 +905,8 @@ mod tests {
             None,
             Some("touch cron-cli-medium-risk"),
             None,
-        );
+        )
+        .await;
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -876,8 +1012,8 @@ mod tests {
             .contains("blocked by security policy"));
     }
 
-    #[test]
-    fn cli_agent_flag_creates_agent_job() {
+    #[tokio::test]
+    async fn cli_agent_flag_creates_agent_job() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
 
@@ -891,6 +1027,7 @@ mod tests {
             },
             &config,
         )
+        .await
         .unwrap();
 
         let jobs = list_jobs(&config).unwrap();
@@ -902,8 +1039,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn cli_agent_flag_bypasses_shell_security_validation() {
+    #[tokio::test]
+    async fn cli_agent_flag_bypasses_shell_security_validation() {
         let tmp = TempDir::new().unwrap();
         let mut config = test_config(&tmp);
         config.autonomy.allowed_commands = vec!["echo".into()];
@@ -921,7 +1058,8 @@ mod tests {
                 command: "Check server health: disk space, memory, CPU load".into(),
             },
             &config,
-        );
+        )
+        .await;
         assert!(result.is_ok());
 
         let jobs = list_jobs(&config).unwrap();
@@ -929,8 +1067,8 @@ mod tests {
         assert_eq!(jobs[0].job_type, JobType::Agent);
     }
 
-    #[test]
-    fn cli_agent_allowed_tools_persist() {
+    #[tokio::test]
+    async fn cli_agent_allowed_tools_persist() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
 
@@ -944,6 +1082,7 @@ mod tests {
             },
             &config,
         )
+        .await
         .unwrap();
 
         let jobs = list_jobs(&config).unwrap();
@@ -954,8 +1093,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn cli_update_agent_allowed_tools_persist() {
+    #[tokio::test]
+    async fn cli_update_agent_allowed_tools_persist() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = add_agent_job(
@@ -982,17 +1121,121 @@ mod tests {
                 command: None,
                 name: None,
                 allowed_tools: vec!["shell".into()],
+                overlap_policy: None,
             },
             &config,
         )
+        .await
         .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.allowed_tools, Some(vec!["shell".into()]));
     }
 
-    #[test]
-    fn cli_without_agent_flag_defaults_to_shell_job() {
+    #[tokio::test]
+    async fn cli_add_nl_creates_job_with_yes() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        handle_command(
+            crate::CronCommands::AddNl {
+                phrase: "every weekday at 9am".into(),
+                tz: Some("Europe/Berlin".into()),
+                yes: true,
+                command: "echo stand-up".into(),
+            },
+            &config,
+        )
+        .await
+        .unwrap();
+
+        let jobs = list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].expression, "0 9 * * 1-5");
+        assert_eq!(jobs[0].command, "echo stand-up");
+        assert!(matches!(
+            &jobs[0].schedule,
+            Schedule::Cron { tz: Some(tz), .. } if tz == "Europe/Berlin"
+        ));
+    }
+
+    #[tokio::test]
+    async fn cli_add_nl_rejects_unparseable_phrase() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let err = handle_command(
+            crate::CronCommands::AddNl {
+                phrase: "whenever you feel like it".into(),
+                tz: None,
+                yes: true,
+                command: "echo never".into(),
+            },
+            &config,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(format!("{err:#}").contains("Supported phrases"));
+        assert!(list_jobs(&config).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cli_update_overlap_policy_persists() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = make_job(&config, "*/5 * * * *", None, "echo overlap");
+        assert_eq!(job.overlap_policy, OverlapPolicy::Skip);
+
+        handle_command(
+            crate::CronCommands::Update {
+                id: job.id.clone(),
+                expression: None,
+                tz: None,
+                command: None,
+                name: None,
+                allowed_tools: vec![],
+                overlap_policy: Some("queue".into()),
+            },
+            &config,
+        )
+        .await
+        .unwrap();
+
+        let updated = get_job(&config, &job.id).unwrap();
+        assert_eq!(updated.overlap_policy, OverlapPolicy::Queue);
+    }
+
+    #[tokio::test]
+    async fn cli_update_rejects_invalid_overlap_policy() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = make_job(&config, "*/5 * * * *", None, "echo overlap");
+
+        let err = handle_command(
+            crate::CronCommands::Update {
+                id: job.id.clone(),
+                expression: None,
+                tz: None,
+                command: None,
+                name: None,
+                allowed_tools: vec![],
+                overlap_policy: Some("sometimes".into()),
+            },
+            &config,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("Invalid overlap policy"));
+        assert_eq!(
+            get_job(&config, &job.id).unwrap().overlap_policy,
+            OverlapPolicy::Skip
+        );
+    }
+
+    #[tokio::test]
+    async fn cli_without_agent_flag_defaults_to_shell_job() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
 
@@ -1006,6 +1249,7 @@ mod tests {
             },
             &config,
         )
+        .await
         .unwrap();
 
         let jobs = list_jobs(&config).unwrap();
@@ -1013,4 +1257,80 @@ mod tests {
         assert_eq!(jobs[0].job_type, JobType::Shell);
         assert_eq!(jobs[0].command, "echo ok");
     }
+
+    #[tokio::test]
+    async fn cli_list_accepts_json_format() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let _ = make_job(&config, "*/5 * * * *", None, "echo json");
+
+        handle_command(
+            crate::CronCommands::List {
+                format: "json".into(),
+            },
+            &config,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn cli_list_rejects_unknown_format() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let result = handle_command(
+            crate::CronCommands::List {
+                format: "yaml".into(),
+            },
+            &config,
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("unsupported format"));
+    }
+
+    #[tokio::test]
+    async fn cli_show_displays_existing_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = make_job(&config, "*/5 * * * *", None, "echo show");
+
+        handle_command(crate::CronCommands::Show { id: job.id }, &config)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn cli_show_unknown_job_fails() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let result = handle_command(
+            crate::CronCommands::Show {
+                id: "no-such-job".into(),
+            },
+            &config,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn cli_run_executes_job_and_records_status() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = make_job(&config, "*/5 * * * *", None, "echo cli-run");
+
+        handle_command(crate::CronCommands::Run { id: job.id.clone() }, &config)
+            .await
+            .unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.last_status.as_deref(), Some("ok"));
+        assert_eq!(stored.run_count, 1);
+    }
 }
@@ -0,0 +1,534 @@
+use crate::cron::Schedule;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
+use std::str::FromStr;
+
+/// Patterns accepted by [`parse_natural_schedule`]; included in parse errors
+/// so users can see what phrasing is supported.
+const SUPPORTED_PATTERNS: &str = "\
+Supported phrases:
+  every day at 9am / daily at 18:30
+  every weekday at 9am / every weekend at 10
+  every monday at 8:30
+  every 2 hours / every 30 minutes / every 10 seconds
+  first monday of the month at 8 (also second/third/fourth)
+  today at 17:00 / tomorrow at 18:30
+  in 45 minutes / in 2 hours";
+
+/// Parse a natural-language schedule phrase into a [`Schedule`].
+///
+/// Pure function: all "current time" decisions (e.g. whether "today at 9"
+/// has already passed) are made against the supplied `now`, and the
+/// optional IANA timezone is used both for interpreting wall-clock times
+/// and as the `tz` of produced cron schedules. Phrases map onto the
+/// existing task types: recurring wall-clock phrases become `Cron`,
+/// fixed intervals become `Every`, and one-shot phrases become `At`.
+pub fn parse_natural_schedule(
+    phrase: &str,
+    tz: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Schedule> {
+    let normalized = phrase.trim().to_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if words.is_empty() {
+        bail!("Empty schedule phrase.\n{SUPPORTED_PATTERNS}");
+    }
+
+    let timezone = tz
+        .map(|name| {
+            chrono_tz::Tz::from_str(name).map_err(|_| anyhow!("Invalid IANA timezone: {name}"))
+        })
+        .transpose()?;
+
+    match words[0] {
+        "every" => parse_every(&words[1..], tz),
+        // "daily at 9am" is shorthand for "every day at 9am".
+        "daily" => {
+            let mut rebuilt = vec!["day"];
+            rebuilt.extend(&words[1..]);
+            parse_every(&rebuilt, tz)
+        }
+        "today" | "tomorrow" => parse_relative_day(words[0], &words[1..], timezone, now),
+        "in" => parse_in(&words[1..], now),
+        "first" | "second" | "third" | "fourth" => parse_nth_weekday(&words, tz),
+        _ => bail!("Could not parse schedule phrase '{phrase}'.\n{SUPPORTED_PATTERNS}"),
+    }
+}
+
+/// Human-readable rendering of a parsed schedule for the
+/// "interpreted as: …" confirmation line.
+pub fn describe_schedule(schedule: &Schedule) -> String {
+    match schedule {
+        Schedule::Cron { expr, tz } => match tz {
+            Some(tz) => format!("{expr} {tz}"),
+            None => format!("{expr} UTC"),
+        },
+        Schedule::Every { every_ms } => format!("every {}", format_interval(*every_ms)),
+        Schedule::At { at } => format!("once at {}", at.to_rfc3339()),
+    }
+}
+
+fn format_interval(every_ms: u64) -> String {
+    if every_ms.is_multiple_of(3_600_000) {
+        format!("{}h", every_ms / 3_600_000)
+    } else if every_ms.is_multiple_of(60_000) {
+        format!("{}m", every_ms / 60_000)
+    } else if every_ms.is_multiple_of(1000) {
+        format!("{}s", every_ms / 1000)
+    } else {
+        format!("{every_ms}ms")
+    }
+}
+
+/// Split an argument list on the `at` keyword: `(before, time words after)`.
+fn split_on_at<'a>(args: &'a [&'a str]) -> (&'a [&'a str], Option<&'a [&'a str]>) {
+    match args.iter().position(|w| *w == "at") {
+        Some(i) => (&args[..i], Some(&args[i + 1..])),
+        None => (args, None),
+    }
+}
+
+fn parse_every(args: &[&str], tz: Option<&str>) -> Result<Schedule> {
+    if args.is_empty() {
+        bail!("'every' needs a unit or weekday.\n{SUPPORTED_PATTERNS}");
+    }
+    let (head, time) = split_on_at(args);
+
+    if let Some(every_ms) = parse_interval(head)? {
+        if time.is_some() {
+            bail!("Interval schedules like 'every 2 hours' do not take an 'at' time");
+        }
+        return Ok(Schedule::Every { every_ms });
+    }
+
+    let dow = match head {
+        ["day" | "days"] => "*".to_string(),
+        ["weekday" | "weekdays"] => "1-5".to_string(),
+        ["weekend" | "weekends"] => "0,6".to_string(),
+        [day] => weekday_number(day)
+            .with_context(|| format!("Unknown schedule unit '{day}'.\n{SUPPORTED_PATTERNS}"))?
+            .to_string(),
+        _ => bail!(
+            "Could not parse schedule phrase 'every {}'.\n{SUPPORTED_PATTERNS}",
+            args.join(" ")
+        ),
+    };
+
+    let (hour, minute) = match time {
+        Some(words) => parse_time(words)?,
+        None => (0, 0),
+    };
+
+    Ok(Schedule::Cron {
+        expr: format!("{minute} {hour} * * {dow}"),
+        tz: tz.map(str::to_string),
+    })
+}
+
+/// Recognize a fixed-interval head (`["2", "hours"]`, `["minute"]`, …).
+/// Returns `Ok(None)` when the head is not an interval so the caller can
+/// try the wall-clock forms instead.
+fn parse_interval(head: &[&str]) -> Result<Option<u64>> {
+    let (count, unit, explicit_count) = match head {
+        [unit] => (1u64, *unit, false),
+        [n, unit] => match n.parse::<u64>() {
+            Ok(count) => (count, *unit, true),
+            Err(_) => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    let unit_ms: u64 = match unit {
+        "second" | "seconds" | "sec" | "secs" => 1000,
+        "minute" | "minutes" | "min" | "mins" => 60_000,
+        "hour" | "hours" | "hr" | "hrs" => 3_600_000,
+        // "every day" without a count is the daily cron form, but an
+        // explicit "every 2 days" is a plain interval.
+        "day" | "days" if explicit_count => 86_400_000,
+        _ => return Ok(None),
+    };
+
+    if count == 0 {
+        bail!("Interval count must be greater than zero");
+    }
+    Ok(Some(count.saturating_mul(unit_ms)))
+}
+
+/// Weekday name to standard crontab number (0 = Sunday … 6 = Saturday).
+/// Accepts full names, common abbreviations, and plurals ("mondays").
+fn weekday_number(word: &str) -> Option<u8> {
+    let word = word.strip_suffix('s').unwrap_or(word);
+    match word {
+        "sunday" | "sun" => Some(0),
+        "monday" | "mon" => Some(1),
+        "tuesday" | "tue" | "tues" => Some(2),
+        "wednesday" | "wed" => Some(3),
+        "thursday" | "thu" | "thur" | "thurs" => Some(4),
+        "friday" | "fri" => Some(5),
+        "saturday" | "sat" => Some(6),
+        _ => None,
+    }
+}
+
+/// "first monday of the month at 8" — expressed as a day-of-month range
+/// intersected with a weekday (the cron crate requires both restricted
+/// fields to match, so `1-7` + `monday` is exactly the first Monday).
+fn parse_nth_weekday(words: &[&str], tz: Option<&str>) -> Result<Schedule> {
+    let (head, time) = split_on_at(words);
+
+    let (ordinal, day, rest) = match head {
+        [ordinal, day, rest @ ..] => (*ordinal, *day, rest),
+        _ => bail!(
+            "Could not parse schedule phrase '{}'.\n{SUPPORTED_PATTERNS}",
+            words.join(" ")
+        ),
+    };
+    if !matches!(rest, ["of", "the", "month"] | ["of", "month"]) {
+        bail!(
+            "Could not parse schedule phrase '{}'. Expected e.g. 'first monday of the month at 8'.\n{SUPPORTED_PATTERNS}",
+            words.join(" ")
+        );
+    }
+
+    let dom = match ordinal {
+        "first" => "1-7",
+        "second" => "8-14",
+        "third" => "15-21",
+        "fourth" => "22-28",
+        _ => bail!("Unsupported ordinal '{ordinal}' (expected first, second, third, or fourth)"),
+    };
+    let dow = weekday_number(day)
+        .with_context(|| format!("Unknown weekday '{day}'.\n{SUPPORTED_PATTERNS}"))?;
+
+    let (hour, minute) = match time {
+        Some(words) => parse_time(words)?,
+        None => (0, 0),
+    };
+
+    Ok(Schedule::Cron {
+        expr: format!("{minute} {hour} {dom} * {dow}"),
+        tz: tz.map(str::to_string),
+    })
+}
+
+fn parse_relative_day(
+    which: &str,
+    args: &[&str],
+    timezone: Option<chrono_tz::Tz>,
+    now: DateTime<Utc>,
+) -> Result<Schedule> {
+    let time = match args {
+        ["at", time @ ..] => time,
+        _ => bail!("'{which}' needs a time, e.g. '{which} at 18:30'"),
+    };
+    let (hour, minute) = parse_time(time)?;
+
+    let local_today = match timezone {
+        Some(tz) => now.with_timezone(&tz).date_naive(),
+        None => now.date_naive(),
+    };
+    let date = if which == "tomorrow" {
+        local_today.succ_opt().context("Date overflow")?
+    } else {
+        local_today
+    };
+
+    let at = resolve_local(timezone, date, hour, minute)?;
+    if at <= now {
+        bail!("'{which} at {hour}:{minute:02}' is already in the past; try 'tomorrow at …'");
+    }
+    Ok(Schedule::At { at })
+}
+
+fn parse_in(args: &[&str], now: DateTime<Utc>) -> Result<Schedule> {
+    let every_ms = parse_interval(args)?.with_context(|| {
+        format!(
+            "Could not parse delay 'in {}'. Expected e.g. 'in 45 minutes'.\n{SUPPORTED_PATTERNS}",
+            args.join(" ")
+        )
+    })?;
+    let ms = i64::try_from(every_ms).context("Delay is too large")?;
+    let at = now
+        .checked_add_signed(ChronoDuration::milliseconds(ms))
+        .context("Delay overflowed DateTime")?;
+    Ok(Schedule::At { at })
+}
+
+/// Convert a wall-clock time on a date in an optional timezone to UTC.
+/// Falls back to the earlier instant for ambiguous DST-rollback times and
+/// rejects times that do not exist (spring-forward gap).
+fn resolve_local(
+    timezone: Option<chrono_tz::Tz>,
+    date: NaiveDate,
+    hour: u32,
+    minute: u32,
+) -> Result<DateTime<Utc>> {
+    let naive = date
+        .and_hms_opt(hour, minute, 0)
+        .with_context(|| format!("Invalid time {hour}:{minute:02}"))?;
+    match timezone {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok_or_else(|| anyhow!("{naive} does not exist in timezone {tz} (DST gap)")),
+        None => Ok(Utc.from_utc_datetime(&naive)),
+    }
+}
+
+/// Parse a time clause: `18:30`, `9am`, `8:05pm`, `9` (24h), `noon`,
+/// `midnight`. The words are joined so "9 am" and "9am" are equivalent.
+fn parse_time(words: &[&str]) -> Result<(u32, u32)> {
+    let raw: String = words.concat();
+    if raw.is_empty() {
+        bail!("Missing time after 'at'");
+    }
+    match raw.as_str() {
+        "noon" => return Ok((12, 0)),
+        "midnight" => return Ok((0, 0)),
+        _ => {}
+    }
+
+    let (digits, meridiem) = if let Some(stripped) = raw.strip_suffix("am") {
+        (stripped, Some("am"))
+    } else if let Some(stripped) = raw.strip_suffix("pm") {
+        (stripped, Some("pm"))
+    } else {
+        (raw.as_str(), None)
+    };
+
+    let (hour_s, minute_s) = digits.split_once(':').unwrap_or((digits, "0"));
+    let hour: u32 = hour_s
+        .trim()
+        .parse()
+        .with_context(|| format!("Could not parse time '{raw}'"))?;
+    let minute: u32 = minute_s
+        .trim()
+        .parse()
+        .with_context(|| format!("Could not parse time '{raw}'"))?;
+    if minute > 59 {
+        bail!("Invalid minute in time '{raw}'");
+    }
+
+    let hour = match meridiem {
+        Some(m) => {
+            if !(1..=12).contains(&hour) {
+                bail!("Invalid hour in time '{raw}' (am/pm hours are 1-12)");
+            }
+            match (m, hour) {
+                ("am", 12) => 0,
+                ("am", h) => h,
+                ("pm", 12) => 12,
+                (_, h) => h + 12,
+            }
+        }
+        None => {
+            if hour > 23 {
+                bail!("Invalid hour in time '{raw}'");
+            }
+            hour
+        }
+    };
+
+    Ok((hour, minute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixed_now() -> DateTime<Utc> {
+        // Friday 2026-08-28 12:00 UTC.
+        Utc.with_ymd_and_hms(2026, 8, 28, 12, 0, 0).unwrap()
+    }
+
+    fn parse(phrase: &str, tz: Option<&str>) -> Schedule {
+        parse_natural_schedule(phrase, tz, fixed_now())
+            .unwrap_or_else(|e| panic!("'{phrase}' should parse: {e:#}"))
+    }
+
+    fn parse_err(phrase: &str) -> String {
+        format!(
+            "{:#}",
+            parse_natural_schedule(phrase, None, fixed_now())
+                .expect_err(&format!("'{phrase}' should not parse"))
+        )
+    }
+
+    #[test]
+    fn recurring_phrases_map_to_cron_expressions() {
+        let cases = [
+            ("every day at 9am", "0 9 * * *"),
+            ("every day at 18:30", "30 18 * * *"),
+            ("every day", "0 0 * * *"),
+            ("daily at 7am", "0 7 * * *"),
+            ("every weekday at 9am", "0 9 * * 1-5"),
+            ("Every Weekday at 9AM", "0 9 * * 1-5"),
+            ("every weekend at 10", "0 10 * * 0,6"),
+            ("every monday at 8:30", "30 8 * * 1"),
+            ("every mondays at 8:30", "30 8 * * 1"),
+            ("every saturday at 10:15", "15 10 * * 6"),
+            ("every sunday at noon", "0 12 * * 0"),
+            ("every day at midnight", "0 0 * * *"),
+            ("every day at 9pm", "0 21 * * *"),
+            ("every day at 12pm", "0 12 * * *"),
+            ("every day at 12am", "0 0 * * *"),
+            ("first monday of the month at 8", "0 8 1-7 * 1"),
+            ("first monday of month at 8", "0 8 1-7 * 1"),
+            ("second tuesday of the month", "0 0 8-14 * 2"),
+            ("third friday of the month at 17:30", "30 17 15-21 * 5"),
+            ("fourth sunday of the month at 9am", "0 9 22-28 * 0"),
+        ];
+        for (phrase, expected) in cases {
+            match parse(phrase, None) {
+                Schedule::Cron { expr, tz } => {
+                    assert_eq!(expr, expected, "phrase: {phrase}");
+                    assert_eq!(tz, None, "phrase: {phrase}");
+                }
+                other => panic!("'{phrase}' should be cron, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn interval_phrases_map_to_every() {
+        let cases = [
+            ("every 2 hours", 7_200_000),
+            ("every hour", 3_600_000),
+            ("every 30 minutes", 1_800_000),
+            ("every minute", 60_000),
+            ("every 10 seconds", 10_000),
+            ("every 2 days", 172_800_000),
+        ];
+        for (phrase, expected) in cases {
+            match parse(phrase, None) {
+                Schedule::Every { every_ms } => {
+                    assert_eq!(every_ms, expected, "phrase: {phrase}");
+                }
+                other => panic!("'{phrase}' should be interval, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn one_shot_phrases_map_to_at() {
+        let cases = [
+            (
+                "tomorrow at 18:30",
+                Utc.with_ymd_and_hms(2026, 8, 29, 18, 30, 0).unwrap(),
+            ),
+            (
+                "today at 20:00",
+                Utc.with_ymd_and_hms(2026, 8, 28, 20, 0, 0).unwrap(),
+            ),
+            (
+                "in 45 minutes",
+                Utc.with_ymd_and_hms(2026, 8, 28, 12, 45, 0).unwrap(),
+            ),
+            (
+                "in 2 hours",
+                Utc.with_ymd_and_hms(2026, 8, 28, 14, 0, 0).unwrap(),
+            ),
+        ];
+        for (phrase, expected) in cases {
+            match parse(phrase, None) {
+                Schedule::At { at } => assert_eq!(at, expected, "phrase: {phrase}"),
+                other => panic!("'{phrase}' should be one-shot, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn timezone_is_attached_to_cron_schedules() {
+        match parse("every weekday at 9am", Some("Europe/Berlin")) {
+            Schedule::Cron { expr, tz } => {
+                assert_eq!(expr, "0 9 * * 1-5");
+                assert_eq!(tz.as_deref(), Some("Europe/Berlin"));
+            }
+            other => panic!("expected cron, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn timezone_shifts_one_shot_wall_clock_times() {
+        // Berlin is UTC+2 in August, so 18:30 local is 16:30 UTC.
+        match parse("tomorrow at 18:30", Some("Europe/Berlin")) {
+            Schedule::At { at } => {
+                assert_eq!(at, Utc.with_ymd_and_hms(2026, 8, 29, 16, 30, 0).unwrap());
+            }
+            other => panic!("expected one-shot, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn today_in_the_past_is_rejected() {
+        let err = parse_err("today at 9am");
+        assert!(err.contains("already in the past"), "{err}");
+    }
+
+    #[test]
+    fn unparseable_phrases_list_supported_patterns() {
+        for phrase in [
+            "whenever you feel like it",
+            "every",
+            "every 2 fortnights",
+            "fifth monday of the month",
+            "first monday at 8",
+            "tomorrow",
+        ] {
+            let err = parse_err(phrase);
+            assert!(
+                err.contains("Supported phrases") || err.contains("needs a time"),
+                "'{phrase}' error should be helpful: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        assert!(parse_err("every day at 13pm").contains("am/pm hours are 1-12"));
+        assert!(parse_err("every day at 25").contains("Invalid hour"));
+        assert!(parse_err("every day at 9:75").contains("Invalid minute"));
+        assert!(parse_err("every 0 hours").contains("greater than zero"));
+        assert!(parse_err("every 2 hours at 9").contains("do not take an 'at' time"));
+
+        let err = format!(
+            "{:#}",
+            parse_natural_schedule("every day at 9", Some("Mars/Olympus"), fixed_now())
+                .expect_err("bad timezone should fail")
+        );
+        assert!(err.contains("Invalid IANA timezone"), "{err}");
+    }
+
+    #[test]
+    fn parsed_cron_schedules_compute_expected_next_runs() {
+        // "first monday of the month at 8": from Friday 2026-08-28 the next
+        // first Monday is 2026-09-07 (2026-09-01 is a Tuesday).
+        let schedule = parse("first monday of the month at 8", None);
+        let next = crate::cron::next_run_for_schedule(&schedule, fixed_now()).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 9, 7, 8, 0, 0).unwrap());
+
+        // "every weekday at 9am" from Friday noon → Monday 09:00.
+        let schedule = parse("every weekday at 9am", None);
+        let next = crate::cron::next_run_for_schedule(&schedule, fixed_now()).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 8, 31, 9, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn describe_schedule_prints_normalized_forms() {
+        assert_eq!(
+            describe_schedule(&parse("every weekday at 9am", Some("Europe/Berlin"))),
+            "0 9 * * 1-5 Europe/Berlin"
+        );
+        assert_eq!(
+            describe_schedule(&parse("every day at 18:30", None)),
+            "30 18 * * * UTC"
+        );
+        assert_eq!(describe_schedule(&parse("every 2 hours", None)), "every 2h");
+        assert_eq!(
+            describe_schedule(&parse("tomorrow at 18:30", None)),
+            "once at 2026-08-29T18:30:00+00:00"
+        );
+    }
+}
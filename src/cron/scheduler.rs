@@ -9,9 +9,10 @@ use crate::channels::{
 use crate::config::schema::{CronJobDecl, CronScheduleDecl};
 use crate::config::Config;
 use crate::cron::{
-    all_overdue_jobs, due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job,
-    reschedule_after_run, sync_declarative_jobs, update_job, CronJob, CronJobPatch, DeliveryConfig,
-    JobType, Schedule, SessionTarget,
+    all_overdue_jobs, due_jobs, mark_job_running, next_run_for_schedule, queue_pending_run,
+    record_last_run, record_run, remove_job, reschedule_after_run, skip_overlapping_run,
+    sync_declarative_jobs, take_pending_run, update_job, CronJob, CronJobPatch, DeliveryConfig,
+    JobType, OverlapPolicy, Schedule, SessionTarget,
 };
 use crate::security::SecurityPolicy;
 use anyhow::Result;
@@ -139,6 +140,41 @@ pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
     Box::pin(execute_job_with_retry(config, &security, job)).await
 }
 
+/// Run a stored job immediately through the full scheduler path: retry
+/// policy, delivery, run history, and last-run bookkeeping. The job's
+/// `next_run` is recomputed afterwards exactly like a scheduled execution.
+///
+/// Backs `zeroclaw cron run <id>`.
+pub async fn run_job_now(config: &Config, job_id: &str) -> Result<(bool, String)> {
+    let job = crate::cron::get_job(config, job_id)?;
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+    Ok(run_job_once(config, &security, &job).await)
+}
+
+/// Execute a single invocation: mark the running marker, run with the
+/// retry policy, then persist the result (delivery, run history,
+/// last-run bookkeeping, reschedule).
+async fn run_job_once(config: &Config, security: &SecurityPolicy, job: &CronJob) -> (bool, String) {
+    let started_at = Utc::now();
+    if let Err(e) = mark_job_running(config, &job.id, started_at) {
+        tracing::warn!("Failed to mark cron job '{}' running: {e}", job.id);
+    }
+    let (success, output) = Box::pin(execute_job_with_retry(config, security, job)).await;
+    let finished_at = Utc::now();
+    let success = Box::pin(persist_job_result(
+        config,
+        job,
+        success,
+        &output,
+        started_at,
+        finished_at,
+    ))
+    .await;
+
+    (success, output)
+}
+
 async fn execute_job_with_retry(
     config: &Config,
     security: &SecurityPolicy,
@@ -183,7 +219,11 @@ async fn process_due_jobs(
     // Refresh scheduler health on every successful poll cycle, including idle cycles.
     crate::health::mark_component_ok(component);
 
-    let max_concurrent = config.scheduler.max_concurrent.max(1);
+    let max_concurrent = config
+        .cron
+        .max_concurrent_jobs
+        .unwrap_or(config.scheduler.max_concurrent)
+        .max(1);
     let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
         let config = config.clone();
         let security = Arc::clone(security);
@@ -207,6 +247,28 @@ async fn process_due_jobs(
     }
 }
 
+/// What the scheduler does with an invocation that comes due while a
+/// previous run of the same job is still in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverlapDecision {
+    Run,
+    Skip,
+    Queue,
+}
+
+/// Decide how to handle a due job given its overlap policy and running
+/// marker. A stale marker (crash leftover) never blocks the run.
+fn overlap_decision(job: &CronJob, now: DateTime<Utc>) -> OverlapDecision {
+    if !job.is_running(now) {
+        return OverlapDecision::Run;
+    }
+    match job.overlap_policy {
+        OverlapPolicy::Allow => OverlapDecision::Run,
+        OverlapPolicy::Skip => OverlapDecision::Skip,
+        OverlapPolicy::Queue => OverlapDecision::Queue,
+    }
+}
+
 async fn execute_and_persist_job(
     config: &Config,
     security: &SecurityPolicy,
@@ -216,18 +278,73 @@ async fn execute_and_persist_job(
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
-    let started_at = Utc::now();
-    let (success, output) = Box::pin(execute_job_with_retry(config, security, job)).await;
-    let finished_at = Utc::now();
-    let success = Box::pin(persist_job_result(
-        config,
-        job,
-        success,
-        &output,
-        started_at,
-        finished_at,
-    ))
-    .await;
+    let now = Utc::now();
+    match overlap_decision(job, now) {
+        OverlapDecision::Run => {}
+        OverlapDecision::Skip => {
+            tracing::info!(
+                "Cron job '{}' is still running; skipping overlapping invocation",
+                job.id
+            );
+            crate::observability::runtime_trace::record_event(
+                "cron_overlap_skipped",
+                None,
+                None,
+                None,
+                None,
+                Some(false),
+                Some(&format!(
+                    "Skipped overlapping invocation of cron job '{}'",
+                    job.id
+                )),
+                serde_json::json!({ "job_id": job.id, "overlap_policy": "skip" }),
+            );
+            if let Err(e) = skip_overlapping_run(config, job, now) {
+                tracing::warn!("Failed to record skipped cron run for '{}': {e}", job.id);
+            }
+            return (
+                job.id.clone(),
+                true,
+                "skipped: previous run still in progress".to_string(),
+            );
+        }
+        OverlapDecision::Queue => {
+            tracing::info!(
+                "Cron job '{}' is still running; queueing missed invocation",
+                job.id
+            );
+            if let Err(e) = queue_pending_run(config, &job.id) {
+                tracing::warn!("Failed to queue pending cron run for '{}': {e}", job.id);
+            }
+            return (
+                job.id.clone(),
+                true,
+                "queued: previous run still in progress".to_string(),
+            );
+        }
+    }
+
+    let (mut success, mut output) = run_job_once(config, security, job).await;
+
+    // Drain a queued invocation that arrived while this run was in
+    // progress. The pending flag is a boolean, so any number of misses
+    // during one long run collapses into a single extra execution.
+    loop {
+        match take_pending_run(config, &job.id) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => {
+                tracing::warn!("Failed to check pending cron run for '{}': {e}", job.id);
+                break;
+            }
+        }
+        let latest = match crate::cron::get_job(config, &job.id) {
+            Ok(latest) => latest,
+            // One-shot jobs may have been auto-deleted by the run above.
+            Err(_) => break,
+        };
+        (success, output) = run_job_once(config, security, &latest).await;
+    }
 
     (job.id.clone(), success, output)
 }
@@ -806,6 +923,10 @@ mod tests {
             last_run: None,
             last_status: None,
             last_output: None,
+            run_count: 0,
+            running_since: None,
+            overlap_policy: OverlapPolicy::default(),
+            skip_count: 0,
         }
     }
 
@@ -1052,6 +1173,199 @@ mod tests {
         assert!(output.contains("rate limit exceeded"));
     }
 
+    #[tokio::test]
+    async fn run_job_now_executes_and_persists_status() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = cron::add_job(&config, "*/5 * * * *", "echo run-now-ok").unwrap();
+
+        let (success, output) = Box::pin(run_job_now(&config, &job.id)).await.unwrap();
+        assert!(success, "{output}");
+        assert!(output.contains("run-now-ok"));
+
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.last_status.as_deref(), Some("ok"));
+        assert!(stored.last_run.is_some());
+        assert_eq!(stored.run_count, 1);
+        assert!(stored.running_since.is_none());
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "ok");
+    }
+
+    #[tokio::test]
+    async fn run_job_now_records_failure_status() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job =
+            cron::add_job(&config, "*/5 * * * *", "ls missing_file_for_run_now_test").unwrap();
+
+        let (success, output) = Box::pin(run_job_now(&config, &job.id)).await.unwrap();
+        assert!(!success);
+        assert!(output.contains("missing_file_for_run_now_test"));
+
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.last_status.as_deref(), Some("error"));
+        assert_eq!(stored.run_count, 1);
+        assert!(stored.running_since.is_none());
+    }
+
+    #[tokio::test]
+    async fn run_job_now_errors_for_unknown_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+
+        let result = Box::pin(run_job_now(&config, "no-such-job")).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn overlap_decision_runs_when_not_running() {
+        let now = Utc::now();
+        let mut job = test_job("echo ok");
+        job.overlap_policy = OverlapPolicy::Skip;
+        job.running_since = None;
+
+        assert_eq!(overlap_decision(&job, now), OverlapDecision::Run);
+    }
+
+    #[test]
+    fn overlap_decision_honors_each_policy_while_running() {
+        let now = Utc::now();
+        let mut job = test_job("echo ok");
+        job.running_since = Some(now - ChronoDuration::seconds(30));
+
+        job.overlap_policy = OverlapPolicy::Skip;
+        assert_eq!(overlap_decision(&job, now), OverlapDecision::Skip);
+
+        job.overlap_policy = OverlapPolicy::Queue;
+        assert_eq!(overlap_decision(&job, now), OverlapDecision::Queue);
+
+        job.overlap_policy = OverlapPolicy::Allow;
+        assert_eq!(overlap_decision(&job, now), OverlapDecision::Run);
+    }
+
+    #[test]
+    fn overlap_decision_ignores_stale_running_marker() {
+        let now = Utc::now();
+        let mut job = test_job("echo ok");
+        job.overlap_policy = OverlapPolicy::Skip;
+        job.running_since =
+            Some(now - ChronoDuration::seconds(crate::cron::RUNNING_STALE_AFTER_SECS + 60));
+
+        assert_eq!(
+            overlap_decision(&job, now),
+            OverlapDecision::Run,
+            "crash leftovers must not block the job forever"
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_policy_drops_overlapping_invocation() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        // Simulate a slow run still in progress.
+        let job = cron::add_job(&config, "* * * * *", "echo slow").unwrap();
+        cron::mark_job_running(&config, &job.id, Utc::now()).unwrap();
+        let running = cron::get_job(&config, &job.id).unwrap();
+
+        let (_, success, output) = Box::pin(execute_and_persist_job(
+            &config, &security, &running, "test",
+        ))
+        .await;
+        assert!(success);
+        assert!(output.contains("skipped"));
+
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.skip_count, 1);
+        assert_eq!(stored.run_count, 0, "skipped invocation must not execute");
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queue_policy_defers_invocation_while_running() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let job = cron::add_job(&config, "* * * * *", "echo queued").unwrap();
+        let _ = cron::update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap_policy: Some(OverlapPolicy::Queue),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        cron::mark_job_running(&config, &job.id, Utc::now()).unwrap();
+        let running = cron::get_job(&config, &job.id).unwrap();
+
+        let (_, success, output) = Box::pin(execute_and_persist_job(
+            &config, &security, &running, "test",
+        ))
+        .await;
+        assert!(success);
+        assert!(output.contains("queued"));
+        assert_eq!(cron::get_job(&config, &job.id).unwrap().run_count, 0);
+        assert!(cron::take_pending_run(&config, &job.id).unwrap());
+    }
+
+    #[tokio::test]
+    async fn queued_invocation_runs_after_current_one_finishes() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let job = cron::add_job(&config, "* * * * *", "echo drain").unwrap();
+        // Pending flag left behind by misses during an earlier long run —
+        // several misses coalesce into this single flag.
+        cron::queue_pending_run(&config, &job.id).unwrap();
+        let stored = cron::get_job(&config, &job.id).unwrap();
+
+        let (_, success, _) =
+            Box::pin(execute_and_persist_job(&config, &security, &stored, "test")).await;
+        assert!(success);
+
+        let after = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(
+            after.run_count, 2,
+            "scheduled run plus one coalesced queued run"
+        );
+        assert!(!cron::take_pending_run(&config, &job.id).unwrap());
+    }
+
+    #[tokio::test]
+    async fn allow_policy_runs_despite_running_marker() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let job = cron::add_job(&config, "* * * * *", "echo concurrent").unwrap();
+        let _ = cron::update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap_policy: Some(OverlapPolicy::Allow),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        cron::mark_job_running(&config, &job.id, Utc::now()).unwrap();
+        let running = cron::get_job(&config, &job.id).unwrap();
+
+        let (_, success, output) = Box::pin(execute_and_persist_job(
+            &config, &security, &running, "test",
+        ))
+        .await;
+        assert!(success, "{output}");
+        assert_eq!(cron::get_job(&config, &job.id).unwrap().run_count, 1);
+    }
+
     #[tokio::test]
     async fn process_due_jobs_marks_component_ok_even_when_idle() {
         let tmp = TempDir::new().unwrap();
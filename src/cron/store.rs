@@ -1,7 +1,8 @@
 use crate::config::Config;
 use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_delivery_config, validate_schedule,
-    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, OverlapPolicy, Schedule,
+    SessionTarget,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -124,7 +125,7 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source
+                    allowed_tools, source, run_count, running_since, overlap_policy, skip_count
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -143,7 +144,7 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source
+                    allowed_tools, source, run_count, running_since, overlap_policy, skip_count
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -177,7 +178,7 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source
+                    allowed_tools, source, run_count, running_since, overlap_policy, skip_count
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -207,7 +208,7 @@ pub fn all_overdue_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJ
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
                     enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
-                    allowed_tools, source
+                    allowed_tools, source, run_count, running_since, overlap_policy, skip_count
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC",
@@ -260,6 +261,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
+    if let Some(overlap_policy) = patch.overlap_policy {
+        job.overlap_policy = overlap_policy;
+    }
     if let Some(allowed_tools) = patch.allowed_tools {
         // Empty list means "clear the allowlist" (all tools available),
         // not "allow zero tools".
@@ -279,8 +283,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 allowed_tools = ?12, next_run = ?13
-             WHERE id = ?14",
+                 allowed_tools = ?12, next_run = ?13, overlap_policy = ?14
+             WHERE id = ?15",
             params![
                 job.expression,
                 job.command,
@@ -295,6 +299,7 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 if job.delete_after_run { 1 } else { 0 },
                 encode_allowed_tools(job.allowed_tools.as_ref())?,
                 job.next_run.to_rfc3339(),
+                job.overlap_policy.as_str(),
                 job.id,
             ],
         )
@@ -317,7 +322,8 @@ pub fn record_last_run(
     with_connection(config, |conn| {
         conn.execute(
             "UPDATE cron_jobs
-             SET last_run = ?1, last_status = ?2, last_output = ?3
+             SET last_run = ?1, last_status = ?2, last_output = ?3,
+                 run_count = run_count + 1, running_since = NULL
              WHERE id = ?4",
             params![finished_at.to_rfc3339(), status, bounded_output, job_id],
         )
@@ -326,6 +332,76 @@ pub fn record_last_run(
     })
 }
 
+/// Mark a job as mid-run. The marker is cleared by the post-run updates;
+/// a crash leaves it behind, which readers treat as stale after
+/// [`crate::cron::RUNNING_STALE_AFTER_SECS`].
+pub fn mark_job_running(config: &Config, job_id: &str, started_at: DateTime<Utc>) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET running_since = ?1 WHERE id = ?2",
+            params![started_at.to_rfc3339(), job_id],
+        )
+        .context("Failed to mark cron job running")?;
+        Ok(())
+    })
+}
+
+/// Record a skipped invocation under the `skip` overlap policy: bump the
+/// skip counter and, for recurring schedules, advance `next_run` so the
+/// scheduler does not immediately re-trigger the same overlap.
+pub fn skip_overlapping_run(config: &Config, job: &CronJob, now: DateTime<Utc>) -> Result<()> {
+    if matches!(job.schedule, Schedule::At { .. }) {
+        // One-shot jobs have no next occurrence to advance to; the pending
+        // row stays due and runs once the current invocation finishes.
+        return with_connection(config, |conn| {
+            conn.execute(
+                "UPDATE cron_jobs SET skip_count = skip_count + 1 WHERE id = ?1",
+                params![job.id],
+            )
+            .context("Failed to record skipped cron run")?;
+            Ok(())
+        });
+    }
+
+    let next_run = next_run_for_schedule(&job.schedule, now)?;
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET skip_count = skip_count + 1, next_run = ?1 WHERE id = ?2",
+            params![next_run.to_rfc3339(), job.id],
+        )
+        .context("Failed to record skipped cron run")?;
+        Ok(())
+    })
+}
+
+/// Flag a missed invocation under the `queue` overlap policy. The flag is
+/// a boolean, so several misses during one long run coalesce into a single
+/// queued invocation.
+pub fn queue_pending_run(config: &Config, job_id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET pending_run = 1 WHERE id = ?1",
+            params![job_id],
+        )
+        .context("Failed to queue pending cron run")?;
+        Ok(())
+    })
+}
+
+/// Atomically consume the pending-run flag. Returns `true` if a queued
+/// invocation was waiting.
+pub fn take_pending_run(config: &Config, job_id: &str) -> Result<bool> {
+    with_connection(config, |conn| {
+        let changed = conn
+            .execute(
+                "UPDATE cron_jobs SET pending_run = 0 WHERE id = ?1 AND pending_run = 1",
+                params![job_id],
+            )
+            .context("Failed to consume pending cron run")?;
+        Ok(changed > 0)
+    })
+}
+
 pub fn reschedule_after_run(
     config: &Config,
     job: &CronJob,
@@ -342,7 +418,8 @@ pub fn reschedule_after_run(
         with_connection(config, |conn| {
             conn.execute(
                 "UPDATE cron_jobs
-                 SET enabled = 0, last_run = ?1, last_status = ?2, last_output = ?3
+                 SET enabled = 0, last_run = ?1, last_status = ?2, last_output = ?3,
+                     run_count = run_count + 1, running_since = NULL
                  WHERE id = ?4",
                 params![now.to_rfc3339(), status, bounded_output, job.id],
             )
@@ -354,7 +431,8 @@ pub fn reschedule_after_run(
         with_connection(config, |conn| {
             conn.execute(
                 "UPDATE cron_jobs
-                 SET next_run = ?1, last_run = ?2, last_status = ?3, last_output = ?4
+                 SET next_run = ?1, last_run = ?2, last_status = ?3, last_output = ?4,
+                     run_count = run_count + 1, running_since = NULL
                  WHERE id = ?5",
                 params![
                     next_run.to_rfc3339(),
@@ -496,6 +574,8 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let created_at_raw: String = row.get(12)?;
     let allowed_tools_raw: Option<String> = row.get(17)?;
     let source: Option<String> = row.get(18)?;
+    let running_since_raw: Option<String> = row.get(20)?;
+    let overlap_policy_raw: Option<String> = row.get(21)?;
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -521,6 +601,16 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         last_output: row.get(16)?,
         allowed_tools: decode_allowed_tools(allowed_tools_raw.as_deref())
             .map_err(sql_conversion_error)?,
+        run_count: row.get::<_, Option<i64>>(19)?.unwrap_or(0),
+        running_since: match running_since_raw {
+            Some(raw) => Some(parse_rfc3339(&raw).map_err(sql_conversion_error)?),
+            None => None,
+        },
+        overlap_policy: overlap_policy_raw
+            .as_deref()
+            .map(OverlapPolicy::parse)
+            .unwrap_or_default(),
+        skip_count: row.get::<_, Option<i64>>(22)?.unwrap_or(0),
     })
 }
 
@@ -904,7 +994,12 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run         TEXT NOT NULL,
             last_run         TEXT,
             last_status      TEXT,
-            last_output      TEXT
+            last_output      TEXT,
+            run_count        INTEGER NOT NULL DEFAULT 0,
+            running_since    TEXT,
+            overlap_policy   TEXT NOT NULL DEFAULT 'skip',
+            skip_count       INTEGER NOT NULL DEFAULT 0,
+            pending_run      INTEGER NOT NULL DEFAULT 0
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
 
@@ -935,6 +1030,11 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
     add_column_if_missing(&conn, "allowed_tools", "TEXT")?;
     add_column_if_missing(&conn, "source", "TEXT DEFAULT 'imperative'")?;
+    add_column_if_missing(&conn, "run_count", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "running_since", "TEXT")?;
+    add_column_if_missing(&conn, "overlap_policy", "TEXT NOT NULL DEFAULT 'skip'")?;
+    add_column_if_missing(&conn, "skip_count", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "pending_run", "INTEGER NOT NULL DEFAULT 0")?;
 
     f(&conn)
 }
@@ -1254,6 +1354,124 @@ mod tests {
         assert_eq!(stored.last_output.as_deref(), Some("failed output"));
     }
 
+    #[test]
+    fn run_bookkeeping_increments_count_and_clears_running_marker() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_job(&config, "*/15 * * * *", "echo count").unwrap();
+        assert_eq!(job.run_count, 0);
+
+        mark_job_running(&config, &job.id, Utc::now()).unwrap();
+        assert!(get_job(&config, &job.id).unwrap().running_since.is_some());
+
+        reschedule_after_run(&config, &job, true, "first").unwrap();
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.run_count, 1);
+        assert!(stored.running_since.is_none());
+
+        reschedule_after_run(&config, &job, false, "second").unwrap();
+        assert_eq!(get_job(&config, &job.id).unwrap().run_count, 2);
+    }
+
+    #[test]
+    fn record_last_run_increments_count_and_clears_running_marker() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_job(&config, "*/15 * * * *", "echo last-run").unwrap();
+        mark_job_running(&config, &job.id, Utc::now()).unwrap();
+
+        record_last_run(&config, &job.id, Utc::now(), true, "done").unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.run_count, 1);
+        assert!(stored.running_since.is_none());
+    }
+
+    #[test]
+    fn stale_running_marker_is_ignored() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let now = Utc::now();
+
+        let job = add_job(&config, "*/15 * * * *", "echo stale").unwrap();
+
+        mark_job_running(&config, &job.id, now).unwrap();
+        assert!(get_job(&config, &job.id).unwrap().is_running(now));
+
+        let crashed_at = now - ChronoDuration::seconds(crate::cron::RUNNING_STALE_AFTER_SECS + 60);
+        mark_job_running(&config, &job.id, crashed_at).unwrap();
+        let stored = get_job(&config, &job.id).unwrap();
+        assert!(stored.running_since.is_some());
+        assert!(
+            !stored.is_running(now),
+            "stale marker must not read as running"
+        );
+    }
+
+    #[test]
+    fn update_job_persists_overlap_policy_patch() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_job(&config, "*/5 * * * *", "echo overlap").unwrap();
+        assert_eq!(job.overlap_policy, OverlapPolicy::Skip);
+
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                overlap_policy: Some(OverlapPolicy::Queue),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(updated.overlap_policy, OverlapPolicy::Queue);
+        assert_eq!(
+            get_job(&config, &job.id).unwrap().overlap_policy,
+            OverlapPolicy::Queue
+        );
+    }
+
+    #[test]
+    fn skip_overlapping_run_bumps_counter_and_advances_next_run() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_job(&config, "*/5 * * * *", "echo skip").unwrap();
+        let now = Utc::now();
+
+        skip_overlapping_run(&config, &job, now).unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.skip_count, 1);
+        assert!(
+            stored.next_run > now,
+            "next_run should move past the overlap"
+        );
+    }
+
+    #[test]
+    fn queued_pending_runs_coalesce_into_one() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_job(&config, "*/5 * * * *", "echo queue").unwrap();
+
+        // Multiple misses during one long run set the same flag.
+        queue_pending_run(&config, &job.id).unwrap();
+        queue_pending_run(&config, &job.id).unwrap();
+        queue_pending_run(&config, &job.id).unwrap();
+
+        assert!(take_pending_run(&config, &job.id).unwrap());
+        assert!(
+            !take_pending_run(&config, &job.id).unwrap(),
+            "flag is consumed once, not per miss"
+        );
+    }
+
     #[test]
     fn job_type_from_sql_reads_valid_value() {
         let tmp = TempDir::new().unwrap();
@@ -84,6 +84,51 @@ impl SessionTarget {
     }
 }
 
+/// How the scheduler handles an invocation that comes due while a previous
+/// run of the same job is still in progress.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlapPolicy {
+    /// Drop the overlapping invocation and count it as skipped.
+    #[default]
+    Skip,
+    /// Run one coalesced invocation right after the current run finishes.
+    Queue,
+    /// Run overlapping invocations concurrently (legacy behavior).
+    Allow,
+}
+
+impl OverlapPolicy {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Queue => "queue",
+            Self::Allow => "allow",
+        }
+    }
+
+    /// Lenient parse for stored values; unknown names fall back to the default.
+    pub(crate) fn parse(raw: &str) -> Self {
+        Self::try_from(raw).unwrap_or_default()
+    }
+}
+
+impl TryFrom<&str> for OverlapPolicy {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "queue" => Ok(Self::Queue),
+            "allow" => Ok(Self::Allow),
+            _ => Err(format!(
+                "Invalid overlap policy '{}'. Expected one of: 'skip', 'queue', 'allow'",
+                value
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum Schedule {
@@ -158,6 +203,33 @@ pub struct CronJob {
     pub last_run: Option<DateTime<Utc>>,
     pub last_status: Option<String>,
     pub last_output: Option<String>,
+    /// Total number of completed runs recorded for this job.
+    #[serde(default)]
+    pub run_count: i64,
+    /// Set when a run starts and cleared when its result is persisted.
+    /// A marker older than [`RUNNING_STALE_AFTER_SECS`] is a leftover from
+    /// a crash mid-run and is ignored by readers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running_since: Option<DateTime<Utc>>,
+    /// What to do when the job comes due while already running.
+    #[serde(default)]
+    pub overlap_policy: OverlapPolicy,
+    /// Number of invocations dropped by the `skip` overlap policy.
+    #[serde(default)]
+    pub skip_count: i64,
+}
+
+/// How long a `running_since` marker stays trustworthy. Runs are bounded by
+/// the shell timeout plus retry backoff, so a marker older than this was left
+/// behind by a crash, not a live run.
+pub const RUNNING_STALE_AFTER_SECS: i64 = 15 * 60;
+
+impl CronJob {
+    /// Whether the job is currently mid-run, ignoring stale crash leftovers.
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        self.running_since
+            .is_some_and(|started| (now - started).num_seconds() < RUNNING_STALE_AFTER_SECS)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -183,6 +255,7 @@ pub struct CronJobPatch {
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
     pub allowed_tools: Option<Vec<String>>,
+    pub overlap_policy: Option<OverlapPolicy>,
 }
 
 #[cfg(test)]
@@ -181,6 +181,12 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         spawn_model_refresh_worker(config.clone()),
     ];
 
+    if config.token_refresh.enabled {
+        handles.push(spawn_token_refresh_worker(config.clone()));
+    } else {
+        tracing::info!("Token refresh disabled; OAuth tokens refresh on demand only");
+    }
+
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
@@ -243,6 +249,39 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.rag_watch.enabled {
+        let watch_cfg = config.rag_watch.clone();
+        let watch_workspace = config.workspace_dir.clone();
+        let watch_chunking = crate::rag::ChunkingSettings {
+            strategy: config.rag.chunking.strategy.clone(),
+            max_tokens: config.rag.chunking.max_tokens,
+            overlap_tokens: config.rag.chunking.overlap_tokens,
+            embed_model: config.embeddings.model.clone(),
+        };
+        handles.push(spawn_component_supervisor(
+            "rag_watch",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = watch_cfg.clone();
+                let workspace_dir = watch_workspace.clone();
+                let chunking = watch_chunking.clone();
+                async move {
+                    Box::pin(crate::rag::watcher::run(
+                        workspace_dir,
+                        cfg.dirs,
+                        cfg.ignore_globs,
+                        cfg.max_file_kb,
+                        cfg.debounce_secs,
+                        cfg.poll_secs,
+                        chunking,
+                    ))
+                    .await
+                }
+            },
+        ));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
@@ -264,6 +303,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         pm.shutdown().await;
         tracing::info!("OpenCode server stopped");
     }
+    crate::peripherals::shutdown_watches();
 
     for handle in &handles {
         handle.abort();
@@ -319,6 +359,70 @@ fn spawn_model_refresh_worker(config: Config) -> JoinHandle<()> {
     })
 }
 
+fn spawn_token_refresh_worker(config: Config) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let auth = crate::auth::AuthService::from_config(&config);
+        let interval = Duration::from_secs(config.token_refresh.interval_secs.max(30));
+        let window = Duration::from_secs(config.token_refresh.window_secs.max(120));
+
+        // Let other components start first.
+        tokio::time::sleep(Duration::from_secs(15)).await;
+        loop {
+            run_token_refresh_pass(&auth, window).await;
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+async fn run_token_refresh_pass(auth: &crate::auth::AuthService, window: Duration) {
+    let outcomes = match auth.refresh_expiring_oauth_profiles(window).await {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            tracing::warn!("Token refresh scan failed: {e}");
+            crate::health::mark_component_error("token_refresh", format!("scan failed: {e}"));
+            return;
+        }
+    };
+
+    for outcome in outcomes {
+        let component = format!("token_refresh:{}", outcome.profile_id);
+        match outcome.result {
+            Ok(true) => {
+                tracing::info!("Refreshed OAuth tokens for {}", outcome.profile_id);
+                crate::health::mark_component_ok(&component);
+                crate::observability::runtime_trace::record_event(
+                    "oauth_token_refresh",
+                    None,
+                    Some(&outcome.provider),
+                    None,
+                    None,
+                    Some(true),
+                    Some("refreshed before expiry"),
+                    serde_json::json!({ "profile_id": outcome.profile_id }),
+                );
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!("Token refresh failed for {}: {e}", outcome.profile_id);
+                crate::health::mark_component_error(
+                    &component,
+                    format!("token refresh failing: {e}"),
+                );
+                crate::observability::runtime_trace::record_event(
+                    "oauth_token_refresh",
+                    None,
+                    Some(&outcome.provider),
+                    None,
+                    None,
+                    Some(false),
+                    Some(&e.to_string()),
+                    serde_json::json!({ "profile_id": outcome.profile_id }),
+                );
+            }
+        }
+    }
+}
+
 async fn refresh_active_providers(config: &Config) {
     let mut providers: Vec<String> = Vec::new();
 
@@ -1084,6 +1188,9 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
@@ -1205,6 +1312,9 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
@@ -1223,6 +1333,9 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
@@ -82,6 +82,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
     check_config_semantics(config, &mut items);
     check_workspace(config, &mut items);
     check_daemon_state(config, &mut items);
+    check_rag_watch(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
 
@@ -89,8 +90,9 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
 }
 
 /// Run diagnostics and print human-readable report to stdout.
-pub fn run(config: &Config) -> Result<()> {
-    let results = diagnose(config);
+pub async fn run(config: &Config) -> Result<()> {
+    let mut results = diagnose(config);
+    check_peripheral_protocols(config, &mut results).await;
 
     // Print report
     println!("🩺 ZeroClaw Doctor (enhanced)");
@@ -133,6 +135,30 @@ pub fn run(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Probe configured serial/TCP boards and report the negotiated wire
+/// protocol (v2 with CRC32 framing, or legacy v1). Only run from the CLI
+/// entry point: opening a serial port can reset some boards, so the passive
+/// `diagnose()` path (gateway dashboard) must not touch hardware.
+async fn check_peripheral_protocols(config: &Config, results: &mut Vec<DiagResult>) {
+    let cat = "peripherals";
+    if !config.peripherals.enabled || config.peripherals.boards.is_empty() {
+        return;
+    }
+
+    let mut items: Vec<DiagItem> = Vec::new();
+    for (board, mode) in crate::peripherals::doctor_protocol_report(&config.peripherals).await {
+        if mode.starts_with("unreachable")
+            || mode.starts_with("invalid")
+            || mode.starts_with("unsupported")
+        {
+            items.push(DiagItem::warn(cat, format!("{board}: {mode}")));
+        } else {
+            items.push(DiagItem::ok(cat, format!("{board}: protocol {mode}")));
+        }
+    }
+    results.extend(items.into_iter().map(DiagItem::into_result));
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ModelProbeOutcome {
     Ok,
@@ -897,11 +923,110 @@ fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
                 format!("{channel_count} channels, {stale} stale"),
             ));
         }
+
+        // Peripherals: the serial transport tracks USB re-enumeration as
+        // component restarts, so a flapping cable shows up here.
+        for (name, component) in components {
+            if !name.starts_with("peripheral:") {
+                continue;
+            }
+            let status_ok = component
+                .get("status")
+                .and_then(serde_json::Value::as_str)
+                .is_some_and(|s| s == "ok");
+            let reconnects = component
+                .get("restart_count")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(0);
+            if status_ok {
+                items.push(DiagItem::ok(
+                    cat,
+                    format!("{name} connected ({reconnects} reconnects)"),
+                ));
+            } else {
+                let error = component
+                    .get("last_error")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("unknown");
+                items.push(DiagItem::error(
+                    cat,
+                    format!("{name} unavailable ({reconnects} reconnects): {error}"),
+                ));
+            }
+        }
     }
 }
 
 // ── Environment checks ───────────────────────────────────────────
 
+fn check_rag_watch(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "rag watch";
+    if !config.rag_watch.enabled {
+        return;
+    }
+
+    let Some(status) = crate::rag::watcher::read_status(&config.workspace_dir) else {
+        items.push(DiagItem::warn(
+            cat,
+            "no sync state yet — watcher has not completed a scan (is the daemon running?)",
+        ));
+        return;
+    };
+
+    match status.last_sync.as_deref().and_then(parse_rfc3339) {
+        Some(ts) => {
+            let age = Utc::now().signed_duration_since(ts).num_seconds();
+            items.push(DiagItem::ok(
+                cat,
+                format!(
+                    "last sync {age}s ago ({} files, {} chunks)",
+                    status.files, status.chunks
+                ),
+            ));
+        }
+        None => items.push(DiagItem::warn(cat, "sync state has no valid timestamp")),
+    }
+
+    if status.pending > 0 {
+        items.push(DiagItem::warn(
+            cat,
+            format!(
+                "{} file(s) pending ingestion (inside debounce window)",
+                status.pending
+            ),
+        ));
+    }
+    if let Some(first) = status.last_errors.first() {
+        items.push(DiagItem::warn(
+            cat,
+            format!(
+                "last scan reported {} warning(s): {first}",
+                status.last_errors.len()
+            ),
+        ));
+    }
+
+    // Stale index detection: the chunking fingerprint stored alongside the
+    // index must match the current [rag.chunking] config.
+    let current = crate::rag::ChunkingSettings {
+        strategy: config.rag.chunking.strategy.clone(),
+        max_tokens: config.rag.chunking.max_tokens,
+        overlap_tokens: config.rag.chunking.overlap_tokens,
+        embed_model: config.embeddings.model.clone(),
+    }
+    .fingerprint();
+    if let Some(stored) = status.chunking_fingerprint {
+        if stored != current {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "index was chunked with different settings ({stored} vs {current}) — run `zeroclaw rag reindex`"
+                ),
+            ));
+        }
+    }
+}
+
 fn check_environment(items: &mut Vec<DiagItem>) {
     let cat = "environment";
 
@@ -1166,6 +1291,7 @@ mod tests {
             provider: "groq".into(),
             model: String::new(),
             api_key: None,
+            openrouter_routing: None,
         }];
         let mut items = Vec::new();
         check_config_semantics(&config, &mut items);
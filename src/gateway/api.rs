@@ -82,6 +82,13 @@ pub struct CronAddBody {
     pub delete_after_run: Option<bool>,
 }
 
+#[derive(Deserialize)]
+pub struct BroadcastBody {
+    pub message: String,
+    /// Optional channel names to restrict the broadcast to.
+    pub only: Option<Vec<String>>,
+}
+
 #[derive(Deserialize)]
 pub struct CronPatchBody {
     pub name: Option<String>,
@@ -705,6 +712,42 @@ pub async fn handle_api_memory_store(
     }
 }
 
+/// POST /api/channels/broadcast — announce to all configured channels
+pub async fn handle_api_channels_broadcast(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<BroadcastBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    if body.message.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "message must not be empty"})),
+        )
+            .into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let only: Option<Vec<String>> = body.only.map(|names| {
+        names
+            .into_iter()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect()
+    });
+    let report = crate::channels::broadcast(&config, &body.message, only.as_deref()).await;
+
+    Json(serde_json::json!({
+        "sent": report.sent(),
+        "failed": report.failed(),
+        "outcomes": report.outcomes,
+    }))
+    .into_response()
+}
+
 /// DELETE /api/memory/:key — delete a memory entry
 pub async fn handle_api_memory_delete(
     State(state): State<AppState>,
@@ -1641,6 +1684,190 @@ pub async fn handle_api_history_delete(
     .into_response()
 }
 
+/// GET /api/estop — current emergency-stop state (dashboard and edge UIs
+/// like the ESP32 panel poll this to render the engaged/clear indicator).
+pub async fn handle_api_estop_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.security.estop.enabled {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Emergency stop is disabled. Enable [security.estop].enabled = true in config.toml"})),
+        )
+            .into_response();
+    }
+    let Some(config_dir) = config.config_path.parent().map(std::path::PathBuf::from) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Config path has no parent directory"})),
+        )
+            .into_response();
+    };
+
+    match crate::security::EstopManager::load(&config.security.estop, &config_dir) {
+        Ok(manager) => Json(serde_json::json!({"state": manager.status()})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load estop state: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EstopBody {
+    /// "engage" or "resume"
+    pub action: String,
+    /// Engage level: "kill-all" (default), "network-kill", "domain-block", "tool-freeze"
+    #[serde(default)]
+    pub level: Option<String>,
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Resume selector: clear the network kill instead of kill-all
+    #[serde(default)]
+    pub network: bool,
+    /// OTP code, required to resume when security.estop.require_otp_to_resume is set
+    #[serde(default)]
+    pub otp: Option<String>,
+}
+
+/// POST /api/estop — engage or resume the emergency stop remotely.
+///
+/// Same semantics as `zeroclaw estop` / `zeroclaw estop resume` on the CLI;
+/// used by physical E-stop buttons (ESP32 UI panel). Resume honors
+/// `require_otp_to_resume`, so a lost panel token alone cannot un-stop
+/// the agent when OTP is configured.
+pub async fn handle_api_estop(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<EstopBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.security.estop.enabled {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Emergency stop is disabled. Enable [security.estop].enabled = true in config.toml"})),
+        )
+            .into_response();
+    }
+    let Some(config_dir) = config.config_path.parent().map(std::path::PathBuf::from) else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Config path has no parent directory"})),
+        )
+            .into_response();
+    };
+
+    let mut manager = match crate::security::EstopManager::load(&config.security.estop, &config_dir)
+    {
+        Ok(m) => m,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to load estop state: {e}")})),
+            )
+                .into_response();
+        }
+    };
+
+    let result = match body.action.as_str() {
+        "engage" => {
+            let level = match body.level.as_deref().unwrap_or("kill-all") {
+                "kill-all" => crate::security::EstopLevel::KillAll,
+                "network-kill" => crate::security::EstopLevel::NetworkKill,
+                "domain-block" if !body.domains.is_empty() => {
+                    crate::security::EstopLevel::DomainBlock(body.domains.clone())
+                }
+                "tool-freeze" if !body.tools.is_empty() => {
+                    crate::security::EstopLevel::ToolFreeze(body.tools.clone())
+                }
+                other => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({"error": format!(
+                            "Invalid engage level {other:?} (domain-block/tool-freeze need domains/tools)"
+                        )})),
+                    )
+                        .into_response();
+                }
+            };
+            manager.engage(level)
+        }
+        "resume" => {
+            let selector = if body.network {
+                crate::security::ResumeSelector::Network
+            } else if !body.domains.is_empty() {
+                crate::security::ResumeSelector::Domains(body.domains.clone())
+            } else if !body.tools.is_empty() {
+                crate::security::ResumeSelector::Tools(body.tools.clone())
+            } else {
+                crate::security::ResumeSelector::KillAll
+            };
+
+            let otp_validator = if config.security.estop.require_otp_to_resume {
+                if !config.security.otp.enabled {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({"error": "security.estop.require_otp_to_resume=true but security.otp.enabled=false"})),
+                    )
+                        .into_response();
+                }
+                let store = crate::security::SecretStore::new(&config_dir, config.secrets.encrypt);
+                match crate::security::OtpValidator::from_config(
+                    &config.security.otp,
+                    &config_dir,
+                    &store,
+                ) {
+                    Ok((validator, _)) => Some(validator),
+                    Err(e) => {
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(serde_json::json!({"error": format!("OTP validator init failed: {e}")})),
+                        )
+                            .into_response();
+                    }
+                }
+            } else {
+                None
+            };
+
+            manager.resume(selector, body.otp.as_deref(), otp_validator.as_ref())
+        }
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(
+                    serde_json::json!({"error": format!("Invalid action {other:?}; use \"engage\" or \"resume\"")}),
+                ),
+            )
+                .into_response();
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            Json(serde_json::json!({"status": "ok", "state": manager.status()})).into_response()
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1818,12 +2045,15 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
             default_subject: "ZeroClaw Message".to_string(),
+            max_attachment_mb: 5,
+            max_message_attachments_mb: 20,
         });
         cfg.model_routes = vec![crate::config::schema::ModelRouteConfig {
             hint: "reasoning".to_string(),
             provider: "openrouter".to_string(),
             model: "anthropic/claude-sonnet-4.6".to_string(),
             api_key: Some("route-model-key".to_string()),
+            openrouter_routing: None,
         }];
         cfg.embedding_routes = vec![crate::config::schema::EmbeddingRouteConfig {
             hint: "semantic".to_string(),
@@ -1954,6 +2184,8 @@ mod tests {
             idle_timeout_secs: 1740,
             allowed_senders: vec!["*".to_string()],
             default_subject: "ZeroClaw Message".to_string(),
+            max_attachment_mb: 5,
+            max_message_attachments_mb: 20,
         });
         current.model_routes = vec![
             crate::config::schema::ModelRouteConfig {
@@ -1961,12 +2193,14 @@ mod tests {
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                openrouter_routing: None,
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                openrouter_routing: None,
             },
         ];
         current.embedding_routes = vec![
@@ -2113,12 +2347,14 @@ mod tests {
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                openrouter_routing: None,
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                openrouter_routing: None,
             },
         ];
         current.embedding_routes = vec![
@@ -2148,6 +2384,7 @@ mod tests {
                 provider: "openai".to_string(),
                 model: "gpt-4.1".to_string(),
                 api_key: Some(MASKED_SECRET.to_string()),
+                openrouter_routing: None,
             });
         incoming
             .embedding_routes
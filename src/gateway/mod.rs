@@ -16,6 +16,8 @@ pub mod api_webauthn;
 pub mod auth_rate_limit;
 pub mod canvas;
 pub mod nodes;
+#[cfg(feature = "robot-kit")]
+pub mod robot_camera;
 pub mod session_queue;
 pub mod sse;
 pub mod static_files;
@@ -445,6 +447,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             extra_headers: config.extra_headers.clone(),
             api_path: config.api_path.clone(),
             provider_max_tokens: config.provider_max_tokens,
+            anthropic_prompt_caching: config.providers.anthropic.prompt_caching,
+            gemini_context_cache: config.providers.gemini.clone(),
+            openrouter_routing: config.providers.openrouter.routing.clone(),
+            custom_provider: config.providers.custom.clone(),
         },
     )?);
     let model = config
@@ -455,6 +461,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
         &config.memory,
         &config.embedding_routes,
+        &config.embeddings,
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -563,6 +570,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // Cost tracker — process-global singleton so channels share the same instance
     let cost_tracker = CostTracker::get_or_init_global(config.cost.clone(), &config.workspace_dir);
 
+    // Quota history — lets the reliability layer record per-provider daily usage
+    crate::providers::quota::get_or_init_global(
+        &config.workspace_dir,
+        config.reliability.quota_history_days,
+    );
+
     // SSE broadcast channel for real-time events
     let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
     // Extract webhook secret for authentication
@@ -987,6 +1000,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/memory", get(api::handle_api_memory_list))
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
+        .route(
+            "/api/channels/broadcast",
+            post(api::handle_api_channels_broadcast),
+        )
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
@@ -1003,6 +1020,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             get(api::handle_api_history_get).delete(api::handle_api_history_delete),
         )
         // ── Pairing + Device management API ──
+        .route(
+            "/api/estop",
+            get(api::handle_api_estop_status).post(api::handle_api_estop),
+        )
         .route("/api/pairing/initiate", post(api_pairing::initiate_pairing))
         .route("/api/pair", post(api_pairing::submit_pairing_enhanced))
         .route("/api/devices", get(api_pairing::list_devices))
@@ -1059,6 +1080,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         get(api_plugins::plugin_routes::list_plugins),
     );
 
+    // ── Robot camera MJPEG stream proxy (requires robot-kit feature) ──
+    #[cfg(feature = "robot-kit")]
+    let inner = inner.route("/robot/camera", get(robot_camera::handle_robot_camera));
+
     let inner = inner
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
@@ -2684,7 +2709,11 @@ mod tests {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -0,0 +1,102 @@
+//! Robot camera stream proxy (`robot-kit` feature).
+//!
+//! Proxies the robot kit's MJPEG camera stream under `/robot/camera` so
+//! remote dashboards only need the gateway's address and auth. The upstream
+//! stream address and token come from the robot's own `robot.toml`
+//! (`[camera.stream]`); the stream token is attached server-side and never
+//! exposed to gateway clients.
+
+use super::AppState;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+/// GET /robot/camera — proxy the robot's MJPEG stream
+pub async fn handle_robot_camera(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    // Same pairing auth as the other gateway endpoints
+    if state.pairing.require_pairing() {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized — provide Authorization: Bearer <token>",
+            )
+                .into_response();
+        }
+    }
+
+    let robot = {
+        let config = state.config.lock();
+        config.peripherals.robot.clone()
+    };
+    if !robot.enabled {
+        return (StatusCode::NOT_FOUND, "Robot peripheral not enabled").into_response();
+    }
+
+    let robot_config = match robot.config_path.as_deref() {
+        Some(path) => {
+            let expanded = shellexpand::tilde(path);
+            match zeroclaw_robot_kit::RobotConfig::load(std::path::Path::new(expanded.as_ref())) {
+                Ok(c) => c,
+                Err(e) => {
+                    return (
+                        StatusCode::BAD_GATEWAY,
+                        format!("Failed to load robot config: {e}"),
+                    )
+                        .into_response();
+                }
+            }
+        }
+        None => zeroclaw_robot_kit::RobotConfig::default(),
+    };
+
+    let stream = &robot_config.camera.stream;
+    if !stream.enabled {
+        return (StatusCode::NOT_FOUND, "Camera stream not enabled").into_response();
+    }
+
+    let upstream = format!("http://{}:{}/stream", stream.bind_addr, stream.port);
+    let mut request = reqwest::Client::new().get(&upstream);
+    if !stream.token.is_empty() {
+        request = request.header("X-Stream-Token", &stream.token);
+    }
+
+    let response = match request.send().await {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                StatusCode::BAD_GATEWAY,
+                format!("Camera stream unreachable at {upstream}: {e}"),
+            )
+                .into_response();
+        }
+    };
+    if !response.status().is_success() {
+        return (
+            StatusCode::BAD_GATEWAY,
+            format!("Camera stream returned {}", response.status()),
+        )
+            .into_response();
+    }
+
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("multipart/x-mixed-replace")
+        .to_string();
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from_stream(response.bytes_stream()))
+        .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response())
+}
@@ -60,9 +60,12 @@ pub struct UsbDeviceInfo {
     pub device_address: u8,
     pub vid: u16,
     pub pid: u16,
+    pub manufacturer_string: Option<String>,
     pub product_string: Option<String>,
+    pub serial_number: Option<String>,
     pub board_name: Option<String>,
     pub architecture: Option<String>,
+    pub class: Option<registry::DeviceClass>,
 }
 
 /// Enumerate all connected USB devices and enrich with board registry lookup.
@@ -84,11 +87,92 @@ pub fn list_usb_devices() -> Result<Vec<UsbDeviceInfo>> {
             device_address: dev.device_address(),
             vid,
             pid,
+            manufacturer_string: dev.manufacturer_string().map(String::from),
             product_string: dev.product_string().map(String::from),
+            serial_number: dev.serial_number().map(String::from),
             board_name: board.map(|b| b.name.to_string()),
             architecture: board.and_then(|b| b.architecture.map(String::from)),
+            class: board.map(|b| b.class),
         });
     }
 
     Ok(devices)
 }
+
+/// The `zeroclaw peripheral add` invocation for a matched device, when
+/// both the board id and a serial port are known.
+pub fn suggested_add_command(board: Option<&str>, port: Option<&str>) -> Option<String> {
+    Some(format!("zeroclaw peripheral add {} {}", board?, port?))
+}
+
+/// One discovery record for `--format json`. The field set is stable so
+/// scripts can rely on it: `vid`/`pid` are 4-digit lowercase hex strings,
+/// unmatched devices carry `"board": null` rather than being dropped.
+pub fn device_record(device: &UsbDeviceInfo, port: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "bus_id": device.bus_id,
+        "device_address": device.device_address,
+        "vid": format!("{:04x}", device.vid),
+        "pid": format!("{:04x}", device.pid),
+        "manufacturer": device.manufacturer_string,
+        "product": device.product_string,
+        "serial": device.serial_number,
+        "board": device.board_name,
+        "architecture": device.architecture,
+        "class": device.class.map(registry::DeviceClass::as_str),
+        "port": port,
+        "suggested_add": suggested_add_command(device.board_name.as_deref(), port),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(vid: u16, pid: u16) -> UsbDeviceInfo {
+        let board = registry::lookup_board(vid, pid);
+        UsbDeviceInfo {
+            bus_id: "001".into(),
+            device_address: 4,
+            vid,
+            pid,
+            manufacturer_string: Some("STMicroelectronics".into()),
+            product_string: Some("STM32 STLink".into()),
+            serial_number: Some("066FFF383541".into()),
+            board_name: board.map(|b| b.name.to_string()),
+            architecture: board.and_then(|b| b.architecture.map(String::from)),
+            class: board.map(|b| b.class),
+        }
+    }
+
+    #[test]
+    fn matched_device_record_has_board_and_suggested_add() {
+        let record = device_record(&fixture(0x0483, 0x374b), Some("/dev/ttyACM0"));
+        assert_eq!(record["vid"], "0483");
+        assert_eq!(record["pid"], "374b");
+        assert_eq!(record["board"], "nucleo-f401re");
+        assert_eq!(record["class"], "board");
+        assert_eq!(
+            record["suggested_add"],
+            "zeroclaw peripheral add nucleo-f401re /dev/ttyACM0"
+        );
+    }
+
+    #[test]
+    fn unknown_device_record_keeps_null_board() {
+        let record = device_record(&fixture(0xdead, 0xbeef), None);
+        assert_eq!(record["board"], serde_json::Value::Null);
+        assert_eq!(record["class"], serde_json::Value::Null);
+        assert_eq!(record["suggested_add"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn suggestion_needs_both_board_and_port() {
+        assert!(suggested_add_command(Some("pico"), None).is_none());
+        assert!(suggested_add_command(None, Some("/dev/ttyACM0")).is_none());
+        assert_eq!(
+            suggested_add_command(Some("pico"), Some("/dev/ttyACM1")).as_deref(),
+            Some("zeroclaw peripheral add pico /dev/ttyACM1")
+        );
+    }
+}
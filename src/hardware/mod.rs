@@ -472,8 +472,8 @@ pub fn config_from_wizard_choice(choice: usize, devices: &[DiscoveredDevice]) ->
 }
 
 /// Handle `zeroclaw hardware` subcommands.
-#[allow(clippy::module_name_repetitions)]
-pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<()> {
+#[allow(clippy::module_name_repetitions, clippy::unused_async)]
+pub async fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<()> {
     #[cfg(not(feature = "hardware"))]
     {
         let _ = &cmd;
@@ -498,8 +498,8 @@ pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<
         any(target_os = "linux", target_os = "macos", target_os = "windows")
     ))]
     match cmd {
-        crate::HardwareCommands::Discover => run_discover(),
-        crate::HardwareCommands::Introspect { path } => run_introspect(&path),
+        crate::HardwareCommands::Discover { format } => run_discover(&format),
+        crate::HardwareCommands::Introspect { path } => run_introspect(&path).await,
         crate::HardwareCommands::Info { chip } => run_info(&chip),
     }
 }
@@ -508,9 +508,31 @@ pub fn handle_command(cmd: crate::HardwareCommands, _config: &Config) -> Result<
     feature = "hardware",
     any(target_os = "linux", target_os = "macos", target_os = "windows")
 ))]
-fn run_discover() -> Result<()> {
+fn run_discover(format: &str) -> Result<()> {
     let devices = discover::list_usb_devices()?;
 
+    // Correlate USB devices with serial ports by VID/PID so the suggested
+    // `peripheral add` command has a path to offer.
+    let ports = discover::scan_serial_devices();
+    let port_for = |vid: u16, pid: u16| {
+        ports
+            .iter()
+            .find(|p| p.vid == vid && p.pid == pid)
+            .map(|p| p.port_path.as_str())
+    };
+
+    if format == "json" {
+        let records: Vec<serde_json::Value> = devices
+            .iter()
+            .map(|d| discover::device_record(d, port_for(d.vid, d.pid)))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+    if format != "text" {
+        anyhow::bail!("unknown format '{format}' (expected 'text' or 'json')");
+    }
+
     if devices.is_empty() {
         println!("No USB devices found.");
         println!();
@@ -528,9 +550,20 @@ fn run_discover() -> Result<()> {
             "  {:04x}:{:04x}  {}  {}  {}",
             d.vid, d.pid, board, arch, product
         );
+        if let Some(cmd) =
+            discover::suggested_add_command(d.board_name.as_deref(), port_for(d.vid, d.pid))
+        {
+            println!("              {cmd}");
+        }
     }
     println!();
-    println!("Known boards: nucleo-f401re, nucleo-f411re, arduino-uno, arduino-mega, cp2102");
+    let mut seen = std::collections::BTreeSet::new();
+    let names: Vec<&str> = registry::known_boards()
+        .iter()
+        .map(|b| b.name)
+        .filter(|n| seen.insert(*n))
+        .collect();
+    println!("Known boards: {}", names.join(", "));
 
     Ok(())
 }
@@ -539,7 +572,7 @@ fn run_discover() -> Result<()> {
     feature = "hardware",
     any(target_os = "linux", target_os = "macos", target_os = "windows")
 ))]
-fn run_introspect(path: &str) -> Result<()> {
+async fn run_introspect(path: &str) -> Result<()> {
     let result = introspect::introspect_device(path)?;
 
     println!("Device at {}:", result.path);
@@ -556,6 +589,38 @@ fn run_introspect(path: &str) -> Result<()> {
         println!("  Architecture {}", arch);
     }
     println!("  Memory map  {}", result.memory_map_note);
+    if result
+        .board_name
+        .as_deref()
+        .is_some_and(|b| b.starts_with("nucleo"))
+    {
+        println!("  Firmware    ZeroClaw firmware: gpio 0-13, pwm 3/5/6/9, adc A0-A5");
+    }
+
+    // Ask the running firmware who it is — best-effort: the port may be
+    // busy, and pre-versioning firmware simply omits the fields.
+    let board_cfg = crate::config::PeripheralBoardConfig {
+        board: result
+            .board_name
+            .clone()
+            .unwrap_or_else(|| "unknown".into()),
+        transport: "serial".into(),
+        path: Some(result.path.clone()),
+        baud: 115_200,
+        metadata: None,
+        secret: None,
+    };
+    match crate::peripherals::probe_firmware_info(&board_cfg).await {
+        Ok(info) => {
+            if let Some(fw) = &info.fw_version {
+                println!("  Fw version  {}", fw);
+            }
+            if let Some(v) = info.protocol_version {
+                println!("  Protocol    v{}", v);
+            }
+        }
+        Err(e) => println!("  Fw version  (unavailable: {})", e),
+    }
 
     Ok(())
 }
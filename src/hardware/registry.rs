@@ -1,5 +1,25 @@
 //! Board registry — maps USB VID/PID to known board names and architectures.
 
+/// How a matched entry should be treated: a specific dev board, a generic
+/// USB-UART bridge (the board behind it is unknown), or a debug probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceClass {
+    Board,
+    GenericSerial,
+    DebugProbe,
+}
+
+impl DeviceClass {
+    /// Stable lowercase label for JSON output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeviceClass::Board => "board",
+            DeviceClass::GenericSerial => "generic-serial",
+            DeviceClass::DebugProbe => "debug-probe",
+        }
+    }
+}
+
 /// Information about a known board.
 #[derive(Debug, Clone)]
 pub struct BoardInfo {
@@ -7,65 +27,144 @@ pub struct BoardInfo {
     pub pid: u16,
     pub name: &'static str,
     pub architecture: Option<&'static str>,
+    pub class: DeviceClass,
 }
 
 /// Known USB VID/PID to board mappings.
-/// VID 0x0483 = STMicroelectronics, 0x2341 = Arduino, 0x10c4 = Silicon Labs.
+/// VID 0x0483 = STMicroelectronics, 0x2341 = Arduino, 0x10c4 = Silicon Labs,
+/// 0x303a = Espressif, 0x2e8a = Raspberry Pi, 0x0403 = FTDI, 0x1a86 = WCH.
 const KNOWN_BOARDS: &[BoardInfo] = &[
     BoardInfo {
         vid: 0x0483,
         pid: 0x374b,
         name: "nucleo-f401re",
         architecture: Some("ARM Cortex-M4"),
+        class: DeviceClass::Board,
     },
     BoardInfo {
         vid: 0x0483,
         pid: 0x3748,
         name: "nucleo-f411re",
         architecture: Some("ARM Cortex-M4"),
+        class: DeviceClass::Board,
+    },
+    BoardInfo {
+        vid: 0x0483,
+        pid: 0x374e,
+        name: "st-link-v3",
+        architecture: Some("STM32 debug probe"),
+        class: DeviceClass::DebugProbe,
+    },
+    BoardInfo {
+        vid: 0x0483,
+        pid: 0x374f,
+        name: "st-link-v3",
+        architecture: Some("STM32 debug probe"),
+        class: DeviceClass::DebugProbe,
     },
     BoardInfo {
         vid: 0x2341,
         pid: 0x0043,
         name: "arduino-uno",
         architecture: Some("AVR ATmega328P"),
+        class: DeviceClass::Board,
     },
     BoardInfo {
         vid: 0x2341,
         pid: 0x0078,
         name: "arduino-uno",
         architecture: Some("Arduino Uno Q / ATmega328P"),
+        class: DeviceClass::Board,
     },
     BoardInfo {
         vid: 0x2341,
         pid: 0x0042,
         name: "arduino-mega",
         architecture: Some("AVR ATmega2560"),
+        class: DeviceClass::Board,
+    },
+    // Espressif native USB: S2 exposes plain CDC, S3/C3 share the
+    // USB-JTAG/serial unit (same PID on both chips).
+    BoardInfo {
+        vid: 0x303a,
+        pid: 0x0002,
+        name: "esp32-s2",
+        architecture: Some("ESP32-S2 (native USB CDC)"),
+        class: DeviceClass::Board,
+    },
+    BoardInfo {
+        vid: 0x303a,
+        pid: 0x1001,
+        name: "esp32-s3",
+        architecture: Some("ESP32-S3/C3 (USB-JTAG/serial)"),
+        class: DeviceClass::Board,
+    },
+    // Raspberry Pi Pico: BOOTSEL mass-storage mode vs. running CDC
+    // firmware (Pico SDK stdio or MicroPython).
+    BoardInfo {
+        vid: 0x2e8a,
+        pid: 0x0003,
+        name: "pico",
+        architecture: Some("RP2040 (BOOTSEL)"),
+        class: DeviceClass::Board,
+    },
+    BoardInfo {
+        vid: 0x2e8a,
+        pid: 0x0005,
+        name: "pico",
+        architecture: Some("RP2040 (MicroPython CDC)"),
+        class: DeviceClass::Board,
+    },
+    BoardInfo {
+        vid: 0x2e8a,
+        pid: 0x000a,
+        name: "pico",
+        architecture: Some("RP2040 (USB CDC)"),
+        class: DeviceClass::Board,
     },
     BoardInfo {
         vid: 0x10c4,
         pid: 0xea60,
         name: "cp2102",
         architecture: Some("USB-UART bridge"),
+        class: DeviceClass::GenericSerial,
     },
     BoardInfo {
         vid: 0x10c4,
         pid: 0xea70,
         name: "cp2102n",
         architecture: Some("USB-UART bridge"),
+        class: DeviceClass::GenericSerial,
     },
-    // ESP32 dev boards often use CH340 USB-UART
+    // ESP32 dev boards often use CH340 USB-UART, but the bridge alone
+    // does not identify the board behind it.
     BoardInfo {
         vid: 0x1a86,
         pid: 0x7523,
         name: "esp32",
         architecture: Some("ESP32 (CH340)"),
+        class: DeviceClass::GenericSerial,
     },
     BoardInfo {
         vid: 0x1a86,
         pid: 0x55d4,
         name: "esp32",
         architecture: Some("ESP32 (CH340)"),
+        class: DeviceClass::GenericSerial,
+    },
+    BoardInfo {
+        vid: 0x0403,
+        pid: 0x6001,
+        name: "ftdi-ft232r",
+        architecture: Some("USB-UART bridge"),
+        class: DeviceClass::GenericSerial,
+    },
+    BoardInfo {
+        vid: 0x0403,
+        pid: 0x6015,
+        name: "ftdi-ft231x",
+        architecture: Some("USB-UART bridge"),
+        class: DeviceClass::GenericSerial,
     },
 ];
 
@@ -99,4 +198,40 @@ mod tests {
     fn known_boards_not_empty() {
         assert!(!known_boards().is_empty());
     }
+
+    #[test]
+    fn lookup_matches_fixture_vid_pid_table() {
+        let fixtures: &[(u16, u16, &str, DeviceClass)] = &[
+            (0x303a, 0x1001, "esp32-s3", DeviceClass::Board),
+            (0x303a, 0x0002, "esp32-s2", DeviceClass::Board),
+            (0x2e8a, 0x0003, "pico", DeviceClass::Board),
+            (0x2e8a, 0x0005, "pico", DeviceClass::Board),
+            (0x2e8a, 0x000a, "pico", DeviceClass::Board),
+            (0x0483, 0x374e, "st-link-v3", DeviceClass::DebugProbe),
+            (0x0403, 0x6001, "ftdi-ft232r", DeviceClass::GenericSerial),
+            (0x0403, 0x6015, "ftdi-ft231x", DeviceClass::GenericSerial),
+            (0x10c4, 0xea60, "cp2102", DeviceClass::GenericSerial),
+            (0x1a86, 0x7523, "esp32", DeviceClass::GenericSerial),
+        ];
+        for &(vid, pid, name, class) in fixtures {
+            let b = lookup_board(vid, pid)
+                .unwrap_or_else(|| panic!("{vid:04x}:{pid:04x} missing from registry"));
+            assert_eq!(b.name, name, "{vid:04x}:{pid:04x}");
+            assert_eq!(b.class, class, "{vid:04x}:{pid:04x}");
+        }
+    }
+
+    #[test]
+    fn registry_has_no_duplicate_vid_pid_entries() {
+        for (i, a) in known_boards().iter().enumerate() {
+            for b in &known_boards()[i + 1..] {
+                assert!(
+                    !(a.vid == b.vid && a.pid == b.pid),
+                    "duplicate entry {:04x}:{:04x}",
+                    a.vid,
+                    a.pid
+                );
+            }
+        }
+    }
 }
@@ -840,6 +840,9 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
             ack_reactions: None,
             proxy_url: None,
         });
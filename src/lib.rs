@@ -185,7 +185,42 @@ pub enum ChannelCommands {
     /// Start all configured channels (handled in main.rs for async)
     Start,
     /// Run health checks for configured channels (handled in main.rs for async)
-    Doctor,
+    #[command(long_about = "\
+Run active connectivity probes for configured channels.
+
+Each probe measures round-trip latency and reports the authenticated \
+identity where the platform exposes it (Telegram getMe, Slack \
+auth.test, Discord users/@me). Probes run concurrently with individual \
+timeouts. Exits non-zero if any configured channel fails.
+
+Examples:
+  zeroclaw channel doctor
+  zeroclaw channel doctor --format json")]
+    Doctor {
+        /// Output format: "text" (default) or "json" for scripting
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Pair WhatsApp Web by scanning a QR code (handled in main.rs for async)
+    #[command(long_about = "\
+Pair (or re-pair) the WhatsApp Web session by scanning a QR code.
+
+Prints the QR code to the terminal and blocks until pairing completes \
+or the timeout elapses. Use this after a logout: while the session is \
+unpaired, the WhatsApp channel rejects sends and suspends inbound \
+message processing.
+
+Examples:
+  zeroclaw channel pair-whatsapp
+  zeroclaw channel pair-whatsapp --png --timeout 300")]
+    PairWhatsapp {
+        /// Also write the QR code as a PNG into the workspace
+        #[arg(long)]
+        png: bool,
+        /// Seconds to wait for pairing before giving up
+        #[arg(long, default_value = "120")]
+        timeout: u64,
+    },
     /// Add a new channel configuration
     #[command(long_about = "\
 Add a new channel configuration.
@@ -249,6 +284,25 @@ Examples:
         #[arg(long)]
         recipient: String,
     },
+    /// Broadcast an announcement to every configured channel
+    #[command(long_about = "\
+Broadcast an announcement to every configured channel at once.
+
+Sends the message through each configured channel's default announce \
+target (the configured channel/room/group, or the first allowlisted \
+identity for DM-style channels). Channels are contacted concurrently; \
+a failure on one never blocks the others.
+
+Examples:
+  zeroclaw channel broadcast 'Going down for maintenance at 22:00 UTC'
+  zeroclaw channel broadcast 'Back online' --only telegram,discord")]
+    Broadcast {
+        /// Announcement text to send
+        message: String,
+        /// Comma-separated channel names to restrict the broadcast to
+        #[arg(long)]
+        only: Option<String>,
+    },
 }
 
 /// Skills management subcommands
@@ -300,7 +354,11 @@ pub enum MigrateCommands {
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CronCommands {
     /// List all scheduled tasks
-    List,
+    List {
+        /// Output format: text (default) or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
     /// Add a new scheduled task
     #[command(long_about = "\
 Add a new recurring scheduled task.
@@ -328,6 +386,33 @@ Examples:
         /// Command (shell) or prompt (agent) to run
         command: String,
     },
+    /// Add a scheduled task from a natural-language phrase
+    #[command(
+        name = "add-nl",
+        long_about = "\
+Add a scheduled task described in plain English instead of cron syntax.
+
+The parsed schedule is printed back (\"interpreted as: …\") and must be
+confirmed before the task is created; pass --yes to skip the prompt.
+Times use UTC unless --tz or [cron] default_timezone is set.
+
+Examples:
+  zeroclaw cron add-nl 'every weekday at 9am' 'echo stand-up' --tz Europe/Berlin
+  zeroclaw cron add-nl 'every 2 hours' 'echo heartbeat' --yes
+  zeroclaw cron add-nl 'tomorrow at 18:30' 'echo reminder'"
+    )]
+    AddNl {
+        /// Natural-language schedule (e.g. \"every weekday at 9am\")
+        phrase: String,
+        /// Optional IANA timezone (e.g. America/Los_Angeles)
+        #[arg(long)]
+        tz: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        /// Shell command to run
+        command: String,
+    },
     /// Add a one-shot scheduled task at an RFC3339 timestamp
     #[command(long_about = "\
 Add a one-shot task that fires at a specific UTC timestamp.
@@ -426,6 +511,9 @@ Examples:
         /// Replace the agent job allowlist with the specified tool names (repeatable)
         #[arg(long = "allowed-tool")]
         allowed_tools: Vec<String>,
+        /// Overlap policy when the task comes due while still running: skip, queue, or allow
+        #[arg(long = "overlap-policy")]
+        overlap_policy: Option<String>,
     },
     /// Pause a scheduled task
     Pause {
@@ -437,10 +525,28 @@ Examples:
         /// Task ID
         id: String,
     },
+    /// Run a task immediately through the scheduler execution path
+    #[command(long_about = "\
+Execute a task right now, exactly as the scheduler would: the same
+retry policy, delivery, run history, and status bookkeeping apply,
+and the task's next scheduled run is recomputed afterwards.
+
+Examples:
+  zeroclaw cron run <task-id>")]
+    Run {
+        /// Task ID
+        id: String,
+    },
+    /// Show full details and execution status for one task
+    Show {
+        /// Task ID
+        id: String,
+    },
 }
 
 /// Memory management subcommands
-#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+// No `Eq`: `Search.min_score` is an `Option<f64>`.
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MemoryCommands {
     /// List memory entries with optional filters
     List {
@@ -450,18 +556,44 @@ pub enum MemoryCommands {
         /// Filter by session ID
         #[arg(long)]
         session: Option<String>,
+        /// Filter by namespace (see [memory.namespacing])
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Only show pinned entries
+        #[arg(long)]
+        pinned: bool,
         /// Maximum number of entries to display
         #[arg(long, default_value = "50")]
         limit: usize,
         /// Number of entries to skip (for pagination)
         #[arg(long, default_value = "0")]
         offset: usize,
+        /// Show namespace, importance, and score breakdown per entry
+        #[arg(long)]
+        verbose: bool,
     },
     /// Get a specific memory entry by key
     Get {
         /// Memory key to look up
         key: String,
     },
+    /// Search memory with ranked scores (the same recall path the agent uses)
+    Search {
+        /// Search query
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Only search entries in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Drop results scoring below this threshold (0.0-1.0)
+        #[arg(long)]
+        min_score: Option<f64>,
+        /// Output format: "text" (default) or "json" for scripting
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
     /// Store a new memory entry
     Store {
         /// Unique key for this memory
@@ -471,20 +603,81 @@ pub enum MemoryCommands {
         /// Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or custom name.
         #[arg(long, default_value = "core")]
         category: String,
+        /// Pin the entry so channel context always includes it
+        #[arg(long)]
+        pin: bool,
+    },
+    /// Pin an entry so channel context always includes it
+    Pin {
+        /// Memory key to pin
+        key: String,
+    },
+    /// Remove an entry's pin
+    Unpin {
+        /// Memory key to unpin
+        key: String,
     },
     /// Show memory backend statistics and health
-    Stats,
+    Stats {
+        /// Restrict statistics to a single namespace
+        #[arg(long)]
+        namespace: Option<String>,
+    },
+    /// Backfill embeddings for entries missing them (enables vector recall)
+    Reindex,
+    /// Delete entries whose TTL has elapsed
+    Prune,
+    /// Distill recent conversation entries into durable core facts
+    Distill {
+        /// Print what would be stored without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Convert legacy markdown memory files to the daily frontmatter layout
+    MigrateMarkdown,
+    /// Export memory entries as JSONL (one JSON object per line)
+    Export {
+        /// Only export entries in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Only export entries created on/after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Output file (defaults to stdout)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Import memory entries from a JSONL file
+    Import {
+        /// Path to the JSONL file to import
+        file: std::path::PathBuf,
+        /// Skip entries whose key already exists (default)
+        #[arg(long, conflicts_with = "overwrite")]
+        merge: bool,
+        /// Replace entries whose key already exists
+        #[arg(long)]
+        overwrite: bool,
+    },
     /// Clear memories by category, by key, or clear all
     Clear {
-        /// Delete a single entry by key (supports prefix match)
+        /// Match entries by key: exact, prefix, or glob (`*`, `?`)
         #[arg(long)]
         key: Option<String>,
         /// Only clear entries in this category
         #[arg(long)]
         category: Option<String>,
+        /// Only clear entries in this namespace
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Also wipe persisted channel conversation sessions
+        #[arg(long)]
+        sessions: bool,
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
+        /// Preview matched entries without deleting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -509,8 +702,13 @@ Scans connected USB devices by VID/PID and matches them against \
 known development boards (STM32 Nucleo, Arduino, ESP32).
 
 Examples:
-  zeroclaw hardware discover")]
-    Discover,
+  zeroclaw hardware discover
+  zeroclaw hardware discover --format json")]
+    Discover {
+        /// Output format: "text" (default) or "json" for scripting
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
     /// Introspect a device by path (e.g. /dev/ttyACM0)
     #[command(long_about = "\
 Introspect a device by its serial or device path.
@@ -560,12 +758,19 @@ Supported boards: nucleo-f401re, rpi-gpio, esp32, arduino-uno.
 Examples:
   zeroclaw peripheral add nucleo-f401re /dev/ttyACM0
   zeroclaw peripheral add rpi-gpio native
-  zeroclaw peripheral add esp32 /dev/ttyUSB0")]
+  zeroclaw peripheral add esp32 /dev/ttyUSB0
+  zeroclaw peripheral add esp32 tcp://192.168.1.50:3333 --secret hunter2")]
     Add {
         /// Board type (nucleo-f401re, rpi-gpio, esp32)
         board: String,
-        /// Path for serial transport (/dev/ttyACM0) or "native" for local GPIO
+        /// Path for serial transport (/dev/ttyACM0), tcp://host:port, or "native" for local GPIO
         path: String,
+        /// Add the board even if the registration-time probe fails
+        #[arg(long)]
+        force: bool,
+        /// Shared secret for tcp:// boards (sent as the first line at connect time)
+        #[arg(long)]
+        secret: Option<String>,
     },
     /// Flash ZeroClaw firmware to Arduino (creates .ino, installs arduino-cli if needed, uploads)
     #[command(long_about = "\
@@ -591,6 +796,30 @@ Examples:
     },
     /// Flash ZeroClaw firmware to Nucleo-F401RE (builds + probe-rs run)
     FlashNucleo,
+    /// Flash ZeroClaw firmware to ESP32 (builds firmware/esp32, installs espflash if needed)
+    #[command(long_about = "\
+Flash ZeroClaw firmware to an ESP32 board via espflash.
+
+Builds the firmware/esp32 crate (or flashes a prebuilt ELF with \
+--binary), installs espflash through cargo if it is missing, flashes, \
+and then watches the serial port for the firmware's ready banner. \
+The port is auto-detected from USB VID/PID when omitted.
+
+Examples:
+  zeroclaw peripheral flash-esp32
+  zeroclaw peripheral flash-esp32 --release
+  zeroclaw peripheral flash-esp32 --port /dev/ttyUSB0 --binary target/esp32.elf")]
+    FlashEsp32 {
+        /// Serial port (e.g. /dev/ttyUSB0). Auto-detected from USB VID/PID if omitted.
+        #[arg(short, long)]
+        port: Option<String>,
+        /// Build the firmware with --release
+        #[arg(long)]
+        release: bool,
+        /// Flash a prebuilt ELF instead of building firmware/esp32
+        #[arg(long)]
+        binary: Option<String>,
+    },
 }
 
 /// SOP management subcommands
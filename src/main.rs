@@ -368,9 +368,11 @@ Examples:
   zeroclaw cron add '0 9 * * 1-5' 'Good morning' --tz America/New_York --agent
   zeroclaw cron add '*/30 * * * *' 'Check system health' --agent
   zeroclaw cron add '*/5 * * * *' 'echo ok'
+  zeroclaw cron add-nl 'every weekday at 9am' 'echo stand-up'
   zeroclaw cron add-at 2025-01-15T14:00:00Z 'Send reminder' --agent
   zeroclaw cron add-every 60000 'Ping heartbeat'
   zeroclaw cron once 30m 'Run backup in 30 minutes' --agent
+  zeroclaw cron run <task-id>
   zeroclaw cron pause <task-id>
   zeroclaw cron update <task-id> --expression '0 8 * * *' --tz Europe/London")]
     Cron {
@@ -387,6 +389,16 @@ Examples:
     /// List supported AI providers
     Providers,
 
+    /// Show per-provider daily usage history (requests, tokens, 429s, breaker trips)
+    ProvidersQuota {
+        /// Days of history to show
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Output format: "json" for machine-readable output
+        #[arg(long)]
+        format: Option<String>,
+    },
+
     /// Manage channels (telegram, discord, slack)
     #[command(long_about = "\
 Manage communication channels.
@@ -460,6 +472,7 @@ Examples:
   zeroclaw peripheral list
   zeroclaw peripheral add nucleo-f401re /dev/ttyACM0
   zeroclaw peripheral add rpi-gpio native
+  zeroclaw peripheral add esp32 tcp://192.168.1.50:3333
   zeroclaw peripheral flash --port /dev/cu.usbmodem12345
   zeroclaw peripheral flash-nucleo")]
     Peripheral {
@@ -480,12 +493,27 @@ Examples:
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
+  zeroclaw memory reindex
+  zeroclaw memory prune
+  zeroclaw memory export --output backup.jsonl
+  zeroclaw memory import backup.jsonl --merge
   zeroclaw memory clear --category conversation --yes")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
     },
 
+    /// Manage the datasheet RAG index
+    #[command(long_about = "\
+Manage the datasheet RAG index.
+
+Examples:
+  zeroclaw rag reindex")]
+    Rag {
+        #[command(subcommand)]
+        rag_command: RagCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -721,6 +749,20 @@ enum AuthCommands {
         #[arg(long)]
         profile: String,
     },
+    /// Export auth profiles to a file for machine migration
+    Export {
+        /// Output file path (default: zeroclaw-auth-export.json)
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Include encrypted credentials (prompts for a passphrase)
+        #[arg(long)]
+        include_secrets: bool,
+    },
+    /// Import auth profiles from an export file
+    Import {
+        /// Export file produced by `zeroclaw auth export`
+        file: PathBuf,
+    },
     /// List auth profiles
     List,
     /// Show auth status with active profile and token expiry info
@@ -787,6 +829,12 @@ enum DoctorCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum RagCommands {
+    /// Rebuild the datasheet index with the current [rag.chunking] settings
+    Reindex,
+}
+
 #[derive(Subcommand, Debug)]
 enum MemoryCommands {
     /// List memory entries with optional filters
@@ -795,13 +843,39 @@ enum MemoryCommands {
         category: Option<String>,
         #[arg(long)]
         session: Option<String>,
+        /// Filter by namespace (see [memory.namespacing])
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Only show pinned entries
+        #[arg(long)]
+        pinned: bool,
         #[arg(long, default_value = "50")]
         limit: usize,
         #[arg(long, default_value = "0")]
         offset: usize,
+        /// Show namespace, importance, and score breakdown per entry
+        #[arg(long)]
+        verbose: bool,
     },
     /// Get a specific memory entry by key
     Get { key: String },
+    /// Search memory with ranked scores (the same recall path the agent uses)
+    Search {
+        /// Search query
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Only search entries in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Drop results scoring below this threshold (0.0-1.0)
+        #[arg(long)]
+        min_score: Option<f64>,
+        /// Output format: "text" (default) or "json" for scripting
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
     /// Store a new memory entry
     Store {
         /// Unique key for this memory
@@ -811,19 +885,74 @@ enum MemoryCommands {
         /// Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or custom.
         #[arg(long, default_value = "core")]
         category: String,
+        /// Pin the entry so channel context always includes it
+        #[arg(long)]
+        pin: bool,
     },
+    /// Pin an entry so channel context always includes it
+    Pin { key: String },
+    /// Remove an entry's pin
+    Unpin { key: String },
     /// Show memory backend statistics and health
-    Stats,
+    Stats {
+        /// Restrict statistics to a single namespace
+        #[arg(long)]
+        namespace: Option<String>,
+    },
+    /// Backfill embeddings for entries missing them (enables vector recall)
+    Reindex,
+    /// Delete entries whose TTL has elapsed
+    Prune,
+    /// Distill recent conversation entries into durable core facts
+    Distill {
+        /// Print what would be stored without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Convert legacy markdown memory files to the daily frontmatter layout
+    MigrateMarkdown,
+    /// Export memory entries as JSONL (one JSON object per line)
+    Export {
+        /// Only export entries in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Only export entries created on/after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Output file (defaults to stdout)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Import memory entries from a JSONL file
+    Import {
+        /// Path to the JSONL file to import
+        file: PathBuf,
+        /// Skip entries whose key already exists (default)
+        #[arg(long, conflicts_with = "overwrite")]
+        merge: bool,
+        /// Replace entries whose key already exists
+        #[arg(long)]
+        overwrite: bool,
+    },
     /// Clear memories by category, by key, or clear all
     Clear {
-        /// Delete a single entry by key (supports prefix match)
+        /// Match entries by key: exact, prefix, or glob (`*`, `?`)
         #[arg(long)]
         key: Option<String>,
         #[arg(long)]
         category: Option<String>,
+        /// Only clear entries in this namespace
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Also wipe persisted channel conversation sessions
+        #[arg(long)]
+        sessions: bool,
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
+        /// Preview matched entries without deleting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -1331,7 +1460,7 @@ async fn main() -> Result<()> {
             tools,
         } => handle_estop_command(&config, estop_command, level, domains, tools),
 
-        Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
+        Commands::Cron { cron_command } => cron::handle_command(cron_command, &config).await,
 
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh {
@@ -1390,6 +1519,46 @@ async fn main() -> Result<()> {
             Ok(())
         }
 
+        Commands::ProvidersQuota { days, format } => {
+            let history = providers::quota::load_history(&config.workspace_dir);
+            let window = providers::quota::last_days_of(&history, days);
+            if format.as_deref() == Some("json") {
+                let map: std::collections::BTreeMap<_, _> = window.into_iter().collect();
+                println!("{}", serde_json::to_string_pretty(&map)?);
+                return Ok(());
+            }
+            if window.is_empty() {
+                println!("No provider usage recorded yet.");
+                println!(
+                    "History is written to {} while the agent runs.",
+                    providers::quota::storage_path(&config.workspace_dir).display()
+                );
+                return Ok(());
+            }
+            println!("Provider usage (last {} day(s)):\n", window.len());
+            println!(
+                "  DATE        PROVIDER            REQUESTS  TOKENS IN  TOKENS OUT  429s  TRIPS"
+            );
+            println!(
+                "  ──────────  ──────────────────  ────────  ─────────  ──────────  ────  ─────"
+            );
+            for (day, by_provider) in &window {
+                for (provider, usage) in by_provider {
+                    println!(
+                        "  {:<10}  {:<18}  {:>8}  {:>9}  {:>10}  {:>4}  {:>5}",
+                        day,
+                        provider,
+                        usage.requests,
+                        usage.input_tokens,
+                        usage.output_tokens,
+                        usage.rate_limit_hits,
+                        usage.breaker_trips
+                    );
+                }
+            }
+            Ok(())
+        }
+
         Commands::Service {
             service_command,
             service_init,
@@ -1415,12 +1584,21 @@ async fn main() -> Result<()> {
                 contains.as_deref(),
                 limit,
             ),
-            None => doctor::run(&config),
+            None => doctor::run(&config).await,
         },
 
         Commands::Channel { channel_command } => match channel_command {
             ChannelCommands::Start => Box::pin(channels::start_channels(config)).await,
-            ChannelCommands::Doctor => Box::pin(channels::doctor_channels(config)).await,
+            ChannelCommands::Doctor { format } => {
+                Box::pin(channels::doctor_channels(
+                    config,
+                    format.eq_ignore_ascii_case("json"),
+                ))
+                .await
+            }
+            ChannelCommands::PairWhatsapp { png, timeout } => {
+                Box::pin(channels::pair_whatsapp(config, png, timeout)).await
+            }
             other => Box::pin(channels::handle_command(other, &config)).await,
         },
 
@@ -1438,10 +1616,31 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Rag { rag_command } => match rag_command {
+            RagCommands::Reindex => {
+                let Some(dir) = config
+                    .peripherals
+                    .datasheet_dir
+                    .clone()
+                    .filter(|d| !d.trim().is_empty())
+                else {
+                    println!("No [peripherals] datasheet_dir configured — nothing to reindex.");
+                    return Ok(());
+                };
+                let chunking = rag::ChunkingSettings {
+                    strategy: config.rag.chunking.strategy.clone(),
+                    max_tokens: config.rag.chunking.max_tokens,
+                    overlap_tokens: config.rag.chunking.overlap_tokens,
+                    embed_model: config.embeddings.model.clone(),
+                };
+                rag::run_reindex(&config.workspace_dir, dir.trim(), &chunking)
+            }
+        },
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
-            hardware::handle_command(hardware_command.clone(), &config)
+            hardware::handle_command(hardware_command.clone(), &config).await
         }
 
         Commands::Peripheral { peripheral_command } => {
@@ -2565,6 +2764,78 @@ async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Res
             Ok(())
         }
 
+        AuthCommands::Export {
+            output,
+            include_secrets,
+        } => {
+            let data = auth_service.load_profiles().await?;
+            if data.profiles.is_empty() {
+                println!("No auth profiles to export.");
+                return Ok(());
+            }
+
+            let passphrase = if include_secrets {
+                let passphrase = Password::new()
+                    .with_prompt("Export passphrase")
+                    .with_confirmation("Confirm passphrase", "Passphrases do not match")
+                    .interact()?;
+                if passphrase.trim().is_empty() {
+                    bail!("Passphrase cannot be empty");
+                }
+                Some(passphrase)
+            } else {
+                None
+            };
+
+            let export = auth::export::build_export(&data, passphrase.as_deref())?;
+            let path = output.unwrap_or_else(|| PathBuf::from("zeroclaw-auth-export.json"));
+            auth::export::write_export_file(&path, &export)?;
+
+            println!(
+                "Exported {} profile(s) to {} ({})",
+                export.profiles.len(),
+                path.display(),
+                if include_secrets {
+                    "credentials encrypted with passphrase"
+                } else {
+                    "metadata only"
+                }
+            );
+            Ok(())
+        }
+
+        AuthCommands::Import { file } => {
+            let export = auth::export::read_export_file(&file)?;
+            let passphrase = Password::new()
+                .with_prompt("Export passphrase")
+                .interact()?;
+            let imported = auth::export::decrypt_export(&export, &passphrase)?;
+
+            let existing = auth_service.load_profiles().await?;
+            let mut imported_count = 0usize;
+            let mut skipped = 0usize;
+            for (id, profile) in imported.profiles {
+                if existing.profiles.contains_key(&id) {
+                    let answer = read_plain_input(&format!(
+                        "Profile {id} already exists. Overwrite? [y/N]"
+                    ))?;
+                    if !answer.eq_ignore_ascii_case("y") {
+                        skipped += 1;
+                        continue;
+                    }
+                }
+                let set_active = imported
+                    .active_profiles
+                    .get(&profile.provider)
+                    .is_some_and(|active_id| active_id == &id);
+                auth_service.upsert_profile(profile, set_active).await?;
+                imported_count += 1;
+            }
+
+            println!("Imported {imported_count} profile(s), skipped {skipped}.");
+            Ok(())
+        }
+
         AuthCommands::List => {
             let data = auth_service.load_profiles().await?;
             if data.profiles.is_empty() {
@@ -2597,13 +2868,17 @@ async fn handle_auth_command(auth_command: AuthCommands, config: &Config) -> Res
                     .get(&profile.provider)
                     .is_some_and(|active_id| active_id == id);
                 let marker = if active { "*" } else { " " };
+                let cooldown = auth::profile_cooldown_remaining(id)
+                    .map(|secs| format!(" cooldown={secs}s"))
+                    .unwrap_or_default();
                 println!(
-                    "{} {} kind={:?} account={} expires={}",
+                    "{} {} kind={:?} account={} expires={}{}",
                     marker,
                     id,
                     profile.kind,
                     crate::security::redact(profile.account_id.as_deref().unwrap_or("unknown")),
-                    format_expiry(profile)
+                    format_expiry(profile),
+                    cooldown
                 );
             }
 
@@ -215,6 +215,27 @@ impl<M: Memory> Memory for AuditedMemory<M> {
             .await
     }
 
+    async fn recall_in_namespaces(
+        &self,
+        namespaces: &[String],
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.log_audit(
+            AuditOp::Recall,
+            None,
+            Some(&namespaces.join(",")),
+            session_id,
+            Some(&format!("query={query}")),
+        );
+        self.inner
+            .recall_in_namespaces(namespaces, query, limit, session_id, since, until)
+            .await
+    }
+
     async fn store_with_metadata(
         &self,
         key: &str,
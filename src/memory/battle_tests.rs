@@ -221,6 +221,93 @@ mod tests {
         assert!(results.is_empty(), "agent-a should not see agent-b data");
     }
 
+    #[tokio::test]
+    async fn recall_in_namespaces_isolates_senders() {
+        let (_tmp, mem) = temp_sqlite();
+
+        mem.store_with_metadata(
+            "alice_pref",
+            "Alice prefers short answers",
+            MemoryCategory::Core,
+            None,
+            Some("sender:slack:alice"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        mem.store_with_metadata(
+            "bob_pref",
+            "Bob prefers long answers",
+            MemoryCategory::Core,
+            None,
+            Some("sender:telegram:bob"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let alice_set = vec!["sender:slack:alice".to_string(), "global".to_string()];
+        let results = mem
+            .recall_in_namespaces(&alice_set, "answers", 10, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].content.contains("Alice"));
+
+        let bob_set = vec!["sender:telegram:bob".to_string(), "global".to_string()];
+        let results = mem
+            .recall_in_namespaces(&bob_set, "answers", 10, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].content.contains("Bob"));
+    }
+
+    #[tokio::test]
+    async fn recall_in_namespaces_falls_through_to_global() {
+        let (_tmp, mem) = temp_sqlite();
+
+        mem.store_with_metadata(
+            "alice_pref",
+            "Alice prefers short answers",
+            MemoryCategory::Core,
+            None,
+            Some("sender:slack:alice"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        mem.store_with_metadata(
+            "team_fact",
+            "Standup answers are due by ten",
+            MemoryCategory::Core,
+            None,
+            Some("global"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Sender namespace plus global: sees both.
+        let alice_set = vec!["sender:slack:alice".to_string(), "global".to_string()];
+        let results = mem
+            .recall_in_namespaces(&alice_set, "answers", 10, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // Without global in the read set (allowlist denial): own entries only.
+        let denied_set = vec!["sender:slack:alice".to_string()];
+        let results = mem
+            .recall_in_namespaces(&denied_set, "answers", 10, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].content.contains("Alice"));
+    }
+
     #[tokio::test]
     async fn namespace_default_assignment() {
         let (_tmp, mem) = temp_sqlite();
@@ -388,6 +475,8 @@ mod tests {
             namespace: "default".into(),
             importance: None,
             superseded_by: None,
+            pinned: false,
+            score_breakdown: None,
         }];
 
         let conflicts = conflict::find_text_conflicts(&entries, "User prefers Go", 0.3);
@@ -410,6 +499,8 @@ mod tests {
             namespace: "default".into(),
             importance: Some(0.7),
             superseded_by: Some("newer_id".into()), // already superseded
+            pinned: false,
+            score_breakdown: None,
         }];
 
         let conflicts =
@@ -433,6 +524,8 @@ mod tests {
             namespace: "default".into(),
             importance: Some(0.7),
             superseded_by: None,
+            pinned: false,
+            score_breakdown: None,
         }];
 
         // Exact same content should not be a conflict
@@ -1039,6 +1132,8 @@ mod tests {
             namespace: "my-namespace".into(),
             importance: Some(0.7),
             superseded_by: Some("newer-id".into()),
+            pinned: false,
+            score_breakdown: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -1066,5 +1161,29 @@ mod tests {
         assert_eq!(parsed.namespace, "default", "Should default to 'default'");
         assert!(parsed.importance.is_none());
         assert!(parsed.superseded_by.is_none());
+        assert!(!parsed.pinned, "Should default to unpinned");
+    }
+
+    #[tokio::test]
+    async fn set_pinned_round_trip() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("tz", "Europe/Lisbon", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(mem.set_pinned("tz", true).await.unwrap());
+        assert!(mem.get("tz").await.unwrap().unwrap().pinned);
+
+        // Re-storing the same key must not clear the pin.
+        mem.store("tz", "Europe/Porto", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(mem.get("tz").await.unwrap().unwrap().pinned);
+
+        assert!(mem.set_pinned("tz", false).await.unwrap());
+        assert!(!mem.get("tz").await.unwrap().unwrap().pinned);
+
+        // Unknown keys report no update.
+        assert!(!mem.set_pinned("missing", true).await.unwrap());
     }
 }
@@ -13,6 +13,234 @@ pub struct Chunk {
     pub heading: Option<Rc<str>>,
 }
 
+/// Chunking strategy selected by `[rag.chunking]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkStrategy {
+    /// Fixed-size token windows over words, ignoring document structure.
+    Fixed,
+    /// Whole sentences packed to the target size; never splits mid-sentence.
+    Sentence,
+    /// Heading-aware sections (the default); never crosses an H1/H2 boundary.
+    Heading,
+}
+
+impl ChunkStrategy {
+    /// Parse a config strategy name; `None` for unknown names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fixed" => Some(Self::Fixed),
+            "sentence" => Some(Self::Sentence),
+            "heading" => Some(Self::Heading),
+            _ => None,
+        }
+    }
+}
+
+/// Chunking parameters shared by all strategies.
+#[derive(Debug, Clone)]
+pub struct ChunkingOptions {
+    pub strategy: ChunkStrategy,
+    /// Target chunk size in estimated tokens.
+    pub max_tokens: usize,
+    /// Estimated tokens repeated from the end of the previous chunk.
+    pub overlap_tokens: usize,
+    /// Embeddings model used for token estimation, when known.
+    pub model: Option<String>,
+}
+
+impl Default for ChunkingOptions {
+    fn default() -> Self {
+        Self {
+            strategy: ChunkStrategy::Heading,
+            max_tokens: 512,
+            overlap_tokens: 0,
+            model: None,
+        }
+    }
+}
+
+/// Estimate the token count of `text` for the given embeddings model.
+///
+/// OpenAI-style BPE tokenizers (the cl100k/o200k families behind the
+/// `text-embedding-*` models) average about 3 tokens per 4 words of English
+/// prose; other or unknown models fall back to the ~4 chars/token heuristic
+/// used elsewhere in this module.
+pub fn estimate_tokens(text: &str, model: Option<&str>) -> usize {
+    if text.trim().is_empty() {
+        return 0;
+    }
+    match model {
+        Some(m) if m.starts_with("text-embedding") => {
+            (text.split_whitespace().count() * 4).div_ceil(3).max(1)
+        }
+        _ => (text.len() / 4).max(1),
+    }
+}
+
+/// Split text according to `options`. The default options (heading strategy,
+/// no overlap, no model) reproduce [`chunk_markdown`] exactly, so existing
+/// indexes are unaffected until the config changes.
+pub fn chunk_text(text: &str, options: &ChunkingOptions) -> Vec<Chunk> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut chunks = match options.strategy {
+        ChunkStrategy::Heading if options.overlap_tokens == 0 && options.model.is_none() => {
+            return chunk_markdown(text, options.max_tokens);
+        }
+        ChunkStrategy::Heading => chunk_heading(text, options),
+        ChunkStrategy::Sentence => pack_units(&split_sentences(text), None, options),
+        ChunkStrategy::Fixed => {
+            let words: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+            pack_units(&words, None, options)
+        }
+    };
+
+    chunks.retain(|c| !c.content.is_empty());
+    for (i, chunk) in chunks.iter_mut().enumerate() {
+        chunk.index = i;
+    }
+    chunks
+}
+
+/// Heading strategy with overlap or model-aware token accounting: split into
+/// heading sections, then pack each section's sentences.
+fn chunk_heading(text: &str, options: &ChunkingOptions) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    for (heading, body) in split_on_headings(text) {
+        let heading: Option<Rc<str>> = heading.map(Rc::from);
+        chunks.extend(pack_units(&split_sentences(&body), heading, options));
+    }
+    chunks
+}
+
+/// Pack units (words or sentences) greedily into chunks of at most
+/// `max_tokens`, repeating trailing units up to `overlap_tokens` at the
+/// start of the next chunk. A unit larger than the budget becomes its own
+/// oversized chunk rather than being split.
+fn pack_units(units: &[String], heading: Option<Rc<str>>, options: &ChunkingOptions) -> Vec<Chunk> {
+    let model = options.model.as_deref();
+    let prefix = heading
+        .as_deref()
+        .map_or_else(String::new, |h| format!("{h}\n"));
+    let budget = options
+        .max_tokens
+        .max(1)
+        .saturating_sub(estimate_tokens(&prefix, model))
+        .max(1);
+    // Cap overlap so a chunk is never overlap-only.
+    let overlap = options.overlap_tokens.min(budget / 2);
+
+    let mut chunks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut current_tokens = 0usize;
+    let mut fresh = 0usize; // units added since the last flush (not overlap carry-over)
+
+    for unit in units {
+        let tokens = estimate_tokens(unit, model);
+        if current_tokens + tokens > budget && fresh > 0 {
+            chunks.push(Chunk {
+                index: 0,
+                content: format!("{prefix}{}", current.join(" ")).trim().to_string(),
+                heading: heading.clone(),
+            });
+            let mut kept: Vec<&str> = Vec::new();
+            let mut kept_tokens = 0usize;
+            for &prev in current.iter().rev() {
+                let prev_tokens = estimate_tokens(prev, model);
+                if kept_tokens + prev_tokens > overlap {
+                    break;
+                }
+                kept_tokens += prev_tokens;
+                kept.push(prev);
+            }
+            kept.reverse();
+            current = kept;
+            current_tokens = kept_tokens;
+            fresh = 0;
+        }
+        current.push(unit.as_str());
+        current_tokens += tokens;
+        fresh += 1;
+    }
+
+    if fresh > 0 {
+        chunks.push(Chunk {
+            index: 0,
+            content: format!("{prefix}{}", current.join(" ")).trim().to_string(),
+            heading: heading.clone(),
+        });
+    } else if chunks.is_empty() && !prefix.trim().is_empty() {
+        // Heading with no body still yields one chunk, matching chunk_markdown.
+        chunks.push(Chunk {
+            index: 0,
+            content: prefix.trim().to_string(),
+            heading,
+        });
+    }
+    chunks
+}
+
+/// Split text into sentence units. Fenced code blocks (``` … ```) are kept
+/// whole as single units so code is never split mid-block.
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut units = Vec::new();
+    let mut prose = String::new();
+    let mut fence: Option<String> = None;
+
+    for line in text.lines() {
+        if let Some(ref mut block) = fence {
+            block.push_str(line);
+            block.push('\n');
+            if line.trim_start().starts_with("```") {
+                units.push(fence.take().unwrap_or_default().trim_end().to_string());
+            }
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            split_prose_sentences(&prose, &mut units);
+            prose.clear();
+            fence = Some(format!("{line}\n"));
+            continue;
+        }
+        prose.push_str(line);
+        prose.push('\n');
+    }
+    if let Some(block) = fence {
+        // Unterminated fence — keep what we have as one unit.
+        units.push(block.trim_end().to_string());
+    }
+    split_prose_sentences(&prose, &mut units);
+    units
+}
+
+/// Split prose on sentence terminators (`.`, `!`, `?`) followed by whitespace.
+fn split_prose_sentences(prose: &str, units: &mut Vec<String>) {
+    let mut current = String::new();
+    let mut chars = prose.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            let boundary = match chars.peek() {
+                Some(next) => next.is_whitespace(),
+                None => true,
+            };
+            if boundary {
+                let sentence = current.trim();
+                if !sentence.is_empty() {
+                    units.push(sentence.to_string());
+                }
+                current.clear();
+            }
+        }
+    }
+    let sentence = current.trim();
+    if !sentence.is_empty() {
+        units.push(sentence.to_string());
+    }
+}
+
 /// Split markdown text into chunks, each under `max_tokens` approximate tokens.
 ///
 /// Strategy:
@@ -357,6 +585,162 @@ mod tests {
         assert_eq!(chunks[0].heading.as_deref(), Some("# Just a heading"));
     }
 
+    // ── Strategy selection (chunk_text) ──────────────────────────
+
+    fn options(
+        strategy: ChunkStrategy,
+        max_tokens: usize,
+        overlap_tokens: usize,
+    ) -> ChunkingOptions {
+        ChunkingOptions {
+            strategy,
+            max_tokens,
+            overlap_tokens,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn strategy_from_name() {
+        assert_eq!(
+            ChunkStrategy::from_name("fixed"),
+            Some(ChunkStrategy::Fixed)
+        );
+        assert_eq!(
+            ChunkStrategy::from_name("sentence"),
+            Some(ChunkStrategy::Sentence)
+        );
+        assert_eq!(
+            ChunkStrategy::from_name("heading"),
+            Some(ChunkStrategy::Heading)
+        );
+        assert_eq!(ChunkStrategy::from_name("semantic"), None);
+    }
+
+    #[test]
+    fn default_options_match_chunk_markdown() {
+        let text = "# Title\nSome intro.\n\n## Section A\nContent A.\n\n## Section B\nContent B.";
+        let legacy = chunk_markdown(text, 512);
+        let chunks = chunk_text(text, &ChunkingOptions::default());
+        assert_eq!(chunks.len(), legacy.len());
+        for (a, b) in chunks.iter().zip(&legacy) {
+            assert_eq!(a.content, b.content);
+            assert_eq!(a.heading, b.heading);
+        }
+    }
+
+    #[test]
+    fn fixed_strategy_windows_with_overlap() {
+        let text: String = (0..40).fold(String::new(), |mut s, i| {
+            use std::fmt::Write;
+            let _ = write!(s, "word{i} ");
+            s
+        });
+        let chunks = chunk_text(&text, &options(ChunkStrategy::Fixed, 5, 2));
+        assert!(chunks.len() > 1);
+        // Each chunk after the first starts with the tail of its predecessor.
+        for pair in chunks.windows(2) {
+            let first_word = pair[1].content.split_whitespace().next().unwrap();
+            assert!(
+                pair[0].content.ends_with(&format!(
+                    "{first_word} {}",
+                    pair[1].content.split_whitespace().nth(1).unwrap_or("")
+                )) || pair[0].content.contains(first_word),
+                "no overlap between {:?} and {:?}",
+                pair[0].content,
+                pair[1].content
+            );
+        }
+    }
+
+    #[test]
+    fn sentence_strategy_never_splits_mid_sentence() {
+        let text = "Short one. Another short sentence here! A third question, perhaps? \
+                    This is a considerably longer sentence with many extra words to push \
+                    the packer over its budget boundary.";
+        let chunks = chunk_text(text, &options(ChunkStrategy::Sentence, 10, 0));
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(
+                chunk.content.ends_with(['.', '!', '?']),
+                "chunk split mid-sentence: {:?}",
+                chunk.content
+            );
+        }
+    }
+
+    #[test]
+    fn sentence_strategy_keeps_code_blocks_whole() {
+        let text = "Intro sentence before the code. \
+                    \n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\
+                    Closing sentence after the code.";
+        let chunks = chunk_text(text, &options(ChunkStrategy::Sentence, 8, 0));
+        let with_code: Vec<_> = chunks
+            .iter()
+            .filter(|c| c.content.contains("```"))
+            .collect();
+        assert_eq!(with_code.len(), 1);
+        assert!(with_code[0].content.contains("fn main()"));
+        assert!(with_code[0].content.contains("println!"));
+    }
+
+    #[test]
+    fn heading_strategy_never_crosses_section_boundary() {
+        let mut text = String::from("# Alpha\n");
+        for i in 0..30 {
+            use std::fmt::Write;
+            let _ = writeln!(text, "Alpha sentence number {i} with several words.");
+        }
+        text.push_str("## Beta\nBeta content only here.\n");
+
+        let chunks = chunk_text(&text, &options(ChunkStrategy::Heading, 40, 12));
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(
+                !(chunk.content.contains("Alpha sentence")
+                    && chunk.content.contains("Beta content")),
+                "chunk crosses heading boundary: {:?}",
+                chunk.content
+            );
+        }
+        // Overlap: consecutive Alpha chunks share a sentence.
+        let alpha: Vec<_> = chunks
+            .iter()
+            .filter(|c| c.heading.as_deref() == Some("# Alpha"))
+            .collect();
+        assert!(alpha.len() > 1);
+        let last_sentence = alpha[0]
+            .content
+            .rsplit_once('.')
+            .map(|(head, _)| head.rsplit('.').next().unwrap_or(head).trim())
+            .unwrap_or("");
+        assert!(
+            alpha[1].content.contains(last_sentence),
+            "expected {:?} repeated in {:?}",
+            last_sentence,
+            alpha[1].content
+        );
+    }
+
+    #[test]
+    fn oversized_sentence_becomes_own_chunk() {
+        let long = format!("{} end.", "word ".repeat(300));
+        let text = format!("Short lead. {long}");
+        let chunks = chunk_text(&text, &options(ChunkStrategy::Sentence, 10, 0));
+        assert!(chunks.iter().any(|c| c.content.contains("end.")));
+        assert!(!chunks.is_empty());
+    }
+
+    #[test]
+    fn estimate_tokens_uses_model_when_known() {
+        let text = "one two three four five six seven eight nine";
+        // 9 words → ceil(9 * 4 / 3) = 12 tokens under a BPE model.
+        assert_eq!(estimate_tokens(text, Some("text-embedding-3-small")), 12);
+        // Char heuristic: 44 chars / 4 = 11.
+        assert_eq!(estimate_tokens(text, None), text.len() / 4);
+        assert_eq!(estimate_tokens("   ", None), 0);
+    }
+
     #[test]
     fn no_content_loss() {
         let text = "# A\nContent A line 1\nContent A line 2\n\n## B\nContent B\n\n## C\nContent C";
@@ -4,7 +4,7 @@ use super::{
     MemoryBackendKind,
 };
 use crate::config::Config;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use console::style;
 
 /// Handle `zeroclaw memory <subcommand>` CLI commands.
@@ -13,19 +13,56 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::List {
             category,
             session,
+            namespace,
+            pinned,
             limit,
             offset,
-        } => handle_list(config, category, session, limit, offset).await,
+            verbose,
+        } => {
+            handle_list(
+                config, category, session, namespace, pinned, limit, offset, verbose,
+            )
+            .await
+        }
         crate::MemoryCommands::Get { key } => handle_get(config, &key).await,
+        crate::MemoryCommands::Search {
+            query,
+            limit,
+            category,
+            min_score,
+            format,
+        } => handle_search(config, &query, limit, category, min_score, &format).await,
         crate::MemoryCommands::Store {
             key,
             content,
             category,
-        } => handle_store(config, &key, &content, &category).await,
-        crate::MemoryCommands::Stats => handle_stats(config).await,
-        crate::MemoryCommands::Clear { key, category, yes } => {
-            handle_clear(config, key, category, yes).await
-        }
+            pin,
+        } => handle_store(config, &key, &content, &category, pin).await,
+        crate::MemoryCommands::Pin { key } => handle_pin(config, &key, true).await,
+        crate::MemoryCommands::Unpin { key } => handle_pin(config, &key, false).await,
+        crate::MemoryCommands::Stats { namespace } => handle_stats(config, namespace).await,
+        crate::MemoryCommands::Reindex => handle_reindex(config).await,
+        crate::MemoryCommands::Prune => handle_prune(config).await,
+        crate::MemoryCommands::Distill { dry_run } => handle_distill(config, dry_run).await,
+        crate::MemoryCommands::MigrateMarkdown => handle_migrate_markdown(config).await,
+        crate::MemoryCommands::Export {
+            category,
+            since,
+            output,
+        } => handle_export(config, category, since, output).await,
+        crate::MemoryCommands::Import {
+            file,
+            merge: _,
+            overwrite,
+        } => handle_import(config, &file, overwrite).await,
+        crate::MemoryCommands::Clear {
+            key,
+            category,
+            namespace,
+            sessions,
+            yes,
+            dry_run,
+        } => handle_clear(config, key, category, namespace, sessions, yes, dry_run).await,
     }
 }
 
@@ -48,16 +85,26 @@ fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_list(
     config: &Config,
     category: Option<String>,
     session: Option<String>,
+    namespace: Option<String>,
+    pinned: bool,
     limit: usize,
     offset: usize,
+    verbose: bool,
 ) -> Result<()> {
     let mem = create_cli_memory(config)?;
     let cat = category.as_deref().map(parse_category);
-    let entries = mem.list(cat.as_ref(), session.as_deref()).await?;
+    let mut entries = mem.list(cat.as_ref(), session.as_deref()).await?;
+    if let Some(ns) = &namespace {
+        entries.retain(|e| &e.namespace == ns);
+    }
+    if pinned {
+        entries.retain(|e| e.pinned);
+    }
 
     if entries.is_empty() {
         println!("No memory entries found.");
@@ -85,6 +132,18 @@ async fn handle_list(
             entry.category,
         );
         println!("    {}", truncate_content(&entry.content, 80));
+        if verbose {
+            println!("    namespace: {}", entry.namespace);
+            if entry.pinned {
+                println!("    pinned: yes");
+            }
+            if let Some(importance) = entry.importance {
+                println!("    importance: {importance:.2}");
+            }
+            if let Some(score) = entry.score {
+                println!("    score: {}", format_score(score, entry.score_breakdown));
+            }
+        }
     }
 
     if offset + page.len() < total {
@@ -126,6 +185,120 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Run the same recall path the channel runtime uses and apply the CLI's
+/// category / minimum-score filters.
+async fn search_memory(
+    mem: &dyn Memory,
+    query: &str,
+    limit: usize,
+    category: Option<&MemoryCategory>,
+    min_score: Option<f64>,
+) -> Result<Vec<super::traits::MemoryEntry>> {
+    let mut entries = mem.recall(query, limit, None, None, None).await?;
+    if let Some(cat) = category {
+        entries.retain(|e| &e.category == cat);
+    }
+    if let Some(min) = min_score {
+        // Entries without a score (non-scoring backends) are kept, matching
+        // the channel runtime's relevance filtering.
+        entries.retain(|e| e.score.is_none_or(|s| s >= min));
+    }
+    Ok(entries)
+}
+
+/// Stable JSON shape for `memory search --format json`.
+fn search_result_json(entry: &super::traits::MemoryEntry) -> serde_json::Value {
+    serde_json::json!({
+        "key": entry.key,
+        "category": entry.category.to_string(),
+        "score": entry.score,
+        "lexical": entry.score_breakdown.and_then(|b| b.lexical),
+        "vector": entry.score_breakdown.and_then(|b| b.vector),
+        "timestamp": entry.timestamp,
+        "content": entry.content,
+    })
+}
+
+async fn handle_search(
+    config: &Config,
+    query: &str,
+    limit: usize,
+    category: Option<String>,
+    min_score: Option<f64>,
+    format: &str,
+) -> Result<()> {
+    // Unlike the management subcommands, search goes through the full
+    // memory factory so the configured embedding provider is active and
+    // the results match what the agent actually recalls.
+    let mem = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        &config.embeddings,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+
+    let cat = category.as_deref().map(parse_category);
+    let entries = search_memory(&*mem, query, limit, cat.as_ref(), min_score).await?;
+
+    if format == "json" {
+        let results: Vec<_> = entries.iter().map(search_result_json).collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::Value::Array(results))?
+        );
+        return Ok(());
+    }
+
+    let mode = match config.memory.search_mode {
+        crate::config::SearchMode::Bm25 => "bm25",
+        crate::config::SearchMode::Embedding => "embedding",
+        crate::config::SearchMode::Hybrid => "hybrid",
+    };
+    println!(
+        "Searching '{}' backend (mode: {mode}, embedding provider: {})\n",
+        style(mem.name()).white().bold(),
+        config.memory.embedding_provider,
+    );
+
+    if entries.is_empty() {
+        println!("No results for '{query}'.");
+        return Ok(());
+    }
+
+    for (rank, entry) in entries.iter().enumerate() {
+        let score = match entry.score {
+            Some(score) => format_score(score, entry.score_breakdown),
+            None => "-".into(),
+        };
+        println!(
+            "{:>2}. {} [{}] score {score} ({})",
+            rank + 1,
+            style(&entry.key).white().bold(),
+            entry.category,
+            format_age(&entry.timestamp),
+        );
+        println!("      {}", truncate_content(&entry.content, 80));
+    }
+
+    // When vector scoring should be active but no result carries a vector
+    // component, the index is likely missing or stale.
+    let vectors_expected = config.memory.embedding_provider != "none"
+        && config.memory.search_mode != crate::config::SearchMode::Bm25;
+    let no_vector_hits = entries
+        .iter()
+        .all(|e| e.score_breakdown.is_none_or(|b| b.vector.is_none()));
+    if vectors_expected && no_vector_hits {
+        println!(
+            "\nNote: no result carried a vector score — the vector index may be \
+             missing or stale. Run 'zeroclaw memory reindex' to rebuild it."
+        );
+    }
+
+    Ok(())
+}
+
 fn print_entry(entry: &super::traits::MemoryEntry) {
     println!("Key:       {}", style(&entry.key).white().bold());
     println!("Category:  {}", entry.category);
@@ -133,14 +306,46 @@ fn print_entry(entry: &super::traits::MemoryEntry) {
     if let Some(sid) = &entry.session_id {
         println!("Session:   {sid}");
     }
+    if let Some(score) = entry.score {
+        println!("Score:     {}", format_score(score, entry.score_breakdown));
+    }
     println!("\n{}", entry.content);
 }
 
-async fn handle_store(config: &Config, key: &str, content: &str, category: &str) -> Result<()> {
+/// Render a blended recall score with its per-method components, when known.
+fn format_score(score: f64, breakdown: Option<super::traits::ScoreBreakdown>) -> String {
+    let Some(breakdown) = breakdown else {
+        return format!("{score:.3}");
+    };
+    let component = |value: Option<f64>| match value {
+        Some(v) => format!("{v:.3}"),
+        None => "-".into(),
+    };
+    format!(
+        "{score:.3} (lexical: {}, vector: {})",
+        component(breakdown.lexical),
+        component(breakdown.vector),
+    )
+}
+
+async fn handle_store(
+    config: &Config,
+    key: &str,
+    content: &str,
+    category: &str,
+    pin: bool,
+) -> Result<()> {
     let mem = create_cli_memory(config)?;
     let cat = parse_category(category);
 
     mem.store(key, content, cat, None).await?;
+    if pin && !mem.set_pinned(key, true).await? {
+        println!(
+            "{} The '{}' backend does not support pinning; stored unpinned.",
+            style("!").yellow().bold(),
+            mem.name()
+        );
+    }
 
     println!(
         "{} Stored memory: {}",
@@ -151,10 +356,41 @@ async fn handle_store(config: &Config, key: &str, content: &str, category: &str)
     Ok(())
 }
 
-async fn handle_stats(config: &Config) -> Result<()> {
+/// Pin or unpin an entry so channel context always injects it.
+async fn handle_pin(config: &Config, key: &str, pinned: bool) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+
+    if mem.set_pinned(key, pinned).await? {
+        println!(
+            "{} {} memory: {}",
+            style("✓").green().bold(),
+            if pinned { "Pinned" } else { "Unpinned" },
+            style(key).white().bold()
+        );
+    } else {
+        println!(
+            "No memory entry found for key '{key}' (or the '{}' backend does not support pinning).",
+            mem.name()
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_stats(config: &Config, namespace: Option<String>) -> Result<()> {
     let mem = create_cli_memory(config)?;
     let healthy = mem.health_check().await;
-    let total = mem.count().await.unwrap_or(0);
+
+    let mut all = mem.list(None, None).await.unwrap_or_default();
+    if let Some(ns) = &namespace {
+        all.retain(|e| &e.namespace == ns);
+    }
+    // Unfiltered totals come from count() so expired-but-unpruned entries
+    // are included; a namespace filter has to count visible entries.
+    let total = match &namespace {
+        Some(_) => all.len(),
+        None => mem.count().await.unwrap_or(0),
+    };
 
     println!("Memory Statistics:\n");
     println!("  Backend:  {}", style(mem.name()).white().bold());
@@ -166,9 +402,29 @@ async fn handle_stats(config: &Config) -> Result<()> {
             style("unhealthy").yellow().bold().to_string()
         }
     );
+    if let Some(ns) = &namespace {
+        println!("  Namespace: {}", style(ns).white().bold());
+    }
     println!("  Total:    {total}");
 
-    let all = mem.list(None, None).await.unwrap_or_default();
+    let pinned_count = all.iter().filter(|e| e.pinned).count();
+    if pinned_count > 0 {
+        println!("  Pinned:   {pinned_count}");
+    }
+
+    // Expired/deduped counters are store-wide; skip them when filtered.
+    if namespace.is_none() {
+        let expired = mem.expired_count().await.unwrap_or(0);
+        if expired > 0 {
+            println!("  Expired:  {expired} (run 'zeroclaw memory prune' to delete)");
+        }
+
+        let deduped = mem.deduped_store_count().await.unwrap_or(0);
+        if deduped > 0 {
+            println!("  Deduped:  {deduped} stores folded into existing entries");
+        }
+    }
+
     if !all.is_empty() {
         let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         for entry in &all {
@@ -181,35 +437,515 @@ async fn handle_stats(config: &Config) -> Result<()> {
         for (cat, count) in sorted {
             println!("    {cat:<20} {count}");
         }
+
+        if namespace.is_none() {
+            let mut ns_counts: std::collections::HashMap<String, usize> =
+                std::collections::HashMap::new();
+            for entry in &all {
+                *ns_counts.entry(entry.namespace.clone()).or_default() += 1;
+            }
+            if ns_counts.len() > 1 {
+                println!("\n  By namespace:");
+                let mut sorted: Vec<_> = ns_counts.into_iter().collect();
+                sorted.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+                for (ns, count) in sorted {
+                    println!("    {ns:<20} {count}");
+                }
+            }
+        }
+    }
+
+    // The markdown backend is file-per-day; show where the bytes live.
+    if mem.name() == "markdown" {
+        let md = super::MarkdownMemory::new(&config.workspace_dir);
+        let sizes = md.file_sizes().await.unwrap_or_default();
+        if !sizes.is_empty() {
+            println!("\n  Files:");
+            for (name, bytes) in sizes {
+                println!("    {name:<28} {}", format_file_size(bytes));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Render a byte count as a short human-readable size.
+fn format_file_size(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// Backfill embeddings for entries that were stored before vector recall
+/// was configured. Unlike the other subcommands this needs the full memory
+/// factory so the configured embedding provider is wired up.
+async fn handle_reindex(config: &Config) -> Result<()> {
+    let mem = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        &config.embeddings,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+
+    println!("Reindexing memory embeddings...");
+
+    let progress = |done: usize, total: usize| {
+        if done.is_multiple_of(25) || done == total {
+            println!("  {done}/{total} entries embedded");
+        }
+    };
+    let embedded = mem.reindex_embeddings(Some(&progress)).await?;
+
+    if embedded == 0 {
+        println!(
+            "{} Nothing to reindex — all entries already have embeddings \
+             (or no embedding provider is configured).",
+            style("✓").green().bold()
+        );
+    } else {
+        println!(
+            "{} Backfilled embeddings for {embedded} entries.",
+            style("✓").green().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Distill recent conversation entries into durable Core facts via the
+/// configured provider. Cron-schedulable by running the command from a
+/// shell cron job; `--dry-run` previews without writing.
+async fn handle_distill(config: &Config, dry_run: bool) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+    let provider_name = config
+        .default_provider
+        .as_deref()
+        .context("no default provider configured; distillation needs an LLM")?;
+    let model = config
+        .default_model
+        .as_deref()
+        .context("no default model configured; distillation needs an LLM")?;
+    let provider = crate::providers::create_provider(provider_name, config.api_key.as_deref())?;
+
+    if dry_run {
+        println!("Distilling conversation memories (dry run)...\n");
+    } else {
+        println!("Distilling conversation memories...\n");
+    }
+
+    let report = super::distill::run(
+        provider.as_ref(),
+        model,
+        &*mem,
+        &config.memory.distill,
+        dry_run,
+    )
+    .await?;
+
+    if report.facts.is_empty() {
+        println!(
+            "No durable facts extracted ({} session(s) processed).",
+            report.sessions_processed
+        );
+    } else if dry_run {
+        println!("Would store {} fact(s):\n", report.facts.len());
+        for fact in &report.facts {
+            println!("- {}", fact.content);
+            println!(
+                "    distilled from {} conversation entries",
+                fact.source_keys.len()
+            );
+        }
+    } else {
+        println!(
+            "{} Stored {} fact(s) from {} session(s).",
+            style("✓").green().bold(),
+            report.facts.len(),
+            report.sessions_processed
+        );
+        if report.consumed > 0 {
+            println!("  Consumed {} conversation entries.", report.consumed);
+        }
+    }
+
+    if report.sessions_skipped_budget > 0 {
+        println!(
+            "  {} session(s) deferred: token budget reached.",
+            report.sessions_skipped_budget
+        );
+    }
+    if report.sessions_failed > 0 {
+        println!(
+            "  {} session(s) failed; their entries were left untouched.",
+            report.sessions_failed
+        );
+    }
+
+    Ok(())
+}
+
+/// Page size for streaming exports: bounds memory usage on large stores.
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Outcome of a JSONL import: per-line failures never abort the run.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ImportReport {
+    imported: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+/// Stream entries as JSONL through `writer`, one page at a time.
+/// Returns the number of entries written.
+async fn export_entries(
+    mem: &dyn Memory,
+    category: Option<&MemoryCategory>,
+    since: Option<&str>,
+    writer: &mut dyn std::io::Write,
+) -> Result<usize> {
+    let mut offset = 0;
+    let mut written = 0;
+
+    loop {
+        let page = mem.iter_all(offset, EXPORT_PAGE_SIZE).await?;
+        if page.is_empty() {
+            break;
+        }
+        offset += page.len();
+
+        for entry in page {
+            if let Some(cat) = category {
+                if entry.category != *cat {
+                    continue;
+                }
+            }
+            if let Some(since) = since {
+                if entry.timestamp.as_str() < since {
+                    continue;
+                }
+            }
+            serde_json::to_writer(&mut *writer, &entry)?;
+            writeln!(writer)?;
+            written += 1;
+        }
+    }
+
+    writer.flush()?;
+    Ok(written)
+}
+
+/// Import JSONL entries from `reader`. Invalid lines are reported to
+/// `errors` and counted, but never abort the import. Key conflicts are
+/// skipped unless `overwrite` is set.
+async fn import_entries(
+    mem: &dyn Memory,
+    reader: &mut dyn std::io::BufRead,
+    overwrite: bool,
+    errors: &mut dyn std::io::Write,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let mut line = String::new();
+    let mut line_no = 0usize;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let entry: super::traits::MemoryEntry = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                writeln!(errors, "  line {line_no}: invalid entry: {e}")?;
+                report.failed += 1;
+                continue;
+            }
+        };
+        if entry.key.trim().is_empty() {
+            writeln!(errors, "  line {line_no}: entry has an empty key")?;
+            report.failed += 1;
+            continue;
+        }
+
+        if !overwrite && mem.get(&entry.key).await?.is_some() {
+            report.skipped += 1;
+            continue;
+        }
+
+        match mem
+            .store_with_metadata(
+                &entry.key,
+                &entry.content,
+                entry.category.clone(),
+                entry.session_id.as_deref(),
+                Some(&entry.namespace),
+                entry.importance,
+            )
+            .await
+        {
+            Ok(()) => report.imported += 1,
+            Err(e) => {
+                writeln!(errors, "  line {line_no}: store failed: {e}")?;
+                report.failed += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+async fn handle_export(
+    config: &Config,
+    category: Option<String>,
+    since: Option<String>,
+    output: Option<std::path::PathBuf>,
+) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+    let cat = category.as_deref().map(parse_category);
+
+    let written = if let Some(path) = &output {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        let mut writer = std::io::BufWriter::new(file);
+        export_entries(&*mem, cat.as_ref(), since.as_deref(), &mut writer).await?
+    } else {
+        let mut stdout = std::io::stdout().lock();
+        export_entries(&*mem, cat.as_ref(), since.as_deref(), &mut stdout).await?
+    };
+
+    // Keep the summary off stdout so piped exports stay valid JSONL.
+    if let Some(path) = &output {
+        println!(
+            "{} Exported {written} entries to {}",
+            style("✓").green().bold(),
+            path.display()
+        );
+    } else {
+        eprintln!("Exported {written} entries.");
     }
 
     Ok(())
 }
 
+async fn handle_import(config: &Config, file: &std::path::Path, overwrite: bool) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+    let opened =
+        std::fs::File::open(file).with_context(|| format!("failed to open {}", file.display()))?;
+    let mut reader = std::io::BufReader::new(opened);
+
+    let report = import_entries(&*mem, &mut reader, overwrite, &mut std::io::stderr()).await?;
+
+    println!(
+        "{} Imported {} entries ({} skipped, {} failed).",
+        style("✓").green().bold(),
+        report.imported,
+        report.skipped,
+        report.failed
+    );
+
+    Ok(())
+}
+
+/// Physically delete entries whose TTL has elapsed.
+async fn handle_prune(config: &Config) -> Result<()> {
+    let mem = create_cli_memory(config)?;
+    let pruned = mem.prune_expired().await?;
+
+    if pruned == 0 {
+        println!("No expired entries to prune.");
+    } else {
+        println!(
+            "{} Pruned {pruned} expired entries.",
+            style("✓").green().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Convert a legacy markdown memory layout (MEMORY.md plus bullet-list
+/// daily files) to the daily frontmatter layout.
+async fn handle_migrate_markdown(config: &Config) -> Result<()> {
+    let mem = super::MarkdownMemory::new(&config.workspace_dir);
+    let report = mem.migrate_legacy().await?;
+
+    if report.files_converted == 0 {
+        println!("No legacy markdown memory files found.");
+    } else {
+        println!(
+            "{} Converted {} file(s), migrated {} entries to the frontmatter layout.",
+            style("✓").green().bold(),
+            report.files_converted,
+            report.entries_migrated,
+        );
+    }
+
+    Ok(())
+}
+
+/// Maximum matched entries shown in the clear preview before eliding.
+const CLEAR_PREVIEW_MAX: usize = 20;
+
+/// Whether a clear key pattern uses glob matching (`*`/`?`) rather than
+/// exact-or-prefix matching.
+fn is_glob_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Match `text` against a simple glob: `*` matches any (possibly empty)
+/// sequence, `?` matches exactly one character. Anchored at both ends.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Iterative matcher with star backtracking.
+    let (mut p, mut t) = (0usize, 0usize);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+    p == pattern.len()
+}
+
+/// Match a memory key against a `--key` pattern: glob when the pattern
+/// contains `*`/`?`, otherwise exact-or-prefix (the original semantics).
+fn key_matches(pattern: &str, key: &str) -> bool {
+    if is_glob_pattern(pattern) {
+        glob_match(pattern, key)
+    } else {
+        key.starts_with(pattern)
+    }
+}
+
+/// Resolve the entries a clear invocation targets. Category filtering is
+/// native to `list()`; the key pattern and namespace intersect on top, so
+/// `--key` combined with `--category` narrows rather than overrides.
+async fn clear_targets(
+    mem: &dyn Memory,
+    key: Option<&str>,
+    category: Option<&MemoryCategory>,
+    namespace: Option<&str>,
+) -> Result<Vec<super::traits::MemoryEntry>> {
+    let mut entries = mem.list(category, None).await?;
+    if let Some(ns) = namespace {
+        entries.retain(|e| e.namespace == ns);
+    }
+    if let Some(pattern) = key {
+        entries.retain(|e| key_matches(pattern, &e.key));
+    }
+    Ok(entries)
+}
+
+/// Human-readable age of an RFC 3339 timestamp ("3d", "5h", "12m").
+fn format_age(timestamp: &str) -> String {
+    let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(timestamp) else {
+        return "?".into();
+    };
+    let age = chrono::Local::now().signed_duration_since(parsed);
+    if age.num_days() > 0 {
+        format!("{}d", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{}m", age.num_minutes())
+    } else {
+        "<1m".into()
+    }
+}
+
+/// Print the preview shown by both `--dry-run` and the deletion prompt.
+fn print_clear_preview(entries: &[super::traits::MemoryEntry]) {
+    for entry in entries.iter().take(CLEAR_PREVIEW_MAX) {
+        println!(
+            "- {} [{}] ({})",
+            style(&entry.key).white().bold(),
+            entry.category,
+            format_age(&entry.timestamp),
+        );
+        println!("    {}", truncate_content(&entry.content, 80));
+    }
+    if entries.len() > CLEAR_PREVIEW_MAX {
+        println!("  … and {} more", entries.len() - CLEAR_PREVIEW_MAX);
+    }
+}
+
 async fn handle_clear(
     config: &Config,
     key: Option<String>,
     category: Option<String>,
+    namespace: Option<String>,
+    sessions: bool,
     yes: bool,
+    dry_run: bool,
 ) -> Result<()> {
-    let mem = create_cli_memory(config)?;
-
-    // Single-key deletion (exact or prefix match).
-    if let Some(key) = key {
-        return handle_clear_key(&*mem, &key, yes).await;
+    if sessions {
+        handle_clear_sessions(config, yes)?;
+        // `--sessions` alone only touches persisted channel histories;
+        // memory entries are cleared too only when another filter is given.
+        if key.is_none() && category.is_none() && namespace.is_none() {
+            return Ok(());
+        }
     }
 
-    // Batch deletion by category (or all).
+    let mem = create_cli_memory(config)?;
     let cat = category.as_deref().map(parse_category);
-    let entries = mem.list(cat.as_ref(), None).await?;
+    let entries = clear_targets(&*mem, key.as_deref(), cat.as_ref(), namespace.as_deref()).await?;
 
     if entries.is_empty() {
         println!("No entries to clear.");
         return Ok(());
     }
 
-    let scope = category.as_deref().unwrap_or("all categories");
-    println!("Found {} entries in '{scope}'.", entries.len());
+    let mut scope_parts = Vec::new();
+    if let Some(key) = &key {
+        scope_parts.push(format!("key '{key}'"));
+    }
+    if let Some(cat) = &category {
+        scope_parts.push(format!("category '{cat}'"));
+    }
+    if let Some(ns) = &namespace {
+        scope_parts.push(format!("namespace '{ns}'"));
+    }
+    let scope = if scope_parts.is_empty() {
+        "all categories".to_string()
+    } else {
+        scope_parts.join(", ")
+    };
+
+    println!("Found {} entries matching {scope}:\n", entries.len());
+    print_clear_preview(&entries);
+
+    if dry_run {
+        println!("\nDry run: {} entries would be deleted.", entries.len());
+        return Ok(());
+    }
 
     if !yes {
         let confirmed = dialoguer::Confirm::new()
@@ -238,38 +974,19 @@ async fn handle_clear(
     Ok(())
 }
 
-/// Delete a single entry by exact key or prefix match.
-async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()> {
-    // Resolve the target key (exact match or unique prefix).
-    let target = if mem.get(key).await?.is_some() {
-        key.to_string()
-    } else {
-        let all = mem.list(None, None).await?;
-        let matches: Vec<_> = all.iter().filter(|e| e.key.starts_with(key)).collect();
-        match matches.len() {
-            0 => {
-                println!("No memory entry found for key: {key}");
-                return Ok(());
-            }
-            1 => matches[0].key.clone(),
-            n => {
-                println!("Prefix '{key}' matched {n} entries:\n");
-                for entry in matches {
-                    println!(
-                        "- {} [{}]",
-                        style(&entry.key).white().bold(),
-                        entry.category
-                    );
-                }
-                println!("\nSpecify a longer prefix to narrow the match.");
-                return Ok(());
-            }
-        }
-    };
+/// Wipe all persisted channel conversation sessions (`{workspace}/sessions/`).
+fn handle_clear_sessions(config: &Config, yes: bool) -> Result<()> {
+    let store = crate::channels::session_store::SessionStore::new(&config.workspace_dir)?;
+    let count = store.list_sessions().len();
+
+    if count == 0 {
+        println!("No persisted channel sessions to clear.");
+        return Ok(());
+    }
 
     if !yes {
         let confirmed = dialoguer::Confirm::new()
-            .with_prompt(format!("  Delete '{target}'?"))
+            .with_prompt(format!("  Delete {count} persisted channel session(s)?"))
             .default(false)
             .interact()?;
         if !confirmed {
@@ -278,9 +995,11 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
         }
     }
 
-    if mem.forget(&target).await? {
-        println!("{} Deleted key: {target}", style("✓").green().bold());
-    }
+    let deleted = store.delete_all_sessions()?;
+    println!(
+        "{} Cleared {deleted} persisted channel session(s).",
+        style("✓").green().bold(),
+    );
 
     Ok(())
 }
@@ -345,4 +1064,288 @@ mod tests {
     fn truncate_content_empty_string() {
         assert_eq!(truncate_content("", 10), "");
     }
+
+    // ── Export / import tests ────────────────────────────────────
+
+    #[tokio::test]
+    async fn export_import_round_trip_across_backends() {
+        let src_tmp = tempfile::TempDir::new().unwrap();
+        let src = crate::memory::SqliteMemory::new(src_tmp.path()).unwrap();
+        src.store("lang", "prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        src.store(
+            "chat",
+            "said hello",
+            MemoryCategory::Conversation,
+            Some("s1"),
+        )
+        .await
+        .unwrap();
+
+        let mut jsonl = Vec::new();
+        let written = export_entries(&src, None, None, &mut jsonl).await.unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            jsonl
+                .split(|b| *b == b'\n')
+                .filter(|l| !l.is_empty())
+                .count(),
+            2
+        );
+
+        let dst_tmp = tempfile::TempDir::new().unwrap();
+        let dst = crate::memory::MarkdownMemory::new(dst_tmp.path());
+        let mut errors = Vec::new();
+        let report = import_entries(&dst, &mut jsonl.as_slice(), false, &mut errors)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 2,
+                skipped: 0,
+                failed: 0
+            }
+        );
+
+        let entry = dst.get("lang").await.unwrap().unwrap();
+        assert_eq!(entry.content, "prefers Rust");
+    }
+
+    #[tokio::test]
+    async fn export_honors_category_filter() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("keep", "core fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("drop", "chit chat", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+
+        let mut jsonl = Vec::new();
+        let written = export_entries(&mem, Some(&MemoryCategory::Core), None, &mut jsonl)
+            .await
+            .unwrap();
+        assert_eq!(written, 1);
+        let text = String::from_utf8(jsonl).unwrap();
+        assert!(text.contains("core fact"));
+        assert!(!text.contains("chit chat"));
+    }
+
+    #[tokio::test]
+    async fn import_tolerates_corrupted_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+
+        let jsonl = concat!(
+            r#"{"id":"1","key":"good","content":"valid entry","category":"core","timestamp":"2026-01-01T00:00:00Z","session_id":null,"score":null}"#,
+            "\n",
+            "not json at all\n",
+            r#"{"id":"2","key":"","content":"missing key","category":"core","timestamp":"2026-01-01T00:00:00Z","session_id":null,"score":null}"#,
+            "\n",
+        );
+        let mut errors = Vec::new();
+        let report = import_entries(&mem, &mut jsonl.as_bytes(), false, &mut errors)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 1,
+                skipped: 0,
+                failed: 2
+            }
+        );
+        let rendered = String::from_utf8(errors).unwrap();
+        assert!(rendered.contains("line 2"));
+        assert!(rendered.contains("line 3"));
+        assert!(mem.get("good").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn import_merge_skips_and_overwrite_replaces_conflicts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("lang", "original", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let jsonl = concat!(
+            r#"{"id":"1","key":"lang","content":"replacement","category":"core","timestamp":"2026-01-01T00:00:00Z","session_id":null,"score":null}"#,
+            "\n",
+        );
+
+        // Merge mode (default): existing key wins.
+        let mut errors = Vec::new();
+        let report = import_entries(&mem, &mut jsonl.as_bytes(), false, &mut errors)
+            .await
+            .unwrap();
+        assert_eq!(report.skipped, 1);
+        assert_eq!(mem.get("lang").await.unwrap().unwrap().content, "original");
+
+        // Overwrite mode: imported entry wins.
+        let report = import_entries(&mem, &mut jsonl.as_bytes(), true, &mut errors)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().content,
+            "replacement"
+        );
+    }
+
+    // ── Clear matching tests ─────────────────────────────────────
+
+    #[test]
+    fn glob_match_star_and_question() {
+        assert!(glob_match("user_*", "user_lang"));
+        assert!(glob_match("*_lang", "user_lang"));
+        assert!(glob_match("user_????", "user_lang"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("u*g", "user_lang"));
+        assert!(!glob_match("user_?", "user_lang"));
+        assert!(!glob_match("user_*", "admin_lang"));
+    }
+
+    #[test]
+    fn glob_match_is_anchored() {
+        assert!(!glob_match("lang", "user_lang"));
+        assert!(!glob_match("user", "user_lang"));
+        assert!(glob_match("user_lang", "user_lang"));
+    }
+
+    #[test]
+    fn key_matches_prefix_without_glob_chars() {
+        // Without `*`/`?` the original prefix semantics apply.
+        assert!(key_matches("user_", "user_lang"));
+        assert!(key_matches("user_lang", "user_lang"));
+        assert!(!key_matches("lang", "user_lang"));
+    }
+
+    #[tokio::test]
+    async fn clear_targets_intersects_key_and_category() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("user_lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("user_note", "hi", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+        mem.store("admin_lang", "Go", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let matched = clear_targets(&mem, Some("user_*"), Some(&MemoryCategory::Core), None)
+            .await
+            .unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].key, "user_lang");
+
+        let matched = clear_targets(&mem, Some("user_*"), None, None)
+            .await
+            .unwrap();
+        assert_eq!(matched.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn clear_targets_does_not_mutate_store() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("a", "one", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "two", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        // Resolving targets (the dry-run path) is read-only.
+        let matched = clear_targets(&mem, Some("*"), None, None).await.unwrap();
+        assert_eq!(matched.len(), 2);
+        assert_eq!(mem.count().await.unwrap(), 2);
+    }
+
+    // ── Search tests ─────────────────────────────────────────────
+
+    async fn seeded_search_mem() -> (tempfile::TempDir, crate::memory::SqliteMemory) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "rust_speed",
+            "Rust is fast and memory safe",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "rust_note",
+            "Started learning Rust",
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "python",
+            "Python is interpreted",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        (tmp, mem)
+    }
+
+    #[tokio::test]
+    async fn search_returns_results_ordered_by_score() {
+        let (_tmp, mem) = seeded_search_mem().await;
+
+        let results = search_memory(&mem, "rust memory safe", 10, None, None)
+            .await
+            .unwrap();
+        assert!(results.len() >= 2);
+        assert_eq!(results[0].key, "rust_speed", "best match must rank first");
+        for pair in results.windows(2) {
+            assert!(
+                pair[0].score.unwrap_or(0.0) >= pair[1].score.unwrap_or(0.0),
+                "results must be sorted by descending score"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn search_applies_category_and_min_score_filters() {
+        let (_tmp, mem) = seeded_search_mem().await;
+
+        let core_only = search_memory(&mem, "rust", 10, Some(&MemoryCategory::Core), None)
+            .await
+            .unwrap();
+        assert!(core_only.iter().all(|e| e.category == MemoryCategory::Core));
+
+        let strict = search_memory(&mem, "rust", 10, None, Some(2.0))
+            .await
+            .unwrap();
+        assert!(strict.is_empty(), "no entry can clear a min score of 2.0");
+    }
+
+    #[tokio::test]
+    async fn search_json_has_stable_shape() {
+        let (_tmp, mem) = seeded_search_mem().await;
+
+        let results = search_memory(&mem, "rust", 1, None, None).await.unwrap();
+        let json = search_result_json(&results[0]);
+
+        assert!(json["key"].is_string());
+        assert!(json["category"].is_string());
+        assert!(json["score"].is_number());
+        assert!(json["timestamp"].is_string());
+        assert!(json["content"].is_string());
+        // Component scores are present as keys even when null.
+        assert!(json.as_object().unwrap().contains_key("lexical"));
+        assert!(json.as_object().unwrap().contains_key("vector"));
+    }
 }
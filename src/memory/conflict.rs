@@ -150,6 +150,8 @@ mod tests {
                 namespace: "default".into(),
                 importance: Some(0.7),
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             },
             MemoryEntry {
                 id: "2".into(),
@@ -162,6 +164,8 @@ mod tests {
                 namespace: "default".into(),
                 importance: Some(0.3),
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             },
         ];
 
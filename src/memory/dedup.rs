@@ -0,0 +1,135 @@
+//! Near-duplicate detection for memory stores.
+//!
+//! Auto-save tends to capture the same fact repeatedly with slightly
+//! different wording ("my favorite color is blue" five times), bloating
+//! recall. Before inserting, the store path compares new content against
+//! recent entries in the same category — embedding cosine when both vectors
+//! exist, word-shingle Jaccard otherwise — and applies the configured
+//! `[memory.dedup]` policy on a hit.
+
+use crate::config::schema::DedupPolicy;
+use std::collections::HashSet;
+
+/// Words per shingle for [`shingle_similarity`]. Three-word shingles keep
+/// word order significant without over-penalizing small insertions.
+const SHINGLE_SIZE: usize = 3;
+
+/// Lowercase and strip punctuation so trivial formatting differences
+/// ("Blue!" vs "blue") don't mask duplicates.
+#[must_use]
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Word shingles of a normalized text. Texts shorter than one shingle fall
+/// back to single words so short entries still compare meaningfully.
+fn shingles(normalized: &str) -> HashSet<String> {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if words.len() < SHINGLE_SIZE {
+        return words.iter().map(ToString::to_string).collect();
+    }
+    words.windows(SHINGLE_SIZE).map(|w| w.join(" ")).collect()
+}
+
+/// Jaccard similarity on word shingles of normalized content (0.0–1.0).
+#[must_use]
+pub fn shingle_similarity(a: &str, b: &str) -> f64 {
+    let sa = shingles(&normalize(a));
+    let sb = shingles(&normalize(b));
+    if sa.is_empty() && sb.is_empty() {
+        return 1.0;
+    }
+    if sa.is_empty() || sb.is_empty() {
+        return 0.0;
+    }
+    let intersection = sa.intersection(&sb).count();
+    let union = sa.len() + sb.len() - intersection;
+    #[allow(clippy::cast_precision_loss)]
+    {
+        intersection as f64 / union as f64
+    }
+}
+
+/// Merge near-duplicate contents without losing information: keeps the
+/// superset when one text contains the other, otherwise concatenates.
+#[must_use]
+pub fn merge_contents(existing: &str, incoming: &str) -> String {
+    if existing.contains(incoming) {
+        existing.to_string()
+    } else if incoming.contains(existing) {
+        incoming.to_string()
+    } else {
+        format!("{existing}\n{incoming}")
+    }
+}
+
+/// Stable label for a dedup policy, used in trace events and log lines.
+#[must_use]
+pub fn policy_label(policy: &DedupPolicy) -> &'static str {
+    match policy {
+        DedupPolicy::Skip => "skip",
+        DedupPolicy::Refresh => "refresh",
+        DedupPolicy::Merge => "merge",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_punctuation_and_case() {
+        assert_eq!(
+            normalize("My favorite color is BLUE!"),
+            "my favorite color is blue"
+        );
+        assert_eq!(normalize("  ...  "), "");
+    }
+
+    #[test]
+    fn shingle_similarity_identical_after_normalization() {
+        let sim = shingle_similarity("My favorite color is blue.", "my favorite color is blue");
+        assert!((sim - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn shingle_similarity_disjoint() {
+        let sim = shingle_similarity("deploy the staging cluster", "grocery list for the weekend");
+        assert!(sim.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn shingle_similarity_borderline_one_word_changed() {
+        // 5-word sentences share 2 of 4 distinct shingles: similarity 0.5.
+        let sim = shingle_similarity("my favorite color is blue", "my favorite color is green");
+        assert!((sim - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn shingle_similarity_short_texts_use_words() {
+        let sim = shingle_similarity("blue", "blue");
+        assert!((sim - 1.0).abs() < f64::EPSILON);
+        let sim = shingle_similarity("blue", "green");
+        assert!(sim.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn merge_keeps_superset_or_concatenates() {
+        assert_eq!(
+            merge_contents("likes Rust and Go", "likes Rust"),
+            "likes Rust and Go"
+        );
+        assert_eq!(
+            merge_contents("likes Rust", "likes Rust and Go"),
+            "likes Rust and Go"
+        );
+        assert_eq!(
+            merge_contents("likes Rust", "prefers tabs"),
+            "likes Rust\nprefers tabs"
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Conversation distillation into durable core memories.
+//!
+//! Raw conversation auto-saves are noisy: the same preferences and facts
+//! are scattered across dozens of chat turns. A distillation run groups
+//! recent Conversation-category entries by session, asks the configured
+//! provider to extract stable facts as a strict JSON list, stores each
+//! fact as a Core entry with a paired provenance record (source keys,
+//! date range), and optionally expires or deletes the consumed
+//! conversation entries. Cost is bounded by an approximate per-run token
+//! budget; provider failures or malformed output leave the source entries
+//! untouched. Triggered via `zeroclaw memory distill` (manually or from a
+//! shell cron job).
+
+use crate::config::schema::{DistillConsumePolicy, MemoryDistillConfig};
+use crate::memory::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::providers::traits::Provider;
+use std::collections::BTreeMap;
+
+/// Key prefix for distilled Core facts.
+const FACT_KEY_PREFIX: &str = "distilled_";
+
+/// Key prefix for provenance records paired with distilled facts.
+pub const PROVENANCE_KEY_PREFIX: &str = "distill_provenance_";
+
+/// Custom category holding provenance records.
+pub const PROVENANCE_CATEGORY: &str = "distill_provenance";
+
+const DISTILL_SYSTEM_PROMPT: &str = r#"You are a memory distillation engine. Given raw conversation notes, extract stable facts, preferences, or decisions worth remembering long-term.
+
+Respond ONLY with valid JSON: {"facts": ["...", "..."]}
+Each fact must be a single self-contained sentence. Return {"facts": []} if nothing durable was learned. Do not include any text outside the JSON object."#;
+
+/// Approximate token count (4 chars ≈ 1 token) used for the run budget.
+fn approx_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// One fact a run stored (or would store, in dry-run mode).
+#[derive(Debug)]
+pub struct DistilledFact {
+    pub key: String,
+    pub content: String,
+    pub session_id: Option<String>,
+    pub source_keys: Vec<String>,
+}
+
+/// Summary of a distillation run.
+#[derive(Debug, Default)]
+pub struct DistillReport {
+    /// Sessions whose notes were sent to the provider and parsed.
+    pub sessions_processed: usize,
+    /// Sessions skipped because the token budget was exhausted.
+    pub sessions_skipped_budget: usize,
+    /// Sessions whose provider call failed or returned malformed output.
+    pub sessions_failed: usize,
+    /// Facts stored (or planned, in dry-run mode).
+    pub facts: Vec<DistilledFact>,
+    /// Conversation entries expired or deleted by the consume policy.
+    pub consumed: usize,
+}
+
+/// Provenance payload stored alongside each distilled fact.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Provenance {
+    /// Keys of the conversation entries the fact was distilled from.
+    pub source_keys: Vec<String>,
+    /// Timestamp of the oldest source entry (RFC 3339).
+    pub from: String,
+    /// Timestamp of the newest source entry (RFC 3339).
+    pub to: String,
+}
+
+/// Parse the provider's distillation response. Unlike consolidation there
+/// is no fallback: anything but the strict schema is an error so the
+/// source entries stay untouched.
+fn parse_facts(raw: &str) -> anyhow::Result<Vec<String>> {
+    #[derive(serde::Deserialize)]
+    struct FactList {
+        facts: Vec<String>,
+    }
+
+    let cleaned = raw
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let parsed: FactList = serde_json::from_str(cleaned)
+        .map_err(|e| anyhow::anyhow!("malformed distillation output: {e}"))?;
+    Ok(parsed
+        .facts
+        .into_iter()
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect())
+}
+
+/// Run one distillation pass over all Conversation-category entries.
+///
+/// With `dry_run` set, nothing is written or consumed; the returned report
+/// lists what would have been stored.
+pub async fn run(
+    provider: &dyn Provider,
+    model: &str,
+    memory: &dyn Memory,
+    config: &MemoryDistillConfig,
+    dry_run: bool,
+) -> anyhow::Result<DistillReport> {
+    let entries = memory
+        .list(Some(&MemoryCategory::Conversation), None)
+        .await?;
+
+    // Group by session so each sender/conversation distills independently.
+    let mut groups: BTreeMap<Option<String>, Vec<MemoryEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry(entry.session_id.clone())
+            .or_default()
+            .push(entry);
+    }
+
+    let mut report = DistillReport::default();
+    let mut spent_tokens = 0usize;
+
+    for (session_id, mut group) in groups {
+        if group.len() < config.min_entries {
+            continue;
+        }
+        group.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        let notes = group
+            .iter()
+            .map(|e| format!("- {}", e.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cost = approx_tokens(DISTILL_SYSTEM_PROMPT) + approx_tokens(&notes);
+        if config.max_tokens_per_run > 0 && spent_tokens + cost > config.max_tokens_per_run {
+            report.sessions_skipped_budget += 1;
+            continue;
+        }
+        spent_tokens += cost;
+
+        let raw = match provider
+            .chat_with_system(Some(DISTILL_SYSTEM_PROMPT), &notes, model, 0.1)
+            .await
+        {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::warn!("distillation provider call failed: {e}");
+                report.sessions_failed += 1;
+                continue;
+            }
+        };
+        let facts = match parse_facts(&raw) {
+            Ok(facts) => facts,
+            Err(e) => {
+                tracing::warn!("distillation skipped a session: {e}");
+                report.sessions_failed += 1;
+                continue;
+            }
+        };
+
+        report.sessions_processed += 1;
+        if facts.is_empty() {
+            continue;
+        }
+
+        let source_keys: Vec<String> = group.iter().map(|e| e.key.clone()).collect();
+        let from = group
+            .first()
+            .map(|e| e.timestamp.clone())
+            .unwrap_or_default();
+        let to = group
+            .last()
+            .map(|e| e.timestamp.clone())
+            .unwrap_or_default();
+
+        for fact in facts {
+            let key = format!("{FACT_KEY_PREFIX}{}", uuid::Uuid::new_v4());
+            if !dry_run {
+                let imp = super::importance::compute_importance(&fact, &MemoryCategory::Core);
+                memory
+                    .store_with_metadata(
+                        &key,
+                        &fact,
+                        MemoryCategory::Core,
+                        session_id.as_deref(),
+                        None,
+                        Some(imp),
+                    )
+                    .await?;
+                let provenance = Provenance {
+                    source_keys: source_keys.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                };
+                memory
+                    .store(
+                        &format!("{PROVENANCE_KEY_PREFIX}{key}"),
+                        &serde_json::to_string(&provenance)?,
+                        MemoryCategory::Custom(PROVENANCE_CATEGORY.into()),
+                        session_id.as_deref(),
+                    )
+                    .await?;
+            }
+            report.facts.push(DistilledFact {
+                key,
+                content: fact,
+                session_id: session_id.clone(),
+                source_keys: source_keys.clone(),
+            });
+        }
+
+        // Consume the sources only after every fact for the session landed.
+        if !dry_run {
+            report.consumed += consume_sources(memory, config, &group).await?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Apply the configured consume policy to a session's source entries.
+async fn consume_sources(
+    memory: &dyn Memory,
+    config: &MemoryDistillConfig,
+    group: &[MemoryEntry],
+) -> anyhow::Result<usize> {
+    match config.consume {
+        DistillConsumePolicy::Keep => Ok(0),
+        DistillConsumePolicy::Delete => {
+            let mut consumed = 0;
+            for entry in group {
+                if memory.forget(&entry.key).await? {
+                    consumed += 1;
+                }
+            }
+            Ok(consumed)
+        }
+        DistillConsumePolicy::Expire => {
+            let Some(ttl) = super::parse_ttl(&config.expire_after)? else {
+                // "never" (or equivalent) disables the expiry entirely.
+                return Ok(0);
+            };
+            let expires_at = (chrono::Local::now() + ttl).to_rfc3339();
+            let mut consumed = 0;
+            for entry in group {
+                memory
+                    .store_with_ttl(
+                        &entry.key,
+                        &entry.content,
+                        entry.category.clone(),
+                        entry.session_id.as_deref(),
+                        Some(&expires_at),
+                    )
+                    .await?;
+                consumed += 1;
+            }
+            Ok(consumed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockProvider {
+        response: &'static str,
+        calls: AtomicUsize,
+    }
+
+    impl MockProvider {
+        fn new(response: &'static str) -> Self {
+            Self {
+                response,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for MockProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.response.to_string())
+        }
+    }
+
+    async fn seed_session(mem: &SqliteMemory, session: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        for (i, content) in [
+            "User mentioned they work at a fintech startup",
+            "User said they prefer Rust for backend services",
+            "User asked about sqlite connection pooling",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let key = format!("{session}_msg_{i}");
+            mem.store(&key, content, MemoryCategory::Conversation, Some(session))
+                .await
+                .unwrap();
+            keys.push(key);
+        }
+        keys
+    }
+
+    fn test_config() -> MemoryDistillConfig {
+        MemoryDistillConfig::default()
+    }
+
+    #[tokio::test]
+    async fn distill_stores_facts_with_provenance() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let source_keys = seed_session(&mem, "s1").await;
+
+        let provider = MockProvider::new(r#"{"facts": ["User prefers Rust for backend work."]}"#);
+        let report = run(&provider, "test-model", &mem, &test_config(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.sessions_processed, 1);
+        assert_eq!(report.facts.len(), 1);
+        let fact = &report.facts[0];
+        assert_eq!(fact.content, "User prefers Rust for backend work.");
+        assert_eq!(fact.source_keys, source_keys);
+
+        let stored = mem.get(&fact.key).await.unwrap().unwrap();
+        assert_eq!(stored.category, MemoryCategory::Core);
+
+        // Provenance record links the fact back to its sources.
+        let prov_entry = mem
+            .get(&format!("{PROVENANCE_KEY_PREFIX}{}", fact.key))
+            .await
+            .unwrap()
+            .unwrap();
+        let provenance: Provenance = serde_json::from_str(&prov_entry.content).unwrap();
+        assert_eq!(provenance.source_keys, source_keys);
+        assert!(provenance.from <= provenance.to);
+
+        // Default consume policy keeps the sources.
+        assert_eq!(report.consumed, 0);
+        for key in &source_keys {
+            assert!(mem.get(key).await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn distill_dry_run_stores_nothing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        seed_session(&mem, "s1").await;
+        let before = mem.count().await.unwrap();
+
+        let provider = MockProvider::new(r#"{"facts": ["User prefers Rust for backend work."]}"#);
+        let report = run(&provider, "test-model", &mem, &test_config(), true)
+            .await
+            .unwrap();
+
+        assert_eq!(report.facts.len(), 1, "dry run still reports planned facts");
+        assert_eq!(report.consumed, 0);
+        assert_eq!(mem.count().await.unwrap(), before, "dry run must not write");
+        assert!(mem.get(&report.facts[0].key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn distill_malformed_output_leaves_sources_untouched() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let source_keys = seed_session(&mem, "s1").await;
+        let before = mem.count().await.unwrap();
+
+        let provider = MockProvider::new("I'm sorry, I can't produce JSON today.");
+        let mut config = test_config();
+        config.consume = DistillConsumePolicy::Delete;
+        let report = run(&provider, "test-model", &mem, &config, false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.sessions_failed, 1);
+        assert!(report.facts.is_empty());
+        assert_eq!(report.consumed, 0);
+        assert_eq!(mem.count().await.unwrap(), before);
+        for key in &source_keys {
+            assert!(mem.get(key).await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn distill_consume_delete_removes_sources() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let source_keys = seed_session(&mem, "s1").await;
+
+        let provider = MockProvider::new(r#"{"facts": ["User works at a fintech startup."]}"#);
+        let mut config = test_config();
+        config.consume = DistillConsumePolicy::Delete;
+        let report = run(&provider, "test-model", &mem, &config, false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.consumed, source_keys.len());
+        for key in &source_keys {
+            assert!(mem.get(key).await.unwrap().is_none());
+        }
+        assert!(mem.get(&report.facts[0].key).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn distill_token_budget_skips_sessions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        seed_session(&mem, "s1").await;
+
+        let provider = MockProvider::new(r#"{"facts": []}"#);
+        let mut config = test_config();
+        config.max_tokens_per_run = 1;
+        let report = run(&provider, "test-model", &mem, &config, false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.sessions_skipped_budget, 1);
+        assert_eq!(report.sessions_processed, 0);
+        assert_eq!(
+            provider.calls.load(Ordering::SeqCst),
+            0,
+            "over-budget sessions must not reach the provider"
+        );
+    }
+
+    #[test]
+    fn parse_facts_strict_schema() {
+        assert_eq!(
+            parse_facts(r#"{"facts": ["a", " b ", ""]}"#).unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert_eq!(
+            parse_facts("```json\n{\"facts\": [\"wrapped\"]}\n```").unwrap(),
+            vec!["wrapped".to_string()]
+        );
+        assert!(parse_facts("no json here").is_err());
+        assert!(parse_facts(r#"{"unexpected": true}"#).is_err());
+    }
+}
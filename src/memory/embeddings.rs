@@ -1,5 +1,17 @@
 use async_trait::async_trait;
 
+/// Max texts sent per embedding API request; larger inputs are split into
+/// sequential batches transparently.
+const MAX_EMBED_BATCH: usize = 96;
+
+/// Max characters per input text; longer inputs are truncated at a char
+/// boundary before embedding so one oversized chunk can't fail the batch.
+const MAX_EMBED_INPUT_CHARS: usize = 32_000;
+
+/// Bounded retries for transient embedding API failures. Error classification
+/// is shared with the chat reliability layer (`providers::reliable`).
+const EMBED_RETRY_ATTEMPTS: u32 = 3;
+
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
 pub trait EmbeddingProvider: Send + Sync {
@@ -21,6 +33,77 @@ pub trait EmbeddingProvider: Send + Sync {
     }
 }
 
+// ── Shared batching / retry helpers ──────────────────────────
+
+/// Truncate an input at a char boundary to `MAX_EMBED_INPUT_CHARS`.
+fn clamp_input(text: &str) -> &str {
+    if text.len() <= MAX_EMBED_INPUT_CHARS {
+        return text;
+    }
+    let mut end = MAX_EMBED_INPUT_CHARS;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Split inputs into API-sized batches.
+fn split_batches<'a>(texts: &'a [&'a str]) -> std::slice::Chunks<'a, &'a str> {
+    texts.chunks(MAX_EMBED_BATCH)
+}
+
+/// Verify the backend returned vectors of the configured dimension.
+/// A mismatch silently poisons the vector index, so fail loudly instead.
+fn check_dimensions(provider: &str, dims: usize, vectors: &[Vec<f32>]) -> anyhow::Result<()> {
+    if dims == 0 {
+        return Ok(());
+    }
+    if let Some(bad) = vectors.iter().find(|v| v.len() != dims) {
+        anyhow::bail!(
+            "{provider} embedding dimension mismatch: model returned {} dimensions but config \
+             expects {dims}; update the configured dimensions and reindex existing memories",
+            bad.len()
+        );
+    }
+    Ok(())
+}
+
+/// POST an embedding request with bounded retries. Transient failures
+/// (5xx, 429, network) are retried with linear backoff; permanent client
+/// errors abort immediately via the reliability layer's classification.
+async fn post_embed_request(
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> anyhow::Result<serde_json::Value> {
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        let result: anyhow::Result<serde_json::Value> = async {
+            let resp = build().send().await?;
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let text = resp.text().await.unwrap_or_default();
+                anyhow::bail!("Embedding API error {status}: {text}");
+            }
+            Ok(resp.json().await?)
+        }
+        .await;
+
+        match result {
+            Ok(json) => return Ok(json),
+            Err(e) => {
+                if attempt >= EMBED_RETRY_ATTEMPTS
+                    || crate::providers::reliable::is_non_retryable(&e)
+                {
+                    return Err(e);
+                }
+                tracing::warn!("Embedding request failed (attempt {attempt}): {e}");
+                tokio::time::sleep(std::time::Duration::from_millis(500 * u64::from(attempt)))
+                    .await;
+            }
+        }
+    }
+}
+
 // ── Noop provider (keyword-only fallback) ────────────────────
 
 pub struct NoopEmbedding;
@@ -93,6 +176,32 @@ impl OpenAiEmbedding {
     }
 }
 
+/// Parse an OpenAI-style `/embeddings` response: `{"data": [{"embedding": [..]}]}`.
+fn parse_openai_embeddings(json: &serde_json::Value) -> anyhow::Result<Vec<Vec<f32>>> {
+    let data = json
+        .get("data")
+        .and_then(|d| d.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'data'"))?;
+
+    let mut embeddings = Vec::with_capacity(data.len());
+    for item in data {
+        let embedding = item
+            .get("embedding")
+            .and_then(|e| e.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let vec: Vec<f32> = embedding
+            .iter()
+            .filter_map(|v| v.as_f64().map(|f| f as f32))
+            .collect();
+
+        embeddings.push(vec);
+    }
+
+    Ok(embeddings)
+}
+
 #[async_trait]
 impl EmbeddingProvider for OpenAiEmbedding {
     fn name(&self) -> &str {
@@ -108,48 +217,229 @@ impl EmbeddingProvider for OpenAiEmbedding {
             return Ok(Vec::new());
         }
 
-        let body = serde_json::json!({
-            "model": self.model,
-            "input": texts,
-        });
+        let client = self.http_client();
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in split_batches(texts) {
+            let inputs: Vec<&str> = batch.iter().map(|t| clamp_input(t)).collect();
+            let body = serde_json::json!({
+                "model": self.model,
+                "input": inputs,
+            });
+
+            let json = post_embed_request(|| {
+                client
+                    .post(self.embeddings_url())
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+            })
+            .await?;
+
+            embeddings.extend(parse_openai_embeddings(&json)?);
+        }
+
+        check_dimensions(self.name(), self.dims, &embeddings)?;
+        Ok(embeddings)
+    }
+}
+
+// ── Gemini embedding provider ────────────────────────────────
+
+pub struct GeminiEmbedding {
+    api_key: String,
+    model: String,
+    dims: usize,
+}
+
+impl GeminiEmbedding {
+    pub fn new(api_key: &str, model: &str, dims: usize) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            model: model.trim_start_matches("models/").to_string(),
+            dims,
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("memory.embeddings")
+    }
+
+    fn batch_embed_url(&self) -> String {
+        format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents?key={}",
+            self.model, self.api_key
+        )
+    }
+
+    fn batch_body(&self, batch: &[&str]) -> serde_json::Value {
+        let requests: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|text| {
+                let mut request = serde_json::json!({
+                    "model": format!("models/{}", self.model),
+                    "content": { "parts": [{ "text": clamp_input(text) }] },
+                });
+                if self.dims > 0 {
+                    request["outputDimensionality"] = serde_json::json!(self.dims);
+                }
+                request
+            })
+            .collect();
+        serde_json::json!({ "requests": requests })
+    }
+}
+
+/// Parse a Gemini `batchEmbedContents` response: `{"embeddings": [{"values": [..]}]}`.
+fn parse_gemini_embeddings(json: &serde_json::Value) -> anyhow::Result<Vec<Vec<f32>>> {
+    let items = json
+        .get("embeddings")
+        .and_then(|e| e.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'embeddings'"))?;
+
+    let mut embeddings = Vec::with_capacity(items.len());
+    for item in items {
+        let values = item
+            .get("values")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let vec: Vec<f32> = values
+            .iter()
+            .filter_map(|v| v.as_f64().map(|f| f as f32))
+            .collect();
+
+        embeddings.push(vec);
+    }
+
+    Ok(embeddings)
+}
+
+#[async_trait]
+impl EmbeddingProvider for GeminiEmbedding {
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let resp = self
-            .http_client()
-            .post(self.embeddings_url())
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+        let client = self.http_client();
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in split_batches(texts) {
+            let body = self.batch_body(batch);
+            let json = post_embed_request(|| {
+                client
+                    .post(self.batch_embed_url())
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+            })
             .await?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Embedding API error {status}: {text}");
+            embeddings.extend(parse_gemini_embeddings(&json)?);
+        }
+
+        check_dimensions(self.name(), self.dims, &embeddings)?;
+        Ok(embeddings)
+    }
+}
+
+// ── Ollama embedding provider (local) ────────────────────────
+
+/// Local embeddings via Ollama's `/api/embed` endpoint. A llama.cpp server
+/// exposes the OpenAI-compatible `/v1/embeddings` route instead — point
+/// `custom:<url>` at it to use the OpenAI backend.
+pub struct OllamaEmbedding {
+    base_url: String,
+    model: String,
+    dims: usize,
+}
+
+impl OllamaEmbedding {
+    pub fn new(base_url: &str, model: &str, dims: usize) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            dims,
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("memory.embeddings")
+    }
+
+    fn embed_url(&self) -> String {
+        format!("{}/api/embed", self.base_url)
+    }
+}
+
+/// Parse an Ollama `/api/embed` response: `{"embeddings": [[..], [..]]}`.
+fn parse_ollama_embeddings(json: &serde_json::Value) -> anyhow::Result<Vec<Vec<f32>>> {
+    let items = json
+        .get("embeddings")
+        .and_then(|e| e.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'embeddings'"))?;
+
+    let mut embeddings = Vec::with_capacity(items.len());
+    for item in items {
+        let values = item
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let vec: Vec<f32> = values
+            .iter()
+            .filter_map(|v| v.as_f64().map(|f| f as f32))
+            .collect();
+
+        embeddings.push(vec);
+    }
+
+    Ok(embeddings)
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbedding {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
         }
 
-        let json: serde_json::Value = resp.json().await?;
-        let data = json
-            .get("data")
-            .and_then(|d| d.as_array())
-            .ok_or_else(|| anyhow::anyhow!("Invalid embedding response: missing 'data'"))?;
-
-        let mut embeddings = Vec::with_capacity(data.len());
-        for item in data {
-            let embedding = item
-                .get("embedding")
-                .and_then(|e| e.as_array())
-                .ok_or_else(|| anyhow::anyhow!("Invalid embedding item"))?;
-
-            #[allow(clippy::cast_possible_truncation)]
-            let vec: Vec<f32> = embedding
-                .iter()
-                .filter_map(|v| v.as_f64().map(|f| f as f32))
-                .collect();
-
-            embeddings.push(vec);
+        let client = self.http_client();
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in split_batches(texts) {
+            let inputs: Vec<&str> = batch.iter().map(|t| clamp_input(t)).collect();
+            let body = serde_json::json!({
+                "model": self.model,
+                "input": inputs,
+            });
+
+            let json = post_embed_request(|| {
+                client
+                    .post(self.embed_url())
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+            })
+            .await?;
+
+            embeddings.extend(parse_ollama_embeddings(&json)?);
         }
 
+        check_dimensions(self.name(), self.dims, &embeddings)?;
         Ok(embeddings)
     }
 }
@@ -181,6 +471,15 @@ pub fn create_embedding_provider(
                 dims,
             ))
         }
+        "gemini" => {
+            let key = api_key.unwrap_or("");
+            Box::new(GeminiEmbedding::new(key, model, dims))
+        }
+        "ollama" => Box::new(OllamaEmbedding::new("http://localhost:11434", model, dims)),
+        name if name.starts_with("ollama:") => {
+            let base_url = name.strip_prefix("ollama:").unwrap_or("");
+            Box::new(OllamaEmbedding::new(base_url, model, dims))
+        }
         name if name.starts_with("custom:") => {
             let base_url = name.strip_prefix("custom:").unwrap_or("");
             let key = api_key.unwrap_or("");
@@ -233,6 +532,26 @@ mod tests {
         assert_eq!(p.dimensions(), 1536);
     }
 
+    #[test]
+    fn factory_gemini() {
+        let p = create_embedding_provider("gemini", Some("key"), "text-embedding-004", 768);
+        assert_eq!(p.name(), "gemini");
+        assert_eq!(p.dimensions(), 768);
+    }
+
+    #[test]
+    fn factory_ollama_default_url() {
+        let p = create_embedding_provider("ollama", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+        assert_eq!(p.dimensions(), 768);
+    }
+
+    #[test]
+    fn factory_ollama_custom_url() {
+        let p = create_embedding_provider("ollama:http://box:11434", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+    }
+
     #[test]
     fn factory_custom_url() {
         let p = create_embedding_provider("custom:http://localhost:1234", None, "model", 768);
@@ -355,4 +674,153 @@ mod tests {
             "https://my-api.example.com/api/v2/embeddings"
         );
     }
+
+    // ── Batching / input guards ──────────────────────────────────
+
+    #[test]
+    fn clamp_input_short_text_unchanged() {
+        assert_eq!(clamp_input("hello"), "hello");
+    }
+
+    #[test]
+    fn clamp_input_at_limit_unchanged() {
+        let text = "a".repeat(MAX_EMBED_INPUT_CHARS);
+        assert_eq!(clamp_input(&text).len(), MAX_EMBED_INPUT_CHARS);
+    }
+
+    #[test]
+    fn clamp_input_truncates_over_limit() {
+        let text = "a".repeat(MAX_EMBED_INPUT_CHARS + 1);
+        assert_eq!(clamp_input(&text).len(), MAX_EMBED_INPUT_CHARS);
+    }
+
+    #[test]
+    fn clamp_input_respects_char_boundary() {
+        // Multibyte char straddling the limit must not be split mid-sequence.
+        let mut text = "a".repeat(MAX_EMBED_INPUT_CHARS - 1);
+        text.push('é'); // 2 bytes, crosses the boundary
+        text.push_str("tail");
+        let clamped = clamp_input(&text);
+        assert_eq!(clamped.len(), MAX_EMBED_INPUT_CHARS - 1);
+        assert!(clamped.is_char_boundary(clamped.len()));
+    }
+
+    #[test]
+    fn split_batches_single_batch_at_limit() {
+        let texts: Vec<&str> = vec!["x"; MAX_EMBED_BATCH];
+        let batches: Vec<_> = split_batches(&texts).collect();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), MAX_EMBED_BATCH);
+    }
+
+    #[test]
+    fn split_batches_overflow_starts_new_batch() {
+        let texts: Vec<&str> = vec!["x"; MAX_EMBED_BATCH + 1];
+        let batches: Vec<_> = split_batches(&texts).collect();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), MAX_EMBED_BATCH);
+        assert_eq!(batches[1].len(), 1);
+    }
+
+    #[test]
+    fn check_dimensions_zero_dims_skips_guard() {
+        assert!(check_dimensions("openai", 0, &[vec![1.0, 2.0]]).is_ok());
+    }
+
+    #[test]
+    fn check_dimensions_mismatch_suggests_reindex() {
+        let err = check_dimensions("gemini", 768, &[vec![0.0; 1536]]).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("1536"));
+        assert!(msg.contains("768"));
+        assert!(msg.contains("reindex"));
+    }
+
+    // ── Response parsing fixtures ────────────────────────────────
+
+    #[test]
+    fn parse_openai_response_fixture() {
+        let json = serde_json::json!({
+            "data": [
+                { "embedding": [0.1, 0.2, 0.3] },
+                { "embedding": [0.4, 0.5, 0.6] },
+            ],
+            "model": "text-embedding-3-small",
+        });
+        let vecs = parse_openai_embeddings(&json).unwrap();
+        assert_eq!(vecs.len(), 2);
+        assert_eq!(vecs[0].len(), 3);
+        assert!((vecs[1][2] - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parse_openai_response_missing_data_errors() {
+        let json = serde_json::json!({ "error": { "message": "bad key" } });
+        assert!(parse_openai_embeddings(&json).is_err());
+    }
+
+    #[test]
+    fn parse_gemini_response_fixture() {
+        let json = serde_json::json!({
+            "embeddings": [
+                { "values": [0.1, 0.2] },
+                { "values": [0.3, 0.4] },
+            ],
+        });
+        let vecs = parse_gemini_embeddings(&json).unwrap();
+        assert_eq!(vecs.len(), 2);
+        assert!((vecs[0][1] - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parse_gemini_response_missing_values_errors() {
+        let json = serde_json::json!({ "embeddings": [{ "vector": [0.1] }] });
+        assert!(parse_gemini_embeddings(&json).is_err());
+    }
+
+    #[test]
+    fn parse_ollama_response_fixture() {
+        let json = serde_json::json!({
+            "model": "nomic-embed-text",
+            "embeddings": [[0.1, 0.2], [0.3, 0.4]],
+        });
+        let vecs = parse_ollama_embeddings(&json).unwrap();
+        assert_eq!(vecs.len(), 2);
+        assert!((vecs[1][0] - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parse_ollama_response_non_array_item_errors() {
+        let json = serde_json::json!({ "embeddings": [{ "values": [0.1] }] });
+        assert!(parse_ollama_embeddings(&json).is_err());
+    }
+
+    #[test]
+    fn gemini_batch_body_includes_output_dimensionality() {
+        let p = GeminiEmbedding::new("key", "text-embedding-004", 768);
+        let body = p.batch_body(&["hello"]);
+        let requests = body["requests"].as_array().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["model"], "models/text-embedding-004");
+        assert_eq!(requests[0]["outputDimensionality"], 768);
+    }
+
+    #[test]
+    fn gemini_batch_body_omits_dimensionality_when_unset() {
+        let p = GeminiEmbedding::new("key", "text-embedding-004", 0);
+        let body = p.batch_body(&["hello"]);
+        assert!(body["requests"][0].get("outputDimensionality").is_none());
+    }
+
+    #[test]
+    fn gemini_model_prefix_stripped() {
+        let p = GeminiEmbedding::new("key", "models/text-embedding-004", 768);
+        assert!(p.batch_embed_url().contains("/models/text-embedding-004:"));
+    }
+
+    #[test]
+    fn ollama_embed_url() {
+        let p = OllamaEmbedding::new("http://localhost:11434/", "nomic-embed-text", 768);
+        assert_eq!(p.embed_url(), "http://localhost:11434/api/embed");
+    }
 }
@@ -18,6 +18,8 @@ struct HygieneReport {
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    #[serde(default)]
+    pruned_expired_rows: u64,
 }
 
 impl HygieneReport {
@@ -27,6 +29,7 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.pruned_expired_rows
     }
 }
 
@@ -64,6 +67,7 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         purged_memory_archives: purge_memory_archives(workspace_dir, config.purge_after_days)?,
         purged_session_archives: purge_session_archives(workspace_dir, config.purge_after_days)?,
         pruned_conversation_rows: prune_conversation_rows(workspace_dir, conversation_retention)?,
+        pruned_expired_rows: prune_expired_rows(workspace_dir)?,
     };
 
     // Prune audit entries if audit is enabled.
@@ -77,12 +81,13 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} pruned_expired_rows={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.pruned_expired_rows,
         );
     }
 
@@ -330,6 +335,35 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+/// Physically delete sqlite rows whose per-entry TTL has elapsed.
+/// Expired entries are already hidden from recall; this reclaims the space.
+fn prune_expired_rows(workspace_dir: &Path) -> Result<u64> {
+    let db_path = workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        return Ok(0);
+    }
+
+    let conn = Connection::open(db_path)?;
+    // Use WAL so hygiene pruning doesn't block agent reads
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+
+    // Databases created before the TTL migration lack the expires_at column.
+    let schema_sql: String = conn
+        .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
+        .query_row([], |row| row.get::<_, String>(0))?;
+    if !schema_sql.contains("expires_at") {
+        return Ok(0);
+    }
+
+    let now = Local::now().to_rfc3339();
+    let affected = conn.execute(
+        "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+        params![now],
+    )?;
+
+    Ok(u64::try_from(affected).unwrap_or(0))
+}
+
 fn prune_audit_entries(workspace_dir: &Path, retention_days: u32) -> Result<()> {
     if retention_days == 0 {
         return Ok(());
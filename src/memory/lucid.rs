@@ -229,6 +229,8 @@ impl LucidMemory {
                 namespace: "default".into(),
                 importance: None,
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             });
         }
 
@@ -1,22 +1,36 @@
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{Memory, MemoryCategory, MemoryEntry, ScoreBreakdown};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use std::path::{Path, PathBuf};
 use tokio::fs;
+use tokio::sync::Mutex;
 
 /// Markdown-based memory — plain files as source of truth
 ///
 /// Layout:
-///   workspace/MEMORY.md          — curated long-term memory (core)
-///   workspace/memory/YYYY-MM-DD.md — daily logs (append-only)
+///   workspace/memory/YYYY-MM-DD.md — daily logs, one YAML frontmatter
+///   block per entry carrying key/category/session/timestamp metadata
+///
+/// The legacy layout (workspace/MEMORY.md plus bullet-list daily files) is
+/// still readable; `zeroclaw memory migrate-markdown` converts it in place.
 pub struct MarkdownMemory {
     workspace_dir: PathBuf,
+    /// Serialises appends so concurrent stores never interleave within a file.
+    write_lock: Mutex<()>,
+}
+
+/// Outcome of converting a legacy markdown layout to the frontmatter layout.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MarkdownMigrationReport {
+    pub files_converted: usize,
+    pub entries_migrated: usize,
 }
 
 impl MarkdownMemory {
     pub fn new(workspace_dir: &Path) -> Self {
         Self {
             workspace_dir: workspace_dir.to_path_buf(),
+            write_lock: Mutex::new(()),
         }
     }
 
@@ -24,6 +38,7 @@ impl MarkdownMemory {
         self.workspace_dir.join("memory")
     }
 
+    /// Legacy curated long-term memory file (pre-frontmatter layout).
     fn core_path(&self) -> PathBuf {
         self.workspace_dir.join("MEMORY.md")
     }
@@ -38,9 +53,13 @@ impl MarkdownMemory {
         Ok(())
     }
 
-    async fn append_to_file(&self, path: &Path, content: &str) -> anyhow::Result<()> {
+    async fn append_to_file(&self, path: &Path, block: &str) -> anyhow::Result<()> {
         self.ensure_dirs().await?;
 
+        // Read-modify-write under the lock so concurrent stores never
+        // interleave their blocks within a file.
+        let _guard = self.write_lock.lock().await;
+
         let existing = if path.exists() {
             fs::read_to_string(path).await.unwrap_or_default()
         } else {
@@ -48,22 +67,114 @@ impl MarkdownMemory {
         };
 
         let updated = if existing.is_empty() {
-            let header = if path == self.core_path() {
-                "# Long-Term Memory\n\n"
-            } else {
-                let date = Local::now().format("%Y-%m-%d").to_string();
-                &format!("# Daily Log — {date}\n\n")
-            };
-            format!("{header}{content}\n")
+            let date = Local::now().format("%Y-%m-%d").to_string();
+            format!("# Memory Log — {date}\n\n{block}")
         } else {
-            format!("{existing}\n{content}\n")
+            format!("{existing}\n{block}")
         };
 
         fs::write(path, updated).await?;
         Ok(())
     }
 
-    fn parse_entries_from_file(
+    /// Render one entry as a frontmatter block followed by its content.
+    fn render_entry_block(
+        key: &str,
+        content: &str,
+        category: &MemoryCategory,
+        session_id: Option<&str>,
+        timestamp: &str,
+    ) -> String {
+        let session_line = session_id
+            .map(|sid| format!("session: {sid}\n"))
+            .unwrap_or_default();
+        format!(
+            "---\nkey: {key}\ncategory: {category}\n{session_line}timestamp: {timestamp}\n---\n{content}\n"
+        )
+    }
+
+    /// Whether a frontmatter block opens at `lines[i]` (a `---` fence
+    /// immediately followed by a `key:` field). Content lines that happen
+    /// to be `---` fail the lookahead and stay part of the current entry.
+    fn is_block_opener(lines: &[&str], i: usize) -> bool {
+        lines[i].trim() == "---" && lines.get(i + 1).is_some_and(|l| l.starts_with("key: "))
+    }
+
+    /// Whether a file uses the frontmatter layout (vs. legacy bullets).
+    fn is_frontmatter_format(content: &str) -> bool {
+        let lines: Vec<&str> = content.lines().collect();
+        (0..lines.len()).any(|i| Self::is_block_opener(&lines, i))
+    }
+
+    fn parse_category_value(s: &str) -> MemoryCategory {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "core" => MemoryCategory::Core,
+            "daily" => MemoryCategory::Daily,
+            "conversation" => MemoryCategory::Conversation,
+            other => MemoryCategory::Custom(other.to_string()),
+        }
+    }
+
+    /// Parse a frontmatter-layout file into entries.
+    fn parse_frontmatter_entries(content: &str) -> Vec<MemoryEntry> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut entries = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            if !Self::is_block_opener(&lines, i) {
+                i += 1;
+                continue;
+            }
+
+            i += 1; // skip opening fence
+            let mut key = None;
+            let mut category = MemoryCategory::Daily;
+            let mut session_id = None;
+            let mut timestamp = String::new();
+            while i < lines.len() && lines[i].trim() != "---" {
+                if let Some(v) = lines[i].strip_prefix("key: ") {
+                    key = Some(v.trim().to_string());
+                } else if let Some(v) = lines[i].strip_prefix("category: ") {
+                    category = Self::parse_category_value(v);
+                } else if let Some(v) = lines[i].strip_prefix("session: ") {
+                    session_id = Some(v.trim().to_string());
+                } else if let Some(v) = lines[i].strip_prefix("timestamp: ") {
+                    timestamp = v.trim().to_string();
+                }
+                i += 1;
+            }
+            i += 1; // skip closing fence
+
+            let mut body = Vec::new();
+            while i < lines.len() && !Self::is_block_opener(&lines, i) {
+                body.push(lines[i]);
+                i += 1;
+            }
+
+            if let Some(key) = key {
+                entries.push(MemoryEntry {
+                    id: key.clone(),
+                    key,
+                    content: body.join("\n").trim().to_string(),
+                    category,
+                    timestamp,
+                    session_id,
+                    score: None,
+                    namespace: "default".into(),
+                    importance: None,
+                    superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
+                });
+            }
+        }
+
+        entries
+    }
+
+    /// Parse a legacy bullet-list file (`- **key**: content` per line).
+    fn parse_legacy_entries(
         path: &Path,
         content: &str,
         category: &MemoryCategory,
@@ -83,10 +194,19 @@ impl MarkdownMemory {
             .map(|(i, line)| {
                 let trimmed = line.trim();
                 let clean = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+                // Recover the key from `**key**: content` bullets; fall back
+                // to a positional key for free-form lines.
+                let (key, content) = match clean
+                    .strip_prefix("**")
+                    .and_then(|rest| rest.split_once("**: "))
+                {
+                    Some((k, c)) => (k.to_string(), c.to_string()),
+                    None => (format!("{filename}:{i}"), clean.to_string()),
+                };
                 MemoryEntry {
                     id: format!("{filename}:{i}"),
-                    key: format!("{filename}:{i}"),
-                    content: clean.to_string(),
+                    key,
+                    content,
                     category: category.clone(),
                     timestamp: filename.to_string(),
                     session_id: None,
@@ -94,34 +214,59 @@ impl MarkdownMemory {
                     namespace: "default".into(),
                     importance: None,
                     superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
                 }
             })
             .collect()
     }
 
-    async fn read_all_entries(&self) -> anyhow::Result<Vec<MemoryEntry>> {
+    /// Read entries across all files, skipping daily files whose filename
+    /// date falls outside the optional `[since, until]` bounds.
+    async fn read_entries(
+        &self,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
         let mut entries = Vec::new();
 
-        // Read MEMORY.md (core)
+        // Legacy MEMORY.md (core) — always in scope, it carries no date.
         let core_path = self.core_path();
         if core_path.exists() {
             let content = fs::read_to_string(&core_path).await?;
-            entries.extend(Self::parse_entries_from_file(
+            entries.extend(Self::parse_legacy_entries(
                 &core_path,
                 &content,
                 &MemoryCategory::Core,
             ));
         }
 
-        // Read daily logs
         let mem_dir = self.memory_dir();
         if mem_dir.exists() {
             let mut dir = fs::read_dir(&mem_dir).await?;
             while let Some(entry) = dir.next_entry().await? {
                 let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("md") {
-                    let content = fs::read_to_string(&path).await?;
-                    entries.extend(Self::parse_entries_from_file(
+                if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                    continue;
+                }
+
+                // Date-bounded scan: skip files outside the window when the
+                // filename parses as a date; unparseable names stay in scope.
+                let file_date = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+                if let Some(date) = file_date {
+                    if since.is_some_and(|s| date < s) || until.is_some_and(|u| date > u) {
+                        continue;
+                    }
+                }
+
+                let content = fs::read_to_string(&path).await?;
+                if Self::is_frontmatter_format(&content) {
+                    entries.extend(Self::parse_frontmatter_entries(&content));
+                } else {
+                    entries.extend(Self::parse_legacy_entries(
                         &path,
                         &content,
                         &MemoryCategory::Daily,
@@ -133,6 +278,107 @@ impl MarkdownMemory {
         entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
         Ok(entries)
     }
+
+    /// Convert legacy files to the frontmatter layout: bullet-list daily
+    /// files are rewritten in place, and MEMORY.md entries move into
+    /// today's daily file (the original is kept as `MEMORY.md.bak`).
+    pub async fn migrate_legacy(&self) -> anyhow::Result<MarkdownMigrationReport> {
+        let mut report = MarkdownMigrationReport::default();
+
+        let mem_dir = self.memory_dir();
+        if mem_dir.exists() {
+            let mut dir = fs::read_dir(&mem_dir).await?;
+            while let Some(entry) = dir.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                    continue;
+                }
+                let content = fs::read_to_string(&path).await?;
+                if Self::is_frontmatter_format(&content) {
+                    continue;
+                }
+
+                let parsed = Self::parse_legacy_entries(&path, &content, &MemoryCategory::Daily);
+                if parsed.is_empty() {
+                    continue;
+                }
+
+                let date = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown");
+                let mut rewritten = format!("# Memory Log — {date}\n\n");
+                for e in &parsed {
+                    // Legacy entries only carried a date, not a time.
+                    let timestamp = format!("{date}T00:00:00Z");
+                    rewritten.push_str(&Self::render_entry_block(
+                        &e.key,
+                        &e.content,
+                        &e.category,
+                        None,
+                        &timestamp,
+                    ));
+                    rewritten.push('\n');
+                }
+
+                {
+                    let _guard = self.write_lock.lock().await;
+                    fs::write(&path, rewritten).await?;
+                }
+                report.files_converted += 1;
+                report.entries_migrated += parsed.len();
+            }
+        }
+
+        let core_path = self.core_path();
+        if core_path.exists() {
+            let content = fs::read_to_string(&core_path).await?;
+            let parsed = Self::parse_legacy_entries(&core_path, &content, &MemoryCategory::Core);
+            let timestamp = Local::now().to_rfc3339();
+            for e in &parsed {
+                let block =
+                    Self::render_entry_block(&e.key, &e.content, &e.category, None, &timestamp);
+                self.append_to_file(&self.daily_path(), &block).await?;
+            }
+            fs::rename(&core_path, self.workspace_dir.join("MEMORY.md.bak")).await?;
+            report.files_converted += 1;
+            report.entries_migrated += parsed.len();
+        }
+
+        Ok(report)
+    }
+
+    /// Sizes of all markdown memory files, for `memory stats`.
+    pub async fn file_sizes(&self) -> anyhow::Result<Vec<(String, u64)>> {
+        let mut sizes = Vec::new();
+
+        let core_path = self.core_path();
+        if core_path.exists() {
+            let meta = fs::metadata(&core_path).await?;
+            sizes.push(("MEMORY.md".to_string(), meta.len()));
+        }
+
+        let mem_dir = self.memory_dir();
+        if mem_dir.exists() {
+            let mut dir = fs::read_dir(&mem_dir).await?;
+            while let Some(entry) = dir.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                    continue;
+                }
+                let name = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                let meta = fs::metadata(&path).await?;
+                sizes.push((format!("memory/{name}"), meta.len()));
+            }
+        }
+
+        sizes.sort();
+        Ok(sizes)
+    }
 }
 
 #[async_trait]
@@ -146,14 +392,11 @@ impl Memory for MarkdownMemory {
         key: &str,
         content: &str,
         category: MemoryCategory,
-        _session_id: Option<&str>,
+        session_id: Option<&str>,
     ) -> anyhow::Result<()> {
-        let entry = format!("- **{key}**: {content}");
-        let path = match category {
-            MemoryCategory::Core => self.core_path(),
-            _ => self.daily_path(),
-        };
-        self.append_to_file(&path, &entry).await
+        let timestamp = Local::now().to_rfc3339();
+        let block = Self::render_entry_block(key, content, &category, session_id, &timestamp);
+        self.append_to_file(&self.daily_path(), &block).await
     }
 
     async fn recall(
@@ -178,7 +421,12 @@ impl Memory for MarkdownMemory {
             }
         }
 
-        let all = self.read_all_entries().await?;
+        let all = self
+            .read_entries(
+                since_dt.map(|dt| dt.date_naive()),
+                until_dt.map(|dt| dt.date_naive()),
+            )
+            .await?;
         let query_lower = query.to_lowercase();
         let keywords: Vec<&str> = query_lower.split_whitespace().collect();
 
@@ -212,6 +460,10 @@ impl Memory for MarkdownMemory {
                     #[allow(clippy::cast_precision_loss)]
                     let score = matched as f64 / keywords.len() as f64;
                     entry.score = Some(score);
+                    entry.score_breakdown = Some(ScoreBreakdown {
+                        lexical: Some(score),
+                        vector: None,
+                    });
                     Some(entry)
                 } else {
                     None
@@ -233,7 +485,7 @@ impl Memory for MarkdownMemory {
     }
 
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        let all = self.read_entries(None, None).await?;
         Ok(all
             .into_iter()
             .find(|e| e.key == key || e.content.contains(key)))
@@ -244,7 +496,7 @@ impl Memory for MarkdownMemory {
         category: Option<&MemoryCategory>,
         _session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        let all = self.read_entries(None, None).await?;
         match category {
             Some(cat) => Ok(all.into_iter().filter(|e| &e.category == cat).collect()),
             None => Ok(all),
@@ -258,7 +510,7 @@ impl Memory for MarkdownMemory {
     }
 
     async fn count(&self) -> anyhow::Result<usize> {
-        let all = self.read_all_entries().await?;
+        let all = self.read_entries(None, None).await?;
         Ok(all.len())
     }
 
@@ -296,7 +548,10 @@ mod tests {
         mem.store("pref", "User likes Rust", MemoryCategory::Core, None)
             .await
             .unwrap();
-        let content = fs::read_to_string(mem.core_path()).await.unwrap();
+        // Core entries land in the daily file with category metadata.
+        let content = fs::read_to_string(mem.daily_path()).await.unwrap();
+        assert!(content.contains("key: pref"));
+        assert!(content.contains("category: core"));
         assert!(content.contains("User likes Rust"));
     }
 
@@ -311,6 +566,138 @@ mod tests {
         assert!(content.contains("Finished tests"));
     }
 
+    #[tokio::test]
+    async fn markdown_frontmatter_round_trip() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store(
+            "pref",
+            "User likes Rust\nand safety",
+            MemoryCategory::Core,
+            Some("s1"),
+        )
+        .await
+        .unwrap();
+
+        let entries = mem.list(None, None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.key, "pref");
+        assert_eq!(entry.content, "User likes Rust\nand safety");
+        assert_eq!(entry.category, MemoryCategory::Core);
+        assert_eq!(entry.session_id.as_deref(), Some("s1"));
+        assert!(chrono::DateTime::parse_from_rfc3339(&entry.timestamp).is_ok());
+    }
+
+    #[tokio::test]
+    async fn markdown_cross_file_recall() {
+        let (_tmp, mem) = temp_workspace();
+        fs::create_dir_all(mem.memory_dir()).await.unwrap();
+
+        for (date, key) in [("2026-01-01", "old"), ("2026-06-01", "new")] {
+            let block = MarkdownMemory::render_entry_block(
+                key,
+                "Rust note",
+                &MemoryCategory::Daily,
+                None,
+                &format!("{date}T12:00:00Z"),
+            );
+            fs::write(mem.memory_dir().join(format!("{date}.md")), block)
+                .await
+                .unwrap();
+        }
+
+        let all = mem.recall("Rust", 10, None, None, None).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        // The date-bounded scan skips files outside the window.
+        let recent = mem
+            .recall("Rust", 10, None, Some("2026-03-01T00:00:00Z"), None)
+            .await
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].key, "new");
+    }
+
+    #[tokio::test]
+    async fn markdown_legacy_files_still_readable() {
+        let (_tmp, mem) = temp_workspace();
+        fs::create_dir_all(mem.memory_dir()).await.unwrap();
+        fs::write(
+            mem.core_path(),
+            "# Long-Term Memory\n\n- **lang**: prefers Rust\n",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            mem.memory_dir().join("2026-01-01.md"),
+            "# Daily Log — 2026-01-01\n\n- **note**: shipped release\n",
+        )
+        .await
+        .unwrap();
+
+        let entries = mem.list(None, None).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        let core = entries.iter().find(|e| e.key == "lang").unwrap();
+        assert_eq!(core.content, "prefers Rust");
+        assert_eq!(core.category, MemoryCategory::Core);
+        let daily = entries.iter().find(|e| e.key == "note").unwrap();
+        assert_eq!(daily.category, MemoryCategory::Daily);
+    }
+
+    #[tokio::test]
+    async fn markdown_migrate_legacy_converts_layout() {
+        let (tmp, mem) = temp_workspace();
+        fs::create_dir_all(mem.memory_dir()).await.unwrap();
+        fs::write(
+            mem.core_path(),
+            "# Long-Term Memory\n\n- **lang**: prefers Rust\n",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            mem.memory_dir().join("2026-01-01.md"),
+            "# Daily Log — 2026-01-01\n\n- **note**: shipped release\n",
+        )
+        .await
+        .unwrap();
+
+        let report = mem.migrate_legacy().await.unwrap();
+        assert_eq!(report.files_converted, 2);
+        assert_eq!(report.entries_migrated, 2);
+
+        // Legacy core file was renamed, daily file rewritten in place.
+        assert!(!mem.core_path().exists());
+        assert!(tmp.path().join("MEMORY.md.bak").exists());
+        let daily = fs::read_to_string(mem.memory_dir().join("2026-01-01.md"))
+            .await
+            .unwrap();
+        assert!(MarkdownMemory::is_frontmatter_format(&daily));
+
+        // Entries survive the migration with keys and categories intact.
+        let entries = mem.list(None, None).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        let core = entries.iter().find(|e| e.key == "lang").unwrap();
+        assert_eq!(core.content, "prefers Rust");
+        assert_eq!(core.category, MemoryCategory::Core);
+
+        // A second run is a no-op.
+        let report = mem.migrate_legacy().await.unwrap();
+        assert_eq!(report, MarkdownMigrationReport::default());
+    }
+
+    #[tokio::test]
+    async fn markdown_file_sizes_reports_each_file() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("a", "first", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let sizes = mem.file_sizes().await.unwrap();
+        assert_eq!(sizes.len(), 1);
+        assert!(sizes[0].0.starts_with("memory/"));
+        assert!(sizes[0].1 > 0);
+    }
+
     #[tokio::test]
     async fn markdown_recall_keyword() {
         let (_tmp, mem) = temp_workspace();
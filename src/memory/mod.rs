@@ -5,12 +5,15 @@ pub mod cli;
 pub mod conflict;
 pub mod consolidation;
 pub mod decay;
+pub mod dedup;
+pub mod distill;
 pub mod embeddings;
 pub mod hygiene;
 pub mod importance;
 pub mod knowledge_graph;
 pub mod lucid;
 pub mod markdown;
+pub mod namespacing;
 pub mod none;
 pub mod policy;
 pub mod qdrant;
@@ -45,7 +48,7 @@ pub use traits::Memory;
 #[allow(unused_imports)]
 pub use traits::{ExportFilter, MemoryCategory, MemoryEntry, ProceduralMessage};
 
-use crate::config::{EmbeddingRouteConfig, MemoryConfig, StorageProviderConfig};
+use crate::config::{EmbeddingRouteConfig, EmbeddingsConfig, MemoryConfig, StorageProviderConfig};
 use anyhow::Context;
 use std::path::Path;
 use std::sync::Arc;
@@ -114,6 +117,59 @@ pub fn should_skip_autosave_content(content: &str) -> bool {
         || lowered.contains("distilled_index_sig:")
 }
 
+/// Parse a TTL duration string from `[memory.ttl]`: a number followed by
+/// `d` (days), `h` (hours), `m` (minutes), or `s` (seconds), e.g. "30d".
+/// "never", "none", or "0" mean no expiry.
+pub fn parse_ttl(value: &str) -> anyhow::Result<Option<chrono::Duration>> {
+    let normalized = value.trim().to_ascii_lowercase();
+    if normalized.is_empty() || normalized == "never" || normalized == "none" || normalized == "0" {
+        return Ok(None);
+    }
+
+    let Some(unit) = normalized.chars().last() else {
+        return Ok(None);
+    };
+    let amount: i64 = normalized[..normalized.len() - unit.len_utf8()]
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid TTL '{value}': expected e.g. \"30d\" or \"never\""))?;
+    if amount < 0 {
+        anyhow::bail!("invalid TTL '{value}': duration must not be negative");
+    }
+    if amount == 0 {
+        return Ok(None);
+    }
+
+    let duration = match unit {
+        'd' => chrono::Duration::days(amount),
+        'h' => chrono::Duration::hours(amount),
+        'm' => chrono::Duration::minutes(amount),
+        's' => chrono::Duration::seconds(amount),
+        _ => anyhow::bail!(
+            "invalid TTL '{value}': unit must be one of d (days), h (hours), m (minutes), s (seconds)"
+        ),
+    };
+    Ok(Some(duration))
+}
+
+/// Resolve `[memory.ttl]` into per-category durations, skipping (and
+/// warning about) entries that fail to parse.
+fn ttl_defaults_from_config(
+    ttl: &std::collections::HashMap<String, String>,
+) -> std::collections::HashMap<String, chrono::Duration> {
+    let mut defaults = std::collections::HashMap::new();
+    for (category, value) in ttl {
+        match parse_ttl(value) {
+            Ok(Some(duration)) => {
+                defaults.insert(category.trim().to_ascii_lowercase(), duration);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("ignoring [memory.ttl] entry for '{category}': {e}"),
+        }
+    }
+    defaults
+}
+
 #[derive(Clone, PartialEq, Eq)]
 struct ResolvedEmbeddingConfig {
     provider: String,
@@ -139,6 +195,7 @@ fn embedding_provider_env_key(provider: &str) -> Option<String> {
     let env_var = match provider.trim() {
         "openai" => "OPENAI_API_KEY",
         "openrouter" => "OPENROUTER_API_KEY",
+        "gemini" => "GEMINI_API_KEY",
         "cohere" => "COHERE_API_KEY",
         _ => return None,
     };
@@ -150,9 +207,35 @@ fn embedding_provider_env_key(provider: &str) -> Option<String> {
 
 fn resolve_embedding_config(
     config: &MemoryConfig,
+    overrides: &EmbeddingsConfig,
     embedding_routes: &[EmbeddingRouteConfig],
     api_key: Option<&str>,
 ) -> ResolvedEmbeddingConfig {
+    // The global `[embeddings]` section takes precedence over the legacy
+    // `[memory]` embedding settings when set.
+    let base_provider = overrides
+        .provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| config.embedding_provider.trim());
+    let base_model = overrides
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| config.embedding_model.trim());
+    let base_dimensions = overrides
+        .dimensions
+        .filter(|dims| *dims > 0)
+        .unwrap_or(config.embedding_dimensions);
+    let override_api_key = overrides
+        .api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+
     let caller_api_key = api_key
         .map(str::trim)
         .filter(|value| !value.is_empty())
@@ -160,17 +243,17 @@ fn resolve_embedding_config(
     // Prefer a provider-specific env var over the caller-supplied key, which
     // may come from the default (chat) provider and differ from the embedding
     // provider (issue #3083: gemini key leaking to openai embeddings endpoint).
-    let fallback_api_key =
-        embedding_provider_env_key(config.embedding_provider.trim()).or(caller_api_key);
+    let fallback_api_key = override_api_key
+        .or_else(|| embedding_provider_env_key(base_provider))
+        .or(caller_api_key);
     let fallback = ResolvedEmbeddingConfig {
-        provider: config.embedding_provider.trim().to_string(),
-        model: config.embedding_model.trim().to_string(),
-        dimensions: config.embedding_dimensions,
+        provider: base_provider.to_string(),
+        model: base_model.to_string(),
+        dimensions: base_dimensions,
         api_key: fallback_api_key.clone(),
     };
 
-    let Some(hint) = config
-        .embedding_model
+    let Some(hint) = base_model
         .strip_prefix("hint:")
         .map(str::trim)
         .filter(|value| !value.is_empty())
@@ -191,7 +274,7 @@ fn resolve_embedding_config(
 
     let provider = route.provider.trim();
     let model = route.model.trim();
-    let dimensions = route.dimensions.unwrap_or(config.embedding_dimensions);
+    let dimensions = route.dimensions.unwrap_or(base_dimensions);
     if provider.is_empty() || model.is_empty() || dimensions == 0 {
         tracing::warn!(
             hint,
@@ -221,7 +304,14 @@ pub fn create_memory(
     workspace_dir: &Path,
     api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
-    create_memory_with_storage_and_routes(config, &[], None, workspace_dir, api_key)
+    create_memory_with_storage_and_routes(
+        config,
+        &[],
+        &EmbeddingsConfig::default(),
+        None,
+        workspace_dir,
+        api_key,
+    )
 }
 
 /// Factory: create memory with optional storage-provider override.
@@ -231,20 +321,29 @@ pub fn create_memory_with_storage(
     workspace_dir: &Path,
     api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
-    create_memory_with_storage_and_routes(config, &[], storage_provider, workspace_dir, api_key)
+    create_memory_with_storage_and_routes(
+        config,
+        &[],
+        &EmbeddingsConfig::default(),
+        storage_provider,
+        workspace_dir,
+        api_key,
+    )
 }
 
 /// Factory: create memory with optional storage-provider override and embedding routes.
 pub fn create_memory_with_storage_and_routes(
     config: &MemoryConfig,
     embedding_routes: &[EmbeddingRouteConfig],
+    embeddings: &EmbeddingsConfig,
     storage_provider: Option<&StorageProviderConfig>,
     workspace_dir: &Path,
     api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
     let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
     let backend_kind = classify_memory_backend(&backend_name);
-    let resolved_embedding = resolve_embedding_config(config, embedding_routes, api_key);
+    let resolved_embedding =
+        resolve_embedding_config(config, embeddings, embedding_routes, api_key);
 
     // Best-effort memory hygiene/retention pass (throttled by state file).
     if let Err(e) = hygiene::run_if_due(config, workspace_dir) {
@@ -299,17 +398,20 @@ pub fn create_memory_with_storage_and_routes(
                 resolved_embedding.dimensions,
             ));
 
+        let (vector_weight, keyword_weight) = config.recall_weights();
         #[allow(clippy::cast_possible_truncation)]
         let mem = SqliteMemory::with_embedder(
             workspace_dir,
             embedder,
-            config.vector_weight as f32,
-            config.keyword_weight as f32,
+            vector_weight as f32,
+            keyword_weight as f32,
             config.embedding_cache_size,
             config.sqlite_open_timeout_secs,
             config.search_mode.clone(),
         )?;
-        Ok(mem)
+        Ok(mem
+            .with_ttl_defaults(ttl_defaults_from_config(&config.ttl))
+            .with_dedup(&config.dedup))
     }
 
     if matches!(backend_kind, MemoryBackendKind::Qdrant) {
@@ -408,7 +510,7 @@ pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Opt
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{EmbeddingRouteConfig, StorageProviderConfig};
+    use crate::config::{EmbeddingRouteConfig, EmbeddingsConfig, StorageProviderConfig};
     use tempfile::TempDir;
 
     #[test]
@@ -422,6 +524,41 @@ mod tests {
         assert_eq!(mem.name(), "sqlite");
     }
 
+    #[test]
+    fn parse_ttl_supports_units_and_never() {
+        assert_eq!(parse_ttl("30d").unwrap(), Some(chrono::Duration::days(30)));
+        assert_eq!(parse_ttl("12h").unwrap(), Some(chrono::Duration::hours(12)));
+        assert_eq!(
+            parse_ttl(" 90M ").unwrap(),
+            Some(chrono::Duration::minutes(90))
+        );
+        assert_eq!(
+            parse_ttl("45s").unwrap(),
+            Some(chrono::Duration::seconds(45))
+        );
+        assert_eq!(parse_ttl("never").unwrap(), None);
+        assert_eq!(parse_ttl("0").unwrap(), None);
+        assert_eq!(parse_ttl("").unwrap(), None);
+        assert!(parse_ttl("30w").is_err());
+        assert!(parse_ttl("soon").is_err());
+    }
+
+    #[test]
+    fn ttl_defaults_skip_invalid_entries() {
+        let ttl = std::collections::HashMap::from([
+            ("conversation".to_string(), "30d".to_string()),
+            ("core".to_string(), "never".to_string()),
+            ("Daily".to_string(), "bogus".to_string()),
+        ]);
+        let defaults = ttl_defaults_from_config(&ttl);
+        assert_eq!(
+            defaults.get("conversation"),
+            Some(&chrono::Duration::days(30))
+        );
+        assert!(!defaults.contains_key("core"));
+        assert!(!defaults.contains_key("daily"));
+    }
+
     #[test]
     fn assistant_autosave_key_detection_matches_legacy_patterns() {
         assert!(is_assistant_autosave_key("assistant_resp"));
@@ -530,7 +667,8 @@ mod tests {
             ..MemoryConfig::default()
         };
 
-        let resolved = resolve_embedding_config(&cfg, &[], Some("base-key"));
+        let resolved =
+            resolve_embedding_config(&cfg, &EmbeddingsConfig::default(), &[], Some("base-key"));
         assert_eq!(
             resolved,
             ResolvedEmbeddingConfig {
@@ -542,6 +680,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_embedding_config_prefers_embeddings_section_over_memory() {
+        let cfg = MemoryConfig {
+            embedding_provider: "openai".into(),
+            embedding_model: "text-embedding-3-small".into(),
+            embedding_dimensions: 1536,
+            ..MemoryConfig::default()
+        };
+        let overrides = EmbeddingsConfig {
+            provider: Some("ollama".into()),
+            model: Some("nomic-embed-text".into()),
+            dimensions: Some(768),
+            api_key: None,
+        };
+
+        let resolved = resolve_embedding_config(&cfg, &overrides, &[], Some("base-key"));
+        assert_eq!(
+            resolved,
+            ResolvedEmbeddingConfig {
+                provider: "ollama".into(),
+                model: "nomic-embed-text".into(),
+                dimensions: 768,
+                api_key: Some("base-key".into()),
+            }
+        );
+    }
+
     #[test]
     fn resolve_embedding_config_uses_matching_route_with_api_key_override() {
         let cfg = MemoryConfig {
@@ -558,7 +723,12 @@ mod tests {
             api_key: Some("route-key".into()),
         }];
 
-        let resolved = resolve_embedding_config(&cfg, &routes, Some("base-key"));
+        let resolved = resolve_embedding_config(
+            &cfg,
+            &EmbeddingsConfig::default(),
+            &routes,
+            Some("base-key"),
+        );
         assert_eq!(
             resolved,
             ResolvedEmbeddingConfig {
@@ -579,7 +749,8 @@ mod tests {
             ..MemoryConfig::default()
         };
 
-        let resolved = resolve_embedding_config(&cfg, &[], Some("base-key"));
+        let resolved =
+            resolve_embedding_config(&cfg, &EmbeddingsConfig::default(), &[], Some("base-key"));
         assert_eq!(
             resolved,
             ResolvedEmbeddingConfig {
@@ -607,7 +778,12 @@ mod tests {
             api_key: None,
         }];
 
-        let resolved = resolve_embedding_config(&cfg, &routes, Some("base-key"));
+        let resolved = resolve_embedding_config(
+            &cfg,
+            &EmbeddingsConfig::default(),
+            &routes,
+            Some("base-key"),
+        );
         assert_eq!(
             resolved,
             ResolvedEmbeddingConfig {
@@ -640,7 +816,12 @@ mod tests {
         };
 
         // Simulate: caller passes the Gemini (default_provider) api key.
-        let resolved = resolve_embedding_config(&cfg, &[], Some("gemini-key-must-not-be-used"));
+        let resolved = resolve_embedding_config(
+            &cfg,
+            &EmbeddingsConfig::default(),
+            &[],
+            Some("gemini-key-must-not-be-used"),
+        );
 
         // Restore env.
         match prev {
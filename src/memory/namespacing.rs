@@ -0,0 +1,150 @@
+//! Per-channel and per-sender memory namespacing.
+//!
+//! With `[memory.namespacing]` enabled, channel auto-save stores entries
+//! under a namespace derived from the message origin ("channel:slack" or
+//! "sender:slack:alice"), and channel recall queries that namespace plus
+//! the global one — so memories captured on one surface stop surfacing in
+//! answers on another. Entries stored outside any channel namespace (CLI
+//! stores, pre-namespacing databases) live in the legacy "default"
+//! namespace, which reads as global.
+
+use crate::config::schema::{MemoryNamespacingConfig, NamespacingMode};
+
+/// Canonical name of the shared namespace every sender may read
+/// (subject to the allowlist).
+pub const GLOBAL_NAMESPACE: &str = "global";
+
+/// On-disk namespace of entries that predate namespacing (the schema
+/// default). Treated as an alias of [`GLOBAL_NAMESPACE`] on reads so
+/// existing databases keep working without a row rewrite.
+pub const LEGACY_GLOBAL_NAMESPACE: &str = "default";
+
+/// Lowercase a channel/sender identifier and collapse runs of
+/// non-identifier characters to a single dash so derived namespaces stay
+/// stable across cosmetic differences ("Slack #ops" vs "slack-ops").
+fn sanitize(part: &str) -> String {
+    let mut out = String::with_capacity(part.len());
+    let mut pending_dash = false;
+    for c in part.trim().to_lowercase().chars() {
+        if c.is_alphanumeric() || c == '.' || c == '@' || c == '_' {
+            if pending_dash && !out.is_empty() {
+                out.push('-');
+            }
+            pending_dash = false;
+            out.push(c);
+        } else {
+            pending_dash = true;
+        }
+    }
+    out
+}
+
+/// Namespace for entries produced by a message on `channel` from `sender`.
+/// `None` when namespacing is off — stores keep the backend default.
+#[must_use]
+pub fn derive(config: &MemoryNamespacingConfig, channel: &str, sender: &str) -> Option<String> {
+    match config.mode {
+        NamespacingMode::Off => None,
+        NamespacingMode::Channel => Some(format!("channel:{}", sanitize(channel))),
+        NamespacingMode::Sender => {
+            Some(format!("sender:{}:{}", sanitize(channel), sanitize(sender)))
+        }
+    }
+}
+
+/// Whether `namespace` may read global entries in addition to its own.
+/// An empty allowlist admits every namespace.
+#[must_use]
+pub fn may_read_global(config: &MemoryNamespacingConfig, namespace: &str) -> bool {
+    config.global_read_allowlist.is_empty()
+        || config
+            .global_read_allowlist
+            .iter()
+            .any(|ns| ns == namespace)
+}
+
+/// Namespaces a channel message should recall from: the derived namespace,
+/// plus the global namespace (and its legacy alias) when allowed.
+/// `None` when namespacing is off — recall stays unfiltered.
+#[must_use]
+pub fn read_set(
+    config: &MemoryNamespacingConfig,
+    channel: &str,
+    sender: &str,
+) -> Option<Vec<String>> {
+    let derived = derive(config, channel, sender)?;
+    let allowed_global = may_read_global(config, &derived);
+    let mut set = vec![derived];
+    if allowed_global {
+        set.push(GLOBAL_NAMESPACE.to_string());
+        set.push(LEGACY_GLOBAL_NAMESPACE.to_string());
+    }
+    Some(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: NamespacingMode, allowlist: &[&str]) -> MemoryNamespacingConfig {
+        MemoryNamespacingConfig {
+            mode,
+            global_read_allowlist: allowlist.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn derive_off_yields_none() {
+        assert_eq!(
+            derive(&config(NamespacingMode::Off, &[]), "slack", "alice"),
+            None
+        );
+    }
+
+    #[test]
+    fn derive_channel_and_sender_modes() {
+        assert_eq!(
+            derive(&config(NamespacingMode::Channel, &[]), "slack", "alice"),
+            Some("channel:slack".into())
+        );
+        assert_eq!(
+            derive(&config(NamespacingMode::Sender, &[]), "slack", "alice"),
+            Some("sender:slack:alice".into())
+        );
+    }
+
+    #[test]
+    fn derive_sanitizes_identifiers() {
+        assert_eq!(
+            derive(
+                &config(NamespacingMode::Sender, &[]),
+                "Slack #ops",
+                "Alice Smith"
+            ),
+            Some("sender:slack-ops:alice-smith".into())
+        );
+    }
+
+    #[test]
+    fn read_set_includes_global_and_legacy_alias() {
+        let set = read_set(&config(NamespacingMode::Sender, &[]), "slack", "alice").unwrap();
+        assert_eq!(
+            set,
+            vec![
+                "sender:slack:alice".to_string(),
+                "global".to_string(),
+                "default".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn read_set_honors_global_allowlist() {
+        let cfg = config(NamespacingMode::Sender, &["sender:slack:alice"]);
+        let allowed = read_set(&cfg, "slack", "alice").unwrap();
+        assert!(allowed.contains(&GLOBAL_NAMESPACE.to_string()));
+
+        let denied = read_set(&cfg, "telegram", "bob").unwrap();
+        assert_eq!(denied, vec!["sender:telegram:bob".to_string()]);
+    }
+}
@@ -111,7 +111,23 @@ impl QdrantMemory {
 
         match resp {
             Ok(r) if r.status().is_success() => {
-                // Collection exists
+                // Collection exists — verify its vector size matches the embedder
+                // before silently serving mismatched searches.
+                let info: serde_json::Value = r.json().await.unwrap_or_default();
+                let existing = info
+                    .pointer("/result/config/params/vectors/size")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|size| usize::try_from(size).ok());
+                if let Some(size) = existing {
+                    if size != dims {
+                        anyhow::bail!(
+                            "Qdrant collection '{}' stores {size}-dimensional vectors but the \
+                             configured embedder produces {dims}; restore the previous embedding \
+                             settings or reindex into a fresh collection",
+                            self.collection
+                        );
+                    }
+                }
                 return Ok(());
             }
             Ok(r) if r.status().as_u16() == 404 => {
@@ -376,6 +392,8 @@ impl Memory for QdrantMemory {
                     namespace: "default".into(),
                     importance: None,
                     superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
                 })
             })
             .collect();
@@ -443,6 +461,8 @@ impl Memory for QdrantMemory {
                 namespace: "default".into(),
                 importance: None,
                 superseded_by: None,
+                pinned: false,
+                score_breakdown: None,
             })
         });
 
@@ -523,6 +543,8 @@ impl Memory for QdrantMemory {
                     namespace: "default".into(),
                     importance: None,
                     superseded_by: None,
+                    pinned: false,
+                    score_breakdown: None,
                 })
             })
             .collect();
@@ -253,6 +253,8 @@ mod tests {
             namespace: "default".into(),
             importance: None,
             superseded_by: None,
+            pinned: false,
+            score_breakdown: None,
         };
         pipeline.store_in_cache(ck, vec![fake_entry]);
 
@@ -1,7 +1,8 @@
+use super::dedup;
 use super::embeddings::EmbeddingProvider;
-use super::traits::{ExportFilter, Memory, MemoryCategory, MemoryEntry};
+use super::traits::{ExportFilter, Memory, MemoryCategory, MemoryEntry, ScoreBreakdown};
 use super::vector;
-use crate::config::schema::SearchMode;
+use crate::config::schema::{DedupPolicy, MemoryDedupConfig, SearchMode};
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
@@ -34,6 +35,8 @@ pub struct SqliteMemory {
     keyword_weight: f32,
     cache_max: usize,
     search_mode: SearchMode,
+    ttl_by_category: std::collections::HashMap<String, chrono::Duration>,
+    dedup: Option<MemoryDedupConfig>,
 }
 
 impl SqliteMemory {
@@ -72,6 +75,8 @@ impl SqliteMemory {
             keyword_weight: 0.3,
             cache_max: 10_000,
             search_mode: SearchMode::default(),
+            ttl_by_category: std::collections::HashMap::new(),
+            dedup: None,
         })
     }
 
@@ -121,9 +126,33 @@ impl SqliteMemory {
             keyword_weight,
             cache_max,
             search_mode,
+            ttl_by_category: std::collections::HashMap::new(),
+            dedup: None,
         })
     }
 
+    /// Set category default TTLs applied to newly stored entries
+    /// (`[memory.ttl]` config). Entries in a listed category expire
+    /// that long after their last update unless an explicit expiry is given.
+    #[must_use]
+    pub fn with_ttl_defaults(
+        mut self,
+        ttl_by_category: std::collections::HashMap<String, chrono::Duration>,
+    ) -> Self {
+        self.ttl_by_category = ttl_by_category;
+        self
+    }
+
+    /// Enable near-duplicate detection on store (`[memory.dedup]` config).
+    /// A disabled config leaves the store path untouched.
+    #[must_use]
+    pub fn with_dedup(mut self, config: &MemoryDedupConfig) -> Self {
+        if config.enabled {
+            self.dedup = Some(config.clone());
+        }
+        self
+    }
+
     /// Open SQLite connection, optionally with a timeout (for locked/slow storage).
     fn open_connection(
         db_path: &Path,
@@ -192,6 +221,12 @@ impl SqliteMemory {
                 VALUES (new.rowid, new.key, new.content);
             END;
 
+            -- Backend metadata and counters (e.g. dedup statistics)
+            CREATE TABLE IF NOT EXISTS memory_meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
             -- Embedding cache with LRU eviction
             CREATE TABLE IF NOT EXISTS embedding_cache (
                 content_hash TEXT PRIMARY KEY,
@@ -232,6 +267,21 @@ impl SqliteMemory {
             conn.execute_batch("ALTER TABLE memories ADD COLUMN superseded_by TEXT;")?;
         }
 
+        // Migration: add pinned column (always-injected context entries)
+        if !schema_sql.contains("pinned") {
+            conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+
+        // Migration: add expires_at column (per-entry TTL)
+        if !schema_sql.contains("expires_at") {
+            conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN expires_at TEXT;
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires ON memories(expires_at);",
+            )?;
+        }
+
         Ok(())
     }
 
@@ -253,6 +303,20 @@ impl SqliteMemory {
         }
     }
 
+    /// Resolve the expiry timestamp for a new entry: an explicit value wins,
+    /// otherwise the category default TTL is applied (if configured).
+    fn resolve_expires_at(
+        &self,
+        category: &MemoryCategory,
+        explicit: Option<&str>,
+    ) -> Option<String> {
+        if let Some(ts) = explicit {
+            return Some(ts.to_string());
+        }
+        let ttl = self.ttl_by_category.get(&Self::category_to_str(category))?;
+        Some((Local::now() + *ttl).to_rfc3339())
+    }
+
     /// Deterministic content hash for embedding cache.
     /// Uses SHA-256 (truncated) instead of DefaultHasher, which is
     /// explicitly documented as unstable across Rust versions.
@@ -359,14 +423,16 @@ impl SqliteMemory {
                    FROM memories_fts f
                    JOIN memories m ON m.rowid = f.rowid
                    WHERE memories_fts MATCH ?1
+                     AND (m.expires_at IS NULL OR m.expires_at > ?3)
                    ORDER BY score
                    LIMIT ?2";
 
         let mut stmt = conn.prepare(sql)?;
         #[allow(clippy::cast_possible_wrap)]
         let limit_i64 = limit as i64;
+        let now = Local::now().to_rfc3339();
 
-        let rows = stmt.query_map(params![fts_query, limit_i64], |row| {
+        let rows = stmt.query_map(params![fts_query, limit_i64, now], |row| {
             let id: String = row.get(0)?;
             let score: f64 = row.get(1)?;
             // BM25 returns negative scores (lower = better), negate for ranking
@@ -392,9 +458,12 @@ impl SqliteMemory {
         category: Option<&str>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<(String, f32)>> {
-        let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL".to_string();
+        let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL \
+                       AND (expires_at IS NULL OR expires_at > ?1)"
+            .to_string();
         let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        let mut idx = 1;
+        param_values.push(Box::new(Local::now().to_rfc3339()));
+        let mut idx = 2;
 
         if let Some(cat) = category {
             let _ = write!(sql, " AND category = ?{idx}");
@@ -416,23 +485,48 @@ impl SqliteMemory {
         })?;
 
         let mut scored: Vec<(String, f32)> = Vec::new();
+        let mut mismatched_dims: Option<usize> = None;
         for row in rows {
             let (id, blob) = row?;
             let emb = vector::bytes_to_vec(&blob);
+            if !emb.is_empty() && emb.len() != query_embedding.len() {
+                mismatched_dims.get_or_insert(emb.len());
+                continue;
+            }
             let sim = vector::cosine_similarity(query_embedding, &emb);
             if sim > 0.0 {
                 scored.push((id, sim));
             }
         }
 
+        if let Some(stored_dims) = mismatched_dims {
+            tracing::warn!(
+                "Stored embeddings are {stored_dims}-dimensional but the current embedder \
+                 produces {} dimensions; skipped mismatched rows. Reindex memories to \
+                 rebuild embeddings with the new model.",
+                query_embedding.len()
+            );
+        }
+
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         scored.truncate(limit);
         Ok(scored)
     }
 
     /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
-    #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
+        self.reindex_with_progress(None).await
+    }
+
+    /// Reindex with an optional `(processed, total)` progress callback.
+    ///
+    /// Only entries with a NULL embedding are re-embedded, so an interrupted
+    /// run resumes where it left off; entries whose embedding fails are
+    /// skipped and picked up by the next run.
+    pub async fn reindex_with_progress(
+        &self,
+        progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    ) -> anyhow::Result<usize> {
         // Step 1: Rebuild FTS5
         {
             let conn = self.conn.clone();
@@ -461,8 +555,9 @@ impl SqliteMemory {
         })
         .await??;
 
+        let total = entries.len();
         let mut count = 0;
-        for (id, content) in &entries {
+        for (processed, (id, content)) in entries.iter().enumerate() {
             if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
                 let bytes = vector::vec_to_bytes(&emb);
                 let conn = self.conn.clone();
@@ -478,11 +573,225 @@ impl SqliteMemory {
                 .await??;
                 count += 1;
             }
+            if let Some(report) = progress {
+                report(processed + 1, total);
+            }
         }
 
         Ok(count)
     }
 
+    /// Shared insert path for `store` / `store_with_ttl`: explicit expiry
+    /// wins, otherwise the category default TTL is applied.
+    async fn store_with_expiry(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        explicit_expiry: Option<&str>,
+    ) -> anyhow::Result<()> {
+        // Compute embedding (async, before blocking work)
+        let embedding_bytes = self
+            .get_or_compute_embedding(content)
+            .await?
+            .map(|emb| vector::vec_to_bytes(&emb));
+
+        let expires_at = self.resolve_expires_at(&category, explicit_expiry);
+        let conn = self.conn.clone();
+        let key = key.to_string();
+        let content = content.to_string();
+        let sid = session_id.map(String::from);
+        let dedup = self.dedup.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            let now = Local::now().to_rfc3339();
+            let cat = Self::category_to_str(&category);
+            let id = Uuid::new_v4().to_string();
+
+            // Near-duplicate detection — exact-key overwrites keep current
+            // semantics, so only brand-new keys are checked.
+            if let Some(cfg) = &dedup {
+                let key_exists: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM memories WHERE key = ?1",
+                    [&key],
+                    |row| row.get(0),
+                )?;
+                if key_exists == 0
+                    && Self::apply_dedup_policy(
+                        &conn,
+                        cfg,
+                        &key,
+                        &content,
+                        &cat,
+                        embedding_bytes.as_deref(),
+                        expires_at.as_deref(),
+                        &now,
+                    )?
+                {
+                    return Ok(());
+                }
+            }
+
+            conn.execute(
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, namespace, importance, expires_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'default', 0.5, ?9)
+                 ON CONFLICT(key) DO UPDATE SET
+                    content = excluded.content,
+                    category = excluded.category,
+                    embedding = excluded.embedding,
+                    updated_at = excluded.updated_at,
+                    session_id = excluded.session_id,
+                    expires_at = excluded.expires_at",
+                params![id, key, content, cat, embedding_bytes, now, now, sid, expires_at],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    /// Look for a near-duplicate of `content` among recent entries in the
+    /// same category and apply the configured policy on a hit.
+    ///
+    /// Similarity is embedding cosine when both vectors exist, word-shingle
+    /// Jaccard otherwise. Returns `true` when the incoming entry was handled
+    /// (skipped, or folded into the existing one) and must not be inserted.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_dedup_policy(
+        conn: &Connection,
+        cfg: &MemoryDedupConfig,
+        key: &str,
+        content: &str,
+        category: &str,
+        new_embedding: Option<&[u8]>,
+        expires_at: Option<&str>,
+        now: &str,
+    ) -> anyhow::Result<bool> {
+        // Recent entries only: dedup guards against auto-save echo, not a
+        // full-corpus scan on every store.
+        const DEDUP_CANDIDATE_LIMIT: usize = 256;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, key, content, embedding FROM memories
+             WHERE category = ?1 AND superseded_by IS NULL
+               AND (expires_at IS NULL OR expires_at > ?2)
+             ORDER BY updated_at DESC LIMIT ?3",
+        )?;
+        #[allow(clippy::cast_possible_wrap)]
+        let rows = stmt.query_map(
+            params![category, now, DEDUP_CANDIDATE_LIMIT as i64],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<Vec<u8>>>(3)?,
+                ))
+            },
+        )?;
+
+        let mut best: Option<(f64, &'static str, String, String, String)> = None;
+        for row in rows {
+            let (cand_id, cand_key, cand_content, cand_embedding) = row?;
+            let (similarity, method) = match (new_embedding, cand_embedding.as_deref()) {
+                (Some(a), Some(b)) if !b.is_empty() => (
+                    f64::from(vector::cosine_similarity(
+                        &vector::bytes_to_vec(a),
+                        &vector::bytes_to_vec(b),
+                    )),
+                    "embedding_cosine",
+                ),
+                _ => (
+                    dedup::shingle_similarity(content, &cand_content),
+                    "shingle_jaccard",
+                ),
+            };
+            if best.as_ref().is_none_or(|(s, ..)| similarity > *s) {
+                best = Some((similarity, method, cand_id, cand_key, cand_content));
+            }
+        }
+
+        let Some((similarity, method, dup_id, dup_key, dup_content)) = best else {
+            return Ok(false);
+        };
+        if similarity < cfg.threshold {
+            return Ok(false);
+        }
+
+        match cfg.policy {
+            DedupPolicy::Skip => {}
+            DedupPolicy::Refresh => {
+                conn.execute(
+                    "UPDATE memories SET updated_at = ?1,
+                            expires_at = COALESCE(?2, expires_at)
+                     WHERE id = ?3",
+                    params![now, expires_at, dup_id],
+                )?;
+            }
+            DedupPolicy::Merge => {
+                // The merged text invalidates the stored vector; clear it so
+                // `memory reindex` backfills a fresh embedding.
+                let merged = dedup::merge_contents(&dup_content, content);
+                conn.execute(
+                    "UPDATE memories SET content = ?1, embedding = NULL,
+                            updated_at = ?2, expires_at = COALESCE(?3, expires_at)
+                     WHERE id = ?4",
+                    params![merged, now, expires_at, dup_id],
+                )?;
+            }
+        }
+
+        Self::bump_meta_counter(conn, "dedup_store_count")?;
+        let policy = dedup::policy_label(&cfg.policy);
+        tracing::debug!(
+            "memory dedup: {policy} '{key}' as near-duplicate of '{dup_key}' \
+             ({method} similarity {similarity:.3})"
+        );
+        crate::observability::runtime_trace::record_event(
+            "memory_dedup",
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            Some(&format!(
+                "{policy}: '{key}' is a near-duplicate of '{dup_key}'"
+            )),
+            serde_json::json!({
+                "policy": policy,
+                "incoming_key": key,
+                "existing_key": dup_key,
+                "similarity": similarity,
+                "method": method,
+            }),
+        );
+        Ok(true)
+    }
+
+    /// Increment a persistent counter in `memory_meta`.
+    fn bump_meta_counter(conn: &Connection, key: &str) -> anyhow::Result<()> {
+        conn.execute(
+            "INSERT INTO memory_meta (key, value) VALUES (?1, '1')
+             ON CONFLICT(key) DO UPDATE SET
+                value = CAST(CAST(value AS INTEGER) + 1 AS TEXT)",
+            [key],
+        )?;
+        Ok(())
+    }
+
+    /// Read a persistent counter from `memory_meta` (0 when unset).
+    fn meta_counter(conn: &Connection, key: &str) -> u64 {
+        conn.query_row(
+            "SELECT CAST(value AS INTEGER) FROM memory_meta WHERE key = ?1",
+            [key],
+            |row| row.get::<_, i64>(0),
+        )
+        .ok()
+        .and_then(|v| u64::try_from(v).ok())
+        .unwrap_or(0)
+    }
+
     /// List memories by time range (used when query is empty).
     async fn recall_by_time_only(
         &self,
@@ -502,11 +811,12 @@ impl SqliteMemory {
             let until_ref = until_owned.as_deref();
 
             let mut sql =
-                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by FROM memories \
-                           WHERE superseded_by IS NULL AND 1=1"
+                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories \
+                           WHERE superseded_by IS NULL AND (expires_at IS NULL OR expires_at > ?1)"
                     .to_string();
             let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-            let mut idx = 1;
+            param_values.push(Box::new(Local::now().to_rfc3339()));
+            let mut idx = 2;
 
             if let Some(sid) = sid.as_deref() {
                 let _ = write!(sql, " AND session_id = ?{idx}");
@@ -542,6 +852,8 @@ impl SqliteMemory {
                     namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
                     importance: row.get(7)?,
                     superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                    score_breakdown: None,
                 })
             })?;
 
@@ -568,37 +880,20 @@ impl Memory for SqliteMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
-        // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
-
-        let conn = self.conn.clone();
-        let key = key.to_string();
-        let content = content.to_string();
-        let sid = session_id.map(String::from);
-
-        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-            let conn = conn.lock();
-            let now = Local::now().to_rfc3339();
-            let cat = Self::category_to_str(&category);
-            let id = Uuid::new_v4().to_string();
+        self.store_with_expiry(key, content, category, session_id, None)
+            .await
+    }
 
-            conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, namespace, importance)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'default', 0.5)
-                 ON CONFLICT(key) DO UPDATE SET
-                    content = excluded.content,
-                    category = excluded.category,
-                    embedding = excluded.embedding,
-                    updated_at = excluded.updated_at,
-                    session_id = excluded.session_id",
-                params![id, key, content, cat, embedding_bytes, now, now, sid],
-            )?;
-            Ok(())
-        })
-        .await?
+    async fn store_with_ttl(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        expires_at: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.store_with_expiry(key, content, category, session_id, expires_at)
+            .await
     }
 
     async fn recall(
@@ -654,15 +949,23 @@ impl Memory for SqliteMemory {
                 Vec::new()
             };
 
-            // Merge results based on search mode
-            let merged = if vector_results.is_empty() {
+            // Merge results based on search mode. When only one method
+            // produced hits, its scores are normalized to [0, 1] (BM25 is
+            // unbounded) so `min_relevance_score` stays meaningful, and the
+            // top-k cut always happens after blending rather than per-method.
+            let mut merged = if vector_results.is_empty() {
+                let max_kw = keyword_results
+                    .iter()
+                    .map(|(_, s)| *s)
+                    .fold(0.0_f32, f32::max);
+                let max_kw = if max_kw < f32::EPSILON { 1.0 } else { max_kw };
                 keyword_results
                     .iter()
                     .map(|(id, score)| vector::ScoredResult {
                         id: id.clone(),
                         vector_score: None,
-                        keyword_score: Some(*score),
-                        final_score: *score,
+                        keyword_score: Some(score / max_kw),
+                        final_score: score / max_kw,
                     })
                     .collect::<Vec<_>>()
             } else if keyword_results.is_empty() {
@@ -684,6 +987,7 @@ impl Memory for SqliteMemory {
                     limit,
                 )
             };
+            merged.truncate(limit);
 
             // Fetch full entries for merged results in a single query
             // instead of N round-trips (N+1 pattern).
@@ -694,7 +998,7 @@ impl Memory for SqliteMemory {
                     .collect::<Vec<_>>()
                     .join(", ");
                 let sql = format!(
-                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by \
+                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned \
                      FROM memories WHERE superseded_by IS NULL AND id IN ({placeholders})"
                 );
                 let mut stmt = conn.prepare(&sql)?;
@@ -715,17 +1019,18 @@ impl Memory for SqliteMemory {
                         row.get::<_, Option<String>>(6)?,
                         row.get::<_, Option<f64>>(7)?,
                         row.get::<_, Option<String>>(8)?,
+                        row.get::<_, bool>(9)?,
                     ))
                 })?;
 
                 let mut entry_map = std::collections::HashMap::new();
                 for row in rows {
-                    let (id, key, content, cat, ts, sid, ns, imp, sup) = row?;
-                    entry_map.insert(id, (key, content, cat, ts, sid, ns, imp, sup));
+                    let (id, key, content, cat, ts, sid, ns, imp, sup, pinned) = row?;
+                    entry_map.insert(id, (key, content, cat, ts, sid, ns, imp, sup, pinned));
                 }
 
                 for scored in &merged {
-                    if let Some((key, content, cat, ts, sid, ns, imp, sup)) = entry_map.remove(&scored.id) {
+                    if let Some((key, content, cat, ts, sid, ns, imp, sup, pinned)) = entry_map.remove(&scored.id) {
                         if let Some(s) = since_ref {
                             if ts.as_str() < s {
                                 continue;
@@ -747,6 +1052,11 @@ impl Memory for SqliteMemory {
                             namespace: ns.unwrap_or_else(|| "default".into()),
                             importance: imp,
                             superseded_by: sup,
+                            pinned,
+                            score_breakdown: Some(ScoreBreakdown {
+                                lexical: scored.keyword_score.map(f64::from),
+                                vector: scored.vector_score.map(f64::from),
+                            }),
                         };
                         if let Some(filter_sid) = session_ref {
                             if entry.session_id.as_deref() != Some(filter_sid) {
@@ -785,8 +1095,13 @@ impl Memory for SqliteMemory {
                         let _ = write!(time_conditions, " AND created_at <= ?{param_idx}");
                         param_idx += 1;
                     }
+                    let _ = write!(
+                        time_conditions,
+                        " AND (expires_at IS NULL OR expires_at > ?{param_idx})"
+                    );
+                    param_idx += 1;
                     let sql = format!(
-                        "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by FROM memories
+                        "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories
                          WHERE superseded_by IS NULL AND ({where_clause}){time_conditions}
                          ORDER BY updated_at DESC
                          LIMIT ?{param_idx}"
@@ -803,6 +1118,7 @@ impl Memory for SqliteMemory {
                     if let Some(u) = until_ref {
                         param_values.push(Box::new(u.to_string()));
                     }
+                    param_values.push(Box::new(Local::now().to_rfc3339()));
                     #[allow(clippy::cast_possible_wrap)]
                     param_values.push(Box::new(limit as i64));
                     let params_ref: Vec<&dyn rusqlite::types::ToSql> =
@@ -819,6 +1135,8 @@ impl Memory for SqliteMemory {
                             namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
                             importance: row.get(7)?,
                             superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                            score_breakdown: None,
                         })
                     })?;
                     for row in rows {
@@ -846,7 +1164,7 @@ impl Memory for SqliteMemory {
         tokio::task::spawn_blocking(move || -> anyhow::Result<Option<MemoryEntry>> {
             let conn = conn.lock();
             let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by FROM memories WHERE key = ?1",
+                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories WHERE key = ?1",
             )?;
 
             let mut rows = stmt.query_map(params![key], |row| {
@@ -861,6 +1179,8 @@ impl Memory for SqliteMemory {
                     namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
                     importance: row.get(7)?,
                     superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                    score_breakdown: None,
                 })
             })?;
 
@@ -886,6 +1206,7 @@ impl Memory for SqliteMemory {
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
             let session_ref = sid.as_deref();
+            let now = Local::now().to_rfc3339();
             let mut results = Vec::new();
 
             let row_mapper = |row: &rusqlite::Row| -> rusqlite::Result<MemoryEntry> {
@@ -900,16 +1221,19 @@ impl Memory for SqliteMemory {
                     namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
                     importance: row.get(7)?,
                     superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                    score_breakdown: None,
                 })
             };
 
             if let Some(ref cat) = category {
                 let cat_str = Self::category_to_str(cat);
                 let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by FROM memories
-                     WHERE superseded_by IS NULL AND category = ?1 ORDER BY updated_at DESC LIMIT ?2",
+                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories
+                     WHERE superseded_by IS NULL AND (expires_at IS NULL OR expires_at > ?3)
+                       AND category = ?1 ORDER BY updated_at DESC LIMIT ?2",
                 )?;
-                let rows = stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT], row_mapper)?;
+                let rows = stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT, now], row_mapper)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -921,10 +1245,11 @@ impl Memory for SqliteMemory {
                 }
             } else {
                 let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by FROM memories
-                     WHERE superseded_by IS NULL ORDER BY updated_at DESC LIMIT ?1",
+                    "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories
+                     WHERE superseded_by IS NULL AND (expires_at IS NULL OR expires_at > ?2)
+                     ORDER BY updated_at DESC LIMIT ?1",
                 )?;
-                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT], row_mapper)?;
+                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT, now], row_mapper)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -985,6 +1310,108 @@ impl Memory for SqliteMemory {
         .await?
     }
 
+    async fn prune_expired(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+            let conn = conn.lock();
+            let now = Local::now().to_rfc3339();
+            let affected = conn.execute(
+                "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+                params![now],
+            )?;
+            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+            Ok(affected as usize)
+        })
+        .await?
+    }
+
+    async fn expired_count(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+            let conn = conn.lock();
+            let now = Local::now().to_rfc3339();
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+                params![now],
+                |row| row.get(0),
+            )?;
+            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+            Ok(count as usize)
+        })
+        .await?
+    }
+
+    async fn set_pinned(&self, key: &str, pinned: bool) -> anyhow::Result<bool> {
+        let conn = self.conn.clone();
+        let key = key.to_string();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
+            let conn = conn.lock();
+            let now = Local::now().to_rfc3339();
+            let affected = conn.execute(
+                "UPDATE memories SET pinned = ?1, updated_at = ?2 WHERE key = ?3",
+                params![pinned, now, key],
+            )?;
+            Ok(affected > 0)
+        })
+        .await?
+    }
+
+    async fn deduped_store_count(&self) -> anyhow::Result<u64> {
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<u64> {
+            let conn = conn.lock();
+            Ok(Self::meta_counter(&conn, "dedup_store_count"))
+        })
+        .await?
+    }
+
+    async fn reindex_embeddings(
+        &self,
+        progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    ) -> anyhow::Result<usize> {
+        self.reindex_with_progress(progress).await
+    }
+
+    async fn iter_all(&self, offset: usize, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
+            let conn = conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned FROM memories
+                 ORDER BY created_at, id LIMIT ?1 OFFSET ?2",
+            )?;
+            #[allow(clippy::cast_possible_wrap)]
+            let rows = stmt.query_map(params![limit as i64, offset as i64], |row| {
+                Ok(MemoryEntry {
+                    id: row.get(0)?,
+                    key: row.get(1)?,
+                    content: row.get(2)?,
+                    category: Self::str_to_category(&row.get::<_, String>(3)?),
+                    timestamp: row.get(4)?,
+                    session_id: row.get(5)?,
+                    score: None,
+                    namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
+                    importance: row.get(7)?,
+                    superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                    score_breakdown: None,
+                })
+            })?;
+
+            let mut results = Vec::new();
+            for row in rows {
+                results.push(row?);
+            }
+            Ok(results)
+        })
+        .await?
+    }
+
     async fn count(&self) -> anyhow::Result<usize> {
         let conn = self.conn.clone();
 
@@ -1012,7 +1439,7 @@ impl Memory for SqliteMemory {
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
             let mut sql =
-                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by \
+                "SELECT id, key, content, category, created_at, session_id, namespace, importance, superseded_by, pinned \
                  FROM memories WHERE 1=1"
                     .to_string();
             let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
@@ -1060,6 +1487,8 @@ impl Memory for SqliteMemory {
                     namespace: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "default".into()),
                     importance: row.get(7)?,
                     superseded_by: row.get(8)?,
+                    pinned: row.get(9)?,
+                    score_breakdown: None,
                 })
             })?;
 
@@ -1112,6 +1541,7 @@ impl Memory for SqliteMemory {
         let sid = session_id.map(String::from);
         let ns = namespace.unwrap_or("default").to_string();
         let imp = importance.unwrap_or(0.5);
+        let expires_at = self.resolve_expires_at(&category, None);
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let conn = conn.lock();
@@ -1120,8 +1550,8 @@ impl Memory for SqliteMemory {
             let id = Uuid::new_v4().to_string();
 
             conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, namespace, importance)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, namespace, importance, expires_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                  ON CONFLICT(key) DO UPDATE SET
                     content = excluded.content,
                     category = excluded.category,
@@ -1129,8 +1559,9 @@ impl Memory for SqliteMemory {
                     updated_at = excluded.updated_at,
                     session_id = excluded.session_id,
                     namespace = excluded.namespace,
-                    importance = excluded.importance",
-                params![id, key, content, cat, embedding_bytes, now, now, sid, ns, imp],
+                    importance = excluded.importance,
+                    expires_at = excluded.expires_at",
+                params![id, key, content, cat, embedding_bytes, now, now, sid, ns, imp, expires_at],
             )?;
             Ok(())
         })
@@ -2671,6 +3102,277 @@ mod tests {
         assert_eq!(mem.count().await.unwrap(), 1);
     }
 
+    // ── Reindex backfill tests ───────────────────────────────────
+
+    /// Deterministic 3-d embedder: vector derived from content bytes.
+    struct StubEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for StubEmbedding {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn dimensions(&self) -> usize {
+            3
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    let sum = t.bytes().map(f32::from).sum::<f32>();
+                    vec![sum, t.len() as f32, 1.0]
+                })
+                .collect())
+        }
+    }
+
+    /// Embedder that errors after a fixed number of calls, simulating an
+    /// interrupted reindex run.
+    struct FlakyEmbedding {
+        calls: std::sync::atomic::AtomicUsize,
+        fail_after: usize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FlakyEmbedding {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn dimensions(&self) -> usize {
+            3
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call >= self.fail_after {
+                anyhow::bail!("embedding provider went away");
+            }
+            StubEmbedding.embed(texts).await
+        }
+    }
+
+    fn reopen_with_embedder(
+        path: &std::path::Path,
+        embedder: Arc<dyn EmbeddingProvider>,
+    ) -> SqliteMemory {
+        SqliteMemory::with_embedder(path, embedder, 0.7, 0.3, 1000, None, SearchMode::default())
+            .unwrap()
+    }
+
+    fn null_embedding_count(mem: &SqliteMemory) -> i64 {
+        let conn = mem.conn.lock();
+        conn.query_row(
+            "SELECT COUNT(*) FROM memories WHERE embedding IS NULL",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reindex_backfills_missing_embeddings_with_progress() {
+        let tmp = TempDir::new().unwrap();
+
+        // Entries stored before embeddings were configured have NULL embeddings.
+        {
+            let mem = SqliteMemory::new(tmp.path()).unwrap();
+            for i in 0..3 {
+                mem.store(
+                    &format!("k{i}"),
+                    &format!("content {i}"),
+                    MemoryCategory::Core,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+            assert_eq!(null_embedding_count(&mem), 3);
+        }
+
+        let mem = reopen_with_embedder(tmp.path(), Arc::new(StubEmbedding));
+        let reports = Mutex::new(Vec::new());
+        let progress = |done: usize, total: usize| reports.lock().push((done, total));
+
+        let embedded = mem.reindex_embeddings(Some(&progress)).await.unwrap();
+        assert_eq!(embedded, 3);
+        assert_eq!(null_embedding_count(&mem), 0);
+        assert_eq!(reports.lock().as_slice(), &[(1, 3), (2, 3), (3, 3)]);
+
+        // A second run has nothing left to backfill.
+        assert_eq!(mem.reindex_embeddings(None).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn reindex_resumes_after_partial_backfill() {
+        let tmp = TempDir::new().unwrap();
+
+        {
+            let mem = SqliteMemory::new(tmp.path()).unwrap();
+            for i in 0..4 {
+                mem.store(
+                    &format!("k{i}"),
+                    &format!("content {i}"),
+                    MemoryCategory::Core,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+        }
+
+        // First run is interrupted after two entries; failures are skipped.
+        let flaky = Arc::new(FlakyEmbedding {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+            fail_after: 2,
+        });
+        let mem = reopen_with_embedder(tmp.path(), flaky);
+        assert_eq!(mem.reindex_embeddings(None).await.unwrap(), 2);
+        assert_eq!(null_embedding_count(&mem), 2);
+
+        // A second run with a healthy embedder only touches the remainder.
+        let mem = reopen_with_embedder(tmp.path(), Arc::new(StubEmbedding));
+        assert_eq!(mem.reindex_embeddings(None).await.unwrap(), 2);
+        assert_eq!(null_embedding_count(&mem), 0);
+    }
+
+    // ── TTL / expiry tests ───────────────────────────────────────
+
+    fn rfc3339_seconds_ago(secs: i64) -> String {
+        (Local::now() - chrono::Duration::seconds(secs)).to_rfc3339()
+    }
+
+    #[tokio::test]
+    async fn ttl_expired_entries_hidden_from_recall_and_list() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store_with_ttl(
+            "stale",
+            "expired greeting noise",
+            MemoryCategory::Conversation,
+            None,
+            Some(&rfc3339_seconds_ago(60)),
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "fresh",
+            "expired greeting keeper",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let recalled = mem.recall("greeting", 10, None, None, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].key, "fresh");
+
+        let listed = mem.list(None, None).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "fresh");
+
+        // Expired rows remain on disk until pruned.
+        assert_eq!(mem.count().await.unwrap(), 2);
+        assert_eq!(mem.expired_count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn ttl_category_default_applies_to_new_entries() {
+        let tmp = TempDir::new().unwrap();
+        // An already-elapsed default TTL makes conversation entries expire on store.
+        let ttl = std::collections::HashMap::from([(
+            "conversation".to_string(),
+            chrono::Duration::seconds(-1),
+        )]);
+        let mem = SqliteMemory::new(tmp.path())
+            .unwrap()
+            .with_ttl_defaults(ttl);
+
+        mem.store("chat", "small talk", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+        mem.store("fact", "small but important", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let listed = mem.list(None, None).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "fact", "core has no default TTL");
+        assert_eq!(mem.expired_count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn ttl_prune_expired_deletes_rows() {
+        let (_tmp, mem) = temp_sqlite();
+        for i in 0..2 {
+            mem.store_with_ttl(
+                &format!("old{i}"),
+                "dead entry",
+                MemoryCategory::Conversation,
+                None,
+                Some(&rfc3339_seconds_ago(60)),
+            )
+            .await
+            .unwrap();
+        }
+        mem.store("live", "still valid", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert_eq!(mem.prune_expired().await.unwrap(), 2);
+        assert_eq!(mem.count().await.unwrap(), 1);
+        assert_eq!(mem.expired_count().await.unwrap(), 0);
+        // Idempotent once nothing is expired.
+        assert_eq!(mem.prune_expired().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn ttl_migration_adds_expires_at_to_old_schema() {
+        let tmp = TempDir::new().unwrap();
+
+        // Fixture: a pre-TTL database without the expires_at column.
+        {
+            std::fs::create_dir_all(tmp.path().join("memory")).unwrap();
+            let conn = Connection::open(tmp.path().join("memory").join("brain.db")).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE memories (
+                    id          TEXT PRIMARY KEY,
+                    key         TEXT NOT NULL UNIQUE,
+                    content     TEXT NOT NULL,
+                    category    TEXT NOT NULL DEFAULT 'core',
+                    embedding   BLOB,
+                    created_at  TEXT NOT NULL,
+                    updated_at  TEXT NOT NULL
+                );
+                INSERT INTO memories (id, key, content, category, created_at, updated_at)
+                VALUES ('old-id', 'legacy', 'legacy content', 'core',
+                        '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        }
+
+        // Reopening runs the migration and leaves old rows intact (no expiry).
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let entry = mem.get("legacy").await.unwrap().unwrap();
+        assert_eq!(entry.content, "legacy content");
+        assert_eq!(mem.expired_count().await.unwrap(), 0);
+
+        // TTL writes work against the migrated schema.
+        mem.store_with_ttl(
+            "ephemeral",
+            "short lived",
+            MemoryCategory::Conversation,
+            None,
+            Some(&rfc3339_seconds_ago(1)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(mem.expired_count().await.unwrap(), 1);
+        assert_eq!(mem.prune_expired().await.unwrap(), 1);
+    }
+
     // ── SearchMode tests ─────────────────────────────────────────
 
     #[tokio::test]
@@ -2758,4 +3460,310 @@ mod tests {
         let results = mem.recall("Rust", 10, None, None, None).await.unwrap();
         assert!(!results.is_empty(), "Hybrid mode should find results");
     }
+
+    // ── Hybrid scoring tests ─────────────────────────────────────
+
+    /// Embedder with a fixed unit vector per marker word, so cosine
+    /// similarity between corpus entries and queries is fully controlled.
+    struct KeyedEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for KeyedEmbedding {
+        fn name(&self) -> &str {
+            "keyed"
+        }
+
+        fn dimensions(&self) -> usize {
+            3
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    if t.contains("checklist") {
+                        vec![0.0, 1.0, 0.0]
+                    } else if t.contains("gradually") {
+                        vec![0.9, 0.435_889_9, 0.0]
+                    } else if t.contains("blue-green") {
+                        vec![0.8, 0.0, 0.6]
+                    } else {
+                        // Queries land here: the reference direction.
+                        vec![1.0, 0.0, 0.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    /// Seed a corpus where keyword and vector search disagree on the best hit:
+    /// - "kw-distractor" stuffs the query terms but is semantically far
+    ///   (cosine 0.0 to the query);
+    /// - "vec-distractor" is semantically close (cosine 0.9) but shares no
+    ///   query terms;
+    /// - "target" has a moderate keyword match and cosine 0.8, so only
+    ///   blended scoring ranks it first.
+    ///
+    /// Filler entries keep the query terms rare enough that BM25 IDF stays
+    /// positive.
+    async fn seed_hybrid_corpus(mem: &SqliteMemory) {
+        let corpus = [
+            (
+                "kw-distractor",
+                "deployment deployment rollout rollout rollout checklist",
+            ),
+            ("vec-distractor", "ship new versions gradually"),
+            ("target", "deployment rollout uses blue-green strategy"),
+            ("filler-1", "grocery checklist for the weekend"),
+            ("filler-2", "travel packing checklist"),
+            ("filler-3", "meeting agenda checklist"),
+            ("filler-4", "onboarding checklist for new hires"),
+            ("filler-5", "weekly review checklist"),
+        ];
+        for (key, content) in corpus {
+            mem.store(key, content, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+    }
+
+    fn open_hybrid_corpus(path: &std::path::Path, search_mode: SearchMode) -> SqliteMemory {
+        // 0.6 vector / 0.4 lexical — the [memory.recall] defaults.
+        SqliteMemory::with_embedder(
+            path,
+            Arc::new(KeyedEmbedding),
+            0.6,
+            0.4,
+            1000,
+            None,
+            search_mode,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn hybrid_recall_beats_each_method_alone() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_hybrid_corpus(tmp.path(), SearchMode::Hybrid);
+        seed_hybrid_corpus(&mem).await;
+
+        // Keyword-only ranks the term-stuffed distractor first.
+        let bm25 = open_hybrid_corpus(tmp.path(), SearchMode::Bm25);
+        let results = bm25
+            .recall("deployment rollout", 3, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results[0].key, "kw-distractor");
+
+        // Vector-only ranks the semantically-near distractor first.
+        let embedding = open_hybrid_corpus(tmp.path(), SearchMode::Embedding);
+        let results = embedding
+            .recall("deployment rollout", 3, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results[0].key, "vec-distractor");
+
+        // Blended scoring surfaces the entry that is decent on both axes.
+        let results = mem
+            .recall("deployment rollout", 3, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results[0].key, "target");
+    }
+
+    #[tokio::test]
+    async fn hybrid_recall_scores_normalized_with_breakdown() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_hybrid_corpus(tmp.path(), SearchMode::Hybrid);
+        seed_hybrid_corpus(&mem).await;
+
+        let results = mem
+            .recall("deployment rollout", 3, None, None, None)
+            .await
+            .unwrap();
+        for entry in &results {
+            let score = entry.score.expect("recall entries carry a score");
+            assert!(
+                (0.0..=1.0).contains(&score),
+                "blended score {score} must stay comparable to min_relevance_score"
+            );
+        }
+        let breakdown = results[0]
+            .score_breakdown
+            .expect("hybrid hits expose component scores");
+        assert!(breakdown.lexical.is_some());
+        assert!(breakdown.vector.is_some());
+
+        // Keyword-only scores are normalized too (raw BM25 is unbounded).
+        let bm25 = open_hybrid_corpus(tmp.path(), SearchMode::Bm25);
+        let results = bm25
+            .recall("deployment rollout", 3, None, None, None)
+            .await
+            .unwrap();
+        let top = results[0].score.unwrap();
+        assert!(
+            (top - 1.0).abs() < 1e-6,
+            "top keyword hit normalizes to 1.0"
+        );
+    }
+
+    // ── Dedup tests ──────────────────────────────────────────────
+
+    fn open_with_dedup(
+        path: &std::path::Path,
+        policy: DedupPolicy,
+        threshold: f64,
+    ) -> SqliteMemory {
+        SqliteMemory::new(path)
+            .unwrap()
+            .with_dedup(&MemoryDedupConfig {
+                enabled: true,
+                policy,
+                threshold,
+            })
+    }
+
+    #[tokio::test]
+    async fn dedup_skip_drops_near_duplicate_keeps_borderline() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with_dedup(tmp.path(), DedupPolicy::Skip, 0.85);
+
+        mem.store(
+            "fav-1",
+            "My favorite color is blue",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        // Identical after normalization: similarity 1.0, dropped.
+        mem.store(
+            "fav-2",
+            "my favorite color is blue!",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        // One word changed: shingle similarity 0.5, below threshold, kept.
+        mem.store(
+            "fav-3",
+            "My favorite color is green",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mem.count().await.unwrap(), 2);
+        assert!(mem.get("fav-2").await.unwrap().is_none());
+        assert_eq!(mem.deduped_store_count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn dedup_refresh_updates_existing_timestamp() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with_dedup(tmp.path(), DedupPolicy::Refresh, 0.85);
+
+        mem.store(
+            "pref-1",
+            "User prefers dark mode",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        let backdated = "2000-01-01T00:00:00+00:00";
+        {
+            let conn = mem.conn.lock();
+            conn.execute("UPDATE memories SET updated_at = ?1", params![backdated])
+                .unwrap();
+        }
+
+        mem.store(
+            "pref-2",
+            "user prefers dark mode",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mem.count().await.unwrap(), 1);
+        let updated_at: String = {
+            let conn = mem.conn.lock();
+            conn.query_row(
+                "SELECT updated_at FROM memories WHERE key = 'pref-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(
+            updated_at.as_str() > backdated,
+            "refresh should bump the existing entry's timestamp"
+        );
+        let existing = mem.get("pref-1").await.unwrap().unwrap();
+        assert_eq!(existing.content, "User prefers dark mode");
+    }
+
+    #[tokio::test]
+    async fn dedup_merge_folds_content_into_existing() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with_dedup(tmp.path(), DedupPolicy::Merge, 0.85);
+
+        mem.store(
+            "style-1",
+            "User prefers tabs for indentation in Go projects",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        // Borderline: 6 of 7 shingles shared (~0.857), just above threshold.
+        mem.store(
+            "style-2",
+            "User prefers tabs for indentation in Go projects always",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mem.count().await.unwrap(), 1);
+        let existing = mem.get("style-1").await.unwrap().unwrap();
+        assert_eq!(
+            existing.content,
+            "User prefers tabs for indentation in Go projects always"
+        );
+        assert_eq!(mem.deduped_store_count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn dedup_exact_key_overwrite_keeps_current_semantics() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with_dedup(tmp.path(), DedupPolicy::Skip, 0.85);
+
+        mem.store(
+            "fav",
+            "My favorite color is blue",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "fav",
+            "my favorite color is blue!",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mem.count().await.unwrap(), 1);
+        let entry = mem.get("fav").await.unwrap().unwrap();
+        assert_eq!(entry.content, "my favorite color is blue!");
+        assert_eq!(mem.deduped_store_count().await.unwrap(), 0);
+    }
 }
@@ -44,6 +44,26 @@ pub struct MemoryEntry {
     /// If this entry was superseded by a newer conflicting entry.
     #[serde(default)]
     pub superseded_by: Option<String>,
+    /// Always inject into channel context, bypassing recall scoring.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Per-method score components from hybrid recall, for debugging ranking.
+    /// Only populated on entries returned by `recall`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ScoreBreakdown>,
+}
+
+/// Component scores behind a blended recall `score`.
+///
+/// Each component is normalized to [0, 1] before blending, so the values are
+/// comparable across queries and backends.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// Lexical score (BM25 on sqlite, token overlap elsewhere), when that
+    /// method produced a hit.
+    pub lexical: Option<f64>,
+    /// Vector-similarity score, when that method produced a hit.
+    pub vector: Option<f64>,
 }
 
 fn default_namespace() -> String {
@@ -161,6 +181,67 @@ pub trait Memory: Send + Sync {
         anyhow::bail!("purge_session not supported by this memory backend")
     }
 
+    /// Store a memory entry with an explicit expiry time (RFC 3339).
+    ///
+    /// `None` falls back to the backend's category default TTL, if any.
+    /// Default implementation delegates to `store()`; backends without
+    /// TTL support ignore the expiry.
+    async fn store_with_ttl(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        _expires_at: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.store(key, content, category, session_id).await
+    }
+
+    /// Physically delete entries whose TTL has elapsed.
+    /// Returns the number of deleted entries.
+    /// Default: returns unsupported error. Backends with TTL support override this.
+    async fn prune_expired(&self) -> anyhow::Result<usize> {
+        anyhow::bail!(
+            "prune_expired not supported by the '{}' memory backend",
+            self.name()
+        )
+    }
+
+    /// Count entries that are expired but not yet pruned.
+    /// Default: 0 for backends without TTL support.
+    async fn expired_count(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+
+    /// Pin or unpin an entry so channel context always injects it ahead of
+    /// scored recall. Returns whether an entry with that key was updated.
+    /// Default: false for backends without pin support.
+    async fn set_pinned(&self, _key: &str, _pinned: bool) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Number of stores suppressed or folded into an existing entry by
+    /// near-duplicate detection. Default: 0 for backends without dedup
+    /// support.
+    async fn deduped_store_count(&self) -> anyhow::Result<u64> {
+        Ok(0)
+    }
+
+    /// Backfill embeddings for entries that lack them, so vector recall
+    /// covers memories stored before embeddings were configured. `progress`
+    /// is invoked as `(processed, total)` after each embedded entry.
+    /// Returns the number of entries embedded.
+    /// Default: returns unsupported error. Backends with a vector index override this.
+    async fn reindex_embeddings(
+        &self,
+        _progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    ) -> anyhow::Result<usize> {
+        anyhow::bail!(
+            "reindex_embeddings not supported by the '{}' memory backend",
+            self.name()
+        )
+    }
+
     /// Count total memories
     async fn count(&self) -> anyhow::Result<usize>;
 
@@ -204,6 +285,31 @@ pub trait Memory: Send + Sync {
         Ok(filtered)
     }
 
+    /// Recall memories from any of the given namespaces.
+    ///
+    /// Used by channel recall to query "this sender's namespace plus global"
+    /// in one pass. Default implementation delegates to `recall()` and
+    /// filters by namespace, mirroring [`Memory::recall_namespaced`].
+    async fn recall_in_namespaces(
+        &self,
+        namespaces: &[String],
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self
+            .recall(query, limit * 2, session_id, since, until)
+            .await?;
+        let filtered: Vec<MemoryEntry> = entries
+            .into_iter()
+            .filter(|e| namespaces.contains(&e.namespace))
+            .take(limit)
+            .collect();
+        Ok(filtered)
+    }
+
     /// Bulk-export memories matching the given filter criteria.
     ///
     /// Intended for GDPR Art. 20 data portability. Returns entries ordered by
@@ -240,6 +346,21 @@ pub trait Memory: Send + Sync {
         Ok(filtered)
     }
 
+    /// Page through all entries ordered by creation time (ascending).
+    ///
+    /// Returns up to `limit` entries starting at `offset`; an empty page
+    /// signals the end. Used for streaming exports so large stores never
+    /// have to be loaded into memory at once. Includes expired-but-unpruned
+    /// entries so backups are complete.
+    ///
+    /// Default implementation delegates to `list()` and slices. Backends
+    /// with native pagination should override for efficiency.
+    async fn iter_all(&self, offset: usize, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        let mut entries = self.list(None, None).await?;
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(entries.into_iter().skip(offset).take(limit).collect())
+    }
+
     /// Store a memory entry with namespace and importance.
     ///
     /// Default implementation delegates to `store()`. Backends with native
@@ -305,6 +426,8 @@ mod tests {
             namespace: "default".into(),
             importance: Some(0.7),
             superseded_by: None,
+            pinned: false,
+            score_breakdown: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
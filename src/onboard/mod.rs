@@ -3,10 +3,11 @@ pub mod wizard;
 // Re-exported for CLI and external use
 #[allow(unused_imports)]
 pub use wizard::{
-    load_provider_defaults, refresh_models_quiet, resolve_default_model_for_provider,
+    cached_model_metadata, cached_model_prices, load_provider_defaults,
+    load_runtime_model_metadata, refresh_models_quiet, resolve_default_model_for_provider,
     run_channels_repair_wizard, run_models_list, run_models_refresh, run_models_refresh_all,
-    run_models_set, run_models_status, run_quick_setup, run_wizard, save_provider_default,
-    MODEL_CACHE_TTL_SECS,
+    run_models_set, run_models_status, run_quick_setup, run_wizard, runtime_context_window,
+    save_provider_default, ModelMetadata, MODEL_CACHE_TTL_SECS,
 };
 
 #[cfg(test)]
@@ -29,6 +30,10 @@ mod tests {
         assert_reexport_exists(load_provider_defaults);
         assert_reexport_exists(save_provider_default);
         assert_reexport_exists(resolve_default_model_for_provider);
+        assert_reexport_exists(cached_model_metadata);
+        assert_reexport_exists(cached_model_prices);
+        assert_reexport_exists(load_runtime_model_metadata);
+        assert_reexport_exists(runtime_context_window);
         let _ = MODEL_CACHE_TTL_SECS;
     }
 }
@@ -139,6 +139,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         default_provider: Some(provider),
         default_model: Some(model),
         model_providers: std::collections::HashMap::new(),
+        providers: crate::config::ProvidersConfig::default(),
         default_temperature: 0.7,
         provider_timeout_secs: 120,
         provider_max_tokens: None,
@@ -162,7 +163,10 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         pipeline: crate::config::PipelineConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
+        embeddings: crate::config::EmbeddingsConfig::default(),
         heartbeat: HeartbeatConfig::default(),
+        auth: crate::config::AuthConfig::default(),
+        token_refresh: crate::config::TokenRefreshConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
@@ -187,6 +191,8 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        rag_watch: crate::config::RagWatchConfig::default(),
+        rag: crate::config::RagConfig::default(),
         delegate: crate::config::DelegateToolConfig::default(),
         agents: std::collections::HashMap::new(),
         swarms: std::collections::HashMap::new(),
@@ -419,7 +425,11 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         vector_weight: 0.7,
         keyword_weight: 0.3,
         search_mode: crate::config::SearchMode::default(),
+        recall: None,
+        dedup: crate::config::MemoryDedupConfig::default(),
+        distill: crate::config::MemoryDistillConfig::default(),
         min_relevance_score: 0.4,
+        pinned_context_max_chars: 1_000,
         embedding_cache_size: if profile.uses_sqlite_hygiene {
             10000
         } else {
@@ -438,10 +448,12 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         rerank_threshold: 5,
         fts_early_return_score: 0.85,
         default_namespace: "default".into(),
+        namespacing: crate::config::MemoryNamespacingConfig::default(),
         conflict_threshold: 0.85,
         audit_enabled: false,
         audit_retention_days: 30,
         policy: crate::config::MemoryPolicyConfig::default(),
+        ttl: std::collections::HashMap::new(),
         sqlite_open_timeout_secs: None,
         qdrant: crate::config::QdrantConfig::default(),
     }
@@ -584,6 +596,7 @@ async fn run_quick_setup_with_home(
         default_provider: Some(provider_name.clone()),
         default_model: Some(model.clone()),
         model_providers: std::collections::HashMap::new(),
+        providers: crate::config::ProvidersConfig::default(),
         default_temperature: 0.7,
         provider_timeout_secs: 120,
         provider_max_tokens: None,
@@ -607,7 +620,10 @@ async fn run_quick_setup_with_home(
         pipeline: crate::config::PipelineConfig::default(),
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
+        embeddings: crate::config::EmbeddingsConfig::default(),
         heartbeat: HeartbeatConfig::default(),
+        auth: crate::config::AuthConfig::default(),
+        token_refresh: crate::config::TokenRefreshConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
@@ -632,6 +648,8 @@ async fn run_quick_setup_with_home(
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        rag_watch: crate::config::RagWatchConfig::default(),
+        rag: crate::config::RagConfig::default(),
         delegate: crate::config::DelegateToolConfig::default(),
         agents: std::collections::HashMap::new(),
         swarms: std::collections::HashMap::new(),
@@ -801,6 +819,7 @@ fn canonical_provider_name(provider_name: &str) -> &str {
     match provider_name {
         "grok" => "xai",
         "together" => "together-ai",
+        "azure-openai" | "azure" => "azure_openai",
         "google" | "google-gemini" => "gemini",
         "github-copilot" => "copilot",
         "openai_codex" | "codex" => "openai-codex",
@@ -1330,6 +1349,8 @@ fn supports_live_model_fetch(provider_name: &str) -> bool {
             | "xai"
             | "together-ai"
             | "gemini"
+            | "azure_openai"
+            | "bedrock"
             | "ollama"
             | "llamacpp"
             | "sglang"
@@ -1426,6 +1447,93 @@ fn parse_openai_compatible_model_ids(payload: &Value) -> Vec<String> {
     normalize_model_ids(models)
 }
 
+/// Convert a provider-reported per-token price (OpenRouter convention:
+/// decimal string or number in USD per token) to USD per 1M tokens.
+fn price_per_million_tokens(value: &Value) -> Option<f64> {
+    let per_token = match value {
+        Value::String(raw) => raw.trim().parse::<f64>().ok()?,
+        Value::Number(number) => number.as_f64()?,
+        _ => return None,
+    };
+
+    if !per_token.is_finite() || per_token < 0.0 {
+        return None;
+    }
+
+    Some(per_token * 1_000_000.0)
+}
+
+/// Extract per-model metadata (context window, pricing, modalities) from an
+/// OpenAI-compatible `/models` payload. OpenRouter exposes all three fields;
+/// other gateways expose a subset or none — models without any metadata are
+/// skipped rather than cached as empty records.
+fn parse_openai_compatible_model_metadata(payload: &Value) -> Vec<ModelMetadata> {
+    let entries = payload
+        .get("data")
+        .and_then(Value::as_array)
+        .or_else(|| payload.as_array());
+
+    let Some(entries) = entries else {
+        return Vec::new();
+    };
+
+    let mut unique = BTreeMap::new();
+    for model in entries {
+        let Some(id) = model.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        let id = id.trim();
+        if id.is_empty() {
+            continue;
+        }
+
+        let context_window = model
+            .get("context_length")
+            .or_else(|| model.get("context_window"))
+            .or_else(|| model.get("max_model_len"))
+            .and_then(Value::as_u64);
+        let pricing = model.get("pricing");
+        let input_price_per_million = pricing
+            .and_then(|pricing| pricing.get("prompt"))
+            .and_then(price_per_million_tokens);
+        let output_price_per_million = pricing
+            .and_then(|pricing| pricing.get("completion"))
+            .and_then(price_per_million_tokens);
+        let modalities: Vec<String> = model
+            .get("architecture")
+            .and_then(|architecture| architecture.get("input_modalities"))
+            .and_then(Value::as_array)
+            .map(|modalities| {
+                modalities
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if context_window.is_none()
+            && input_price_per_million.is_none()
+            && output_price_per_million.is_none()
+            && modalities.is_empty()
+        {
+            continue;
+        }
+
+        unique
+            .entry(id.to_ascii_lowercase())
+            .or_insert_with(|| ModelMetadata {
+                id: id.to_string(),
+                context_window,
+                input_price_per_million,
+                output_price_per_million,
+                modalities,
+            });
+    }
+
+    unique.into_values().collect()
+}
+
 fn parse_gemini_model_ids(payload: &Value) -> Vec<String> {
     let Some(models) = payload.get("models").and_then(Value::as_array) else {
         return Vec::new();
@@ -1473,7 +1581,7 @@ async fn fetch_openai_compatible_models(
     endpoint: &str,
     api_key: Option<&str>,
     allow_unauthenticated: bool,
-) -> Result<Vec<String>> {
+) -> Result<(Vec<String>, Vec<ModelMetadata>)> {
     let client = build_model_fetch_client()?;
     let mut request = client.get(endpoint);
 
@@ -1492,10 +1600,15 @@ async fn fetch_openai_compatible_models(
         .await
         .context("failed to parse model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok((
+        parse_openai_compatible_model_ids(&payload),
+        parse_openai_compatible_model_metadata(&payload),
+    ))
 }
 
-async fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
+async fn fetch_openrouter_models(
+    api_key: Option<&str>,
+) -> Result<(Vec<String>, Vec<ModelMetadata>)> {
     let client = build_model_fetch_client()?;
     let mut request = client.get("https://openrouter.ai/api/v1/models");
     if let Some(api_key) = api_key {
@@ -1511,7 +1624,10 @@ async fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .await
         .context("failed to parse OpenRouter model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok((
+        parse_openai_compatible_model_ids(&payload),
+        parse_openai_compatible_model_metadata(&payload),
+    ))
 }
 
 async fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
@@ -1571,6 +1687,62 @@ async fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
     Ok(parse_gemini_model_ids(&payload))
 }
 
+/// List deployments on the configured Azure OpenAI resource via the
+/// data-plane deployments endpoint. Degrades to echoing the configured
+/// deployment when the resource is unset, no credential is available, or
+/// the listing call fails (e.g. the credential lacks the management scope).
+async fn fetch_azure_openai_models(api_key: Option<&str>) -> Result<Vec<String>> {
+    let configured_deployment = std::env::var("AZURE_OPENAI_DEPLOYMENT")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let fallback: Vec<String> = configured_deployment.into_iter().collect();
+
+    let resource = std::env::var("AZURE_OPENAI_RESOURCE")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let Some(resource) = resource else {
+        return Ok(fallback);
+    };
+
+    let aad_token = std::env::var("AZURE_OPENAI_AAD_TOKEN")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let client = build_model_fetch_client()?;
+    let api_version = std::env::var("AZURE_OPENAI_API_VERSION")
+        .unwrap_or_else(|_| "2024-08-01-preview".to_string());
+    let url =
+        format!("https://{resource}.openai.azure.com/openai/deployments?api-version={api_version}");
+
+    let mut request = client.get(&url);
+    if let Some(key) = api_key {
+        request = request.header("api-key", key);
+    } else if let Some(token) = aad_token {
+        request = request.bearer_auth(token);
+    } else {
+        return Ok(fallback);
+    }
+
+    let payload: Option<Value> = match request.send().await {
+        Ok(response) if response.status().is_success() => response.json().await.ok(),
+        _ => None,
+    };
+
+    let deployments = payload
+        .as_ref()
+        .map(parse_openai_compatible_model_ids)
+        .unwrap_or_default();
+
+    if deployments.is_empty() {
+        Ok(fallback)
+    } else {
+        Ok(deployments)
+    }
+}
+
 async fn fetch_ollama_models() -> Result<Vec<String>> {
     let client = build_model_fetch_client()?;
     let payload: Value = client
@@ -1669,7 +1841,7 @@ async fn fetch_live_models_for_provider(
     provider_name: &str,
     api_key: &str,
     provider_api_url: Option<&str>,
-) -> Result<Vec<String>> {
+) -> Result<(Vec<String>, Vec<ModelMetadata>)> {
     let requested_provider_name = provider_name;
     let provider_name = canonical_provider_name(provider_name);
     let ollama_remote = provider_name == "ollama" && ollama_uses_remote_endpoint(provider_api_url);
@@ -1696,12 +1868,23 @@ async fn fetch_live_models_for_provider(
         Some(api_key.trim().to_string())
     };
 
-    let models = match provider_name {
+    let (models, metadata) = match provider_name {
         "openrouter" => fetch_openrouter_models(api_key.as_deref()).await?,
-        "anthropic" => fetch_anthropic_models(api_key.as_deref()).await?,
-        "gemini" => fetch_gemini_models(api_key.as_deref()).await?,
+        "anthropic" => (
+            fetch_anthropic_models(api_key.as_deref()).await?,
+            Vec::new(),
+        ),
+        "gemini" => (fetch_gemini_models(api_key.as_deref()).await?, Vec::new()),
+        "azure_openai" => (
+            fetch_azure_openai_models(api_key.as_deref()).await?,
+            Vec::new(),
+        ),
+        "bedrock" => (
+            crate::providers::bedrock::list_foundation_models().await?,
+            Vec::new(),
+        ),
         "ollama" => {
-            if ollama_remote {
+            let models = if ollama_remote {
                 // Remote Ollama endpoints can serve cloud-routed models.
                 // Keep this curated list aligned with current Ollama cloud catalog.
                 vec![
@@ -1723,7 +1906,8 @@ async fn fetch_live_models_for_provider(
                     .into_iter()
                     .filter(|model_id| !model_id.ends_with(":cloud"))
                     .collect()
-            }
+            };
+            (models, Vec::new())
         }
         _ => {
             if let Some(endpoint) =
@@ -1734,12 +1918,33 @@ async fn fetch_live_models_for_provider(
                 fetch_openai_compatible_models(&endpoint, api_key.as_deref(), allow_unauthenticated)
                     .await?
             } else {
-                Vec::new()
+                (Vec::new(), Vec::new())
             }
         }
     };
 
-    Ok(models)
+    Ok((models, metadata))
+}
+
+/// Current on-disk model cache format. Bump when the schema changes in a way
+/// old binaries cannot read; version-0 caches (pre-metadata) still load.
+const MODEL_CACHE_VERSION: u32 = 1;
+
+/// Per-model metadata collected during `models refresh` where the provider
+/// exposes it. All fields are optional — providers report different subsets.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    /// USD per 1M input tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_price_per_million: Option<f64>,
+    /// USD per 1M output tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_price_per_million: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modalities: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1747,16 +1952,21 @@ struct ModelCacheEntry {
     provider: String,
     fetched_at_unix: u64,
     models: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    metadata: Vec<ModelMetadata>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct ModelCacheState {
+    #[serde(default)]
+    version: u32,
     entries: Vec<ModelCacheEntry>,
 }
 
 #[derive(Debug, Clone)]
 struct CachedModels {
     models: Vec<String>,
+    metadata: Vec<ModelMetadata>,
     age_secs: u64,
 }
 
@@ -1781,8 +1991,10 @@ async fn load_model_cache_state(workspace_dir: &Path) -> Result<ModelCacheState>
         .with_context(|| format!("failed to read model cache at {}", path.display()))?;
 
     match serde_json::from_str::<ModelCacheState>(&raw) {
-        Ok(state) => Ok(state),
-        Err(_) => Ok(ModelCacheState::default()),
+        // A cache written by a newer binary may not be readable; start fresh
+        // rather than misinterpreting it.
+        Ok(state) if state.version <= MODEL_CACHE_VERSION => Ok(state),
+        _ => Ok(ModelCacheState::default()),
     }
 }
 
@@ -1809,6 +2021,7 @@ async fn cache_live_models_for_provider(
     workspace_dir: &Path,
     provider_name: &str,
     models: &[String],
+    metadata: &[ModelMetadata],
 ) -> Result<()> {
     let normalized_models = normalize_model_ids(models.to_vec());
     if normalized_models.is_empty() {
@@ -1816,6 +2029,7 @@ async fn cache_live_models_for_provider(
     }
 
     let mut state = load_model_cache_state(workspace_dir).await?;
+    state.version = MODEL_CACHE_VERSION;
     let now = now_unix_secs();
 
     if let Some(entry) = state
@@ -1825,17 +2039,144 @@ async fn cache_live_models_for_provider(
     {
         entry.fetched_at_unix = now;
         entry.models = normalized_models;
+        entry.metadata = metadata.to_vec();
     } else {
         state.entries.push(ModelCacheEntry {
             provider: provider_name.to_string(),
             fetched_at_unix: now,
             models: normalized_models,
+            metadata: metadata.to_vec(),
         });
     }
 
     save_model_cache_state(workspace_dir, &state).await
 }
 
+// ── Cached model metadata at runtime ─────────────────────────────
+
+/// Process-wide context-window lookup, keyed by `provider:model` and plain
+/// model id (both lowercase). Populated from the model cache at startup so
+/// the agent loop can size compaction thresholds without touching disk.
+static RUNTIME_CONTEXT_WINDOWS: std::sync::OnceLock<std::sync::Mutex<HashMap<String, u64>>> =
+    std::sync::OnceLock::new();
+
+fn runtime_context_windows() -> &'static std::sync::Mutex<HashMap<String, u64>> {
+    RUNTIME_CONTEXT_WINDOWS.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
+/// Load cached context windows into the process-wide lookup consulted by the
+/// agent loop. No-op when the cache holds no metadata (e.g. before the first
+/// `models refresh` against a provider that exposes it).
+pub async fn load_runtime_model_metadata(workspace_dir: &Path) {
+    let Ok(state) = load_model_cache_state(workspace_dir).await else {
+        return;
+    };
+
+    let mut windows = HashMap::new();
+    for entry in &state.entries {
+        for metadata in &entry.metadata {
+            let Some(window) = metadata.context_window else {
+                continue;
+            };
+            let model = metadata.id.to_ascii_lowercase();
+            windows.insert(format!("{}:{model}", entry.provider), window);
+            windows.entry(model).or_insert(window);
+        }
+    }
+
+    if windows.is_empty() {
+        return;
+    }
+
+    if let Ok(mut map) = runtime_context_windows().lock() {
+        map.extend(windows);
+    }
+}
+
+/// Cached context window for a model, if `models refresh` recorded one.
+/// Tries `provider:model`, the bare model id, then the id after the last `/`
+/// (mirrors the pricing lookup tiers in cost tracking).
+pub fn runtime_context_window(provider_name: &str, model: &str) -> Option<u64> {
+    let map = runtime_context_windows().lock().ok()?;
+    let model = model.to_ascii_lowercase();
+
+    map.get(&format!("{provider_name}:{model}"))
+        .or_else(|| map.get(&model))
+        .or_else(|| {
+            model
+                .rsplit_once('/')
+                .and_then(|(_, suffix)| map.get(suffix))
+        })
+        .copied()
+}
+
+/// Cached per-model metadata for one provider, matched case-insensitively by
+/// model id (with or without a `vendor/` prefix).
+pub async fn cached_model_metadata(
+    workspace_dir: &Path,
+    provider_name: &str,
+    model: &str,
+) -> Result<Option<ModelMetadata>> {
+    let state = load_model_cache_state(workspace_dir).await?;
+
+    let Some(entry) = state
+        .entries
+        .into_iter()
+        .find(|entry| entry.provider == provider_name)
+    else {
+        return Ok(None);
+    };
+
+    Ok(find_model_metadata(&entry.metadata, model).cloned())
+}
+
+fn find_model_metadata<'a>(
+    metadata: &'a [ModelMetadata],
+    model: &str,
+) -> Option<&'a ModelMetadata> {
+    metadata
+        .iter()
+        .find(|entry| entry.id.eq_ignore_ascii_case(model))
+        .or_else(|| {
+            let suffix = model.rsplit_once('/').map(|(_, suffix)| suffix)?;
+            metadata.iter().find(|entry| {
+                entry.id.eq_ignore_ascii_case(suffix)
+                    || entry
+                        .id
+                        .rsplit_once('/')
+                        .is_some_and(|(_, entry_suffix)| entry_suffix.eq_ignore_ascii_case(suffix))
+            })
+        })
+}
+
+/// Cached pricing as a `[cost.prices]` overlay. Only models with both input
+/// and output prices are included; explicit config entries always win at the
+/// merge site.
+pub async fn cached_model_prices(
+    workspace_dir: &Path,
+) -> HashMap<String, crate::config::schema::ModelPricing> {
+    let Ok(state) = load_model_cache_state(workspace_dir).await else {
+        return HashMap::new();
+    };
+
+    let mut prices = HashMap::new();
+    for entry in &state.entries {
+        for metadata in &entry.metadata {
+            let (Some(input), Some(output)) = (
+                metadata.input_price_per_million,
+                metadata.output_price_per_million,
+            ) else {
+                continue;
+            };
+            prices
+                .entry(metadata.id.clone())
+                .or_insert(crate::config::schema::ModelPricing { input, output });
+        }
+    }
+
+    prices
+}
+
 // ── Provider defaults ───────────────────────────────────────────
 
 const PROVIDER_DEFAULTS_FILE: &str = "provider_defaults.json";
@@ -1941,6 +2282,7 @@ async fn load_cached_models_for_provider_internal(
 
     Ok(Some(CachedModels {
         models: entry.models,
+        metadata: entry.metadata,
         age_secs,
     }))
 }
@@ -2040,12 +2382,19 @@ pub async fn run_models_refresh(
 
     match fetch_live_models_for_provider(&provider_name, &api_key, config.api_url.as_deref()).await
     {
-        Ok(models) if !models.is_empty() => {
-            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &models).await?;
+        Ok((models, metadata)) if !models.is_empty() => {
+            cache_live_models_for_provider(
+                &config.workspace_dir,
+                &provider_name,
+                &models,
+                &metadata,
+            )
+            .await?;
             println!(
-                "Refreshed '{}' model cache with {} models.",
+                "Refreshed '{}' model cache with {} models ({} with metadata).",
                 provider_name,
-                models.len()
+                models.len(),
+                metadata.len()
             );
             print_model_preview(&models);
             Ok(())
@@ -2113,12 +2462,45 @@ pub async fn run_models_list(config: &Config, provider_override: Option<&str>) -
         } else {
             "  "
         };
-        println!("  {marker}{model}");
+        let details = find_model_metadata(&cached.metadata, model)
+            .and_then(format_model_metadata_summary)
+            .map(|summary| format!("  {}", style(summary).dim()))
+            .unwrap_or_default();
+        println!("  {marker}{model}{details}");
     }
     println!();
     Ok(())
 }
 
+/// One-line human summary of cached model metadata, e.g.
+/// `131k ctx, $0.05/$0.40 per 1M, text+image`.
+fn format_model_metadata_summary(metadata: &ModelMetadata) -> Option<String> {
+    let mut parts = Vec::new();
+
+    if let Some(window) = metadata.context_window {
+        if window >= 1000 {
+            parts.push(format!("{}k ctx", window / 1000));
+        } else {
+            parts.push(format!("{window} ctx"));
+        }
+    }
+    if let (Some(input), Some(output)) = (
+        metadata.input_price_per_million,
+        metadata.output_price_per_million,
+    ) {
+        parts.push(format!("${input:.2}/${output:.2} per 1M"));
+    }
+    if !metadata.modalities.is_empty() {
+        parts.push(metadata.modalities.join("+"));
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
 pub async fn run_models_set(config: &Config, model: &str) -> Result<()> {
     let model = model.trim();
     if model.is_empty() {
@@ -2147,6 +2529,46 @@ pub async fn run_models_status(config: &Config) -> Result<()> {
         style(format!("{:.1}", config.default_temperature)).cyan()
     );
 
+    let routing = &config.providers.openrouter.routing;
+    if !routing.is_unset() {
+        println!("  OpenRouter routing:");
+        if !routing.order.is_empty() {
+            println!(
+                "    order:              {}",
+                style(routing.order.join(", ")).cyan()
+            );
+        }
+        if !routing.only.is_empty() {
+            println!(
+                "    only:               {}",
+                style(routing.only.join(", ")).cyan()
+            );
+        }
+        if !routing.ignore.is_empty() {
+            println!(
+                "    ignore:             {}",
+                style(routing.ignore.join(", ")).cyan()
+            );
+        }
+        if let Some(allow) = routing.allow_fallbacks {
+            println!("    allow_fallbacks:    {}", style(allow).cyan());
+        }
+        if let Some(require) = routing.require_parameters {
+            println!("    require_parameters: {}", style(require).cyan());
+        }
+        if let Some(ref policy) = routing.data_collection {
+            println!("    data_collection:    {}", style(policy).cyan());
+        }
+    }
+    for route in &config.model_routes {
+        if route.openrouter_routing.is_some() {
+            println!(
+                "  Route '{}': OpenRouter routing override active",
+                style(&route.hint).cyan()
+            );
+        }
+    }
+
     match load_any_cached_models_for_provider(&config.workspace_dir, provider).await? {
         Some(cached) => {
             println!(
@@ -2160,6 +2582,11 @@ pub async fn run_models_status(config: &Config) -> Result<()> {
             } else {
                 println!("  Freshness: {}", style("stale").yellow());
             }
+            if let Some(summary) =
+                find_model_metadata(&cached.metadata, model).and_then(format_model_metadata_summary)
+            {
+                println!("  Metadata:  {}", style(summary).cyan());
+            }
         }
         None => {
             println!("  Cache:     {}", style("none").yellow());
@@ -2263,8 +2690,8 @@ pub async fn refresh_models_quiet(
     })
     .await;
 
-    let models = match result {
-        Ok(Ok(models)) if !models.is_empty() => models,
+    let (models, metadata) = match result {
+        Ok(Ok((models, metadata))) if !models.is_empty() => (models, metadata),
         Ok(Ok(_)) => {
             tracing::warn!(
                 "Model refresh for '{provider_name}' returned empty list; keeping stale cache"
@@ -2282,7 +2709,7 @@ pub async fn refresh_models_quiet(
     };
 
     let count = models.len();
-    cache_live_models_for_provider(workspace_dir, &provider_name, &models).await?;
+    cache_live_models_for_provider(workspace_dir, &provider_name, &models, &metadata).await?;
     tracing::info!("Refreshed '{provider_name}' model cache: {count} models");
     Ok(count)
 }
@@ -3054,11 +3481,12 @@ async fn setup_provider(workspace_dir: &Path) -> Result<(String, String, String,
                 )
                 .await
                 {
-                    Ok(live_model_ids) if !live_model_ids.is_empty() => {
+                    Ok((live_model_ids, live_metadata)) if !live_model_ids.is_empty() => {
                         cache_live_models_for_provider(
                             workspace_dir,
                             provider_name,
                             &live_model_ids,
+                            &live_metadata,
                         )
                         .await?;
 
@@ -3235,6 +3663,7 @@ fn provider_env_var(name: &str) -> &'static str {
         "cloudflare" | "cloudflare-ai" => "CLOUDFLARE_API_KEY",
         "bedrock" | "aws-bedrock" => "AWS_ACCESS_KEY_ID",
         "gemini" => "GEMINI_API_KEY",
+        "azure_openai" => "AZURE_OPENAI_API_KEY",
         "nvidia" | "nvidia-nim" | "build.nvidia.com" => "NVIDIA_API_KEY",
         "astrai" => "ASTRAI_API_KEY",
         "avian" => "AVIAN_API_KEY",
@@ -3976,6 +4405,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     draft_update_interval_ms: 1000,
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    respond_mode: None,
+                    group_respond_modes: std::collections::HashMap::new(),
+                    trigger_prefix: None,
                     ack_reactions: None,
                     proxy_url: None,
                 });
@@ -4078,10 +4510,14 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     listen_to_bots: false,
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    respond_mode: None,
+                    group_respond_modes: std::collections::HashMap::new(),
+                    trigger_prefix: None,
                     proxy_url: None,
                     stream_mode: StreamMode::MultiMessage,
                     draft_update_interval_ms: 1000,
                     multi_message_delay_ms: 800,
+                    broadcast_channel_id: None,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -4213,6 +4649,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     interrupt_on_new_message: false,
                     thread_replies: None,
                     mention_only: false,
+                    respond_mode: None,
+                    group_respond_modes: std::collections::HashMap::new(),
+                    trigger_prefix: None,
                     use_markdown_blocks: false,
                     proxy_url: None,
                     stream_drafts: false,
@@ -4481,6 +4920,10 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     ignore_attachments,
                     ignore_stories,
                     proxy_url: None,
+                    respond_mode: None,
+                    group_respond_modes: std::collections::HashMap::new(),
+                    trigger_prefix: None,
+                    profile_name: None,
                 });
 
                 println!("  {} Signal configured", style("✅").green().bold());
@@ -4924,6 +5367,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(sasl_password.trim().to_string())
                     },
                     verify_tls: Some(verify_tls),
+                    tls_fingerprint: None,
+                    channel_allowed_users: std::collections::HashMap::new(),
                 });
             }
             ChannelMenuChoice::Webhook => {
@@ -7067,6 +7512,8 @@ mod tests {
         assert_eq!(canonical_provider_name("zai-cn"), "zai");
         assert_eq!(canonical_provider_name("z.ai-global"), "zai");
         assert_eq!(canonical_provider_name("nvidia-nim"), "nvidia");
+        assert_eq!(canonical_provider_name("azure"), "azure_openai");
+        assert_eq!(canonical_provider_name("azure-openai"), "azure_openai");
         assert_eq!(canonical_provider_name("aws-bedrock"), "bedrock");
         assert_eq!(canonical_provider_name("build.nvidia.com"), "nvidia");
         assert_eq!(canonical_provider_name("llama.cpp"), "llamacpp");
@@ -7218,6 +7665,11 @@ mod tests {
         assert!(supports_live_model_fetch("venice"));
         assert!(supports_live_model_fetch("glm-cn"));
         assert!(supports_live_model_fetch("qwen-intl"));
+        assert!(supports_live_model_fetch("azure_openai"));
+        assert!(supports_live_model_fetch("azure-openai"));
+        assert!(supports_live_model_fetch("azure"));
+        assert!(supports_live_model_fetch("bedrock"));
+        assert!(supports_live_model_fetch("aws-bedrock"));
         assert!(!supports_live_model_fetch("minimax-cn"));
         assert!(!supports_live_model_fetch("unknown-provider"));
     }
@@ -7515,7 +7967,7 @@ mod tests {
         let tmp = TempDir::new().unwrap();
         let models = vec!["gpt-5.1".to_string(), "gpt-5-mini".to_string()];
 
-        cache_live_models_for_provider(tmp.path(), "openai", &models)
+        cache_live_models_for_provider(tmp.path(), "openai", &models, &[])
             .await
             .unwrap();
 
@@ -7533,10 +7985,12 @@ mod tests {
     async fn model_cache_ttl_filters_stale_entries() {
         let tmp = TempDir::new().unwrap();
         let stale = ModelCacheState {
+            version: MODEL_CACHE_VERSION,
             entries: vec![ModelCacheEntry {
                 provider: "openai".to_string(),
                 fetched_at_unix: now_unix_secs().saturating_sub(MODEL_CACHE_TTL_SECS + 120),
                 models: vec!["gpt-5.1".to_string()],
+                metadata: Vec::new(),
             }],
         };
 
@@ -7553,11 +8007,139 @@ mod tests {
         assert!(stale_any.is_some());
     }
 
+    #[test]
+    fn parse_openai_compatible_model_metadata_extracts_openrouter_fields() {
+        let payload = json!({
+            "data": [
+                {
+                    "id": "acme/omni-large",
+                    "context_length": 131_072,
+                    "pricing": {"prompt": "0.0000005", "completion": "0.0000015"},
+                    "architecture": {"input_modalities": ["text", "image"]}
+                },
+                {
+                    "id": "acme/ids-only"
+                }
+            ]
+        });
+
+        let metadata = parse_openai_compatible_model_metadata(&payload);
+        assert_eq!(metadata.len(), 1, "models without metadata are skipped");
+
+        let entry = &metadata[0];
+        assert_eq!(entry.id, "acme/omni-large");
+        assert_eq!(entry.context_window, Some(131_072));
+        assert_eq!(entry.input_price_per_million, Some(0.5));
+        assert_eq!(entry.output_price_per_million, Some(1.5));
+        assert_eq!(
+            entry.modalities,
+            vec!["text".to_string(), "image".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn model_cache_round_trips_metadata_and_reads_legacy_format() {
+        let tmp = TempDir::new().unwrap();
+        let metadata = vec![ModelMetadata {
+            id: "acme/omni-large".to_string(),
+            context_window: Some(131_072),
+            input_price_per_million: Some(0.5),
+            output_price_per_million: Some(1.5),
+            modalities: vec!["text".to_string()],
+        }];
+
+        cache_live_models_for_provider(
+            tmp.path(),
+            "openrouter",
+            &["acme/omni-large".to_string()],
+            &metadata,
+        )
+        .await
+        .unwrap();
+
+        let cached = load_any_cached_models_for_provider(tmp.path(), "openrouter")
+            .await
+            .unwrap()
+            .expect("expected cached models");
+        assert_eq!(cached.metadata.len(), 1);
+        assert_eq!(cached.metadata[0].context_window, Some(131_072));
+
+        // A pre-metadata cache (no version, no metadata fields) still loads.
+        let legacy = json!({
+            "entries": [{
+                "provider": "openai",
+                "fetched_at_unix": now_unix_secs(),
+                "models": ["gpt-5.1"]
+            }]
+        });
+        tokio::fs::write(model_cache_path(tmp.path()), legacy.to_string())
+            .await
+            .unwrap();
+
+        let cached = load_any_cached_models_for_provider(tmp.path(), "openai")
+            .await
+            .unwrap()
+            .expect("expected legacy cached models");
+        assert_eq!(cached.models, vec!["gpt-5.1".to_string()]);
+        assert!(cached.metadata.is_empty());
+    }
+
+    #[tokio::test]
+    async fn cached_model_prices_and_metadata_expose_cached_pricing() {
+        let tmp = TempDir::new().unwrap();
+        let metadata = vec![
+            ModelMetadata {
+                id: "acme/priced".to_string(),
+                context_window: Some(65_536),
+                input_price_per_million: Some(0.25),
+                output_price_per_million: Some(1.25),
+                modalities: Vec::new(),
+            },
+            ModelMetadata {
+                id: "acme/unpriced".to_string(),
+                context_window: Some(8_192),
+                input_price_per_million: None,
+                output_price_per_million: None,
+                modalities: Vec::new(),
+            },
+        ];
+
+        cache_live_models_for_provider(
+            tmp.path(),
+            "openrouter",
+            &["acme/priced".to_string(), "acme/unpriced".to_string()],
+            &metadata,
+        )
+        .await
+        .unwrap();
+
+        let prices = cached_model_prices(tmp.path()).await;
+        let priced = prices.get("acme/priced").expect("expected cached pricing");
+        assert!((priced.input - 0.25).abs() < f64::EPSILON);
+        assert!((priced.output - 1.25).abs() < f64::EPSILON);
+        assert!(
+            !prices.contains_key("acme/unpriced"),
+            "models without both prices are excluded"
+        );
+
+        let found = cached_model_metadata(tmp.path(), "openrouter", "acme/unpriced")
+            .await
+            .unwrap()
+            .expect("expected metadata for cached model");
+        assert_eq!(found.context_window, Some(8_192));
+
+        // Vendor-prefix-insensitive match mirrors the pricing lookup tiers.
+        let by_suffix = cached_model_metadata(tmp.path(), "openrouter", "other/priced")
+            .await
+            .unwrap();
+        assert_eq!(by_suffix.map(|m| m.id), Some("acme/priced".to_string()));
+    }
+
     #[tokio::test]
     async fn run_models_refresh_uses_fresh_cache_without_network() {
         let tmp = TempDir::new().unwrap();
 
-        cache_live_models_for_provider(tmp.path(), "openai", &["gpt-5.1".to_string()])
+        cache_live_models_for_provider(tmp.path(), "openai", &["gpt-5.1".to_string()], &[])
             .await
             .unwrap();
 
@@ -7595,6 +8177,8 @@ mod tests {
         assert_eq!(provider_env_var("anthropic"), "ANTHROPIC_API_KEY");
         assert_eq!(provider_env_var("openai-codex"), "OPENAI_API_KEY");
         assert_eq!(provider_env_var("openai"), "OPENAI_API_KEY");
+        assert_eq!(provider_env_var("azure_openai"), "AZURE_OPENAI_API_KEY");
+        assert_eq!(provider_env_var("azure"), "AZURE_OPENAI_API_KEY");
         assert_eq!(provider_env_var("ollama"), "OLLAMA_API_KEY");
         assert_eq!(provider_env_var("llamacpp"), "LLAMACPP_API_KEY");
         assert_eq!(provider_env_var("llama.cpp"), "LLAMACPP_API_KEY");
@@ -7733,6 +8317,10 @@ mod tests {
             ignore_attachments: false,
             ignore_stories: true,
             proxy_url: None,
+            respond_mode: None,
+            group_respond_modes: std::collections::HashMap::new(),
+            trigger_prefix: None,
+            profile_name: None,
         });
         assert!(has_launchable_channels(&channels));
 
@@ -7826,6 +8414,7 @@ mod tests {
             provider: "gemini".into(),
             model: "gemini-3-flash-preview".into(),
             api_key: None,
+            openrouter_routing: None,
         }];
 
         let model = resolve_default_model_for_provider(&ws, "gemini", &routes).await;
@@ -6,7 +6,8 @@
 use anyhow::{Context, Result};
 use std::process::Command;
 
-/// ZeroClaw Arduino Uno base firmware (capabilities, gpio_read, gpio_write).
+/// ZeroClaw Arduino Uno base firmware (capabilities, gpio_read, gpio_write,
+/// servo_write, tone/no_tone).
 const FIRMWARE_INO: &str = include_str!("../../firmware/arduino/arduino.ino");
 
 const FQBN: &str = "arduino:avr:uno";
@@ -127,7 +128,9 @@ pub fn flash_arduino_firmware(port: &str) -> Result<()> {
     }
 
     println!("ZeroClaw firmware flashed successfully.");
-    println!("The Arduino now supports: capabilities, gpio_read, gpio_write.");
+    println!(
+        "The Arduino now supports: capabilities, gpio_read, gpio_write, servo_write, tone, no_tone."
+    );
     Ok(())
 }
 
@@ -143,3 +146,60 @@ pub fn resolve_port(config: &crate::config::Config, path_override: Option<&str>)
         .find(|b| b.board == "arduino-uno" && b.transport == "serial")
         .and_then(|b| b.path.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_sketch_has_servo_and_tone_handlers() {
+        for needle in [
+            "#include <Servo.h>",
+            "hasCmd(line, \"servo_write\")",
+            "hasCmd(line, \"tone\")",
+            "hasCmd(line, \"no_tone\")",
+        ] {
+            assert!(FIRMWARE_INO.contains(needle), "sketch lost: {needle}");
+        }
+        // Advertised in capabilities so the host can discover support.
+        assert!(FIRMWARE_INO.contains("\\\"servo\\\":true"));
+        assert!(FIRMWARE_INO.contains("\\\"tone\\\":true"));
+        // Pin ownership guard: servo on a GPIO-claimed pin is rejected.
+        assert!(FIRMWARE_INO.contains("already in use as gpio"));
+    }
+
+    /// Compile the embedded sketch with arduino-cli when the toolchain is
+    /// installed (CI); silently skips on dev machines without it.
+    #[test]
+    fn embedded_sketch_compiles_under_arduino_cli() {
+        if !arduino_cli_available() {
+            eprintln!("skipping: arduino-cli not installed");
+            return;
+        }
+        let core_list = Command::new("arduino-cli").args(["core", "list"]).output();
+        let has_avr_core = core_list
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("arduino:avr"))
+            .unwrap_or(false);
+        if !has_avr_core {
+            eprintln!("skipping: arduino:avr core not installed");
+            return;
+        }
+
+        let temp_dir =
+            std::env::temp_dir().join(format!("zeroclaw_sketch_test_{}", uuid::Uuid::new_v4()));
+        let sketch_dir = temp_dir.join(SKETCH_NAME);
+        std::fs::create_dir_all(&sketch_dir).unwrap();
+        std::fs::write(sketch_dir.join(format!("{SKETCH_NAME}.ino")), FIRMWARE_INO).unwrap();
+
+        let compile = Command::new("arduino-cli")
+            .args(["compile", "--fqbn", FQBN, &*sketch_dir.to_string_lossy()])
+            .output()
+            .expect("arduino-cli compile failed to run");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        assert!(
+            compile.status.success(),
+            "sketch does not compile:\n{}",
+            String::from_utf8_lossy(&compile.stderr)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Hardware capabilities tool — Phase C: query device for reported GPIO pins.
 
-use super::serial::SerialTransport;
+use super::traits::CommandTransport;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
@@ -8,12 +8,12 @@ use std::sync::Arc;
 
 /// Tool: query device capabilities (GPIO pins, LED pin) from firmware.
 pub struct HardwareCapabilitiesTool {
-    /// (board_name, transport) for each serial board.
-    boards: Vec<(String, Arc<SerialTransport>)>,
+    /// (board_name, transport) for each serial or TCP board.
+    boards: Vec<(String, Arc<dyn CommandTransport>)>,
 }
 
 impl HardwareCapabilitiesTool {
-    pub(crate) fn new(boards: Vec<(String, Arc<SerialTransport>)>) -> Self {
+    pub(crate) fn new(boards: Vec<(String, Arc<dyn CommandTransport>)>) -> Self {
         Self { boards }
     }
 }
@@ -25,7 +25,7 @@ impl Tool for HardwareCapabilitiesTool {
     }
 
     fn description(&self) -> &str {
-        "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available."
+        "Query connected hardware for reported GPIO, PWM and ADC pins and LED pin. Use when: user asks what pins are available."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -56,11 +56,28 @@ impl Tool for HardwareCapabilitiesTool {
                         if let Ok(parsed) =
                             serde_json::from_str::<serde_json::Value>(&result.output)
                         {
+                            let i2c = if parsed.get("i2c").and_then(|v| v.as_bool())
+                                == Some(true)
+                            {
+                                format!(
+                                    ", i2c {}",
+                                    parsed
+                                        .get("i2c_pins")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or("available")
+                                )
+                            } else {
+                                String::new()
+                            };
                             format!(
-                                "{}: gpio {:?}, led_pin {:?}",
+                                "{}: gpio {:?}, pwm {:?}, adc {:?}, modes {}, led_pin {:?}{}",
                                 board_name,
                                 parsed.get("gpio").unwrap_or(&json!([])),
-                                parsed.get("led_pin").unwrap_or(&json!(null))
+                                parsed.get("pwm").unwrap_or(&json!([])),
+                                parsed.get("adc").unwrap_or(&json!([])),
+                                parsed.get("modes").unwrap_or(&json!({})),
+                                parsed.get("led_pin").unwrap_or(&json!(null)),
+                                i2c
                             )
                         } else {
                             format!("{}: {}", board_name, result.output)
@@ -0,0 +1,381 @@
+//! Capability-driven per-board tools — ADC and PWM tools generated at
+//! registration time from the pin lists a board's `capabilities` reports,
+//! so the model only ever sees valid pins in the schema enum.
+
+use super::traits::CommandTransport;
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// The `adc` / `pwm` pin lists one board advertised, reduced to the parts
+/// needed to build tools.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct BoardPins {
+    pub adc: Vec<u64>,
+    pub pwm: Vec<u64>,
+}
+
+/// Pull the `adc` / `pwm` pin lists out of a (nested JSON string)
+/// `capabilities` result. Firmware that omits a list gets no tool for it.
+pub(crate) fn parse_board_pins(capabilities: &str) -> BoardPins {
+    let parsed: Value = serde_json::from_str(capabilities).unwrap_or(Value::Null);
+    let pins = |key: &str| -> Vec<u64> {
+        parsed
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|a| a.iter().filter_map(Value::as_u64).collect())
+            .unwrap_or_default()
+    };
+    BoardPins {
+        adc: pins("adc"),
+        pwm: pins("pwm"),
+    }
+}
+
+/// Which protocol command a generated tool drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CapabilityKind {
+    AnalogRead,
+    PwmWrite,
+}
+
+/// Name + pin enum for one generated tool.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ToolSpec {
+    pub kind: CapabilityKind,
+    pub name: String,
+    pub pins: Vec<u64>,
+}
+
+/// Build the specs for the tools one board earns from its capabilities:
+/// an `adc` list yields `peripheral_analog_read`, a `pwm` list
+/// `peripheral_pwm_write`. With several boards attached the board name is
+/// folded into the tool name so calls stay unambiguous.
+pub(crate) fn capability_tool_specs(
+    board: &str,
+    pins: &BoardPins,
+    multiple_boards: bool,
+) -> Vec<ToolSpec> {
+    let suffix = if multiple_boards {
+        format!("_{}", board.replace(['-', '.'], "_"))
+    } else {
+        String::new()
+    };
+    let mut specs = Vec::new();
+    if !pins.adc.is_empty() {
+        specs.push(ToolSpec {
+            kind: CapabilityKind::AnalogRead,
+            name: format!("peripheral_analog_read{suffix}"),
+            pins: pins.adc.clone(),
+        });
+    }
+    if !pins.pwm.is_empty() {
+        specs.push(ToolSpec {
+            kind: CapabilityKind::PwmWrite,
+            name: format!("peripheral_pwm_write{suffix}"),
+            pins: pins.pwm.clone(),
+        });
+    }
+    specs
+}
+
+/// Materialize a spec into a registered tool.
+pub(crate) fn build_tool(
+    spec: ToolSpec,
+    board: &str,
+    transport: Arc<dyn CommandTransport>,
+) -> Box<dyn Tool> {
+    match spec.kind {
+        CapabilityKind::AnalogRead => {
+            Box::new(PeripheralAnalogReadTool::new(spec, board, transport))
+        }
+        CapabilityKind::PwmWrite => Box::new(PeripheralPwmWriteTool::new(spec, board, transport)),
+    }
+}
+
+/// Shared pin schema: an integer restricted to the pins the board reported.
+fn pin_schema(pins: &[u64], description: &str) -> Value {
+    json!({
+        "type": "integer",
+        "enum": pins,
+        "description": description
+    })
+}
+
+/// Tool: sample an ADC pin of one specific board.
+pub(crate) struct PeripheralAnalogReadTool {
+    name: String,
+    description: String,
+    pins: Vec<u64>,
+    transport: Arc<dyn CommandTransport>,
+}
+
+impl PeripheralAnalogReadTool {
+    fn new(spec: ToolSpec, board: &str, transport: Arc<dyn CommandTransport>) -> Self {
+        Self {
+            description: format!(
+                "Sample an analog input on the {board} board via its ADC. \
+                 Valid pins: {:?}. Returns the raw reading and millivolts.",
+                spec.pins
+            ),
+            name: spec.name,
+            pins: spec.pins,
+            transport,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralAnalogReadTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": pin_schema(&self.pins, "ADC channel (from this board's capabilities)")
+            },
+            "required": ["pin"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        if !self.pins.contains(&pin) {
+            anyhow::bail!(
+                "Pin {pin} has no ADC channel on this board (valid: {:?})",
+                self.pins
+            );
+        }
+        let result = self
+            .transport
+            .request("analog_read", json!({ "pin": pin }))
+            .await?;
+        Ok(annotate_analog(result))
+    }
+}
+
+/// Surface both the raw count and the converted millivolts from an
+/// `analog_read` result. Firmware that reports only a bare value passes
+/// through unchanged.
+fn annotate_analog(result: ToolResult) -> ToolResult {
+    if !result.success {
+        return result;
+    }
+    let Ok(parsed) = serde_json::from_str::<Value>(&result.output) else {
+        return result;
+    };
+    match (
+        parsed.get("raw").and_then(Value::as_u64),
+        parsed.get("millivolts").and_then(Value::as_u64),
+    ) {
+        (Some(raw), Some(mv)) => ToolResult {
+            success: true,
+            output: format!("raw {raw} ({mv} mV)"),
+            error: None,
+        },
+        _ => result,
+    }
+}
+
+/// Tool: drive a PWM-capable pin of one specific board.
+pub(crate) struct PeripheralPwmWriteTool {
+    name: String,
+    description: String,
+    pins: Vec<u64>,
+    transport: Arc<dyn CommandTransport>,
+}
+
+impl PeripheralPwmWriteTool {
+    fn new(spec: ToolSpec, board: &str, transport: Arc<dyn CommandTransport>) -> Self {
+        Self {
+            description: format!(
+                "Output a PWM signal on the {board} board (servos, motor ESCs, \
+                 LED dimming). Valid pins: {:?}. Duty is per mille (0-1000).",
+                spec.pins
+            ),
+            name: spec.name,
+            pins: spec.pins,
+            transport,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralPwmWriteTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": pin_schema(&self.pins, "PWM-capable pin (from this board's capabilities)"),
+                "frequency_hz": {
+                    "type": "integer",
+                    "description": "PWM frequency in Hz (e.g. 50 for servos, 1000 for motors)"
+                },
+                "duty": {
+                    "type": "integer",
+                    "description": "Duty cycle in per mille, 0-1000 (e.g. 75 = 7.5%)"
+                }
+            },
+            "required": ["pin", "frequency_hz", "duty"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        if !self.pins.contains(&pin) {
+            anyhow::bail!(
+                "Pin {pin} is not PWM-capable on this board (valid: {:?})",
+                self.pins
+            );
+        }
+        let frequency_hz = args
+            .get("frequency_hz")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'frequency_hz' parameter"))?;
+        let duty = args
+            .get("duty")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'duty' parameter"))?;
+        if duty > 1000 {
+            anyhow::bail!("'duty' is per mille (0-1000), got {duty}");
+        }
+        let mut result = self
+            .transport
+            .request(
+                "pwm_write",
+                json!({ "pin": pin, "frequency_hz": frequency_hz, "duty": duty }),
+            )
+            .await?;
+        if result.success {
+            result.output = format!("{} (duty {}.{}%)", result.output, duty / 10, duty % 10);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal mock peripheral: answers every request with a canned result.
+    struct MockPeripheral {
+        reply: String,
+    }
+
+    #[async_trait]
+    impl CommandTransport for MockPeripheral {
+        async fn request(&self, _cmd: &str, _args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: self.reply.clone(),
+                error: None,
+            })
+        }
+    }
+
+    fn nucleo_caps() -> &'static str {
+        r#"{"gpio":[2,3,4,5],"pwm":[3,5,6,9],"adc":[0,1,2,3,4,5],"led_pin":13}"#
+    }
+
+    #[test]
+    fn capability_specs_cover_adc_and_pwm_lists() {
+        let pins = parse_board_pins(nucleo_caps());
+        let specs = capability_tool_specs("nucleo-f401re", &pins, false);
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].name, "peripheral_analog_read");
+        assert_eq!(specs[0].pins, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(specs[1].name, "peripheral_pwm_write");
+        assert_eq!(specs[1].pins, vec![3, 5, 6, 9]);
+    }
+
+    #[test]
+    fn multiple_boards_fold_the_board_name_into_tool_names() {
+        let pins = parse_board_pins(nucleo_caps());
+        let specs = capability_tool_specs("nucleo-f401re", &pins, true);
+        assert_eq!(specs[0].name, "peripheral_analog_read_nucleo_f401re");
+        assert_eq!(specs[1].name, "peripheral_pwm_write_nucleo_f401re");
+    }
+
+    #[test]
+    fn boards_without_adc_or_pwm_get_no_tools() {
+        let pins = parse_board_pins(r#"{"gpio":[2,3,4],"led_pin":13}"#);
+        assert!(capability_tool_specs("arduino-uno", &pins, false).is_empty());
+    }
+
+    #[test]
+    fn schema_restricts_pins_to_the_reported_enum() {
+        let pins = parse_board_pins(nucleo_caps());
+        let spec = capability_tool_specs("nucleo-f401re", &pins, false).remove(0);
+        let transport = Arc::new(MockPeripheral {
+            reply: String::new(),
+        });
+        let tool = build_tool(spec, "nucleo-f401re", transport);
+        let schema = tool.parameters_schema();
+        assert_eq!(
+            schema["properties"]["pin"]["enum"],
+            json!([0, 1, 2, 3, 4, 5])
+        );
+    }
+
+    #[tokio::test]
+    async fn analog_read_reports_raw_and_millivolts() {
+        let pins = parse_board_pins(nucleo_caps());
+        let spec = capability_tool_specs("nucleo-f401re", &pins, false).remove(0);
+        let transport = Arc::new(MockPeripheral {
+            reply: r#"{"raw":2048,"millivolts":1650}"#.to_string(),
+        });
+        let tool = build_tool(spec, "nucleo-f401re", transport);
+
+        let result = tool.execute(json!({ "pin": 0 })).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "raw 2048 (1650 mV)");
+
+        let err = tool.execute(json!({ "pin": 7 })).await.unwrap_err();
+        assert!(err.to_string().contains("no ADC channel"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn pwm_write_appends_duty_percent_and_rejects_unknown_pins() {
+        let pins = parse_board_pins(nucleo_caps());
+        let spec = capability_tool_specs("nucleo-f401re", &pins, false).remove(1);
+        let transport = Arc::new(MockPeripheral {
+            reply: "pwm set".to_string(),
+        });
+        let tool = build_tool(spec, "nucleo-f401re", transport);
+
+        let result = tool
+            .execute(json!({ "pin": 9, "frequency_hz": 50, "duty": 75 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "pwm set (duty 7.5%)");
+
+        let err = tool
+            .execute(json!({ "pin": 4, "frequency_hz": 50, "duty": 75 }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not PWM-capable"), "got: {err}");
+    }
+}
@@ -0,0 +1,298 @@
+//! Flash ZeroClaw ESP32 firmware via espflash.
+//!
+//! Builds the `firmware/esp32` crate (or takes a prebuilt binary), installs
+//! espflash through cargo if it is missing — mirroring the arduino-cli
+//! bootstrap — then flashes and optionally watches the serial port for the
+//! "firmware ready" banner. Each stage (build, flash, verify) fails with its
+//! own message so the exit code points at the right step.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Target triple from `firmware/esp32/.cargo/config.toml`.
+const TARGET: &str = "riscv32imc-esp-espidf";
+/// Banner the firmware logs once its command loop is up.
+const READY_BANNER: &str = "firmware ready";
+/// How long the post-flash monitor window waits for the banner.
+const VERIFY_WINDOW_SECS: u64 = 10;
+
+/// Check if espflash is available.
+pub fn espflash_available() -> bool {
+    Command::new("espflash")
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Try to install espflash via cargo. Returns Ok(()) if installed or present.
+pub fn ensure_espflash() -> Result<()> {
+    if espflash_available() {
+        return Ok(());
+    }
+
+    println!("espflash not found. Installing via cargo...");
+    let status = Command::new("cargo")
+        .args(["install", "espflash", "--locked"])
+        .status()
+        .context("Failed to run cargo install espflash")?;
+    if !status.success() {
+        anyhow::bail!(
+            "cargo install espflash failed. Install manually: https://github.com/esp-rs/espflash"
+        );
+    }
+    println!("espflash installed.");
+    if !espflash_available() {
+        anyhow::bail!("espflash still not found after install. Ensure ~/.cargo/bin is in PATH.");
+    }
+    Ok(())
+}
+
+/// Pick the first serial port whose registry match is an ESP32 family entry.
+/// Input is (port_path, board_name) pairs from the discover scan.
+fn select_esp32_port(candidates: &[(String, Option<String>)]) -> Option<String> {
+    candidates
+        .iter()
+        .find(|(_, board)| board.as_deref().is_some_and(|b| b.starts_with("esp32")))
+        .map(|(port, _)| port.clone())
+}
+
+/// Auto-detect an ESP32 serial port from the hardware discover VID/PID table.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn detect_esp32_port() -> Option<String> {
+    let candidates: Vec<(String, Option<String>)> =
+        crate::hardware::discover::scan_serial_devices()
+            .into_iter()
+            .map(|d| (d.port_path, d.board_name))
+            .collect();
+    select_esp32_port(&candidates)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn detect_esp32_port() -> Option<String> {
+    None
+}
+
+/// Cargo arguments for building the firmware crate.
+fn build_args(release: bool) -> Vec<&'static str> {
+    if release {
+        vec!["build", "--release"]
+    } else {
+        vec!["build"]
+    }
+}
+
+/// Where the built firmware ELF lands for the given profile.
+fn built_binary_path(firmware_dir: &Path, release: bool) -> PathBuf {
+    firmware_dir
+        .join("target")
+        .join(TARGET)
+        .join(if release { "release" } else { "debug" })
+        .join("esp32")
+}
+
+/// espflash invocation for a binary and optional port.
+fn espflash_args(binary: &Path, port: Option<&str>) -> Vec<String> {
+    let mut args = vec!["flash".to_string()];
+    if let Some(port) = port {
+        args.push("--port".to_string());
+        args.push(port.to_string());
+    }
+    args.push(binary.to_string_lossy().into_owned());
+    args
+}
+
+/// Build the `firmware/esp32` crate and return the ELF path.
+fn build_esp32_firmware(release: bool) -> Result<PathBuf> {
+    let repo_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let firmware_dir = repo_root.join("firmware").join("esp32");
+    if !firmware_dir.join("Cargo.toml").exists() {
+        anyhow::bail!(
+            "ESP32 firmware not found at {}. Run from zeroclaw repo root.",
+            firmware_dir.display()
+        );
+    }
+
+    println!("Building ZeroClaw ESP32 firmware...");
+    let build = Command::new("cargo")
+        .args(build_args(release))
+        .current_dir(&firmware_dir)
+        .output()
+        .context("cargo build failed to run")?;
+    if !build.status.success() {
+        let stderr = String::from_utf8_lossy(&build.stderr);
+        anyhow::bail!(
+            "Build failed:\n{}\n\n\
+             The ESP32 toolchain comes from espup (cargo install espup; espup install).",
+            stderr
+        );
+    }
+
+    let elf_path = built_binary_path(&firmware_dir, release);
+    if !elf_path.exists() {
+        anyhow::bail!("Built binary not found at {}", elf_path.display());
+    }
+    Ok(elf_path)
+}
+
+/// Watch the serial port briefly for the firmware's ready banner.
+async fn verify_ready_banner(port: &str) -> Result<()> {
+    use tokio::io::AsyncReadExt;
+    use tokio_serial::SerialPortBuilderExt;
+
+    println!("Waiting for firmware banner on {port} (up to {VERIFY_WINDOW_SECS}s)...");
+    let mut serial = tokio_serial::new(port, 115_200)
+        .open_native_async()
+        .with_context(|| format!("Verify failed: could not open {port}"))?;
+
+    let mut seen = String::new();
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(VERIFY_WINDOW_SECS);
+    let mut buf = [0u8; 256];
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            anyhow::bail!(
+                "Verify failed: no \"{READY_BANNER}\" banner within {VERIFY_WINDOW_SECS}s. \
+                 The flash may still be fine — press the board's reset button and check manually."
+            );
+        }
+        match tokio::time::timeout(remaining, serial.read(&mut buf)).await {
+            // Nothing (or a timeout slice) read: loop back to the deadline check.
+            Ok(Ok(0)) | Err(_) => {}
+            Ok(Ok(n)) => {
+                seen.push_str(&String::from_utf8_lossy(&buf[..n]));
+                if seen.contains(READY_BANNER) {
+                    println!("Firmware is up: ready banner received.");
+                    return Ok(());
+                }
+            }
+            Ok(Err(e)) => {
+                anyhow::bail!("Verify failed: error reading {port}: {e}");
+            }
+        }
+    }
+}
+
+/// Flash ZeroClaw ESP32 firmware: build (unless a prebuilt binary is given),
+/// espflash, then verify the ready banner over serial.
+pub async fn flash_esp32_firmware(
+    port: Option<&str>,
+    release: bool,
+    binary: Option<&str>,
+) -> Result<()> {
+    ensure_espflash()?;
+
+    let elf_path = match binary {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if !path.exists() {
+                anyhow::bail!("Prebuilt binary not found at {}", path.display());
+            }
+            path
+        }
+        None => build_esp32_firmware(release)?,
+    };
+
+    let port = match port {
+        Some(p) => Some(p.to_string()),
+        None => {
+            let detected = detect_esp32_port();
+            if let Some(p) = &detected {
+                println!("Auto-detected ESP32 on {p}.");
+            }
+            detected
+        }
+    };
+
+    println!(
+        "Flashing {} via espflash{}...",
+        elf_path.display(),
+        port.as_deref()
+            .map(|p| format!(" (port {p})"))
+            .unwrap_or_default()
+    );
+    let flash = Command::new("espflash")
+        .args(espflash_args(&elf_path, port.as_deref()))
+        .output()
+        .context("espflash failed to run")?;
+    if !flash.status.success() {
+        let stderr = String::from_utf8_lossy(&flash.stderr);
+        anyhow::bail!(
+            "Flash failed:\n{}\n\n\
+             Ensure the board is connected; some boards need BOOT held during flashing.",
+            stderr
+        );
+    }
+    println!("ZeroClaw ESP32 firmware flashed successfully.");
+
+    match &port {
+        Some(p) => verify_ready_banner(p).await?,
+        None => println!("No port known for verification; skipping banner check."),
+    }
+
+    println!("The ESP32 now supports: ping, capabilities, gpio_read, gpio_write, sleep.");
+    println!(
+        "Add to config.toml: board = \"esp32\", transport = \"serial\", path = \"/dev/ttyUSB0\""
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_args_toggle_release() {
+        assert_eq!(build_args(false), vec!["build"]);
+        assert_eq!(build_args(true), vec!["build", "--release"]);
+    }
+
+    #[test]
+    fn built_binary_path_follows_profile() {
+        let debug = built_binary_path(Path::new("/repo/firmware/esp32"), false);
+        assert!(debug.ends_with("target/riscv32imc-esp-espidf/debug/esp32"));
+        let release = built_binary_path(Path::new("/repo/firmware/esp32"), true);
+        assert!(release.ends_with("target/riscv32imc-esp-espidf/release/esp32"));
+    }
+
+    #[test]
+    fn espflash_args_include_port_only_when_known() {
+        let bare = espflash_args(Path::new("/tmp/esp32.elf"), None);
+        assert_eq!(bare, vec!["flash", "/tmp/esp32.elf"]);
+        let with_port = espflash_args(Path::new("/tmp/esp32.elf"), Some("/dev/ttyUSB0"));
+        assert_eq!(
+            with_port,
+            vec!["flash", "--port", "/dev/ttyUSB0", "/tmp/esp32.elf"]
+        );
+    }
+
+    #[test]
+    fn port_auto_selection_prefers_esp32_family_matches() {
+        let candidates = vec![
+            (
+                "/dev/ttyACM0".to_string(),
+                Some("nucleo-f401re".to_string()),
+            ),
+            ("/dev/ttyUSB0".to_string(), None),
+            ("/dev/ttyUSB1".to_string(), Some("esp32".to_string())),
+            ("/dev/ttyACM1".to_string(), Some("esp32-s3".to_string())),
+        ];
+        assert_eq!(
+            select_esp32_port(&candidates).as_deref(),
+            Some("/dev/ttyUSB1")
+        );
+    }
+
+    #[test]
+    fn port_auto_selection_yields_none_without_esp32() {
+        let candidates = vec![
+            (
+                "/dev/ttyACM0".to_string(),
+                Some("nucleo-f401re".to_string()),
+            ),
+            ("/dev/ttyUSB0".to_string(), None),
+        ];
+        assert_eq!(select_esp32_port(&candidates), None);
+    }
+}
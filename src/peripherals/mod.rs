@@ -7,19 +7,33 @@ pub mod traits;
 
 #[cfg(feature = "hardware")]
 pub mod serial;
+#[cfg(feature = "hardware")]
+pub mod tcp;
 
 #[cfg(feature = "hardware")]
 pub mod arduino_flash;
 #[cfg(feature = "hardware")]
 pub mod arduino_upload;
 #[cfg(feature = "hardware")]
+pub mod bme280;
+#[cfg(feature = "hardware")]
 pub mod capabilities_tool;
 #[cfg(feature = "hardware")]
+pub mod capability_tools;
+#[cfg(feature = "hardware")]
+pub mod esp32_flash;
+#[cfg(feature = "hardware")]
 pub mod nucleo_flash;
 #[cfg(feature = "hardware")]
+pub mod queue;
+#[cfg(feature = "hardware")]
+pub mod sleep_tool;
+#[cfg(feature = "hardware")]
 pub mod uno_q_bridge;
 #[cfg(feature = "hardware")]
 pub mod uno_q_setup;
+#[cfg(feature = "hardware")]
+pub mod watch;
 
 #[cfg(all(feature = "peripheral-rpi", target_os = "linux"))]
 pub mod rpi;
@@ -27,7 +41,7 @@ pub mod rpi;
 #[cfg(any(feature = "hardware", feature = "peripheral-rpi"))]
 pub use traits::Peripheral;
 
-use crate::config::{Config, PeripheralBoardConfig, PeripheralsConfig};
+use crate::config::{Config, PeripheralBoardConfig, PeripheralBoardMetadata, PeripheralsConfig};
 #[cfg(feature = "hardware")]
 use crate::tools::HardwareMemoryMapTool;
 use crate::tools::Tool;
@@ -65,36 +79,106 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 println!("Configured peripherals:");
                 for b in boards {
                     let path = b.path.as_deref().unwrap_or("(native)");
-                    println!("  {}  {}  {}", b.board, b.transport, path);
+                    println!(
+                        "  {}  {}  {}{}",
+                        b.board,
+                        b.transport,
+                        path,
+                        board_version_suffix(b).await
+                    );
+                    if let Some(meta) = &b.metadata {
+                        println!(
+                            "      gpio {:?}, pwm {:?}, adc {:?}, led_pin {:?}{}",
+                            meta.gpio,
+                            meta.pwm,
+                            meta.adc,
+                            meta.led_pin,
+                            meta.fw_version
+                                .as_deref()
+                                .map(|fw| format!(", fw {fw}"))
+                                .unwrap_or_default()
+                        );
+                        if let Some(verified_at) = &meta.verified_at {
+                            println!("      {}", staleness_hint(verified_at));
+                        }
+                    }
                 }
             }
         }
-        crate::PeripheralCommands::Add { board, path } => {
-            let transport = if path == "native" { "native" } else { "serial" };
+        crate::PeripheralCommands::Add {
+            board,
+            path,
+            force,
+            secret,
+        } => {
+            let transport = if path == "native" {
+                "native"
+            } else if path.starts_with("tcp://") {
+                "tcp"
+            } else {
+                "serial"
+            };
             let path_opt = if path == "native" {
                 None
             } else {
                 Some(path.clone())
             };
 
+            if secret.is_some() && transport != "tcp" {
+                anyhow::bail!("--secret only applies to tcp:// boards");
+            }
+            let candidate = PeripheralBoardConfig {
+                board: board.clone(),
+                transport: transport.to_string(),
+                path: path_opt.clone(),
+                baud: 115_200,
+                metadata: None,
+                secret: secret.clone(),
+            };
+
+            // Probe the board before persisting, so typos and dead cables
+            // surface at add time instead of at the first tool call.
+            let metadata = resolve_add_probe(probe_for_add(&candidate).await, force)?;
+            if let Some(meta) = &metadata {
+                println!(
+                    "Probed {}: gpio {:?}, pwm {:?}, adc {:?}, led_pin {:?}{}",
+                    board,
+                    meta.gpio,
+                    meta.pwm,
+                    meta.adc,
+                    meta.led_pin,
+                    meta.fw_version
+                        .as_deref()
+                        .map(|fw| format!(", fw {fw}"))
+                        .unwrap_or_default()
+                );
+            }
+
             let mut cfg = Box::pin(crate::config::Config::load_or_init()).await?;
             cfg.peripherals.enabled = true;
 
-            if cfg
+            if let Some(existing) = cfg
                 .peripherals
                 .boards
-                .iter()
-                .any(|b| b.board == board && b.path.as_deref() == path_opt.as_deref())
+                .iter_mut()
+                .find(|b| b.board == board && b.path.as_deref() == path_opt.as_deref())
             {
-                println!("Board {} at {:?} already configured.", board, path_opt);
+                // Already registered: refresh the stored metadata (and
+                // secret, when given) instead of duplicating the entry.
+                if metadata.is_some() {
+                    existing.metadata = metadata;
+                }
+                if secret.is_some() {
+                    existing.secret = secret.clone();
+                }
+                cfg.save().await?;
+                println!("Board {} at {:?} refreshed.", board, path_opt);
                 return Ok(());
             }
 
             cfg.peripherals.boards.push(PeripheralBoardConfig {
-                board: board.clone(),
-                transport: transport.to_string(),
-                path: path_opt,
-                baud: 115_200,
+                metadata,
+                ..candidate
             });
             cfg.save().await?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
@@ -131,28 +215,61 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
             println!("Nucleo flash requires the 'hardware' feature.");
             println!("Build with: cargo build --features hardware");
         }
+        #[cfg(feature = "hardware")]
+        crate::PeripheralCommands::FlashEsp32 {
+            port,
+            release,
+            binary,
+        } => {
+            esp32_flash::flash_esp32_firmware(port.as_deref(), release, binary.as_deref()).await?;
+        }
+        #[cfg(not(feature = "hardware"))]
+        crate::PeripheralCommands::FlashEsp32 { .. } => {
+            println!("ESP32 flash requires the 'hardware' feature.");
+            println!("Build with: cargo build --features hardware");
+        }
     }
     Ok(())
 }
 
 /// Create and connect peripherals from config, returning their tools.
 /// Returns empty vec if peripherals disabled or hardware feature off.
+/// The security policy gates side-effecting tools like `sleep_device`.
 #[cfg(feature = "hardware")]
-pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
-    if !config.enabled || config.boards.is_empty() {
+pub async fn create_peripheral_tools(
+    config: &Config,
+    security: &std::sync::Arc<crate::security::SecurityPolicy>,
+) -> Result<Vec<Box<dyn Tool>>> {
+    let peripherals = &config.peripherals;
+    if !peripherals.enabled || peripherals.boards.is_empty() {
         return Ok(Vec::new());
     }
 
     let mut tools: Vec<Box<dyn Tool>> = Vec::new();
-    let mut serial_transports: Vec<(String, std::sync::Arc<serial::SerialTransport>)> = Vec::new();
+    let mut transports: Vec<(String, std::sync::Arc<dyn traits::CommandTransport>)> = Vec::new();
 
-    for board in &config.boards {
+    for board in &peripherals.boards {
         // Arduino Uno Q: Bridge transport (socket to local Bridge app)
         if board.transport == "bridge" && (board.board == "arduino-uno-q" || board.board == "uno-q")
         {
             tools.push(Box::new(uno_q_bridge::UnoQGpioReadTool));
             tools.push(Box::new(uno_q_bridge::UnoQGpioWriteTool));
-            tracing::info!(board = %board.board, "Uno Q Bridge GPIO tools added");
+            // Analog/PWM need a v2 bridge; a confirmed v1 deployment gets
+            // only the GPIO set, while an unreachable bridge (not started
+            // yet) optimistically gets the full set.
+            match uno_q_bridge::bridge_version().await {
+                Ok(v) if v < uno_q_bridge::BRIDGE_V2 => {
+                    tracing::info!(
+                        board = %board.board,
+                        "Uno Q bridge is v{v} (GPIO only); re-run 'zeroclaw peripheral setup-uno-q' for analog/PWM"
+                    );
+                }
+                _ => {
+                    tools.push(Box::new(uno_q_bridge::UnoQAnalogReadTool));
+                    tools.push(Box::new(uno_q_bridge::UnoQPwmWriteTool));
+                }
+            }
+            tracing::info!(board = %board.board, "Uno Q Bridge tools added");
             continue;
         }
 
@@ -173,6 +290,37 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
             continue;
         }
 
+        // TCP transport (ESP32 over WiFi); reconnects with backoff on demand
+        if board.transport == "tcp" {
+            match tcp::TcpPeripheral::from_config(board) {
+                Ok(mut peripheral) => {
+                    if let Err(e) = peripheral.connect().await {
+                        tracing::warn!(
+                            "Peripheral {} unreachable ({}); will retry on demand",
+                            peripheral.name(),
+                            e
+                        );
+                    }
+                    // Queue actor serializes concurrent tool calls onto the
+                    // socket; everything below talks to the queue, not the
+                    // raw transport.
+                    let queued: std::sync::Arc<dyn traits::CommandTransport> =
+                        std::sync::Arc::new(queue::QueuedTransport::new(
+                            board.board.clone(),
+                            peripheral.transport(),
+                            peripherals.queue_depth,
+                        ));
+                    transports.push((board.board.clone(), queued.clone()));
+                    tools.extend(serial::protocol_tools(queued));
+                    tracing::info!(board = %board.board, "TCP peripheral added");
+                }
+                Err(e) => {
+                    tracing::warn!("Invalid TCP peripheral {}: {}", board.board, e);
+                }
+            }
+            continue;
+        }
+
         // Serial transport (STM32, ESP32, Arduino, etc.)
         if board.transport != "serial" {
             continue;
@@ -188,8 +336,15 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
                 if p.connect().await.is_err() {
                     tracing::warn!("Peripheral {} connect warning (continuing)", p.name());
                 }
-                serial_transports.push((board.board.clone(), p.transport()));
-                tools.extend(p.tools());
+                // As with TCP: one queue actor per board fronts the port.
+                let queued: std::sync::Arc<dyn traits::CommandTransport> =
+                    std::sync::Arc::new(queue::QueuedTransport::new(
+                        board.board.clone(),
+                        p.transport() as std::sync::Arc<dyn traits::CommandTransport>,
+                        peripherals.queue_depth,
+                    ));
+                transports.push((board.board.clone(), queued.clone()));
+                tools.extend(serial::protocol_tools(queued));
                 if board.board == "arduino-uno" {
                     if let Some(ref path) = board.path {
                         tools.push(Box::new(arduino_upload::ArduinoUploadTool::new(
@@ -208,7 +363,7 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
 
     // Phase B: Add hardware tools when any boards configured
     if !tools.is_empty() {
-        let board_names: Vec<String> = config.boards.iter().map(|b| b.board.clone()).collect();
+        let board_names: Vec<String> = peripherals.boards.iter().map(|b| b.board.clone()).collect();
         tools.push(Box::new(HardwareMemoryMapTool::new(board_names.clone())));
         tools.push(Box::new(crate::tools::HardwareBoardInfoTool::new(
             board_names.clone(),
@@ -218,10 +373,49 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
         )));
     }
 
-    // Phase C: Add hardware_capabilities tool when any serial boards
-    if !serial_transports.is_empty() {
+    // Capability-driven per-board tools: boards advertising ADC or PWM pin
+    // lists get schema-restricted analog/pwm tools, so the model only sees
+    // pins the firmware actually reported.
+    let multiple_boards = transports.len() > 1;
+    for (board_name, transport) in &transports {
+        let Ok(result) = transport.capabilities().await else {
+            continue;
+        };
+        if !result.success {
+            continue;
+        }
+        let pins = capability_tools::parse_board_pins(&result.output);
+        for spec in capability_tools::capability_tool_specs(board_name, &pins, multiple_boards) {
+            tracing::info!(board = %board_name, tool = %spec.name, "capability tool added");
+            tools.push(capability_tools::build_tool(
+                spec,
+                board_name,
+                transport.clone(),
+            ));
+        }
+    }
+
+    // Phase C: Add hardware_capabilities tool when any serial/TCP boards
+    if !transports.is_empty() {
+        tools.push(Box::new(sleep_tool::SleepDeviceTool::new(
+            transports.clone(),
+            security.clone(),
+        )));
+
+        // Pin-watch tools share one manager so the concurrency limit and
+        // daemon shutdown apply across boards.
+        let manager = watch::PinWatchManager::new(config.clone());
+        tools.push(Box::new(watch::PeripheralWatchTool::new(
+            transports.clone(),
+            manager.clone(),
+        )));
+        tools.push(Box::new(watch::PeripheralWatchListTool::new(
+            manager.clone(),
+        )));
+        tools.push(Box::new(watch::PeripheralWatchCancelTool::new(manager)));
+
         tools.push(Box::new(capabilities_tool::HardwareCapabilitiesTool::new(
-            serial_transports,
+            transports,
         )));
     }
 
@@ -230,10 +424,22 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
 
 #[cfg(not(feature = "hardware"))]
 #[allow(clippy::unused_async)]
-pub async fn create_peripheral_tools(_config: &PeripheralsConfig) -> Result<Vec<Box<dyn Tool>>> {
+pub async fn create_peripheral_tools(
+    _config: &Config,
+    _security: &std::sync::Arc<crate::security::SecurityPolicy>,
+) -> Result<Vec<Box<dyn Tool>>> {
     Ok(Vec::new())
 }
 
+/// Abort all active pin watches; called from daemon shutdown.
+#[cfg(feature = "hardware")]
+pub fn shutdown_watches() {
+    watch::shutdown_all();
+}
+
+#[cfg(not(feature = "hardware"))]
+pub fn shutdown_watches() {}
+
 /// Create probe-rs / static board info tools (hardware_board_info, hardware_memory_map,
 /// hardware_memory_read). These use USB/probe-rs or static datasheet data — they never
 /// open a serial port, so they are safe to register regardless of the `hardware` feature.
@@ -259,6 +465,234 @@ pub fn create_board_info_tools(_config: &PeripheralsConfig) -> Vec<Box<dyn Tool>
     Vec::new()
 }
 
+/// Turn an add-time probe outcome into the metadata to persist. A failed
+/// probe is fatal unless `--force` was passed, in which case the board is
+/// added without metadata.
+fn resolve_add_probe(
+    probe: Result<Option<PeripheralBoardMetadata>>,
+    force: bool,
+) -> Result<Option<PeripheralBoardMetadata>> {
+    match probe {
+        Ok(metadata) => Ok(metadata),
+        Err(e) if force => {
+            println!("Probe failed ({e}); adding anyway (--force).");
+            Ok(None)
+        }
+        Err(e) => Err(anyhow::anyhow!(
+            "Probe failed: {e}. Check the connection and path, or re-run with --force to add anyway."
+        )),
+    }
+}
+
+/// Probe a board for `peripheral add`: open its transport, `ping` it and
+/// read `capabilities` into persisted metadata. Transports without a wire
+/// protocol (native GPIO, bridge) have nothing to probe and yield `None`.
+#[cfg(feature = "hardware")]
+async fn probe_for_add(board: &PeripheralBoardConfig) -> Result<Option<PeripheralBoardMetadata>> {
+    match board.transport.as_str() {
+        "serial" => {
+            let peripheral = serial::SerialPeripheral::connect(board).await?;
+            let transport = peripheral.transport();
+            transport.negotiate().await;
+            Ok(Some(probe_board_metadata(&*transport).await?))
+        }
+        "tcp" => {
+            let mut peripheral = tcp::TcpPeripheral::from_config(board)?;
+            peripheral.connect().await?;
+            Ok(Some(probe_board_metadata(&*peripheral.transport()).await?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Without the hardware feature no transport can be opened, so `add`
+/// keeps its historic probe-free behavior.
+#[cfg(not(feature = "hardware"))]
+#[allow(clippy::unused_async)]
+async fn probe_for_add(_board: &PeripheralBoardConfig) -> Result<Option<PeripheralBoardMetadata>> {
+    Ok(None)
+}
+
+/// `ping` then `capabilities` over an open transport, parsed into the
+/// metadata persisted on the board's config entry.
+#[cfg(feature = "hardware")]
+pub(crate) async fn probe_board_metadata(
+    transport: &dyn traits::CommandTransport,
+) -> Result<PeripheralBoardMetadata> {
+    let ping = transport.request("ping", serde_json::json!({})).await?;
+    if !ping.success {
+        anyhow::bail!(
+            "ping failed: {}",
+            ping.error.as_deref().unwrap_or("no response")
+        );
+    }
+    let caps = transport.capabilities().await?;
+    if !caps.success {
+        anyhow::bail!(
+            "capabilities failed: {}",
+            caps.error.as_deref().unwrap_or("no response")
+        );
+    }
+    Ok(metadata_from_capabilities(&caps.output))
+}
+
+/// Parse a `capabilities` result into board metadata, stamping the
+/// verification time.
+#[cfg(feature = "hardware")]
+pub(crate) fn metadata_from_capabilities(capabilities: &str) -> PeripheralBoardMetadata {
+    let pins = capability_tools::parse_board_pins(capabilities);
+    let info = serial::parse_firmware_info(capabilities);
+    let parsed: serde_json::Value = serde_json::from_str(capabilities).unwrap_or_default();
+    PeripheralBoardMetadata {
+        gpio: parsed
+            .get("gpio")
+            .and_then(serde_json::Value::as_array)
+            .map(|a| a.iter().filter_map(serde_json::Value::as_u64).collect())
+            .unwrap_or_default(),
+        pwm: pins.pwm,
+        adc: pins.adc,
+        led_pin: parsed.get("led_pin").and_then(serde_json::Value::as_u64),
+        fw_version: info.fw_version,
+        verified_at: Some(chrono::Utc::now().to_rfc3339()),
+    }
+}
+
+/// Human staleness hint for a stored `verified_at` timestamp.
+fn staleness_hint(verified_at: &str) -> String {
+    let Ok(ts) = chrono::DateTime::parse_from_rfc3339(verified_at) else {
+        return format!("verified {verified_at}");
+    };
+    let days = chrono::Utc::now()
+        .signed_duration_since(ts.with_timezone(&chrono::Utc))
+        .num_days()
+        .max(0);
+    match days {
+        0 => "verified today".to_string(),
+        1 => "verified yesterday".to_string(),
+        d if d > 30 => format!("verified {d} days ago — re-run 'peripheral add' to refresh"),
+        d => format!("verified {d} days ago"),
+    }
+}
+
+/// Connect to one configured board and read its firmware identity from
+/// `capabilities` (also cross-checking the protocol version against the
+/// host). Opens a fresh connection, so only interactive CLI paths
+/// (`peripheral list`, `hardware introspect`) should call it.
+#[cfg(feature = "hardware")]
+pub async fn probe_firmware_info(board: &PeripheralBoardConfig) -> Result<serial::FirmwareInfo> {
+    let label = format!(
+        "{} ({})",
+        board.board,
+        board.path.as_deref().unwrap_or("native")
+    );
+    match board.transport.as_str() {
+        "serial" => {
+            let peripheral = serial::SerialPeripheral::connect(board).await?;
+            let transport = peripheral.transport();
+            transport.negotiate().await;
+            Ok(serial::check_firmware_version(&*transport, &label).await)
+        }
+        "tcp" => {
+            let mut peripheral = tcp::TcpPeripheral::from_config(board)?;
+            peripheral.connect().await?;
+            Ok(serial::check_firmware_version(&*peripheral.transport(), &label).await)
+        }
+        other => anyhow::bail!("no firmware probe for '{other}' transport"),
+    }
+}
+
+/// Best-effort firmware identity suffix for a `peripheral list` row.
+/// Unreachable boards and firmware without the fields get no suffix.
+#[cfg(feature = "hardware")]
+async fn board_version_suffix(board: &PeripheralBoardConfig) -> String {
+    let Ok(info) = probe_firmware_info(board).await else {
+        return String::new();
+    };
+    match (info.fw_version, info.protocol_version) {
+        (Some(fw), Some(v)) => format!("  fw {fw} (protocol v{v})"),
+        (Some(fw), None) => format!("  fw {fw}"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(not(feature = "hardware"))]
+#[allow(clippy::unused_async)]
+async fn board_version_suffix(_board: &PeripheralBoardConfig) -> String {
+    String::new()
+}
+
+/// Probe each configured serial/TCP board and report its negotiated wire
+/// protocol and firmware version, as `(board label, mode)` rows for
+/// `zeroclaw doctor`. Opens a fresh connection per board; unreachable
+/// boards report the error instead.
+#[cfg(feature = "hardware")]
+pub async fn doctor_protocol_report(config: &PeripheralsConfig) -> Vec<(String, String)> {
+    fn describe(negotiated: u8, info: &serial::FirmwareInfo) -> String {
+        let base = match info.protocol_version {
+            Some(v) if !(serial::PROTOCOL_V1..=serial::PROTOCOL_V2).contains(&v) => format!(
+                "unsupported: firmware protocol v{v}, host speaks v{}-v{}",
+                serial::PROTOCOL_V1,
+                serial::PROTOCOL_V2
+            ),
+            _ if negotiated >= serial::PROTOCOL_V2 => "v2 (crc32 framing)".to_string(),
+            _ => "v1 (plain, no crc)".to_string(),
+        };
+        match &info.fw_version {
+            Some(fw) => format!("{base}, fw {fw}"),
+            None => base,
+        }
+    }
+
+    let mut rows = Vec::new();
+    if !config.enabled {
+        return rows;
+    }
+    for board in &config.boards {
+        let label = format!(
+            "{} ({})",
+            board.board,
+            board.path.as_deref().unwrap_or("native")
+        );
+        let mode = match board.transport.as_str() {
+            "serial" => match serial::SerialPeripheral::connect(board).await {
+                Ok(peripheral) => {
+                    let transport = peripheral.transport();
+                    transport.negotiate().await;
+                    let info = serial::check_firmware_version(&*transport, &label).await;
+                    describe(
+                        traits::CommandTransport::protocol_version(&*transport),
+                        &info,
+                    )
+                }
+                Err(e) => format!("unreachable: {e}"),
+            },
+            "tcp" => match tcp::TcpPeripheral::from_config(board) {
+                Ok(mut peripheral) => match peripheral.connect().await {
+                    Ok(()) => {
+                        let transport = peripheral.transport();
+                        let info = serial::check_firmware_version(&*transport, &label).await;
+                        describe(
+                            traits::CommandTransport::protocol_version(&*transport),
+                            &info,
+                        )
+                    }
+                    Err(e) => format!("unreachable: {e}"),
+                },
+                Err(e) => format!("invalid: {e}"),
+            },
+            other => format!("{other} transport (no protocol negotiation)"),
+        };
+        rows.push((label, mode));
+    }
+    rows
+}
+
+#[cfg(not(feature = "hardware"))]
+#[allow(clippy::unused_async)]
+pub async fn doctor_protocol_report(_config: &PeripheralsConfig) -> Vec<(String, String)> {
+    Vec::new()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,8 +707,13 @@ mod tests {
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                metadata: None,
+                secret: None,
             }],
             datasheet_dir: None,
+            robot: crate::config::RobotPeripheralConfig::default(),
+            watch: crate::config::PeripheralWatchConfig::default(),
+            queue_depth: 16,
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -293,15 +732,22 @@ mod tests {
                     transport: "serial".into(),
                     path: Some("/dev/ttyACM0".into()),
                     baud: 115_200,
+                    metadata: None,
+                    secret: None,
                 },
                 PeripheralBoardConfig {
                     board: "rpi-gpio".into(),
                     transport: "native".into(),
                     path: None,
                     baud: 115_200,
+                    metadata: None,
+                    secret: None,
                 },
             ],
             datasheet_dir: None,
+            robot: crate::config::RobotPeripheralConfig::default(),
+            watch: crate::config::PeripheralWatchConfig::default(),
+            queue_depth: 16,
         };
         let result = list_configured_boards(&config);
         assert_eq!(result.len(), 2);
@@ -315,6 +761,9 @@ mod tests {
             enabled: true,
             boards: vec![],
             datasheet_dir: None,
+            robot: crate::config::RobotPeripheralConfig::default(),
+            watch: crate::config::PeripheralWatchConfig::default(),
+            queue_depth: 16,
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -323,14 +772,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn staleness_hint_tracks_age() {
+        assert_eq!(
+            staleness_hint(&chrono::Utc::now().to_rfc3339()),
+            "verified today"
+        );
+        let old = (chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        assert!(staleness_hint(&old).contains("45 days ago"), "got: {old}");
+        assert!(staleness_hint("not-a-date").contains("not-a-date"));
+    }
+
+    /// Mock transport for the add-time probe: answers `ping` and
+    /// `capabilities` from a script.
+    #[cfg(feature = "hardware")]
+    struct ScriptedProbe {
+        ping_ok: bool,
+        caps: &'static str,
+    }
+
+    #[cfg(feature = "hardware")]
+    #[async_trait::async_trait]
+    impl traits::CommandTransport for ScriptedProbe {
+        async fn request(
+            &self,
+            cmd: &str,
+            _args: serde_json::Value,
+        ) -> Result<crate::tools::traits::ToolResult> {
+            let (success, output, error) = match cmd {
+                "ping" if self.ping_ok => (true, "pong".to_string(), None),
+                "ping" => (false, String::new(), Some("no response".to_string())),
+                "capabilities" => (true, self.caps.to_string(), None),
+                other => (
+                    false,
+                    String::new(),
+                    Some(format!("Unknown command: {other}")),
+                ),
+            };
+            Ok(crate::tools::traits::ToolResult {
+                success,
+                output,
+                error,
+            })
+        }
+    }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn add_probe_persists_pins_firmware_and_timestamp() {
+        let transport = ScriptedProbe {
+            ping_ok: true,
+            caps: r#"{"gpio":[2,3,4],"pwm":[3,5],"adc":[0,1],"led_pin":13,"fw_version":"0.1.0"}"#,
+        };
+        let meta = probe_board_metadata(&transport).await.unwrap();
+        assert_eq!(meta.gpio, vec![2, 3, 4]);
+        assert_eq!(meta.pwm, vec![3, 5]);
+        assert_eq!(meta.adc, vec![0, 1]);
+        assert_eq!(meta.led_pin, Some(13));
+        assert_eq!(meta.fw_version.as_deref(), Some("0.1.0"));
+        assert!(meta.verified_at.is_some());
+    }
+
+    #[cfg(feature = "hardware")]
+    #[tokio::test]
+    async fn failed_probe_requires_force() {
+        let transport = ScriptedProbe {
+            ping_ok: false,
+            caps: "{}",
+        };
+
+        let probe = probe_board_metadata(&transport).await.map(Some);
+        let err = resolve_add_probe(probe, false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "got: {err}");
+
+        let probe = probe_board_metadata(&transport).await.map(Some);
+        assert!(resolve_add_probe(probe, true).unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn create_peripheral_tools_returns_empty_when_disabled() {
-        let config = PeripheralsConfig {
-            enabled: false,
-            boards: vec![],
-            datasheet_dir: None,
+        let config = Config {
+            peripherals: PeripheralsConfig {
+                enabled: false,
+                ..PeripheralsConfig::default()
+            },
+            ..Config::default()
         };
-        let tools = create_peripheral_tools(&config).await.unwrap();
+        let security = std::sync::Arc::new(crate::security::SecurityPolicy::default());
+        let tools = create_peripheral_tools(&config, &security).await.unwrap();
         assert!(
             tools.is_empty(),
             "disabled peripherals should produce no tools"
@@ -77,7 +77,7 @@ pub fn flash_nucleo_firmware() -> Result<()> {
     }
 
     println!("ZeroClaw Nucleo firmware flashed successfully.");
-    println!("The Nucleo now supports: ping, capabilities, gpio_read, gpio_write.");
+    println!("The Nucleo now supports: ping, capabilities, gpio_mode, gpio_read, gpio_write, pwm_write, analog_read, i2c_read, i2c_write, batch.");
     println!("Add to config.toml: board = \"nucleo-f401re\", transport = \"serial\", path = \"/dev/ttyACM0\"");
     Ok(())
 }
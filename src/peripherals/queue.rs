@@ -0,0 +1,321 @@
+//! Per-board command queue — serializes concurrent callers onto one device.
+//!
+//! Several tools can hit the same board at once (agent loop, watch poller,
+//! heartbeat). The queue actor owns the underlying transport and works
+//! through submissions one at a time: callers enqueue a command with a
+//! oneshot for the answer, the actor applies a per-command timeout, and a
+//! full queue rejects new work with a "peripheral busy" error instead of
+//! piling up unboundedly. Unsolicited device lines (wake notices, future
+//! event pushes) are routed onto a broadcast bus rather than being matched
+//! against a pending request. The actor is also the single place where
+//! per-board command/error counts are tracked for observability.
+
+use super::traits::CommandTransport;
+use crate::tools::traits::ToolResult;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// Upper bound for one queued command, on top of the transport's own
+/// per-frame timeouts (which retry; this does not).
+const QUEUE_COMMAND_TIMEOUT_SECS: u64 = 30;
+
+/// An unsolicited line from a device — a JSON object with an `"event"`
+/// field and no `"id"`, like the wake notice after deep sleep.
+#[derive(Debug, Clone)]
+pub struct DeviceEvent {
+    pub event: String,
+    pub reason: Option<String>,
+}
+
+/// Process-wide bus for device events. Lagging subscribers lose old
+/// events rather than blocking the transports.
+fn event_bus() -> &'static broadcast::Sender<DeviceEvent> {
+    static BUS: OnceLock<broadcast::Sender<DeviceEvent>> = OnceLock::new();
+    BUS.get_or_init(|| broadcast::channel(64).0)
+}
+
+/// Publish an unsolicited device line onto the event bus. Called by the
+/// transports when a non-response line arrives mid-request.
+pub(crate) fn publish_device_event(event: &str, reason: Option<&str>) {
+    let _ = event_bus().send(DeviceEvent {
+        event: event.to_string(),
+        reason: reason.map(String::from),
+    });
+}
+
+/// Subscribe to unsolicited device events (wake notices, etc.).
+pub fn subscribe_device_events() -> broadcast::Receiver<DeviceEvent> {
+    event_bus().subscribe()
+}
+
+/// Command/error counters for one board's queue; the hook point for
+/// commands/sec and error-rate metrics.
+#[derive(Default)]
+pub(crate) struct QueueStats {
+    commands: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl QueueStats {
+    pub(crate) fn commands(&self) -> u64 {
+        self.commands.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+}
+
+struct Submission {
+    cmd: String,
+    args: Value,
+    respond: oneshot::Sender<anyhow::Result<ToolResult>>,
+}
+
+/// [`CommandTransport`] front that funnels all requests for one board
+/// through a single actor task, so concurrent callers never interleave
+/// on the wire.
+pub(crate) struct QueuedTransport {
+    board: String,
+    depth: usize,
+    submit: mpsc::Sender<Submission>,
+    /// Kept for protocol-version delegation; all requests go via the actor.
+    inner: Arc<dyn CommandTransport>,
+    stats: Arc<QueueStats>,
+}
+
+impl QueuedTransport {
+    pub(crate) fn new(board: String, inner: Arc<dyn CommandTransport>, depth: usize) -> Self {
+        Self::with_timeout(
+            board,
+            inner,
+            depth,
+            Duration::from_secs(QUEUE_COMMAND_TIMEOUT_SECS),
+        )
+    }
+
+    fn with_timeout(
+        board: String,
+        inner: Arc<dyn CommandTransport>,
+        depth: usize,
+        timeout: Duration,
+    ) -> Self {
+        let depth = depth.max(1);
+        let (submit, mut rx) = mpsc::channel::<Submission>(depth);
+        let stats = Arc::new(QueueStats::default());
+        let actor_inner = inner.clone();
+        let actor_stats = stats.clone();
+        let actor_board = board.clone();
+        tokio::spawn(async move {
+            while let Some(sub) = rx.recv().await {
+                let result =
+                    match tokio::time::timeout(timeout, actor_inner.request(&sub.cmd, sub.args))
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(_) => Err(anyhow::anyhow!(
+                            "Command '{}' on {} timed out after {}s in the queue",
+                            sub.cmd,
+                            actor_board,
+                            timeout.as_secs()
+                        )),
+                    };
+                actor_stats.commands.fetch_add(1, Ordering::Relaxed);
+                if !result.as_ref().is_ok_and(|r| r.success) {
+                    actor_stats.errors.fetch_add(1, Ordering::Relaxed);
+                }
+                // The caller may have given up (dropped the oneshot); the
+                // command already ran, so there is nothing to roll back.
+                let _ = sub.respond.send(result);
+            }
+        });
+        Self {
+            board,
+            depth,
+            submit,
+            inner,
+            stats,
+        }
+    }
+
+    pub(crate) fn stats(&self) -> &QueueStats {
+        &self.stats
+    }
+}
+
+#[async_trait]
+impl CommandTransport for QueuedTransport {
+    async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+        let (respond, rx) = oneshot::channel();
+        self.submit
+            .try_send(Submission {
+                cmd: cmd.to_string(),
+                args,
+                respond,
+            })
+            .map_err(|e| match e {
+                mpsc::error::TrySendError::Full(_) => anyhow::anyhow!(
+                    "Peripheral {} busy: {} commands already queued",
+                    self.board,
+                    self.depth
+                ),
+                mpsc::error::TrySendError::Closed(_) => {
+                    anyhow::anyhow!("Command queue for {} has stopped", self.board)
+                }
+            })?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Command queue for {} dropped the request", self.board))?
+    }
+
+    fn protocol_version(&self) -> u8 {
+        self.inner.protocol_version()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Echoes the command and its `n` argument back after a short delay,
+    /// and panics if two requests ever overlap.
+    struct EchoTransport {
+        in_flight: std::sync::Mutex<bool>,
+    }
+
+    impl EchoTransport {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                in_flight: std::sync::Mutex::new(false),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CommandTransport for EchoTransport {
+        async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+            {
+                let mut guard = self.in_flight.lock().unwrap();
+                assert!(!*guard, "two commands hit the transport concurrently");
+                *guard = true;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            *self.in_flight.lock().unwrap() = false;
+            Ok(ToolResult {
+                success: true,
+                output: format!("{cmd}:{}", args["n"]),
+                error: None,
+            })
+        }
+    }
+
+    /// Accepts a request and never answers.
+    struct StuckTransport;
+
+    #[async_trait]
+    impl CommandTransport for StuckTransport {
+        async fn request(&self, _cmd: &str, _args: Value) -> anyhow::Result<ToolResult> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn fifty_concurrent_callers_never_cross_wires() {
+        let queued = Arc::new(QueuedTransport::new(
+            "nucleo-f401re".into(),
+            EchoTransport::new(),
+            64,
+        ));
+
+        let mut handles = Vec::new();
+        for n in 0..50 {
+            let queued = queued.clone();
+            handles.push(tokio::spawn(async move {
+                let result = queued
+                    .request(&format!("cmd{n}"), json!({ "n": n }))
+                    .await
+                    .unwrap();
+                assert!(result.success);
+                assert_eq!(result.output, format!("cmd{n}:{n}"));
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(queued.stats().commands(), 50);
+        assert_eq!(queued.stats().errors(), 0);
+    }
+
+    #[tokio::test]
+    async fn full_queue_rejects_with_peripheral_busy() {
+        let queued = Arc::new(QueuedTransport::new(
+            "esp32".into(),
+            Arc::new(StuckTransport),
+            2,
+        ));
+
+        // First request is pulled by the actor and sticks there; the next
+        // two fill the queue to its depth.
+        for _ in 0..3 {
+            let queued = queued.clone();
+            tokio::spawn(async move {
+                let _ = queued.request("ping", json!({})).await;
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let err = queued.request("ping", json!({})).await.unwrap_err();
+        assert!(
+            err.to_string().contains("busy: 2 commands already queued"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn stuck_command_times_out_in_the_queue() {
+        let queued = QueuedTransport::with_timeout(
+            "esp32".into(),
+            Arc::new(StuckTransport),
+            4,
+            Duration::from_millis(50),
+        );
+        let err = queued.request("ping", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "got: {err}");
+        assert_eq!(queued.stats().errors(), 1);
+    }
+
+    #[tokio::test]
+    async fn device_errors_count_toward_the_error_rate() {
+        struct Failing;
+
+        #[async_trait]
+        impl CommandTransport for Failing {
+            async fn request(&self, _cmd: &str, _args: Value) -> anyhow::Result<ToolResult> {
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Invalid pin".into()),
+                })
+            }
+        }
+
+        let queued = QueuedTransport::new("esp32".into(), Arc::new(Failing), 4);
+        let result = queued.request("gpio_read", json!({ "pin": 99 })).await;
+        assert!(!result.unwrap().success);
+        assert_eq!(queued.stats().commands(), 1);
+        assert_eq!(queued.stats().errors(), 1);
+    }
+
+    #[tokio::test]
+    async fn unsolicited_lines_reach_event_subscribers() {
+        let mut rx = subscribe_device_events();
+        publish_device_event("wake", Some("timer"));
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event, "wake");
+        assert_eq!(event.reason.as_deref(), Some("timer"));
+    }
+}
@@ -2,16 +2,25 @@
 //!
 //! Protocol: newline-delimited JSON.
 //! Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
+//!           {"id":"2","cmd":"pwm_write","args":{"pin":9,"frequency_hz":50,"duty":75}}
 //! Response: {"id":"1","ok":true,"result":"done"}
+//!
+//! Integrity (protocol v2, negotiated via `protocol_hello`): both sides
+//! append an optional `"crc"` field — the CRC32 of the frame without that
+//! field — and the host retransmits a request (same `id`) on mismatch or
+//! timeout. Firmware that does not answer `protocol_hello` keeps speaking
+//! plain v1, so old boards continue to work.
 
 use crate::config::PeripheralBoardConfig;
+use crate::peripherals::traits::CommandTransport;
 use crate::peripherals::Peripheral;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
-use portable_atomic::{AtomicU64, Ordering};
+use portable_atomic::{AtomicBool, AtomicU64, Ordering};
 use serde_json::{json, Value};
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::sync::Mutex;
 use tokio_serial::{SerialPortBuilderExt, SerialStream};
 
@@ -22,84 +31,537 @@ const ALLOWED_PATH_PREFIXES: &[&str] = &[
     "/dev/tty.usbmodem",
     "/dev/cu.usbmodem",
     "/dev/tty.usbserial",
-    "/dev/cu.usbserial", // Arduino Uno (FTDI), clones
-    "COM",               // Windows
+    "/dev/cu.usbserial",  // Arduino Uno (FTDI), clones
+    "/dev/serial/by-id/", // Linux stable aliases, survive re-enumeration
+    "COM",                // Windows
 ];
 
 fn is_path_allowed(path: &str) -> bool {
     ALLOWED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
 }
 
-/// JSON request/response over serial.
-async fn send_request(port: &mut SerialStream, cmd: &str, args: Value) -> anyhow::Result<Value> {
+/// Legacy plain protocol version; v2 adds CRC32 framing. Each firmware
+/// duplicates the current version as `PROTOCOL_VERSION` (no crate is
+/// shared across std, no_std and C); a test cross-checks the copies.
+pub(crate) const PROTOCOL_V1: u8 = 1;
+pub(crate) const PROTOCOL_V2: u8 = 2;
+
+/// Transmissions per request before giving up (timeout or corrupted frame).
+const REQUEST_ATTEMPTS: u32 = 3;
+
+/// Reconnect backoff after a device vanished: 2s, 4s, 8s, ... capped, so a
+/// dead device does not stall every tool call with a blocking open.
+const RECONNECT_BACKOFF_BASE_SECS: u64 = 1;
+const RECONNECT_BACKOFF_CAP_SECS: u64 = 30;
+
+/// Exponential-backoff bookkeeping for reopening a vanished device.
+#[derive(Default)]
+struct ReconnectBackoff {
+    /// Consecutive failed reopen attempts since the device vanished.
+    failures: u32,
+    /// Earliest time another reopen may be attempted.
+    retry_at: Option<std::time::Instant>,
+}
+
+impl ReconnectBackoff {
+    /// Time left inside the backoff window, or `None` once a reopen may
+    /// be attempted again.
+    fn wait_remaining(&self, now: std::time::Instant) -> Option<Duration> {
+        self.retry_at
+            .and_then(|at| at.checked_duration_since(now))
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Record a failed reopen and schedule the next attempt. Returns the
+    /// consecutive failure count.
+    fn register_failure(&mut self, now: std::time::Instant) -> u32 {
+        self.failures = self.failures.saturating_add(1);
+        let delay =
+            (RECONNECT_BACKOFF_BASE_SECS << self.failures.min(5)).min(RECONNECT_BACKOFF_CAP_SECS);
+        self.retry_at = Some(now + Duration::from_secs(delay));
+        self.failures
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Find the stable `/dev/serial/by-id` alias for a device path (Linux).
+/// The alias points at the same device even after a replug moves it to a
+/// different `/dev/ttyACM*` number, so reconnection falls back to it.
+#[cfg(target_os = "linux")]
+fn stable_by_id_path(path: &str) -> Option<String> {
+    let device = std::fs::canonicalize(path).ok()?;
+    std::fs::read_dir("/dev/serial/by-id")
+        .ok()?
+        .flatten()
+        .find(|entry| std::fs::canonicalize(entry.path()).is_ok_and(|target| target == device))
+        .map(|entry| entry.path().to_string_lossy().into_owned())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn stable_by_id_path(_path: &str) -> Option<String> {
+    None
+}
+
+/// Monotonic request id, shared by all transports so retransmissions and
+/// stale responses can be told apart by id alone.
+fn next_id() -> String {
     static ID: AtomicU64 = AtomicU64::new(0);
-    let id = ID.fetch_add(1, Ordering::Relaxed);
-    let id_str = id.to_string();
+    ID.fetch_add(1, Ordering::Relaxed).to_string()
+}
 
-    let req = json!({
-        "id": id_str,
-        "cmd": cmd,
-        "args": args
-    });
-    let line = format!("{}\n", req);
+/// CRC32 (IEEE, bit-reflected), bitwise — frames are short, no table needed.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
 
-    port.write_all(line.as_bytes()).await?;
-    port.flush().await?;
+/// Append a `,"crc":N` field (CRC32 of the payload without it) before the
+/// final `}` of a serialized JSON object.
+pub(crate) fn add_crc(payload: &str) -> String {
+    let crc = crc32(payload.as_bytes());
+    let body = &payload[..payload.len() - 1];
+    format!("{body},\"crc\":{crc}}}")
+}
 
+/// Split off a trailing `,"crc":N` field, verify it against the re-closed
+/// payload, and return the payload. Lines without the field pass through
+/// unchanged (legacy v1 peers).
+pub(crate) fn strip_and_verify_crc(line: &str) -> anyhow::Result<String> {
+    let Some(idx) = line.rfind(",\"crc\":") else {
+        return Ok(line.to_string());
+    };
+    let digits = line
+        .strip_suffix('}')
+        .map(|rest| &rest[idx + 7..])
+        .ok_or_else(|| anyhow::anyhow!("Malformed crc frame (no closing brace)"))?;
+    let claimed: u32 = digits
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Malformed crc field: {digits:?}"))?;
+    let payload = format!("{}}}", &line[..idx]);
+    let actual = crc32(payload.as_bytes());
+    if actual != claimed {
+        anyhow::bail!("CRC mismatch: frame claims {claimed}, computed {actual}");
+    }
+    Ok(payload)
+}
+
+/// Read one newline-terminated frame.
+async fn read_line<S: AsyncRead + Unpin>(port: &mut S) -> anyhow::Result<String> {
     let mut buf = Vec::new();
     let mut b = [0u8; 1];
-    while port.read_exact(&mut b).await.is_ok() {
+    loop {
+        if port.read_exact(&mut b).await.is_err() {
+            if buf.is_empty() {
+                anyhow::bail!("Connection closed");
+            }
+            break;
+        }
         if b[0] == b'\n' {
             break;
         }
         buf.push(b[0]);
     }
-    let line_str = String::from_utf8_lossy(&buf);
-    let resp: Value = serde_json::from_str(line_str.trim())?;
-    let resp_id = resp["id"].as_str().unwrap_or("");
-    if resp_id != id_str {
-        anyhow::bail!("Response id mismatch: expected {}, got {}", id_str, resp_id);
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// One exchange: write the request, read frames until the response with a
+/// matching id arrives. Responses for other ids (stale answers to earlier
+/// timed-out attempts) are skipped.
+pub(crate) async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    port: &mut S,
+    id_str: &str,
+    cmd: &str,
+    args: Value,
+    use_crc: bool,
+) -> anyhow::Result<Value> {
+    let req = json!({
+        "id": id_str,
+        "cmd": cmd,
+        "args": args
+    });
+    let mut payload = req.to_string();
+    if use_crc {
+        payload = add_crc(&payload);
+    }
+    port.write_all(format!("{payload}\n").as_bytes()).await?;
+    port.flush().await?;
+
+    loop {
+        let line = read_line(port).await?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let payload = strip_and_verify_crc(line)?;
+        if let Some((event, reason)) = parse_device_event(&payload) {
+            // Unsolicited device event (e.g. wake after deep sleep) —
+            // route it onto the event bus, then keep waiting for our
+            // response rather than mismatching it.
+            tracing::info!(
+                "peripheral event: {event}{}",
+                reason
+                    .as_deref()
+                    .map(|r| format!(" ({r})"))
+                    .unwrap_or_default()
+            );
+            crate::peripherals::queue::publish_device_event(&event, reason.as_deref());
+            continue;
+        }
+        let resp: Value = serde_json::from_str(&payload)?;
+        if resp["id"].as_str().unwrap_or("") == id_str {
+            return Ok(resp);
+        }
+    }
+}
+
+/// Parse an unsolicited device event line — a JSON object with an
+/// `"event"` field and no `"id"`, like the wake notice an ESP32 emits
+/// after deep sleep: `{"event":"wake","reason":"timer"}`. Reasons for
+/// `wake`: `timer`, `gpio`, `other`. Returns `(event, reason)`.
+pub(crate) fn parse_device_event(payload: &str) -> Option<(String, Option<String>)> {
+    let parsed: Value = serde_json::from_str(payload).ok()?;
+    if parsed.get("id").is_some() {
+        return None;
+    }
+    let event = parsed.get("event")?.as_str()?.to_string();
+    let reason = parsed
+        .get("reason")
+        .and_then(Value::as_str)
+        .map(String::from);
+    Some((event, reason))
+}
+
+/// Request with retransmission: the same id is resent up to
+/// [`REQUEST_ATTEMPTS`] times on timeout or a corrupted frame.
+pub(crate) async fn request_with_retry<S: AsyncRead + AsyncWrite + Unpin>(
+    port: &mut S,
+    cmd: &str,
+    args: Value,
+    use_crc: bool,
+    timeout: Duration,
+) -> anyhow::Result<Value> {
+    let id_str = next_id();
+    let mut last_err = None;
+    for _ in 0..REQUEST_ATTEMPTS {
+        match tokio::time::timeout(timeout, exchange(port, &id_str, cmd, args.clone(), use_crc))
+            .await
+        {
+            Ok(Ok(resp)) => return Ok(resp),
+            Ok(Err(e)) => last_err = Some(e),
+            Err(_) => {
+                last_err = Some(anyhow::anyhow!(
+                    "request timed out after {}s",
+                    timeout.as_secs()
+                ));
+            }
+        }
+    }
+    Err(anyhow::anyhow!(
+        "Request '{}' failed after {} attempts: {}",
+        cmd,
+        REQUEST_ATTEMPTS,
+        last_err.map(|e| e.to_string()).unwrap_or_default()
+    ))
+}
+
+/// Map a protocol response into a ToolResult.
+pub(crate) fn parse_response(resp: &Value) -> ToolResult {
+    let ok = resp["ok"].as_bool().unwrap_or(false);
+    let result = resp["result"]
+        .as_str()
+        .map(String::from)
+        .unwrap_or_else(|| resp["result"].to_string());
+    let error = resp["error"].as_str().map(String::from);
+
+    ToolResult {
+        success: ok,
+        output: result,
+        error,
     }
-    Ok(resp)
 }
 
 /// Shared serial transport for tools. Pub(crate) for capabilities tool.
 pub(crate) struct SerialTransport {
     port: Mutex<SerialStream>,
+    /// Set once `protocol_hello` negotiated CRC32 framing (protocol v2).
+    crc: AtomicBool,
+    /// Device path and baud rate, kept so a vanished device (deep sleep,
+    /// re-enumeration) can be re-opened on the next request.
+    path: String,
+    baud: u32,
+    /// Stable `/dev/serial/by-id` alias resolved at connect time (Linux);
+    /// reopen falls back to it when a replug renamed the device node.
+    by_id_path: Option<String>,
+    /// Backoff state between reopen attempts while the device is gone.
+    reconnect: std::sync::Mutex<ReconnectBackoff>,
 }
 
 /// Timeout for serial request/response (seconds).
 const SERIAL_TIMEOUT_SECS: u64 = 5;
 
+/// Shared `protocol_hello` negotiation: advertise CRC support and return
+/// whether the peer confirmed it. Firmware that predates the handshake
+/// answers "Unknown command" (or nothing), which leaves us on plain v1.
+pub(crate) async fn negotiate_crc<S: AsyncRead + AsyncWrite + Unpin>(
+    port: &mut S,
+    timeout: Duration,
+) -> bool {
+    let hello = tokio::time::timeout(
+        timeout,
+        exchange(
+            port,
+            &next_id(),
+            "protocol_hello",
+            json!({ "version": 2, "crc": true }),
+            false,
+        ),
+    )
+    .await;
+    let Ok(Ok(resp)) = hello else {
+        return false;
+    };
+    if !resp["ok"].as_bool().unwrap_or(false) {
+        return false;
+    }
+    // Result is a nested JSON string: {"version":2,"crc":true}
+    resp["result"]
+        .as_str()
+        .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        .is_some_and(|v| v["crc"].as_bool().unwrap_or(false))
+}
+
+/// Firmware identity as reported in the `capabilities` response. Firmware
+/// that predates the fields leaves both empty.
+pub struct FirmwareInfo {
+    pub fw_version: Option<String>,
+    pub protocol_version: Option<u8>,
+}
+
+/// Pull `fw_version` / `protocol_version` out of a (nested JSON string)
+/// `capabilities` result.
+pub(crate) fn parse_firmware_info(capabilities: &str) -> FirmwareInfo {
+    let parsed: Value = serde_json::from_str(capabilities).unwrap_or(Value::Null);
+    FirmwareInfo {
+        fw_version: parsed
+            .get("fw_version")
+            .and_then(Value::as_str)
+            .map(String::from),
+        protocol_version: parsed
+            .get("protocol_version")
+            .and_then(Value::as_u64)
+            .and_then(|v| u8::try_from(v).ok()),
+    }
+}
+
+/// Query `capabilities` and cross-check the firmware's protocol version
+/// against what this host speaks. A mismatch is logged and flagged on the
+/// `peripherals` health component so `zeroclaw doctor` surfaces it;
+/// firmware that omits the fields passes silently.
+pub(crate) async fn check_firmware_version(
+    transport: &dyn CommandTransport,
+    label: &str,
+) -> FirmwareInfo {
+    let capabilities = match transport.request("capabilities", json!({})).await {
+        Ok(r) if r.success => r.output,
+        _ => {
+            return FirmwareInfo {
+                fw_version: None,
+                protocol_version: None,
+            }
+        }
+    };
+    note_firmware_info(label, &capabilities)
+}
+
+/// Parse firmware identity out of a `capabilities` result and flag a
+/// protocol-version skew on the `peripherals` health component. Shared by
+/// the connect handshake and the post-reconnect handshake.
+pub(crate) fn note_firmware_info(label: &str, capabilities: &str) -> FirmwareInfo {
+    let info = parse_firmware_info(capabilities);
+    match info.protocol_version {
+        Some(v) if !(PROTOCOL_V1..=PROTOCOL_V2).contains(&v) => {
+            let side = if v > PROTOCOL_V2 {
+                "the host"
+            } else {
+                "the firmware"
+            };
+            tracing::warn!(
+                "{label}: firmware speaks protocol v{v}, host supports v{PROTOCOL_V1}-v{PROTOCOL_V2} — update {side}"
+            );
+            crate::health::mark_component_error(
+                "peripherals",
+                format!(
+                    "{label}: firmware protocol v{v}, host supports v{PROTOCOL_V1}-v{PROTOCOL_V2}"
+                ),
+            );
+        }
+        _ => crate::health::mark_component_ok("peripherals"),
+    }
+    info
+}
+
 impl SerialTransport {
+    /// Run the `protocol_hello` handshake; silently stays on v1 if the
+    /// firmware does not understand it.
+    pub(crate) async fn negotiate(&self) {
+        let mut port = self.port.lock().await;
+        let crc = negotiate_crc(&mut *port, Duration::from_secs(SERIAL_TIMEOUT_SECS)).await;
+        self.crc.store(crc, Ordering::Relaxed);
+    }
+
+    /// Re-open the serial device after it disappeared (deep sleep,
+    /// re-enumeration). Tries the configured path first, then the stable
+    /// by-id alias in case a replug renamed the device node. The fresh
+    /// link starts on plain v1, since the device rebooted and forgot the
+    /// CRC mode.
+    async fn reopen(&self) -> anyhow::Result<()> {
+        let port = match tokio_serial::new(&self.path, self.baud).open_native_async() {
+            Ok(port) => port,
+            Err(primary) => match &self.by_id_path {
+                Some(alias) => tokio_serial::new(alias, self.baud)
+                    .open_native_async()
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to reopen {} ({primary}) or {alias} ({e})",
+                            self.path
+                        )
+                    })?,
+                None => anyhow::bail!("Failed to reopen {}: {primary}", self.path),
+            },
+        };
+        *self.port.lock().await = port;
+        self.crc.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Bring a vanished device back: honor the backoff window, reopen the
+    /// port, then re-run the protocol and capabilities handshakes before
+    /// the link serves commands again. Reconnects count as restarts on the
+    /// `peripheral:<path>` health component, which `zeroclaw doctor`
+    /// reports.
+    async fn reconnect(&self) -> anyhow::Result<()> {
+        let component = format!("peripheral:{}", self.path);
+        let now = std::time::Instant::now();
+        if let Some(wait) = self.reconnect.lock().unwrap().wait_remaining(now) {
+            anyhow::bail!(
+                "peripheral temporarily unavailable, reconnecting (next attempt in {}s)",
+                wait.as_secs().max(1)
+            );
+        }
+
+        if let Err(e) = self.reopen().await {
+            let failures = self
+                .reconnect
+                .lock()
+                .unwrap()
+                .register_failure(std::time::Instant::now());
+            crate::health::mark_component_error(&component, format!("reconnecting: {e}"));
+            anyhow::bail!(
+                "peripheral temporarily unavailable, reconnecting ({failures} failed attempts): {e}"
+            );
+        }
+
+        // The device rebooted: renegotiate and re-run the capabilities
+        // handshake before serving commands, mirroring the initial connect.
+        self.negotiate().await;
+        let handshake = {
+            let mut port = self.port.lock().await;
+            let use_crc = self.crc.load(Ordering::Relaxed);
+            request_with_retry(
+                &mut *port,
+                "capabilities",
+                json!({}),
+                use_crc,
+                Duration::from_secs(SERIAL_TIMEOUT_SECS),
+            )
+            .await
+        };
+        match handshake {
+            Ok(resp) => {
+                let result = parse_response(&resp);
+                if result.success {
+                    note_firmware_info(&self.path, &result.output);
+                }
+                self.reconnect.lock().unwrap().reset();
+                crate::health::bump_component_restart(&component);
+                crate::health::mark_component_ok(&component);
+                tracing::info!("{}: device reconnected", self.path);
+                Ok(())
+            }
+            Err(e) => {
+                let failures = self
+                    .reconnect
+                    .lock()
+                    .unwrap()
+                    .register_failure(std::time::Instant::now());
+                crate::health::mark_component_error(&component, format!("reconnecting: {e}"));
+                anyhow::bail!(
+                    "peripheral temporarily unavailable, reconnecting ({failures} failed attempts): port reopened but handshake failed: {e}"
+                );
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CommandTransport for SerialTransport {
     async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+        let attempt = {
+            let mut port = self.port.lock().await;
+            let use_crc = self.crc.load(Ordering::Relaxed);
+            request_with_retry(
+                &mut *port,
+                cmd,
+                args.clone(),
+                use_crc,
+                Duration::from_secs(SERIAL_TIMEOUT_SECS),
+            )
+            .await
+        };
+        let err = match attempt {
+            Ok(resp) => return Ok(parse_response(&resp)),
+            Err(e) => e,
+        };
+
+        // The device may have vanished mid-exchange (deep sleep, USB
+        // re-enumeration). Reconnect and retry once; while the device is
+        // still gone, callers get a clear "temporarily unavailable" error
+        // and the backoff window keeps the open attempts cheap.
+        tracing::warn!(
+            "{}: request '{cmd}' failed ({err}); attempting reconnect",
+            self.path
+        );
+        self.reconnect().await?;
+
         let mut port = self.port.lock().await;
-        let resp = tokio::time::timeout(
-            std::time::Duration::from_secs(SERIAL_TIMEOUT_SECS),
-            send_request(&mut port, cmd, args),
+        let use_crc = self.crc.load(Ordering::Relaxed);
+        let resp = request_with_retry(
+            &mut *port,
+            cmd,
+            args,
+            use_crc,
+            Duration::from_secs(SERIAL_TIMEOUT_SECS),
         )
-        .await
-        .map_err(|_| {
-            anyhow::anyhow!("Serial request timed out after {}s", SERIAL_TIMEOUT_SECS)
-        })??;
-
-        let ok = resp["ok"].as_bool().unwrap_or(false);
-        let result = resp["result"]
-            .as_str()
-            .map(String::from)
-            .unwrap_or_else(|| resp["result"].to_string());
-        let error = resp["error"].as_str().map(String::from);
-
-        Ok(ToolResult {
-            success: ok,
-            output: result,
-            error,
-        })
+        .await?;
+        Ok(parse_response(&resp))
     }
 
-    /// Phase C: fetch capabilities from device (gpio pins, led_pin).
-    pub async fn capabilities(&self) -> anyhow::Result<ToolResult> {
-        self.request("capabilities", json!({})).await
+    fn protocol_version(&self) -> u8 {
+        if self.crc.load(Ordering::Relaxed) {
+            PROTOCOL_V2
+        } else {
+            PROTOCOL_V1
+        }
     }
 }
 
@@ -121,7 +583,7 @@ impl SerialPeripheral {
 
         if !is_path_allowed(path) {
             anyhow::bail!(
-                "Serial path not allowed: {}. Allowed: /dev/ttyACM*, /dev/ttyUSB*, /dev/tty.usbmodem*, /dev/cu.usbmodem*",
+                "Serial path not allowed: {}. Allowed: /dev/ttyACM*, /dev/ttyUSB*, /dev/tty.usbmodem*, /dev/cu.usbmodem*, /dev/serial/by-id/*",
                 path
             );
         }
@@ -133,6 +595,11 @@ impl SerialPeripheral {
         let name = format!("{}-{}", config.board, path.replace('/', "_"));
         let transport = Arc::new(SerialTransport {
             port: Mutex::new(port),
+            crc: AtomicBool::new(false),
+            path: path.to_string(),
+            baud: config.baud,
+            by_id_path: stable_by_id_path(path),
+            reconnect: std::sync::Mutex::new(ReconnectBackoff::default()),
         });
 
         Ok(Self {
@@ -154,6 +621,8 @@ impl Peripheral for SerialPeripheral {
     }
 
     async fn connect(&mut self) -> anyhow::Result<()> {
+        self.transport.negotiate().await;
+        check_firmware_version(&*self.transport, &self.name).await;
         Ok(())
     }
 
@@ -170,14 +639,7 @@ impl Peripheral for SerialPeripheral {
     }
 
     fn tools(&self) -> Vec<Box<dyn Tool>> {
-        vec![
-            Box::new(GpioReadTool {
-                transport: self.transport.clone(),
-            }),
-            Box::new(GpioWriteTool {
-                transport: self.transport.clone(),
-            }),
-        ]
+        protocol_tools(self.transport.clone())
     }
 }
 
@@ -188,9 +650,92 @@ impl SerialPeripheral {
     }
 }
 
+/// Tools shared by every transport speaking the JSON protocol.
+pub(crate) fn protocol_tools(transport: Arc<dyn CommandTransport>) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(GpioModeTool {
+            transport: transport.clone(),
+        }),
+        Box::new(GpioReadTool {
+            transport: transport.clone(),
+        }),
+        Box::new(GpioWriteTool {
+            transport: transport.clone(),
+        }),
+        Box::new(PwmWriteTool {
+            transport: transport.clone(),
+        }),
+        Box::new(AnalogReadTool {
+            transport: transport.clone(),
+        }),
+        Box::new(ServoWriteTool {
+            transport: transport.clone(),
+        }),
+        Box::new(ToneTool {
+            transport: transport.clone(),
+        }),
+        Box::new(I2cTool {
+            transport: transport.clone(),
+        }),
+        Box::new(super::bme280::Bme280ReadTool { transport }),
+    ]
+}
+
+/// Tool: set GPIO pin direction.
+struct GpioModeTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+#[async_trait]
+impl Tool for GpioModeTool {
+    fn name(&self) -> &str {
+        "gpio_mode"
+    }
+
+    fn description(&self) -> &str {
+        "Configure a GPIO pin on a connected peripheral as input, input with pull-up, \
+         or output. Required before gpio_read (input) or gpio_write (output) on a pin."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "GPIO pin number"
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["input", "input_pullup", "output"],
+                    "description": "Pin direction; input_pullup enables the internal pull-up resistor"
+                }
+            },
+            "required": ["pin", "mode"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let mode = args
+            .get("mode")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'mode' parameter"))?;
+        if !matches!(mode, "input" | "input_pullup" | "output") {
+            anyhow::bail!("'mode' must be \"input\", \"input_pullup\" or \"output\", got {mode}");
+        }
+        self.transport
+            .request("gpio_mode", json!({ "pin": pin, "mode": mode }))
+            .await
+    }
+}
+
 /// Tool: read GPIO pin value.
 struct GpioReadTool {
-    transport: Arc<SerialTransport>,
+    transport: Arc<dyn CommandTransport>,
 }
 
 #[async_trait]
@@ -200,7 +745,8 @@ impl Tool for GpioReadTool {
     }
 
     fn description(&self) -> &str {
-        "Read the value (0 or 1) of a GPIO pin on a connected peripheral (e.g. STM32 Nucleo)"
+        "Read the value (0 or 1) of an input-configured GPIO pin on a connected peripheral \
+         (e.g. STM32 Nucleo). Configure the pin with gpio_mode first."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -229,7 +775,7 @@ impl Tool for GpioReadTool {
 
 /// Tool: write GPIO pin value.
 struct GpioWriteTool {
-    transport: Arc<SerialTransport>,
+    transport: Arc<dyn CommandTransport>,
 }
 
 #[async_trait]
@@ -239,7 +785,9 @@ impl Tool for GpioWriteTool {
     }
 
     fn description(&self) -> &str {
-        "Set a GPIO pin high (1) or low (0) on a connected peripheral (e.g. turn on/off LED)"
+        "Set a GPIO pin high (1) or low (0) on a connected peripheral (e.g. turn on/off LED). \
+         Pass 'operations' instead of pin/value to run several writes in one device \
+         round-trip (e.g. LED patterns)."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -253,13 +801,71 @@ impl Tool for GpioWriteTool {
                 "value": {
                     "type": "integer",
                     "description": "0 for low, 1 for high"
+                },
+                "operations": {
+                    "type": "array",
+                    "description": "Multiple writes, executed back-to-back on the device in one round-trip. Overrides pin/value.",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "pin": { "type": "integer" },
+                            "value": { "type": "integer" }
+                        },
+                        "required": ["pin", "value"]
+                    }
                 }
-            },
-            "required": ["pin", "value"]
+            }
         })
     }
 
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        if let Some(ops) = args.get("operations").and_then(|v| v.as_array()) {
+            if ops.is_empty() {
+                anyhow::bail!("'operations' must not be empty");
+            }
+            let mut commands = Vec::with_capacity(ops.len());
+            let mut pins = Vec::with_capacity(ops.len());
+            for op in ops {
+                let pin = op
+                    .get("pin")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'pin' in operations entry"))?;
+                let value = op
+                    .get("value")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'value' in operations entry"))?;
+                pins.push(pin);
+                commands.push((
+                    "gpio_write".to_string(),
+                    json!({ "pin": pin, "value": value }),
+                ));
+            }
+
+            let results = self.transport.batch(&commands).await?;
+            let success = results.iter().all(|r| r.success);
+            let output = pins
+                .iter()
+                .zip(&results)
+                .map(|(pin, r)| {
+                    if r.success {
+                        format!("pin {}: {}", pin, r.output)
+                    } else {
+                        format!(
+                            "pin {}: error - {}",
+                            pin,
+                            r.error.as_deref().unwrap_or("unknown")
+                        )
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Ok(ToolResult {
+                success,
+                output,
+                error: None,
+            });
+        }
+
         let pin = args
             .get("pin")
             .and_then(|v| v.as_u64())
@@ -273,3 +879,739 @@ impl Tool for GpioWriteTool {
             .await
     }
 }
+
+/// Tool: drive a PWM-capable pin (motors, servos, LED dimming).
+struct PwmWriteTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+#[async_trait]
+impl Tool for PwmWriteTool {
+    fn name(&self) -> &str {
+        "pwm_write"
+    }
+
+    fn description(&self) -> &str {
+        "Output a PWM signal on a timer-capable pin of a connected peripheral \
+         (servos, motor ESCs, LED dimming). Check 'capabilities' for which pins \
+         support PWM; the device reports the actually achieved frequency."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "PWM-capable pin number (Arduino-style, e.g. 3, 5, 6, 9 on Nucleo)"
+                },
+                "frequency_hz": {
+                    "type": "integer",
+                    "description": "PWM frequency in Hz (e.g. 50 for servos, 1000 for motors)"
+                },
+                "duty": {
+                    "type": "integer",
+                    "description": "Duty cycle in per mille, 0-1000 (e.g. 75 = 7.5%)"
+                }
+            },
+            "required": ["pin", "frequency_hz", "duty"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let frequency_hz = args
+            .get("frequency_hz")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'frequency_hz' parameter"))?;
+        let duty = args
+            .get("duty")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'duty' parameter"))?;
+        if duty > 1000 {
+            anyhow::bail!("'duty' is per mille (0-1000), got {duty}");
+        }
+        self.transport
+            .request(
+                "pwm_write",
+                json!({ "pin": pin, "frequency_hz": frequency_hz, "duty": duty }),
+            )
+            .await
+    }
+}
+
+/// Tool: sample an analog pin via the on-chip ADC.
+struct AnalogReadTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+#[async_trait]
+impl Tool for AnalogReadTool {
+    fn name(&self) -> &str {
+        "analog_read"
+    }
+
+    fn description(&self) -> &str {
+        "Sample an analog input pin (A0-A5) on a connected peripheral via its ADC. \
+         Returns the raw 12-bit reading and millivolts (e.g. potentiometers, battery dividers). \
+         Check 'capabilities' for which pins have an ADC channel."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "Analog pin number (0 for A0, 1 for A1, ...)"
+                }
+            },
+            "required": ["pin"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        self.transport
+            .request("analog_read", json!({ "pin": pin }))
+            .await
+    }
+}
+
+/// Tool: position a hobby servo via the device's Servo support.
+struct ServoWriteTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+#[async_trait]
+impl Tool for ServoWriteTool {
+    fn name(&self) -> &str {
+        "servo_write"
+    }
+
+    fn description(&self) -> &str {
+        "Move a hobby servo on a connected peripheral to an angle (0-180 degrees). \
+         The firmware attaches the servo on first use and keeps driving it; pins \
+         already used as plain GPIO are rejected. Check 'capabilities' for servo support."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "Digital pin the servo signal wire is on (e.g. 9)"
+                },
+                "angle": {
+                    "type": "integer",
+                    "description": "Target angle in degrees, 0-180"
+                }
+            },
+            "required": ["pin", "angle"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let angle = args
+            .get("angle")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'angle' parameter"))?;
+        if angle > 180 {
+            anyhow::bail!("'angle' is degrees (0-180), got {angle}");
+        }
+        self.transport
+            .request("servo_write", json!({ "pin": pin, "angle": angle }))
+            .await
+    }
+}
+
+/// Tool: square-wave tone output (piezo buzzers, speakers).
+struct ToneTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+#[async_trait]
+impl Tool for ToneTool {
+    fn name(&self) -> &str {
+        "tone"
+    }
+
+    fn description(&self) -> &str {
+        "Play a square-wave tone on a buzzer or speaker pin of a connected peripheral. \
+         Give 'frequency_hz' (31-65535) and an optional 'duration_ms'; omit \
+         'frequency_hz' to stop a tone that is still playing. Check 'capabilities' \
+         for tone support."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "Digital pin the buzzer/speaker is on (e.g. 8)"
+                },
+                "frequency_hz": {
+                    "type": "integer",
+                    "description": "Tone frequency in Hz, 31-65535; omit to stop the current tone"
+                },
+                "duration_ms": {
+                    "type": "integer",
+                    "description": "How long to play in milliseconds; omit to play until stopped"
+                }
+            },
+            "required": ["pin"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let Some(frequency_hz) = args.get("frequency_hz").and_then(|v| v.as_u64()) else {
+            return self
+                .transport
+                .request("no_tone", json!({ "pin": pin }))
+                .await;
+        };
+        if !(31..=65_535).contains(&frequency_hz) {
+            anyhow::bail!("'frequency_hz' must be 31-65535, got {frequency_hz}");
+        }
+        let mut request = json!({ "pin": pin, "frequency_hz": frequency_hz });
+        if let Some(duration_ms) = args.get("duration_ms").and_then(|v| v.as_u64()) {
+            request["duration_ms"] = json!(duration_ms);
+        }
+        self.transport.request("tone", request).await
+    }
+}
+
+/// Tool: raw transfers on the peripheral's I2C bus.
+struct I2cTool {
+    transport: Arc<dyn CommandTransport>,
+}
+
+/// Largest transfer the firmware bridges per command.
+const I2C_MAX_BYTES: usize = 32;
+
+#[async_trait]
+impl Tool for I2cTool {
+    fn name(&self) -> &str {
+        "i2c"
+    }
+
+    fn description(&self) -> &str {
+        "Read or write raw bytes on a connected peripheral's I2C bus (sensors, \
+         OLED displays, port expanders). action=read takes address/register/length \
+         and returns hex-encoded bytes; action=write takes address and a hex byte \
+         string (register first, e.g. \"F425\"). Check 'capabilities' for the bus pins."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["read", "write"],
+                    "description": "read a register or write raw bytes"
+                },
+                "address": {
+                    "type": "integer",
+                    "description": "7-bit I2C device address (0-127, e.g. 0x76 = 118 for BME280)"
+                },
+                "register": {
+                    "type": "integer",
+                    "description": "Register to read (0-255); read only"
+                },
+                "length": {
+                    "type": "integer",
+                    "description": "Bytes to read, 1-32 (default 1); read only"
+                },
+                "bytes": {
+                    "type": "string",
+                    "description": "Hex byte string to write, 1-32 bytes; write only"
+                }
+            },
+            "required": ["action", "address"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let address = args
+            .get("address")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'address' parameter"))?;
+        if address > 127 {
+            anyhow::bail!("'address' must be a 7-bit I2C address (0-127), got {address}");
+        }
+        match action {
+            "read" => {
+                let register = args
+                    .get("register")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'register' parameter"))?;
+                if register > 255 {
+                    anyhow::bail!("'register' must be 0-255, got {register}");
+                }
+                let length = args.get("length").and_then(|v| v.as_u64()).unwrap_or(1);
+                if !(1..=I2C_MAX_BYTES as u64).contains(&length) {
+                    anyhow::bail!("'length' must be 1-{I2C_MAX_BYTES}, got {length}");
+                }
+                self.transport
+                    .request(
+                        "i2c_read",
+                        json!({ "address": address, "register": register, "length": length }),
+                    )
+                    .await
+            }
+            "write" => {
+                let bytes = args
+                    .get("bytes")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'bytes' parameter"))?;
+                if bytes.is_empty()
+                    || bytes.len() % 2 != 0
+                    || bytes.len() > I2C_MAX_BYTES * 2
+                    || !bytes.bytes().all(|b| b.is_ascii_hexdigit())
+                {
+                    anyhow::bail!(
+                        "'bytes' must be a hex string of 1-{I2C_MAX_BYTES} bytes, got {bytes:?}"
+                    );
+                }
+                self.transport
+                    .request("i2c_write", json!({ "address": address, "bytes": bytes }))
+                    .await
+            }
+            other => anyhow::bail!("'action' must be \"read\" or \"write\", got {other}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{duplex, AsyncBufReadExt, BufReader, DuplexStream};
+
+    #[test]
+    fn crc32_matches_known_vector() {
+        // IEEE CRC32 check value for "123456789".
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn add_crc_roundtrips_through_verification() {
+        let payload = r#"{"id":"7","cmd":"ping","args":{}}"#;
+        let framed = add_crc(payload);
+        assert!(framed.contains(",\"crc\":"));
+        assert_eq!(strip_and_verify_crc(&framed).unwrap(), payload);
+    }
+
+    #[test]
+    fn corrupted_frame_fails_verification() {
+        let framed = add_crc(r#"{"id":"7","ok":true,"result":"pong"}"#);
+        let corrupted = framed.replace("pong", "pONg");
+        let err = strip_and_verify_crc(&corrupted).unwrap_err();
+        assert!(err.to_string().contains("CRC mismatch"), "got: {err}");
+    }
+
+    #[test]
+    fn legacy_line_without_crc_passes_through() {
+        let line = r#"{"id":"7","ok":true,"result":"pong"}"#;
+        assert_eq!(strip_and_verify_crc(line).unwrap(), line);
+    }
+
+    /// Device side of a duplex pipe: reads request lines, passes each to
+    /// `reply`, and writes whatever frames it returns.
+    fn spawn_device(
+        stream: DuplexStream,
+        mut reply: impl FnMut(Value) -> Vec<String> + Send + 'static,
+    ) {
+        tokio::spawn(async move {
+            let (reader, mut writer) = tokio::io::split(stream);
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let payload = strip_and_verify_crc(line.trim()).unwrap();
+                let req: Value = serde_json::from_str(&payload).unwrap();
+                for frame in reply(req) {
+                    if writer
+                        .write_all(format!("{frame}\n").as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn retransmits_same_id_after_corrupted_frame() {
+        let (mut host, device) = duplex(1024);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_by_device = seen.clone();
+        let mut calls = 0;
+        spawn_device(device, move |req| {
+            let id = req["id"].as_str().unwrap().to_string();
+            seen_by_device.try_lock().unwrap().push(id.clone());
+            calls += 1;
+            let resp = json!({ "id": id, "ok": true, "result": "pong" }).to_string();
+            if calls == 1 {
+                // First answer arrives corrupted on the wire.
+                vec![add_crc(&resp).replace("pong", "pONg")]
+            } else {
+                vec![add_crc(&resp)]
+            }
+        });
+
+        let resp = request_with_retry(&mut host, "ping", json!({}), true, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(resp["result"], "pong");
+
+        // The retransmission reused the original id.
+        let seen = seen.lock().await;
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0], seen[1]);
+    }
+
+    #[tokio::test]
+    async fn stale_response_for_another_id_is_skipped() {
+        let (mut host, device) = duplex(1024);
+        spawn_device(device, |req| {
+            let id = req["id"].as_str().unwrap();
+            vec![
+                // Late answer to an earlier, timed-out request.
+                json!({ "id": "stale-999", "ok": true, "result": "old" }).to_string(),
+                json!({ "id": id, "ok": true, "result": "pong" }).to_string(),
+            ]
+        });
+
+        let resp = request_with_retry(&mut host, "ping", json!({}), false, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(resp["result"], "pong");
+    }
+
+    #[tokio::test]
+    async fn silent_device_times_out_then_retransmission_recovers() {
+        let (mut host, device) = duplex(1024);
+        let mut calls = 0;
+        spawn_device(device, move |req| {
+            calls += 1;
+            if calls == 1 {
+                // Drop the first request on the floor.
+                vec![]
+            } else {
+                let resp = json!({ "id": req["id"], "ok": true, "result": "pong" }).to_string();
+                vec![resp]
+            }
+        });
+
+        let resp = request_with_retry(
+            &mut host,
+            "ping",
+            json!({}),
+            false,
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp["result"], "pong");
+    }
+
+    /// Transport double: records every request and answers with a canned
+    /// ToolResult, for exercising the batch() default implementation.
+    struct ScriptedTransport {
+        seen: std::sync::Mutex<Vec<(String, Value)>>,
+        reply: ToolResult,
+    }
+
+    impl ScriptedTransport {
+        fn replying(reply: ToolResult) -> Self {
+            Self {
+                seen: std::sync::Mutex::new(Vec::new()),
+                reply,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommandTransport for ScriptedTransport {
+        async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+            self.seen.lock().unwrap().push((cmd.to_string(), args));
+            Ok(self.reply.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_correlates_responses_by_sub_command_id() {
+        let reply = ToolResult {
+            success: true,
+            output: json!([
+                { "id": "b0", "ok": true, "result": "done" },
+                { "id": "b1", "ok": false, "result": "", "error": "Invalid pin 99" },
+            ])
+            .to_string(),
+            error: None,
+        };
+        let transport = ScriptedTransport::replying(reply);
+
+        let results = transport
+            .batch(&[
+                ("gpio_write".to_string(), json!({ "pin": 13, "value": 1 })),
+                ("gpio_write".to_string(), json!({ "pin": 99, "value": 0 })),
+            ])
+            .await
+            .unwrap();
+
+        // Partial failure is per sub-command, not one batch-wide error.
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert_eq!(results[0].output, "done");
+        assert!(!results[1].success);
+        assert_eq!(results[1].error.as_deref(), Some("Invalid pin 99"));
+
+        // One wire request carrying both sub-commands with derived ids.
+        let seen = transport.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, "batch");
+        let commands = seen[0].1["commands"].as_array().unwrap();
+        assert_eq!(commands[0]["id"], "b0");
+        assert_eq!(commands[1]["id"], "b1");
+    }
+
+    #[tokio::test]
+    async fn batch_rejects_misordered_or_short_responses() {
+        let misordered = ToolResult {
+            success: true,
+            output: json!([
+                { "id": "b1", "ok": true, "result": "done" },
+                { "id": "b0", "ok": true, "result": "done" },
+            ])
+            .to_string(),
+            error: None,
+        };
+        let commands = vec![
+            ("gpio_write".to_string(), json!({ "pin": 2, "value": 1 })),
+            ("gpio_write".to_string(), json!({ "pin": 4, "value": 1 })),
+        ];
+        let err = ScriptedTransport::replying(misordered)
+            .batch(&commands)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("out of order"), "got: {err}");
+
+        let short = ToolResult {
+            success: true,
+            output: json!([{ "id": "b0", "ok": true, "result": "done" }]).to_string(),
+            error: None,
+        };
+        let err = ScriptedTransport::replying(short)
+            .batch(&commands)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("1 responses for 2"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn gpio_write_operations_run_as_one_batch() {
+        let reply = ToolResult {
+            success: true,
+            output: json!([
+                { "id": "b0", "ok": true, "result": "done" },
+                { "id": "b1", "ok": false, "result": "", "error": "Pin 7 is configured as input; send gpio_mode output first" },
+            ])
+            .to_string(),
+            error: None,
+        };
+        let transport = Arc::new(ScriptedTransport::replying(reply));
+        let tool = GpioWriteTool {
+            transport: transport.clone(),
+        };
+
+        let result = tool
+            .execute(json!({ "operations": [
+                { "pin": 13, "value": 1 },
+                { "pin": 7, "value": 0 },
+            ]}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(
+            result.output.contains("pin 13: done"),
+            "got: {}",
+            result.output
+        );
+        assert!(
+            result.output.contains("pin 7: error"),
+            "got: {}",
+            result.output
+        );
+        assert_eq!(transport.seen.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn persistent_corruption_exhausts_attempts() {
+        let (mut host, device) = duplex(1024);
+        spawn_device(device, |req| {
+            let resp = json!({ "id": req["id"], "ok": true, "result": "pong" }).to_string();
+            vec![add_crc(&resp).replace("pong", "pONg")]
+        });
+
+        let err = request_with_retry(&mut host, "ping", json!({}), true, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"), "got: {err}");
+    }
+
+    #[test]
+    fn wake_event_lines_are_recognized() {
+        let (event, reason) = parse_device_event(r#"{"event":"wake","reason":"timer"}"#).unwrap();
+        assert_eq!(event, "wake");
+        assert_eq!(reason.as_deref(), Some("timer"));
+
+        // Responses (anything carrying an id) and junk are not events.
+        assert!(parse_device_event(r#"{"id":"1","ok":true,"result":"pong"}"#).is_none());
+        assert!(parse_device_event("not json").is_none());
+    }
+
+    #[tokio::test]
+    async fn unsolicited_wake_event_does_not_break_an_exchange() {
+        let (mut host, device) = duplex(1024);
+        spawn_device(device, |req| {
+            vec![
+                // Device announces it woke up just before answering.
+                r#"{"event":"wake","reason":"gpio"}"#.to_string(),
+                json!({ "id": req["id"], "ok": true, "result": "pong" }).to_string(),
+            ]
+        });
+
+        let resp = request_with_retry(&mut host, "ping", json!({}), false, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(resp["result"], "pong");
+    }
+
+    #[test]
+    fn parse_firmware_info_reads_versioned_and_legacy_capabilities() {
+        let info = parse_firmware_info(
+            r#"{"gpio":[0,1],"led_pin":13,"fw_version":"0.1.0","protocol_version":2}"#,
+        );
+        assert_eq!(info.fw_version.as_deref(), Some("0.1.0"));
+        assert_eq!(info.protocol_version, Some(PROTOCOL_V2));
+
+        // Pre-versioning firmware omits the fields entirely.
+        let legacy = parse_firmware_info(r#"{"gpio":[0,1],"led_pin":13}"#);
+        assert!(legacy.fw_version.is_none());
+        assert!(legacy.protocol_version.is_none());
+    }
+
+    #[tokio::test]
+    async fn unsupported_protocol_version_is_flagged_for_doctor() {
+        let reply = ToolResult {
+            success: true,
+            output: r#"{"gpio":[0],"fw_version":"9.0.0","protocol_version":9}"#.to_string(),
+            error: None,
+        };
+        let transport = ScriptedTransport::replying(reply);
+
+        let info = check_firmware_version(&transport, "nucleo-f401re (/dev/ttyACM0)").await;
+        assert_eq!(info.protocol_version, Some(9));
+
+        let health = crate::health::snapshot();
+        let peripherals = health.components.get("peripherals").unwrap();
+        assert_eq!(peripherals.status, "error");
+        assert!(
+            peripherals
+                .last_error
+                .as_deref()
+                .unwrap()
+                .contains("protocol v9"),
+            "got: {:?}",
+            peripherals.last_error
+        );
+    }
+
+    #[test]
+    fn firmware_protocol_constants_match_host() {
+        // PROTOCOL_V2 is duplicated into each firmware tree (no crate is
+        // shared across std, no_std and C); keep them in lockstep here.
+        for src in [
+            concat!(env!("CARGO_MANIFEST_DIR"), "/firmware/nucleo/src/main.rs"),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/firmware/esp32/src/main.rs"),
+        ] {
+            let text = std::fs::read_to_string(src).unwrap();
+            assert!(
+                text.contains(&format!("const PROTOCOL_VERSION: u8 = {PROTOCOL_V2};")),
+                "{src}: PROTOCOL_VERSION out of sync with host PROTOCOL_V2"
+            );
+        }
+        let sketch = include_str!("../../firmware/arduino/arduino.ino");
+        assert!(
+            sketch.contains(&format!("#define PROTOCOL_VERSION {PROTOCOL_V2}")),
+            "arduino.ino: PROTOCOL_VERSION out of sync with host PROTOCOL_V2"
+        );
+    }
+
+    #[tokio::test]
+    async fn device_vanishing_mid_command_fails_the_request() {
+        let (mut host, device) = duplex(1024);
+        tokio::spawn(async move {
+            let (reader, _writer) = tokio::io::split(device);
+            let mut lines = BufReader::new(reader).lines();
+            // Accept the request, then unplug without answering.
+            let _ = lines.next_line().await;
+        });
+
+        let err = request_with_retry(&mut host, "ping", json!({}), false, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("failed after"),
+            "expected exhausted attempts, got: {err}"
+        );
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_and_caps() {
+        let mut state = ReconnectBackoff::default();
+        let now = std::time::Instant::now();
+        let mut delays = Vec::new();
+        for _ in 0..6 {
+            state.register_failure(now);
+            delays.push(state.retry_at.unwrap().duration_since(now).as_secs());
+        }
+        assert_eq!(delays, vec![2, 4, 8, 16, 30, 30]);
+    }
+
+    #[test]
+    fn reconnect_backoff_window_opens_after_the_delay() {
+        let mut state = ReconnectBackoff::default();
+        let now = std::time::Instant::now();
+        state.register_failure(now);
+        assert!(state.wait_remaining(now).is_some());
+        assert!(state.wait_remaining(now + Duration::from_secs(3)).is_none());
+
+        state.reset();
+        assert!(state.wait_remaining(now).is_none());
+        assert_eq!(state.failures, 0);
+    }
+}
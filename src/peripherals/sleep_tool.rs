@@ -0,0 +1,172 @@
+//! Device sleep tool — park a battery-powered board in deep sleep.
+//!
+//! Deliberately separate from the per-board protocol tools: sleeping takes
+//! the board offline until its timer or wake pin fires, so the action is
+//! gated on the autonomy policy like other side-effecting tools.
+
+use super::traits::CommandTransport;
+use crate::security::SecurityPolicy;
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Tool: send a connected board into deep sleep (ESP32 battery nodes).
+pub struct SleepDeviceTool {
+    /// (board_name, transport) for each serial or TCP board.
+    boards: Vec<(String, Arc<dyn CommandTransport>)>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl SleepDeviceTool {
+    pub(crate) fn new(
+        boards: Vec<(String, Arc<dyn CommandTransport>)>,
+        security: Arc<SecurityPolicy>,
+    ) -> Self {
+        Self { boards, security }
+    }
+}
+
+#[async_trait]
+impl Tool for SleepDeviceTool {
+    fn name(&self) -> &str {
+        "sleep_device"
+    }
+
+    fn description(&self) -> &str {
+        "Put a connected board into deep sleep to save battery. The board is \
+         unreachable until 'duration_ms' elapses (or the optional 'wake_gpio' \
+         pin goes high), then it reboots and announces itself with a wake \
+         event. Only boards whose 'capabilities' report sleep support this. \
+         Blocked when autonomy is read-only, since it takes hardware offline."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "board": {
+                    "type": "string",
+                    "description": "Board name; required when several boards are configured"
+                },
+                "duration_ms": {
+                    "type": "integer",
+                    "description": "How long to sleep in milliseconds (timer wake source)"
+                },
+                "wake_gpio": {
+                    "type": "integer",
+                    "description": "Optional RTC-capable GPIO that wakes the board early when pulled high"
+                }
+            },
+            "required": ["duration_ms"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        let duration_ms = args
+            .get("duration_ms")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'duration_ms' parameter"))?;
+        if duration_ms == 0 {
+            anyhow::bail!("'duration_ms' must be > 0");
+        }
+
+        let filter = args.get("board").and_then(|v| v.as_str());
+        let (board_name, transport) = match (filter, self.boards.as_slice()) {
+            (Some(name), boards) => boards
+                .iter()
+                .find(|(b, _)| b == name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown board: {name}"))?,
+            (None, [only]) => only,
+            (None, _) => anyhow::bail!(
+                "Several boards are configured; pass 'board' to pick which one sleeps"
+            ),
+        };
+
+        let mut request = json!({ "duration_ms": duration_ms });
+        if let Some(wake_gpio) = args.get("wake_gpio").and_then(|v| v.as_u64()) {
+            request["wake_gpio"] = json!(wake_gpio);
+        }
+        let result = transport.request("sleep", request).await?;
+        if result.success {
+            tracing::info!(
+                board = %board_name,
+                duration_ms,
+                "board acknowledged sleep; it is now unreachable"
+            );
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    struct AckTransport {
+        seen: std::sync::Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    #[async_trait]
+    impl CommandTransport for AckTransport {
+        async fn request(&self, cmd: &str, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            self.seen.lock().unwrap().push((cmd.to_string(), args));
+            Ok(ToolResult {
+                success: true,
+                output: "sleeping for 60000 ms".into(),
+                error: None,
+            })
+        }
+    }
+
+    fn tool_with(autonomy: AutonomyLevel) -> (SleepDeviceTool, Arc<AckTransport>) {
+        let transport = Arc::new(AckTransport {
+            seen: std::sync::Mutex::new(Vec::new()),
+        });
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        let tool = SleepDeviceTool::new(
+            vec![("esp32".to_string(), transport.clone() as _)],
+            security,
+        );
+        (tool, transport)
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_sleep() {
+        let (tool, transport) = tool_with(AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({ "duration_ms": 60_000 }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert!(transport.seen.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sleep_request_reaches_the_single_board() {
+        let (tool, transport) = tool_with(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({ "duration_ms": 60_000, "wake_gpio": 14 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let seen = transport.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, "sleep");
+        assert_eq!(seen[0].1["duration_ms"], 60_000);
+        assert_eq!(seen[0].1["wake_gpio"], 14);
+    }
+}
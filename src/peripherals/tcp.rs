@@ -0,0 +1,474 @@
+//! TCP peripheral — WiFi boards (ESP32) on the local network.
+//!
+//! Same newline-delimited JSON protocol as the serial transport, carried
+//! over a TCP socket (`tcp://192.168.1.50:3333`). The connection is opened
+//! lazily and re-established with exponential backoff when it drops, so a
+//! board rebooting or roaming off WiFi does not permanently break its tools.
+
+use crate::config::PeripheralBoardConfig;
+use crate::peripherals::serial::{
+    negotiate_crc, parse_response, protocol_tools, request_with_retry, PROTOCOL_V1, PROTOCOL_V2,
+};
+use crate::peripherals::traits::CommandTransport;
+use crate::peripherals::Peripheral;
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use portable_atomic::{AtomicBool, Ordering};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+/// Timeout for one TCP request/response (seconds).
+const TCP_TIMEOUT_SECS: u64 = 5;
+
+/// Reconnect attempts per request before giving up.
+const RECONNECT_ATTEMPTS: u32 = 3;
+
+/// First reconnect delay; doubles per attempt (250ms, 500ms, 1s).
+const RECONNECT_BASE_DELAY_MS: u64 = 250;
+
+/// Parse a `tcp://host:port` peripheral path into `host:port`.
+pub(crate) fn parse_addr(path: &str) -> anyhow::Result<String> {
+    let rest = path
+        .strip_prefix("tcp://")
+        .ok_or_else(|| anyhow::anyhow!("TCP peripheral path must look like tcp://host:port"))?;
+    let (host, port) = rest
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow::anyhow!("TCP peripheral path missing port: {}", path))?;
+    if host.is_empty() {
+        anyhow::bail!("TCP peripheral path missing host: {}", path);
+    }
+    port.parse::<u16>()
+        .map_err(|_| anyhow::anyhow!("Invalid TCP port in {}: {}", path, port))?;
+    Ok(rest.to_string())
+}
+
+/// Shared TCP transport for tools; reconnects with backoff on demand.
+pub(crate) struct TcpTransport {
+    addr: String,
+    stream: Mutex<Option<TcpStream>>,
+    timeout: Duration,
+    /// Shared secret sent as the first protocol line after each connect;
+    /// an open TCP port on the LAN is otherwise an open board.
+    secret: Option<String>,
+    /// Set once `protocol_hello` negotiated CRC32 framing (protocol v2).
+    crc: AtomicBool,
+}
+
+impl TcpTransport {
+    fn new(addr: String, secret: Option<String>) -> Self {
+        Self {
+            addr,
+            stream: Mutex::new(None),
+            timeout: Duration::from_secs(TCP_TIMEOUT_SECS),
+            secret,
+            crc: AtomicBool::new(false),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_timeout(addr: String, timeout: Duration) -> Self {
+        Self {
+            addr,
+            stream: Mutex::new(None),
+            timeout,
+            secret: None,
+            crc: AtomicBool::new(false),
+        }
+    }
+
+    /// Connect if needed and run the `protocol_hello` handshake; stays on
+    /// plain v1 if the firmware does not understand it.
+    async fn negotiate(&self) -> anyhow::Result<()> {
+        let mut guard = self.stream.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.connect_with_backoff().await?);
+        }
+        let stream = guard.as_mut().expect("stream populated above");
+        let crc = negotiate_crc(stream, self.timeout).await;
+        self.crc.store(crc, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Drop the current connection; the next request reconnects.
+    async fn reset(&self) {
+        *self.stream.lock().await = None;
+    }
+
+    async fn connect_with_backoff(&self) -> anyhow::Result<TcpStream> {
+        let mut delay = Duration::from_millis(RECONNECT_BASE_DELAY_MS);
+        let mut last_err = None;
+        for attempt in 0..RECONNECT_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            match TcpStream::connect(&self.addr).await {
+                Ok(mut stream) => {
+                    self.authenticate(&mut stream).await?;
+                    return Ok(stream);
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(anyhow::anyhow!(
+            "Failed to connect to {} after {} attempts: {}",
+            self.addr,
+            RECONNECT_ATTEMPTS,
+            last_err.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+
+    /// Present the shared secret as the first protocol exchange on a fresh
+    /// connection. A rejected secret fails immediately — retrying with
+    /// backoff cannot fix a wrong credential.
+    async fn authenticate(&self, stream: &mut TcpStream) -> anyhow::Result<()> {
+        let Some(secret) = &self.secret else {
+            return Ok(());
+        };
+        let resp = request_with_retry(
+            stream,
+            "auth",
+            json!({ "secret": secret }),
+            false,
+            self.timeout,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Auth exchange with {} failed: {}", self.addr, e))?;
+        let result = parse_response(&resp);
+        if !result.success {
+            anyhow::bail!(
+                "Peripheral at {} rejected the shared secret: {}",
+                self.addr,
+                result.error.unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommandTransport for TcpTransport {
+    async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+        let mut guard = self.stream.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.connect_with_backoff().await?);
+        }
+
+        // One retry after a fresh reconnect: the cached connection may have
+        // died since the last request (board rebooted, WiFi dropped).
+        // request_with_retry already retransmits on per-frame timeouts and
+        // CRC mismatches; this outer loop only handles a dead connection.
+        let use_crc = self.crc.load(Ordering::Relaxed);
+        for attempt in 0..2 {
+            let stream = guard.as_mut().expect("stream populated above");
+            match request_with_retry(stream, cmd, args.clone(), use_crc, self.timeout).await {
+                Ok(resp) => return Ok(parse_response(&resp)),
+                Err(e) => {
+                    // The stream may hold a stale half-response after a
+                    // timeout, so drop it rather than desync the protocol.
+                    *guard = None;
+                    if attempt == 1 {
+                        return Err(e);
+                    }
+                    *guard = Some(self.connect_with_backoff().await?);
+                }
+            }
+        }
+        unreachable!("request loop returns within two attempts")
+    }
+
+    fn protocol_version(&self) -> u8 {
+        if self.crc.load(Ordering::Relaxed) {
+            PROTOCOL_V2
+        } else {
+            PROTOCOL_V1
+        }
+    }
+}
+
+/// TCP peripheral for ESP32 and similar WiFi boards.
+pub struct TcpPeripheral {
+    name: String,
+    board_type: String,
+    transport: Arc<TcpTransport>,
+}
+
+impl TcpPeripheral {
+    /// Create a TCP peripheral from config; no connection is made until
+    /// the first request (or [`Peripheral::connect`]).
+    pub fn from_config(config: &PeripheralBoardConfig) -> anyhow::Result<Self> {
+        let path = config
+            .path
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("TCP peripheral requires a tcp://host:port path"))?;
+        let addr = parse_addr(path)?;
+
+        let name = format!("{}-{}", config.board, addr.replace(':', "_"));
+        Ok(Self {
+            name,
+            board_type: config.board.clone(),
+            transport: Arc::new(TcpTransport::new(addr, config.secret.clone())),
+        })
+    }
+
+    /// Expose transport for capabilities tool (Phase C).
+    pub(crate) fn transport(&self) -> Arc<TcpTransport> {
+        self.transport.clone()
+    }
+}
+
+#[async_trait]
+impl Peripheral for TcpPeripheral {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn board_type(&self) -> &str {
+        &self.board_type
+    }
+
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        self.transport.negotiate().await?;
+        let result = self.transport.request("ping", json!({})).await?;
+        if !result.success {
+            anyhow::bail!(
+                "Peripheral at {} rejected ping: {}",
+                self.transport.addr,
+                result.error.unwrap_or_default()
+            );
+        }
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> anyhow::Result<()> {
+        self.transport.reset().await;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.transport
+            .request("ping", json!({}))
+            .await
+            .map(|r| r.success)
+            .unwrap_or(false)
+    }
+
+    fn tools(&self) -> Vec<Box<dyn Tool>> {
+        protocol_tools(self.transport.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Serve `replies` connections, answering every request line with
+    /// `result` and the request's own id, then closing the connection.
+    async fn spawn_echo_server(connections: usize, result: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            for _ in 0..connections {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let req: Value = serde_json::from_str(&line).unwrap();
+                    let resp = json!({ "id": req["id"], "ok": true, "result": result });
+                    if writer
+                        .write_all(format!("{}\n", resp).as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn parse_addr_accepts_host_and_port() {
+        assert_eq!(
+            parse_addr("tcp://192.168.1.50:3333").unwrap(),
+            "192.168.1.50:3333"
+        );
+        assert_eq!(
+            parse_addr("tcp://esp32.local:3333").unwrap(),
+            "esp32.local:3333"
+        );
+    }
+
+    #[test]
+    fn parse_addr_rejects_malformed_paths() {
+        assert!(parse_addr("/dev/ttyACM0").is_err());
+        assert!(parse_addr("tcp://192.168.1.50").is_err());
+        assert!(parse_addr("tcp://:3333").is_err());
+        assert!(parse_addr("tcp://host:notaport").is_err());
+    }
+
+    #[tokio::test]
+    async fn request_roundtrip_frames_one_line_per_command() {
+        let addr = spawn_echo_server(1, "pong").await;
+        let transport = TcpTransport::new(addr, None);
+
+        let first = transport.request("ping", json!({})).await.unwrap();
+        assert!(first.success);
+        assert_eq!(first.output, "pong");
+
+        // Same connection: framing must not leak bytes between requests.
+        let second = transport
+            .request("gpio_read", json!({ "pin": 4 }))
+            .await
+            .unwrap();
+        assert!(second.success);
+        assert_eq!(second.output, "pong");
+    }
+
+    #[tokio::test]
+    async fn device_error_surfaces_in_tool_result() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            if let Ok(Some(line)) = lines.next_line().await {
+                let req: Value = serde_json::from_str(&line).unwrap();
+                let resp = json!({
+                    "id": req["id"], "ok": false, "result": "", "error": "Invalid pin 99"
+                });
+                let _ = writer.write_all(format!("{}\n", resp).as_bytes()).await;
+            }
+        });
+
+        let transport = TcpTransport::new(addr, None);
+        let result = transport
+            .request("gpio_read", json!({ "pin": 99 }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("Invalid pin 99"));
+    }
+
+    #[tokio::test]
+    async fn silent_server_times_out_and_drops_the_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            // Accept and hold the connection without ever replying.
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        let transport = TcpTransport::with_timeout(addr, Duration::from_millis(100));
+        let err = transport.request("ping", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "got: {err}");
+        assert!(transport.stream.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_server_drops_the_connection() {
+        // Server answers one request per connection, then closes — the
+        // second request only succeeds if the transport reconnects.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                if let Ok(Some(line)) = lines.next_line().await {
+                    let req: Value = serde_json::from_str(&line).unwrap();
+                    let resp = json!({ "id": req["id"], "ok": true, "result": "pong" });
+                    let _ = writer.write_all(format!("{}\n", resp).as_bytes()).await;
+                }
+                // Connection dropped here.
+            }
+        });
+
+        let transport = TcpTransport::new(addr, None);
+        assert!(transport.request("ping", json!({})).await.unwrap().success);
+        assert!(transport.request("ping", json!({})).await.unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn shared_secret_is_the_first_line_on_every_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+
+            // First line must be the auth exchange with the secret.
+            let line = lines.next_line().await.unwrap().unwrap();
+            let req: Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(req["cmd"], "auth");
+            assert_eq!(req["args"]["secret"], "hunter2");
+            let resp = json!({ "id": req["id"], "ok": true, "result": "authenticated" });
+            writer
+                .write_all(format!("{}\n", resp).as_bytes())
+                .await
+                .unwrap();
+
+            // Only then does the real command arrive.
+            let line = lines.next_line().await.unwrap().unwrap();
+            let req: Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(req["cmd"], "ping");
+            let resp = json!({ "id": req["id"], "ok": true, "result": "pong" });
+            let _ = writer.write_all(format!("{}\n", resp).as_bytes()).await;
+        });
+
+        let transport = TcpTransport::new(addr, Some("hunter2".to_string()));
+        let result = transport.request("ping", json!({})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "pong");
+    }
+
+    #[tokio::test]
+    async fn rejected_secret_fails_the_request_with_a_clear_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            // Reject the secret on every connection the transport opens.
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                if let Ok(Some(line)) = lines.next_line().await {
+                    let req: Value = serde_json::from_str(&line).unwrap();
+                    let resp = json!({
+                        "id": req["id"], "ok": false, "result": "", "error": "bad secret"
+                    });
+                    let _ = writer.write_all(format!("{}\n", resp).as_bytes()).await;
+                }
+            }
+        });
+
+        let transport = TcpTransport::new(addr, Some("wrong".to_string()));
+        let err = transport.request("ping", json!({})).await.unwrap_err();
+        assert!(
+            err.to_string().contains("rejected the shared secret"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_address_fails_after_backoff() {
+        // Port 1 on localhost: connection refused immediately.
+        let transport = TcpTransport::new("127.0.0.1:1".to_string(), None);
+        let err = transport.request("ping", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"), "got: {err}");
+    }
+}
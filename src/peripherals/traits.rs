@@ -7,7 +7,7 @@
 
 use async_trait::async_trait;
 
-use crate::tools::Tool;
+use crate::tools::{Tool, ToolResult};
 
 /// A hardware peripheral that exposes capabilities as agent tools.
 ///
@@ -73,3 +73,79 @@ pub trait Peripheral: Send + Sync {
     /// [`connect`](Peripheral::connect).
     fn tools(&self) -> Vec<Box<dyn Tool>>;
 }
+
+/// Request/response transport for the newline-delimited JSON protocol.
+///
+/// Implemented by the serial transport (USB CDC) and the TCP transport
+/// (WiFi boards). Peripheral tools hold an `Arc<dyn CommandTransport>`, so
+/// the same GPIO/PWM/ADC tools work over whichever transport the board is
+/// reachable on.
+#[async_trait]
+pub trait CommandTransport: Send + Sync {
+    /// Send one command and await its matching response.
+    async fn request(&self, cmd: &str, args: serde_json::Value) -> anyhow::Result<ToolResult>;
+
+    /// Query device capabilities (Phase C: reported pins, modes, LED pin).
+    async fn capabilities(&self) -> anyhow::Result<ToolResult> {
+        self.request("capabilities", serde_json::json!({})).await
+    }
+
+    /// Negotiated wire protocol version: 1 (plain JSON lines) unless the
+    /// `protocol_hello` handshake upgraded the link to 2 (CRC32 framing).
+    fn protocol_version(&self) -> u8 {
+        1
+    }
+
+    /// Run several sub-commands in one device round-trip via the `batch`
+    /// protocol command. Each entry is `(cmd, args)`; the device executes
+    /// them back-to-back and answers with one response per entry, in
+    /// order, so partial failures surface per sub-command.
+    async fn batch(
+        &self,
+        commands: &[(String, serde_json::Value)],
+    ) -> anyhow::Result<Vec<ToolResult>> {
+        let entries: Vec<serde_json::Value> = commands
+            .iter()
+            .enumerate()
+            .map(|(i, (cmd, args))| {
+                serde_json::json!({ "id": format!("b{i}"), "cmd": cmd, "args": args })
+            })
+            .collect();
+        let outer = self
+            .request("batch", serde_json::json!({ "commands": entries }))
+            .await?;
+        if !outer.success {
+            anyhow::bail!(
+                "Batch failed: {}",
+                outer.error.as_deref().unwrap_or("unknown")
+            );
+        }
+
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&outer.output)
+            .map_err(|e| anyhow::anyhow!("Invalid batch response: {e}"))?;
+        if parsed.len() != commands.len() {
+            anyhow::bail!(
+                "Batch returned {} responses for {} commands",
+                parsed.len(),
+                commands.len()
+            );
+        }
+        let mut results = Vec::with_capacity(parsed.len());
+        for (i, entry) in parsed.iter().enumerate() {
+            let expected = format!("b{i}");
+            let got = entry["id"].as_str().unwrap_or("");
+            if got != expected {
+                anyhow::bail!("Batch response out of order: expected id {expected}, got {got}");
+            }
+            results.push(ToolResult {
+                success: entry["ok"].as_bool().unwrap_or(false),
+                output: entry["result"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| entry["result"].to_string()),
+                error: entry["error"].as_str().map(String::from),
+            });
+        }
+        Ok(results)
+    }
+}
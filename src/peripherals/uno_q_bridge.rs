@@ -2,6 +2,11 @@
 //!
 //! When ZeroClaw runs on Uno Q, the Bridge app (Python + MCU) exposes
 //! digitalWrite/digitalRead over a local socket. These tools connect to it.
+//! Bridge v2 adds `analog_read` and `pwm_write` using the same command
+//! names (and per-mille duty) as the serial protocol; the host asks the
+//! bridge for its version and stays on the GPIO-only v1 set when an older
+//! deployment does not understand the question — mirroring how the serial
+//! `protocol_hello` negotiation falls back to v1.
 
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
@@ -13,9 +18,17 @@ use tokio::net::TcpStream;
 const BRIDGE_HOST: &str = "127.0.0.1";
 const BRIDGE_PORT: u16 = 9999;
 
-async fn bridge_request(cmd: &str, args: &[String]) -> anyhow::Result<String> {
-    let addr = format!("{}:{}", BRIDGE_HOST, BRIDGE_PORT);
-    let mut stream = tokio::time::timeout(Duration::from_secs(5), TcpStream::connect(&addr))
+/// GPIO-only bridges that predate the `version` command.
+pub(crate) const BRIDGE_V1: u32 = 1;
+/// Adds `analog_read`, `pwm_write`, and the `version` command itself.
+pub(crate) const BRIDGE_V2: u32 = 2;
+
+fn default_addr() -> String {
+    format!("{BRIDGE_HOST}:{BRIDGE_PORT}")
+}
+
+async fn bridge_request_at(addr: &str, cmd: &str, args: &[String]) -> anyhow::Result<String> {
+    let mut stream = tokio::time::timeout(Duration::from_secs(5), TcpStream::connect(addr))
         .await
         .map_err(|_| anyhow::anyhow!("Bridge connection timed out"))??;
 
@@ -30,6 +43,49 @@ async fn bridge_request(cmd: &str, args: &[String]) -> anyhow::Result<String> {
     Ok(resp)
 }
 
+async fn bridge_request(cmd: &str, args: &[String]) -> anyhow::Result<String> {
+    bridge_request_at(&default_addr(), cmd, args).await
+}
+
+/// Ask the bridge which command set it speaks. A v1 bridge answers the
+/// unknown `version` command with an error line, so that maps to v1;
+/// only an unreachable bridge is an actual failure.
+pub(crate) async fn bridge_version_at(addr: &str) -> anyhow::Result<u32> {
+    let resp = bridge_request_at(addr, "version", &[]).await?;
+    Ok(resp.trim().parse().unwrap_or(BRIDGE_V1))
+}
+
+/// Version of the bridge at the default local address.
+pub(crate) async fn bridge_version() -> anyhow::Result<u32> {
+    bridge_version_at(&default_addr()).await
+}
+
+/// Wrap a raw bridge reply line into a ToolResult (`error:` lines fail).
+fn bridge_result(resp: anyhow::Result<String>, ok_output: Option<&str>) -> ToolResult {
+    match resp {
+        Ok(resp) => {
+            if resp.starts_with("error:") {
+                ToolResult {
+                    success: false,
+                    output: resp.clone(),
+                    error: Some(resp),
+                }
+            } else {
+                ToolResult {
+                    success: true,
+                    output: ok_output.map_or(resp, String::from),
+                    error: None,
+                }
+            }
+        }
+        Err(e) => ToolResult {
+            success: false,
+            output: format!("Bridge error: {}", e),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
 /// Tool: read GPIO pin via Uno Q Bridge.
 pub struct UnoQGpioReadTool;
 
@@ -61,28 +117,10 @@ impl Tool for UnoQGpioReadTool {
             .get("pin")
             .and_then(|v| v.as_u64())
             .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
-        match bridge_request("gpio_read", &[pin.to_string()]).await {
-            Ok(resp) => {
-                if resp.starts_with("error:") {
-                    Ok(ToolResult {
-                        success: false,
-                        output: resp.clone(),
-                        error: Some(resp),
-                    })
-                } else {
-                    Ok(ToolResult {
-                        success: true,
-                        output: resp,
-                        error: None,
-                    })
-                }
-            }
-            Err(e) => Ok(ToolResult {
-                success: false,
-                output: format!("Bridge error: {}", e),
-                error: Some(e.to_string()),
-            }),
-        }
+        Ok(bridge_result(
+            bridge_request("gpio_read", &[pin.to_string()]).await,
+            None,
+        ))
     }
 }
 
@@ -125,27 +163,199 @@ impl Tool for UnoQGpioWriteTool {
             .get("value")
             .and_then(|v| v.as_u64())
             .ok_or_else(|| anyhow::anyhow!("Missing 'value' parameter"))?;
-        match bridge_request("gpio_write", &[pin.to_string(), value.to_string()]).await {
-            Ok(resp) => {
-                if resp.starts_with("error:") {
-                    Ok(ToolResult {
-                        success: false,
-                        output: resp.clone(),
-                        error: Some(resp),
-                    })
-                } else {
-                    Ok(ToolResult {
-                        success: true,
-                        output: "done".into(),
-                        error: None,
-                    })
+        Ok(bridge_result(
+            bridge_request("gpio_write", &[pin.to_string(), value.to_string()]).await,
+            Some("done"),
+        ))
+    }
+}
+
+/// Tool: read an analog pin via Uno Q Bridge (bridge v2).
+pub struct UnoQAnalogReadTool;
+
+#[async_trait]
+impl Tool for UnoQAnalogReadTool {
+    fn name(&self) -> &str {
+        "analog_read"
+    }
+
+    fn description(&self) -> &str {
+        "Read an analog pin (raw ADC value) on Arduino Uno Q. Requires \
+         uno-q-bridge app v2; re-run 'zeroclaw peripheral setup-uno-q' to upgrade."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "Analog pin number (e.g. 0 for A0)"
+                }
+            },
+            "required": ["pin"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        Ok(bridge_result(
+            bridge_request("analog_read", &[pin.to_string()]).await,
+            None,
+        ))
+    }
+}
+
+/// Tool: set a PWM duty cycle via Uno Q Bridge (bridge v2).
+pub struct UnoQPwmWriteTool;
+
+#[async_trait]
+impl Tool for UnoQPwmWriteTool {
+    fn name(&self) -> &str {
+        "pwm_write"
+    }
+
+    fn description(&self) -> &str {
+        "Set PWM duty cycle on an Arduino Uno Q pin. Duty is per-mille \
+         (0-1000), matching the serial protocol. Requires uno-q-bridge app v2."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pin": {
+                    "type": "integer",
+                    "description": "PWM-capable pin number"
+                },
+                "duty": {
+                    "type": "integer",
+                    "description": "Duty cycle in per-mille (0-1000)"
+                }
+            },
+            "required": ["pin", "duty"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let duty = args
+            .get("duty")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'duty' parameter"))?;
+        if duty > 1000 {
+            anyhow::bail!("'duty' is per-mille: 0-1000");
+        }
+        Ok(bridge_result(
+            bridge_request("pwm_write", &[pin.to_string(), duty.to_string()]).await,
+            Some("done"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Mock bridge: answers each connection's one command line from the
+    /// given handler, like the Python bridge (one command per connection).
+    async fn spawn_mock_bridge(handler: fn(&str) -> String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                if let Ok(Some(line)) = lines.next_line().await {
+                    let reply = handler(line.trim());
+                    let _ = writer.write_all(format!("{reply}\n").as_bytes()).await;
                 }
             }
-            Err(e) => Ok(ToolResult {
-                success: false,
-                output: format!("Bridge error: {}", e),
-                error: Some(e.to_string()),
-            }),
+        });
+        addr
+    }
+
+    fn v2_bridge(line: &str) -> String {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["version"] => "2".to_string(),
+            ["analog_read", _pin] => "512".to_string(),
+            ["pwm_write", _pin, duty] if duty.parse::<u32>().is_ok_and(|d| d <= 1000) => {
+                "ok".to_string()
+            }
+            ["pwm_write", ..] => "error: duty must be 0-1000 (per-mille)".to_string(),
+            _ => "error: unknown command".to_string(),
+        }
+    }
+
+    fn v1_bridge(line: &str) -> String {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["gpio_read", _pin] => "1".to_string(),
+            ["gpio_write", _pin, _value] => "ok".to_string(),
+            _ => "error: unknown command".to_string(),
         }
     }
+
+    #[tokio::test]
+    async fn analog_read_returns_the_raw_value() {
+        let addr = spawn_mock_bridge(v2_bridge).await;
+        let resp = bridge_request_at(&addr, "analog_read", &["3".into()])
+            .await
+            .unwrap();
+        assert_eq!(resp, "512");
+    }
+
+    #[tokio::test]
+    async fn pwm_write_accepts_per_mille_and_rejects_out_of_range() {
+        let addr = spawn_mock_bridge(v2_bridge).await;
+        let ok = bridge_request_at(&addr, "pwm_write", &["9".into(), "750".into()])
+            .await
+            .unwrap();
+        assert_eq!(ok, "ok");
+
+        let err = bridge_request_at(&addr, "pwm_write", &["9".into(), "1500".into()])
+            .await
+            .unwrap();
+        assert!(err.starts_with("error:"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn v2_bridge_reports_its_version() {
+        let addr = spawn_mock_bridge(v2_bridge).await;
+        assert_eq!(bridge_version_at(&addr).await.unwrap(), BRIDGE_V2);
+    }
+
+    #[tokio::test]
+    async fn v1_bridge_error_line_negotiates_down_to_v1() {
+        let addr = spawn_mock_bridge(v1_bridge).await;
+        assert_eq!(bridge_version_at(&addr).await.unwrap(), BRIDGE_V1);
+    }
+
+    #[tokio::test]
+    async fn unreachable_bridge_is_an_error_not_a_version() {
+        assert!(bridge_version_at("127.0.0.1:1").await.is_err());
+    }
+
+    #[test]
+    fn error_lines_fail_the_tool_result() {
+        let result = bridge_result(Ok("error: unknown command".to_string()), None);
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("error: unknown command"));
+
+        let result = bridge_result(Ok("ok".to_string()), Some("done"));
+        assert!(result.success);
+        assert_eq!(result.output, "done");
+    }
 }
@@ -5,6 +5,39 @@ use std::process::Command;
 
 const BRIDGE_APP_NAME: &str = "uno-q-bridge";
 
+/// Parse `BRIDGE_VERSION = N` out of a bridge `main.py`. Deployments that
+/// predate the version marker parse as v1.
+fn parse_bridge_version(main_py: &str) -> u32 {
+    main_py
+        .lines()
+        .find_map(|line| {
+            let rest = line.trim().strip_prefix("BRIDGE_VERSION")?;
+            rest.trim_start().strip_prefix('=')?.trim().parse().ok()
+        })
+        .unwrap_or(1)
+}
+
+/// Version of the bridge app shipped in this binary.
+fn embedded_bridge_version() -> u32 {
+    parse_bridge_version(include_str!("../../firmware/uno-q-bridge/python/main.py"))
+}
+
+/// Version of an already-deployed bridge, or None when none is deployed.
+fn deployed_bridge_version(dest: &std::path::Path) -> Option<u32> {
+    let main_py = std::fs::read_to_string(dest.join("python").join("main.py")).ok()?;
+    Some(parse_bridge_version(&main_py))
+}
+
+/// Report what an existing deployment is and whether this run upgrades it.
+fn announce_upgrade(deployed: u32) {
+    let embedded = embedded_bridge_version();
+    if deployed < embedded {
+        println!("Found deployed bridge v{deployed}; upgrading in place to v{embedded}.");
+    } else {
+        println!("Bridge v{deployed} already deployed; redeploying.");
+    }
+}
+
 /// Deploy the Bridge app. If host is Some, scp from repo and ssh to start.
 /// If host is None, assume we're ON the Uno Q — use embedded files and start.
 pub fn setup_uno_q_bridge(host: Option<&str>) -> Result<()> {
@@ -38,6 +71,10 @@ fn deploy_remote(host: &str, bridge_dir: &std::path::Path) -> Result<()> {
         format!("arduino@{}", host)
     };
 
+    if let Some(deployed) = remote_bridge_version(&ssh_target) {
+        announce_upgrade(deployed);
+    }
+
     println!("Copying Bridge app to {}...", host);
     let status = Command::new("ssh")
         .args([&ssh_target, "mkdir", "-p", "~/ArduinoApps"])
@@ -86,6 +123,10 @@ fn deploy_local(bridge_dir: Option<&std::path::Path>) -> Result<()> {
     let apps_dir = std::path::Path::new(&home).join("ArduinoApps");
     let dest_dir = apps_dir.join(BRIDGE_APP_NAME);
 
+    if let Some(deployed) = deployed_bridge_version(&dest_dir) {
+        announce_upgrade(deployed);
+    }
+
     std::fs::create_dir_all(&dest_dir).context("create dest dir")?;
 
     if let Some(src) = bridge_dir {
@@ -126,6 +167,23 @@ fn write_embedded_bridge(dest: &std::path::Path) -> Result<()> {
     Ok(())
 }
 
+/// Version of the bridge deployed on a remote Uno Q, or None when the
+/// app (or ssh) is not there.
+fn remote_bridge_version(ssh_target: &str) -> Option<u32> {
+    let out = Command::new("ssh")
+        .args([
+            ssh_target,
+            "cat",
+            "~/ArduinoApps/uno-q-bridge/python/main.py",
+        ])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(parse_bridge_version(&String::from_utf8_lossy(&out.stdout)))
+}
+
 fn copy_dir(src: &std::path::Path, dst: &std::path::Path) -> Result<()> {
     for entry in std::fs::read_dir(src)? {
         let e = entry?;
@@ -141,3 +199,33 @@ fn copy_dir(src: &std::path::Path, dst: &std::path::Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bridge_version_marker_parses() {
+        assert_eq!(parse_bridge_version("BRIDGE_VERSION = 2\n"), 2);
+        assert_eq!(parse_bridge_version("  BRIDGE_VERSION=3"), 3);
+        // Pre-marker deployments count as v1.
+        assert_eq!(parse_bridge_version("ZEROCLAW_PORT = 9999\n"), 1);
+    }
+
+    #[test]
+    fn embedded_bridge_is_at_least_v2() {
+        assert!(embedded_bridge_version() >= 2);
+    }
+
+    #[test]
+    fn deployed_version_reads_main_py() {
+        let dir =
+            std::env::temp_dir().join(format!("zeroclaw_bridge_test_{}", uuid::Uuid::new_v4()));
+        assert_eq!(deployed_bridge_version(&dir), None);
+
+        std::fs::create_dir_all(dir.join("python")).unwrap();
+        std::fs::write(dir.join("python").join("main.py"), "BRIDGE_VERSION = 1\n").unwrap();
+        assert_eq!(deployed_bridge_version(&dir), Some(1));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
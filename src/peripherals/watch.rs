@@ -0,0 +1,601 @@
+//! Pin watch — poll a GPIO pin for edges and report transitions as events.
+//!
+//! The firmware protocol is strictly request/response, so "streaming" is
+//! host-side: a watch task polls `gpio_read` at the configured interval and
+//! turns level changes into runtime-trace events ("GPIO 7 went HIGH at
+//! 14:02"). When `[peripherals.watch]` names an announce channel, each
+//! transition is also delivered there through the cron announcement path.
+//! Watches are bounded (`max_concurrent`), listable, cancellable, and torn
+//! down with the daemon.
+
+use super::traits::CommandTransport;
+use crate::config::Config;
+use crate::tools::traits::{Tool, ToolResult};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Which level transitions a watch reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Edge {
+    Rising,
+    Falling,
+    Both,
+}
+
+impl Edge {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "rising" => Some(Edge::Rising),
+            "falling" => Some(Edge::Falling),
+            "both" => Some(Edge::Both),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Edge::Rising => "rising",
+            Edge::Falling => "falling",
+            Edge::Both => "both",
+        }
+    }
+
+    /// Whether a level change from `prev` to `cur` matches this edge.
+    pub(crate) fn matches(self, prev: u8, cur: u8) -> bool {
+        match self {
+            Edge::Rising => prev == 0 && cur == 1,
+            Edge::Falling => prev == 1 && cur == 0,
+            Edge::Both => prev != cur,
+        }
+    }
+}
+
+/// One active watch, owned by the manager's list.
+struct WatchEntry {
+    id: u64,
+    board: String,
+    pin: u64,
+    edge: Edge,
+    expires_at: Instant,
+    /// Transitions seen so far, shared with the polling task.
+    events: Arc<AtomicU64>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+/// Owns all active pin watches for the agent. One per process; the daemon
+/// calls [`PinWatchManager::shutdown`] on exit so polling tasks stop with it.
+pub(crate) struct PinWatchManager {
+    config: Config,
+    watches: std::sync::Mutex<Vec<WatchEntry>>,
+    next_id: AtomicU64,
+}
+
+impl PinWatchManager {
+    pub(crate) fn new(config: Config) -> Arc<Self> {
+        let manager = Arc::new(Self {
+            config,
+            watches: std::sync::Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        });
+        *active_manager().lock().unwrap_or_else(|e| e.into_inner()) = Some(manager.clone());
+        manager
+    }
+
+    /// Start polling `pin` on `board` for `duration`. Fails when the
+    /// configured concurrency limit is already reached.
+    pub(crate) fn start_watch(
+        self: &Arc<Self>,
+        board: &str,
+        transport: Arc<dyn CommandTransport>,
+        pin: u64,
+        edge: Edge,
+        duration: Duration,
+    ) -> Result<u64> {
+        let mut watches = self.watches.lock().unwrap_or_else(|e| e.into_inner());
+        watches.retain(|w| !w.handle.is_finished());
+        let max = self.config.peripherals.watch.max_concurrent;
+        if watches.len() >= max {
+            anyhow::bail!(
+                "Watch limit reached ({max} active); cancel one with peripheral_watch_cancel"
+            );
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let expires_at = Instant::now() + duration;
+        let events = Arc::new(AtomicU64::new(0));
+        let handle = tokio::spawn(run_watch(
+            self.clone(),
+            id,
+            board.to_string(),
+            transport,
+            pin,
+            edge,
+            expires_at,
+            events.clone(),
+        ));
+        watches.push(WatchEntry {
+            id,
+            board: board.to_string(),
+            pin,
+            edge,
+            expires_at,
+            events,
+            handle,
+        });
+        Ok(id)
+    }
+
+    /// One status line per live watch.
+    pub(crate) fn list(&self) -> Vec<String> {
+        let mut watches = self.watches.lock().unwrap_or_else(|e| e.into_inner());
+        watches.retain(|w| !w.handle.is_finished());
+        watches
+            .iter()
+            .map(|w| {
+                let remaining = w.expires_at.saturating_duration_since(Instant::now());
+                format!(
+                    "#{} {} pin {} ({}, {} events, {}s left)",
+                    w.id,
+                    w.board,
+                    w.pin,
+                    w.edge.as_str(),
+                    w.events.load(Ordering::Relaxed),
+                    remaining.as_secs()
+                )
+            })
+            .collect()
+    }
+
+    /// Stop watch `id`. Errors when no such watch is active.
+    pub(crate) fn cancel(&self, id: u64) -> Result<()> {
+        let mut watches = self.watches.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(pos) = watches.iter().position(|w| w.id == id) else {
+            anyhow::bail!("No active watch #{id}");
+        };
+        let entry = watches.remove(pos);
+        entry.handle.abort();
+        Ok(())
+    }
+
+    /// Abort every polling task; called from daemon shutdown.
+    pub(crate) fn shutdown(&self) {
+        let mut watches = self.watches.lock().unwrap_or_else(|e| e.into_inner());
+        for entry in watches.drain(..) {
+            entry.handle.abort();
+        }
+    }
+
+    fn remove(&self, id: u64) {
+        let mut watches = self.watches.lock().unwrap_or_else(|e| e.into_inner());
+        watches.retain(|w| w.id != id);
+    }
+
+    /// Record one transition in the runtime trace and, when configured,
+    /// announce it on the watch channel.
+    async fn report(&self, board: &str, pin: u64, level: u8) {
+        let state = if level == 1 { "HIGH" } else { "LOW" };
+        let message = format!(
+            "GPIO {pin} went {state} at {}",
+            chrono::Local::now().format("%H:%M")
+        );
+        crate::observability::runtime_trace::record_event(
+            "peripheral_watch",
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            Some(&message),
+            json!({ "board": board, "pin": pin, "level": level }),
+        );
+        let watch = &self.config.peripherals.watch;
+        if let (Some(channel), Some(target)) = (&watch.announce_channel, &watch.announce_to) {
+            if let Err(e) = crate::cron::scheduler::deliver_announcement(
+                &self.config,
+                channel,
+                target,
+                &message,
+            )
+            .await
+            {
+                tracing::warn!("Pin watch announcement failed: {e}");
+            }
+        }
+    }
+}
+
+/// Poll loop for one watch: read the pin each tick, compare against the
+/// previous level, report matching edges, and expire at the deadline.
+#[allow(clippy::too_many_arguments)]
+async fn run_watch(
+    manager: Arc<PinWatchManager>,
+    id: u64,
+    board: String,
+    transport: Arc<dyn CommandTransport>,
+    pin: u64,
+    edge: Edge,
+    expires_at: Instant,
+    events: Arc<AtomicU64>,
+) {
+    let poll = Duration::from_millis(manager.config.peripherals.watch.poll_ms.max(1));
+    let mut ticker = tokio::time::interval(poll);
+    let mut prev: Option<u8> = None;
+    while Instant::now() < expires_at {
+        ticker.tick().await;
+        let Ok(result) = transport.request("gpio_read", json!({ "pin": pin })).await else {
+            continue;
+        };
+        if !result.success {
+            continue;
+        }
+        let cur: u8 = u8::from(result.output.trim() == "1");
+        if let Some(prev) = prev {
+            if edge.matches(prev, cur) {
+                events.fetch_add(1, Ordering::Relaxed);
+                manager.report(&board, pin, cur).await;
+            }
+        }
+        prev = Some(cur);
+    }
+    manager.remove(id);
+}
+
+/// The process-wide manager, so the daemon can tear watches down on exit
+/// without threading a handle through the agent loop.
+fn active_manager() -> &'static std::sync::Mutex<Option<Arc<PinWatchManager>>> {
+    static MANAGER: std::sync::Mutex<Option<Arc<PinWatchManager>>> = std::sync::Mutex::new(None);
+    &MANAGER
+}
+
+/// Abort all active watches; called from daemon shutdown.
+pub fn shutdown_all() {
+    if let Some(manager) = active_manager()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        manager.shutdown();
+    }
+}
+
+/// Tool: start watching a GPIO pin for edges.
+pub struct PeripheralWatchTool {
+    boards: Vec<(String, Arc<dyn CommandTransport>)>,
+    manager: Arc<PinWatchManager>,
+}
+
+impl PeripheralWatchTool {
+    pub(crate) fn new(
+        boards: Vec<(String, Arc<dyn CommandTransport>)>,
+        manager: Arc<PinWatchManager>,
+    ) -> Self {
+        Self { boards, manager }
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralWatchTool {
+    fn name(&self) -> &str {
+        "peripheral_watch"
+    }
+
+    fn description(&self) -> &str {
+        "Watch a GPIO pin for edges for a limited time. The pin is polled in \
+         the background and each matching transition is recorded as an event \
+         (and announced on the configured channel, if any). Returns a watch \
+         id for peripheral_watch_list / peripheral_watch_cancel."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "board": {
+                    "type": "string",
+                    "description": "Board name; required when several boards are configured"
+                },
+                "pin": {
+                    "type": "integer",
+                    "description": "GPIO pin number to watch"
+                },
+                "edge": {
+                    "type": "string",
+                    "enum": ["rising", "falling", "both"],
+                    "description": "Which transitions to report (default: both)"
+                },
+                "duration_s": {
+                    "type": "integer",
+                    "description": "How long to watch, in seconds"
+                }
+            },
+            "required": ["pin", "duration_s"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pin = args
+            .get("pin")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pin' parameter"))?;
+        let duration_s = args
+            .get("duration_s")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'duration_s' parameter"))?;
+        if duration_s == 0 {
+            anyhow::bail!("'duration_s' must be > 0");
+        }
+        let edge = match args.get("edge").and_then(|v| v.as_str()) {
+            None => Edge::Both,
+            Some(s) => Edge::parse(s)
+                .ok_or_else(|| anyhow::anyhow!("Invalid edge '{s}' (rising|falling|both)"))?,
+        };
+
+        let filter = args.get("board").and_then(|v| v.as_str());
+        let (board_name, transport) = match (filter, self.boards.as_slice()) {
+            (Some(name), boards) => boards
+                .iter()
+                .find(|(b, _)| b == name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown board: {name}"))?,
+            (None, [only]) => only,
+            (None, _) => {
+                anyhow::bail!("Several boards are configured; pass 'board' to pick one")
+            }
+        };
+
+        match self.manager.start_watch(
+            board_name,
+            transport.clone(),
+            pin,
+            edge,
+            Duration::from_secs(duration_s),
+        ) {
+            Ok(id) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Watch #{id} started: {board_name} pin {pin}, {} edges, {duration_s}s",
+                    edge.as_str()
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+/// Tool: list active pin watches.
+pub struct PeripheralWatchListTool {
+    manager: Arc<PinWatchManager>,
+}
+
+impl PeripheralWatchListTool {
+    pub(crate) fn new(manager: Arc<PinWatchManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralWatchListTool {
+    fn name(&self) -> &str {
+        "peripheral_watch_list"
+    }
+
+    fn description(&self) -> &str {
+        "List active GPIO pin watches with their id, pin, edge, event count, \
+         and remaining time."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({ "type": "object", "properties": {} })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let rows = self.manager.list();
+        let output = if rows.is_empty() {
+            "No active watches".to_string()
+        } else {
+            rows.join("\n")
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// Tool: cancel an active pin watch by id.
+pub struct PeripheralWatchCancelTool {
+    manager: Arc<PinWatchManager>,
+}
+
+impl PeripheralWatchCancelTool {
+    pub(crate) fn new(manager: Arc<PinWatchManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl Tool for PeripheralWatchCancelTool {
+    fn name(&self) -> &str {
+        "peripheral_watch_cancel"
+    }
+
+    fn description(&self) -> &str {
+        "Cancel an active GPIO pin watch by its id (see peripheral_watch_list)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "integer",
+                    "description": "Watch id returned by peripheral_watch"
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = args
+            .get("id")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter"))?;
+        match self.manager.cancel(id) {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Watch #{id} cancelled"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PeripheralWatchConfig, PeripheralsConfig};
+    use std::collections::VecDeque;
+
+    /// Mock transport: pops a scripted pin level per `gpio_read`, then
+    /// repeats the last one.
+    struct SteppedPin {
+        levels: std::sync::Mutex<(VecDeque<u8>, u8)>,
+    }
+
+    impl SteppedPin {
+        fn new(levels: &[u8]) -> Arc<Self> {
+            Arc::new(Self {
+                levels: std::sync::Mutex::new((levels.iter().copied().collect(), 0)),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CommandTransport for SteppedPin {
+        async fn request(&self, cmd: &str, _args: serde_json::Value) -> Result<ToolResult> {
+            assert_eq!(cmd, "gpio_read");
+            let mut guard = self.levels.lock().unwrap();
+            if let Some(level) = guard.0.pop_front() {
+                guard.1 = level;
+            }
+            let level = guard.1;
+            Ok(ToolResult {
+                success: true,
+                output: level.to_string(),
+                error: None,
+            })
+        }
+    }
+
+    fn test_manager(max_concurrent: usize) -> Arc<PinWatchManager> {
+        PinWatchManager::new(Config {
+            peripherals: PeripheralsConfig {
+                watch: PeripheralWatchConfig {
+                    poll_ms: 5,
+                    max_concurrent,
+                    announce_channel: None,
+                    announce_to: None,
+                },
+                ..PeripheralsConfig::default()
+            },
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn edge_matching_covers_all_transitions() {
+        assert!(Edge::Rising.matches(0, 1));
+        assert!(!Edge::Rising.matches(1, 0));
+        assert!(!Edge::Rising.matches(1, 1));
+        assert!(Edge::Falling.matches(1, 0));
+        assert!(!Edge::Falling.matches(0, 1));
+        assert!(Edge::Both.matches(0, 1));
+        assert!(Edge::Both.matches(1, 0));
+        assert!(!Edge::Both.matches(0, 0));
+    }
+
+    #[test]
+    fn edge_parse_rejects_unknown_values() {
+        assert_eq!(Edge::parse("rising"), Some(Edge::Rising));
+        assert_eq!(Edge::parse("falling"), Some(Edge::Falling));
+        assert_eq!(Edge::parse("both"), Some(Edge::Both));
+        assert_eq!(Edge::parse("sideways"), None);
+    }
+
+    #[tokio::test]
+    async fn rising_edges_are_counted_and_listed() {
+        let manager = test_manager(4);
+        let pin = SteppedPin::new(&[0, 1, 0, 1, 0]);
+        let id = manager
+            .start_watch("esp32", pin, 7, Edge::Rising, Duration::from_secs(10))
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let rows = manager.list();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].contains("pin 7"), "got: {}", rows[0]);
+        assert!(rows[0].contains("2 events"), "got: {}", rows[0]);
+        manager.cancel(id).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watch_expires_and_removes_itself() {
+        let manager = test_manager(4);
+        let pin = SteppedPin::new(&[0]);
+        manager
+            .start_watch("esp32", pin, 2, Edge::Both, Duration::from_millis(20))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(manager.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrency_limit_is_enforced() {
+        let manager = test_manager(1);
+        manager
+            .start_watch(
+                "esp32",
+                SteppedPin::new(&[0]),
+                2,
+                Edge::Both,
+                Duration::from_secs(10),
+            )
+            .unwrap();
+        let err = manager
+            .start_watch(
+                "esp32",
+                SteppedPin::new(&[0]),
+                3,
+                Edge::Both,
+                Duration::from_secs(10),
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("peripheral_watch_cancel"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_unknown_watch_errors() {
+        let manager = test_manager(4);
+        let err = manager.cancel(999).unwrap_err();
+        assert!(err.to_string().contains("No active watch"), "got: {err}");
+    }
+}
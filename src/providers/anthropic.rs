@@ -1,3 +1,4 @@
+use crate::providers::compatible::AuthStyle;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
     Provider, ProviderCapabilities, StreamChunk, StreamError, StreamEvent, StreamOptions,
@@ -14,6 +15,16 @@ pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
     max_tokens: u32,
+    prompt_caching: bool,
+    /// Override for how the credential is sent, from
+    /// `[providers.custom] auth_scheme` on `anthropic-custom:` endpoints.
+    auth_override: Option<AuthStyle>,
+    /// Extra HTTP headers to include in all API requests.
+    extra_headers: std::collections::HashMap<String, String>,
+    /// Extra PEM root certificates to trust (internal PKI).
+    tls_ca_bundle: Option<std::path::PathBuf>,
+    /// Skip TLS certificate verification. Only for trusted internal networks.
+    tls_accept_invalid_certs: bool,
 }
 
 const DEFAULT_ANTHROPIC_MAX_TOKENS: u32 = 4096;
@@ -195,6 +206,11 @@ impl AnthropicProvider {
                 .map(ToString::to_string),
             base_url,
             max_tokens: DEFAULT_ANTHROPIC_MAX_TOKENS,
+            prompt_caching: true,
+            auth_override: None,
+            extra_headers: std::collections::HashMap::new(),
+            tls_ca_bundle: None,
+            tls_accept_invalid_certs: false,
         }
     }
 
@@ -204,6 +220,43 @@ impl AnthropicProvider {
         self
     }
 
+    /// Enable or disable `cache_control` markers on the stable prompt prefix
+    /// (system prompt, tool definitions, conversation tail). On by default;
+    /// `[providers.anthropic] prompt_caching = false` turns it off for
+    /// proxies that reject cache_control blocks.
+    pub fn with_prompt_caching(mut self, enabled: bool) -> Self {
+        self.prompt_caching = enabled;
+        self
+    }
+
+    /// Override how the credential is sent (`[providers.custom] auth_scheme`).
+    /// Default: `x-api-key` for API keys, Bearer for setup tokens.
+    pub fn with_auth_style(mut self, style: AuthStyle) -> Self {
+        self.auth_override = Some(style);
+        self
+    }
+
+    /// Set extra HTTP headers to include in all API requests.
+    pub fn with_extra_headers(
+        mut self,
+        headers: std::collections::HashMap<String, String>,
+    ) -> Self {
+        self.extra_headers = headers;
+        self
+    }
+
+    /// Configure TLS trust for self-hosted endpoints: an extra PEM CA bundle
+    /// and/or disabled certificate verification (`[providers.custom]`).
+    pub fn with_tls_options(
+        mut self,
+        ca_bundle: Option<std::path::PathBuf>,
+        accept_invalid_certs: bool,
+    ) -> Self {
+        self.tls_ca_bundle = ca_bundle;
+        self.tls_accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
     fn is_setup_token(token: &str) -> bool {
         token.starts_with("sk-ant-oat01-")
     }
@@ -213,6 +266,11 @@ impl AnthropicProvider {
         request: reqwest::RequestBuilder,
         credential: &str,
     ) -> reqwest::RequestBuilder {
+        if let Some(style) = &self.auth_override {
+            let (name, value) = style.header_pair(credential);
+            return request.header(name, value);
+        }
+
         if Self::is_setup_token(credential) {
             request
                 .header("Authorization", format!("Bearer {credential}"))
@@ -261,6 +319,31 @@ impl AnthropicProvider {
         messages.iter().filter(|m| m.role != "system").count() > 1
     }
 
+    /// Remove every `cache_control` marker from an assembled request. Used
+    /// when prompt caching is disabled via `[providers.anthropic]`.
+    fn strip_cache_markers(request: &mut NativeChatRequest<'_>) {
+        if let Some(SystemPrompt::Blocks(blocks)) = request.system.as_mut() {
+            for block in blocks {
+                block.cache_control = None;
+            }
+        }
+        for message in &mut request.messages {
+            for content in &mut message.content {
+                match content {
+                    NativeContentOut::Text { cache_control, .. }
+                    | NativeContentOut::ToolUse { cache_control, .. }
+                    | NativeContentOut::ToolResult { cache_control, .. } => *cache_control = None,
+                    NativeContentOut::Image { .. } => {}
+                }
+            }
+        }
+        if let Some(tools) = request.tools.as_mut() {
+            for tool in tools {
+                tool.cache_control = None;
+            }
+        }
+    }
+
     /// Apply cache control to the last message content block
     fn apply_cache_to_last_message(messages: &mut [NativeMessage]) {
         if let Some(last_msg) = messages.last_mut() {
@@ -521,6 +604,7 @@ impl AnthropicProvider {
             input_tokens: u.input_tokens,
             output_tokens: u.output_tokens,
             cached_input_tokens: u.cache_read_input_tokens,
+            cache_write_input_tokens: u.cache_creation_input_tokens,
         });
 
         for block in response.content {
@@ -563,6 +647,43 @@ impl AnthropicProvider {
     }
 
     fn http_client(&self) -> Client {
+        let has_tls_options = self.tls_ca_bundle.is_some() || self.tls_accept_invalid_certs;
+        if !self.extra_headers.is_empty() || has_tls_options {
+            let mut headers = reqwest::header::HeaderMap::new();
+            for (key, value) in &self.extra_headers {
+                match (
+                    reqwest::header::HeaderName::from_bytes(key.as_bytes()),
+                    reqwest::header::HeaderValue::from_str(value),
+                ) {
+                    (Ok(name), Ok(val)) => {
+                        headers.insert(name, val);
+                    }
+                    _ => {
+                        tracing::warn!(header = key, "Skipping invalid extra header name or value");
+                    }
+                }
+            }
+
+            let mut builder = Client::builder()
+                .timeout(std::time::Duration::from_secs(120))
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .default_headers(headers);
+            builder = super::compatible::apply_tls_options(
+                builder,
+                self.tls_ca_bundle.as_deref(),
+                self.tls_accept_invalid_certs,
+            );
+            let builder =
+                crate::config::apply_runtime_proxy_to_builder(builder, "provider.anthropic");
+
+            return builder.build().unwrap_or_else(|error| {
+                tracing::warn!(
+                    "Failed to build proxied timeout client with custom headers: {error}"
+                );
+                Client::new()
+            });
+        }
+
         crate::config::build_runtime_proxy_client_with_timeouts("provider.anthropic", 120, 10)
     }
 
@@ -591,6 +712,10 @@ impl AnthropicProvider {
         let mut tool_id: Option<String> = None;
         let mut tool_name: Option<String> = None;
         let mut tool_input_json = String::new();
+        // Anthropic splits usage across events: `message_start` carries
+        // input_tokens, `message_delta` carries cumulative output_tokens.
+        let mut usage_input: Option<u64> = None;
+        let mut usage_output: Option<u64> = None;
 
         while let Ok(Some(line)) = lines.next_line().await {
             let line = line.trim().to_string();
@@ -610,6 +735,26 @@ impl AnthropicProvider {
                 .unwrap_or_default();
 
             match event_type {
+                "message_start" => {
+                    if let Some(u) = event.pointer("/message/usage") {
+                        usage_input = u
+                            .get("input_tokens")
+                            .and_then(|v| v.as_u64())
+                            .or(usage_input);
+                        usage_output = u
+                            .get("output_tokens")
+                            .and_then(|v| v.as_u64())
+                            .or(usage_output);
+                    }
+                }
+                "message_delta" => {
+                    if let Some(u) = event.get("usage") {
+                        usage_output = u
+                            .get("output_tokens")
+                            .and_then(|v| v.as_u64())
+                            .or(usage_output);
+                    }
+                }
                 "content_block_start" => {
                     if let Some(block) = event.get("content_block") {
                         let block_type = block
@@ -686,6 +831,9 @@ impl AnthropicProvider {
                     }
                 }
                 "message_stop" => {
+                    if let Some(usage) = combine_stream_usage(usage_input, usage_output) {
+                        let _ = tx.send(Ok(StreamEvent::Usage(usage))).await;
+                    }
                     let _ = tx.send(Ok(StreamEvent::Final)).await;
                     return;
                 }
@@ -702,10 +850,30 @@ impl AnthropicProvider {
             }
         }
 
+        if let Some(usage) = combine_stream_usage(usage_input, usage_output) {
+            let _ = tx.send(Ok(StreamEvent::Usage(usage))).await;
+        }
         let _ = tx.send(Ok(StreamEvent::Final)).await;
     }
 }
 
+/// Combine the token counts collected from `message_start` and `message_delta`
+/// events into a single usage record, or `None` when the stream reported neither.
+fn combine_stream_usage(
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+) -> Option<TokenUsage> {
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return None;
+    }
+    Some(TokenUsage {
+        input_tokens,
+        output_tokens,
+        cached_input_tokens: None,
+        cache_write_input_tokens: None,
+    })
+}
+
 #[async_trait]
 impl Provider for AnthropicProvider {
     async fn chat_with_system(
@@ -728,7 +896,7 @@ impl Provider for AnthropicProvider {
             system
         };
 
-        let request = NativeChatRequest {
+        let mut request = NativeChatRequest {
             model: model.to_string(),
             max_tokens: self.max_tokens,
             system,
@@ -744,6 +912,9 @@ impl Provider for AnthropicProvider {
             tool_choice: None,
             stream: None,
         };
+        if !self.prompt_caching {
+            Self::strip_cache_markers(&mut request);
+        }
 
         let mut request = self
             .http_client()
@@ -782,7 +953,7 @@ impl Provider for AnthropicProvider {
         let (system_prompt, mut messages) = Self::convert_messages(request.messages);
 
         // Auto-cache last message if conversation is long
-        if Self::should_cache_conversation(request.messages) {
+        if self.prompt_caching && Self::should_cache_conversation(request.messages) {
             Self::apply_cache_to_last_message(&mut messages);
         }
 
@@ -805,7 +976,7 @@ impl Provider for AnthropicProvider {
         } else {
             system_prompt
         };
-        let native_request = NativeChatRequest {
+        let mut native_request = NativeChatRequest {
             model: model.to_string(),
             max_tokens: self.max_tokens,
             system: system_prompt,
@@ -815,6 +986,9 @@ impl Provider for AnthropicProvider {
             tool_choice,
             stream: None,
         };
+        if !self.prompt_caching {
+            Self::strip_cache_markers(&mut native_request);
+        }
 
         let req = self
             .http_client()
@@ -937,7 +1111,7 @@ impl Provider for AnthropicProvider {
         };
 
         let (system_prompt, mut messages) = Self::convert_messages(request.messages);
-        if Self::should_cache_conversation(request.messages) {
+        if self.prompt_caching && Self::should_cache_conversation(request.messages) {
             Self::apply_cache_to_last_message(&mut messages);
         }
 
@@ -958,7 +1132,7 @@ impl Provider for AnthropicProvider {
             system_prompt
         };
 
-        let native_request = NativeChatRequest {
+        let mut native_request = NativeChatRequest {
             model: model.to_string(),
             max_tokens: 4096,
             system: system_prompt,
@@ -968,6 +1142,9 @@ impl Provider for AnthropicProvider {
             tool_choice,
             stream: Some(true),
         };
+        if !self.prompt_caching {
+            Self::strip_cache_markers(&mut native_request);
+        }
 
         let body = Self::build_streaming_request(&native_request);
         let client = self.http_client();
@@ -1080,6 +1257,49 @@ mod tests {
         assert_eq!(p.base_url, "https://api.anthropic.com");
     }
 
+    #[test]
+    fn with_auth_style_overrides_credential_header() {
+        let p =
+            AnthropicProvider::with_base_url(Some("org-secret"), Some("https://gateway.internal"))
+                .with_auth_style(AuthStyle::Custom("X-Org-Token".into()));
+        let request = p
+            .apply_auth(
+                Client::new().post("https://gateway.internal/v1"),
+                "org-secret",
+            )
+            .build()
+            .unwrap();
+        assert_eq!(request.headers().get("X-Org-Token").unwrap(), "org-secret");
+        assert!(request.headers().get("x-api-key").is_none());
+    }
+
+    #[test]
+    fn default_auth_sends_x_api_key() {
+        let p = AnthropicProvider::new(Some("anthropic-test-credential"));
+        let request = p
+            .apply_auth(
+                Client::new().post("https://api.anthropic.com/v1/messages"),
+                "anthropic-test-credential",
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.headers().get("x-api-key").unwrap(),
+            "anthropic-test-credential"
+        );
+    }
+
+    #[test]
+    fn http_client_with_extra_headers_builds_successfully() {
+        let mut headers = std::collections::HashMap::new();
+        headers.insert("X-Org-Token".to_string(), "org-secret".to_string());
+        let p = AnthropicProvider::with_base_url(None, Some("https://gateway.internal"))
+            .with_extra_headers(headers)
+            .with_tls_options(None, true);
+        // Should not panic
+        let _client = p.http_client();
+    }
+
     #[tokio::test]
     async fn chat_fails_without_key() {
         let p = AnthropicProvider::new(None);
@@ -1634,6 +1854,79 @@ mod tests {
         );
     }
 
+    fn assembled_request(provider: &AnthropicProvider) -> String {
+        let messages = vec![
+            ChatMessage::system("You are ZeroClaw."),
+            ChatMessage::user("hello"),
+        ];
+        let tools = vec![ToolSpec {
+            name: "get_weather".to_string(),
+            description: "Get weather info".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let (system, messages) = AnthropicProvider::convert_messages(&messages);
+        let mut request = NativeChatRequest {
+            model: "claude-sonnet-4".to_string(),
+            max_tokens: 4096,
+            system,
+            messages,
+            temperature: 0.7,
+            tools: AnthropicProvider::convert_tools(Some(&tools)),
+            tool_choice: None,
+            stream: None,
+        };
+        if !provider.prompt_caching {
+            AnthropicProvider::strip_cache_markers(&mut request);
+        }
+        serde_json::to_string(&request).unwrap()
+    }
+
+    #[test]
+    fn prompt_caching_enabled_marks_system_and_tools() {
+        let provider = AnthropicProvider::new(Some("key"));
+        let json = assembled_request(&provider);
+        assert!(
+            json.contains(r#""cache_control":{"type":"ephemeral"}"#),
+            "request JSON should carry cache markers by default: {json}"
+        );
+    }
+
+    #[test]
+    fn prompt_caching_disabled_strips_all_markers() {
+        let provider = AnthropicProvider::new(Some("key")).with_prompt_caching(false);
+        let json = assembled_request(&provider);
+        assert!(
+            !json.contains("cache_control"),
+            "no cache markers expected with prompt_caching = false: {json}"
+        );
+    }
+
+    #[test]
+    fn with_prompt_caching_defaults_to_enabled() {
+        let provider = AnthropicProvider::new(Some("key"));
+        assert!(provider.prompt_caching);
+        let provider = provider.with_prompt_caching(false);
+        assert!(!provider.prompt_caching);
+    }
+
+    #[test]
+    fn usage_maps_cache_read_and_write_tokens() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "hi"}],
+            "usage": {
+                "input_tokens": 12,
+                "output_tokens": 5,
+                "cache_creation_input_tokens": 800,
+                "cache_read_input_tokens": 7200
+            }
+        }"#;
+        let response: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let parsed = AnthropicProvider::parse_native_response(response);
+        let usage = parsed.usage.unwrap();
+        assert_eq!(usage.cached_input_tokens, Some(7200));
+        assert_eq!(usage.cache_write_input_tokens, Some(800));
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
@@ -1713,11 +2006,8 @@ mod tests {
         });
 
         // Create provider pointing at mock server
-        let provider = AnthropicProvider {
-            credential: Some("test-key".to_string()),
-            base_url: format!("http://{addr}"),
-            max_tokens: DEFAULT_ANTHROPIC_MAX_TOKENS,
-        };
+        let provider =
+            AnthropicProvider::with_base_url(Some("test-key"), Some(&format!("http://{addr}")));
 
         // Multi-turn conversation: system → user (Go code) → assistant (code response) → user (follow-up)
         let messages = vec![
@@ -1826,6 +2116,19 @@ mod tests {
         assert!(result.usage.is_none());
     }
 
+    #[test]
+    fn combine_stream_usage_merges_partial_counts() {
+        assert!(combine_stream_usage(None, None).is_none());
+
+        let input_only = combine_stream_usage(Some(200), None).unwrap();
+        assert_eq!(input_only.input_tokens, Some(200));
+        assert_eq!(input_only.output_tokens, None);
+
+        let both = combine_stream_usage(Some(200), Some(35)).unwrap();
+        assert_eq!(both.input_tokens, Some(200));
+        assert_eq!(both.output_tokens, Some(35));
+    }
+
     #[test]
     fn capabilities_returns_vision_and_native_tools() {
         let provider = AnthropicProvider::new(Some("test-key"));
@@ -1,16 +1,37 @@
+use crate::multimodal;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
+    Provider, ProviderCapabilities, StreamChunk, StreamError, StreamOptions, StreamResult,
+    TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 const DEFAULT_API_VERSION: &str = "2024-08-01-preview";
 
+/// Credential used to authenticate against an Azure OpenAI resource.
+#[derive(Clone)]
+enum AzureAuth {
+    /// Static resource key, sent via the `api-key` header.
+    ApiKey(String),
+    /// Microsoft Entra ID (AAD) access token, sent as a bearer token.
+    AadToken(String),
+}
+
+impl AzureAuth {
+    fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::ApiKey(key) => request.header("api-key", key.as_str()),
+            Self::AadToken(token) => request.bearer_auth(token),
+        }
+    }
+}
+
 pub struct AzureOpenAiProvider {
-    credential: Option<String>,
+    auth: Option<AzureAuth>,
     resource_name: String,
     deployment_name: String,
     api_version: String,
@@ -64,13 +85,16 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
 struct NativeMessage {
     role: String,
+    /// Either a plain string or an OpenAI-style multimodal parts array.
     #[serde(skip_serializing_if = "Option::is_none")]
-    content: Option<String>,
+    content: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -161,12 +185,82 @@ impl NativeResponseMessage {
     }
 }
 
+/// Azure's standard error envelope: `{"error":{"code":"...","message":"..."}}`.
+#[derive(Debug, Deserialize)]
+struct AzureErrorEnvelope {
+    error: AzureErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureErrorDetail {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Map Azure's error envelope into a readable, sanitized message. Falls back
+/// to the raw (sanitized) body when the response is not the standard envelope.
+fn format_azure_error(status: reqwest::StatusCode, body: &str) -> String {
+    if let Ok(envelope) = serde_json::from_str::<AzureErrorEnvelope>(body) {
+        let code = envelope.error.code.unwrap_or_else(|| "unknown".to_string());
+        let message = envelope.error.message.unwrap_or_default();
+        return format!(
+            "Azure OpenAI API error ({status}): {code}: {}",
+            super::sanitize_api_error(&message)
+        );
+    }
+    format!(
+        "Azure OpenAI API error ({status}): {}",
+        super::sanitize_api_error(body)
+    )
+}
+
+async fn azure_api_error(response: reqwest::Response) -> anyhow::Error {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
+    anyhow::anyhow!("{}", format_azure_error(status, &body))
+}
+
 impl AzureOpenAiProvider {
     pub fn new(
         credential: Option<&str>,
         resource_name: &str,
         deployment_name: &str,
         api_version: Option<&str>,
+    ) -> Self {
+        Self::with_auth(
+            credential.map(|key| AzureAuth::ApiKey(key.to_string())),
+            resource_name,
+            deployment_name,
+            api_version,
+        )
+    }
+
+    /// Construct with a Microsoft Entra ID (AAD) access token instead of a
+    /// resource key. The token is sent as `Authorization: Bearer`.
+    pub fn new_with_aad_token(
+        token: &str,
+        resource_name: &str,
+        deployment_name: &str,
+        api_version: Option<&str>,
+    ) -> Self {
+        Self::with_auth(
+            Some(AzureAuth::AadToken(token.to_string())),
+            resource_name,
+            deployment_name,
+            api_version,
+        )
+    }
+
+    fn with_auth(
+        auth: Option<AzureAuth>,
+        resource_name: &str,
+        deployment_name: &str,
+        api_version: Option<&str>,
     ) -> Self {
         let version = api_version.unwrap_or(DEFAULT_API_VERSION);
         let base_url = format!(
@@ -174,7 +268,7 @@ impl AzureOpenAiProvider {
             resource_name, deployment_name
         );
         Self {
-            credential: credential.map(ToString::to_string),
+            auth,
             resource_name: resource_name.to_string(),
             deployment_name: deployment_name.to_string(),
             api_version: version.to_string(),
@@ -182,6 +276,15 @@ impl AzureOpenAiProvider {
         }
     }
 
+    fn require_auth(&self) -> anyhow::Result<&AzureAuth> {
+        self.auth.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Azure OpenAI API key not set. Set AZURE_OPENAI_API_KEY (or \
+                 AZURE_OPENAI_AAD_TOKEN for Entra ID auth) or edit config.toml."
+            )
+        })
+    }
+
     fn chat_completions_url(&self) -> String {
         format!(
             "{}/chat/completions?api-version={}",
@@ -205,6 +308,29 @@ impl AzureOpenAiProvider {
         })
     }
 
+    /// Convert user-message text into chat-completions content, expanding
+    /// inline image markers into OpenAI-style multimodal parts so vision
+    /// requests pass through unchanged.
+    fn user_message_content(content: &str) -> serde_json::Value {
+        let (cleaned_text, image_refs) = multimodal::parse_image_markers(content);
+        if image_refs.is_empty() {
+            return serde_json::Value::String(content.to_string());
+        }
+
+        let mut parts = Vec::with_capacity(image_refs.len() + 1);
+        let trimmed_text = cleaned_text.trim();
+        if !trimmed_text.is_empty() {
+            parts.push(serde_json::json!({ "type": "text", "text": trimmed_text }));
+        }
+        for image_ref in image_refs {
+            parts.push(serde_json::json!({
+                "type": "image_url",
+                "image_url": { "url": image_ref }
+            }));
+        }
+        serde_json::Value::Array(parts)
+    }
+
     fn convert_messages(messages: &[ChatMessage]) -> Vec<NativeMessage> {
         messages
             .iter()
@@ -231,7 +357,7 @@ impl AzureOpenAiProvider {
                                 let content = value
                                     .get("content")
                                     .and_then(serde_json::Value::as_str)
-                                    .map(ToString::to_string);
+                                    .map(|text| serde_json::Value::String(text.to_string()));
                                 let reasoning_content = value
                                     .get("reasoning_content")
                                     .and_then(serde_json::Value::as_str)
@@ -257,7 +383,7 @@ impl AzureOpenAiProvider {
                         let content = value
                             .get("content")
                             .and_then(serde_json::Value::as_str)
-                            .map(ToString::to_string);
+                            .map(|text| serde_json::Value::String(text.to_string()));
                         return NativeMessage {
                             role: "tool".to_string(),
                             content,
@@ -268,9 +394,14 @@ impl AzureOpenAiProvider {
                     }
                 }
 
+                let content = if m.role == "user" {
+                    Self::user_message_content(&m.content)
+                } else {
+                    serde_json::Value::String(m.content.clone())
+                };
                 NativeMessage {
                     role: m.role.clone(),
-                    content: Some(m.content.clone()),
+                    content: Some(content),
                     tool_call_id: None,
                     tool_calls: None,
                     reasoning_content: None,
@@ -349,11 +480,7 @@ impl Provider for AzureOpenAiProvider {
         _model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let credential = self.credential.as_ref().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Azure OpenAI API key not set. Set AZURE_OPENAI_API_KEY or edit config.toml."
-            )
-        })?;
+        let auth = self.require_auth()?;
 
         let mut messages = Vec::new();
 
@@ -374,16 +501,14 @@ impl Provider for AzureOpenAiProvider {
             temperature,
         };
 
-        let response = self
-            .http_client()
-            .post(self.chat_completions_url())
-            .header("api-key", credential.as_str())
+        let response = auth
+            .apply(self.http_client().post(self.chat_completions_url()))
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(super::api_error("Azure OpenAI", response).await);
+            return Err(azure_api_error(response).await);
         }
 
         let chat_response: ChatResponse = response.json().await?;
@@ -402,11 +527,7 @@ impl Provider for AzureOpenAiProvider {
         _model: &str,
         temperature: f64,
     ) -> anyhow::Result<ProviderChatResponse> {
-        let credential = self.credential.as_ref().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Azure OpenAI API key not set. Set AZURE_OPENAI_API_KEY or edit config.toml."
-            )
-        })?;
+        let auth = self.require_auth()?;
 
         let tools = Self::convert_tools(request.tools);
         let native_request = NativeChatRequest {
@@ -414,18 +535,17 @@ impl Provider for AzureOpenAiProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            stream: None,
         };
 
-        let response = self
-            .http_client()
-            .post(self.chat_completions_url())
-            .header("api-key", credential.as_str())
+        let response = auth
+            .apply(self.http_client().post(self.chat_completions_url()))
             .json(&native_request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(super::api_error("Azure OpenAI", response).await);
+            return Err(azure_api_error(response).await);
         }
 
         let native_response: NativeChatResponse = response.json().await?;
@@ -433,6 +553,7 @@ impl Provider for AzureOpenAiProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let message = native_response
             .choices
@@ -452,11 +573,7 @@ impl Provider for AzureOpenAiProvider {
         _model: &str,
         temperature: f64,
     ) -> anyhow::Result<ProviderChatResponse> {
-        let credential = self.credential.as_ref().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Azure OpenAI API key not set. Set AZURE_OPENAI_API_KEY or edit config.toml."
-            )
-        })?;
+        let auth = self.require_auth()?;
 
         let native_tools: Option<Vec<NativeToolSpec>> = if tools.is_empty() {
             None
@@ -475,18 +592,17 @@ impl Provider for AzureOpenAiProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            stream: None,
         };
 
-        let response = self
-            .http_client()
-            .post(self.chat_completions_url())
-            .header("api-key", credential.as_str())
+        let response = auth
+            .apply(self.http_client().post(self.chat_completions_url()))
             .json(&native_request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(super::api_error("Azure OpenAI", response).await);
+            return Err(azure_api_error(response).await);
         }
 
         let native_response: NativeChatResponse = response.json().await?;
@@ -494,6 +610,7 @@ impl Provider for AzureOpenAiProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let message = native_response
             .choices
@@ -506,6 +623,82 @@ impl Provider for AzureOpenAiProvider {
         Ok(result)
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        _model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let auth = match self.require_auth() {
+            Ok(auth) => auth.clone(),
+            Err(e) => {
+                return stream::once(async move { Err(StreamError::Provider(e.to_string())) })
+                    .boxed();
+            }
+        };
+
+        let request = NativeChatRequest {
+            messages: Self::convert_messages(messages),
+            temperature,
+            tools: None,
+            tool_choice: None,
+            stream: Some(options.enabled),
+        };
+
+        let url = self.chat_completions_url();
+        let client = self.http_client();
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+        tokio::spawn(async move {
+            let req_builder = auth
+                .apply(client.post(&url))
+                .header("Accept", "text/event-stream")
+                .json(&request);
+
+            let response = match req_builder.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| format!("HTTP error: {status}"));
+                let _ = tx
+                    .send(Err(StreamError::Provider(format_azure_error(
+                        status, &body,
+                    ))))
+                    .await;
+                return;
+            }
+
+            // Azure streams OpenAI-format chat-completions chunks.
+            let mut chunk_stream =
+                super::compatible::sse_bytes_to_chunks(response, options.count_tokens);
+            while let Some(chunk) = chunk_stream.next().await {
+                if tx.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         // Azure OpenAI does not have a lightweight models endpoint,
         // so warmup is a no-op to avoid unnecessary API calls.
@@ -551,11 +744,32 @@ mod tests {
 
     #[test]
     fn auth_header_uses_api_key_not_bearer() {
-        // This test verifies the provider stores the credential correctly
-        // and that the auth header name is "api-key" (verified via the
-        // implementation in chat_with_system which uses .header("api-key", ...)).
+        // An API-key credential must be stored as AzureAuth::ApiKey, which
+        // AzureAuth::apply sends via the "api-key" header rather than as a
+        // bearer token.
         let p = AzureOpenAiProvider::new(Some("my-azure-key"), "resource", "deployment", None);
-        assert_eq!(p.credential.as_deref(), Some("my-azure-key"));
+        match p.auth {
+            Some(AzureAuth::ApiKey(ref key)) => assert_eq!(key, "my-azure-key"),
+            _ => panic!("expected AzureAuth::ApiKey"),
+        }
+    }
+
+    #[test]
+    fn aad_token_constructor_stores_bearer_credential() {
+        let p = AzureOpenAiProvider::new_with_aad_token(
+            "eyJ-aad-token",
+            "resource",
+            "deployment",
+            None,
+        );
+        match p.auth {
+            Some(AzureAuth::AadToken(ref token)) => assert_eq!(token, "eyJ-aad-token"),
+            _ => panic!("expected AzureAuth::AadToken"),
+        }
+        assert_eq!(
+            p.chat_completions_url(),
+            "https://resource.openai.azure.com/openai/deployments/deployment/chat/completions?api-version=2024-08-01-preview"
+        );
     }
 
     #[test]
@@ -566,7 +780,7 @@ mod tests {
             "deployment",
             None,
         );
-        assert_eq!(p.credential.as_deref(), Some("azure-test-credential"));
+        assert!(matches!(p.auth, Some(AzureAuth::ApiKey(_))));
         assert_eq!(p.resource_name, "resource");
         assert_eq!(p.deployment_name, "deployment");
         assert_eq!(p.api_version, DEFAULT_API_VERSION);
@@ -575,7 +789,7 @@ mod tests {
     #[test]
     fn creates_without_credential() {
         let p = AzureOpenAiProvider::new(None, "resource", "deployment", None);
-        assert!(p.credential.is_none());
+        assert!(p.auth.is_none());
     }
 
     #[tokio::test]
@@ -756,4 +970,62 @@ mod tests {
         let p = AzureOpenAiProvider::new(Some("key"), "resource", "deployment", Some("2025-01-01"));
         assert_eq!(p.api_version, "2025-01-01");
     }
+
+    #[test]
+    fn azure_error_envelope_maps_to_readable_message() {
+        let body = r#"{"error":{"code":"DeploymentNotFound","message":"The API deployment for this resource does not exist."}}"#;
+        let formatted = format_azure_error(reqwest::StatusCode::NOT_FOUND, body);
+        assert!(formatted.contains("Azure OpenAI API error (404 Not Found)"));
+        assert!(formatted.contains("DeploymentNotFound"));
+        assert!(formatted.contains("does not exist"));
+    }
+
+    #[test]
+    fn azure_error_falls_back_to_raw_body_when_not_enveloped() {
+        let formatted =
+            format_azure_error(reqwest::StatusCode::BAD_GATEWAY, "upstream connect error");
+        assert!(formatted.contains("Azure OpenAI API error (502 Bad Gateway)"));
+        assert!(formatted.contains("upstream connect error"));
+    }
+
+    #[test]
+    fn user_message_content_expands_image_markers_to_parts() {
+        let content = "Describe this\n\n[IMAGE:data:image/png;base64,abcd]";
+        let value = AzureOpenAiProvider::user_message_content(content);
+        let parts = value.as_array().expect("expected multimodal parts array");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0]["type"], "text");
+        assert_eq!(parts[0]["text"], "Describe this");
+        assert_eq!(parts[1]["type"], "image_url");
+        assert_eq!(parts[1]["image_url"]["url"], "data:image/png;base64,abcd");
+    }
+
+    #[test]
+    fn user_message_content_keeps_plain_text_as_string() {
+        let value = AzureOpenAiProvider::user_message_content("just text");
+        assert_eq!(value, serde_json::json!("just text"));
+    }
+
+    #[test]
+    fn native_request_serializes_stream_flag_only_when_set() {
+        let request = NativeChatRequest {
+            messages: vec![],
+            temperature: 0.0,
+            tools: None,
+            tool_choice: None,
+            stream: Some(true),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"stream\":true"));
+
+        let request = NativeChatRequest {
+            messages: vec![],
+            temperature: 0.0,
+            tools: None,
+            tool_choice: None,
+            stream: None,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains("stream"));
+    }
 }
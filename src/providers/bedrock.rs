@@ -8,10 +8,12 @@
 
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
+    Provider, ProviderCapabilities, StreamChunk, StreamError, StreamOptions, StreamResult,
+    TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
 use hmac::{Hmac, Mac};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -27,6 +29,7 @@ const DEFAULT_MAX_TOKENS: u32 = 4096;
 // ── Authentication ──────────────────────────────────────────────
 
 /// Authentication method for Bedrock: either SigV4 (AKSK) or Bearer token.
+#[derive(Clone)]
 enum BedrockAuth {
     SigV4(AwsCredentials),
     BearerToken(String),
@@ -35,6 +38,7 @@ enum BedrockAuth {
 // ── AWS Credentials ─────────────────────────────────────────────
 
 /// Resolved AWS credentials for SigV4 signing.
+#[derive(Clone)]
 struct AwsCredentials {
     access_key_id: String,
     secret_access_key: String,
@@ -143,11 +147,24 @@ impl AwsCredentials {
     }
 
     /// Resolve credentials: env vars first, then EC2 IMDS.
+    ///
+    /// When both sources fail, the error spells out every option so `doctor`
+    /// (and users reading logs) can see exactly what is missing.
     async fn resolve() -> anyhow::Result<Self> {
-        if let Ok(creds) = Self::from_env() {
-            return Ok(creds);
+        let env_error = match Self::from_env() {
+            Ok(creds) => return Ok(creds),
+            Err(e) => e,
+        };
+        match Self::from_imds().await {
+            Ok(creds) => Ok(creds),
+            Err(imds_error) => Err(anyhow::anyhow!(
+                "AWS credential resolution failed: {env_error}; EC2 instance \
+                 metadata also unavailable ({imds_error}). Set AWS_ACCESS_KEY_ID \
+                 and AWS_SECRET_ACCESS_KEY (plus AWS_SESSION_TOKEN for temporary \
+                 credentials), set BEDROCK_API_KEY for Bearer token auth, or run \
+                 on an EC2 instance with an IAM role attached."
+            )),
         }
-        Self::from_imds().await
     }
 
     fn host(&self) -> String {
@@ -252,6 +269,58 @@ fn build_authorization_header(
     )
 }
 
+// ── AWS event-stream decoding (ConverseStream) ──────────────────
+
+/// Split complete `application/vnd.amazon.eventstream` frames off the front
+/// of `buffer`, returning each frame's payload. A frame is:
+/// total length (u32 BE), headers length (u32 BE), prelude CRC (4 bytes),
+/// headers, payload, message CRC (4 bytes). Incomplete trailing bytes stay
+/// in the buffer for the next network chunk.
+fn drain_event_stream_payloads(buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+    const PRELUDE_LEN: usize = 12;
+    const MESSAGE_CRC_LEN: usize = 4;
+
+    let mut payloads = Vec::new();
+    loop {
+        if buffer.len() < PRELUDE_LEN {
+            break;
+        }
+        let total_len = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+        if total_len < PRELUDE_LEN + MESSAGE_CRC_LEN || buffer.len() < total_len {
+            break;
+        }
+        let headers_len = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]) as usize;
+        let payload_start = PRELUDE_LEN + headers_len;
+        let payload_end = total_len - MESSAGE_CRC_LEN;
+        if payload_start <= payload_end {
+            payloads.push(buffer[payload_start..payload_end].to_vec());
+        }
+        buffer.drain(..total_len);
+    }
+    payloads
+}
+
+/// Map a ConverseStream event payload to a text or reasoning chunk.
+/// Non-delta events (messageStart, contentBlockStop, metadata, …) yield None.
+fn event_payload_to_chunk(payload: &[u8]) -> Option<StreamChunk> {
+    let value: serde_json::Value = serde_json::from_slice(payload).ok()?;
+    let delta = value.get("delta")?;
+    if let Some(text) = delta.get("text").and_then(serde_json::Value::as_str) {
+        if !text.is_empty() {
+            return Some(StreamChunk::delta(text.to_string()));
+        }
+    }
+    if let Some(reasoning) = delta
+        .pointer("/reasoningContent/text")
+        .and_then(serde_json::Value::as_str)
+    {
+        if !reasoning.is_empty() {
+            return Some(StreamChunk::reasoning(reasoning.to_string()));
+        }
+    }
+    None
+}
+
 // ── Converse API Types (Request) ────────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -541,6 +610,16 @@ impl BedrockProvider {
         format!("/model/{encoded}/converse")
     }
 
+    /// Streaming variants of `endpoint_url` / `canonical_uri` (ConverseStream).
+    fn stream_endpoint_url(region: &str, model_id: &str) -> String {
+        format!("https://{ENDPOINT_PREFIX}.{region}.amazonaws.com/model/{model_id}/converse-stream")
+    }
+
+    fn stream_canonical_uri(model_id: &str) -> String {
+        let encoded = Self::encode_model_path(model_id);
+        format!("/model/{encoded}/converse-stream")
+    }
+
     fn require_auth(&self) -> anyhow::Result<&BedrockAuth> {
         self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -568,11 +647,8 @@ impl BedrockProvider {
         if let Some(token) = env_optional("BEDROCK_API_KEY") {
             return Ok(BedrockAuth::BearerToken(token));
         }
-        // Fall back to SigV4.
-        if let Ok(creds) = AwsCredentials::from_env() {
-            return Ok(BedrockAuth::SigV4(creds));
-        }
-        Ok(BedrockAuth::SigV4(AwsCredentials::from_imds().await?))
+        // Fall back to SigV4 (env vars, then IMDS).
+        Ok(BedrockAuth::SigV4(AwsCredentials::resolve().await?))
     }
 
     // ── Cache heuristics (same thresholds as AnthropicProvider) ──
@@ -899,6 +975,7 @@ impl BedrockProvider {
             input_tokens: u.input_tokens,
             output_tokens: u.output_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
 
         if let Some(output) = response.output {
@@ -1170,6 +1247,148 @@ impl Provider for BedrockProvider {
         Ok(Self::parse_converse_response(response))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let auth = match self.require_auth() {
+            Ok(auth) => auth.clone(),
+            Err(e) => {
+                return stream::once(async move { Err(StreamError::Provider(e.to_string())) })
+                    .boxed();
+            }
+        };
+
+        let (system, converse_messages) = Self::convert_messages(messages);
+        let request = ConverseRequest {
+            system,
+            messages: converse_messages,
+            inference_config: Some(InferenceConfig {
+                max_tokens: self.max_tokens,
+                temperature,
+            }),
+            tool_config: None,
+        };
+        let payload = match serde_json::to_vec(&request) {
+            Ok(payload) => payload,
+            Err(e) => {
+                return stream::once(async move { Err(StreamError::Json(e)) }).boxed();
+            }
+        };
+
+        let model = model.to_string();
+        let client = self.http_client();
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+        tokio::spawn(async move {
+            let req_builder = match &auth {
+                BedrockAuth::BearerToken(token) => {
+                    let region = Self::resolve_region();
+                    let url = Self::stream_endpoint_url(&region, &model);
+                    client
+                        .post(&url)
+                        .header("content-type", "application/json")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(payload)
+                }
+                BedrockAuth::SigV4(credentials) => {
+                    let url = Self::stream_endpoint_url(&credentials.region, &model);
+                    let canonical_uri = Self::stream_canonical_uri(&model);
+                    let now = chrono::Utc::now();
+                    let host = credentials.host();
+                    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+                    let mut headers_to_sign = vec![
+                        ("content-type".to_string(), "application/json".to_string()),
+                        ("host".to_string(), host),
+                        ("x-amz-date".to_string(), amz_date.clone()),
+                    ];
+                    if let Some(ref session_token) = credentials.session_token {
+                        headers_to_sign
+                            .push(("x-amz-security-token".to_string(), session_token.clone()));
+                    }
+                    headers_to_sign.sort_by(|a, b| a.0.cmp(&b.0));
+
+                    let authorization = build_authorization_header(
+                        credentials,
+                        "POST",
+                        &canonical_uri,
+                        "",
+                        &headers_to_sign,
+                        &payload,
+                        &now,
+                    );
+
+                    let mut req = client
+                        .post(&url)
+                        .header("content-type", "application/json")
+                        .header("x-amz-date", &amz_date)
+                        .header("authorization", &authorization);
+                    if let Some(ref session_token) = credentials.session_token {
+                        req = req.header("x-amz-security-token", session_token);
+                    }
+                    req.body(payload)
+                }
+            };
+
+            let response = match req_builder.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!(
+                        "Bedrock API error ({status}): {}",
+                        super::sanitize_api_error(&body)
+                    ))))
+                    .await;
+                return;
+            }
+
+            let mut bytes_stream = response.bytes_stream();
+            let mut buffer: Vec<u8> = Vec::new();
+            while let Some(item) = bytes_stream.next().await {
+                match item {
+                    Ok(bytes) => {
+                        buffer.extend_from_slice(&bytes);
+                        for frame_payload in drain_event_stream_payloads(&mut buffer) {
+                            if let Some(chunk) = event_payload_to_chunk(&frame_payload) {
+                                if tx.send(Ok(chunk)).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(StreamError::Http(e))).await;
+                        return;
+                    }
+                }
+            }
+
+            let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         let region = match self.auth {
             Some(BedrockAuth::SigV4(ref creds)) => creds.region.clone(),
@@ -1182,6 +1401,85 @@ impl Provider for BedrockProvider {
     }
 }
 
+// ── Model catalog (ListFoundationModels) ────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListFoundationModelsResponse {
+    #[serde(default)]
+    model_summaries: Vec<FoundationModelSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundationModelSummary {
+    model_id: String,
+}
+
+/// List foundation model ids via the Bedrock control plane
+/// (`bedrock.{region}.amazonaws.com`, not the runtime endpoint). Used by
+/// `models refresh`. Credentials resolve the same way as the provider:
+/// `BEDROCK_API_KEY` Bearer token first, then the SigV4 chain.
+pub async fn list_foundation_models() -> anyhow::Result<Vec<String>> {
+    let client =
+        crate::config::build_runtime_proxy_client_with_timeouts("provider.bedrock", 30, 10);
+
+    let response = if let Some(token) = env_optional("BEDROCK_API_KEY") {
+        let region = BedrockProvider::resolve_region();
+        let url = format!("https://bedrock.{region}.amazonaws.com/foundation-models");
+        client
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .send()
+            .await?
+    } else {
+        let credentials = AwsCredentials::resolve().await?;
+        let host = format!("bedrock.{}.amazonaws.com", credentials.region);
+        let url = format!("https://{host}/foundation-models");
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut headers_to_sign = vec![
+            ("host".to_string(), host),
+            ("x-amz-date".to_string(), amz_date.clone()),
+        ];
+        if let Some(ref session_token) = credentials.session_token {
+            headers_to_sign.push(("x-amz-security-token".to_string(), session_token.clone()));
+        }
+        headers_to_sign.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let authorization = build_authorization_header(
+            &credentials,
+            "GET",
+            "/foundation-models",
+            "",
+            &headers_to_sign,
+            b"",
+            &now,
+        );
+
+        let mut request = client
+            .get(&url)
+            .header("x-amz-date", &amz_date)
+            .header("authorization", &authorization);
+        if let Some(ref session_token) = credentials.session_token {
+            request = request.header("x-amz-security-token", session_token);
+        }
+        request.send().await?
+    };
+
+    if !response.status().is_success() {
+        return Err(super::api_error("Bedrock", response).await);
+    }
+
+    let parsed: ListFoundationModelsResponse = response.json().await?;
+    Ok(parsed
+        .model_summaries
+        .into_iter()
+        .map(|summary| summary.model_id)
+        .collect())
+}
+
 // ── Tests ───────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -1844,4 +2142,99 @@ mod tests {
             panic!("Expected ToolResult");
         }
     }
+
+    /// Build a synthetic event-stream frame with no headers around `payload`.
+    fn event_stream_frame(payload: &[u8]) -> Vec<u8> {
+        let total_len = (12 + payload.len() + 4) as u32;
+        let mut frame = Vec::new();
+        frame.extend_from_slice(&total_len.to_be_bytes());
+        frame.extend_from_slice(&0u32.to_be_bytes()); // headers length
+        frame.extend_from_slice(&[0, 0, 0, 0]); // prelude CRC (unchecked)
+        frame.extend_from_slice(payload);
+        frame.extend_from_slice(&[0, 0, 0, 0]); // message CRC (unchecked)
+        frame
+    }
+
+    #[test]
+    fn drain_event_stream_payloads_splits_frames_and_keeps_partial() {
+        let first = br#"{"delta":{"text":"Hello"}}"#;
+        let second = br#"{"stopReason":"end_turn"}"#;
+        let mut buffer = event_stream_frame(first);
+        buffer.extend_from_slice(&event_stream_frame(second));
+        // Append a truncated third frame: it must survive in the buffer.
+        let partial = event_stream_frame(br#"{"delta":{"text":"tail"}}"#);
+        buffer.extend_from_slice(&partial[..10]);
+
+        let payloads = drain_event_stream_payloads(&mut buffer);
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0], first);
+        assert_eq!(payloads[1], second);
+        assert_eq!(buffer, &partial[..10]);
+    }
+
+    #[test]
+    fn drain_event_stream_payloads_waits_for_complete_prelude() {
+        let mut buffer = vec![0u8, 0, 0];
+        assert!(drain_event_stream_payloads(&mut buffer).is_empty());
+        assert_eq!(buffer.len(), 3);
+    }
+
+    #[test]
+    fn event_payload_to_chunk_extracts_text_delta() {
+        let chunk =
+            event_payload_to_chunk(br#"{"contentBlockIndex":0,"delta":{"text":"Hi"}}"#).unwrap();
+        assert_eq!(chunk.delta, "Hi");
+        assert!(!chunk.is_final);
+    }
+
+    #[test]
+    fn event_payload_to_chunk_extracts_reasoning_delta() {
+        let chunk = event_payload_to_chunk(br#"{"delta":{"reasoningContent":{"text":"because"}}}"#)
+            .unwrap();
+        assert_eq!(chunk.reasoning.as_deref(), Some("because"));
+    }
+
+    #[test]
+    fn event_payload_to_chunk_ignores_non_delta_events() {
+        assert!(event_payload_to_chunk(br#"{"role":"assistant"}"#).is_none());
+        assert!(event_payload_to_chunk(br#"{"stopReason":"end_turn"}"#).is_none());
+        assert!(event_payload_to_chunk(b"not json").is_none());
+    }
+
+    #[test]
+    fn stream_canonical_uri_encodes_colons() {
+        assert_eq!(
+            BedrockProvider::stream_canonical_uri("anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            "/model/anthropic.claude-3-5-sonnet-20241022-v2%3A0/converse-stream"
+        );
+    }
+
+    #[test]
+    fn list_foundation_models_response_parses_model_ids() {
+        let json = r#"{
+            "modelSummaries": [
+                {"modelId": "anthropic.claude-3-5-sonnet-20241022-v2:0", "modelName": "Claude 3.5 Sonnet"},
+                {"modelId": "anthropic.claude-3-haiku-20240307-v1:0", "modelName": "Claude 3 Haiku"}
+            ]
+        }"#;
+        let parsed: ListFoundationModelsResponse = serde_json::from_str(json).unwrap();
+        let ids: Vec<String> = parsed
+            .model_summaries
+            .into_iter()
+            .map(|summary| summary.model_id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "anthropic.claude-3-5-sonnet-20241022-v2:0",
+                "anthropic.claude-3-haiku-20240307-v1:0"
+            ]
+        );
+    }
+
+    #[test]
+    fn list_foundation_models_response_tolerates_empty_payload() {
+        let parsed: ListFoundationModelsResponse = serde_json::from_str("{}").unwrap();
+        assert!(parsed.model_summaries.is_empty());
+    }
 }
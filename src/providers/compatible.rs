@@ -9,6 +9,7 @@ use crate::providers::traits::{
     ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
+use base64::Engine as _;
 use futures_util::{stream, StreamExt};
 use reqwest::{
     header::{HeaderMap, HeaderValue, USER_AGENT},
@@ -48,6 +49,11 @@ pub struct OpenAiCompatibleProvider {
     api_path: Option<String>,
     /// Maximum output tokens to include in API requests.
     max_tokens: Option<u32>,
+    /// Extra PEM root certificates to trust (internal PKI), from
+    /// `[providers.custom] ca_bundle`.
+    tls_ca_bundle: Option<std::path::PathBuf>,
+    /// Skip TLS certificate verification. Only for trusted internal networks.
+    tls_accept_invalid_certs: bool,
 }
 
 /// How the provider expects the API key to be sent.
@@ -55,12 +61,32 @@ pub struct OpenAiCompatibleProvider {
 pub enum AuthStyle {
     /// `Authorization: Bearer <key>`
     Bearer,
+    /// `Authorization: Basic <base64(key)>` (self-hosted gateways)
+    Basic,
     /// `x-api-key: <key>` (used by some Chinese providers)
     XApiKey,
     /// Custom header name
     Custom(String),
 }
 
+impl AuthStyle {
+    /// Render the credential as a `(header name, header value)` pair.
+    pub(crate) fn header_pair(&self, credential: &str) -> (String, String) {
+        match self {
+            Self::Bearer => ("Authorization".to_string(), format!("Bearer {credential}")),
+            Self::Basic => (
+                "Authorization".to_string(),
+                format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode(credential)
+                ),
+            ),
+            Self::XApiKey => ("x-api-key".to_string(), credential.to_string()),
+            Self::Custom(header) => (header.clone(), credential.to_string()),
+        }
+    }
+}
+
 impl OpenAiCompatibleProvider {
     pub fn new(
         name: &str,
@@ -186,6 +212,8 @@ impl OpenAiCompatibleProvider {
             reasoning_effort: None,
             api_path: None,
             max_tokens: None,
+            tls_ca_bundle: None,
+            tls_accept_invalid_certs: false,
         }
     }
 
@@ -210,6 +238,30 @@ impl OpenAiCompatibleProvider {
         self
     }
 
+    /// Add extra HTTP headers without overriding ones already set. Used to
+    /// layer global `extra_headers` config under provider-specific headers.
+    pub fn with_additional_headers(
+        mut self,
+        headers: std::collections::HashMap<String, String>,
+    ) -> Self {
+        for (key, value) in headers {
+            self.extra_headers.entry(key).or_insert(value);
+        }
+        self
+    }
+
+    /// Configure TLS trust for self-hosted endpoints: an extra PEM CA bundle
+    /// and/or disabled certificate verification (`[providers.custom]`).
+    pub fn with_tls_options(
+        mut self,
+        ca_bundle: Option<std::path::PathBuf>,
+        accept_invalid_certs: bool,
+    ) -> Self {
+        self.tls_ca_bundle = ca_bundle;
+        self.tls_accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
     /// Set reasoning effort for GPT-5/Codex-compatible chat-completions APIs.
     pub fn with_reasoning_effort(mut self, reasoning_effort: Option<String>) -> Self {
         self.reasoning_effort = reasoning_effort;
@@ -264,8 +316,9 @@ impl OpenAiCompatibleProvider {
         let timeout = self.timeout_secs;
         let has_user_agent = self.user_agent.is_some();
         let has_extra_headers = !self.extra_headers.is_empty();
+        let has_tls_options = self.tls_ca_bundle.is_some() || self.tls_accept_invalid_certs;
 
-        if has_user_agent || has_extra_headers {
+        if has_user_agent || has_extra_headers || has_tls_options {
             let mut headers = HeaderMap::new();
             if let Some(ua) = self.user_agent.as_deref() {
                 if let Ok(value) = HeaderValue::from_str(ua) {
@@ -286,10 +339,15 @@ impl OpenAiCompatibleProvider {
                 }
             }
 
-            let builder = Client::builder()
+            let mut builder = Client::builder()
                 .timeout(std::time::Duration::from_secs(timeout))
                 .connect_timeout(std::time::Duration::from_secs(10))
                 .default_headers(headers);
+            builder = apply_tls_options(
+                builder,
+                self.tls_ca_bundle.as_deref(),
+                self.tls_accept_invalid_certs,
+            );
             let builder =
                 crate::config::apply_runtime_proxy_to_builder(builder, "provider.compatible");
 
@@ -414,6 +472,39 @@ impl OpenAiCompatibleProvider {
     }
 }
 
+/// Apply custom TLS trust settings to a client builder: an extra PEM CA
+/// bundle for internal PKI and/or disabled certificate verification.
+/// Unreadable bundles are logged and skipped rather than failing the client.
+pub(crate) fn apply_tls_options(
+    mut builder: reqwest::ClientBuilder,
+    ca_bundle: Option<&std::path::Path>,
+    accept_invalid_certs: bool,
+) -> reqwest::ClientBuilder {
+    if let Some(path) = ca_bundle {
+        match std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|pem| {
+                reqwest::Certificate::from_pem_bundle(&pem).map_err(anyhow::Error::from)
+            }) {
+            Ok(certificates) => {
+                for certificate in certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    "Failed to load TLS CA bundle, continuing without it: {error}"
+                );
+            }
+        }
+    }
+    if accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    builder
+}
+
 #[derive(Debug, Serialize)]
 struct ApiChatRequest {
     model: String,
@@ -733,6 +824,10 @@ struct ResponsesContent {
 struct StreamChunkResponse {
     #[serde(default)]
     choices: Vec<StreamChoice>,
+    /// Cumulative token usage, sent on the final chunk by endpoints that
+    /// support `stream_options.include_usage` (OpenRouter sends it by default).
+    #[serde(default)]
+    usage: Option<UsageInfo>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -942,7 +1037,10 @@ fn parse_sse_line(line: &str) -> StreamResult<Option<StreamChunk>> {
 }
 
 /// Convert SSE byte stream to text chunks.
-fn sse_bytes_to_chunks(
+///
+/// Shared with the Azure OpenAI provider, whose streaming chunks use the same
+/// OpenAI chat-completions wire format.
+pub(crate) fn sse_bytes_to_chunks(
     response: reqwest::Response,
     count_tokens: bool,
 ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
@@ -1042,6 +1140,7 @@ fn sse_bytes_to_events(
         let mut buffer = String::new();
         let mut tool_calls: Vec<StreamToolCallAccumulator> = Vec::new();
         let mut emitted_tool_calls = false;
+        let mut reported_usage: Option<TokenUsage> = None;
 
         match response.error_for_status_ref() {
             Ok(_) => {}
@@ -1136,6 +1235,17 @@ fn sse_bytes_to_events(
                             }
                         }
 
+                        // Usage arrives on the usage-bearing (usually last)
+                        // chunk with cumulative totals; keep the latest value.
+                        if let Some(u) = chunk.usage {
+                            reported_usage = Some(TokenUsage {
+                                input_tokens: u.prompt_tokens,
+                                output_tokens: u.completion_tokens,
+                                cached_input_tokens: None,
+                                cache_write_input_tokens: None,
+                            });
+                        }
+
                         if should_emit_tool_calls && !emitted_tool_calls {
                             emitted_tool_calls = true;
                             for tool_call in tool_calls
@@ -1167,6 +1277,12 @@ fn sse_bytes_to_events(
             }
         }
 
+        if let Some(usage) = reported_usage.take() {
+            if tx.send(Ok(StreamEvent::Usage(usage))).await.is_err() {
+                return;
+            }
+        }
+
         let _ = tx.send(Ok(StreamEvent::Final)).await;
     });
 
@@ -1279,11 +1395,8 @@ impl OpenAiCompatibleProvider {
         req: reqwest::RequestBuilder,
         credential: &str,
     ) -> reqwest::RequestBuilder {
-        match &self.auth_header {
-            AuthStyle::Bearer => req.header("Authorization", format!("Bearer {credential}")),
-            AuthStyle::XApiKey => req.header("x-api-key", credential),
-            AuthStyle::Custom(header) => req.header(header, credential),
-        }
+        let (name, value) = self.auth_header.header_pair(credential);
+        req.header(name, value)
     }
 
     async fn chat_via_responses(
@@ -1902,6 +2015,7 @@ impl Provider for OpenAiCompatibleProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let choice = chat_response
             .choices
@@ -2050,6 +2164,7 @@ impl Provider for OpenAiCompatibleProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let message = native_response
             .choices
@@ -2166,13 +2281,8 @@ impl Provider for OpenAiCompatibleProvider {
         tokio::spawn(async move {
             let mut req_builder = client.post(&url).json(&payload);
 
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
+            let (auth_name, auth_value) = auth_header.header_pair(&credential);
+            req_builder = req_builder.header(auth_name, auth_value);
             req_builder = req_builder.header("Accept", "text/event-stream");
 
             let response = match req_builder.send().await {
@@ -2267,13 +2377,8 @@ impl Provider for OpenAiCompatibleProvider {
             let mut req_builder = client.post(&url).json(&request);
 
             // Apply auth header
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
+            let (auth_name, auth_value) = auth_header.header_pair(&credential);
+            req_builder = req_builder.header(auth_name, auth_value);
 
             // Set accept header for streaming
             req_builder = req_builder.header("Accept", "text/event-stream");
@@ -2375,13 +2480,8 @@ impl Provider for OpenAiCompatibleProvider {
         tokio::spawn(async move {
             let mut req_builder = client.post(&url).json(&request);
 
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
+            let (auth_name, auth_value) = auth_header.header_pair(&credential);
+            req_builder = req_builder.header(auth_name, auth_value);
 
             req_builder = req_builder.header("Accept", "text/event-stream");
 
@@ -3697,6 +3797,26 @@ mod tests {
         assert_eq!(tool_call.arguments, r#"{"command":"date"}"#);
     }
 
+    #[test]
+    fn stream_chunk_parses_usage_from_final_chunk() {
+        let line = r#"data: {"choices":[],"usage":{"prompt_tokens":321,"completion_tokens":45}}"#;
+        let chunk = parse_sse_chunk(line)
+            .unwrap()
+            .expect("chunk should be parsed");
+        let usage = chunk.usage.expect("usage should be parsed");
+        assert_eq!(usage.prompt_tokens, Some(321));
+        assert_eq!(usage.completion_tokens, Some(45));
+    }
+
+    #[test]
+    fn stream_chunk_parses_without_usage() {
+        let line = r#"data: {"choices":[{"delta":{"content":"hi"}}]}"#;
+        let chunk = parse_sse_chunk(line)
+            .unwrap()
+            .expect("chunk should be parsed");
+        assert!(chunk.usage.is_none());
+    }
+
     #[test]
     fn api_response_parses_usage() {
         let json = r#"{
@@ -3865,6 +3985,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_additional_headers_keeps_existing_entries() {
+        let mut specific = std::collections::HashMap::new();
+        specific.insert("X-Org-Token".to_string(), "org-secret".to_string());
+        let mut global = std::collections::HashMap::new();
+        global.insert("X-Org-Token".to_string(), "global-value".to_string());
+        global.insert("X-Title".to_string(), "zeroclaw".to_string());
+
+        let p = make_provider("test", "https://example.com", None)
+            .with_extra_headers(specific)
+            .with_additional_headers(global);
+
+        assert_eq!(p.extra_headers.get("X-Org-Token").unwrap(), "org-secret");
+        assert_eq!(p.extra_headers.get("X-Title").unwrap(), "zeroclaw");
+    }
+
+    #[test]
+    fn basic_auth_style_sets_authorization_header() {
+        let p = OpenAiCompatibleProvider::new(
+            "custom",
+            "https://gateway.internal",
+            Some("user:pass"),
+            AuthStyle::Basic,
+        );
+        let request = p
+            .apply_auth_header(
+                Client::new().post("https://gateway.internal/v1"),
+                "user:pass",
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.headers().get("Authorization").unwrap(),
+            "Basic dXNlcjpwYXNz"
+        );
+    }
+
+    #[test]
+    fn custom_header_auth_style_injects_named_header() {
+        let p = OpenAiCompatibleProvider::new(
+            "custom",
+            "https://gateway.internal",
+            Some("org-secret"),
+            AuthStyle::Custom("X-Org-Token".into()),
+        );
+        let request = p
+            .apply_auth_header(
+                Client::new().post("https://gateway.internal/v1"),
+                "org-secret",
+            )
+            .build()
+            .unwrap();
+        assert_eq!(request.headers().get("X-Org-Token").unwrap(), "org-secret");
+    }
+
+    #[test]
+    fn http_client_with_tls_options_builds_successfully() {
+        let p = make_provider("test", "https://example.com", None)
+            .with_tls_options(Some(std::path::PathBuf::from("/nonexistent/ca.pem")), true);
+        // Missing bundle is logged and skipped; the client must still build.
+        let _client = p.http_client();
+    }
+
     #[test]
     fn http_client_with_extra_headers_builds_successfully() {
         let mut headers = std::collections::HashMap::new();
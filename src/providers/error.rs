@@ -0,0 +1,333 @@
+//! Structured provider error taxonomy.
+//!
+//! Providers historically surfaced failures as flat `anyhow!` strings, which
+//! forced the reliability layer and channel runtime into substring matching.
+//! [`ProviderError`] classifies a failed API call once — at construction, from
+//! the HTTP status, `Retry-After` header, and provider-specific error body —
+//! and travels inside the `anyhow::Error` chain so downstream code can
+//! `downcast_ref` instead of grepping message text. `Display` is lossless:
+//! the full sanitized provider message is preserved for logs, so existing
+//! string-based diagnostics keep working for errors raised outside this path.
+
+use std::time::Duration;
+
+/// What went wrong, as far as retry/fallback policy is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderErrorKind {
+    /// Transient rate limit (HTTP 429). Retrying after `retry_after` (when
+    /// the provider reported one) is expected to succeed.
+    RateLimited { retry_after: Option<Duration> },
+    /// Plan/billing exhaustion — retries cannot fix this request.
+    QuotaExhausted,
+    /// Invalid or missing credentials (HTTP 401/403 or equivalent body).
+    AuthFailed,
+    /// The prompt exceeds the model's context window.
+    ContextTooLarge,
+    /// The provider is temporarily overloaded or unavailable (5xx).
+    Overloaded,
+    /// Transport-level failure before an HTTP response was received.
+    Network,
+    /// Anything we could not classify.
+    Other,
+}
+
+/// Classified provider API failure. Constructed where the HTTP response is
+/// still available (status + headers + body); carried through `anyhow` so the
+/// reliability wrapper and channel runtime can branch on [`ProviderErrorKind`]
+/// without re-parsing message strings.
+#[derive(Debug, Clone)]
+pub struct ProviderError {
+    provider: String,
+    status: Option<u16>,
+    kind: ProviderErrorKind,
+    /// Full sanitized message, preserved verbatim for `Display`/logging.
+    message: String,
+}
+
+impl ProviderError {
+    /// Build from a failed HTTP response's parts. `body` must already be
+    /// sanitized (see `sanitize_api_error`); `retry_after` comes from the
+    /// `Retry-After` response header when present.
+    pub fn from_response_parts(
+        provider: &str,
+        status: reqwest::StatusCode,
+        retry_after: Option<Duration>,
+        body: &str,
+    ) -> Self {
+        Self {
+            provider: provider.to_string(),
+            status: Some(status.as_u16()),
+            kind: classify(status.as_u16(), retry_after, body),
+            message: format!("{provider} API error ({status}): {body}"),
+        }
+    }
+
+    /// Build from a transport-level failure (connect/timeout/TLS) where no
+    /// HTTP response was received.
+    pub fn network(provider: &str, error: &reqwest::Error) -> Self {
+        Self {
+            provider: provider.to_string(),
+            status: None,
+            kind: ProviderErrorKind::Network,
+            message: format!("{provider} request failed: {error}"),
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    pub fn kind(&self) -> ProviderErrorKind {
+        self.kind
+    }
+
+    /// Provider-reported retry delay, when rate limited with one.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.kind {
+            ProviderErrorKind::RateLimited { retry_after } => retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+/// Context-window hints observed across providers (OpenAI, Anthropic, Gemini,
+/// llama.cpp, and OpenAI-compatible gateways). Kept aligned with the string
+/// fallback in `reliable::is_context_window_exceeded`.
+const CONTEXT_HINTS: [&str; 10] = [
+    "exceeds the context window",
+    "exceeds the available context size",
+    "context window of this model",
+    "maximum context length",
+    "context length exceeded",
+    "too many tokens",
+    "token limit exceeded",
+    "prompt is too long",
+    "input is too long",
+    "prompt exceeds max length",
+];
+
+/// Auth hints for providers that report credential failures with a non-401
+/// status (e.g. Gemini returns 400 `API_KEY_INVALID`).
+const AUTH_HINTS: [&str; 8] = [
+    "invalid api key",
+    "incorrect api key",
+    "api key not valid",
+    "invalid x-api-key",
+    "authentication failed",
+    "invalid token",
+    "api_key_invalid",
+    "unauthenticated",
+];
+
+/// Business/plan hints on 429s where retrying is futile. Kept aligned with
+/// `reliable::is_non_retryable_rate_limit`.
+const QUOTA_HINTS: [&str; 12] = [
+    "plan does not include",
+    "insufficient balance",
+    "insufficient_balance",
+    "insufficient quota",
+    "insufficient_quota",
+    "quota exhausted",
+    "out of credits",
+    "no available package",
+    "package not active",
+    "purchase package",
+    "model not available for your plan",
+    "exceeded your current quota",
+];
+
+fn classify(status: u16, retry_after: Option<Duration>, body: &str) -> ProviderErrorKind {
+    let lower = body.to_lowercase();
+
+    if status == 401 || status == 403 || AUTH_HINTS.iter().any(|hint| lower.contains(hint)) {
+        return ProviderErrorKind::AuthFailed;
+    }
+
+    if status == 413 || CONTEXT_HINTS.iter().any(|hint| lower.contains(hint)) {
+        return ProviderErrorKind::ContextTooLarge;
+    }
+
+    if status == 429 {
+        if QUOTA_HINTS.iter().any(|hint| lower.contains(hint)) || has_quota_business_code(&lower) {
+            return ProviderErrorKind::QuotaExhausted;
+        }
+        return ProviderErrorKind::RateLimited {
+            retry_after: retry_after.or_else(|| parse_body_retry_delay(body)),
+        };
+    }
+
+    if (500..600).contains(&status) || lower.contains("overloaded") {
+        return ProviderErrorKind::Overloaded;
+    }
+
+    ProviderErrorKind::Other
+}
+
+/// Known provider business codes observed on 429 where retry is futile
+/// (e.g. Z.AI: 1311, 1113).
+fn has_quota_business_code(lower: &str) -> bool {
+    lower
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|token| token.parse::<u16>().ok())
+        .any(|code| matches!(code, 1113 | 1311))
+}
+
+/// Extract a retry delay embedded in the error body when the `Retry-After`
+/// header is absent: Gemini's `"retryDelay": "21s"` and OpenAI-style
+/// `retry_after`/`retry-after` fields.
+fn parse_body_retry_delay(body: &str) -> Option<Duration> {
+    let lower = body.to_lowercase();
+
+    for prefix in ["\"retrydelay\": \"", "\"retrydelay\":\""] {
+        if let Some(pos) = lower.find(prefix) {
+            let after = &lower[pos + prefix.len()..];
+            let digits: String = after
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            if let Ok(secs) = digits.parse::<f64>() {
+                if secs.is_finite() && secs >= 0.0 {
+                    return Some(Duration::from_secs_f64(secs));
+                }
+            }
+        }
+    }
+
+    for prefix in [
+        "retry-after:",
+        "retry_after:",
+        "retry-after ",
+        "retry_after ",
+    ] {
+        if let Some(pos) = lower.find(prefix) {
+            let after = lower[pos + prefix.len()..].trim_start();
+            let digits: String = after
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            if let Ok(secs) = digits.parse::<f64>() {
+                if secs.is_finite() && secs >= 0.0 {
+                    return Some(Duration::from_secs_f64(secs));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_for(status: u16, retry_after: Option<Duration>, body: &str) -> ProviderErrorKind {
+        ProviderError::from_response_parts(
+            "Test",
+            reqwest::StatusCode::from_u16(status).unwrap(),
+            retry_after,
+            body,
+        )
+        .kind()
+    }
+
+    #[test]
+    fn openai_insufficient_quota_classifies_as_quota_exhausted() {
+        let body = r#"{"error":{"message":"You exceeded your current quota, please check your plan and billing details.","type":"insufficient_quota","code":"insufficient_quota"}}"#;
+        assert_eq!(kind_for(429, None, body), ProviderErrorKind::QuotaExhausted);
+    }
+
+    #[test]
+    fn openai_rate_limit_honors_retry_after_header() {
+        let body = r#"{"error":{"message":"Rate limit reached for gpt-4o in organization org-x on tokens per min (TPM).","type":"tokens","code":"rate_limit_exceeded"}}"#;
+        assert_eq!(
+            kind_for(429, Some(Duration::from_secs(20)), body),
+            ProviderErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(20)),
+            }
+        );
+    }
+
+    #[test]
+    fn gemini_resource_exhausted_parses_retry_delay_from_body() {
+        let body = r#"{"error":{"code":429,"message":"You exceeded your current quota, please check your plan and billing details.","status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"21s"}]}}"#;
+        // Quota wording wins over the retry hint — retrying the same key is futile.
+        assert_eq!(kind_for(429, None, body), ProviderErrorKind::QuotaExhausted);
+
+        let transient = r#"{"error":{"code":429,"message":"Resource has been exhausted (e.g. check quota).","status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"21s"}]}}"#;
+        assert_eq!(
+            kind_for(429, None, transient),
+            ProviderErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(21)),
+            }
+        );
+    }
+
+    #[test]
+    fn gemini_invalid_api_key_classifies_as_auth_failed() {
+        let body = r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT","details":[{"reason":"API_KEY_INVALID"}]}}"#;
+        assert_eq!(kind_for(400, None, body), ProviderErrorKind::AuthFailed);
+    }
+
+    #[test]
+    fn anthropic_overloaded_and_context_errors_classify() {
+        let overloaded =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert_eq!(
+            kind_for(529, None, overloaded),
+            ProviderErrorKind::Overloaded
+        );
+
+        let context = r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 210034 tokens > 200000 maximum"}}"#;
+        assert_eq!(
+            kind_for(400, None, context),
+            ProviderErrorKind::ContextTooLarge
+        );
+    }
+
+    #[test]
+    fn zai_business_code_classifies_as_quota_exhausted() {
+        let body = r#"{"error":{"code":"1113","message":"Your account balance is insufficient, please recharge."}}"#;
+        assert_eq!(kind_for(429, None, body), ProviderErrorKind::QuotaExhausted);
+    }
+
+    #[test]
+    fn unauthorized_status_classifies_as_auth_failed() {
+        let body = r#"{"error":{"message":"Incorrect API key provided.","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        assert_eq!(kind_for(401, None, body), ProviderErrorKind::AuthFailed);
+    }
+
+    #[test]
+    fn display_is_lossless_and_matches_legacy_format() {
+        let body = r#"{"error":{"message":"boom"}}"#;
+        let error = ProviderError::from_response_parts(
+            "OpenRouter",
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            None,
+            body,
+        );
+        assert_eq!(
+            error.to_string(),
+            format!("OpenRouter API error (500 Internal Server Error): {body}")
+        );
+        assert_eq!(error.status(), Some(500));
+        assert_eq!(error.provider(), "OpenRouter");
+    }
+
+    #[test]
+    fn unclassified_client_error_is_other() {
+        let body = r#"{"error":{"message":"unknown parameter: foo"}}"#;
+        assert_eq!(kind_for(400, None, body), ProviderErrorKind::Other);
+    }
+}
@@ -5,7 +5,9 @@
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, Provider, TokenUsage};
+use crate::providers::traits::{
+    build_tool_instructions_text, ChatMessage, ChatRequest, ChatResponse, Provider, TokenUsage,
+};
 use async_trait::async_trait;
 use base64::Engine;
 use directories::UserDirs;
@@ -24,6 +26,9 @@ pub struct GeminiProvider {
     auth_service: Option<AuthService>,
     /// Override profile name for managed auth.
     auth_profile_override: Option<String>,
+    /// Static-prefix cache via `cachedContent` resources. `None` when
+    /// `[providers.gemini] context_caching` is off (the default).
+    context_cache: Option<Arc<ContextCacheManager>>,
 }
 
 /// Mutable OAuth token state — supports runtime refresh for long-lived processes.
@@ -78,6 +83,122 @@ impl GeminiAuth {
     }
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// CONTEXT CACHING (cachedContent)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// What to do with the static prefix on a given request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ContextCacheAction {
+    /// Prefix below the configured threshold — send it inline.
+    Inline,
+    /// No live cache — create a `cachedContent` resource.
+    Create,
+    /// Live cache matches the prefix — reference it by name.
+    Reuse { name: String },
+    /// Cache expired or the prefix changed — drop the old resource and create anew.
+    Recreate { previous: String },
+}
+
+/// A live `cachedContent` resource and the prefix it holds.
+struct ContextCacheState {
+    /// Resource name returned by the API (`cachedContents/...`).
+    name: String,
+    /// Hash of the cached prefix text — a mismatch means the prefix changed.
+    prefix_hash: u64,
+    /// Expiry as unix millis (creation time + TTL).
+    expires_at_millis: i64,
+}
+
+/// Lifecycle state machine for the static-prefix cache.
+///
+/// The decision logic lives in [`plan`](Self::plan), which is pure over
+/// `now_millis` so the create/reuse/expire/recreate transitions are unit
+/// testable without a live API.
+struct ContextCacheManager {
+    /// Minimum estimated prefix tokens before a cache is worth creating.
+    min_prefix_tokens: u32,
+    /// TTL requested for created resources, in seconds.
+    ttl_secs: u64,
+    state: std::sync::Mutex<Option<ContextCacheState>>,
+    /// Requests served from the cache (for runtime-trace diagnostics).
+    hits: std::sync::atomic::AtomicU64,
+}
+
+/// Refresh the cache this long before its TTL expires, so a reference never
+/// races the server-side eviction.
+const CONTEXT_CACHE_REFRESH_MARGIN_MILLIS: i64 = 60_000;
+
+impl ContextCacheManager {
+    fn new(min_prefix_tokens: u32, ttl_secs: u64) -> Self {
+        Self {
+            min_prefix_tokens,
+            ttl_secs,
+            state: std::sync::Mutex::new(None),
+            hits: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    /// Rough token estimate for threshold checks (≈4 chars per token).
+    fn estimate_tokens(text: &str) -> u32 {
+        u32::try_from(text.len() / 4).unwrap_or(u32::MAX)
+    }
+
+    fn hash_prefix(text: &str) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        text.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Decide what to do with a prefix of `estimated_tokens` hashing to
+    /// `prefix_hash` at time `now_millis`.
+    fn plan(&self, prefix_hash: u64, estimated_tokens: u32, now_millis: i64) -> ContextCacheAction {
+        if estimated_tokens < self.min_prefix_tokens {
+            return ContextCacheAction::Inline;
+        }
+        let state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        match state.as_ref() {
+            None => ContextCacheAction::Create,
+            Some(live) if live.prefix_hash != prefix_hash => ContextCacheAction::Recreate {
+                previous: live.name.clone(),
+            },
+            Some(live)
+                if now_millis + CONTEXT_CACHE_REFRESH_MARGIN_MILLIS >= live.expires_at_millis =>
+            {
+                ContextCacheAction::Recreate {
+                    previous: live.name.clone(),
+                }
+            }
+            Some(live) => ContextCacheAction::Reuse {
+                name: live.name.clone(),
+            },
+        }
+    }
+
+    /// Record a freshly created resource.
+    fn note_created(&self, name: String, prefix_hash: u64, now_millis: i64) {
+        let expires_at_millis =
+            now_millis.saturating_add(i64::try_from(self.ttl_secs * 1000).unwrap_or(i64::MAX));
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        *state = Some(ContextCacheState {
+            name,
+            prefix_hash,
+            expires_at_millis,
+        });
+    }
+
+    /// Forget the live cache, returning its resource name (for deletion).
+    fn invalidate(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        state.take().map(|s| s.name)
+    }
+
+    fn record_hit(&self) -> u64 {
+        self.hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // API REQUEST/RESPONSE TYPES
 // ══════════════════════════════════════════════════════════════════════════════
@@ -87,6 +208,10 @@ struct GenerateContentRequest {
     contents: Vec<Content>,
     #[serde(rename = "systemInstruction", skip_serializing_if = "Option::is_none")]
     system_instruction: Option<Content>,
+    /// Resource name of a `cachedContent` holding the static prefix
+    /// (public API only; the content replaces the inline system instruction).
+    #[serde(rename = "cachedContent", skip_serializing_if = "Option::is_none")]
+    cached_content: Option<String>,
     #[serde(rename = "generationConfig")]
     generation_config: GenerationConfig,
 }
@@ -157,6 +282,15 @@ struct InlineData {
 /// If the content contains [IMAGE:data:...] markers (already normalized by the
 /// multimodal pipeline), they are extracted as inline_data parts. The remaining
 /// text becomes a text part. Falls back to a single text part if no markers.
+/// Classified Gemini API error. Keeps the legacy
+/// `Gemini API error ({status}): {body}` display while letting the
+/// reliability layer branch on the typed kind (rate limit, auth, context).
+fn gemini_api_error(status: reqwest::StatusCode, error_text: &str) -> anyhow::Error {
+    anyhow::Error::new(super::error::ProviderError::from_response_parts(
+        "Gemini", status, None, error_text,
+    ))
+}
+
 fn build_parts(content: &str) -> Vec<Part> {
     let (text, image_refs) = crate::multimodal::parse_image_markers(content);
     let mut parts = Vec::new();
@@ -185,6 +319,46 @@ fn build_parts(content: &str) -> Vec<Part> {
     parts
 }
 
+/// Convert a unified chat history into Gemini `contents` plus an optional
+/// system instruction. System messages are joined into one instruction;
+/// the "assistant" role maps to Gemini's "model" role.
+fn history_to_contents(messages: &[ChatMessage]) -> (Vec<Content>, Option<Content>) {
+    let mut system_parts: Vec<&str> = Vec::new();
+    let mut contents: Vec<Content> = Vec::new();
+
+    for msg in messages {
+        match msg.role.as_str() {
+            "system" => {
+                system_parts.push(&msg.content);
+            }
+            "user" => {
+                contents.push(Content {
+                    role: Some("user".to_string()),
+                    parts: build_parts(&msg.content),
+                });
+            }
+            "assistant" => {
+                contents.push(Content {
+                    role: Some("model".to_string()),
+                    parts: vec![Part::text(&msg.content)],
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let system_instruction = if system_parts.is_empty() {
+        None
+    } else {
+        Some(Content {
+            role: None,
+            parts: vec![Part::text(system_parts.join("\n\n"))],
+        })
+    };
+
+    (contents, system_instruction)
+}
+
 #[derive(Debug, Serialize, Clone)]
 struct GenerationConfig {
     temperature: f64,
@@ -495,6 +669,7 @@ impl GeminiProvider {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            context_cache: None,
         }
     }
 
@@ -568,9 +743,24 @@ impl GeminiProvider {
                 None
             },
             auth_profile_override: profile_override,
+            context_cache: None,
         }
     }
 
+    /// Enable `cachedContent` context caching per `[providers.gemini]`.
+    ///
+    /// Only takes effect for API-key auth — the internal OAuth endpoint does
+    /// not expose the `cachedContents` API.
+    pub fn with_context_cache(mut self, config: &crate::config::GeminiProviderConfig) -> Self {
+        if config.context_caching {
+            self.context_cache = Some(Arc::new(ContextCacheManager::new(
+                config.context_cache_min_tokens,
+                config.context_cache_ttl_secs,
+            )));
+        }
+        self
+    }
+
     fn normalize_non_empty(value: &str) -> Option<String> {
         let trimmed = value.trim();
         if trimmed.is_empty() {
@@ -977,6 +1167,109 @@ impl GeminiProvider {
 }
 
 impl GeminiProvider {
+    /// Create a `cachedContent` resource holding the system instruction and
+    /// return its resource name (`cachedContents/...`). Public API only.
+    async fn create_cached_content(
+        &self,
+        auth: &GeminiAuth,
+        system_instruction: &Content,
+        model: &str,
+        ttl_secs: u64,
+    ) -> anyhow::Result<String> {
+        let url = format!(
+            "{PUBLIC_API_ENDPOINT}/cachedContents?key={}",
+            auth.api_key_credential()
+        );
+        let body = serde_json::json!({
+            "model": Self::format_model_name(model),
+            "systemInstruction": system_instruction,
+            "ttl": format!("{ttl_secs}s"),
+        });
+
+        let response = self.http_client().post(&url).json(&body).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("cachedContents create failed ({status}): {error_text}");
+        }
+
+        #[derive(Deserialize)]
+        struct CachedContentResponse {
+            name: String,
+        }
+
+        let created: CachedContentResponse = response.json().await?;
+        Ok(created.name)
+    }
+
+    /// Delete a `cachedContent` resource. Failures are logged, not fatal —
+    /// the resource expires on its own TTL regardless.
+    async fn delete_cached_content(&self, auth: &GeminiAuth, name: &str) {
+        let url = format!(
+            "{PUBLIC_API_ENDPOINT}/{name}?key={}",
+            auth.api_key_credential()
+        );
+        if let Err(e) = self.http_client().delete(&url).send().await {
+            tracing::debug!("Failed to delete Gemini cachedContent {name}: {e}");
+        }
+    }
+
+    /// Resolve the `cachedContent` reference for this request's static prefix,
+    /// creating or refreshing the resource as the cache manager dictates.
+    /// Returns `None` (inline fallback) when the prefix is below the threshold
+    /// or the create call fails — caching must never break a request.
+    async fn resolve_cached_content(
+        &self,
+        manager: &ContextCacheManager,
+        auth: &GeminiAuth,
+        system_instruction: &Content,
+        model: &str,
+    ) -> Option<String> {
+        let prefix_text = serde_json::to_string(system_instruction).unwrap_or_default();
+        let prefix_hash = ContextCacheManager::hash_prefix(&prefix_text);
+        let estimated_tokens = ContextCacheManager::estimate_tokens(&prefix_text);
+        let now_millis = chrono::Utc::now().timestamp_millis();
+
+        let action = manager.plan(prefix_hash, estimated_tokens, now_millis);
+        match action {
+            ContextCacheAction::Inline => None,
+            ContextCacheAction::Reuse { name } => {
+                let hits = manager.record_hit();
+                crate::observability::runtime_trace::record_event(
+                    "gemini_context_cache_hit",
+                    None,
+                    Some("gemini"),
+                    Some(model),
+                    None,
+                    Some(true),
+                    None,
+                    serde_json::json!({ "name": name, "hits": hits }),
+                );
+                Some(name)
+            }
+            ContextCacheAction::Create | ContextCacheAction::Recreate { .. } => {
+                if let ContextCacheAction::Recreate { previous } = &action {
+                    self.delete_cached_content(auth, previous).await;
+                }
+                match self
+                    .create_cached_content(auth, system_instruction, model, manager.ttl_secs)
+                    .await
+                {
+                    Ok(name) => {
+                        manager.note_created(name.clone(), prefix_hash, now_millis);
+                        Some(name)
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Gemini cachedContent create failed; sending prefix inline: {e}"
+                        );
+                        None
+                    }
+                }
+            }
+        }
+    }
+
     async fn send_generate_content(
         &self,
         contents: Vec<Content>,
@@ -1001,6 +1294,7 @@ impl GeminiProvider {
         };
 
         // For OAuth: get a valid (potentially refreshed) token and resolve project
+        let mut rotation_profile: Option<String> = None;
         let (mut oauth_token, mut project) = match auth {
             GeminiAuth::OAuthToken(state) => {
                 let token = Self::get_valid_oauth_token(state).await?;
@@ -1012,8 +1306,15 @@ impl GeminiProvider {
                     .auth_service
                     .as_ref()
                     .ok_or_else(|| anyhow::anyhow!("ManagedOAuth requires auth_service"))?;
+                rotation_profile = auth_service
+                    .next_rotation_profile("gemini", self.auth_profile_override.as_deref())
+                    .await?;
                 let token = auth_service
-                    .get_valid_gemini_access_token(self.auth_profile_override.as_deref())
+                    .get_valid_gemini_access_token(
+                        rotation_profile
+                            .as_deref()
+                            .or(self.auth_profile_override.as_deref()),
+                    )
                     .await?
                     .ok_or_else(|| {
                         anyhow::anyhow!(
@@ -1026,9 +1327,23 @@ impl GeminiProvider {
             _ => (None, None),
         };
 
+        // Static-prefix caching (public API only): swap the inline system
+        // instruction for a cachedContent reference when the manager says so.
+        let mut cached_content: Option<String> = None;
+        if auth.is_api_key() {
+            if let (Some(manager), Some(sys)) = (self.context_cache.as_ref(), &system_instruction) {
+                cached_content = self.resolve_cached_content(manager, auth, sys, model).await;
+            }
+        }
+
         let request = GenerateContentRequest {
             contents,
-            system_instruction,
+            system_instruction: if cached_content.is_some() {
+                None
+            } else {
+                system_instruction.clone()
+            },
+            cached_content: cached_content.clone(),
             generation_config: GenerationConfig {
                 temperature,
                 max_output_tokens: 8192,
@@ -1054,7 +1369,36 @@ impl GeminiProvider {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
 
-            if auth.is_oauth() && Self::should_rotate_oauth_on_error(status, &error_text) {
+            if cached_content.is_some() {
+                // The referenced cachedContent may have been evicted
+                // server-side. Drop it and retry once with the inline prefix.
+                if let Some(manager) = self.context_cache.as_ref() {
+                    if let Some(previous) = manager.invalidate() {
+                        self.delete_cached_content(auth, &previous).await;
+                    }
+                }
+                tracing::warn!(
+                    "Gemini cachedContent request failed ({status}); retrying with inline prefix"
+                );
+                let inline_request = GenerateContentRequest {
+                    contents: request.contents.clone(),
+                    system_instruction: system_instruction.clone(),
+                    cached_content: None,
+                    generation_config: request.generation_config.clone(),
+                };
+                response = self
+                    .build_generate_content_request(
+                        auth,
+                        &url,
+                        &inline_request,
+                        model,
+                        true,
+                        project.as_deref(),
+                        oauth_token.as_deref(),
+                    )
+                    .send()
+                    .await?;
+            } else if auth.is_oauth() && Self::should_rotate_oauth_on_error(status, &error_text) {
                 // For CLI OAuth: rotate credentials
                 // For ManagedOAuth: AuthService handles refresh, just retry
                 let can_retry = match auth {
@@ -1079,9 +1423,27 @@ impl GeminiProvider {
                         }
                         GeminiAuth::ManagedOAuth => {
                             let auth_service = self.auth_service.as_ref().unwrap();
+                            let quota_error = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                                || error_text.contains("RESOURCE_EXHAUSTED");
+                            if quota_error && self.auth_profile_override.is_none() {
+                                // Cool down the exhausted profile and rotate to
+                                // the next one within this request, if rotation
+                                // is enabled and another profile is available.
+                                if let Some(next) = auth_service
+                                    .handle_quota_error("gemini", rotation_profile.as_deref())
+                                    .await?
+                                {
+                                    tracing::warn!(
+                                        "Gemini quota exhausted; rotating to auth profile {next}"
+                                    );
+                                    rotation_profile = Some(next);
+                                }
+                            }
                             let token = auth_service
                                 .get_valid_gemini_access_token(
-                                    self.auth_profile_override.as_deref(),
+                                    rotation_profile
+                                        .as_deref()
+                                        .or(self.auth_profile_override.as_deref()),
                                 )
                                 .await?
                                 .ok_or_else(|| anyhow::anyhow!("Gemini auth profile not found"))?;
@@ -1105,7 +1467,7 @@ impl GeminiProvider {
                         .send()
                         .await?;
                 } else {
-                    anyhow::bail!("Gemini API error ({status}): {error_text}");
+                    return Err(gemini_api_error(status, &error_text));
                 }
             } else if auth.is_oauth()
                 && Self::should_retry_oauth_without_generation_config(status, &error_text)
@@ -1126,7 +1488,7 @@ impl GeminiProvider {
                     .send()
                     .await?;
             } else {
-                anyhow::bail!("Gemini API error ({status}): {error_text}");
+                return Err(gemini_api_error(status, &error_text));
             }
         }
 
@@ -1152,14 +1514,14 @@ impl GeminiProvider {
                     .send()
                     .await?;
             } else {
-                anyhow::bail!("Gemini API error ({status}): {error_text}");
+                return Err(gemini_api_error(status, &error_text));
             }
         }
 
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Gemini API error ({status}): {error_text}");
+            return Err(gemini_api_error(status, &error_text));
         }
 
         let result: GenerateContentResponse = response.json().await?;
@@ -1175,6 +1537,7 @@ impl GeminiProvider {
             input_tokens: u.prompt_token_count,
             output_tokens: u.candidates_token_count,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
 
         let text = result
@@ -1227,44 +1590,47 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
+        let (contents, system_instruction) = history_to_contents(messages);
+        let (text, _usage) = self
+            .send_generate_content(contents, system_instruction, model, temperature)
+            .await?;
+        Ok(text)
+    }
 
-        for msg in messages {
-            match msg.role.as_str() {
-                "system" => {
-                    system_parts.push(&msg.content);
-                }
-                "user" => {
-                    contents.push(Content {
-                        role: Some("user".to_string()),
-                        parts: build_parts(&msg.content),
-                    });
-                }
-                "assistant" => {
-                    // Gemini API uses "model" role instead of "assistant"
-                    contents.push(Content {
-                        role: Some("model".to_string()),
-                        parts: vec![Part::text(&msg.content)],
-                    });
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        // Same prompt-guided tool injection as the trait default, but routed
+        // through send_generate_content directly so the usageMetadata Gemini
+        // reports reaches cost accounting instead of being discarded.
+        let mut messages = request.messages.to_vec();
+        if let Some(tools) = request.tools {
+            if !tools.is_empty() {
+                let instructions = build_tool_instructions_text(tools);
+                if let Some(system_message) = messages.iter_mut().find(|m| m.role == "system") {
+                    if !system_message.content.is_empty() {
+                        system_message.content.push_str("\n\n");
+                    }
+                    system_message.content.push_str(&instructions);
+                } else {
+                    messages.insert(0, ChatMessage::system(instructions));
                 }
-                _ => {}
             }
         }
 
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part::text(system_parts.join("\n\n"))],
-            })
-        };
-
-        let (text, _usage) = self
+        let (contents, system_instruction) = history_to_contents(&messages);
+        let (text, usage) = self
             .send_generate_content(contents, system_instruction, model, temperature)
             .await?;
-        Ok(text)
+        Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: Vec::new(),
+            usage,
+            reasoning_content: None,
+        })
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -1316,6 +1682,20 @@ impl Provider for GeminiProvider {
         }
         Ok(())
     }
+
+    async fn clear_conversation_state(&self) {
+        let Some(manager) = self.context_cache.as_ref() else {
+            return;
+        };
+        let Some(name) = manager.invalidate() else {
+            return;
+        };
+        if let Some(auth) = self.auth.as_ref() {
+            if auth.is_api_key() {
+                self.delete_cached_content(auth, &name).await;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1342,6 +1722,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            context_cache: None,
         }
     }
 
@@ -1539,6 +1920,7 @@ mod tests {
                 parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            cached_content: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1578,6 +1960,7 @@ mod tests {
                 parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            cached_content: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1620,6 +2003,7 @@ mod tests {
                 parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
+            cached_content: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1653,6 +2037,7 @@ mod tests {
                 role: None,
                 parts: vec![Part::text("You are helpful")],
             }),
+            cached_content: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
@@ -1666,6 +2051,103 @@ mod tests {
         assert!(!json.contains("\"system_instruction\""));
         assert!(json.contains("\"temperature\":0.7"));
         assert!(json.contains("\"maxOutputTokens\":8192"));
+        assert!(!json.contains("cachedContent"));
+    }
+
+    #[test]
+    fn request_serialization_includes_cached_content_when_set() {
+        let request = GenerateContentRequest {
+            contents: vec![Content {
+                role: Some("user".to_string()),
+                parts: vec![Part::text("Hello")],
+            }],
+            system_instruction: None,
+            cached_content: Some("cachedContents/abc".to_string()),
+            generation_config: GenerationConfig {
+                temperature: 0.7,
+                max_output_tokens: 8192,
+            },
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"cachedContent\":\"cachedContents/abc\""));
+        assert!(!json.contains("cached_content"));
+    }
+
+    // ── Context cache lifecycle ──────────────────────────────────────────────
+
+    #[test]
+    fn context_cache_inlines_prefixes_below_threshold() {
+        let manager = ContextCacheManager::new(1000, 3600);
+        assert_eq!(manager.plan(42, 999, 0), ContextCacheAction::Inline);
+    }
+
+    #[test]
+    fn context_cache_creates_then_reuses() {
+        let manager = ContextCacheManager::new(100, 3600);
+        assert_eq!(manager.plan(42, 5000, 0), ContextCacheAction::Create);
+
+        manager.note_created("cachedContents/a".into(), 42, 0);
+        assert_eq!(
+            manager.plan(42, 5000, 1_000),
+            ContextCacheAction::Reuse {
+                name: "cachedContents/a".into()
+            }
+        );
+        assert_eq!(manager.record_hit(), 1);
+    }
+
+    #[test]
+    fn context_cache_recreates_near_expiry() {
+        let manager = ContextCacheManager::new(100, 3600);
+        manager.note_created("cachedContents/a".into(), 42, 0);
+
+        // Inside the refresh margin of the 3_600_000 ms expiry → recreate.
+        assert_eq!(
+            manager.plan(42, 5000, 3_600_000 - 30_000),
+            ContextCacheAction::Recreate {
+                previous: "cachedContents/a".into()
+            }
+        );
+    }
+
+    #[test]
+    fn context_cache_recreates_when_prefix_changes() {
+        let manager = ContextCacheManager::new(100, 3600);
+        manager.note_created("cachedContents/a".into(), 42, 0);
+
+        assert_eq!(
+            manager.plan(43, 5000, 1_000),
+            ContextCacheAction::Recreate {
+                previous: "cachedContents/a".into()
+            }
+        );
+    }
+
+    #[test]
+    fn context_cache_invalidate_returns_name_and_forces_create() {
+        let manager = ContextCacheManager::new(100, 3600);
+        manager.note_created("cachedContents/a".into(), 42, 0);
+
+        assert_eq!(manager.invalidate(), Some("cachedContents/a".to_string()));
+        assert_eq!(manager.invalidate(), None);
+        assert_eq!(manager.plan(42, 5000, 1_000), ContextCacheAction::Create);
+    }
+
+    #[test]
+    fn with_context_cache_respects_config_toggle() {
+        let off = crate::config::GeminiProviderConfig::default();
+        let provider =
+            test_provider(Some(GeminiAuth::ExplicitKey("key".into()))).with_context_cache(&off);
+        assert!(provider.context_cache.is_none());
+
+        let on = crate::config::GeminiProviderConfig {
+            context_caching: true,
+            ..Default::default()
+        };
+        let provider =
+            test_provider(Some(GeminiAuth::ExplicitKey("key".into()))).with_context_cache(&on);
+        assert!(provider.context_cache.is_some());
     }
 
     #[test]
@@ -2089,6 +2571,28 @@ mod tests {
         assert!(resp.usage_metadata.is_none());
     }
 
+    #[test]
+    fn history_to_contents_maps_roles_and_joins_system() {
+        let messages = vec![
+            ChatMessage::system("Be terse."),
+            ChatMessage::user("Hello"),
+            ChatMessage::assistant("Hi"),
+            ChatMessage::system("Reply in English."),
+        ];
+
+        let (contents, system_instruction) = history_to_contents(&messages);
+
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents[0].role.as_deref(), Some("user"));
+        assert_eq!(contents[1].role.as_deref(), Some("model"));
+
+        let system = system_instruction.expect("system instruction should be built");
+        match &system.parts[0] {
+            Part::Text { text } => assert_eq!(text, "Be terse.\n\nReply in English."),
+            other => panic!("expected text part, got {other:?}"),
+        }
+    }
+
     /// Validates that warmup() for ManagedOAuth requires auth_service.
     #[tokio::test]
     async fn warmup_managed_oauth_requires_auth_service() {
@@ -2099,6 +2603,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None, // Missing auth_service
             auth_profile_override: None,
+            context_cache: None,
         };
 
         let result = provider.warmup().await;
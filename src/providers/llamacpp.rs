@@ -0,0 +1,351 @@
+//! Dedicated llama.cpp server provider.
+//!
+//! llama.cpp's HTTP server speaks the OpenAI chat-completions protocol, so
+//! the wire format (including streaming) delegates to
+//! [`OpenAiCompatibleProvider`]. This wrapper layers on the server-specific
+//! pieces: `/health` probing for warmup, context-window detection from
+//! `/props`, and mapping the server's slot-busy 503 responses into errors
+//! the reliability layer treats as retryable.
+
+use crate::providers::compatible::{AuthStyle, OpenAiCompatibleProvider};
+use crate::providers::traits::{
+    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
+    Provider, ProviderCapabilities, StreamChunk, StreamEvent, StreamOptions, StreamResult,
+    ToolsPayload,
+};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+use reqwest::Client;
+
+const DEFAULT_BASE_URL: &str = "http://localhost:8080/v1";
+
+pub struct LlamaCppProvider {
+    inner: OpenAiCompatibleProvider,
+    /// Server root (base URL without the `/v1` suffix), for `/health` and `/props`.
+    server_root: String,
+    /// Context window detected from `/props`, probed at most once
+    /// (a failed probe is cached as `None` and never retried).
+    detected_window: std::sync::OnceLock<Option<usize>>,
+}
+
+/// Strip the `/v1` API suffix to get the server root, where llama.cpp
+/// exposes its non-OpenAI endpoints (`/health`, `/props`, `/slots`).
+fn server_root_url(base_url: &str) -> String {
+    let trimmed = base_url.trim_end_matches('/');
+    trimmed.strip_suffix("/v1").unwrap_or(trimmed).to_string()
+}
+
+/// Rewrite llama.cpp's slot-busy 503 into a clearly retryable error.
+/// The "503" token is preserved so the reliability layer's status-code
+/// parsing keeps classifying it as transient.
+fn map_slot_busy_error(err: anyhow::Error) -> anyhow::Error {
+    let msg = err.to_string();
+    let lower = msg.to_lowercase();
+    if msg.contains("503")
+        && (lower.contains("slot")
+            || lower.contains("busy")
+            || lower.contains("loading model")
+            || lower.contains("unavailable"))
+    {
+        return anyhow::anyhow!(
+            "llama.cpp server busy (503): all slots are processing other requests \
+             or the model is still loading; retrying shortly should succeed ({msg})"
+        );
+    }
+    err
+}
+
+/// Extract the context window (`n_ctx`) from a `/props` response.
+fn parse_props_context_window(props: &serde_json::Value) -> Option<usize> {
+    props
+        .pointer("/default_generation_settings/n_ctx")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+}
+
+impl LlamaCppProvider {
+    pub fn new(base_url: Option<&str>, api_key: Option<&str>) -> Self {
+        let base_url = base_url
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(DEFAULT_BASE_URL);
+        // llama.cpp ignores the key unless started with --api-key, but the
+        // OpenAI-compatible path always sends one, so default to a marker.
+        let key = api_key
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("llama.cpp");
+        Self {
+            inner: OpenAiCompatibleProvider::new_with_vision(
+                "llama.cpp",
+                base_url,
+                Some(key),
+                AuthStyle::Bearer,
+                true,
+            ),
+            server_root: server_root_url(base_url),
+            detected_window: std::sync::OnceLock::new(),
+        }
+    }
+
+    fn http_client(&self) -> Client {
+        crate::config::build_runtime_proxy_client_with_timeouts("provider.llamacpp", 30, 10)
+    }
+
+    /// Hit the server's `/health` endpoint. llama.cpp returns 200 once the
+    /// model is loaded and 503 with `{"status":"loading model"}` during load.
+    pub async fn probe_health(&self) -> anyhow::Result<()> {
+        let url = format!("{}/health", self.server_root);
+        let response = self.http_client().get(&url).send().await.map_err(|e| {
+            anyhow::anyhow!(
+                "llama.cpp server unreachable at {url}: {e}. Is the server \
+                 running? Start it with `llama-server -m <model.gguf>`."
+            )
+        })?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 503 {
+            anyhow::bail!(
+                "llama.cpp server is not ready (503): {}",
+                super::sanitize_api_error(&body)
+            );
+        }
+        anyhow::bail!(
+            "llama.cpp health check failed ({status}): {}",
+            super::sanitize_api_error(&body)
+        )
+    }
+
+    /// Read the model's context window from `/props`, probing the server at
+    /// most once per provider instance.
+    pub async fn detect_context_window(&self) -> Option<usize> {
+        if let Some(cached) = self.detected_window.get() {
+            return *cached;
+        }
+
+        let url = format!("{}/props", self.server_root);
+        let detected = async {
+            let props: serde_json::Value = self
+                .http_client()
+                .get(&url)
+                .send()
+                .await
+                .ok()?
+                .error_for_status()
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            parse_props_context_window(&props)
+        }
+        .await;
+
+        *self.detected_window.get_or_init(|| detected)
+    }
+}
+
+#[async_trait]
+impl Provider for LlamaCppProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn supports_streaming_tool_events(&self) -> bool {
+        self.inner.supports_streaming_tool_events()
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await
+            .map_err(map_slot_busy_error)
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.inner
+            .chat_with_history(messages, model, temperature)
+            .await
+            .map_err(map_slot_busy_error)
+    }
+
+    async fn chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        self.inner
+            .chat(request, model, temperature)
+            .await
+            .map_err(map_slot_busy_error)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        self.inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await
+            .map_err(map_slot_busy_error)
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        self.inner.stream_chat(request, model, temperature, options)
+    }
+
+    async fn detected_context_window(&self) -> Option<usize> {
+        self.detect_context_window().await
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.probe_health().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_root_strips_v1_suffix() {
+        assert_eq!(
+            server_root_url("http://localhost:8080/v1"),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            server_root_url("http://localhost:8080/v1/"),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            server_root_url("http://localhost:8080"),
+            "http://localhost:8080"
+        );
+    }
+
+    #[test]
+    fn slot_busy_503_maps_to_retryable_error() {
+        let err =
+            anyhow::anyhow!("llama.cpp API error (503 Service Unavailable): no slot available");
+        let mapped = map_slot_busy_error(err);
+        let msg = mapped.to_string();
+        assert!(msg.contains("llama.cpp server busy (503)"));
+        // The reliability layer must keep classifying this as retryable.
+        assert!(!crate::providers::reliable::is_non_retryable(&mapped));
+    }
+
+    #[test]
+    fn loading_model_503_maps_to_retryable_error() {
+        let err = anyhow::anyhow!("llama.cpp API error (503): {{\"status\":\"loading model\"}}");
+        let mapped = map_slot_busy_error(err);
+        assert!(mapped.to_string().contains("llama.cpp server busy (503)"));
+    }
+
+    #[test]
+    fn non_503_errors_pass_through_unchanged() {
+        let err = anyhow::anyhow!("llama.cpp API error (400 Bad Request): invalid schema");
+        let msg = map_slot_busy_error(err).to_string();
+        assert!(msg.contains("400 Bad Request"));
+        assert!(!msg.contains("server busy"));
+    }
+
+    #[test]
+    fn props_context_window_parses_n_ctx() {
+        let props: serde_json::Value = serde_json::from_str(
+            r#"{
+                "default_generation_settings": {"n_ctx": 8192, "temperature": 0.8},
+                "total_slots": 4,
+                "model_path": "/models/llama-3.2-3b.gguf"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(parse_props_context_window(&props), Some(8192));
+    }
+
+    #[test]
+    fn props_context_window_missing_returns_none() {
+        let props = serde_json::json!({"total_slots": 1});
+        assert_eq!(parse_props_context_window(&props), None);
+    }
+
+    #[test]
+    fn defaults_to_local_server_and_marker_key() {
+        let p = LlamaCppProvider::new(None, None);
+        assert_eq!(p.server_root, "http://localhost:8080");
+        assert!(p.supports_streaming());
+        assert!(p.supports_vision());
+    }
+
+    #[tokio::test]
+    async fn warmup_fails_with_doctor_friendly_message_when_unreachable() {
+        // Nothing listens on this port; the error should say what to start.
+        let p = LlamaCppProvider::new(Some("http://127.0.0.1:1/v1"), None);
+        let err = p.warmup().await.unwrap_err().to_string();
+        assert!(err.contains("llama.cpp server unreachable"));
+        assert!(err.contains("llama-server"));
+    }
+}
@@ -22,18 +22,24 @@ pub mod bedrock;
 pub mod claude_code;
 pub mod compatible;
 pub mod copilot;
+pub mod error;
 pub mod gemini;
 pub mod gemini_cli;
 pub mod kilocli;
+pub mod llamacpp;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
+pub mod quota;
 pub mod reliable;
+pub mod response_cache;
 pub mod router;
 pub mod telnyx;
 pub mod traits;
 
+#[allow(unused_imports)]
+pub use error::{ProviderError, ProviderErrorKind};
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
@@ -709,6 +715,19 @@ pub struct ProviderRuntimeOptions {
     /// Maximum output tokens for LLM provider API requests.
     /// `None` uses the provider's built-in default.
     pub provider_max_tokens: Option<u32>,
+    /// Mark the stable prompt prefix (system prompt, tool definitions) with
+    /// Anthropic `cache_control` blocks. From `[providers.anthropic]`.
+    pub anthropic_prompt_caching: bool,
+    /// Gemini `cachedContent` settings for long static prefixes.
+    /// From `[providers.gemini]`.
+    pub gemini_context_cache: crate::config::GeminiProviderConfig,
+    /// OpenRouter upstream routing preferences injected into request bodies.
+    /// From `[providers.openrouter.routing]`.
+    pub openrouter_routing: crate::config::OpenRouterRoutingConfig,
+    /// Custom endpoint options (extra headers, auth scheme, TLS trust) for
+    /// `custom:<URL>` and `anthropic-custom:<URL>` providers.
+    /// From `[providers.custom]`.
+    pub custom_provider: crate::config::CustomProviderConfig,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -724,6 +743,10 @@ impl Default for ProviderRuntimeOptions {
             extra_headers: std::collections::HashMap::new(),
             api_path: None,
             provider_max_tokens: None,
+            anthropic_prompt_caching: true,
+            gemini_context_cache: crate::config::GeminiProviderConfig::default(),
+            openrouter_routing: crate::config::OpenRouterRoutingConfig::default(),
+            custom_provider: crate::config::CustomProviderConfig::default(),
         }
     }
 }
@@ -742,6 +765,10 @@ pub fn provider_runtime_options_from_config(
         extra_headers: config.extra_headers.clone(),
         api_path: config.api_path.clone(),
         provider_max_tokens: config.provider_max_tokens,
+        anthropic_prompt_caching: config.providers.anthropic.prompt_caching,
+        gemini_context_cache: config.providers.gemini.clone(),
+        openrouter_routing: config.providers.openrouter.routing.clone(),
+        custom_provider: config.providers.custom.clone(),
     }
 }
 
@@ -819,15 +846,30 @@ pub fn sanitize_api_error(input: &str) -> String {
     format!("{}...", &scrubbed[..end])
 }
 
-/// Build a sanitized provider error from a failed HTTP response.
+/// Build a classified, sanitized provider error from a failed HTTP response.
+///
+/// The returned `anyhow::Error` wraps a [`ProviderError`] so the reliability
+/// layer and channel runtime can branch on its kind; `Display` output is
+/// unchanged from the historical flat-string format.
 pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::Error {
     let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs);
     let body = response
         .text()
         .await
         .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
     let sanitized = sanitize_api_error(&body);
-    anyhow::anyhow!("{provider} API error ({status}): {sanitized}")
+    anyhow::Error::new(ProviderError::from_response_parts(
+        provider,
+        status,
+        retry_after,
+        &sanitized,
+    ))
 }
 
 /// Resolve API key for a provider from config and environment variables.
@@ -1047,6 +1089,25 @@ fn parse_custom_provider_url(
     }
 }
 
+/// Map `[providers.custom] auth_scheme` to a concrete credential header style.
+/// Accepts `"bearer"` (default when unset), `"basic"`, and `"header:<name>"`.
+fn custom_auth_style(scheme: Option<&str>) -> anyhow::Result<AuthStyle> {
+    let Some(scheme) = scheme.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(AuthStyle::Bearer);
+    };
+    match scheme.to_ascii_lowercase().as_str() {
+        "bearer" => Ok(AuthStyle::Bearer),
+        "basic" => Ok(AuthStyle::Basic),
+        _ => match scheme.strip_prefix("header:") {
+            Some(name) if !name.trim().is_empty() => Ok(AuthStyle::Custom(name.trim().to_string())),
+            _ => anyhow::bail!(
+                "providers.custom.auth_scheme must be \"bearer\", \"basic\", or \
+                 \"header:<name>\" (got '{scheme}')"
+            ),
+        },
+    }
+}
+
 /// Factory: create the right provider from config (without custom URL)
 pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<dyn Provider>> {
     create_provider_with_options(name, api_key, &ProviderRuntimeOptions::default())
@@ -1100,7 +1161,7 @@ fn create_provider_with_url_and_options(
                 p = p.with_reasoning_effort(Some(effort.clone()));
             }
             if !extra_headers.is_empty() {
-                p = p.with_extra_headers(extra_headers.clone());
+                p = p.with_additional_headers(extra_headers.clone());
             }
             if api_path.is_some() {
                 p = p.with_api_path(api_path.clone());
@@ -1159,9 +1220,12 @@ fn create_provider_with_url_and_options(
         "openrouter" => Ok(Box::new(openrouter::OpenRouterProvider::new(
             key,
             options.provider_timeout_secs,
-        ).with_max_tokens(options.provider_max_tokens))),
+        )
+        .with_max_tokens(options.provider_max_tokens)
+        .with_routing(&options.openrouter_routing))),
         "anthropic" => {
-            let mut p = anthropic::AnthropicProvider::new(key);
+            let mut p = anthropic::AnthropicProvider::new(key)
+                .with_prompt_caching(options.anthropic_prompt_caching);
             if let Some(mt) = options.provider_max_tokens {
                 p = p.with_max_tokens(mt);
             }
@@ -1200,11 +1264,14 @@ fn create_provider_with_url_and_options(
                     )
                 });
             let auth_service = AuthService::new(&state_dir, options.secrets_encrypt);
-            Ok(Box::new(gemini::GeminiProvider::new_with_auth(
-                key,
-                auth_service,
-                options.auth_profile_override.clone(),
-            )))
+            Ok(Box::new(
+                gemini::GeminiProvider::new_with_auth(
+                    key,
+                    auth_service,
+                    options.auth_profile_override.clone(),
+                )
+                .with_context_cache(&options.gemini_context_cache),
+            ))
         }
         "telnyx" => Ok(Box::new(telnyx::TelnyxProvider::new(key))),
 
@@ -1279,12 +1346,30 @@ fn create_provider_with_url_and_options(
             let deployment = std::env::var("AZURE_OPENAI_DEPLOYMENT")
                 .unwrap_or_else(|_| "gpt-4o".to_string());
             let api_version = std::env::var("AZURE_OPENAI_API_VERSION").ok();
-            Ok(Box::new(azure_openai::AzureOpenAiProvider::new(
-                key,
-                &resource,
-                &deployment,
-                api_version.as_deref(),
-            )))
+            // Prefer an explicit resource key; fall back to a Microsoft Entra ID
+            // (AAD) access token for tenants that disallow key-based auth.
+            let aad_token = if key.is_none() {
+                std::env::var("AZURE_OPENAI_AAD_TOKEN")
+                    .ok()
+                    .map(|token| token.trim().to_string())
+                    .filter(|token| !token.is_empty())
+            } else {
+                None
+            };
+            Ok(Box::new(match aad_token {
+                Some(token) => azure_openai::AzureOpenAiProvider::new_with_aad_token(
+                    &token,
+                    &resource,
+                    &deployment,
+                    api_version.as_deref(),
+                ),
+                None => azure_openai::AzureOpenAiProvider::new(
+                    key,
+                    &resource,
+                    &deployment,
+                    api_version.as_deref(),
+                ),
+            }))
         }
         "bedrock" | "aws-bedrock" => {
             let mut p = if let Some(api_key) = key {
@@ -1389,23 +1474,7 @@ fn create_provider_with_url_and_options(
                 AuthStyle::Bearer,
             )))
         }
-        "llamacpp" | "llama.cpp" => {
-            let base_url = api_url
-                .map(str::trim)
-                .filter(|value| !value.is_empty())
-                .unwrap_or("http://localhost:8080/v1");
-            let llama_cpp_key = key
-                .map(str::trim)
-                .filter(|value| !value.is_empty())
-                .unwrap_or("llama.cpp");
-            Ok(compat(OpenAiCompatibleProvider::new_with_vision(
-                "llama.cpp",
-                base_url,
-                Some(llama_cpp_key),
-                AuthStyle::Bearer,
-                true,
-            )))
-        }
+        "llamacpp" | "llama.cpp" => Ok(Box::new(llamacpp::LlamaCppProvider::new(api_url, key))),
         "sglang" => {
             let base_url = api_url
                 .map(str::trim)
@@ -1570,13 +1639,28 @@ fn create_provider_with_url_and_options(
                 "Custom provider",
                 "custom:https://your-api.com",
             )?;
-            Ok(compat(OpenAiCompatibleProvider::new_with_vision(
+            let custom = &options.custom_provider;
+            let mut provider = OpenAiCompatibleProvider::new_with_vision(
                 "Custom",
                 &base_url,
                 key,
-                AuthStyle::Bearer,
+                custom_auth_style(custom.auth_scheme.as_deref())?,
                 true,
-            )))
+            )
+            .with_tls_options(
+                custom.ca_bundle.as_deref().map(PathBuf::from),
+                custom.danger_accept_invalid_certs,
+            );
+            let custom_headers = custom.resolved_headers()?;
+            if !custom_headers.is_empty() {
+                // Values may carry credentials — log names only.
+                tracing::debug!(
+                    headers = ?custom.redacted_headers(),
+                    "Applying [providers.custom] headers to custom provider"
+                );
+                provider = provider.with_extra_headers(custom_headers);
+            }
+            Ok(compat(provider))
         }
 
         // ── Anthropic-compatible custom endpoints ───────────
@@ -1587,10 +1671,25 @@ fn create_provider_with_url_and_options(
                 "Anthropic-custom provider",
                 "anthropic-custom:https://your-api.com",
             )?;
-            Ok(Box::new(anthropic::AnthropicProvider::with_base_url(
-                key,
-                Some(&base_url),
-            )))
+            let custom = &options.custom_provider;
+            let mut provider = anthropic::AnthropicProvider::with_base_url(key, Some(&base_url))
+                .with_tls_options(
+                    custom.ca_bundle.as_deref().map(PathBuf::from),
+                    custom.danger_accept_invalid_certs,
+                );
+            if let Some(scheme) = custom.auth_scheme.as_deref() {
+                provider = provider.with_auth_style(custom_auth_style(Some(scheme))?);
+            }
+            let custom_headers = custom.resolved_headers()?;
+            if !custom_headers.is_empty() {
+                // Values may carry credentials — log names only.
+                tracing::debug!(
+                    headers = ?custom.redacted_headers(),
+                    "Applying [providers.custom] headers to anthropic-custom provider"
+                );
+                provider = provider.with_extra_headers(custom_headers);
+            }
+            Ok(Box::new(provider))
         }
 
         _ => anyhow::bail!(
@@ -1699,7 +1798,8 @@ pub fn create_resilient_provider_with_options(
         reliability.provider_backoff_ms,
     )
     .with_api_keys(reliability.api_keys.clone())
-    .with_model_fallbacks(reliability.model_fallbacks.clone());
+    .with_model_fallbacks(reliability.model_fallbacks.clone())
+    .with_response_cache(&reliability.cache);
 
     Ok(Box::new(reliable))
 }
@@ -1783,19 +1883,54 @@ pub fn create_routed_provider_with_options(
         }
     }
 
-    // Build route table
-    let routes: Vec<(String, router::Route)> = model_routes
-        .iter()
-        .map(|r| {
-            (
-                r.hint.clone(),
-                router::Route {
-                    provider_name: r.provider.clone(),
-                    model: r.model.clone(),
-                },
-            )
-        })
-        .collect();
+    // Build route table. Routes with an OpenRouter routing override get a
+    // dedicated provider instance keyed by hint, so the override only applies
+    // to that route's traffic.
+    let mut routes: Vec<(String, router::Route)> = Vec::new();
+    for r in model_routes {
+        let mut provider_key = r.provider.clone();
+        if let Some(ref routing) = r.openrouter_routing {
+            let route_key = r.api_key.as_ref().and_then(|raw_key| {
+                let trimmed_key = raw_key.trim();
+                (!trimmed_key.is_empty()).then_some(trimmed_key)
+            });
+            let key = route_key.or(api_key);
+            let url = if r.provider == primary_name {
+                api_url
+            } else {
+                None
+            };
+            let mut route_options = options.clone();
+            route_options.openrouter_routing = routing.clone();
+            match create_resilient_provider_with_options(
+                &r.provider,
+                key,
+                url,
+                reliability,
+                &route_options,
+            ) {
+                Ok(provider) => {
+                    let dedicated_key = format!("{}@{}", r.provider, r.hint);
+                    providers.push((dedicated_key.clone(), provider));
+                    provider_key = dedicated_key;
+                }
+                Err(_error) => {
+                    tracing::warn!(
+                        provider = r.provider.as_str(),
+                        hint = r.hint.as_str(),
+                        "Ignoring per-route routing override whose provider failed to initialize"
+                    );
+                }
+            }
+        }
+        routes.push((
+            r.hint.clone(),
+            router::Route {
+                provider_name: provider_key,
+                model: r.model.clone(),
+            },
+        ));
+    }
 
     Ok(Box::new(router::RouterProvider::new(
         providers,
@@ -2983,6 +3118,70 @@ mod tests {
         assert!(p.is_ok());
     }
 
+    #[test]
+    fn custom_auth_style_maps_configured_schemes() {
+        assert!(matches!(custom_auth_style(None), Ok(AuthStyle::Bearer)));
+        assert!(matches!(
+            custom_auth_style(Some("bearer")),
+            Ok(AuthStyle::Bearer)
+        ));
+        assert!(matches!(
+            custom_auth_style(Some("Basic")),
+            Ok(AuthStyle::Basic)
+        ));
+        match custom_auth_style(Some("header:X-Org-Token")) {
+            Ok(AuthStyle::Custom(name)) => assert_eq!(name, "X-Org-Token"),
+            other => panic!("Expected custom header style, got: {other:?}"),
+        }
+        assert!(custom_auth_style(Some("digest")).is_err());
+        assert!(custom_auth_style(Some("header:")).is_err());
+    }
+
+    #[test]
+    fn factory_custom_applies_configured_auth_and_headers() {
+        let mut options = ProviderRuntimeOptions::default();
+        options.custom_provider.auth_scheme = Some("header:X-Gateway-Key".to_string());
+        options
+            .custom_provider
+            .headers
+            .insert("X-Org-Token".to_string(), "org-secret".to_string());
+
+        let p = create_provider_with_url_and_options(
+            "custom:https://gateway.internal",
+            Some("key"),
+            None,
+            &options,
+        );
+        assert!(p.is_ok());
+
+        let p = create_provider_with_url_and_options(
+            "anthropic-custom:https://gateway.internal",
+            Some("key"),
+            None,
+            &options,
+        );
+        assert!(p.is_ok());
+    }
+
+    #[test]
+    fn factory_custom_invalid_auth_scheme_errors() {
+        let mut options = ProviderRuntimeOptions::default();
+        options.custom_provider.auth_scheme = Some("digest".to_string());
+
+        match create_provider_with_url_and_options(
+            "custom:https://gateway.internal",
+            Some("key"),
+            None,
+            &options,
+        ) {
+            Err(e) => assert!(
+                e.to_string().contains("auth_scheme"),
+                "Expected auth_scheme error, got: {e}"
+            ),
+            Ok(_) => panic!("Expected error for unsupported auth scheme"),
+        }
+    }
+
     #[test]
     fn factory_custom_empty_url_errors() {
         match create_provider("custom:", None) {
@@ -3109,6 +3308,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         let provider = create_resilient_provider(
@@ -3149,6 +3350,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -3172,6 +3375,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         let provider =
@@ -3199,6 +3404,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -3232,6 +3439,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -3531,6 +3740,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -3561,6 +3772,8 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            cache: crate::config::ResponseCacheConfig::default(),
+            quota_history_days: 30,
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
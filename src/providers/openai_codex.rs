@@ -1609,6 +1609,10 @@ data: [DONE]
             extra_headers: std::collections::HashMap::new(),
             api_path: None,
             provider_max_tokens: None,
+            anthropic_prompt_caching: true,
+            gemini_context_cache: crate::config::GeminiProviderConfig::default(),
+            openrouter_routing: crate::config::OpenRouterRoutingConfig::default(),
+            custom_provider: crate::config::CustomProviderConfig::default(),
         };
         let provider =
             OpenAiCodexProvider::new(&options, None).expect("provider should initialize");
@@ -13,6 +13,7 @@ pub struct OpenRouterProvider {
     credential: Option<String>,
     timeout_secs: u64,
     max_tokens: Option<u32>,
+    routing: Option<ProviderPreferences>,
 }
 
 const DEFAULT_OPENROUTER_TIMEOUT_SECS: u64 = 120;
@@ -25,6 +26,27 @@ struct ChatRequest {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
+}
+
+/// OpenRouter's request-level `provider` object — pins or excludes upstreams
+/// per request (<https://openrouter.ai/docs/features/provider-routing>).
+/// Built from `[providers.openrouter.routing]`.
+#[derive(Debug, Clone, Serialize)]
+struct ProviderPreferences {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    order: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    only: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ignore: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_fallbacks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    require_parameters: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_collection: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -78,6 +100,8 @@ struct NativeChatRequest {
     tool_choice: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
 }
 
 #[derive(Debug, Serialize)]
@@ -163,6 +187,7 @@ impl OpenRouterProvider {
                 .filter(|secs| *secs > 0)
                 .unwrap_or(DEFAULT_OPENROUTER_TIMEOUT_SECS),
             max_tokens: None,
+            routing: None,
         }
     }
 
@@ -178,6 +203,24 @@ impl OpenRouterProvider {
         self
     }
 
+    /// Set upstream routing preferences (`[providers.openrouter.routing]`).
+    /// Unset preferences leave the request body untouched.
+    pub fn with_routing(mut self, routing: &crate::config::OpenRouterRoutingConfig) -> Self {
+        self.routing = if routing.is_unset() {
+            None
+        } else {
+            Some(ProviderPreferences {
+                order: routing.order.clone(),
+                only: routing.only.clone(),
+                ignore: routing.ignore.clone(),
+                allow_fallbacks: routing.allow_fallbacks,
+                require_parameters: routing.require_parameters,
+                data_collection: routing.data_collection.clone(),
+            })
+        };
+        self
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         let items = tools?;
         if items.is_empty() {
@@ -419,6 +462,7 @@ impl Provider for OpenRouterProvider {
             messages,
             temperature,
             max_tokens: self.max_tokens,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -469,6 +513,7 @@ impl Provider for OpenRouterProvider {
             messages: api_messages,
             temperature,
             max_tokens: self.max_tokens,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -517,6 +562,7 @@ impl Provider for OpenRouterProvider {
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
             max_tokens: self.max_tokens,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -540,6 +586,7 @@ impl Provider for OpenRouterProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let message = native_response
             .choices
@@ -612,6 +659,7 @@ impl Provider for OpenRouterProvider {
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
             max_tokens: self.max_tokens,
+            provider: self.routing.clone(),
         };
 
         let response = self
@@ -635,6 +683,7 @@ impl Provider for OpenRouterProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
             cached_input_tokens: None,
+            cache_write_input_tokens: None,
         });
         let message = native_response
             .choices
@@ -754,6 +803,7 @@ mod tests {
             ],
             temperature: 0.5,
             max_tokens: None,
+            provider: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -788,6 +838,7 @@ mod tests {
                 .collect(),
             temperature: 0.0,
             max_tokens: None,
+            provider: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -1222,6 +1273,106 @@ mod tests {
         assert_eq!(result[1].function.name, "another-valid");
     }
 
+    // ═══════════════════════════════════════════════════════════════════════
+    // provider routing preference tests
+    // ═══════════════════════════════════════════════════════════════════════
+
+    fn routing_config(
+        order: &[&str],
+        only: &[&str],
+        ignore: &[&str],
+    ) -> crate::config::OpenRouterRoutingConfig {
+        crate::config::OpenRouterRoutingConfig {
+            order: order.iter().map(ToString::to_string).collect(),
+            only: only.iter().map(ToString::to_string).collect(),
+            ignore: ignore.iter().map(ToString::to_string).collect(),
+            allow_fallbacks: None,
+            require_parameters: None,
+            data_collection: None,
+        }
+    }
+
+    #[test]
+    fn with_routing_unset_config_injects_nothing() {
+        let provider = OpenRouterProvider::new(Some("key"), None)
+            .with_routing(&crate::config::OpenRouterRoutingConfig::default());
+        assert!(provider.routing.is_none());
+
+        let request = NativeChatRequest {
+            model: "openai/gpt-4o".into(),
+            messages: vec![],
+            temperature: 0.7,
+            tools: None,
+            tool_choice: None,
+            max_tokens: None,
+            provider: provider.routing.clone(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains("\"provider\""));
+    }
+
+    #[test]
+    fn native_request_serializes_order_and_allow_fallbacks() {
+        let mut config = routing_config(&["anthropic", "openai"], &[], &[]);
+        config.allow_fallbacks = Some(false);
+        let provider = OpenRouterProvider::new(Some("key"), None).with_routing(&config);
+
+        let request = NativeChatRequest {
+            model: "anthropic/claude-sonnet-4".into(),
+            messages: vec![],
+            temperature: 0.7,
+            tools: None,
+            tool_choice: None,
+            max_tokens: None,
+            provider: provider.routing.clone(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains(
+            "\"provider\":{\"order\":[\"anthropic\",\"openai\"],\"allow_fallbacks\":false}"
+        ));
+    }
+
+    #[test]
+    fn chat_request_serializes_ignore_and_data_collection() {
+        let mut config = routing_config(&[], &[], &["deepinfra"]);
+        config.data_collection = Some("deny".into());
+        let provider = OpenRouterProvider::new(Some("key"), None).with_routing(&config);
+
+        let request = ChatRequest {
+            model: "openai/gpt-4o".into(),
+            messages: vec![],
+            temperature: 0.0,
+            max_tokens: None,
+            provider: provider.routing.clone(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"ignore\":[\"deepinfra\"]"));
+        assert!(json.contains("\"data_collection\":\"deny\""));
+        assert!(!json.contains("\"order\""));
+        assert!(!json.contains("\"allow_fallbacks\""));
+    }
+
+    #[test]
+    fn native_request_serializes_only_and_require_parameters() {
+        let mut config = routing_config(&[], &["anthropic"], &[]);
+        config.require_parameters = Some(true);
+        let provider = OpenRouterProvider::new(Some("key"), None).with_routing(&config);
+
+        let request = NativeChatRequest {
+            model: "anthropic/claude-sonnet-4".into(),
+            messages: vec![],
+            temperature: 0.2,
+            tools: None,
+            tool_choice: None,
+            max_tokens: Some(2048),
+            provider: provider.routing.clone(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"only\":[\"anthropic\"]"));
+        assert!(json.contains("\"require_parameters\":true"));
+        assert!(json.contains("\"max_tokens\":2048"));
+    }
+
     #[test]
     fn convert_tools_returns_none_when_all_invalid() {
         use crate::tools::ToolSpec;
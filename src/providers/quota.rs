@@ -0,0 +1,334 @@
+//! Per-provider daily usage history for `zeroclaw providers-quota`.
+//!
+//! The reliability layer reports events (requests served, tokens, rate-limit
+//! hits, circuit-breaker trips) as they happen; this module aggregates them
+//! per provider per day and persists the history as JSON under the workspace
+//! (`state/provider_quota.json`). Writes are fire-and-forget so recording
+//! never blocks a request path. History older than the configured retention
+//! is pruned when the tracker is initialised.
+
+use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Usage counters for one provider on one day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyProviderUsage {
+    /// Requests successfully served by this provider.
+    #[serde(default)]
+    pub requests: u64,
+    /// Input tokens, when the provider reported usage.
+    #[serde(default)]
+    pub input_tokens: u64,
+    /// Output tokens, when the provider reported usage.
+    #[serde(default)]
+    pub output_tokens: u64,
+    /// Attempts that failed with a rate-limit (429) error.
+    #[serde(default)]
+    pub rate_limit_hits: u64,
+    /// Times the circuit breaker opened for this provider.
+    #[serde(default)]
+    pub breaker_trips: u64,
+}
+
+/// On-disk structure: day → provider → counters. `BTreeMap` keeps both the
+/// JSON file and CLI output in chronological order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuotaHistory {
+    #[serde(default)]
+    pub days: BTreeMap<NaiveDate, BTreeMap<String, DailyProviderUsage>>,
+}
+
+/// Drop days older than `retention_days` before `today`.
+fn prune_history(history: &mut QuotaHistory, today: NaiveDate, retention_days: u32) {
+    let cutoff = today - ChronoDuration::days(i64::from(retention_days));
+    history.days.retain(|day, _| *day > cutoff);
+}
+
+/// Where the quota history lives under the workspace.
+pub fn storage_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("provider_quota.json")
+}
+
+/// Load the quota history from disk, tolerating a missing or corrupt file.
+pub fn load_history(workspace_dir: &Path) -> QuotaHistory {
+    let path = storage_path(workspace_dir);
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return QuotaHistory::default();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        tracing::warn!(
+            "Ignoring malformed quota history at {}: {e}",
+            path.display()
+        );
+        QuotaHistory::default()
+    })
+}
+
+/// Tracks per-provider daily usage and persists it asynchronously.
+pub struct QuotaTracker {
+    path: PathBuf,
+    history: Mutex<QuotaHistory>,
+}
+
+impl QuotaTracker {
+    /// Open (or create) the tracker, pruning history past `retention_days`.
+    pub fn new(workspace_dir: &Path, retention_days: u32) -> Self {
+        let mut history = load_history(workspace_dir);
+        prune_history(&mut history, Utc::now().date_naive(), retention_days);
+        Self {
+            path: storage_path(workspace_dir),
+            history: Mutex::new(history),
+        }
+    }
+
+    fn update(&self, provider: &str, apply: impl FnOnce(&mut DailyProviderUsage)) {
+        let snapshot = {
+            let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = history
+                .days
+                .entry(Utc::now().date_naive())
+                .or_default()
+                .entry(provider.to_string())
+                .or_default();
+            apply(entry);
+            history.clone()
+        };
+        self.persist_async(snapshot);
+    }
+
+    /// Write the snapshot without blocking the caller. Runs on the tokio
+    /// runtime when one is available, otherwise on a detached thread; either
+    /// way a failed write only logs.
+    fn persist_async(&self, snapshot: QuotaHistory) {
+        let path = self.path.clone();
+        let write = move || {
+            if let Err(e) = write_history(&path, &snapshot) {
+                tracing::warn!("Failed to persist quota history to {}: {e}", path.display());
+            }
+        };
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn_blocking(write);
+        } else {
+            std::thread::spawn(write);
+        }
+    }
+
+    /// Record a successfully served request.
+    pub fn record_request(&self, provider: &str) {
+        self.update(provider, |usage| usage.requests += 1);
+    }
+
+    /// Record provider-reported token usage.
+    pub fn record_tokens(&self, provider: &str, input_tokens: u64, output_tokens: u64) {
+        if input_tokens == 0 && output_tokens == 0 {
+            return;
+        }
+        self.update(provider, |usage| {
+            usage.input_tokens += input_tokens;
+            usage.output_tokens += output_tokens;
+        });
+    }
+
+    /// Record a rate-limited (429) attempt.
+    pub fn record_rate_limit(&self, provider: &str) {
+        self.update(provider, |usage| usage.rate_limit_hits += 1);
+    }
+
+    /// Record a circuit-breaker trip.
+    pub fn record_breaker_trip(&self, provider: &str) {
+        self.update(provider, |usage| usage.breaker_trips += 1);
+    }
+
+    /// The `days` most recent days on record, oldest first.
+    pub fn last_days(&self, days: u32) -> Vec<(NaiveDate, BTreeMap<String, DailyProviderUsage>)> {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        last_days_of(&history, days)
+    }
+}
+
+/// The `days` most recent days in a history, oldest first.
+pub fn last_days_of(
+    history: &QuotaHistory,
+    days: u32,
+) -> Vec<(NaiveDate, BTreeMap<String, DailyProviderUsage>)> {
+    let skip = history.days.len().saturating_sub(days as usize);
+    history
+        .days
+        .iter()
+        .skip(skip)
+        .map(|(day, providers)| (*day, providers.clone()))
+        .collect()
+}
+
+/// Atomic write: temp file in the same directory, then rename.
+fn write_history(path: &Path, history: &QuotaHistory) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(history)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// ── Process-global singleton ────────────────────────────────────────
+// The reliability layer records events through free functions so it does not
+// need workspace plumbing; recording is a no-op until a runtime entry point
+// (daemon, gateway, channels) initialises the tracker.
+
+static GLOBAL_QUOTA_TRACKER: OnceLock<Arc<QuotaTracker>> = OnceLock::new();
+
+/// Initialise the process-global tracker. Later calls return the existing one.
+pub fn get_or_init_global(workspace_dir: &Path, retention_days: u32) -> Arc<QuotaTracker> {
+    GLOBAL_QUOTA_TRACKER
+        .get_or_init(|| Arc::new(QuotaTracker::new(workspace_dir, retention_days)))
+        .clone()
+}
+
+fn global() -> Option<Arc<QuotaTracker>> {
+    GLOBAL_QUOTA_TRACKER.get().cloned()
+}
+
+/// Record a served request against the global tracker, if initialised.
+pub fn record_request(provider: &str) {
+    if let Some(tracker) = global() {
+        tracker.record_request(provider);
+    }
+}
+
+/// Record token usage against the global tracker, if initialised.
+pub fn record_tokens(provider: &str, input_tokens: u64, output_tokens: u64) {
+    if let Some(tracker) = global() {
+        tracker.record_tokens(provider, input_tokens, output_tokens);
+    }
+}
+
+/// Record a rate-limit hit against the global tracker, if initialised.
+pub fn record_rate_limit(provider: &str) {
+    if let Some(tracker) = global() {
+        tracker.record_rate_limit(provider);
+    }
+}
+
+/// Record a breaker trip against the global tracker, if initialised.
+pub fn record_breaker_trip(provider: &str) {
+    if let Some(tracker) = global() {
+        tracker.record_breaker_trip(provider);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn history_with_days(days: &[&str]) -> QuotaHistory {
+        let mut history = QuotaHistory::default();
+        for d in days {
+            history
+                .days
+                .entry(day(d))
+                .or_default()
+                .insert("openrouter".into(), DailyProviderUsage::default());
+        }
+        history
+    }
+
+    #[test]
+    fn counters_accumulate_under_todays_date() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = QuotaTracker::new(tmp.path(), 30);
+
+        tracker.record_request("anthropic");
+        tracker.record_request("anthropic");
+        tracker.record_tokens("anthropic", 100, 50);
+        tracker.record_rate_limit("openai");
+        tracker.record_breaker_trip("openai");
+
+        let days = tracker.last_days(1);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].0, Utc::now().date_naive());
+
+        let anthropic = &days[0].1["anthropic"];
+        assert_eq!(anthropic.requests, 2);
+        assert_eq!(anthropic.input_tokens, 100);
+        assert_eq!(anthropic.output_tokens, 50);
+
+        let openai = &days[0].1["openai"];
+        assert_eq!(openai.rate_limit_hits, 1);
+        assert_eq!(openai.breaker_trips, 1);
+    }
+
+    #[test]
+    fn day_boundary_keeps_existing_days_separate() {
+        let tmp = TempDir::new().unwrap();
+        let yesterday = Utc::now().date_naive() - ChronoDuration::days(1);
+        let mut history = QuotaHistory::default();
+        history.days.entry(yesterday).or_default().insert(
+            "openrouter".into(),
+            DailyProviderUsage {
+                requests: 5,
+                ..Default::default()
+            },
+        );
+        write_history(&storage_path(tmp.path()), &history).unwrap();
+
+        let tracker = QuotaTracker::new(tmp.path(), 30);
+        tracker.record_request("openrouter");
+
+        let days = tracker.last_days(10);
+        assert_eq!(days.len(), 2, "today must roll over into its own bucket");
+        assert_eq!(days[0].0, yesterday);
+        assert_eq!(days[0].1["openrouter"].requests, 5);
+        assert_eq!(days[1].0, Utc::now().date_naive());
+        assert_eq!(days[1].1["openrouter"].requests, 1);
+    }
+
+    #[test]
+    fn startup_prunes_days_past_retention() {
+        let tmp = TempDir::new().unwrap();
+        let today = Utc::now().date_naive();
+        let recent = today - ChronoDuration::days(2);
+        let stale = today - ChronoDuration::days(40);
+
+        let mut history = QuotaHistory::default();
+        for d in [recent, stale] {
+            history
+                .days
+                .entry(d)
+                .or_default()
+                .insert("openrouter".into(), DailyProviderUsage::default());
+        }
+        write_history(&storage_path(tmp.path()), &history).unwrap();
+
+        let tracker = QuotaTracker::new(tmp.path(), 30);
+        let days = tracker.last_days(100);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].0, recent);
+    }
+
+    #[test]
+    fn last_days_returns_most_recent_window_oldest_first() {
+        let history = history_with_days(&["2026-08-25", "2026-08-26", "2026-08-27"]);
+        let window = last_days_of(&history, 2);
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].0, day("2026-08-26"));
+        assert_eq!(window[1].0, day("2026-08-27"));
+    }
+
+    #[test]
+    fn corrupt_history_file_is_ignored() {
+        let tmp = TempDir::new().unwrap();
+        let path = storage_path(tmp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_history(tmp.path()).days.is_empty());
+    }
+}
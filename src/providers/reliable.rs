@@ -1,3 +1,4 @@
+use super::error::{ProviderError, ProviderErrorKind};
 use super::traits::{
     ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamEvent, StreamOptions, StreamResult,
 };
@@ -6,7 +7,7 @@ use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
@@ -89,6 +90,23 @@ fn record_provider_fallback(
 
 /// Check if an error is non-retryable (client errors that won't resolve with retries).
 pub fn is_non_retryable(err: &anyhow::Error) -> bool {
+    // Typed path: providers that classify at construction need no heuristics.
+    // `Other` falls through to the string-based checks below.
+    if let Some(provider_error) = err.downcast_ref::<ProviderError>() {
+        match provider_error.kind() {
+            ProviderErrorKind::AuthFailed => return true,
+            // Context overflow recovers via history truncation; rate limits,
+            // quota (handled by `is_non_retryable_rate_limit`), overload and
+            // network errors are all transient for this check.
+            ProviderErrorKind::ContextTooLarge
+            | ProviderErrorKind::RateLimited { .. }
+            | ProviderErrorKind::QuotaExhausted
+            | ProviderErrorKind::Overloaded
+            | ProviderErrorKind::Network => return false,
+            ProviderErrorKind::Other => {}
+        }
+    }
+
     // Context window errors are NOT non-retryable — they can be recovered
     // by truncating conversation history, so let the retry loop handle them.
     if is_context_window_exceeded(err) {
@@ -170,6 +188,10 @@ pub fn is_tool_schema_error(err: &anyhow::Error) -> bool {
 }
 
 pub(crate) fn is_context_window_exceeded(err: &anyhow::Error) -> bool {
+    if let Some(provider_error) = err.downcast_ref::<ProviderError>() {
+        return provider_error.kind() == ProviderErrorKind::ContextTooLarge;
+    }
+
     let lower = err.to_string().to_lowercase();
     let hints = [
         "exceeds the context window",
@@ -189,6 +211,12 @@ pub(crate) fn is_context_window_exceeded(err: &anyhow::Error) -> bool {
 
 /// Check if an error is a rate-limit (429) error.
 fn is_rate_limited(err: &anyhow::Error) -> bool {
+    if let Some(provider_error) = err.downcast_ref::<ProviderError>() {
+        return matches!(
+            provider_error.kind(),
+            ProviderErrorKind::RateLimited { .. } | ProviderErrorKind::QuotaExhausted
+        );
+    }
     if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
         if let Some(status) = reqwest_err.status() {
             return status.as_u16() == 429;
@@ -206,6 +234,10 @@ fn is_rate_limited(err: &anyhow::Error) -> bool {
 /// - insufficient balance / package not active
 /// - known provider business codes (e.g. Z.AI: 1311, 1113)
 fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
+    if let Some(provider_error) = err.downcast_ref::<ProviderError>() {
+        return provider_error.kind() == ProviderErrorKind::QuotaExhausted;
+    }
+
     if !is_rate_limited(err) {
         return false;
     }
@@ -248,6 +280,15 @@ fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
 /// Try to extract a Retry-After value (in milliseconds) from an error message.
 /// Looks for patterns like `Retry-After: 5` or `retry_after: 2.5` in the error string.
 fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
+    // Typed path: the provider already extracted Retry-After (header or body)
+    // at classification time.
+    if let Some(retry_after) = err
+        .downcast_ref::<ProviderError>()
+        .and_then(ProviderError::retry_after)
+    {
+        return u64::try_from(retry_after.as_millis()).ok();
+    }
+
     let msg = err.to_string();
     let lower = msg.to_lowercase();
 
@@ -290,6 +331,17 @@ fn failure_reason(rate_limited: bool, non_retryable: bool) -> &'static str {
     }
 }
 
+/// Wrap an aggregate failure message around the last classified provider
+/// error, when one exists, so callers can still `downcast_ref::<ProviderError>`
+/// after retries and fallbacks are exhausted. `Display` shows only the
+/// aggregate message, keeping the diagnostic trail unchanged.
+fn aggregate_failure(message: String, last_error: Option<ProviderError>) -> anyhow::Error {
+    match last_error {
+        Some(provider_error) => anyhow::Error::new(provider_error).context(message),
+        None => anyhow::anyhow!(message),
+    }
+}
+
 fn compact_error_detail(err: &anyhow::Error) -> String {
     super::sanitize_api_error(&err.to_string())
         .split_whitespace()
@@ -400,6 +452,13 @@ pub struct ReliableProvider {
     /// Rate-limit cooldown: provider_index → earliest retry time.
     /// Providers are skipped until their cooldown expires (default 60s).
     rate_limit_cooldowns: Mutex<HashMap<usize, Instant>>,
+    /// Circuit breaker: provider_index → consecutive requests on which the
+    /// provider exhausted every model/retry combination. Cleared on success.
+    consecutive_failures: Mutex<HashMap<usize, u32>>,
+    /// Opt-in response cache for byte-identical prompts (`[reliability.cache]`).
+    response_cache: Option<super::response_cache::ResponseCache>,
+    /// Per-request cache opt-out, toggled via `set_response_cache_bypass`.
+    cache_bypass: AtomicBool,
 }
 
 impl ReliableProvider {
@@ -422,6 +481,9 @@ impl ReliableProvider {
             key_index: AtomicUsize::new(0),
             model_fallbacks: HashMap::new(),
             rate_limit_cooldowns: Mutex::new(HashMap::new()),
+            consecutive_failures: Mutex::new(HashMap::new()),
+            response_cache: None,
+            cache_bypass: AtomicBool::new(false),
         }
     }
 
@@ -437,6 +499,59 @@ impl ReliableProvider {
         self
     }
 
+    /// Enable the response cache when `[reliability.cache]` is configured.
+    pub fn with_response_cache(mut self, config: &crate::config::ResponseCacheConfig) -> Self {
+        if config.enabled {
+            self.response_cache = Some(super::response_cache::ResponseCache::new(config));
+        }
+        self
+    }
+
+    /// Compute the response cache key for a request, or `None` when the cache
+    /// is disabled, opted out for this request, or the messages carry tool
+    /// results / images that must never be served from cache.
+    fn response_cache_key(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> Option<u64> {
+        self.response_cache.as_ref()?;
+        if self.cache_bypass.load(Ordering::Relaxed)
+            || super::response_cache::should_bypass(messages)
+        {
+            return None;
+        }
+        let primary = self
+            .providers
+            .first()
+            .map(|(n, _)| n.as_str())
+            .unwrap_or("");
+        Some(super::response_cache::cache_key(
+            primary,
+            model,
+            temperature,
+            messages,
+        ))
+    }
+
+    /// Serve a cached response for the key, if one is still live.
+    fn cached_response(&self, key: Option<u64>) -> Option<String> {
+        let cache = self.response_cache.as_ref()?;
+        let cached = cache.get(key?);
+        if cached.is_some() {
+            tracing::debug!("Serving provider response from cache");
+        }
+        cached
+    }
+
+    /// Store a fresh response under the key, when caching applies.
+    fn store_cached_response(&self, key: Option<u64>, response: &str) {
+        if let (Some(cache), Some(key)) = (self.response_cache.as_ref(), key) {
+            cache.insert(key, response.to_string());
+        }
+    }
+
     /// Build the list of models to try: [original, fallback1, fallback2, ...]
     fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
         let mut chain = vec![model];
@@ -487,6 +602,54 @@ impl ReliableProvider {
     /// Default rate-limit cooldown: 10s. Short enough to retry gemini quickly
     /// but long enough to skip wasted attempts within a single tool-loop iteration.
     const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(10);
+
+    /// Consecutive exhausted requests before the circuit breaker opens.
+    const CIRCUIT_TRIP_FAILURES: u32 = 2;
+
+    /// How long an open circuit keeps a provider out of the chain. Expiry
+    /// half-opens the circuit: the next request tries the provider again,
+    /// and one more exhaustion re-opens it.
+    const CIRCUIT_OPEN_COOLDOWN: Duration = Duration::from_secs(30);
+
+    /// Record a successful call: closes the provider's circuit and counts
+    /// the request in the daily quota history.
+    fn record_provider_success(&self, provider_idx: usize) {
+        let mut lock = self
+            .consecutive_failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        lock.remove(&provider_idx);
+        drop(lock);
+        if let Some((name, _)) = self.providers.get(provider_idx) {
+            super::quota::record_request(name);
+        }
+    }
+
+    /// Record that a provider exhausted every model/retry combination for a
+    /// request. After [`Self::CIRCUIT_TRIP_FAILURES`] consecutive exhaustions
+    /// the circuit opens and the chain skips straight to fallbacks (via the
+    /// cooldown map) until it half-opens.
+    fn record_provider_exhausted(&self, provider_idx: usize, provider_name: &str) {
+        let failures = {
+            let mut lock = self
+                .consecutive_failures
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let entry = lock.entry(provider_idx).or_insert(0);
+            *entry += 1;
+            *entry
+        };
+        if failures >= Self::CIRCUIT_TRIP_FAILURES {
+            tracing::warn!(
+                provider = provider_name,
+                consecutive_failures = failures,
+                cooldown_secs = Self::CIRCUIT_OPEN_COOLDOWN.as_secs(),
+                "Circuit breaker opened; skipping provider until cooldown expires"
+            );
+            self.set_cooldown(provider_idx, Self::CIRCUIT_OPEN_COOLDOWN);
+            super::quota::record_breaker_trip(provider_name);
+        }
+    }
 }
 
 #[async_trait]
@@ -501,6 +664,25 @@ impl Provider for ReliableProvider {
         Ok(())
     }
 
+    async fn detected_context_window(&self) -> Option<usize> {
+        for (_, provider) in &self.providers {
+            if let Some(window) = provider.detected_context_window().await {
+                return Some(window);
+            }
+        }
+        None
+    }
+
+    fn set_response_cache_bypass(&self, bypass: bool) {
+        self.cache_bypass.store(bypass, Ordering::Relaxed);
+    }
+
+    async fn clear_conversation_state(&self) {
+        for (_, provider) in &self.providers {
+            provider.clear_conversation_state().await;
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -508,8 +690,19 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
+        let cache_messages: Vec<ChatMessage> = system_prompt
+            .map(ChatMessage::system)
+            .into_iter()
+            .chain(std::iter::once(ChatMessage::user(message)))
+            .collect();
+        let cache_key = self.response_cache_key(&cache_messages, model, temperature);
+        if let Some(cached) = self.cached_response(cache_key) {
+            return Ok(cached);
+        }
+
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_provider_error: Option<ProviderError> = None;
 
         // Outer: provider priority. Middle: compatible models. Inner: retries.
         for (provider_idx, (provider_name, provider)) in self.providers.iter().enumerate() {
@@ -560,9 +753,14 @@ impl Provider for ReliableProvider {
                                     current_model,
                                 );
                             }
+                            self.record_provider_success(provider_idx);
+                            self.store_cached_response(cache_key, &resp);
                             return Ok(resp);
                         }
                         Err(e) => {
+                            if let Some(typed) = e.downcast_ref::<ProviderError>() {
+                                last_provider_error = Some(typed.clone());
+                            }
                             // Context window exceeded: no history to truncate
                             // in chat_with_system, bail immediately.
                             if is_context_window_exceeded(&e) {
@@ -576,15 +774,21 @@ impl Provider for ReliableProvider {
                                     "non_retryable",
                                     &error_detail,
                                 );
-                                anyhow::bail!(
-                                    "Request exceeds model context window. Attempts:\n{}",
-                                    failures.join("\n")
-                                );
+                                return Err(aggregate_failure(
+                                    format!(
+                                        "Request exceeds model context window. Attempts:\n{}",
+                                        failures.join("\n")
+                                    ),
+                                    last_provider_error,
+                                ));
                             }
 
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
+                            if rate_limited {
+                                super::quota::record_rate_limit(provider_name);
+                            }
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
 
@@ -620,10 +824,13 @@ impl Provider for ReliableProvider {
                                 );
 
                                 if is_context_window_exceeded(&e) {
-                                    anyhow::bail!(
-                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
-                                        failures.join("\n")
-                                    );
+                                    return Err(aggregate_failure(
+                                        format!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        ),
+                                        last_provider_error,
+                                    ));
                                 }
 
                                 break; // try next model on this provider
@@ -661,12 +868,18 @@ impl Provider for ReliableProvider {
                     }
                 }
             }
+            // Every model/retry combination failed for this provider on this
+            // request — feed the circuit breaker.
+            self.record_provider_exhausted(provider_idx, provider_name);
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+        Err(aggregate_failure(
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
+            last_provider_error,
+        ))
     }
 
     async fn chat_with_history(
@@ -675,8 +888,14 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
+        let cache_key = self.response_cache_key(messages, model, temperature);
+        if let Some(cached) = self.cached_response(cache_key) {
+            return Ok(cached);
+        }
+
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_provider_error: Option<ProviderError> = None;
         let mut effective_messages = messages.to_vec();
         let mut context_truncated = false;
 
@@ -724,9 +943,14 @@ impl Provider for ReliableProvider {
                                     current_model,
                                 );
                             }
+                            self.record_provider_success(provider_idx);
+                            self.store_cached_response(cache_key, &resp);
                             return Ok(resp);
                         }
                         Err(e) => {
+                            if let Some(typed) = e.downcast_ref::<ProviderError>() {
+                                last_provider_error = Some(typed.clone());
+                            }
                             // Context window exceeded: truncate history and retry
                             if is_context_window_exceeded(&e) && !context_truncated {
                                 let dropped = truncate_for_context(&mut effective_messages);
@@ -754,17 +978,23 @@ impl Provider for ReliableProvider {
                                     "non_retryable",
                                     &error_detail,
                                 );
-                                anyhow::bail!(
-                                    "Request exceeds model context window and cannot be reduced further. \
-                                     Try using a model with a larger context window, reducing the number \
-                                     of tools/skills, or enabling compact_context in config. Attempts:\n{}",
-                                    failures.join("\n")
-                                );
+                                return Err(aggregate_failure(
+                                    format!(
+                                        "Request exceeds model context window and cannot be reduced further. \
+                                         Try using a model with a larger context window, reducing the number \
+                                         of tools/skills, or enabling compact_context in config. Attempts:\n{}",
+                                        failures.join("\n")
+                                    ),
+                                    last_provider_error,
+                                ));
                             }
 
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
+                            if rate_limited {
+                                super::quota::record_rate_limit(provider_name);
+                            }
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
 
@@ -832,12 +1062,18 @@ impl Provider for ReliableProvider {
                     }
                 }
             }
+            // Every model/retry combination failed for this provider on this
+            // request — feed the circuit breaker.
+            self.record_provider_exhausted(provider_idx, provider_name);
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+        Err(aggregate_failure(
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
+            last_provider_error,
+        ))
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -862,6 +1098,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_provider_error: Option<ProviderError> = None;
         let mut effective_messages = messages.to_vec();
         let mut context_truncated = false;
 
@@ -910,9 +1147,20 @@ impl Provider for ReliableProvider {
                                     current_model,
                                 );
                             }
+                            self.record_provider_success(provider_idx);
+                            if let Some(usage) = &resp.usage {
+                                super::quota::record_tokens(
+                                    provider_name,
+                                    usage.input_tokens.unwrap_or(0),
+                                    usage.output_tokens.unwrap_or(0),
+                                );
+                            }
                             return Ok(resp);
                         }
                         Err(e) => {
+                            if let Some(typed) = e.downcast_ref::<ProviderError>() {
+                                last_provider_error = Some(typed.clone());
+                            }
                             // Context window exceeded: truncate history and retry
                             if is_context_window_exceeded(&e) && !context_truncated {
                                 let dropped = truncate_for_context(&mut effective_messages);
@@ -940,17 +1188,23 @@ impl Provider for ReliableProvider {
                                     "non_retryable",
                                     &error_detail,
                                 );
-                                anyhow::bail!(
-                                    "Request exceeds model context window and cannot be reduced further. \
-                                     Try using a model with a larger context window, reducing the number \
-                                     of tools/skills, or enabling compact_context in config. Attempts:\n{}",
-                                    failures.join("\n")
-                                );
+                                return Err(aggregate_failure(
+                                    format!(
+                                        "Request exceeds model context window and cannot be reduced further. \
+                                         Try using a model with a larger context window, reducing the number \
+                                         of tools/skills, or enabling compact_context in config. Attempts:\n{}",
+                                        failures.join("\n")
+                                    ),
+                                    last_provider_error,
+                                ));
                             }
 
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
+                            if rate_limited {
+                                super::quota::record_rate_limit(provider_name);
+                            }
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
 
@@ -1018,12 +1272,18 @@ impl Provider for ReliableProvider {
                     }
                 }
             }
+            // Every model/retry combination failed for this provider on this
+            // request — feed the circuit breaker.
+            self.record_provider_exhausted(provider_idx, provider_name);
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+        Err(aggregate_failure(
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
+            last_provider_error,
+        ))
     }
 
     async fn chat(
@@ -1034,6 +1294,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut last_provider_error: Option<ProviderError> = None;
         let mut effective_messages = request.messages.to_vec();
         let mut context_truncated = false;
 
@@ -1083,9 +1344,20 @@ impl Provider for ReliableProvider {
                                     current_model,
                                 );
                             }
+                            self.record_provider_success(provider_idx);
+                            if let Some(usage) = &resp.usage {
+                                super::quota::record_tokens(
+                                    provider_name,
+                                    usage.input_tokens.unwrap_or(0),
+                                    usage.output_tokens.unwrap_or(0),
+                                );
+                            }
                             return Ok(resp);
                         }
                         Err(e) => {
+                            if let Some(typed) = e.downcast_ref::<ProviderError>() {
+                                last_provider_error = Some(typed.clone());
+                            }
                             // Context window exceeded: truncate history and retry
                             if is_context_window_exceeded(&e) && !context_truncated {
                                 let dropped = truncate_for_context(&mut effective_messages);
@@ -1113,17 +1385,23 @@ impl Provider for ReliableProvider {
                                     "non_retryable",
                                     &error_detail,
                                 );
-                                anyhow::bail!(
-                                    "Request exceeds model context window and cannot be reduced further. \
-                                     Try using a model with a larger context window, reducing the number \
-                                     of tools/skills, or enabling compact_context in config. Attempts:\n{}",
-                                    failures.join("\n")
-                                );
+                                return Err(aggregate_failure(
+                                    format!(
+                                        "Request exceeds model context window and cannot be reduced further. \
+                                         Try using a model with a larger context window, reducing the number \
+                                         of tools/skills, or enabling compact_context in config. Attempts:\n{}",
+                                        failures.join("\n")
+                                    ),
+                                    last_provider_error,
+                                ));
                             }
 
                             let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                             let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
                             let rate_limited = is_rate_limited(&e);
+                            if rate_limited {
+                                super::quota::record_rate_limit(provider_name);
+                            }
                             let failure_reason = failure_reason(rate_limited, non_retryable);
                             let error_detail = compact_error_detail(&e);
 
@@ -1191,12 +1469,18 @@ impl Provider for ReliableProvider {
                     }
                 }
             }
+            // Every model/retry combination failed for this provider on this
+            // request — feed the circuit breaker.
+            self.record_provider_exhausted(provider_idx, provider_name);
         }
 
-        anyhow::bail!(
-            "All providers/models failed. Attempts:\n{}",
-            failures.join("\n")
-        )
+        Err(aggregate_failure(
+            format!(
+                "All providers/models failed. Attempts:\n{}",
+                failures.join("\n")
+            ),
+            last_provider_error,
+        ))
     }
 
     fn supports_streaming(&self) -> bool {
@@ -1557,6 +1841,251 @@ mod tests {
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn circuit_breaker_skips_exhausted_provider_after_consecutive_failures() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "primary down",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "from fallback",
+                        error: "fallback down",
+                    }),
+                ),
+            ],
+            1,
+            1,
+        );
+
+        // Two requests where the primary exhausts every retry trip its breaker.
+        for _ in 0..2 {
+            let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+            assert_eq!(result, "from fallback");
+        }
+        let calls_after_trip = primary_calls.load(Ordering::SeqCst);
+        assert_eq!(calls_after_trip, 4); // 2 attempts per request
+
+        // Third request: the circuit is open, so the chain skips straight to
+        // the fallback without touching the primary.
+        let result = provider.simple_chat("hello", "test", 0.0).await.unwrap();
+        assert_eq!(result, "from fallback");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), calls_after_trip);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn circuit_breaker_success_resets_failure_count() {
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::new(AtomicUsize::new(0)),
+                    fail_until_attempt: 0,
+                    response: "ok",
+                    error: "unused",
+                }),
+            )],
+            0,
+            1,
+        );
+
+        provider.record_provider_exhausted(0, "primary");
+        assert!(!provider.is_in_cooldown(0));
+
+        provider.record_provider_success(0);
+        provider.record_provider_exhausted(0, "primary");
+        assert!(
+            !provider.is_in_cooldown(0),
+            "success must reset the consecutive-failure count"
+        );
+
+        provider.record_provider_exhausted(0, "primary");
+        assert!(
+            provider.is_in_cooldown(0),
+            "second consecutive exhaustion must open the circuit"
+        );
+    }
+
+    fn enabled_cache_config() -> crate::config::ResponseCacheConfig {
+        crate::config::ResponseCacheConfig {
+            enabled: true,
+            ttl_secs: 300,
+            max_entries: 16,
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_served_from_cache() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    response: "cached ok",
+                    error: "unused",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_response_cache(&enabled_cache_config());
+
+        let messages = vec![ChatMessage::user("heartbeat ping")];
+        let first = provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        let second = provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, "cached ok");
+        assert_eq!(second, "cached ok");
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            1,
+            "second identical request must be served from cache"
+        );
+
+        // A different prompt misses the cache and reaches the provider.
+        let other = vec![ChatMessage::user("different prompt")];
+        provider
+            .chat_with_history(&other, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn tool_results_skip_the_response_cache() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    response: "ok",
+                    error: "unused",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_response_cache(&enabled_cache_config());
+
+        let messages = vec![
+            ChatMessage::user("run the tool"),
+            ChatMessage::tool("exit code 0"),
+        ];
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "requests with tool results must bypass the cache"
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_bypass_toggle_forces_provider_calls() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    response: "ok",
+                    error: "unused",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_response_cache(&enabled_cache_config());
+
+        let messages = vec![ChatMessage::user("always fresh")];
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+
+        provider.set_response_cache_bypass(true);
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "bypass must force the request through to the provider"
+        );
+
+        provider.set_response_cache_bypass(false);
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "clearing the bypass restores cache hits"
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_disabled_by_default() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    response: "ok",
+                    error: "unused",
+                }),
+            )],
+            0,
+            1,
+        )
+        .with_response_cache(&crate::config::ResponseCacheConfig::default());
+
+        let messages = vec![ChatMessage::user("hello")];
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        provider
+            .chat_with_history(&messages, "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn returns_aggregated_error_when_all_providers_fail() {
         let provider = ReliableProvider::new(
@@ -1939,6 +2468,81 @@ mod tests {
         assert_eq!(parse_retry_after_ms(&err), None);
     }
 
+    // ── Typed ProviderError classification ──
+
+    fn typed_error(status: u16, retry_after: Option<Duration>, body: &str) -> anyhow::Error {
+        anyhow::Error::new(ProviderError::from_response_parts(
+            "Test",
+            reqwest::StatusCode::from_u16(status).unwrap(),
+            retry_after,
+            body,
+        ))
+    }
+
+    #[test]
+    fn typed_rate_limit_drives_retry_after_instead_of_string_parsing() {
+        // Retry-After came from the HTTP header — the message body has no hint.
+        let err = typed_error(
+            429,
+            Some(Duration::from_secs(20)),
+            r#"{"error":{"message":"Rate limit reached for gpt-4o on tokens per min (TPM).","code":"rate_limit_exceeded"}}"#,
+        );
+        assert!(is_rate_limited(&err));
+        assert!(!is_non_retryable_rate_limit(&err));
+        assert_eq!(parse_retry_after_ms(&err), Some(20_000));
+    }
+
+    #[test]
+    fn typed_quota_exhaustion_is_a_non_retryable_rate_limit() {
+        let err = typed_error(
+            429,
+            None,
+            r#"{"error":{"message":"You exceeded your current quota, please check your plan and billing details.","type":"insufficient_quota"}}"#,
+        );
+        assert!(is_rate_limited(&err));
+        assert!(is_non_retryable_rate_limit(&err));
+    }
+
+    #[test]
+    fn typed_auth_failure_is_non_retryable() {
+        let err = typed_error(
+            401,
+            None,
+            r#"{"error":{"message":"Incorrect API key provided.","code":"invalid_api_key"}}"#,
+        );
+        assert!(is_non_retryable(&err));
+        assert!(!is_rate_limited(&err));
+    }
+
+    #[test]
+    fn typed_context_overflow_detected_without_substring_hints() {
+        // HTTP 413 carries no recognizable hint text — only the typed kind.
+        let err = typed_error(413, None, "payload too large");
+        assert!(is_context_window_exceeded(&err));
+        assert!(!is_non_retryable(&err));
+    }
+
+    #[test]
+    fn aggregate_failure_preserves_typed_error_and_message() {
+        let typed = ProviderError::from_response_parts(
+            "Test",
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(7)),
+            "slow down",
+        );
+        let err = aggregate_failure(
+            "All providers/models failed. Attempts:\nprovider=test model=m attempt 1/1: rate_limited".to_string(),
+            Some(typed),
+        );
+
+        assert!(err.to_string().contains("All providers/models failed"));
+        assert_eq!(parse_retry_after_ms(&err), Some(7_000));
+        assert!(is_rate_limited(&err));
+
+        let bare = aggregate_failure("All providers/models failed. Attempts:\n".to_string(), None);
+        assert!(bare.downcast_ref::<ProviderError>().is_none());
+    }
+
     #[test]
     fn rate_limited_detection() {
         assert!(is_rate_limited(&anyhow::anyhow!("429 Too Many Requests")));
@@ -0,0 +1,225 @@
+//! In-memory response cache for byte-identical prompts.
+//!
+//! Heartbeat prompts and repeated classification calls send the same request
+//! to the provider many times an hour. When `[reliability.cache]` is enabled,
+//! `ReliableProvider` serves those from this TTL-bounded cache instead of
+//! burning quota. Caching is restricted to non-streamed text responses and is
+//! bypassed for any request carrying tool results or image markers, where
+//! replaying a stale answer would be wrong.
+
+use super::traits::ChatMessage;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Set once any cache is constructed, so `/status` can tell "cache disabled"
+/// apart from "cache enabled but idle".
+static CACHE_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Process-wide hit/miss counters surfaced by the `/status` command.
+static GLOBAL_HITS: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Process-wide `(hits, misses)` across all caches, or `None` when no
+/// response cache has been constructed (i.e. the feature is disabled).
+pub fn global_counters() -> Option<(u64, u64)> {
+    CACHE_ACTIVE.load(Ordering::Relaxed).then(|| {
+        (
+            GLOBAL_HITS.load(Ordering::Relaxed),
+            GLOBAL_MISSES.load(Ordering::Relaxed),
+        )
+    })
+}
+
+/// Stable cache key over everything that determines a provider response:
+/// provider, model, temperature, and the full ordered message list.
+pub fn cache_key(provider: &str, model: &str, temperature: f64, messages: &[ChatMessage]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    provider.hash(&mut hasher);
+    model.hash(&mut hasher);
+    temperature.to_bits().hash(&mut hasher);
+    for message in messages {
+        message.role.hash(&mut hasher);
+        message.content.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// True when a request must not be served from (or stored into) the cache:
+/// tool results and image attachments make responses request-specific.
+pub fn should_bypass(messages: &[ChatMessage]) -> bool {
+    messages
+        .iter()
+        .any(|m| m.role == "tool" || m.content.contains("[IMAGE:"))
+}
+
+struct CacheEntry {
+    response: String,
+    inserted_at: Instant,
+}
+
+/// TTL-bounded in-memory cache keyed by [`cache_key`].
+///
+/// Eviction is oldest-first when `max_entries` is reached; expired entries
+/// are dropped lazily on lookup.
+pub struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<u64, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub fn new(config: &crate::config::ResponseCacheConfig) -> Self {
+        CACHE_ACTIVE.store(true, Ordering::Relaxed);
+        Self {
+            ttl: Duration::from_secs(config.ttl_secs),
+            max_entries: config.max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Look up a cached response, counting the hit or miss.
+    pub fn get(&self, key: u64) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let response = match entries.get(&key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => Some(entry.response.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+        drop(entries);
+
+        if response.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            GLOBAL_HITS.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            GLOBAL_MISSES.fetch_add(1, Ordering::Relaxed);
+        }
+        response
+    }
+
+    /// Store a response, evicting the oldest entry when the cache is full.
+    pub fn insert(&self, key: u64, response: String) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(k, _)| *k)
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                response,
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Hits recorded by this cache instance.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Misses recorded by this cache instance.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ResponseCacheConfig;
+
+    fn test_cache(ttl_secs: u64, max_entries: usize) -> ResponseCache {
+        ResponseCache::new(&ResponseCacheConfig {
+            enabled: true,
+            ttl_secs,
+            max_entries,
+        })
+    }
+
+    fn msgs(content: &str) -> Vec<ChatMessage> {
+        vec![ChatMessage::system("be brief"), ChatMessage::user(content)]
+    }
+
+    #[test]
+    fn key_is_stable_for_identical_requests() {
+        let a = cache_key("anthropic", "claude-x", 0.7, &msgs("ping"));
+        let b = cache_key("anthropic", "claude-x", 0.7, &msgs("ping"));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn key_changes_with_any_request_component() {
+        let base = cache_key("anthropic", "claude-x", 0.7, &msgs("ping"));
+        assert_ne!(base, cache_key("openai", "claude-x", 0.7, &msgs("ping")));
+        assert_ne!(base, cache_key("anthropic", "gpt-x", 0.7, &msgs("ping")));
+        assert_ne!(base, cache_key("anthropic", "claude-x", 0.8, &msgs("ping")));
+        assert_ne!(base, cache_key("anthropic", "claude-x", 0.7, &msgs("pong")));
+    }
+
+    #[test]
+    fn hit_returns_cached_response_and_counts() {
+        let cache = test_cache(300, 16);
+        let key = cache_key("p", "m", 0.0, &msgs("hello"));
+        assert_eq!(cache.get(key), None);
+        cache.insert(key, "cached answer".into());
+        assert_eq!(cache.get(key).as_deref(), Some("cached answer"));
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let cache = test_cache(0, 16);
+        let key = cache_key("p", "m", 0.0, &msgs("hello"));
+        cache.insert(key, "stale".into());
+        // ttl_secs = 0 means every entry is expired by lookup time.
+        assert_eq!(cache.get(key), None);
+    }
+
+    #[test]
+    fn oldest_entry_evicted_at_capacity() {
+        let cache = test_cache(300, 2);
+        let first = cache_key("p", "m", 0.0, &msgs("one"));
+        let second = cache_key("p", "m", 0.0, &msgs("two"));
+        let third = cache_key("p", "m", 0.0, &msgs("three"));
+        cache.insert(first, "1".into());
+        cache.insert(second, "2".into());
+        cache.insert(third, "3".into());
+        assert_eq!(cache.get(first), None);
+        assert_eq!(cache.get(second).as_deref(), Some("2"));
+        assert_eq!(cache.get(third).as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn tool_results_bypass_the_cache() {
+        let mut messages = msgs("run it");
+        messages.push(ChatMessage::tool("exit code 0"));
+        assert!(should_bypass(&messages));
+    }
+
+    #[test]
+    fn image_markers_bypass_the_cache() {
+        let messages = vec![ChatMessage::user("look at [IMAGE:/tmp/cat.png]")];
+        assert!(should_bypass(&messages));
+    }
+
+    #[test]
+    fn plain_text_requests_are_cacheable() {
+        assert!(!should_bypass(&msgs("status check")));
+    }
+}
@@ -57,6 +57,9 @@ pub struct TokenUsage {
     /// Tokens served from the provider's prompt cache (Anthropic `cache_read_input_tokens`,
     /// OpenAI `prompt_tokens_details.cached_tokens`).
     pub cached_input_tokens: Option<u64>,
+    /// Tokens written to the provider's prompt cache this turn (Anthropic
+    /// `cache_creation_input_tokens`).
+    pub cache_write_input_tokens: Option<u64>,
 }
 
 /// An LLM response that may contain text, tool calls, or both.
@@ -195,6 +198,10 @@ pub enum StreamEvent {
     PreExecutedToolCall { name: String, args: String },
     /// The result of a pre-executed tool call.
     PreExecutedToolResult { name: String, output: String },
+    /// Token usage reported by the provider, typically on the stream's final
+    /// usage-bearing chunk. Emitted at most once, before [`StreamEvent::Final`],
+    /// so consumers can feed streamed turns into cost accounting.
+    Usage(TokenUsage),
     /// Stream has completed.
     Final,
 }
@@ -444,6 +451,23 @@ pub trait Provider: Send + Sync {
         Ok(())
     }
 
+    /// Context window (in tokens) reported by the provider itself, when it
+    /// exposes one (e.g. llama.cpp's `/props`). `None` means unknown; callers
+    /// should fall back to the configured `max_context_tokens`.
+    async fn detected_context_window(&self) -> Option<usize> {
+        None
+    }
+
+    /// Opt the next requests on this handle out of the response cache
+    /// (`[reliability.cache]`). The agent loop toggles this around calls that
+    /// must always reach the provider. No-op for providers without a cache.
+    fn set_response_cache_bypass(&self, _bypass: bool) {}
+
+    /// Drop any provider-side state tied to the current conversation
+    /// (e.g. Gemini `cachedContent` resources). Called when the conversation
+    /// history is cleared. Default is a no-op.
+    async fn clear_conversation_state(&self) {}
+
     /// Chat with tool definitions for native function calling support.
     /// The default implementation falls back to chat_with_history and returns
     /// an empty tool_calls vector (prompt-based tool use only).
@@ -649,6 +673,7 @@ mod tests {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
                 cached_input_tokens: None,
+                cache_write_input_tokens: None,
             }),
             reasoning_content: None,
         };
@@ -2,23 +2,205 @@
 //!
 //! Supports:
 //! - Markdown and text datasheets (always)
+//! - DOCX ingestion via zip + WordprocessingML text extraction (always)
 //! - PDF ingestion (with `rag-pdf` feature)
+//! - Page numbers and heading paths preserved as chunk metadata for citations
 //! - Pin/alias tables (e.g. `red_led: 13`) for explicit lookup
 //! - Keyword retrieval (default) or semantic search via embeddings (optional)
+//! - Optional rerank stage over an enlarged candidate set (`[rag.rerank]`)
+
+pub mod rerank;
+pub mod watcher;
 
 use crate::memory::chunker;
+use crate::memory::embeddings::EmbeddingProvider;
+use crate::memory::vector;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 /// A chunk of datasheet content with board metadata.
 #[derive(Debug, Clone)]
 pub struct DatasheetChunk {
     /// Board this chunk applies to (e.g. "nucleo-f401re", "rpi-gpio"), or None for generic.
     pub board: Option<String>,
-    /// Source file path (for debugging).
+    /// Source file path (for citations and debugging).
     pub source: String,
     /// Chunk content.
     pub content: String,
+    /// 1-based page number for paginated sources (PDF/DOCX); None for flat text.
+    pub page: Option<u32>,
+    /// Heading path leading to this chunk (e.g. "Peripherals > SPI"), when known.
+    pub heading_path: Option<String>,
+}
+
+impl DatasheetChunk {
+    /// Citation detail for this chunk: board tag plus page and heading when
+    /// known, e.g. "nucleo-f401re, p. 3, Peripherals > SPI".
+    pub fn citation_detail(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut detail = self.board.as_deref().unwrap_or("generic").to_string();
+        if let Some(page) = self.page {
+            let _ = write!(detail, ", p. {page}");
+        }
+        if let Some(ref heading) = self.heading_path {
+            let _ = write!(detail, ", {heading}");
+        }
+        detail
+    }
+}
+
+/// Provenance of one numbered context block, used by
+/// [`expand_citation_markers`] to build the sources footer.
+#[derive(Debug, Clone)]
+pub struct CitationSource {
+    /// Workspace-relative source path.
+    pub path: String,
+    /// Reference detail as rendered by [`DatasheetChunk::citation_detail`].
+    pub detail: String,
+}
+
+/// Expand `[n]` citation markers in a model response into a sources footer.
+///
+/// Markers referencing provided context blocks are kept and listed once in a
+/// trailing "Sources:" footer; markers pointing at blocks that were not
+/// provided are removed. When `link_base` is set (a gateway route serving
+/// workspace files), source paths are rendered as markdown links so channels
+/// with link support can hyperlink them. Responses that cite nothing are
+/// returned unchanged.
+pub fn expand_citation_markers(
+    response: &str,
+    sources: &[CitationSource],
+    link_base: Option<&str>,
+) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::with_capacity(response.len());
+    let mut cited = std::collections::BTreeSet::new();
+    let mut rest = response;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+        // A marker is "[<digits>]" not followed by "(" (markdown link syntax).
+        let is_marker =
+            digits > 0 && after[digits..].starts_with(']') && !after[digits + 1..].starts_with('(');
+        if !is_marker {
+            out.push('[');
+            rest = after;
+            continue;
+        }
+        let n: usize = after[..digits].parse().unwrap_or(0);
+        if n >= 1 && n <= sources.len() {
+            cited.insert(n);
+            out.push('[');
+            out.push_str(&after[..=digits]);
+        } else if out.ends_with(' ') {
+            // Drop the marker and the space left dangling before it.
+            out.pop();
+        }
+        rest = &after[digits + 1..];
+    }
+    out.push_str(rest);
+
+    if cited.is_empty() {
+        return out;
+    }
+
+    let mut expanded = out.trim_end().to_string();
+    expanded.push_str("\n\nSources:");
+    for n in cited {
+        let source = &sources[n - 1];
+        let path = match link_base {
+            Some(base) => format!(
+                "[{}]({}/{})",
+                source.path,
+                base.trim_end_matches('/'),
+                source.path
+            ),
+            None => source.path.clone(),
+        };
+        let _ = write!(expanded, "\n[{n}] {path} ({})", source.detail);
+    }
+    expanded
+}
+
+/// Chunking settings resolved from `[rag.chunking]` into plain values (the
+/// rag module is re-exported into the binary crate, so it avoids the
+/// crate-local config types).
+#[derive(Debug, Clone)]
+pub struct ChunkingSettings {
+    /// Strategy name: "fixed", "sentence", or "heading".
+    pub strategy: String,
+    /// Target chunk size in estimated tokens.
+    pub max_tokens: usize,
+    /// Estimated tokens repeated from the end of the previous chunk.
+    pub overlap_tokens: usize,
+    /// Embeddings model for token estimation, when configured.
+    pub embed_model: Option<String>,
+}
+
+impl Default for ChunkingSettings {
+    fn default() -> Self {
+        Self {
+            strategy: "heading".to_string(),
+            max_tokens: 512,
+            overlap_tokens: 0,
+            embed_model: None,
+        }
+    }
+}
+
+impl ChunkingSettings {
+    /// Identity of this configuration, stored alongside the index so
+    /// `zeroclaw doctor` can flag indexes that need a reindex after a
+    /// config change.
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.strategy,
+            self.max_tokens,
+            self.overlap_tokens,
+            self.embed_model.as_deref().unwrap_or("-")
+        )
+    }
+
+    fn chunker_options(&self) -> chunker::ChunkingOptions {
+        let strategy = chunker::ChunkStrategy::from_name(&self.strategy).unwrap_or_else(|| {
+            tracing::warn!(
+                strategy = %self.strategy,
+                "Unknown [rag.chunking] strategy; using heading"
+            );
+            chunker::ChunkStrategy::Heading
+        });
+        chunker::ChunkingOptions {
+            strategy,
+            max_tokens: self.max_tokens,
+            overlap_tokens: self.overlap_tokens,
+            model: self.embed_model.clone(),
+        }
+    }
+}
+
+/// Per-file ingest outcome: pages processed, chunks produced, extraction warnings.
+#[derive(Debug, Default)]
+pub struct IngestFileReport {
+    /// Source path relative to the workspace.
+    pub source: String,
+    /// Pages processed (0 for flat text files, which have no page structure).
+    pub pages: usize,
+    /// Chunks produced for the index.
+    pub chunks: usize,
+    /// Extraction warnings (scanned PDFs, unreadable archives, …).
+    pub warnings: Vec<String>,
+}
+
+/// Ingest summary across all datasheet files, built by [`HardwareRag::load_with_report`].
+#[derive(Debug, Default)]
+pub struct IngestReport {
+    /// One entry per file found in the datasheet directory.
+    pub files: Vec<IngestFileReport>,
 }
 
 /// Pin alias: human-readable name → pin number (e.g. "red_led" → 13).
@@ -98,44 +280,280 @@ fn parse_pin_aliases(content: &str) -> PinAliases {
     aliases
 }
 
-fn collect_md_txt_paths(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
+fn collect_datasheet_paths(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
     let Ok(entries) = std::fs::read_dir(dir) else {
         return;
     };
     for entry in entries.flatten() {
         let path = entry.path();
         if path.is_dir() {
-            collect_md_txt_paths(&path, out);
+            collect_datasheet_paths(&path, out);
         } else if path.is_file() {
             let ext = path.extension().and_then(|e| e.to_str());
-            if ext == Some("md") || ext == Some("txt") {
+            if matches!(ext, Some("md" | "txt" | "pdf" | "docx")) {
                 out.push(path);
             }
         }
     }
 }
 
+/// Extract PDF text split into pages. `pdf_extract` separates pages with
+/// form feeds; a PDF without a form feed comes back as a single page.
 #[cfg(feature = "rag-pdf")]
-fn collect_pdf_paths(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
-    let Ok(entries) = std::fs::read_dir(dir) else {
-        return;
+fn extract_pdf_pages(path: &Path) -> anyhow::Result<Vec<String>> {
+    let bytes = std::fs::read(path)?;
+    let text = pdf_extract::extract_text_from_mem(&bytes)?;
+    Ok(text.split('\u{0c}').map(str::to_string).collect())
+}
+
+/// A contiguous run of DOCX paragraphs under one heading path on one page.
+#[derive(Debug, Default)]
+struct DocxSection {
+    /// 1-based page the section starts on (from explicit/rendered page breaks).
+    page: u32,
+    /// Heading path at the start of the section (e.g. "Peripherals > SPI").
+    heading_path: Option<String>,
+    /// Section text with headings re-emitted as markdown for the chunker.
+    text: String,
+}
+
+/// Split `document.xml` into `<w:p>…</w:p>` paragraph bodies.
+fn docx_paragraphs(xml: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<w:p") {
+        let after = &rest[start + 4..];
+        // Only "<w:p>" or "<w:p …>" open a paragraph; skip <w:pPr>, <w:pStyle>, …
+        if !(after.starts_with('>') || after.starts_with(' ')) {
+            rest = after;
+            continue;
+        }
+        let Some(end) = after.find("</w:p>") else {
+            break;
+        };
+        out.push(&after[..end]);
+        rest = &after[end + 6..];
+    }
+    out
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Concatenate the text runs (`<w:t>` elements) of one paragraph.
+fn docx_paragraph_text(para: &str) -> String {
+    let mut out = String::new();
+    let mut rest = para;
+    while let Some(start) = rest.find("<w:t") {
+        let after = &rest[start + 4..];
+        // Only "<w:t>" or "<w:t …>" are text runs; skip <w:tab>, <w:tc>, …
+        if !(after.starts_with('>') || after.starts_with(' ')) {
+            rest = after;
+            continue;
+        }
+        let Some(tag_end) = after.find('>') else {
+            break;
+        };
+        let body = &after[tag_end + 1..];
+        let Some(close) = body.find("</w:t>") else {
+            break;
+        };
+        out.push_str(&unescape_xml(&body[..close]));
+        rest = &body[close + 6..];
+    }
+    out
+}
+
+/// Heading level from a paragraph style (`<w:pStyle w:val="Heading2"/>` → 2).
+fn docx_heading_level(para: &str) -> Option<u8> {
+    let rest = &para[para.find("<w:pStyle")?..];
+    let val_start = rest.find("w:val=\"")? + 7;
+    let val_end = rest[val_start..].find('"')? + val_start;
+    let digits = rest[val_start..val_end].to_lowercase();
+    let digits = digits.strip_prefix("heading")?.trim().to_string();
+    digits.parse::<u8>().ok().filter(|l| (1..=9).contains(l))
+}
+
+/// Parse WordprocessingML into sections, tracking heading paths and page
+/// breaks. Returns the sections and the total page count.
+fn parse_docx_xml(xml: &str) -> (Vec<DocxSection>, usize) {
+    use std::fmt::Write as _;
+
+    fn flush(
+        sections: &mut Vec<DocxSection>,
+        cur: &mut DocxSection,
+        page: u32,
+        path: Option<String>,
+    ) {
+        if !cur.text.trim().is_empty() {
+            sections.push(std::mem::take(cur));
+        }
+        cur.page = page;
+        cur.heading_path = path;
+        cur.text.clear();
+    }
+
+    let mut sections: Vec<DocxSection> = Vec::new();
+    let mut heading_stack: Vec<(u8, String)> = Vec::new();
+    let mut page: u32 = 1;
+    let mut cur = DocxSection {
+        page: 1,
+        heading_path: None,
+        text: String::new(),
     };
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_pdf_paths(&path, out);
-        } else if path.is_file() {
-            if path.extension().and_then(|e| e.to_str()) == Some("pdf") {
-                out.push(path);
+
+    for para in docx_paragraphs(xml) {
+        if para.contains("<w:br w:type=\"page\"") || para.contains("<w:lastRenderedPageBreak") {
+            page += 1;
+            let path = cur.heading_path.clone();
+            flush(&mut sections, &mut cur, page, path);
+        }
+        let text = docx_paragraph_text(para);
+        let text = text.trim();
+        if let Some(level) = docx_heading_level(para) {
+            if !text.is_empty() {
+                while heading_stack.last().is_some_and(|(l, _)| *l >= level) {
+                    heading_stack.pop();
+                }
+                heading_stack.push((level, text.to_string()));
+                let path: Vec<&str> = heading_stack.iter().map(|(_, t)| t.as_str()).collect();
+                flush(&mut sections, &mut cur, page, Some(path.join(" > ")));
+                // Re-emit as markdown so the chunker keeps the heading with its
+                // content; the chunker only splits on levels 1-3.
+                let _ = writeln!(cur.text, "{} {text}\n", "#".repeat(level.min(3) as usize));
             }
+            continue;
+        }
+        if !text.is_empty() {
+            cur.text.push_str(text);
+            cur.text.push_str("\n\n");
         }
     }
+    let path = cur.heading_path.clone();
+    flush(&mut sections, &mut cur, page, path);
+
+    (sections, page as usize)
 }
 
-#[cfg(feature = "rag-pdf")]
-fn extract_pdf_text(path: &Path) -> Option<String> {
-    let bytes = std::fs::read(path).ok()?;
-    pdf_extract::extract_text_from_mem(&bytes).ok()
+/// Extract sections from a DOCX archive (zip containing `word/document.xml`).
+fn extract_docx_sections(path: &Path) -> anyhow::Result<(Vec<DocxSection>, usize)> {
+    use std::io::Read as _;
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| anyhow::anyhow!("not a DOCX archive (word/document.xml missing): {e}"))?
+        .read_to_string(&mut xml)?;
+    Ok(parse_docx_xml(&xml))
+}
+
+/// Ingest one datasheet file: extraction, chunking, and pin-alias parsing.
+/// `base` is the datasheet root used for board inference.
+pub(crate) fn ingest_file(
+    workspace_dir: &Path,
+    base: &Path,
+    path: &Path,
+    chunking: &ChunkingSettings,
+) -> (Vec<DatasheetChunk>, PinAliases, IngestFileReport) {
+    let source = path
+        .strip_prefix(workspace_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string();
+    let mut file_report = IngestFileReport {
+        source: source.clone(),
+        ..Default::default()
+    };
+    // (page, heading-path override, text) units feeding the chunker.
+    let mut units: Vec<(Option<u32>, Option<String>, String)> = Vec::new();
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("pdf") => {
+            #[cfg(feature = "rag-pdf")]
+            match extract_pdf_pages(path) {
+                Ok(pages) => {
+                    file_report.pages = pages.len();
+                    if pages.iter().all(|p| p.trim().is_empty()) {
+                        file_report
+                            .warnings
+                            .push("no text layer (scanned PDF?) — not supported".to_string());
+                    } else {
+                        for (i, page) in pages.iter().enumerate() {
+                            if !page.trim().is_empty() {
+                                units.push((Some(i as u32 + 1), None, page.clone()));
+                            }
+                        }
+                    }
+                }
+                Err(e) => file_report
+                    .warnings
+                    .push(format!("PDF extraction failed: {e}")),
+            }
+            #[cfg(not(feature = "rag-pdf"))]
+            file_report
+                .warnings
+                .push("PDF ingestion requires the `rag-pdf` feature".to_string());
+        }
+        Some("docx") => match extract_docx_sections(path) {
+            Ok((sections, pages)) => {
+                file_report.pages = pages;
+                if sections.is_empty() {
+                    file_report.warnings.push("no extractable text".to_string());
+                }
+                for section in sections {
+                    units.push((Some(section.page), section.heading_path, section.text));
+                }
+            }
+            Err(e) => file_report
+                .warnings
+                .push(format!("DOCX extraction failed: {e}")),
+        },
+        _ => {
+            let content = std::fs::read_to_string(path).unwrap_or_default();
+            if !content.trim().is_empty() {
+                units.push((None, None, content));
+            }
+        }
+    }
+
+    let board = infer_board_from_path(path, base);
+
+    // Parse pin aliases from the full extracted text
+    let full_text: String = units
+        .iter()
+        .map(|(_, _, t)| t.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let aliases = parse_pin_aliases(&full_text);
+
+    let options = chunking.chunker_options();
+    let mut chunks = Vec::new();
+    for (page, heading_override, text) in units {
+        for chunk in chunker::chunk_text(&text, &options) {
+            file_report.chunks += 1;
+            let heading_path = heading_override.clone().or_else(|| {
+                chunk
+                    .heading
+                    .as_deref()
+                    .map(|h| h.trim_start_matches('#').trim().to_string())
+            });
+            chunks.push(DatasheetChunk {
+                board: board.clone(),
+                source: source.clone(),
+                content: chunk.content,
+                page,
+                heading_path,
+            });
+        }
+    }
+
+    (chunks, aliases, file_report)
 }
 
 /// Hardware RAG index — loads and retrieves datasheet chunks.
@@ -143,76 +561,187 @@ pub struct HardwareRag {
     chunks: Vec<DatasheetChunk>,
     /// Per-board pin aliases (board -> alias -> pin).
     pin_aliases: HashMap<String, PinAliases>,
+    /// Optional embedding backend for semantic retrieval; keyword-only when absent.
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    /// Chunk embeddings, parallel to `chunks`. Built by `embed_index`.
+    chunk_embeddings: Vec<Vec<f32>>,
+    /// Optional rerank scorer; `retrieve_reranked` keeps base order when absent.
+    reranker: Option<Arc<dyn rerank::RerankScorer>>,
+    /// Candidate multiplier and score threshold for the rerank stage.
+    rerank_options: rerank::RerankOptions,
+}
+
+/// A retrieval result carrying both the original retrieval score and, when
+/// the rerank stage ran, the rerank score — so callers can log and compare.
+#[derive(Debug)]
+pub struct RetrievedChunk<'a> {
+    pub chunk: &'a DatasheetChunk,
+    /// Keyword retrieval score (term hits plus board boost).
+    pub score: f32,
+    /// Scorer output (0–10), when reranking ran.
+    pub rerank_score: Option<f32>,
 }
 
 impl HardwareRag {
-    /// Load datasheets from a directory. Expects .md, .txt, and optionally .pdf (with rag-pdf).
-    /// Filename (without extension) is used as board tag.
-    /// Supports `## Pin Aliases` section for explicit alias→pin mapping.
+    /// Load datasheets from a directory. Expects .md, .txt, .docx, and
+    /// optionally .pdf (with rag-pdf). Filename (without extension) is used
+    /// as board tag. Supports `## Pin Aliases` section for explicit
+    /// alias→pin mapping.
     pub fn load(workspace_dir: &Path, datasheet_dir: &str) -> anyhow::Result<Self> {
+        Ok(Self::load_with_report(workspace_dir, datasheet_dir)?.0)
+    }
+
+    /// Like [`Self::load`], but also returns a per-file ingest report with
+    /// pages processed, chunks produced, and extraction warnings.
+    pub fn load_with_report(
+        workspace_dir: &Path,
+        datasheet_dir: &str,
+    ) -> anyhow::Result<(Self, IngestReport)> {
+        Self::load_with_settings(workspace_dir, datasheet_dir, &ChunkingSettings::default())
+    }
+
+    /// Like [`Self::load_with_report`], but chunks with the given
+    /// `[rag.chunking]` settings instead of the defaults.
+    pub fn load_with_settings(
+        workspace_dir: &Path,
+        datasheet_dir: &str,
+        chunking: &ChunkingSettings,
+    ) -> anyhow::Result<(Self, IngestReport)> {
         let base = workspace_dir.join(datasheet_dir);
         if !base.exists() || !base.is_dir() {
-            return Ok(Self {
-                chunks: Vec::new(),
-                pin_aliases: HashMap::new(),
-            });
+            return Ok((
+                Self {
+                    chunks: Vec::new(),
+                    pin_aliases: HashMap::new(),
+                    embedder: None,
+                    chunk_embeddings: Vec::new(),
+                    reranker: None,
+                    rerank_options: rerank::RerankOptions::default(),
+                },
+                IngestReport::default(),
+            ));
         }
 
         let mut paths: Vec<std::path::PathBuf> = Vec::new();
-        collect_md_txt_paths(&base, &mut paths);
-        #[cfg(feature = "rag-pdf")]
-        collect_pdf_paths(&base, &mut paths);
+        collect_datasheet_paths(&base, &mut paths);
 
         let mut chunks = Vec::new();
         let mut pin_aliases: HashMap<String, PinAliases> = HashMap::new();
-        let max_tokens = 512;
+        let mut report = IngestReport::default();
 
         for path in paths {
-            let content = if path.extension().and_then(|e| e.to_str()) == Some("pdf") {
-                #[cfg(feature = "rag-pdf")]
-                {
-                    extract_pdf_text(&path).unwrap_or_default()
-                }
-                #[cfg(not(feature = "rag-pdf"))]
-                {
-                    String::new()
+            let (file_chunks, aliases, file_report) =
+                ingest_file(workspace_dir, &base, &path, chunking);
+
+            if let Some(board) = infer_board_from_path(&path, &base) {
+                if !aliases.is_empty() {
+                    pin_aliases.insert(board, aliases);
                 }
-            } else {
-                std::fs::read_to_string(&path).unwrap_or_default()
-            };
+            }
+            chunks.extend(file_chunks);
 
-            if content.trim().is_empty() {
-                continue;
+            for warning in &file_report.warnings {
+                tracing::warn!(source = %file_report.source, "RAG ingest: {warning}");
             }
+            tracing::debug!(
+                source = %file_report.source,
+                pages = file_report.pages,
+                chunks = file_report.chunks,
+                "RAG ingest"
+            );
+            report.files.push(file_report);
+        }
 
-            let board = infer_board_from_path(&path, &base);
-            let source = path
-                .strip_prefix(workspace_dir)
-                .unwrap_or(&path)
-                .display()
-                .to_string();
+        Ok((
+            Self {
+                chunks,
+                pin_aliases,
+                embedder: None,
+                chunk_embeddings: Vec::new(),
+                reranker: None,
+                rerank_options: rerank::RerankOptions::default(),
+            },
+            report,
+        ))
+    }
 
-            // Parse pin aliases from full content
-            let aliases = parse_pin_aliases(&content);
-            if let Some(ref b) = board {
-                if !aliases.is_empty() {
-                    pin_aliases.insert(b.clone(), aliases);
-                }
-            }
+    /// Attach an embedding backend for semantic retrieval. Noop embedders
+    /// (0 dimensions) are ignored, leaving keyword retrieval in place.
+    pub fn with_embedder(mut self, embedder: Arc<dyn EmbeddingProvider>) -> Self {
+        if embedder.dimensions() > 0 {
+            self.embedder = Some(embedder);
+        }
+        self
+    }
 
-            for chunk in chunker::chunk_markdown(&content, max_tokens) {
-                chunks.push(DatasheetChunk {
-                    board: board.clone(),
-                    source: source.clone(),
-                    content: chunk.content,
-                });
+    /// Attach a rerank scorer; `retrieve_reranked` then over-fetches
+    /// candidates and keeps the scorer's top picks.
+    pub fn with_reranker(
+        mut self,
+        scorer: Arc<dyn rerank::RerankScorer>,
+        options: rerank::RerankOptions,
+    ) -> Self {
+        self.reranker = Some(scorer);
+        self.rerank_options = options;
+        self
+    }
+
+    /// Embed all indexed chunks so `retrieve_semantic` can rank by cosine
+    /// similarity. Returns the number of chunks embedded.
+    pub async fn embed_index(&mut self) -> anyhow::Result<usize> {
+        let Some(ref embedder) = self.embedder else {
+            return Ok(0);
+        };
+        if self.chunks.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<&str> = self.chunks.iter().map(|c| c.content.as_str()).collect();
+        let embeddings = embedder.embed(&texts).await?;
+        if embeddings.len() != self.chunks.len() {
+            anyhow::bail!(
+                "Embedding backend returned {} vectors for {} chunks",
+                embeddings.len(),
+                self.chunks.len()
+            );
+        }
+        self.chunk_embeddings = embeddings;
+        Ok(self.chunk_embeddings.len())
+    }
+
+    /// Retrieve chunks by cosine similarity against the embedded index,
+    /// with the same board boost as keyword retrieval. Falls back to
+    /// `retrieve` when no embedder or index is available.
+    pub async fn retrieve_semantic(
+        &self,
+        query: &str,
+        boards: &[String],
+        limit: usize,
+    ) -> anyhow::Result<Vec<&DatasheetChunk>> {
+        let Some(ref embedder) = self.embedder else {
+            return Ok(self.retrieve(query, boards, limit));
+        };
+        if self.chunk_embeddings.len() != self.chunks.len() || limit == 0 {
+            return Ok(self.retrieve(query, boards, limit));
+        }
+
+        let query_embedding = embedder.embed_one(query).await?;
+        let mut scored: Vec<(&DatasheetChunk, f32)> = Vec::new();
+        for (chunk, embedding) in self.chunks.iter().zip(&self.chunk_embeddings) {
+            let mut score = vector::cosine_similarity(&query_embedding, embedding);
+            if score <= 0.0 {
+                continue;
             }
+            let board_match = chunk.board.as_ref().map_or(false, |b| boards.contains(b));
+            if board_match {
+                score += 0.2;
+            }
+            scored.push((chunk, score));
         }
 
-        Ok(Self {
-            chunks,
-            pin_aliases,
-        })
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored.into_iter().map(|(c, _)| c).collect())
     }
 
     /// Get pin aliases for a board (e.g. "red_led" -> 13).
@@ -250,6 +779,105 @@ impl HardwareRag {
     /// Retrieve chunks relevant to the query and boards.
     /// Uses keyword matching and board filter. Pin-alias context is built separately via `pin_alias_context`.
     pub fn retrieve(&self, query: &str, boards: &[String], limit: usize) -> Vec<&DatasheetChunk> {
+        self.retrieve_scored(query, boards, limit)
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Like [`Self::retrieve`], but runs the rerank stage when a scorer is
+    /// attached: fetch `limit × candidate_multiplier` candidates, score them
+    /// against the query, drop those below the score threshold, and keep the
+    /// top `limit`. Without a scorer this returns the base results unchanged
+    /// (rerank score `None`), and scorer failures fall back to the base
+    /// order with a warning rather than erroring the agent turn.
+    pub async fn retrieve_reranked(
+        &self,
+        query: &str,
+        boards: &[String],
+        limit: usize,
+    ) -> Vec<RetrievedChunk<'_>> {
+        fn base(results: Vec<(&DatasheetChunk, f32)>, limit: usize) -> Vec<RetrievedChunk<'_>> {
+            results
+                .into_iter()
+                .take(limit)
+                .map(|(chunk, score)| RetrievedChunk {
+                    chunk,
+                    score,
+                    rerank_score: None,
+                })
+                .collect()
+        }
+
+        let Some(ref scorer) = self.reranker else {
+            return base(self.retrieve_scored(query, boards, limit), limit);
+        };
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        let candidate_limit = limit.saturating_mul(self.rerank_options.candidate_multiplier.max(1));
+        let candidates = self.retrieve_scored(query, boards, candidate_limit);
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let texts: Vec<&str> = candidates.iter().map(|(c, _)| c.content.as_str()).collect();
+        let scores = match scorer.score(query, &texts).await {
+            Ok(scores) if scores.len() == candidates.len() => scores,
+            Ok(scores) => {
+                tracing::warn!(
+                    scorer = scorer.name(),
+                    expected = candidates.len(),
+                    got = scores.len(),
+                    "RAG rerank returned wrong score count; keeping retrieval order"
+                );
+                return base(candidates, limit);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    scorer = scorer.name(),
+                    "RAG rerank failed; keeping retrieval order: {e}"
+                );
+                return base(candidates, limit);
+            }
+        };
+
+        let candidate_count = candidates.len();
+        let mut reranked: Vec<RetrievedChunk<'_>> = candidates
+            .into_iter()
+            .zip(scores)
+            .filter(|(_, rerank_score)| *rerank_score >= self.rerank_options.score_threshold)
+            .map(|((chunk, score), rerank_score)| RetrievedChunk {
+                chunk,
+                score,
+                rerank_score: Some(rerank_score),
+            })
+            .collect();
+        reranked.sort_by(|a, b| {
+            b.rerank_score
+                .partial_cmp(&a.rerank_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        reranked.truncate(limit);
+
+        tracing::debug!(
+            scorer = scorer.name(),
+            candidates = candidate_count,
+            kept = reranked.len(),
+            "RAG rerank"
+        );
+        reranked
+    }
+
+    /// Keyword retrieval returning `(chunk, score)` pairs, already sorted
+    /// and truncated to `limit`. Shared by `retrieve` and the rerank stage.
+    fn retrieve_scored(
+        &self,
+        query: &str,
+        boards: &[String],
+        limit: usize,
+    ) -> Vec<(&DatasheetChunk, f32)> {
         if self.chunks.is_empty() || limit == 0 {
             return Vec::new();
         }
@@ -282,7 +910,7 @@ impl HardwareRag {
 
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         scored.truncate(limit);
-        scored.into_iter().map(|(c, _)| c).collect()
+        scored
     }
 
     /// Number of indexed chunks.
@@ -296,6 +924,44 @@ impl HardwareRag {
     }
 }
 
+/// Rebuild the datasheet index with the given chunking settings and persist
+/// the outcome — including the chunking fingerprint — to the watcher status
+/// file. Backs `zeroclaw rag reindex`.
+pub fn run_reindex(
+    workspace_dir: &Path,
+    datasheet_dir: &str,
+    chunking: &ChunkingSettings,
+) -> anyhow::Result<()> {
+    let (rag, report) = HardwareRag::load_with_settings(workspace_dir, datasheet_dir, chunking)?;
+
+    for file in &report.files {
+        println!("  {}: {} chunks", file.source, file.chunks);
+        for warning in &file.warnings {
+            println!("    warning: {warning}");
+        }
+    }
+
+    let status = watcher::RagWatchStatus {
+        last_sync: Some(chrono::Utc::now().to_rfc3339()),
+        pending: 0,
+        files: report.files.len(),
+        chunks: rag.len(),
+        last_errors: Vec::new(),
+        chunking_fingerprint: Some(chunking.fingerprint()),
+    };
+    watcher::write_status_file(workspace_dir, &status)?;
+
+    println!(
+        "Reindexed {} file(s) into {} chunk(s) (strategy: {}, max_tokens: {}, overlap: {})",
+        report.files.len(),
+        rag.len(),
+        chunking.strategy,
+        chunking.max_tokens,
+        chunking.overlap_tokens
+    );
+    Ok(())
+}
+
 /// Infer board tag from file path. `nucleo-f401re.md` → Some("nucleo-f401re").
 fn infer_board_from_path(path: &Path, base: &Path) -> Option<String> {
     let rel = path.strip_prefix(base).ok()?;
@@ -392,4 +1058,463 @@ Pin 13: LED
         let rag = HardwareRag::load(tmp.path(), "empty_ds").unwrap();
         assert!(rag.is_empty());
     }
+
+    // ── PDF / DOCX ingestion ─────────────────────────────────────
+
+    fn write_min_docx(path: &Path, body_xml: &str) {
+        use std::io::Write as _;
+        let xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+             <w:body>{body_xml}</w:body></w:document>"
+        );
+        let file = std::fs::File::create(path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        zip.start_file(
+            "word/document.xml",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+        zip.write_all(xml.as_bytes()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn docx_ingest_preserves_pages_and_headings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        write_min_docx(
+            &base.join("doc-board.docx"),
+            "<w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr><w:r><w:t>GPIO</w:t></w:r></w:p>\
+             <w:p><w:r><w:t>Pin 13 drives the LED.</w:t></w:r></w:p>\
+             <w:p><w:r><w:br w:type=\"page\"/><w:t>UART TX is pin 2.</w:t></w:r></w:p>",
+        );
+
+        let (rag, report) = HardwareRag::load_with_report(tmp.path(), "datasheets").unwrap();
+        assert!(!rag.is_empty());
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.files[0].pages, 2);
+        assert!(report.files[0].chunks >= 2);
+        assert!(report.files[0].warnings.is_empty());
+
+        let led = rag.retrieve("led", &[], 1);
+        assert_eq!(led[0].page, Some(1));
+        assert_eq!(led[0].heading_path.as_deref(), Some("GPIO"));
+        let uart = rag.retrieve("uart", &[], 1);
+        assert_eq!(uart[0].page, Some(2));
+    }
+
+    #[test]
+    fn docx_heading_path_nests_levels() {
+        let (sections, pages) = parse_docx_xml(
+            "<w:document><w:body>\
+             <w:p><w:pPr><w:pStyle w:val=\"Heading1\"/></w:pPr><w:r><w:t>Peripherals</w:t></w:r></w:p>\
+             <w:p><w:pPr><w:pStyle w:val=\"Heading2\"/></w:pPr><w:r><w:t>SPI &amp; I2C</w:t></w:r></w:p>\
+             <w:p><w:r><w:t>SCK is pin 18.</w:t></w:r></w:p>\
+             </w:body></w:document>",
+        );
+        assert_eq!(pages, 1);
+        let body = sections.iter().find(|s| s.text.contains("SCK")).unwrap();
+        assert_eq!(
+            body.heading_path.as_deref(),
+            Some("Peripherals > SPI & I2C")
+        );
+    }
+
+    #[test]
+    fn docx_unreadable_archive_reports_warning() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("broken.docx"), b"not a zip").unwrap();
+
+        let (rag, report) = HardwareRag::load_with_report(tmp.path(), "datasheets").unwrap();
+        assert!(rag.is_empty());
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.files[0].chunks, 0);
+        assert!(!report.files[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn markdown_chunks_carry_heading_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(
+            base.join("test-board.md"),
+            "# Test Board\n## GPIO\nPin 13: LED\n",
+        )
+        .unwrap();
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets").unwrap();
+        let chunks = rag.retrieve("led", &[], 5);
+        assert!(!chunks.is_empty());
+        assert_eq!(chunks[0].heading_path.as_deref(), Some("GPIO"));
+        assert_eq!(chunks[0].page, None);
+    }
+
+    #[cfg(feature = "rag-pdf")]
+    fn write_min_pdf(path: &Path, page_text: Option<&str>) {
+        use std::fmt::Write as _;
+        // Hand-assembled single-page PDF with a computed xref table.
+        let content = match page_text {
+            Some(t) => format!("BT /F1 12 Tf 72 720 Td ({t}) Tj ET"),
+            None => String::new(),
+        };
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+             /Resources << /Font << /F1 5 0 R >> >> >>"
+                .to_string(),
+            format!(
+                "<< /Length {} >>\nstream\n{content}\nendstream",
+                content.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        ];
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, obj) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            let _ = write!(pdf, "{} 0 obj\n{obj}\nendobj\n", i + 1);
+        }
+        let xref_at = pdf.len();
+        let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for off in offsets {
+            let _ = write!(pdf, "{off:010} 00000 n \n");
+        }
+        let _ = write!(
+            pdf,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_at}\n%%EOF\n",
+            objects.len() + 1
+        );
+        std::fs::write(path, pdf).unwrap();
+    }
+
+    #[cfg(feature = "rag-pdf")]
+    #[test]
+    fn pdf_ingest_extracts_text_with_page_number() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        write_min_pdf(&base.join("pdf-board.pdf"), Some("Pin 13 drives the LED"));
+
+        let (rag, report) = HardwareRag::load_with_report(tmp.path(), "datasheets").unwrap();
+        assert!(report.files[0].warnings.is_empty());
+        assert!(report.files[0].pages >= 1);
+        let chunks = rag.retrieve("led", &[], 1);
+        assert!(!chunks.is_empty());
+        assert_eq!(chunks[0].page, Some(1));
+    }
+
+    #[cfg(feature = "rag-pdf")]
+    #[test]
+    fn pdf_without_text_layer_reports_unsupported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        write_min_pdf(&base.join("scan.pdf"), None);
+
+        let (rag, report) = HardwareRag::load_with_report(tmp.path(), "datasheets").unwrap();
+        assert!(rag.is_empty());
+        assert_eq!(report.files[0].chunks, 0);
+        assert!(!report.files[0].warnings.is_empty());
+    }
+
+    // ── Semantic retrieval via the embedding trait ───────────────
+
+    /// Deterministic 2-d embedder: axis 0 for LED content, axis 1 otherwise.
+    struct KeywordEmbedding;
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for KeywordEmbedding {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    if t.to_lowercase().contains("led") {
+                        vec![1.0, 0.0]
+                    } else {
+                        vec![0.0, 1.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    fn two_board_rag(tmp: &tempfile::TempDir) -> HardwareRag {
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("led-board.md"), "Pin 13 drives the LED.").unwrap();
+        std::fs::write(base.join("uart-board.md"), "Pin 2 is UART TX.").unwrap();
+        HardwareRag::load(tmp.path(), "datasheets").unwrap()
+    }
+
+    #[tokio::test]
+    async fn retrieve_semantic_ranks_by_similarity() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut rag = two_board_rag(&tmp).with_embedder(Arc::new(KeywordEmbedding));
+
+        let embedded = rag.embed_index().await.unwrap();
+        assert_eq!(embedded, 2);
+
+        let chunks = rag
+            .retrieve_semantic("blink the led", &[], 1)
+            .await
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].content.contains("LED"));
+    }
+
+    #[tokio::test]
+    async fn retrieve_semantic_without_embedder_falls_back_to_keyword() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rag = two_board_rag(&tmp);
+
+        let chunks = rag.retrieve_semantic("led", &[], 5).await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].content.contains("LED"));
+    }
+
+    #[tokio::test]
+    async fn with_embedder_ignores_noop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut rag =
+            two_board_rag(&tmp).with_embedder(Arc::new(crate::memory::embeddings::NoopEmbedding));
+        assert_eq!(rag.embed_index().await.unwrap(), 0);
+    }
+
+    // ── Rerank stage ─────────────────────────────────────────────
+
+    /// Deterministic scorer: 9.0 for chunks mentioning "status", 1.0 otherwise.
+    struct StatusScorer;
+
+    #[async_trait::async_trait]
+    impl rerank::RerankScorer for StatusScorer {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn score(&self, _query: &str, candidates: &[&str]) -> anyhow::Result<Vec<f32>> {
+            Ok(candidates
+                .iter()
+                .map(|c| if c.contains("status") { 9.0 } else { 1.0 })
+                .collect())
+        }
+    }
+
+    /// Scorer that always fails, to exercise the fallback path.
+    struct FailingScorer;
+
+    #[async_trait::async_trait]
+    impl rerank::RerankScorer for FailingScorer {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn score(&self, _query: &str, _candidates: &[&str]) -> anyhow::Result<Vec<f32>> {
+            anyhow::bail!("scorer offline")
+        }
+    }
+
+    /// Two chunks where keyword scoring prefers the "reset" chunk (3 term
+    /// hits) over the "status" chunk (2 term hits) for the query below.
+    fn rerank_rag(tmp: &tempfile::TempDir) -> HardwareRag {
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("a-board.md"), "The status led lives on pin 13.").unwrap();
+        std::fs::write(base.join("b-board.md"), "Reset pin and led strap notes.").unwrap();
+        HardwareRag::load(tmp.path(), "datasheets").unwrap()
+    }
+
+    const RERANK_QUERY: &str = "led pin reset";
+
+    #[tokio::test]
+    async fn rerank_reorders_candidates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rag = rerank_rag(&tmp);
+
+        // Keyword order puts the "reset" chunk first.
+        assert!(rag.retrieve(RERANK_QUERY, &[], 2)[0]
+            .content
+            .contains("Reset"));
+
+        let rag = rerank_rag(&tmp)
+            .with_reranker(Arc::new(StatusScorer), rerank::RerankOptions::default());
+        let results = rag.retrieve_reranked(RERANK_QUERY, &[], 1).await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].chunk.content.contains("status"));
+        assert_eq!(results[0].rerank_score, Some(9.0));
+        // Original keyword score is preserved for observability.
+        assert_eq!(results[0].score, 2.0);
+    }
+
+    #[tokio::test]
+    async fn rerank_threshold_drops_low_scoring_candidates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rag = rerank_rag(&tmp).with_reranker(
+            Arc::new(StatusScorer),
+            rerank::RerankOptions {
+                candidate_multiplier: 3,
+                score_threshold: 5.0,
+            },
+        );
+
+        let results = rag.retrieve_reranked(RERANK_QUERY, &[], 5).await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].chunk.content.contains("status"));
+    }
+
+    #[tokio::test]
+    async fn rerank_without_scorer_keeps_base_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rag = rerank_rag(&tmp);
+
+        let results = rag.retrieve_reranked(RERANK_QUERY, &[], 2).await;
+        let base = rag.retrieve(RERANK_QUERY, &[], 2);
+        assert_eq!(results.len(), base.len());
+        for (got, want) in results.iter().zip(&base) {
+            assert_eq!(got.chunk.content, want.content);
+            assert_eq!(got.rerank_score, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn rerank_scorer_failure_falls_back_to_base_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rag = rerank_rag(&tmp)
+            .with_reranker(Arc::new(FailingScorer), rerank::RerankOptions::default());
+
+        let results = rag.retrieve_reranked(RERANK_QUERY, &[], 2).await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].chunk.content.contains("Reset"));
+        assert!(results.iter().all(|r| r.rerank_score.is_none()));
+    }
+
+    // ── Chunking settings ────────────────────────────────────────
+
+    #[test]
+    fn chunking_fingerprint_changes_with_settings() {
+        let default = ChunkingSettings::default();
+        let changed = ChunkingSettings {
+            strategy: "sentence".to_string(),
+            ..ChunkingSettings::default()
+        };
+        assert_ne!(default.fingerprint(), changed.fingerprint());
+        assert_eq!(
+            default.fingerprint(),
+            ChunkingSettings::default().fingerprint()
+        );
+    }
+
+    #[test]
+    fn run_reindex_writes_status_with_fingerprint() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("board.md"), "Pin 13 drives the LED.").unwrap();
+
+        let settings = ChunkingSettings::default();
+        run_reindex(tmp.path(), "datasheets", &settings).unwrap();
+
+        let status = watcher::read_status(tmp.path()).expect("status file should exist");
+        assert_eq!(status.files, 1);
+        assert!(status.chunks >= 1);
+        assert_eq!(
+            status.chunking_fingerprint.as_deref(),
+            Some(settings.fingerprint().as_str())
+        );
+    }
+
+    // ── Citation expansion ───────────────────────────────────────
+
+    fn citation_sources() -> Vec<CitationSource> {
+        vec![
+            CitationSource {
+                path: "board.md".into(),
+                detail: "nucleo, p. 3, GPIO".into(),
+            },
+            CitationSource {
+                path: "uart.pdf".into(),
+                detail: "generic, p. 12".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn expand_citations_appends_sources_footer() {
+        let out = expand_citation_markers(
+            "Pin 13 drives the LED [1]. UART is on pin 2 [2].",
+            &citation_sources(),
+            None,
+        );
+        assert!(out.starts_with("Pin 13 drives the LED [1]."));
+        assert!(out.ends_with(
+            "Sources:\n[1] board.md (nucleo, p. 3, GPIO)\n[2] uart.pdf (generic, p. 12)"
+        ));
+    }
+
+    #[test]
+    fn expand_citations_drops_out_of_range_markers() {
+        let out = expand_citation_markers("See [1] and [7].", &citation_sources(), None);
+        assert!(out.starts_with("See [1] and."));
+        assert!(!out.contains("[7]"));
+        assert!(out.contains("[1] board.md"));
+        assert!(!out.contains("uart.pdf"));
+    }
+
+    #[test]
+    fn expand_citations_without_markers_is_unchanged() {
+        let text = "No citations in this answer.";
+        assert_eq!(
+            expand_citation_markers(text, &citation_sources(), None),
+            text
+        );
+    }
+
+    #[test]
+    fn expand_citations_keeps_markdown_links_intact() {
+        let text = "See [docs](https://example.com) and [1].";
+        let out = expand_citation_markers(text, &citation_sources(), None);
+        assert!(out.contains("[docs](https://example.com)"));
+        assert!(out.contains("[1] board.md"));
+    }
+
+    #[test]
+    fn expand_citations_links_paths_with_base() {
+        let out =
+            expand_citation_markers("LED [1].", &citation_sources(), Some("http://host/files/"));
+        assert!(out.contains("[1] [board.md](http://host/files/board.md) (nucleo, p. 3, GPIO)"));
+    }
+
+    #[test]
+    fn citation_detail_includes_page_and_heading() {
+        let chunk = DatasheetChunk {
+            board: Some("nucleo".into()),
+            source: "board.md".into(),
+            content: String::new(),
+            page: Some(3),
+            heading_path: Some("GPIO".into()),
+        };
+        assert_eq!(chunk.citation_detail(), "nucleo, p. 3, GPIO");
+
+        let flat = DatasheetChunk {
+            board: None,
+            source: "notes.txt".into(),
+            content: String::new(),
+            page: None,
+            heading_path: None,
+        };
+        assert_eq!(flat.citation_detail(), "generic");
+    }
 }
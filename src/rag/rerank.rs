@@ -0,0 +1,144 @@
+//! Rerank stage for datasheet retrieval (`[rag.rerank]` config).
+//!
+//! Top-k retrieval returns plausible-looking chunks that are not always the
+//! best ones. When enabled, retrieval over-fetches a candidate set, a
+//! [`RerankScorer`] scores each candidate against the query, and only the
+//! top results survive into the prompt. The built-in backend scores with a
+//! cheap LLM call via the configured provider; the trait leaves room for a
+//! local cross-encoder backend.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::providers::Provider;
+
+/// Scores candidate chunks against a query. Higher is more relevant.
+#[async_trait]
+pub trait RerankScorer: Send + Sync {
+    /// Backend name for logging (e.g. "llm").
+    fn name(&self) -> &str;
+
+    /// Return one relevance score (0–10) per candidate, in order.
+    async fn score(&self, query: &str, candidates: &[&str]) -> anyhow::Result<Vec<f32>>;
+}
+
+/// Rerank settings as plain values (mirrors `[rag.rerank]`). The rag module
+/// is re-exported into the binary crate, so it takes plain types rather
+/// than the crate-local config structs.
+#[derive(Debug, Clone)]
+pub struct RerankOptions {
+    /// Candidates fetched per final result slot (limit × multiplier).
+    pub candidate_multiplier: usize,
+    /// Candidates scoring below this are dropped, even if that leaves
+    /// fewer than the requested number of results.
+    pub score_threshold: f32,
+}
+
+impl Default for RerankOptions {
+    fn default() -> Self {
+        Self {
+            candidate_multiplier: 3,
+            score_threshold: 0.0,
+        }
+    }
+}
+
+const SCORING_SYSTEM_PROMPT: &str = "You score document chunks for relevance to a query. \
+Reply with a JSON array of numbers from 0 (irrelevant) to 10 (directly answers the query), \
+one per chunk, in order. Reply with the array only, no other text.";
+
+/// Truncate each candidate to this many characters in the scoring prompt.
+const MAX_CANDIDATE_CHARS: usize = 500;
+
+/// LLM-backed scorer: one cheap scoring call per candidate batch using the
+/// configured classifier/fast model.
+pub struct LlmRerankScorer {
+    provider: Arc<dyn Provider>,
+    model: String,
+}
+
+impl LlmRerankScorer {
+    pub fn new(provider: Arc<dyn Provider>, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl RerankScorer for LlmRerankScorer {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    async fn score(&self, query: &str, candidates: &[&str]) -> anyhow::Result<Vec<f32>> {
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut prompt = format!("Query: {query}\n\nChunks:\n");
+        for (i, candidate) in candidates.iter().enumerate() {
+            let excerpt: String = candidate.chars().take(MAX_CANDIDATE_CHARS).collect();
+            let _ = writeln!(prompt, "[{i}] {excerpt}");
+        }
+
+        let response = self
+            .provider
+            .chat_with_system(Some(SCORING_SYSTEM_PROMPT), &prompt, &self.model, 0.0)
+            .await?;
+        parse_scores(&response, candidates.len())
+    }
+}
+
+/// Extract a JSON number array from a model reply, tolerating code fences
+/// and surrounding prose.
+fn parse_scores(response: &str, expected: usize) -> anyhow::Result<Vec<f32>> {
+    let start = response
+        .find('[')
+        .ok_or_else(|| anyhow::anyhow!("no score array in scorer reply"))?;
+    let end = response[start..]
+        .find(']')
+        .map(|i| start + i)
+        .ok_or_else(|| anyhow::anyhow!("unterminated score array in scorer reply"))?;
+
+    let scores: Vec<f32> = serde_json::from_str(&response[start..=end])
+        .map_err(|e| anyhow::anyhow!("malformed score array in scorer reply: {e}"))?;
+    if scores.len() != expected {
+        anyhow::bail!(
+            "scorer returned {} scores for {} candidates",
+            scores.len(),
+            expected
+        );
+    }
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scores_accepts_plain_array() {
+        assert_eq!(parse_scores("[7, 2.5, 0]", 3).unwrap(), vec![7.0, 2.5, 0.0]);
+    }
+
+    #[test]
+    fn parse_scores_tolerates_fences_and_prose() {
+        let reply = "Here are the scores:\n```json\n[9, 1]\n```";
+        assert_eq!(parse_scores(reply, 2).unwrap(), vec![9.0, 1.0]);
+    }
+
+    #[test]
+    fn parse_scores_rejects_wrong_count() {
+        let err = parse_scores("[1, 2]", 3).unwrap_err();
+        assert!(err.to_string().contains("2 scores for 3 candidates"));
+    }
+
+    #[test]
+    fn parse_scores_rejects_missing_array() {
+        assert!(parse_scores("no numbers here", 1).is_err());
+    }
+}
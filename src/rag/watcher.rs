@@ -0,0 +1,435 @@
+//! Watch-folder auto-ingestion for the datasheet RAG index.
+//!
+//! A daemon task polls the configured workspace directories for new, changed,
+//! and deleted files and feeds them through the same extraction pipeline as
+//! [`super::HardwareRag::load`]. Change detection is a debounced mtime+hash
+//! poll (no inotify dependency), so re-ingesting an unchanged file is a no-op
+//! and half-written files are left alone until they settle. Progress and
+//! errors land in `runtime_trace`; `zeroclaw doctor` reads the persisted
+//! status file for last-sync time and pending backlog.
+
+use super::{ChunkingSettings, DatasheetChunk};
+use crate::config::RagWatchConfig;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Persisted watcher status, read by `zeroclaw doctor`.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RagWatchStatus {
+    /// RFC3339 time of the last completed scan.
+    pub last_sync: Option<String>,
+    /// Files detected but still inside the debounce window.
+    pub pending: usize,
+    /// Files currently in the index.
+    pub files: usize,
+    /// Chunks currently in the index.
+    pub chunks: usize,
+    /// Errors and extraction warnings from the most recent scan.
+    pub last_errors: Vec<String>,
+    /// Fingerprint of the `[rag.chunking]` settings the index was built
+    /// with; `zeroclaw doctor` flags a mismatch with the current config.
+    #[serde(default)]
+    pub chunking_fingerprint: Option<String>,
+}
+
+/// Status file path: `{workspace}/rag_watch_state.json`.
+pub fn status_file_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("rag_watch_state.json")
+}
+
+/// Read the persisted watcher status, if any.
+pub fn read_status(workspace_dir: &Path) -> Option<RagWatchStatus> {
+    let text = std::fs::read_to_string(status_file_path(workspace_dir)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Persist a watcher status. Shared by the watcher and `zeroclaw rag reindex`.
+pub fn write_status_file(workspace_dir: &Path, status: &RagWatchStatus) -> anyhow::Result<()> {
+    let json = serde_json::to_vec_pretty(status)?;
+    std::fs::write(status_file_path(workspace_dir), json)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+struct FileEntry {
+    mtime_unix: i64,
+    hash: String,
+    chunks: Vec<DatasheetChunk>,
+}
+
+/// Outcome of one scan pass.
+#[derive(Debug, Default)]
+pub struct ScanOutcome {
+    /// Files ingested (new or content changed).
+    pub ingested: usize,
+    /// Files removed from the index because they vanished on disk.
+    pub removed: usize,
+    /// Files left alone (same mtime, or same hash after an mtime touch).
+    pub unchanged: usize,
+    /// Files skipped by ignore globs or the size limit.
+    pub skipped: usize,
+    /// Files still inside the debounce window; retried next pass.
+    pub pending: usize,
+    /// Errors and extraction warnings from this pass.
+    pub errors: Vec<String>,
+}
+
+/// Incremental RAG index over the watched directories.
+pub struct RagWatcher {
+    workspace_dir: PathBuf,
+    dirs: Vec<String>,
+    ignore_globs: Vec<String>,
+    max_bytes: u64,
+    debounce_secs: u64,
+    chunking: ChunkingSettings,
+    /// Workspace-relative path → indexed entry.
+    files: HashMap<String, FileEntry>,
+}
+
+impl RagWatcher {
+    pub fn new(workspace_dir: &Path, config: &RagWatchConfig, chunking: ChunkingSettings) -> Self {
+        Self {
+            workspace_dir: workspace_dir.to_path_buf(),
+            dirs: config.dirs.clone(),
+            ignore_globs: config.ignore_globs.clone(),
+            max_bytes: config.max_file_kb.saturating_mul(1024),
+            debounce_secs: config.debounce_secs,
+            chunking,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Number of files currently indexed.
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Number of chunks currently indexed.
+    pub fn chunk_count(&self) -> usize {
+        self.files.values().map(|e| e.chunks.len()).sum()
+    }
+
+    /// All indexed chunks.
+    pub fn chunks(&self) -> impl Iterator<Item = &DatasheetChunk> {
+        self.files.values().flat_map(|e| e.chunks.iter())
+    }
+
+    fn is_ignored(&self, rel: &str) -> bool {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        self.ignore_globs.iter().any(|glob| {
+            crate::memory::cli::glob_match(glob, rel) || crate::memory::cli::glob_match(glob, name)
+        })
+    }
+
+    /// One scan pass: ingest new/changed files, drop deleted ones.
+    pub fn scan(&mut self) -> ScanOutcome {
+        let mut outcome = ScanOutcome::default();
+        let now_unix = chrono::Utc::now().timestamp();
+
+        // (rel path, absolute path, mtime, size, datasheet root for board inference)
+        let mut seen: Vec<(String, PathBuf, i64, u64, PathBuf)> = Vec::new();
+        for dir in &self.dirs {
+            let base = self.workspace_dir.join(dir.trim());
+            if !base.is_dir() {
+                continue;
+            }
+            let mut paths: Vec<PathBuf> = Vec::new();
+            super::collect_datasheet_paths(&base, &mut paths);
+            for path in paths {
+                let rel = path
+                    .strip_prefix(&self.workspace_dir)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string();
+                let Ok(meta) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let mtime = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX));
+                seen.push((rel, path, mtime, meta.len(), base.clone()));
+            }
+        }
+
+        // Deletions: indexed files that no longer exist on disk.
+        let alive: std::collections::HashSet<&String> = seen.iter().map(|(rel, ..)| rel).collect();
+        let removed: Vec<String> = self
+            .files
+            .keys()
+            .filter(|rel| !alive.contains(rel))
+            .cloned()
+            .collect();
+        for rel in &removed {
+            self.files.remove(rel);
+            crate::observability::runtime_trace::record_event(
+                "rag_ingest",
+                None,
+                None,
+                None,
+                None,
+                Some(true),
+                Some(rel),
+                serde_json::json!({ "action": "removed" }),
+            );
+        }
+        outcome.removed = removed.len();
+
+        for (rel, path, mtime, size, base) in seen {
+            if self.is_ignored(&rel) {
+                outcome.skipped += 1;
+                continue;
+            }
+            if size > self.max_bytes {
+                outcome.skipped += 1;
+                tracing::debug!(file = %rel, size, "RAG watch: file over size limit, skipped");
+                continue;
+            }
+            if self
+                .files
+                .get(&rel)
+                .is_some_and(|entry| entry.mtime_unix == mtime)
+            {
+                outcome.unchanged += 1;
+                continue;
+            }
+            if self.debounce_secs > 0
+                && now_unix - mtime < i64::try_from(self.debounce_secs).unwrap_or(i64::MAX)
+            {
+                outcome.pending += 1;
+                continue;
+            }
+            let Ok(bytes) = std::fs::read(&path) else {
+                outcome.errors.push(format!("{rel}: unreadable"));
+                continue;
+            };
+            let hash = format!("{:x}", Sha256::digest(&bytes));
+            if let Some(entry) = self.files.get_mut(&rel) {
+                if entry.hash == hash {
+                    // mtime touched but content identical — still a no-op.
+                    entry.mtime_unix = mtime;
+                    outcome.unchanged += 1;
+                    continue;
+                }
+            }
+
+            let (chunks, _aliases, report) =
+                super::ingest_file(&self.workspace_dir, &base, &path, &self.chunking);
+            for warning in &report.warnings {
+                outcome.errors.push(format!("{rel}: {warning}"));
+            }
+            crate::observability::runtime_trace::record_event(
+                "rag_ingest",
+                None,
+                None,
+                None,
+                None,
+                Some(report.warnings.is_empty()),
+                Some(&rel),
+                serde_json::json!({
+                    "action": "ingested",
+                    "pages": report.pages,
+                    "chunks": report.chunks,
+                    "warnings": report.warnings,
+                }),
+            );
+            self.files.insert(
+                rel,
+                FileEntry {
+                    mtime_unix: mtime,
+                    hash,
+                    chunks,
+                },
+            );
+            outcome.ingested += 1;
+        }
+
+        outcome
+    }
+
+    /// Persist status for `zeroclaw doctor`.
+    pub fn write_status(&self, outcome: &ScanOutcome) {
+        let status = RagWatchStatus {
+            last_sync: Some(chrono::Utc::now().to_rfc3339()),
+            pending: outcome.pending,
+            files: self.file_count(),
+            chunks: self.chunk_count(),
+            last_errors: outcome.errors.clone(),
+            chunking_fingerprint: Some(self.chunking.fingerprint()),
+        };
+        let _ = write_status_file(&self.workspace_dir, &status);
+    }
+}
+
+/// Daemon entry point: scan the watched directories until aborted.
+///
+/// Takes plain values rather than a `Config` because the daemon module is
+/// compiled into both the lib and bin crates while this module lives in the
+/// lib only.
+pub async fn run(
+    workspace_dir: PathBuf,
+    dirs: Vec<String>,
+    ignore_globs: Vec<String>,
+    max_file_kb: u64,
+    debounce_secs: u64,
+    poll_secs: u64,
+    chunking: ChunkingSettings,
+) -> anyhow::Result<()> {
+    let settings = RagWatchConfig {
+        enabled: true,
+        dirs,
+        poll_secs,
+        debounce_secs,
+        ignore_globs,
+        max_file_kb,
+    };
+    let mut watcher = RagWatcher::new(&workspace_dir, &settings, chunking);
+    let poll = tokio::time::Duration::from_secs(settings.poll_secs.max(1));
+    tracing::info!(dirs = ?settings.dirs, "RAG watcher started");
+
+    loop {
+        let outcome = watcher.scan();
+        if outcome.ingested > 0 || outcome.removed > 0 || !outcome.errors.is_empty() {
+            tracing::info!(
+                ingested = outcome.ingested,
+                removed = outcome.removed,
+                pending = outcome.pending,
+                files = watcher.file_count(),
+                chunks = watcher.chunk_count(),
+                "RAG watch scan"
+            );
+        }
+        for err in &outcome.errors {
+            tracing::warn!("RAG watch: {err}");
+        }
+        watcher.write_status(&outcome);
+        tokio::time::sleep(poll).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watch_config() -> RagWatchConfig {
+        RagWatchConfig {
+            enabled: true,
+            dirs: vec!["knowledge".into()],
+            poll_secs: 1,
+            debounce_secs: 0,
+            ignore_globs: Vec::new(),
+            max_file_kb: 1024,
+        }
+    }
+
+    fn knowledge_dir(tmp: &tempfile::TempDir) -> PathBuf {
+        let dir = tmp.path().join("knowledge");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn scan_ingests_modifies_and_removes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let knowledge = knowledge_dir(&tmp);
+        std::fs::write(knowledge.join("board.md"), "Pin 13 drives the LED.").unwrap();
+
+        let mut watcher = RagWatcher::new(tmp.path(), &watch_config(), ChunkingSettings::default());
+        let first = watcher.scan();
+        assert_eq!(first.ingested, 1);
+        assert_eq!(watcher.file_count(), 1);
+        assert!(watcher.chunk_count() >= 1);
+
+        // Unchanged mtime: second scan is a no-op.
+        let second = watcher.scan();
+        assert_eq!(second.ingested, 0);
+        assert_eq!(second.unchanged, 1);
+
+        // mtime touched but content identical: hash check makes it a no-op.
+        watcher
+            .files
+            .get_mut("knowledge/board.md")
+            .unwrap()
+            .mtime_unix -= 10;
+        let touched = watcher.scan();
+        assert_eq!(touched.ingested, 0);
+        assert_eq!(touched.unchanged, 1);
+
+        // Content changed: re-ingested, old chunks replaced.
+        std::fs::write(knowledge.join("board.md"), "Pin 2 is UART TX.").unwrap();
+        watcher
+            .files
+            .get_mut("knowledge/board.md")
+            .unwrap()
+            .mtime_unix -= 10;
+        let modified = watcher.scan();
+        assert_eq!(modified.ingested, 1);
+        assert!(watcher.chunks().any(|c| c.content.contains("UART")));
+        assert!(!watcher.chunks().any(|c| c.content.contains("LED")));
+
+        // Deleted: index entry removed.
+        std::fs::remove_file(knowledge.join("board.md")).unwrap();
+        let deleted = watcher.scan();
+        assert_eq!(deleted.removed, 1);
+        assert_eq!(watcher.chunk_count(), 0);
+    }
+
+    #[test]
+    fn ignore_globs_and_size_limit_skip_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let knowledge = knowledge_dir(&tmp);
+        std::fs::write(knowledge.join("notes.txt"), "scratch notes").unwrap();
+        std::fs::write(knowledge.join("big.md"), "x".repeat(2048)).unwrap();
+        std::fs::write(knowledge.join("ok.md"), "Pin 13 drives the LED.").unwrap();
+
+        let mut config = watch_config();
+        config.ignore_globs = vec!["*.txt".into()];
+        config.max_file_kb = 1;
+
+        let mut watcher = RagWatcher::new(tmp.path(), &config, ChunkingSettings::default());
+        let outcome = watcher.scan();
+        assert_eq!(outcome.ingested, 1);
+        assert_eq!(outcome.skipped, 2);
+        assert_eq!(watcher.file_count(), 1);
+    }
+
+    #[test]
+    fn debounce_defers_recently_written_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let knowledge = knowledge_dir(&tmp);
+        std::fs::write(knowledge.join("board.md"), "Pin 13 drives the LED.").unwrap();
+
+        let mut config = watch_config();
+        config.debounce_secs = 3600;
+
+        let mut watcher = RagWatcher::new(tmp.path(), &config, ChunkingSettings::default());
+        let outcome = watcher.scan();
+        assert_eq!(outcome.ingested, 0);
+        assert_eq!(outcome.pending, 1);
+        assert_eq!(watcher.file_count(), 0);
+    }
+
+    #[test]
+    fn status_file_round_trips_for_doctor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let knowledge = knowledge_dir(&tmp);
+        std::fs::write(knowledge.join("board.md"), "Pin 13 drives the LED.").unwrap();
+
+        let mut watcher = RagWatcher::new(tmp.path(), &watch_config(), ChunkingSettings::default());
+        let outcome = watcher.scan();
+        watcher.write_status(&outcome);
+
+        let status = read_status(tmp.path()).expect("status file should exist");
+        assert!(status.last_sync.is_some());
+        assert_eq!(status.files, 1);
+        assert!(status.chunks >= 1);
+        assert_eq!(status.pending, 0);
+        assert!(status.last_errors.is_empty());
+        assert_eq!(
+            status.chunking_fingerprint.as_deref(),
+            Some(ChunkingSettings::default().fingerprint().as_str())
+        );
+    }
+}
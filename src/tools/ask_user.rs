@@ -305,7 +305,11 @@ mod tests {
                 thread_ts: None,
                 reply_to_message_id: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             };
             let _ = tx.send(msg).await;
             Ok(())
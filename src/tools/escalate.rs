@@ -424,7 +424,11 @@ mod tests {
                 timestamp: 1000,
                 thread_ts: None,
                 interruption_scope_id: None,
+                is_edit: false,
+                is_group: false,
+                payload: None,
                 attachments: vec![],
+                metadata: None,
             };
             let _ = tx.send(msg).await;
             Ok(())
@@ -43,6 +43,10 @@ impl Tool for MemoryStoreTool {
                 "category": {
                     "type": "string",
                     "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                },
+                "pin": {
+                    "type": "boolean",
+                    "description": "Pin the entry so it is always injected into conversation context. Defaults to false."
                 }
             },
             "required": ["key", "content"]
@@ -78,12 +82,19 @@ impl Tool for MemoryStoreTool {
             });
         }
 
+        let pin = args.get("pin").and_then(|v| v.as_bool()).unwrap_or(false);
+
         match self.memory.store(key, content, category, None).await {
-            Ok(()) => Ok(ToolResult {
-                success: true,
-                output: format!("Stored memory: {key}"),
-                error: None,
-            }),
+            Ok(()) => {
+                if pin {
+                    let _ = self.memory.set_pinned(key, true).await;
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Stored memory: {key}"),
+                    error: None,
+                })
+            }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
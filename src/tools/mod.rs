@@ -86,6 +86,8 @@ pub mod proxy_config;
 pub mod pushover;
 pub mod reaction;
 pub mod read_skill;
+#[cfg(feature = "robot-kit")]
+pub mod robot;
 pub mod report_template_tool;
 pub mod report_templates;
 pub mod schedule;
@@ -182,6 +184,8 @@ pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use reaction::ReactionTool;
 pub use read_skill::ReadSkillTool;
+#[cfg(feature = "robot-kit")]
+pub use robot::register_robot_tools;
 pub use report_template_tool::ReportTemplateTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
@@ -513,6 +517,10 @@ pub fn all_tools_with_runtime(
             extra_headers: root_config.extra_headers.clone(),
             api_path: root_config.api_path.clone(),
             provider_max_tokens: root_config.provider_max_tokens,
+            anthropic_prompt_caching: root_config.providers.anthropic.prompt_caching,
+            gemini_context_cache: root_config.providers.gemini.clone(),
+            openrouter_routing: root_config.providers.openrouter.routing.clone(),
+            custom_provider: root_config.providers.custom.clone(),
         };
         tool_arcs.push(Arc::new(LlmTaskTool::new(
             security.clone(),
@@ -962,6 +970,10 @@ pub fn all_tools_with_runtime(
         provider_max_tokens: root_config.provider_max_tokens,
         extra_headers: root_config.extra_headers.clone(),
         api_path: root_config.api_path.clone(),
+        anthropic_prompt_caching: root_config.providers.anthropic.prompt_caching,
+        gemini_context_cache: root_config.providers.gemini.clone(),
+        openrouter_routing: root_config.providers.openrouter.routing.clone(),
+        custom_provider: root_config.providers.custom.clone(),
     };
 
     let delegate_handle: Option<DelegateParentToolsHandle> = if agents.is_empty() {
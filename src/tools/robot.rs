@@ -0,0 +1,275 @@
+//! Robot kit tool adapters (`robot-kit` feature).
+//!
+//! Bridges `zeroclaw-robot-kit` tools (drive, look, listen, speak, sense,
+//! emote) into the host [`Tool`] trait so the agent tool-call loop can invoke
+//! them like any other tool. The drive tool is always registered through the
+//! kit's `SafeDrive` wrapper so the safety monitor can veto or slow any
+//! movement command — the raw `DriveTool` is never exposed to the LLM.
+
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Default bound on the safety-event forwarding queue.
+const SAFETY_EVENT_QUEUE_CAPACITY: usize = 64;
+
+/// Forwards robot safety events into the runtime trace as
+/// `robot_safety_event` entries.
+///
+/// `emit` is called inline from the safety path and must never block: events
+/// go through a bounded channel and a background task does the trace write.
+/// When the queue is full the event is dropped and counted instead.
+pub struct RuntimeTraceEventSink {
+    tx: tokio::sync::mpsc::Sender<zeroclaw_robot_kit::SafetyEvent>,
+    dropped: AtomicU64,
+}
+
+impl RuntimeTraceEventSink {
+    /// Create a sink plus the receiving end of its bounded queue.
+    /// Exposed separately so tests can exercise the drop-counting path
+    /// without a drain task.
+    fn with_capacity(
+        capacity: usize,
+    ) -> (
+        Arc<Self>,
+        tokio::sync::mpsc::Receiver<zeroclaw_robot_kit::SafetyEvent>,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        (
+            Arc::new(Self {
+                tx,
+                dropped: AtomicU64::new(0),
+            }),
+            rx,
+        )
+    }
+
+    /// Spawn the drain task on the current tokio runtime and return the sink.
+    pub fn spawn(capacity: usize) -> Arc<Self> {
+        let (sink, mut rx) = Self::with_capacity(capacity);
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let payload = serde_json::to_value(&event).unwrap_or_default();
+                let denied = matches!(
+                    event,
+                    zeroclaw_robot_kit::SafetyEvent::MovementDenied { .. }
+                        | zeroclaw_robot_kit::SafetyEvent::EmergencyStop { .. }
+                        | zeroclaw_robot_kit::SafetyEvent::ObstacleDetected { .. }
+                        | zeroclaw_robot_kit::SafetyEvent::BumpDetected { .. }
+                );
+                crate::observability::runtime_trace::record_event(
+                    "robot_safety_event",
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(!denied),
+                    Some(&format!("{event:?}")),
+                    payload,
+                );
+            }
+        });
+        sink
+    }
+
+    /// Number of events dropped because the queue was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl zeroclaw_robot_kit::EventSink for RuntimeTraceEventSink {
+    fn emit(&self, event: &zeroclaw_robot_kit::SafetyEvent) {
+        if self.tx.try_send(event.clone()).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Wraps a robot-kit tool in the host [`Tool`] trait.
+///
+/// Name, description, and JSON parameter schema are forwarded unchanged, so
+/// the host's `spec()` produces the same `ToolSpec` the kit would.
+pub struct RobotToolAdapter {
+    inner: Box<dyn zeroclaw_robot_kit::Tool>,
+}
+
+impl RobotToolAdapter {
+    pub fn new(inner: Box<dyn zeroclaw_robot_kit::Tool>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl Tool for RobotToolAdapter {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let result = self.inner.execute(args).await?;
+        Ok(ToolResult {
+            success: result.success,
+            output: result.output,
+            error: result.error,
+        })
+    }
+}
+
+/// Register robot kit tools into an agent tool registry.
+///
+/// Called at agent/daemon startup when `[peripherals.robot]` is enabled.
+/// Loads `robot.toml` from `config_path` when set (mock backends otherwise),
+/// spins up the kit's `SafetyMonitor`, and registers all tools with the drive
+/// tool wrapped in `SafeDrive`.
+pub fn register_robot_tools(
+    config: &crate::config::RobotPeripheralConfig,
+    registry: &mut Vec<Box<dyn Tool>>,
+) -> anyhow::Result<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let robot_config = match config.config_path.as_deref() {
+        Some(path) => {
+            let expanded = shellexpand::tilde(path);
+            zeroclaw_robot_kit::RobotConfig::load(std::path::Path::new(expanded.as_ref()))?
+        }
+        None => zeroclaw_robot_kit::RobotConfig::default(),
+    };
+
+    let (safety, _event_rx) = zeroclaw_robot_kit::SafetyMonitor::new(robot_config.safety.clone());
+    let safety = Arc::new(safety);
+
+    // Forward safety events into the runtime trace (requires a tokio runtime
+    // for the drain task; registration from sync test contexts skips it).
+    if tokio::runtime::Handle::try_current().is_ok() {
+        safety.set_event_sink(RuntimeTraceEventSink::spawn(SAFETY_EVENT_QUEUE_CAPACITY));
+    }
+
+    let kit_tools = zeroclaw_robot_kit::create_safe_tools(&robot_config, safety);
+    let count = kit_tools.len();
+    for tool in kit_tools {
+        registry.push(Box::new(RobotToolAdapter::new(tool)));
+    }
+    tracing::info!(count, "Robot kit tools registered");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RobotPeripheralConfig;
+
+    fn registered_tools() -> Vec<Box<dyn Tool>> {
+        let config = RobotPeripheralConfig {
+            enabled: true,
+            config_path: None,
+        };
+        let mut registry: Vec<Box<dyn Tool>> = Vec::new();
+        register_robot_tools(&config, &mut registry).unwrap();
+        registry
+    }
+
+    #[test]
+    fn register_skipped_when_disabled() {
+        let config = RobotPeripheralConfig::default();
+        let mut registry: Vec<Box<dyn Tool>> = Vec::new();
+        register_robot_tools(&config, &mut registry).unwrap();
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn register_adds_all_robot_tools() {
+        let registry = registered_tools();
+        let names: Vec<&str> = registry.iter().map(|t| t.name()).collect();
+        for expected in ["drive", "look", "listen", "speak", "sense", "emote"] {
+            assert!(names.contains(&expected), "missing tool: {expected}");
+        }
+    }
+
+    #[test]
+    fn adapter_spec_forwards_kit_schema() {
+        let registry = registered_tools();
+        let drive = registry.iter().find(|t| t.name() == "drive").unwrap();
+        let spec = drive.spec();
+        assert_eq!(spec.name, "drive");
+        assert!(spec.parameters["properties"]["action"].is_object());
+    }
+
+    #[tokio::test]
+    async fn drive_executes_through_host_trait_with_mock_backend() {
+        let registry = registered_tools();
+        let drive = registry.iter().find(|t| t.name() == "drive").unwrap();
+        let result = drive
+            .execute(serde_json::json!({"action": "forward", "distance": 0.5}))
+            .await
+            .unwrap();
+        assert!(result.success, "error: {:?}", result.error);
+    }
+
+    #[test]
+    fn event_sink_counts_drops_when_queue_full() {
+        use zeroclaw_robot_kit::EventSink as _;
+
+        let (sink, _rx) = RuntimeTraceEventSink::with_capacity(1);
+        for _ in 0..3 {
+            sink.emit(&zeroclaw_robot_kit::SafetyEvent::WatchdogTimeout);
+        }
+        // Capacity 1 and no drain task: first event queued, two dropped.
+        assert_eq!(sink.dropped_events(), 2);
+    }
+
+    #[tokio::test]
+    async fn safety_events_flow_through_sink_queue() {
+        let (sink, mut rx) = RuntimeTraceEventSink::with_capacity(8);
+        let (monitor, _brx) =
+            zeroclaw_robot_kit::SafetyMonitor::new(zeroclaw_robot_kit::RobotConfig::default().safety);
+        monitor.set_event_sink(sink.clone());
+
+        monitor.emergency_stop("test").await;
+
+        let event = rx.try_recv().unwrap();
+        assert!(matches!(
+            event,
+            zeroclaw_robot_kit::SafetyEvent::EmergencyStop { .. }
+        ));
+        assert_eq!(sink.dropped_events(), 0);
+    }
+
+    #[tokio::test]
+    async fn registered_drive_is_safety_wrapped() {
+        // An obstacle inside min_obstacle_distance must block movement even
+        // when the request comes through the host tool-call path.
+        let robot_config = zeroclaw_robot_kit::RobotConfig::default();
+        let (safety, _rx) =
+            zeroclaw_robot_kit::SafetyMonitor::new(robot_config.safety.clone());
+        let safety = Arc::new(safety);
+        safety.update_obstacle_distance(0.1, 0).await;
+
+        let kit_tools = zeroclaw_robot_kit::create_safe_tools(&robot_config, safety);
+        let drive = kit_tools.into_iter().find(|t| t.name() == "drive").unwrap();
+        let adapter = RobotToolAdapter::new(drive);
+
+        let result = adapter
+            .execute(serde_json::json!({"action": "forward", "distance": 1.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("Safety blocked"));
+    }
+}
@@ -131,7 +131,11 @@ impl Channel for MatrixTestChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -628,7 +632,11 @@ fn channel_message_thread_ts_preserved_on_clone() {
         thread_ts: Some("1700000000.000001".into()),
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     let cloned = msg.clone();
@@ -647,7 +655,11 @@ fn channel_message_none_thread_ts_preserved() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     assert!(msg.clone().thread_ts.is_none());
@@ -703,7 +715,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "discord" => ChannelMessage {
             id: "dc_1".into(),
@@ -715,7 +731,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "slack" => ChannelMessage {
             id: "sl_1".into(),
@@ -727,7 +747,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: Some("1700000000.000001".into()),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "imessage" => ChannelMessage {
             id: "im_1".into(),
@@ -739,7 +763,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "irc" => ChannelMessage {
             id: "irc_1".into(),
@@ -751,7 +779,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "email" => ChannelMessage {
             id: "email_1".into(),
@@ -763,7 +795,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "signal" => ChannelMessage {
             id: "sig_1".into(),
@@ -775,7 +811,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "mattermost" => ChannelMessage {
             id: "mm_1".into(),
@@ -787,7 +827,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: Some("root_msg_id".into()),
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "whatsapp" => ChannelMessage {
             id: "wa_1".into(),
@@ -799,7 +843,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "nextcloud_talk" => ChannelMessage {
             id: "nc_1".into(),
@@ -811,7 +859,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "wecom" => ChannelMessage {
             id: "wc_1".into(),
@@ -823,7 +875,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "dingtalk" => ChannelMessage {
             id: "dt_1".into(),
@@ -835,7 +891,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "qq" => ChannelMessage {
             id: "qq_1".into(),
@@ -847,7 +907,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "linq" => ChannelMessage {
             id: "lq_1".into(),
@@ -859,7 +923,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "wati" => ChannelMessage {
             id: "wt_1".into(),
@@ -871,7 +939,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         "cli" => ChannelMessage {
             id: "cli_1".into(),
@@ -883,7 +955,11 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         },
         _ => panic!("Unknown platform: {platform}"),
     }
@@ -1186,7 +1262,11 @@ fn channel_message_zero_timestamp() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
     assert_eq!(msg.timestamp, 0);
 }
@@ -1203,7 +1283,11 @@ fn channel_message_max_timestamp() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
     assert_eq!(msg.timestamp, u64::MAX);
 }
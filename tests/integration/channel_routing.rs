@@ -27,7 +27,11 @@ fn channel_message_sender_field_holds_platform_user_id() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     assert_eq!(msg.sender, "123456789");
@@ -52,7 +56,11 @@ fn channel_message_reply_target_distinct_from_sender() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     assert_ne!(
@@ -75,7 +83,11 @@ fn channel_message_fields_not_swapped() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     assert_eq!(
@@ -104,7 +116,11 @@ fn channel_message_preserves_all_fields_on_clone() {
         thread_ts: None,
         reply_to_message_id: None,
         interruption_scope_id: None,
+        is_edit: false,
+        is_group: false,
+        payload: None,
         attachments: vec![],
+        metadata: None,
     };
 
     let cloned = original.clone();
@@ -200,7 +216,11 @@ impl Channel for CapturingChannel {
             thread_ts: None,
             reply_to_message_id: None,
             interruption_scope_id: None,
+            is_edit: false,
+            is_group: false,
+            payload: None,
             attachments: vec![],
+            metadata: None,
         })
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))
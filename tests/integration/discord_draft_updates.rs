@@ -0,0 +1,145 @@
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use zeroclaw::channels::discord::DiscordChannel;
+use zeroclaw::channels::traits::{Channel, SendMessage};
+use zeroclaw::config::StreamMode;
+
+fn test_channel(mock_url: &str, interval_ms: u64) -> DiscordChannel {
+    DiscordChannel::new("TEST_TOKEN".into(), None, vec!["*".into()], false, false)
+        .with_streaming(StreamMode::Partial, interval_ms, 800)
+        .with_api_base(mock_url.to_string())
+}
+
+fn discord_message_response(id: &str) -> serde_json::Value {
+    json!({ "id": id, "channel_id": "chan1", "content": "ok" })
+}
+
+#[tokio::test]
+async fn update_draft_coalesces_edits_within_throttle_interval() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/channels/chan1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discord_message_response("m1")))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/channels/chan1/messages/m1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discord_message_response("m1")))
+        .mount(&server)
+        .await;
+
+    // Interval far larger than the test runtime: every edit after the initial
+    // draft send must be coalesced away without touching the network.
+    let channel = test_channel(&server.uri(), 60_000);
+    let msg_id = channel
+        .send_draft(&SendMessage::new("...", "chan1"))
+        .await
+        .expect("send_draft should succeed")
+        .expect("Partial mode should return a message ID");
+    assert_eq!(msg_id, "m1");
+
+    for text in ["partial one", "partial one two", "partial one two three"] {
+        channel
+            .update_draft("chan1", &msg_id, text)
+            .await
+            .expect("throttled update_draft should still succeed");
+    }
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    assert_eq!(
+        requests.len(),
+        1,
+        "only the initial draft POST should hit the API"
+    );
+    assert_eq!(requests[0].method.as_str(), "POST");
+}
+
+#[tokio::test]
+async fn update_draft_edits_again_after_interval_elapses() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/channels/chan1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discord_message_response("m1")))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/channels/chan1/messages/m1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discord_message_response("m1")))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri(), 100);
+    let msg_id = channel
+        .send_draft(&SendMessage::new("...", "chan1"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    channel
+        .update_draft("chan1", &msg_id, "more text")
+        .await
+        .expect("update after interval should succeed");
+    // Immediately following edit falls inside the fresh interval — coalesced.
+    channel
+        .update_draft("chan1", &msg_id, "more text again")
+        .await
+        .expect("coalesced update should succeed");
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    assert_eq!(requests.len(), 2, "one POST plus exactly one PATCH");
+    assert_eq!(requests[1].method.as_str(), "PATCH");
+    assert_eq!(requests[1].url.path(), "/channels/chan1/messages/m1");
+}
+
+#[tokio::test]
+async fn finalize_draft_falls_back_to_fresh_message_when_edit_fails() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/channels/chan1/messages/m1"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("internal error"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/channels/chan1/messages/m1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/channels/chan1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(discord_message_response("m2")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri(), 100);
+    let result = channel.finalize_draft("chan1", "m1", "final text").await;
+
+    assert!(
+        result.is_ok(),
+        "failed edit should degrade to delete+send, got: {result:?}"
+    );
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+    assert_eq!(methods, ["PATCH", "DELETE", "POST"]);
+}
@@ -3,10 +3,13 @@ mod agent_robustness;
 mod backup_cron_scheduling;
 mod channel_matrix;
 mod channel_routing;
+mod discord_draft_updates;
 mod hooks;
 mod memory_comparison;
 mod memory_loop_continuity;
 mod memory_restart;
 mod report_template_tool_test;
+mod slack_draft_updates;
 mod telegram_attachment_fallback;
 mod telegram_finalize_draft;
+mod webhook_channel;
@@ -0,0 +1,158 @@
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use zeroclaw::channels::slack::SlackChannel;
+use zeroclaw::channels::traits::{Channel, SendMessage};
+
+fn test_channel(mock_url: &str, interval_ms: u64) -> SlackChannel {
+    SlackChannel::new("xoxb-test".into(), None, None, vec![], vec!["*".into()])
+        .with_streaming(true, interval_ms)
+        .with_api_base(mock_url.to_string())
+}
+
+fn slack_ok_response(ts: &str) -> serde_json::Value {
+    json!({ "ok": true, "channel": "C1", "ts": ts })
+}
+
+fn slack_error_response(error: &str) -> serde_json::Value {
+    json!({ "ok": false, "error": error })
+}
+
+#[tokio::test]
+async fn update_draft_materializes_once_and_coalesces_within_interval() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.postMessage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("111.222")))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.update"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("111.222")))
+        .mount(&server)
+        .await;
+
+    // Interval far larger than the test runtime: after the lazy draft is
+    // materialized, every further edit must be coalesced away.
+    let channel = test_channel(&server.uri(), 60_000);
+    let msg_id = channel
+        .send_draft(&SendMessage::new("", "C1"))
+        .await
+        .expect("send_draft should succeed")
+        .expect("stream_drafts should return a lazy ID");
+
+    for text in ["partial one", "partial one two", "partial one two three"] {
+        channel
+            .update_draft("C1", &msg_id, text)
+            .await
+            .expect("update_draft should succeed");
+    }
+
+    // update_draft fires chat.update in the background; give it time to land.
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    assert_eq!(
+        requests.len(),
+        1,
+        "only the materializing chat.postMessage should hit the API"
+    );
+    assert_eq!(requests[0].url.path(), "/chat.postMessage");
+}
+
+#[tokio::test]
+async fn update_draft_edits_again_after_interval_elapses() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.postMessage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("111.222")))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.update"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("111.222")))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri(), 100);
+    let msg_id = channel
+        .send_draft(&SendMessage::new("", "C1"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    channel.update_draft("C1", &msg_id, "first").await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    channel
+        .update_draft("C1", &msg_id, "first second")
+        .await
+        .expect("update after interval should succeed");
+    // Immediately following edit falls inside the fresh interval — coalesced.
+    channel
+        .update_draft("C1", &msg_id, "first second third")
+        .await
+        .expect("coalesced update should succeed");
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    assert_eq!(
+        requests.len(),
+        2,
+        "one chat.postMessage plus exactly one chat.update"
+    );
+    assert_eq!(requests[1].url.path(), "/chat.update");
+}
+
+#[tokio::test]
+async fn finalize_draft_falls_back_to_fresh_message_when_update_fails() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.update"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(slack_error_response("message_not_found")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.delete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("111.222")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat.postMessage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(slack_ok_response("333.444")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri(), 100);
+    let result = channel.finalize_draft("C1", "111.222", "final text").await;
+
+    assert!(
+        result.is_ok(),
+        "failed update should degrade to delete+send, got: {result:?}"
+    );
+
+    let requests = server
+        .received_requests()
+        .await
+        .expect("requests should be captured");
+    let paths: Vec<&str> = requests.iter().map(|r| r.url.path()).collect();
+    assert_eq!(paths, ["/chat.update", "/chat.delete", "/chat.postMessage"]);
+}
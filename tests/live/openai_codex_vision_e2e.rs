@@ -156,6 +156,10 @@ async fn openai_codex_second_vision_support() -> Result<()> {
         provider_max_tokens: None,
         extra_headers: std::collections::HashMap::new(),
         api_path: None,
+        anthropic_prompt_caching: true,
+        gemini_context_cache: zeroclaw::config::GeminiProviderConfig::default(),
+        openrouter_routing: zeroclaw::config::OpenRouterRoutingConfig::default(),
+        custom_provider: zeroclaw::config::CustomProviderConfig::default(),
     };
 
     let provider = zeroclaw::providers::create_provider_with_options("openai-codex", None, &opts)?;
@@ -172,6 +172,7 @@ impl Provider for TraceLlmProvider {
                     input_tokens: Some(input_tokens),
                     output_tokens: Some(output_tokens),
                     cached_input_tokens: None,
+                    cache_write_input_tokens: None,
                 }),
                 reasoning_content: None,
             }),
@@ -195,6 +196,7 @@ impl Provider for TraceLlmProvider {
                         input_tokens: Some(input_tokens),
                         output_tokens: Some(output_tokens),
                         cached_input_tokens: None,
+                        cache_write_input_tokens: None,
                     }),
                     reasoning_content: None,
                 })
@@ -23,6 +23,10 @@ async fn codex_gpt51_native_tool_call() {
         extra_headers: std::collections::HashMap::new(),
         api_path: None,
         provider_max_tokens: None,
+        anthropic_prompt_caching: true,
+        gemini_context_cache: zeroclaw::config::GeminiProviderConfig::default(),
+        openrouter_routing: zeroclaw::config::OpenRouterRoutingConfig::default(),
+        custom_provider: zeroclaw::config::CustomProviderConfig::default(),
     };
 
     let provider = zeroclaw::providers::openai_codex::OpenAiCodexProvider::new(&options, None)